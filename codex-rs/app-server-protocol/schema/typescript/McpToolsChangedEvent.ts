@@ -0,0 +1,18 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { McpListChangedKind } from "./McpListChangedKind";
+
+export type McpToolsChangedEvent = { 
+/**
+ * Server whose tool or resource list changed.
+ */
+server: string, 
+/**
+ * Which list the server reported as changed.
+ */
+kind: McpListChangedKind, 
+/**
+ * Number of tools exposed by the server after the refresh.
+ */
+tool_count: number, };
@@ -0,0 +1,13 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type ProjectDocLoadedEvent = { path: string, 
+/**
+ * Bytes counted against `project_doc_max_bytes`.
+ */
+bytes: bigint, 
+/**
+ * Whether the file was cut short because the budget ran out.
+ */
+truncated: boolean, };
@@ -7,7 +7,17 @@ import type { NetworkAccess } from "./NetworkAccess";
 /**
  * Determines execution restrictions for model shell commands.
  */
-export type SandboxPolicy = { "type": "danger-full-access" } | { "type": "read-only" } | { "type": "external-sandbox", 
+export type SandboxPolicy = { "type": "danger-full-access" } | { "type": "read-only", 
+/**
+ * Folders that stay writable, e.g. `/tmp` for test runs. Unlike
+ * `WorkspaceWrite`, neither cwd nor the temp dirs are added
+ * implicitly.
+ */
+writable_roots?: Array<AbsolutePathBuf>, 
+/**
+ * Paths the sandbox may not read. See [`SandboxPolicy::get_deny_read_paths_with_cwd`].
+ */
+deny_read?: Array<string>, } | { "type": "external-sandbox", 
 /**
  * Whether the external sandbox permits outbound network traffic.
  */
@@ -22,6 +32,11 @@ writable_roots?: Array<AbsolutePathBuf>,
  * default.
  */
 network_access: boolean, 
+/**
+ * TCP ports on loopback that stay reachable when `network_access` is
+ * `false`, e.g. a local database or dev server.
+ */
+allowed_loopback_ports?: Array<number>, 
 /**
  * When set to `true`, will NOT include the per-user `TMPDIR`
  * environment variable among the default writable roots. Defaults to
@@ -32,4 +47,9 @@ exclude_tmpdir_env_var: boolean,
  * When set to `true`, will NOT include the `/tmp` among the default
  * writable roots on UNIX. Defaults to `false`.
  */
-exclude_slash_tmp: boolean, };
+exclude_slash_tmp: boolean, 
+/**
+ * Paths the sandbox may not read, even under a writable root. See
+ * [`SandboxPolicy::get_deny_read_paths_with_cwd`].
+ */
+deny_read?: Array<string>, };
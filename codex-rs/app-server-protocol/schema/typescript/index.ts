@@ -111,6 +111,7 @@ export type { LoginChatGptResponse } from "./LoginChatGptResponse";
 export type { LogoutChatGptResponse } from "./LogoutChatGptResponse";
 export type { McpAuthStatus } from "./McpAuthStatus";
 export type { McpInvocation } from "./McpInvocation";
+export type { McpListChangedKind } from "./McpListChangedKind";
 export type { McpListToolsResponseEvent } from "./McpListToolsResponseEvent";
 export type { McpStartupCompleteEvent } from "./McpStartupCompleteEvent";
 export type { McpStartupFailure } from "./McpStartupFailure";
@@ -118,6 +119,7 @@ export type { McpStartupStatus } from "./McpStartupStatus";
 export type { McpStartupUpdateEvent } from "./McpStartupUpdateEvent";
 export type { McpToolCallBeginEvent } from "./McpToolCallBeginEvent";
 export type { McpToolCallEndEvent } from "./McpToolCallEndEvent";
+export type { McpToolsChangedEvent } from "./McpToolsChangedEvent";
 export type { MessagePhase } from "./MessagePhase";
 export type { ModeKind } from "./ModeKind";
 export type { NetworkAccess } from "./NetworkAccess";
@@ -132,6 +134,7 @@ export type { PlanItem } from "./PlanItem";
 export type { PlanItemArg } from "./PlanItemArg";
 export type { PlanType } from "./PlanType";
 export type { Profile } from "./Profile";
+export type { ProjectDocLoadedEvent } from "./ProjectDocLoadedEvent";
 export type { RateLimitSnapshot } from "./RateLimitSnapshot";
 export type { RateLimitWindow } from "./RateLimitWindow";
 export type { RawResponseItemEvent } from "./RawResponseItemEvent";
@@ -2,4 +2,4 @@
 
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 
-export type SandboxWorkspaceWrite = { writable_roots: Array<string>, network_access: boolean, exclude_tmpdir_env_var: boolean, exclude_slash_tmp: boolean, };
+export type SandboxWorkspaceWrite = { writable_roots: Array<string>, network_access: boolean, allowed_loopback_ports: Array<number>, exclude_tmpdir_env_var: boolean, exclude_slash_tmp: boolean, };
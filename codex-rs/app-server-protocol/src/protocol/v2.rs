@@ -328,6 +328,8 @@ pub struct SandboxWorkspaceWrite {
     #[serde(default)]
     pub network_access: bool,
     #[serde(default)]
+    pub allowed_loopback_ports: Vec<u16>,
+    #[serde(default)]
     pub exclude_tmpdir_env_var: bool,
     #[serde(default)]
     pub exclude_slash_tmp: bool,
@@ -640,7 +642,14 @@ pub enum NetworkAccess {
 #[ts(export_to = "v2/")]
 pub enum SandboxPolicy {
     DangerFullAccess,
-    ReadOnly,
+    #[serde(rename_all = "camelCase")]
+    #[ts(rename_all = "camelCase")]
+    ReadOnly {
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        writable_roots: Vec<AbsolutePathBuf>,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        deny_read: Vec<String>,
+    },
     #[serde(rename_all = "camelCase")]
     #[ts(rename_all = "camelCase")]
     ExternalSandbox {
@@ -654,10 +663,14 @@ pub enum SandboxPolicy {
         writable_roots: Vec<AbsolutePathBuf>,
         #[serde(default)]
         network_access: bool,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        allowed_loopback_ports: Vec<u16>,
         #[serde(default)]
         exclude_tmpdir_env_var: bool,
         #[serde(default)]
         exclude_slash_tmp: bool,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        deny_read: Vec<String>,
     },
 }
 
@@ -667,7 +680,13 @@ impl SandboxPolicy {
             SandboxPolicy::DangerFullAccess => {
                 codex_protocol::protocol::SandboxPolicy::DangerFullAccess
             }
-            SandboxPolicy::ReadOnly => codex_protocol::protocol::SandboxPolicy::ReadOnly,
+            SandboxPolicy::ReadOnly {
+                writable_roots,
+                deny_read,
+            } => codex_protocol::protocol::SandboxPolicy::ReadOnly {
+                writable_roots: writable_roots.clone(),
+                deny_read: deny_read.clone(),
+            },
             SandboxPolicy::ExternalSandbox { network_access } => {
                 codex_protocol::protocol::SandboxPolicy::ExternalSandbox {
                     network_access: match network_access {
@@ -679,13 +698,17 @@ impl SandboxPolicy {
             SandboxPolicy::WorkspaceWrite {
                 writable_roots,
                 network_access,
+                allowed_loopback_ports,
                 exclude_tmpdir_env_var,
                 exclude_slash_tmp,
+                deny_read,
             } => codex_protocol::protocol::SandboxPolicy::WorkspaceWrite {
                 writable_roots: writable_roots.clone(),
                 network_access: *network_access,
+                allowed_loopback_ports: allowed_loopback_ports.clone(),
                 exclude_tmpdir_env_var: *exclude_tmpdir_env_var,
                 exclude_slash_tmp: *exclude_slash_tmp,
+                deny_read: deny_read.clone(),
             },
         }
     }
@@ -697,7 +720,13 @@ impl From<codex_protocol::protocol::SandboxPolicy> for SandboxPolicy {
             codex_protocol::protocol::SandboxPolicy::DangerFullAccess => {
                 SandboxPolicy::DangerFullAccess
             }
-            codex_protocol::protocol::SandboxPolicy::ReadOnly => SandboxPolicy::ReadOnly,
+            codex_protocol::protocol::SandboxPolicy::ReadOnly {
+                writable_roots,
+                deny_read,
+            } => SandboxPolicy::ReadOnly {
+                writable_roots,
+                deny_read,
+            },
             codex_protocol::protocol::SandboxPolicy::ExternalSandbox { network_access } => {
                 SandboxPolicy::ExternalSandbox {
                     network_access: match network_access {
@@ -709,13 +738,17 @@ impl From<codex_protocol::protocol::SandboxPolicy> for SandboxPolicy {
             codex_protocol::protocol::SandboxPolicy::WorkspaceWrite {
                 writable_roots,
                 network_access,
+                allowed_loopback_ports,
                 exclude_tmpdir_env_var,
                 exclude_slash_tmp,
+                deny_read,
             } => SandboxPolicy::WorkspaceWrite {
                 writable_roots,
                 network_access,
+                allowed_loopback_ports,
                 exclude_tmpdir_env_var,
                 exclude_slash_tmp,
+                deny_read,
             },
         }
     }
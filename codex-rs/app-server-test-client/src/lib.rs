@@ -247,7 +247,10 @@ fn trigger_cmd_approval(
         config_overrides,
         message,
         Some(AskForApproval::OnRequest),
-        Some(SandboxPolicy::ReadOnly),
+        Some(SandboxPolicy::ReadOnly {
+            writable_roots: Vec::new(),
+            deny_read: Vec::new(),
+        }),
         dynamic_tools,
     )
 }
@@ -266,7 +269,10 @@ fn trigger_patch_approval(
         config_overrides,
         message,
         Some(AskForApproval::OnRequest),
-        Some(SandboxPolicy::ReadOnly),
+        Some(SandboxPolicy::ReadOnly {
+            writable_roots: Vec::new(),
+            deny_read: Vec::new(),
+        }),
         dynamic_tools,
     )
 }
@@ -4579,6 +4579,7 @@ impl CodexMessageProcessor {
                     cwd: params.cwd,
                     approval_policy: params.approval_policy.map(AskForApproval::to_core),
                     sandbox_policy: params.sandbox_policy.map(|p| p.to_core()),
+                    sandbox_profile: None,
                     windows_sandbox_level: None,
                     model: params.model,
                     subagent_model: None,
@@ -57,8 +57,12 @@ mod fuzzy_file_search;
 mod message_processor;
 mod models;
 mod outgoing_message;
+mod share;
 mod transport;
 
+pub use crate::share::ShareServer;
+pub use crate::share::ShareViewer;
+pub use crate::share::viewer_may_send;
 pub use crate::transport::AppServerTransport;
 
 fn config_warning_from_error(
@@ -0,0 +1,680 @@
+//! Live, read-only sharing of a running session over the websocket transport.
+//!
+//! The owner's process runs a [`ShareServer`] and publishes every event of the shared thread to
+//! it. Viewers connect with [`ShareViewer::connect`] using the URL from [`ShareServer::url`],
+//! which carries a random token. A new viewer first receives the events published so far and
+//! then the live stream.
+//!
+//! Messages are JSON-RPC notifications:
+//! - `share/hello` is sent once per connection and tells the viewer whether it may approve.
+//! - `codex/event/<type>` carries one [`Event`], as in the legacy app-server notifications.
+//! - `share/op` is the only thing viewers send; the server accepts approval decisions when the
+//!   owner allowed them and drops everything else.
+
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::io;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use base64::Engine;
+use codex_app_server_protocol::JSONRPCMessage;
+use codex_app_server_protocol::JSONRPCNotification;
+use codex_protocol::protocol::Event;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::Op;
+use codex_protocol::protocol::ReviewDecision;
+use futures::SinkExt;
+use futures::StreamExt;
+use rand::RngCore;
+use serde::Deserialize;
+use serde::Serialize;
+use tokio::net::TcpListener;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio_tungstenite::connect_async;
+use tokio_tungstenite::tungstenite::Error as WebSocketError;
+use tokio_tungstenite::tungstenite::Message as WebSocketMessage;
+use tokio_tungstenite::tungstenite::http::StatusCode;
+use tracing::info;
+use tracing::warn;
+
+use crate::outgoing_message::ConnectionId;
+use crate::outgoing_message::OutgoingMessage;
+use crate::outgoing_message::OutgoingNotification;
+use crate::transport::CHANNEL_CAPACITY;
+use crate::transport::TransportEvent;
+use crate::transport::WebSocketAcceptOptions;
+use crate::transport::spawn_websocket_acceptor;
+
+const HELLO_METHOD: &str = "share/hello";
+const EVENT_METHOD_PREFIX: &str = "codex/event/";
+const OP_METHOD: &str = "share/op";
+
+/// Events kept for viewers that join late. Streaming deltas dominate long sessions, so this is
+/// generous; the oldest events are dropped first.
+const SHARE_HISTORY_CAPACITY: usize = 50_000;
+
+/// Live events queued for one viewer. A viewer that falls this far behind is disconnected so it
+/// cannot hold up the owner's session or the other viewers.
+const VIEWER_QUEUE_CAPACITY: usize = 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ShareHello {
+    can_approve: bool,
+}
+
+/// Whether a viewer may send `op` to the owner's session.
+///
+/// Viewers answer one request at a time: decisions that also change the owner's policy, such as
+/// approving for the rest of the session, are left to the owner.
+pub fn viewer_may_send(op: &Op, can_approve: bool) -> bool {
+    match op {
+        Op::ExecApproval { decision, .. } | Op::PatchApproval { decision, .. } => {
+            can_approve
+                && matches!(
+                    decision,
+                    ReviewDecision::Approved | ReviewDecision::Denied | ReviewDecision::Abort
+                )
+        }
+        _ => false,
+    }
+}
+
+/// The owner side of a shared session.
+///
+/// Dropping the server closes every viewer connection.
+pub struct ShareServer {
+    url: String,
+    events_tx: mpsc::UnboundedSender<Event>,
+    accept_handle: JoinHandle<()>,
+    router_handle: JoinHandle<()>,
+}
+
+impl ShareServer {
+    /// Listens on `bind_address` and returns the server with a receiver for approval decisions
+    /// sent by viewers. The receiver never yields anything unless `allow_approvals` is set.
+    pub async fn start(
+        bind_address: SocketAddr,
+        allow_approvals: bool,
+    ) -> io::Result<(Self, mpsc::UnboundedReceiver<Op>)> {
+        let listener = TcpListener::bind(bind_address).await?;
+        let local_addr = listener.local_addr()?;
+        let token = generate_token();
+        info!("sharing session on ws://{local_addr}");
+
+        let (transport_event_tx, transport_event_rx) =
+            mpsc::channel::<TransportEvent>(CHANNEL_CAPACITY);
+        let accept_handle = spawn_websocket_acceptor(
+            listener,
+            WebSocketAcceptOptions {
+                required_token: Some(Arc::from(token.as_str())),
+                announce_connections: false,
+            },
+            transport_event_tx,
+        );
+
+        let (events_tx, events_rx) = mpsc::unbounded_channel();
+        let (ops_tx, ops_rx) = mpsc::unbounded_channel();
+        let router_handle = tokio::spawn(run_share_router(
+            transport_event_rx,
+            events_rx,
+            ops_tx,
+            allow_approvals,
+        ));
+
+        Ok((
+            Self {
+                url: format!("ws://{local_addr}/?token={token}"),
+                events_tx,
+                accept_handle,
+                router_handle,
+            },
+            ops_rx,
+        ))
+    }
+
+    /// The URL viewers pass to `codex attach`, including the token.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Sends `event` to every viewer and keeps it for viewers that join later.
+    pub fn publish(&self, event: Event) {
+        if self.events_tx.send(event).is_err() {
+            warn!("share router exited; dropping event");
+        }
+    }
+}
+
+impl Drop for ShareServer {
+    fn drop(&mut self) {
+        self.accept_handle.abort();
+        self.router_handle.abort();
+    }
+}
+
+async fn run_share_router(
+    mut transport_event_rx: mpsc::Receiver<TransportEvent>,
+    mut events_rx: mpsc::UnboundedReceiver<Event>,
+    ops_tx: mpsc::UnboundedSender<Op>,
+    can_approve: bool,
+) {
+    let mut history = ShareHistory::default();
+    let mut viewers = HashMap::<ConnectionId, Viewer>::new();
+    loop {
+        tokio::select! {
+            transport_event = transport_event_rx.recv() => {
+                let Some(transport_event) = transport_event else {
+                    break;
+                };
+                match transport_event {
+                    TransportEvent::ConnectionOpened { connection_id, writer } => {
+                        let replay = history.events().cloned().collect();
+                        let (live_tx, live_rx) = mpsc::channel(VIEWER_QUEUE_CAPACITY);
+                        let forward_handle = tokio::spawn(forward_to_viewer(
+                            writer,
+                            can_approve,
+                            replay,
+                            live_rx,
+                        ));
+                        viewers.insert(
+                            connection_id,
+                            Viewer {
+                                live_tx,
+                                forward_handle,
+                            },
+                        );
+                    }
+                    TransportEvent::ConnectionClosed { connection_id } => {
+                        viewers.remove(&connection_id);
+                    }
+                    TransportEvent::IncomingMessage { message, .. } => match viewer_op(message) {
+                        Some(op) if viewer_may_send(&op, can_approve) => {
+                            let _ = ops_tx.send(op);
+                        }
+                        Some(_) | None => warn!("ignoring message from a read-only viewer"),
+                    },
+                }
+            }
+            event = events_rx.recv() => {
+                let Some(event) = event else {
+                    break;
+                };
+                if let Some(message) = event_message(&event) {
+                    viewers.retain(|connection_id, viewer| {
+                        match viewer.live_tx.try_send(message.clone()) {
+                            Ok(()) => true,
+                            Err(mpsc::error::TrySendError::Full(_)) => {
+                                warn!(
+                                    "disconnecting share viewer {}: it fell behind",
+                                    connection_id.0
+                                );
+                                false
+                            }
+                            Err(mpsc::error::TrySendError::Closed(_)) => false,
+                        }
+                    });
+                }
+                history.push(event);
+            }
+        }
+    }
+}
+
+/// A connected viewer. Dropping it closes the connection.
+struct Viewer {
+    live_tx: mpsc::Sender<OutgoingMessage>,
+    forward_handle: JoinHandle<()>,
+}
+
+impl Drop for Viewer {
+    fn drop(&mut self) {
+        self.forward_handle.abort();
+    }
+}
+
+/// Writes the hello and the history to a new viewer, then its live events. Runs apart from the
+/// router so a slow viewer catching up does not delay anyone else; the connection closes when
+/// this returns.
+async fn forward_to_viewer(
+    writer: mpsc::Sender<OutgoingMessage>,
+    can_approve: bool,
+    replay: Vec<Arc<Event>>,
+    mut live_rx: mpsc::Receiver<OutgoingMessage>,
+) {
+    if writer.send(hello_message(can_approve)).await.is_err() {
+        return;
+    }
+    for event in replay {
+        let Some(message) = event_message(&event) else {
+            continue;
+        };
+        if writer.send(message).await.is_err() {
+            return;
+        }
+    }
+    while let Some(message) = live_rx.recv().await {
+        if writer.send(message).await.is_err() {
+            return;
+        }
+    }
+}
+
+fn hello_message(can_approve: bool) -> OutgoingMessage {
+    OutgoingMessage::Notification(OutgoingNotification {
+        method: HELLO_METHOD.to_string(),
+        params: serde_json::to_value(ShareHello { can_approve }).ok(),
+    })
+}
+
+fn event_message(event: &Event) -> Option<OutgoingMessage> {
+    let params = match serde_json::to_value(event) {
+        Ok(params) => params,
+        Err(err) => {
+            warn!("failed to serialize shared event: {err}");
+            return None;
+        }
+    };
+    Some(OutgoingMessage::Notification(OutgoingNotification {
+        method: format!("{EVENT_METHOD_PREFIX}{}", event.msg),
+        params: Some(params),
+    }))
+}
+
+fn viewer_op(message: JSONRPCMessage) -> Option<Op> {
+    match message {
+        JSONRPCMessage::Notification(JSONRPCNotification {
+            method,
+            params: Some(params),
+        }) if method == OP_METHOD => serde_json::from_value(params).ok(),
+        _ => None,
+    }
+}
+
+fn generate_token() -> String {
+    let mut bytes = [0u8; 24];
+    rand::rng().fill_bytes(&mut bytes);
+    base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(bytes)
+}
+
+/// Events replayed to viewers that connect mid-session.
+///
+/// Approval requests are dropped once answered (their call starts or the turn ends) so a late
+/// viewer is not prompted for decisions the owner already made. A `SessionConfigured` for a
+/// different thread starts the history over.
+#[derive(Debug, Default)]
+struct ShareHistory {
+    session_configured: Option<Arc<Event>>,
+    /// Everything else in publish order, tagged with a sequence number.
+    events: VecDeque<(u64, Arc<Event>)>,
+    /// Unanswered approval requests by call id, kept out of `events` so answering one does not
+    /// scan the history. The sequence number puts them back in order on replay.
+    pending_approvals: HashMap<String, (u64, Arc<Event>)>,
+    next_seq: u64,
+}
+
+impl ShareHistory {
+    fn push(&mut self, event: Event) {
+        let event = Arc::new(event);
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        match &event.msg {
+            EventMsg::SessionConfigured(session) => {
+                // The owner started another thread; late viewers only need the current one.
+                let new_thread = match self.session_configured.as_ref().map(|event| &event.msg) {
+                    Some(EventMsg::SessionConfigured(previous)) => {
+                        previous.session_id != session.session_id
+                    }
+                    _ => false,
+                };
+                if new_thread {
+                    self.events.clear();
+                    self.pending_approvals.clear();
+                }
+                self.session_configured = Some(event);
+                return;
+            }
+            EventMsg::ExecApprovalRequest(ev) => {
+                let call_id = ev.call_id.clone();
+                self.pending_approvals.insert(call_id, (seq, event));
+                return;
+            }
+            EventMsg::ApplyPatchApprovalRequest(ev) => {
+                let call_id = ev.call_id.clone();
+                self.pending_approvals.insert(call_id, (seq, event));
+                return;
+            }
+            EventMsg::ExecCommandBegin(ev) => {
+                self.pending_approvals.remove(&ev.call_id);
+            }
+            EventMsg::PatchApplyBegin(ev) => {
+                self.pending_approvals.remove(&ev.call_id);
+            }
+            EventMsg::TurnComplete(_) | EventMsg::TurnAborted(_) => {
+                self.pending_approvals.clear();
+            }
+            _ => {}
+        }
+        self.events.push_back((seq, event));
+        if self.events.len() > SHARE_HISTORY_CAPACITY {
+            self.events.pop_front();
+        }
+    }
+
+    fn events(&self) -> impl Iterator<Item = &Arc<Event>> {
+        let mut approvals: Vec<&(u64, Arc<Event>)> = self.pending_approvals.values().collect();
+        approvals.sort_unstable_by_key(|(seq, _)| *seq);
+        let mut approvals = approvals.into_iter().peekable();
+        let mut events = self.events.iter().peekable();
+        let in_order = std::iter::from_fn(move || {
+            let approval_first = match (approvals.peek(), events.peek()) {
+                (Some((approval_seq, _)), Some((event_seq, _))) => approval_seq < event_seq,
+                (Some(_), None) => true,
+                (None, _) => false,
+            };
+            let (_, event) = if approval_first {
+                approvals.next()?
+            } else {
+                events.next()?
+            };
+            Some(event)
+        });
+        self.session_configured.iter().chain(in_order)
+    }
+}
+
+/// The viewer side of a shared session.
+pub struct ShareViewer {
+    /// Whether the owner allowed viewers to answer approval requests.
+    pub can_approve: bool,
+    /// Events of the shared thread, starting with the ones published before connecting. Closes
+    /// when the owner stops sharing.
+    pub events: mpsc::Receiver<Event>,
+    /// Decisions to send to the owner. Anything [`viewer_may_send`] rejects is dropped.
+    pub ops: mpsc::UnboundedSender<Op>,
+}
+
+impl ShareViewer {
+    /// Connects to a URL printed by the owner (`ws://HOST:PORT/?token=...`).
+    pub async fn connect(url: &str) -> io::Result<Self> {
+        let (websocket, _response) = connect_async(url).await.map_err(|err| match err {
+            WebSocketError::Http(response) if response.status() == StatusCode::UNAUTHORIZED => {
+                io::Error::new(
+                    io::ErrorKind::PermissionDenied,
+                    "the shared session rejected this token",
+                )
+            }
+            err => io::Error::other(err),
+        })?;
+        let (mut writer, mut reader) = websocket.split();
+
+        let hello = loop {
+            match reader.next().await {
+                Some(Ok(WebSocketMessage::Text(text))) => {
+                    let notification = serde_json::from_str::<JSONRPCNotification>(&text)
+                        .map_err(io::Error::other)?;
+                    if notification.method != HELLO_METHOD {
+                        return Err(io::Error::new(
+                            io::ErrorKind::InvalidData,
+                            format!("unexpected first message `{}`", notification.method),
+                        ));
+                    }
+                    break serde_json::from_value::<ShareHello>(
+                        notification.params.unwrap_or_default(),
+                    )
+                    .map_err(io::Error::other)?;
+                }
+                Some(Ok(_)) => {}
+                Some(Err(err)) => return Err(io::Error::other(err)),
+                None => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::UnexpectedEof,
+                        "the shared session closed the connection",
+                    ));
+                }
+            }
+        };
+
+        let can_approve = hello.can_approve;
+        let (events_tx, events_rx) = mpsc::channel(CHANNEL_CAPACITY);
+        let (ops_tx, mut ops_rx) = mpsc::unbounded_channel::<Op>();
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    incoming = reader.next() => {
+                        let text = match incoming {
+                            Some(Ok(WebSocketMessage::Text(text))) => text,
+                            Some(Ok(WebSocketMessage::Close(_))) | Some(Err(_)) | None => break,
+                            Some(Ok(_)) => continue,
+                        };
+                        let Some(event) = shared_event(&text) else {
+                            continue;
+                        };
+                        if events_tx.send(event).await.is_err() {
+                            break;
+                        }
+                    }
+                    op = ops_rx.recv() => {
+                        let Some(op) = op else {
+                            break;
+                        };
+                        if !viewer_may_send(&op, can_approve) {
+                            continue;
+                        }
+                        let message = JSONRPCMessage::Notification(JSONRPCNotification {
+                            method: OP_METHOD.to_string(),
+                            params: serde_json::to_value(op).ok(),
+                        });
+                        let Ok(json) = serde_json::to_string(&message) else {
+                            continue;
+                        };
+                        if writer.send(WebSocketMessage::Text(json.into())).await.is_err() {
+                            break;
+                        }
+                    }
+                }
+            }
+            let _ = writer.close().await;
+        });
+
+        Ok(Self {
+            can_approve,
+            events: events_rx,
+            ops: ops_tx,
+        })
+    }
+}
+
+fn shared_event(text: &str) -> Option<Event> {
+    let notification = serde_json::from_str::<JSONRPCNotification>(text).ok()?;
+    if !notification.method.starts_with(EVENT_METHOD_PREFIX) {
+        return None;
+    }
+    match serde_json::from_value(notification.params?) {
+        Ok(event) => Some(event),
+        Err(err) => {
+            warn!("failed to parse shared event: {err}");
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    use codex_protocol::protocol::AgentMessageEvent;
+    use codex_protocol::protocol::ExecApprovalRequestEvent;
+    use codex_protocol::protocol::TurnCompleteEvent;
+    use pretty_assertions::assert_eq;
+    use tokio::time::timeout;
+
+    use super::*;
+
+    fn localhost() -> SocketAddr {
+        "127.0.0.1:0".parse().expect("valid socket address")
+    }
+
+    fn agent_message(message: &str) -> Event {
+        Event {
+            id: "turn-1".to_string(),
+            msg: EventMsg::AgentMessage(AgentMessageEvent {
+                message: message.to_string(),
+            }),
+        }
+    }
+
+    fn approval_request(call_id: &str) -> Event {
+        Event {
+            id: "turn-1".to_string(),
+            msg: EventMsg::ExecApprovalRequest(ExecApprovalRequestEvent {
+                call_id: call_id.to_string(),
+                turn_id: "turn-1".to_string(),
+                command: vec!["cargo".to_string(), "publish".to_string()],
+                cwd: PathBuf::from("/repo"),
+                reason: None,
+                proposed_execpolicy_amendment: None,
+                parsed_cmd: Vec::new(),
+            }),
+        }
+    }
+
+    fn message_text(event: &Event) -> Option<&str> {
+        match &event.msg {
+            EventMsg::AgentMessage(ev) => Some(ev.message.as_str()),
+            _ => None,
+        }
+    }
+
+    async fn next_event(viewer: &mut ShareViewer) -> Event {
+        timeout(Duration::from_secs(5), viewer.events.recv())
+            .await
+            .expect("timed out waiting for a shared event")
+            .expect("share closed")
+    }
+
+    #[tokio::test]
+    async fn late_viewers_get_history_then_live_events() {
+        let (server, _ops) = ShareServer::start(localhost(), false)
+            .await
+            .expect("start share");
+        server.publish(agent_message("before"));
+
+        let mut viewer = ShareViewer::connect(server.url())
+            .await
+            .expect("connect viewer");
+        assert!(!viewer.can_approve);
+        server.publish(agent_message("after"));
+
+        let first = next_event(&mut viewer).await;
+        let second = next_event(&mut viewer).await;
+        assert_eq!(message_text(&first), Some("before"));
+        assert_eq!(message_text(&second), Some("after"));
+    }
+
+    #[tokio::test]
+    async fn connections_without_the_token_are_rejected() {
+        let (server, _ops) = ShareServer::start(localhost(), false)
+            .await
+            .expect("start share");
+        let (base, _token) = server.url().split_once("?token=").expect("url has a token");
+
+        let err = ShareViewer::connect(&format!("{base}?token=wrong"))
+            .await
+            .err()
+            .expect("wrong token should be rejected");
+        assert_eq!(err.kind(), io::ErrorKind::PermissionDenied);
+    }
+
+    #[tokio::test]
+    async fn only_approvals_reach_the_owner_when_allowed() {
+        let (server, mut ops) = ShareServer::start(localhost(), true)
+            .await
+            .expect("start share");
+        let viewer = ShareViewer::connect(server.url())
+            .await
+            .expect("connect viewer");
+        assert!(viewer.can_approve);
+
+        viewer.ops.send(Op::Interrupt).expect("send interrupt");
+        viewer
+            .ops
+            .send(Op::ExecApproval {
+                id: "call-1".to_string(),
+                decision: ReviewDecision::Approved,
+            })
+            .expect("send approval");
+
+        let op = timeout(Duration::from_secs(5), ops.recv())
+            .await
+            .expect("timed out waiting for an op")
+            .expect("ops closed");
+        assert_eq!(
+            op,
+            Op::ExecApproval {
+                id: "call-1".to_string(),
+                decision: ReviewDecision::Approved,
+            }
+        );
+    }
+
+    #[test]
+    fn history_drops_approvals_once_the_turn_moves_on() {
+        let mut history = ShareHistory::default();
+        history.push(approval_request("call-1"));
+        history.push(approval_request("call-2"));
+        history.push(agent_message("working"));
+        history.push(Event {
+            id: "turn-1".to_string(),
+            msg: EventMsg::TurnComplete(TurnCompleteEvent {
+                last_agent_message: None,
+            }),
+        });
+
+        let kept: Vec<String> = history
+            .events()
+            .map(|event| event.msg.to_string())
+            .collect();
+        assert_eq!(kept, vec!["agent_message", "turn_complete"]);
+    }
+
+    #[test]
+    fn history_replays_pending_approvals_in_order() {
+        let mut history = ShareHistory::default();
+        history.push(agent_message("before"));
+        history.push(approval_request("call-1"));
+        history.push(agent_message("after"));
+
+        let kept: Vec<String> = history
+            .events()
+            .map(|event| event.msg.to_string())
+            .collect();
+        assert_eq!(
+            kept,
+            vec!["agent_message", "exec_approval_request", "agent_message"]
+        );
+        let messages: Vec<Option<&str>> =
+            history.events().map(|event| message_text(event)).collect();
+        assert_eq!(messages, vec![Some("before"), None, Some("after")]);
+    }
+
+    #[test]
+    fn viewers_only_send_one_off_decisions() {
+        let approval = |decision| Op::ExecApproval {
+            id: "call-1".to_string(),
+            decision,
+        };
+        assert!(viewer_may_send(&approval(ReviewDecision::Approved), true));
+        assert!(viewer_may_send(&approval(ReviewDecision::Denied), true));
+        assert!(viewer_may_send(&approval(ReviewDecision::Abort), true));
+        assert!(!viewer_may_send(
+            &approval(ReviewDecision::ApprovedForSession),
+            true
+        ));
+        assert!(!viewer_may_send(&approval(ReviewDecision::Approved), false));
+        assert!(!viewer_may_send(&Op::Interrupt, true));
+    }
+}
@@ -3,6 +3,7 @@ use crate::outgoing_message::ConnectionId;
 use crate::outgoing_message::OutgoingEnvelope;
 use crate::outgoing_message::OutgoingMessage;
 use codex_app_server_protocol::JSONRPCMessage;
+use codex_utils_string::constant_time_eq;
 use futures::SinkExt;
 use futures::StreamExt;
 use owo_colors::OwoColorize;
@@ -24,8 +25,12 @@ use tokio::net::TcpListener;
 use tokio::net::TcpStream;
 use tokio::sync::mpsc;
 use tokio::task::JoinHandle;
-use tokio_tungstenite::accept_async;
+use tokio_tungstenite::accept_hdr_async;
 use tokio_tungstenite::tungstenite::Message as WebSocketMessage;
+use tokio_tungstenite::tungstenite::handshake::server::ErrorResponse;
+use tokio_tungstenite::tungstenite::handshake::server::Request;
+use tokio_tungstenite::tungstenite::handshake::server::Response;
+use tokio_tungstenite::tungstenite::http::StatusCode;
 use tracing::debug;
 use tracing::error;
 use tracing::info;
@@ -218,6 +223,15 @@ pub(crate) async fn start_stdio_connection(
     Ok(())
 }
 
+/// How a websocket acceptor treats incoming connections.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct WebSocketAcceptOptions {
+    /// When set, clients must pass this value as the `token` query parameter of the URL.
+    pub(crate) required_token: Option<Arc<str>>,
+    /// Print a line to stderr for every client, as the standalone server does.
+    pub(crate) announce_connections: bool,
+}
+
 pub(crate) async fn start_websocket_acceptor(
     bind_address: SocketAddr,
     transport_event_tx: mpsc::Sender<TransportEvent>,
@@ -227,19 +241,39 @@ pub(crate) async fn start_websocket_acceptor(
     print_websocket_startup_banner(local_addr);
     info!("app-server websocket listening on ws://{local_addr}");
 
+    Ok(spawn_websocket_acceptor(
+        listener,
+        WebSocketAcceptOptions {
+            required_token: None,
+            announce_connections: true,
+        },
+        transport_event_tx,
+    ))
+}
+
+/// Accepts websocket clients on an already bound listener and reports them as transport events.
+pub(crate) fn spawn_websocket_acceptor(
+    listener: TcpListener,
+    options: WebSocketAcceptOptions,
+    transport_event_tx: mpsc::Sender<TransportEvent>,
+) -> JoinHandle<()> {
     let connection_counter = Arc::new(AtomicU64::new(1));
-    Ok(tokio::spawn(async move {
+    tokio::spawn(async move {
         loop {
             match listener.accept().await {
                 Ok((stream, peer_addr)) => {
-                    print_websocket_connection(peer_addr);
+                    if options.announce_connections {
+                        print_websocket_connection(peer_addr);
+                    }
                     let connection_id =
                         ConnectionId(connection_counter.fetch_add(1, Ordering::Relaxed));
                     let transport_event_tx_for_connection = transport_event_tx.clone();
+                    let required_token = options.required_token.clone();
                     tokio::spawn(async move {
                         run_websocket_connection(
                             connection_id,
                             stream,
+                            required_token,
                             transport_event_tx_for_connection,
                         )
                         .await;
@@ -250,15 +284,30 @@ pub(crate) async fn start_websocket_acceptor(
                 }
             }
         }
-    }))
+    })
 }
 
 async fn run_websocket_connection(
     connection_id: ConnectionId,
     stream: TcpStream,
+    required_token: Option<Arc<str>>,
     transport_event_tx: mpsc::Sender<TransportEvent>,
 ) {
-    let websocket_stream = match accept_async(stream).await {
+    let check_token = |request: &Request, response: Response| {
+        let Some(required_token) = required_token.as_deref() else {
+            return Ok(response);
+        };
+        let token = request.uri().query().and_then(token_from_query);
+        if token.is_some_and(|token| constant_time_eq(token.as_bytes(), required_token.as_bytes()))
+        {
+            Ok(response)
+        } else {
+            let mut rejection = ErrorResponse::new(Some("invalid or missing token".to_string()));
+            *rejection.status_mut() = StatusCode::UNAUTHORIZED;
+            Err(rejection)
+        }
+    };
+    let websocket_stream = match accept_hdr_async(stream, check_token).await {
         Ok(stream) => stream,
         Err(err) => {
             warn!("failed to complete websocket handshake: {err}");
@@ -324,6 +373,14 @@ async fn run_websocket_connection(
         .await;
 }
 
+/// Returns the `token` parameter of a URL query string.
+fn token_from_query(query: &str) -> Option<&str> {
+    query
+        .split('&')
+        .find_map(|pair| pair.strip_prefix("token="))
+        .filter(|token| !token.is_empty())
+}
+
 async fn forward_incoming_message(
     transport_event_tx: &mpsc::Sender<TransportEvent>,
     connection_id: ConnectionId,
@@ -447,6 +504,14 @@ mod tests {
         );
     }
 
+    #[test]
+    fn token_is_read_from_the_query_string() {
+        assert_eq!(token_from_query("token=abc&x=1"), Some("abc"));
+        assert_eq!(token_from_query("x=1&token=abc"), Some("abc"));
+        assert_eq!(token_from_query("token="), None);
+        assert_eq!(token_from_query("tokens=abc"), None);
+    }
+
     #[test]
     fn app_server_transport_rejects_unsupported_listen_url() {
         let err = AppServerTransport::from_listen_url("http://127.0.0.1:1234")
@@ -445,8 +445,10 @@ async fn test_send_user_turn_updates_sandbox_and_cwd_between_turns() -> Result<(
             sandbox_policy: SandboxPolicy::WorkspaceWrite {
                 writable_roots: vec![first_cwd.try_into()?],
                 network_access: false,
+                allowed_loopback_ports: Vec::new(),
                 exclude_tmpdir_env_var: false,
                 exclude_slash_tmp: false,
+                deny_read: Vec::new(),
             },
             model: model.clone(),
             effort: Some(ReasoningEffort::Medium),
@@ -1100,8 +1100,10 @@ async fn turn_start_updates_sandbox_and_cwd_between_turns_v2() -> Result<()> {
             sandbox_policy: Some(codex_app_server_protocol::SandboxPolicy::WorkspaceWrite {
                 writable_roots: vec![first_cwd.try_into()?],
                 network_access: false,
+                allowed_loopback_ports: Vec::new(),
                 exclude_tmpdir_env_var: false,
                 exclude_slash_tmp: false,
+                deny_read: Vec::new(),
             }),
             model: Some("mock-model".to_string()),
             effort: Some(ReasoningEffort::Medium),
@@ -0,0 +1,90 @@
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::Result;
+use codex_common::CliConfigOverrides;
+use codex_core::config::Config;
+use codex_core::config::log_dir;
+use codex_core::debug_bundle::DebugBundle;
+use codex_core::debug_bundle::default_bundle_path;
+use codex_core::debug_bundle::read_log_tail;
+use codex_core::find_thread_path_by_id_str;
+use codex_core::find_thread_path_by_name_str;
+
+/// Write an offline debug bundle for support.
+///
+/// The bundle is a gzipped tarball with the TUI log, a session rollout, the config with secrets
+/// redacted, config layer origins, feature flags, MCP server status and an environment summary.
+/// Nothing is uploaded.
+#[derive(Debug, clap::Parser)]
+pub struct DebugBundleCommand {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    /// Where to write the tarball. Defaults to a timestamped file in the current directory.
+    #[arg(long, short = 'o', value_name = "FILE")]
+    pub output: Option<PathBuf>,
+
+    /// Include the rollout of this session (ID or name).
+    #[arg(long, value_name = "SESSION")]
+    pub session: Option<String>,
+
+    /// Leave out the TUI log file.
+    #[arg(long, default_value_t = false)]
+    pub no_logs: bool,
+
+    /// Only print what the bundle would include.
+    #[arg(long, default_value_t = false)]
+    pub dry_run: bool,
+}
+
+impl DebugBundleCommand {
+    pub async fn run(self) -> Result<()> {
+        let overrides = self
+            .config_overrides
+            .parse_overrides()
+            .map_err(anyhow::Error::msg)?;
+        let config = Config::load_with_cli_overrides(overrides)
+            .await
+            .context("failed to load configuration")?;
+
+        let rollout_path = match self.session.as_deref() {
+            Some(session) => Some(find_session(&config, session).await?),
+            None => None,
+        };
+        let logs = if self.no_logs {
+            None
+        } else {
+            let path = log_dir(&config)?.join("codex-tui.log");
+            read_log_tail(&path).ok()
+        };
+
+        let bundle = DebugBundle::collect(&config, logs, rollout_path.as_deref()).await;
+        for line in bundle.preview_lines() {
+            println!("{line}");
+        }
+        if self.dry_run {
+            return Ok(());
+        }
+
+        let output = match self.output {
+            Some(output) => output,
+            None => default_bundle_path(&std::env::current_dir()?),
+        };
+        bundle
+            .write_tarball(&output)
+            .with_context(|| format!("failed to write {}", output.display()))?;
+        println!();
+        println!("Wrote debug bundle to {}", output.display());
+        Ok(())
+    }
+}
+
+async fn find_session(config: &Config, session: &str) -> Result<PathBuf> {
+    if let Some(path) = find_thread_path_by_id_str(&config.codex_home, session).await? {
+        return Ok(path);
+    }
+    find_thread_path_by_name_str(&config.codex_home, session)
+        .await?
+        .with_context(|| format!("no saved session found with ID or name {session}"))
+}
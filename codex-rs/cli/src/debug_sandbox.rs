@@ -31,6 +31,7 @@ pub async fn run_command_under_seatbelt(
     let SeatbeltCommand {
         full_auto,
         log_denials,
+        deny_read,
         config_overrides,
         command,
     } = command;
@@ -41,6 +42,7 @@ pub async fn run_command_under_seatbelt(
         codex_linux_sandbox_exe,
         SandboxType::Seatbelt,
         log_denials,
+        deny_read,
     )
     .await
 }
@@ -59,6 +61,7 @@ pub async fn run_command_under_landlock(
 ) -> anyhow::Result<()> {
     let LandlockCommand {
         full_auto,
+        deny_read,
         config_overrides,
         command,
     } = command;
@@ -69,6 +72,7 @@ pub async fn run_command_under_landlock(
         codex_linux_sandbox_exe,
         SandboxType::Landlock,
         false,
+        deny_read,
     )
     .await
 }
@@ -89,6 +93,7 @@ pub async fn run_command_under_windows(
         codex_linux_sandbox_exe,
         SandboxType::Windows,
         false,
+        Vec::new(),
     )
     .await
 }
@@ -107,6 +112,7 @@ async fn run_command_under_sandbox(
     codex_linux_sandbox_exe: Option<PathBuf>,
     sandbox_type: SandboxType,
     log_denials: bool,
+    deny_read: Vec<String>,
 ) -> anyhow::Result<()> {
     let sandbox_mode = create_sandbox_mode(full_auto);
     let config = Config::load_with_cli_overrides_and_harness_overrides(
@@ -128,6 +134,12 @@ async fn run_command_under_sandbox(
     // sandbox policy. In the future, we could add a CLI option to set them
     // separately.
     let sandbox_policy_cwd = cwd.clone();
+    let mut sandbox_policy = config.sandbox_policy.get().clone();
+    if !deny_read.is_empty() {
+        let mut entries = sandbox_policy.deny_read().to_vec();
+        entries.extend(deny_read);
+        sandbox_policy.set_deny_read(entries);
+    }
 
     let stdio_policy = StdioPolicy::Inherit;
     let env = create_env(&config.shell_environment_policy, None);
@@ -141,7 +153,7 @@ async fn run_command_under_sandbox(
             use codex_windows_sandbox::run_windows_sandbox_capture;
             use codex_windows_sandbox::run_windows_sandbox_capture_elevated;
 
-            let policy_str = serde_json::to_string(config.sandbox_policy.get())?;
+            let policy_str = serde_json::to_string(&sandbox_policy)?;
 
             let sandbox_cwd = sandbox_policy_cwd.clone();
             let cwd_clone = cwd.clone();
@@ -219,7 +231,7 @@ async fn run_command_under_sandbox(
             spawn_command_under_seatbelt(
                 command,
                 cwd,
-                config.sandbox_policy.get(),
+                &sandbox_policy,
                 sandbox_policy_cwd.as_path(),
                 stdio_policy,
                 env,
@@ -237,7 +249,7 @@ async fn run_command_under_sandbox(
                 codex_linux_sandbox_exe,
                 command,
                 cwd,
-                config.sandbox_policy.get(),
+                &sandbox_policy,
                 sandbox_policy_cwd.as_path(),
                 use_bwrap_sandbox,
                 stdio_policy,
@@ -15,6 +15,11 @@ pub struct SeatbeltCommand {
     #[arg(long = "log-denials", default_value_t = false)]
     pub log_denials: bool,
 
+    /// Hide a path from the sandboxed command, in addition to `sandbox_deny_read`
+    /// from config.toml. May be repeated; supports `~/` and `*` in the last component.
+    #[arg(long = "deny-read", value_name = "PATH")]
+    pub deny_read: Vec<String>,
+
     #[clap(skip)]
     pub config_overrides: CliConfigOverrides,
 
@@ -29,6 +34,11 @@ pub struct LandlockCommand {
     #[arg(long = "full-auto", default_value_t = false)]
     pub full_auto: bool,
 
+    /// Hide a path from the sandboxed command, in addition to `sandbox_deny_read`
+    /// from config.toml. May be repeated; supports `~/` and `*` in the last component.
+    #[arg(long = "deny-read", value_name = "PATH")]
+    pub deny_read: Vec<String>,
+
     #[clap(skip)]
     pub config_overrides: CliConfigOverrides,
 
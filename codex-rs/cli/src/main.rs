@@ -21,6 +21,7 @@ use codex_exec::Cli as ExecCli;
 use codex_exec::Command as ExecCommand;
 use codex_exec::ReviewArgs;
 use codex_execpolicy::ExecPolicyCheckCommand;
+use codex_mcp_server::Cli as McpServerCli;
 use codex_responses_api_proxy::Args as ResponsesApiProxyArgs;
 use codex_tui::AppExitInfo;
 use codex_tui::Cli as TuiCli;
@@ -28,18 +29,23 @@ use codex_tui::ExitReason;
 use codex_tui::update_action::UpdateAction;
 use owo_colors::OwoColorize;
 use std::io::IsTerminal;
+use std::net::SocketAddr;
 use std::path::PathBuf;
 use supports_color::Stream;
 
 #[cfg(target_os = "macos")]
 mod app_cmd;
+mod debug_bundle_cmd;
 #[cfg(target_os = "macos")]
 mod desktop_app;
 mod mcp_cmd;
+mod usage_cmd;
 #[cfg(not(windows))]
 mod wsl_paths;
 
+use crate::debug_bundle_cmd::DebugBundleCommand;
 use crate::mcp_cmd::McpCli;
+use crate::usage_cmd::UsageCli;
 
 use codex_core::config::Config;
 use codex_core::config::ConfigOverrides;
@@ -96,8 +102,8 @@ enum Subcommand {
     /// [experimental] Run Codex as an MCP server and manage MCP servers.
     Mcp(McpCli),
 
-    /// [experimental] Run the Codex MCP server (stdio transport).
-    McpServer,
+    /// [experimental] Run the Codex MCP server (stdio or streamable HTTP transport).
+    McpServer(McpServerCli),
 
     /// [experimental] Run the app server or related tooling.
     AppServer(AppServerCommand),
@@ -129,6 +135,12 @@ enum Subcommand {
     /// Fork a previous interactive session (picker by default; use --last to fork the most recent).
     Fork(ForkCommand),
 
+    /// [experimental] Start an interactive session that others can watch with `codex attach`.
+    Share(ShareCommand),
+
+    /// [experimental] Watch a session started with `codex share`.
+    Attach(AttachCommand),
+
     /// [EXPERIMENTAL] Browse tasks from Codex Cloud and apply changes locally.
     #[clap(name = "cloud", alias = "cloud-tasks")]
     Cloud(CloudTasksCli),
@@ -143,6 +155,9 @@ enum Subcommand {
 
     /// Inspect feature flags.
     Features(FeaturesCli),
+
+    /// Show token usage and estimated cost across sessions.
+    Usage(UsageCli),
 }
 
 #[derive(Debug, Parser)]
@@ -162,6 +177,9 @@ struct DebugCommand {
 enum DebugSubcommand {
     /// Tooling: helps debug the app server.
     AppServer(DebugAppServerCommand),
+
+    /// Write an offline debug bundle (logs, rollout, redacted config) for support.
+    Bundle(DebugBundleCommand),
 }
 
 #[derive(Debug, Parser)]
@@ -220,6 +238,43 @@ struct ForkCommand {
     config_overrides: TuiCli,
 }
 
+#[derive(Debug, Parser)]
+struct ShareCommand {
+    /// Address viewers connect to, as `ws://IP:PORT`. Port 0 picks a free port. Binding a
+    /// non-loopback address exposes the session to your network; prefer SSH port-forwarding.
+    #[arg(
+        long = "listen",
+        value_name = "URL",
+        default_value = "ws://127.0.0.1:0",
+        value_parser = parse_share_listen_url
+    )]
+    listen: SocketAddr,
+
+    /// Let viewers answer approval requests. By default they can only watch.
+    #[arg(long = "allow-approvals", default_value_t = false)]
+    allow_approvals: bool,
+
+    #[clap(flatten)]
+    config_overrides: TuiCli,
+}
+
+#[derive(Debug, Parser)]
+struct AttachCommand {
+    /// URL printed by `codex share`, including its token.
+    #[arg(value_name = "URL")]
+    url: String,
+}
+
+fn parse_share_listen_url(listen_url: &str) -> Result<SocketAddr, String> {
+    match codex_app_server::AppServerTransport::from_listen_url(listen_url) {
+        Ok(codex_app_server::AppServerTransport::WebSocket { bind_address }) => Ok(bind_address),
+        Ok(codex_app_server::AppServerTransport::Stdio) => {
+            Err("sharing needs a websocket address; expected `ws://IP:PORT`".to_string())
+        }
+        Err(err) => Err(err.to_string()),
+    }
+}
+
 #[derive(Debug, Parser)]
 struct SandboxArgs {
     #[command(subcommand)]
@@ -586,14 +641,27 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
             );
             codex_exec::run_main(exec_cli, codex_linux_sandbox_exe).await?;
         }
-        Some(Subcommand::McpServer) => {
-            codex_mcp_server::run_main(codex_linux_sandbox_exe, root_config_overrides).await?;
+        Some(Subcommand::McpServer(mcp_server_cli)) => {
+            codex_mcp_server::run_main_with_transport(
+                codex_linux_sandbox_exe,
+                root_config_overrides,
+                mcp_server_cli.listen,
+                mcp_server_cli.bearer_token_env_var,
+            )
+            .await?;
         }
         Some(Subcommand::Mcp(mut mcp_cli)) => {
             // Propagate any root-level config overrides (e.g. `-c key=value`).
             prepend_config_flags(&mut mcp_cli.config_overrides, root_config_overrides.clone());
             mcp_cli.run().await?;
         }
+        Some(Subcommand::Usage(mut usage_cli)) => {
+            prepend_config_flags(
+                &mut usage_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            usage_cli.run().await?;
+        }
         Some(Subcommand::AppServer(app_server_cli)) => match app_server_cli.subcommand {
             None => {
                 let transport = app_server_cli.listen;
@@ -662,6 +730,30 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
             let exit_info = run_interactive_tui(interactive, codex_linux_sandbox_exe).await?;
             handle_app_exit(exit_info)?;
         }
+        Some(Subcommand::Share(ShareCommand {
+            listen,
+            allow_approvals,
+            config_overrides,
+        })) => {
+            interactive = finalize_share_interactive(
+                interactive,
+                root_config_overrides.clone(),
+                listen,
+                allow_approvals,
+                config_overrides,
+            );
+            let exit_info = run_interactive_tui(interactive, codex_linux_sandbox_exe).await?;
+            handle_app_exit(exit_info)?;
+        }
+        Some(Subcommand::Attach(AttachCommand { url })) => {
+            interactive.attach_url = Some(url);
+            prepend_config_flags(
+                &mut interactive.config_overrides,
+                root_config_overrides.clone(),
+            );
+            let exit_info = run_interactive_tui(interactive, codex_linux_sandbox_exe).await?;
+            handle_app_exit(exit_info)?;
+        }
         Some(Subcommand::Login(mut login_cli)) => {
             prepend_config_flags(
                 &mut login_cli.config_overrides,
@@ -749,6 +841,10 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
             DebugSubcommand::AppServer(cmd) => {
                 run_debug_app_server_command(cmd)?;
             }
+            DebugSubcommand::Bundle(mut cmd) => {
+                prepend_config_flags(&mut cmd.config_overrides, root_config_overrides.clone());
+                cmd.run().await?;
+            }
         },
         Some(Subcommand::Execpolicy(ExecpolicyCommand { sub })) => match sub {
             ExecpolicySubcommand::Check(cmd) => run_execpolicycheck(cmd)?,
@@ -976,8 +1072,28 @@ fn finalize_fork_interactive(
     interactive
 }
 
-/// Merge flags provided to `codex resume`/`codex fork` so they take precedence over any
-/// root-level flags. Only overrides fields explicitly set on the subcommand-scoped
+/// Build the final `TuiCli` for a `codex share` invocation.
+fn finalize_share_interactive(
+    mut interactive: TuiCli,
+    root_config_overrides: CliConfigOverrides,
+    listen: SocketAddr,
+    allow_approvals: bool,
+    share_cli: TuiCli,
+) -> TuiCli {
+    interactive.share_listen = Some(listen);
+    interactive.share_allow_approvals = allow_approvals;
+
+    // Merge share-scoped flags and overrides with highest precedence.
+    merge_interactive_cli_flags(&mut interactive, share_cli);
+
+    // Propagate any root-level config overrides (e.g. `-c key=value`).
+    prepend_config_flags(&mut interactive.config_overrides, root_config_overrides);
+
+    interactive
+}
+
+/// Merge flags provided to `codex resume`/`codex fork`/`codex share` so they take precedence
+/// over any root-level flags. Only overrides fields explicitly set on the subcommand-scoped
 /// CLI. Also appends `-c key=value` overrides with highest precedence.
 fn merge_interactive_cli_flags(interactive: &mut TuiCli, subcommand_cli: TuiCli) {
     if let Some(model) = subcommand_cli.model {
@@ -1335,6 +1451,40 @@ mod tests {
         assert!(interactive.fork_show_all);
     }
 
+    #[test]
+    fn share_defaults_to_a_free_localhost_port() {
+        let cli = MultitoolCli::try_parse_from(["codex", "share", "-m", "gpt-5.1"]).expect("parse");
+        let Some(Subcommand::Share(ShareCommand {
+            listen,
+            allow_approvals,
+            config_overrides,
+        })) = cli.subcommand
+        else {
+            panic!("expected share subcommand");
+        };
+        let interactive = finalize_share_interactive(
+            cli.interactive,
+            cli.config_overrides,
+            listen,
+            allow_approvals,
+            config_overrides,
+        );
+
+        assert_eq!(
+            interactive.share_listen,
+            Some("127.0.0.1:0".parse().expect("socket address"))
+        );
+        assert!(!interactive.share_allow_approvals);
+        assert_eq!(interactive.model.as_deref(), Some("gpt-5.1"));
+    }
+
+    #[test]
+    fn share_rejects_stdio_listen_url() {
+        let err = MultitoolCli::try_parse_from(["codex", "share", "--listen", "stdio://"])
+            .expect_err("stdio cannot be shared");
+        assert!(err.to_string().contains("expected `ws://IP:PORT`"));
+    }
+
     #[test]
     fn app_server_analytics_default_disabled_without_flag() {
         let app_server = app_server_from_args(["codex", "app-server"].as_ref());
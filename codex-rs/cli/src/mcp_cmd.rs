@@ -279,6 +279,8 @@ async fn run_add(config_overrides: &CliConfigOverrides, add_args: AddArgs) -> Re
         enabled_tools: None,
         disabled_tools: None,
         scopes: None,
+        default_tool_approval: None,
+        tool_approvals: HashMap::new(),
     };
 
     servers.insert(name.clone(), new_entry);
@@ -0,0 +1,90 @@
+use anyhow::Context;
+use anyhow::Result;
+use codex_common::CliConfigOverrides;
+use codex_core::config::Config;
+use codex_core::usage::UsageGroupBy;
+use codex_core::usage::load_usage_report;
+use codex_core::usage::parse_usage_date;
+
+/// Show token usage and estimated cost recorded across sessions.
+///
+/// Usage is recorded per turn in the state database, so the `sqlite` feature must be enabled.
+/// Costs come from the `pricing` tables under `model_providers` in config.toml.
+#[derive(Debug, clap::Parser)]
+pub struct UsageCli {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    /// Dimension to total usage by.
+    #[arg(long = "by", value_enum, default_value_t = GroupByArg::Day)]
+    pub group_by: GroupByArg,
+
+    /// Only include turns on or after this local date (YYYY-MM-DD or YYYY-MM).
+    #[arg(long, value_name = "DATE")]
+    pub since: Option<String>,
+
+    /// Only include turns before this local date (YYYY-MM-DD or YYYY-MM).
+    #[arg(long, value_name = "DATE")]
+    pub until: Option<String>,
+
+    /// Output format.
+    #[arg(long, value_enum, default_value_t = UsageFormat::Table)]
+    pub format: UsageFormat,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum GroupByArg {
+    Day,
+    Month,
+    Repo,
+    Model,
+    Thread,
+}
+
+impl From<GroupByArg> for UsageGroupBy {
+    fn from(value: GroupByArg) -> Self {
+        match value {
+            GroupByArg::Day => UsageGroupBy::Day,
+            GroupByArg::Month => UsageGroupBy::Month,
+            GroupByArg::Repo => UsageGroupBy::Repo,
+            GroupByArg::Model => UsageGroupBy::Model,
+            GroupByArg::Thread => UsageGroupBy::Thread,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum UsageFormat {
+    Table,
+    Csv,
+    Json,
+}
+
+impl UsageCli {
+    pub async fn run(self) -> Result<()> {
+        let overrides = self
+            .config_overrides
+            .parse_overrides()
+            .map_err(anyhow::Error::msg)?;
+        let config = Config::load_with_cli_overrides(overrides)
+            .await
+            .context("failed to load configuration")?;
+        let since = self.since.as_deref().map(parse_usage_date).transpose()?;
+        let until = self.until.as_deref().map(parse_usage_date).transpose()?;
+
+        let report = load_usage_report(&config, self.group_by.into(), since, until).await?;
+        match self.format {
+            UsageFormat::Table if report.rows.is_empty() => {
+                println!("No usage recorded for this period.");
+            }
+            UsageFormat::Table => {
+                for line in report.table_lines() {
+                    println!("{line}");
+                }
+            }
+            UsageFormat::Csv => print!("{}", report.to_csv()),
+            UsageFormat::Json => println!("{}", report.to_json()?),
+        }
+        Ok(())
+    }
+}
@@ -4,7 +4,7 @@ use codex_core::protocol::SandboxPolicy;
 pub fn summarize_sandbox_policy(sandbox_policy: &SandboxPolicy) -> String {
     match sandbox_policy {
         SandboxPolicy::DangerFullAccess => "danger-full-access".to_string(),
-        SandboxPolicy::ReadOnly => "read-only".to_string(),
+        SandboxPolicy::ReadOnly { .. } => "read-only".to_string(),
         SandboxPolicy::ExternalSandbox { network_access } => {
             let mut summary = "external-sandbox".to_string();
             if matches!(network_access, NetworkAccess::Enabled) {
@@ -15,8 +15,10 @@ pub fn summarize_sandbox_policy(sandbox_policy: &SandboxPolicy) -> String {
         SandboxPolicy::WorkspaceWrite {
             writable_roots,
             network_access,
+            allowed_loopback_ports,
             exclude_tmpdir_env_var,
             exclude_slash_tmp,
+            deny_read: _,
         } => {
             let mut summary = "workspace-write".to_string();
 
@@ -37,6 +39,12 @@ pub fn summarize_sandbox_policy(sandbox_policy: &SandboxPolicy) -> String {
             summary.push_str(&format!(" [{}]", writable_entries.join(", ")));
             if *network_access {
                 summary.push_str(" (network access enabled)");
+            } else if !allowed_loopback_ports.is_empty() {
+                let ports: Vec<String> = allowed_loopback_ports
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect();
+                summary.push_str(&format!(" (loopback ports {})", ports.join(", ")));
             }
             summary
         }
@@ -72,8 +80,10 @@ mod tests {
         let summary = summarize_sandbox_policy(&SandboxPolicy::WorkspaceWrite {
             writable_roots: vec![writable_root.clone()],
             network_access: true,
+            allowed_loopback_ports: Vec::new(),
             exclude_tmpdir_env_var: true,
             exclude_slash_tmp: true,
+            deny_read: Vec::new(),
         });
         assert_eq!(
             summary,
@@ -83,4 +93,20 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    fn workspace_write_summary_lists_loopback_ports() {
+        let summary = summarize_sandbox_policy(&SandboxPolicy::WorkspaceWrite {
+            writable_roots: Vec::new(),
+            network_access: false,
+            allowed_loopback_ports: vec![5432, 3000],
+            exclude_tmpdir_env_var: true,
+            exclude_slash_tmp: true,
+            deny_read: Vec::new(),
+        });
+        assert_eq!(
+            summary,
+            "workspace-write [workdir] (loopback ports 5432, 3000)"
+        );
+    }
 }
@@ -109,14 +109,79 @@ pub fn extract_bash_command(command: &[String]) -> Option<(&str, &str)> {
     Some((shell, script))
 }
 
-/// Returns the sequence of plain commands within a `bash -lc "..."` or
-/// `zsh -lc "..."` invocation when the script only contains word-only commands
-/// joined by safe operators.
+/// Extracts the shell and script from `fish -c "..."` or `nu -c "..."`,
+/// optionally with `-l`/`--login` (or `-lc`) as `Shell::derive_exec_args`
+/// produces them.
+pub fn extract_fish_or_nu_command(command: &[String]) -> Option<(&str, &str)> {
+    let (shell, script) = match command {
+        [shell, flag, script] if matches!(flag.as_str(), "-c" | "-lc") => (shell, script),
+        [shell, login, flag, script]
+            if matches!(login.as_str(), "-l" | "--login") && flag == "-c" =>
+        {
+            (shell, script)
+        }
+        _ => return None,
+    };
+    if !matches!(
+        detect_shell_type(&PathBuf::from(shell)),
+        Some(ShellType::Fish) | Some(ShellType::Nu)
+    ) {
+        return None;
+    }
+    Some((shell, script))
+}
+
+/// Returns the sequence of plain commands within a `bash -lc "..."`,
+/// `zsh -lc "..."`, `fish -c "..."` or `nu -c "..."` invocation when the
+/// script only contains word-only commands joined by safe operators.
 pub fn parse_shell_lc_plain_commands(command: &[String]) -> Option<Vec<Vec<String>>> {
-    let (_, script) = extract_bash_command(command)?;
+    if let Some((_, script)) = extract_bash_command(command) {
+        let tree = try_parse_shell(script)?;
+        return try_parse_word_only_commands_sequence(&tree, script);
+    }
 
+    // Word-only fish and Nushell scripts are a subset of what the bash grammar
+    // accepts, apart from the prefixes normalized below.
+    let (shell, script) = extract_fish_or_nu_command(command)?;
     let tree = try_parse_shell(script)?;
-    try_parse_word_only_commands_sequence(&tree, script)
+    let commands = try_parse_word_only_commands_sequence(&tree, script)?;
+    let shell_type = detect_shell_type(&PathBuf::from(shell))?;
+    commands
+        .into_iter()
+        .map(|words| normalize_fish_or_nu_words(&shell_type, words))
+        .collect()
+}
+
+/// Drops fish's `and`/`or`/`not` keywords and Nushell's `^` external-command
+/// marker so the remaining words can be classified like a bash command.
+/// Rejects fish words starting with `^`, which older fish versions treat as a
+/// stderr redirection.
+fn normalize_fish_or_nu_words(shell_type: &ShellType, words: Vec<String>) -> Option<Vec<String>> {
+    let mut words = words;
+    match shell_type {
+        ShellType::Fish => {
+            let keywords = words
+                .iter()
+                .take_while(|word| matches!(word.as_str(), "and" | "or" | "not"))
+                .count();
+            words.drain(..keywords);
+            if words.iter().any(|word| word.starts_with('^')) {
+                return None;
+            }
+        }
+        ShellType::Nu => {
+            if let Some(first) = words.first_mut()
+                && let Some(external) = first.strip_prefix('^')
+            {
+                *first = external.to_string();
+            }
+        }
+        _ => {}
+    }
+    if words.first().is_none_or(String::is_empty) {
+        return None;
+    }
+    Some(words)
 }
 
 fn parse_plain_command_from_node(cmd: tree_sitter::Node, src: &str) -> Option<Vec<String>> {
@@ -333,6 +398,41 @@ mod tests {
         assert_eq!(parsed, vec![vec!["ls".to_string()]]);
     }
 
+    #[test]
+    fn parse_fish_and_nu_plain_commands() {
+        let command = vec![
+            "/usr/bin/fish".to_string(),
+            "-l".to_string(),
+            "-c".to_string(),
+            "ls; or rm -rf build".to_string(),
+        ];
+        assert_eq!(
+            parse_shell_lc_plain_commands(&command),
+            Some(vec![
+                vec!["ls".to_string()],
+                vec!["rm".to_string(), "-rf".to_string(), "build".to_string()],
+            ])
+        );
+
+        let command = vec![
+            "nu".to_string(),
+            "-c".to_string(),
+            "^git status".to_string(),
+        ];
+        assert_eq!(
+            parse_shell_lc_plain_commands(&command),
+            Some(vec![vec!["git".to_string(), "status".to_string()]])
+        );
+    }
+
+    #[test]
+    fn rejects_fish_caret_redirection_and_substitution() {
+        for script in ["ls ^/tmp/errors", "echo (rm -rf build)"] {
+            let command = vec!["fish".to_string(), "-c".to_string(), script.to_string()];
+            assert_eq!(parse_shell_lc_plain_commands(&command), None, "{script}");
+        }
+    }
+
     #[test]
     fn accepts_concatenated_flag_and_value() {
         // Test case: -g"*.py" (flag directly concatenated with quoted value)
@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::fmt::Debug;
@@ -35,7 +36,6 @@ use crate::terminal;
 use crate::transport_manager::TransportManager;
 use crate::truncate::TruncationPolicy;
 use crate::turn_metadata::build_turn_metadata_header;
-use crate::user_notification::UserNotifier;
 use crate::util::error_or_panic;
 use async_channel::Receiver;
 use async_channel::Sender;
@@ -58,6 +58,7 @@ use codex_protocol::protocol::FileChange;
 use codex_protocol::protocol::HasLegacyEvent;
 use codex_protocol::protocol::ItemCompletedEvent;
 use codex_protocol::protocol::ItemStartedEvent;
+use codex_protocol::protocol::ProjectDocLoadedEvent;
 use codex_protocol::protocol::RawResponseItemEvent;
 use codex_protocol::protocol::ReviewRequest;
 use codex_protocol::protocol::RolloutItem;
@@ -112,6 +113,8 @@ use crate::config::ConstraintResult;
 use crate::config::GhostSnapshotConfig;
 use crate::config::resolve_web_search_mode_for_turn;
 use crate::config::types::McpServerConfig;
+use crate::config::types::ResourceLimits;
+use crate::config::types::SandboxProfile;
 use crate::config::types::ShellEnvironmentPolicy;
 use crate::context_manager::ContextManager;
 use crate::environment_context::EnvironmentContext;
@@ -122,6 +125,11 @@ use crate::exec::StreamOutput;
 use crate::exec_policy::ExecPolicyUpdateError;
 use crate::feedback_tags;
 use crate::git_info::get_git_repo_root;
+use crate::hooks::HookEvent;
+use crate::hooks::HookEventAfterAgent;
+use crate::hooks::HookPayload;
+use crate::hooks::Hooks;
+use crate::hooks::hook_event_for;
 use crate::instructions::UserInstructions;
 use crate::mcp::CODEX_APPS_MCP_SERVER_NAME;
 use crate::mcp::ExplicitMcpToolCall;
@@ -136,6 +144,9 @@ use crate::mentions::build_connector_slug_counts;
 use crate::mentions::build_skill_name_counts;
 use crate::mentions::collect_explicit_app_paths;
 use crate::mentions::collect_tool_mentions_from_messages;
+use crate::project_doc::InstructionContext;
+use crate::project_doc::NestedProjectDocs;
+use crate::project_doc::UserInstructionsTemplate;
 use crate::project_doc::get_user_instructions;
 use crate::proposed_plan_parser::ProposedPlanParser;
 use crate::proposed_plan_parser::ProposedPlanSegment;
@@ -199,17 +210,19 @@ use crate::tasks::SessionTask;
 use crate::tasks::SessionTaskContext;
 use crate::tools::ToolRouter;
 use crate::tools::context::SharedTurnDiffTracker;
+use crate::tools::handlers::WebFetchCache;
 use crate::tools::parallel::ToolCallRuntime;
 use crate::tools::sandboxing::ApprovalStore;
 use crate::tools::spec::ToolsConfig;
 use crate::tools::spec::ToolsConfigParams;
 use crate::turn_diff_tracker::TurnDiffTracker;
 use crate::unified_exec::UnifiedExecProcessManager;
-use crate::user_notification::UserNotification;
 use crate::util::backoff;
 use crate::windows_sandbox::WindowsSandboxLevelExt;
 use codex_async_utils::OrCancelExt;
 use codex_otel::OtelManager;
+use codex_otel::traces::spans::model_request_span;
+use codex_otel::traces::spans::record_token_usage;
 use codex_protocol::config_types::CollaborationMode;
 use codex_protocol::config_types::Personality;
 use codex_protocol::config_types::ReasoningSummary as ReasoningSummaryConfig;
@@ -374,6 +387,7 @@ impl Codex {
             compact_prompt: config.compact_prompt.clone(),
             approval_policy: config.approval_policy.clone(),
             sandbox_policy: config.sandbox_policy.clone(),
+            sandbox_profile: None,
             windows_sandbox_level: WindowsSandboxLevel::from_config(&config),
             cwd: config.cwd.clone(),
             codex_home: config.codex_home.clone(),
@@ -410,7 +424,7 @@ impl Codex {
         let thread_id = session.conversation_id;
 
         // This task will run until Op::Shutdown is received.
-        let session_loop_span = info_span!("session_loop", thread_id = %thread_id);
+        let session_loop_span = session.services.otel_manager.session_span();
         tokio::spawn(
             submission_loop(Arc::clone(&session), config, rx_sub).instrument(session_loop_span),
         );
@@ -516,6 +530,11 @@ pub(crate) struct TurnContext {
     pub(crate) history_depth: Option<u32>,
     pub(crate) terminal_size: Option<TerminalSize>,
     pub(crate) codex_linux_sandbox_exe: Option<PathBuf>,
+    pub(crate) resource_limits: ResourceLimits,
+    /// Named sandbox profiles the shell tools may run commands under.
+    pub(crate) sandbox_profiles: BTreeMap<String, SandboxProfile>,
+    /// Entry of `sandbox_profiles` the session's `sandbox_policy` comes from.
+    pub(crate) sandbox_profile: Option<String>,
     pub(crate) tool_call_gate: Arc<ReadinessFlag>,
     pub(crate) truncation_policy: TruncationPolicy,
     pub(crate) dynamic_tools: Vec<DynamicToolSpec>,
@@ -578,8 +597,9 @@ pub(crate) struct SessionConfiguration {
     /// Developer instructions that supplement the base instructions.
     developer_instructions: Option<String>,
 
-    /// Model instructions that are appended to the base instructions.
-    user_instructions: Option<String>,
+    /// Model instructions that are appended to the base instructions,
+    /// rendered for each turn.
+    user_instructions: Option<UserInstructionsTemplate>,
 
     /// Personality preference for the model.
     personality: Option<Personality>,
@@ -610,6 +630,8 @@ pub(crate) struct SessionConfiguration {
     approval_policy: Constrained<AskForApproval>,
     /// How to sandbox commands executed in the system
     sandbox_policy: Constrained<SandboxPolicy>,
+    /// `[sandbox_profiles]` entry `sandbox_policy` was selected from, if any.
+    sandbox_profile: Option<String>,
     windows_sandbox_level: WindowsSandboxLevel,
 
     /// Working directory that should be treated as the *root* of the
@@ -676,7 +698,27 @@ impl SessionConfiguration {
         if let Some(approval_policy) = updates.approval_policy {
             next_configuration.approval_policy.set(approval_policy)?;
         }
-        if let Some(sandbox_policy) = updates.sandbox_policy.clone() {
+        if let Some(name) = &updates.sandbox_profile {
+            match self.original_config_do_not_use.sandbox_profiles.get(name) {
+                Some(profile) => {
+                    next_configuration
+                        .sandbox_policy
+                        .set(profile.policy.clone())?;
+                    next_configuration.sandbox_profile = Some(name.clone());
+                }
+                None => warn!("ignoring unknown sandbox profile `{name}`"),
+            }
+        } else if let Some(mut sandbox_policy) = updates.sandbox_policy.clone() {
+            // Switching sandbox modes must not drop the configured read denials.
+            if sandbox_policy.deny_read().is_empty() {
+                sandbox_policy
+                    .set_deny_read(self.original_config_do_not_use.sandbox_deny_read.clone());
+            }
+            // Turns restate the current policy; only an actual change leaves
+            // the profile.
+            if sandbox_policy != *self.sandbox_policy.get() {
+                next_configuration.sandbox_profile = None;
+            }
             next_configuration.sandbox_policy.set(sandbox_policy)?;
         }
         if let Some(windows_sandbox_level) = updates.windows_sandbox_level {
@@ -700,6 +742,7 @@ pub(crate) struct SessionSettingsUpdate {
     pub(crate) cwd: Option<PathBuf>,
     pub(crate) approval_policy: Option<AskForApproval>,
     pub(crate) sandbox_policy: Option<SandboxPolicy>,
+    pub(crate) sandbox_profile: Option<String>,
     pub(crate) windows_sandbox_level: Option<WindowsSandboxLevel>,
     pub(crate) collaboration_mode: Option<CollaborationMode>,
     pub(crate) reasoning_summary: Option<ReasoningSummaryConfig>,
@@ -757,6 +800,8 @@ impl Session {
             model_info.slug.as_str(),
         );
         let per_turn_config = Arc::new(per_turn_config);
+        let local_web_search =
+            per_turn_config.web_search_backend.is_some() && !provider.is_openai();
         let client = ModelClient::new(
             per_turn_config.clone(),
             auth_manager,
@@ -775,7 +820,9 @@ impl Session {
             features: &per_turn_config.features,
             web_search_mode: per_turn_config.web_search_mode,
             disallowed_tools: &per_turn_config.disallowed_tools,
-        });
+        })
+        .with_sandbox_profiles(per_turn_config.sandbox_profiles.keys().cloned().collect())
+        .with_local_web_search(local_web_search);
 
         let cwd = session_configuration.cwd.clone();
         TurnContext {
@@ -784,7 +831,14 @@ impl Session {
             cwd,
             developer_instructions: session_configuration.developer_instructions.clone(),
             compact_prompt: session_configuration.compact_prompt.clone(),
-            user_instructions: session_configuration.user_instructions.clone(),
+            user_instructions: session_configuration.user_instructions.as_ref().and_then(
+                |instructions| {
+                    instructions.render(&InstructionContext::new(
+                        model_info.slug.as_str(),
+                        session_configuration.collaboration_mode.mode,
+                    ))
+                },
+            ),
             collaboration_mode: session_configuration.collaboration_mode.clone(),
             personality: session_configuration.personality,
             approval_policy: session_configuration.approval_policy.value(),
@@ -798,6 +852,9 @@ impl Session {
             history_depth: session_configuration.history_depth,
             terminal_size: session_configuration.terminal_size,
             codex_linux_sandbox_exe: per_turn_config.codex_linux_sandbox_exe.clone(),
+            resource_limits: per_turn_config.sandbox_resource_limits,
+            sandbox_profiles: per_turn_config.sandbox_profiles.clone(),
+            sandbox_profile: session_configuration.sandbox_profile.clone(),
             tool_call_gate: Arc::new(ReadinessFlag::new()),
             truncation_policy: model_info.truncation_policy.into(),
             dynamic_tools: session_configuration.dynamic_tools.clone(),
@@ -996,7 +1053,14 @@ impl Session {
                 }
             };
         session_configuration.thread_name = thread_name.clone();
-        let state = SessionState::new(session_configuration.clone());
+        let mut state = SessionState::new(session_configuration.clone());
+        state.nested_project_docs = NestedProjectDocs::new(
+            &config,
+            session_configuration
+                .user_instructions
+                .as_ref()
+                .map_or(0, UserInstructionsTemplate::project_doc_bytes),
+        );
 
         let services = SessionServices {
             mcp_connection_manager: Arc::new(RwLock::new(McpConnectionManager::default())),
@@ -1006,7 +1070,7 @@ impl Session {
                 Arc::clone(&config),
                 Arc::clone(&auth_manager),
             ),
-            notifier: UserNotifier::new(config.notify.clone()),
+            hooks: Hooks::new(&config),
             rollout: Mutex::new(rollout_recorder),
             user_shell: Arc::new(default_shell),
             show_raw_agent_reasoning: config.show_raw_agent_reasoning,
@@ -1015,6 +1079,7 @@ impl Session {
             otel_manager,
             models_manager: Arc::clone(&models_manager),
             tool_approvals: Mutex::new(ApprovalStore::default()),
+            web_fetch_cache: Mutex::new(WebFetchCache::default()),
             skills_manager,
             agent_control,
             state_db: state_db_ctx.clone(),
@@ -1196,6 +1261,7 @@ impl Session {
                     let mut state = self.state.lock().await;
                     state.set_token_info(Some(info));
                 }
+                self.restore_nested_project_docs(&rollout_items).await;
 
                 // Defer seeding the session's initial context until the first turn starts so
                 // turn/start overrides can be merged before we write to the rollout.
@@ -1217,6 +1283,7 @@ impl Session {
                     let mut state = self.state.lock().await;
                     state.set_token_info(Some(info));
                 }
+                self.restore_nested_project_docs(&rollout_items).await;
 
                 // If persisting, persist all rollout items as-is (recorder filters)
                 if !rollout_items.is_empty() {
@@ -1237,6 +1304,40 @@ impl Session {
         }
     }
 
+    /// Restores the nested project docs sent in a previous run, re-reading
+    /// them so their `when:` sections are still evaluated on every turn.
+    async fn restore_nested_project_docs(&self, rollout_items: &[RolloutItem]) {
+        let mut loaded = Vec::new();
+        let mut sent = HashMap::new();
+        for item in rollout_items {
+            match item {
+                RolloutItem::EventMsg(EventMsg::ProjectDocLoaded(event)) => {
+                    loaded.push((event.path.clone(), event.bytes));
+                }
+                RolloutItem::ResponseItem(ResponseItem::Message { role, content, .. })
+                    if role == "user" =>
+                {
+                    if let Some(instructions) = UserInstructions::from_message(content) {
+                        sent.insert(instructions.directory, instructions.text);
+                    }
+                }
+                _ => {}
+            }
+        }
+        if loaded.is_empty() {
+            return;
+        }
+
+        let restored = {
+            let mut state = self.state.lock().await;
+            state.nested_project_docs.mark_loaded(&loaded)
+        };
+        // Read the docs without holding the session lock.
+        let docs = restored.load().await;
+        let mut state = self.state.lock().await;
+        state.nested_project_docs.add_restored(&docs, &sent);
+    }
+
     fn last_token_info_from_rollout(rollout_items: &[RolloutItem]) -> Option<TokenUsageInfo> {
         rollout_items.iter().rev().find_map(|item| match item {
             RolloutItem::EventMsg(EventMsg::TokenCount(ev)) => ev.info.clone(),
@@ -1483,6 +1584,27 @@ impl Session {
         }
     }
 
+    /// Resends the user instructions when a model or collaboration mode
+    /// change selects different conditional sections.
+    fn build_user_instructions_update_item(
+        &self,
+        previous: Option<&Arc<TurnContext>>,
+        next: &TurnContext,
+    ) -> Option<ResponseItem> {
+        let prev = previous?;
+        if prev.user_instructions == next.user_instructions {
+            return None;
+        }
+        let text = next.user_instructions.clone()?;
+        Some(
+            UserInstructions {
+                text,
+                directory: next.cwd.to_string_lossy().into_owned(),
+            }
+            .into(),
+        )
+    }
+
     fn build_settings_update_items(
         &self,
         previous_context: Option<&Arc<TurnContext>>,
@@ -1504,6 +1626,11 @@ impl Session {
         {
             update_items.push(collaboration_mode_item);
         }
+        if let Some(user_instructions_item) =
+            self.build_user_instructions_update_item(previous_context, current_context)
+        {
+            update_items.push(user_instructions_item);
+        }
         if let Some(personality_item) =
             self.build_personality_update_item(previous_context, current_context)
         {
@@ -1529,6 +1656,12 @@ impl Session {
             };
             self.send_event_raw(legacy_event).await;
         }
+
+        if let Some(hook_event) =
+            hook_event_for(self.conversation_id, &turn_context.sub_id, &legacy_source)
+        {
+            self.dispatch_hook(turn_context, hook_event).await;
+        }
     }
 
     pub(crate) async fn send_event_raw(&self, event: Event) {
@@ -1911,6 +2044,63 @@ impl Session {
         state.record_items(items.iter(), turn_context.truncation_policy);
     }
 
+    /// Notes files the agent read or edited so that project docs covering
+    /// them are sent with the next model request.
+    pub(crate) async fn note_touched_paths(&self, paths: Vec<PathBuf>) {
+        if paths.is_empty() {
+            return;
+        }
+        let mut state = self.state.lock().await;
+        for path in paths {
+            state.nested_project_docs.note_path(&path);
+        }
+    }
+
+    /// Records project docs queued by [`Session::note_touched_paths`] as
+    /// scoped instructions, and resends loaded ones whose `when:` sections
+    /// select different text for this turn.
+    async fn record_nested_project_docs(&self, turn_context: &TurnContext) {
+        let mut pending = {
+            let mut state = self.state.lock().await;
+            state.nested_project_docs.take_pending()
+        };
+        // Read the docs without holding the session lock.
+        let docs = pending.load().await;
+        let model = turn_context.client.get_model();
+        let context = InstructionContext::new(&model, turn_context.collaboration_mode.mode);
+        let updates = {
+            let mut state = self.state.lock().await;
+            state.nested_project_docs.return_budget(pending);
+            state.nested_project_docs.add_loaded(&docs);
+            state.nested_project_docs.render_updates(&context)
+        };
+        if !updates.is_empty() {
+            let items: Vec<ResponseItem> = updates
+                .into_iter()
+                .map(|(path, text)| {
+                    let directory = path.parent().unwrap_or(path.as_path());
+                    UserInstructions {
+                        directory: directory.to_string_lossy().into_owned(),
+                        text,
+                    }
+                    .into()
+                })
+                .collect();
+            self.record_conversation_items(turn_context, &items).await;
+        }
+        for doc in docs {
+            self.send_event(
+                turn_context,
+                EventMsg::ProjectDocLoaded(ProjectDocLoadedEvent {
+                    bytes: doc.bytes(),
+                    path: doc.path,
+                    truncated: doc.truncated,
+                }),
+            )
+            .await;
+        }
+    }
+
     pub(crate) async fn record_model_warning(&self, message: impl Into<String>, ctx: &TurnContext) {
         self.services
             .otel_manager
@@ -2082,9 +2272,41 @@ impl Session {
                 );
             }
         }
+        if let Some(token_usage) = token_usage {
+            self.record_turn_usage(turn_context, token_usage).await;
+        }
         self.send_token_count_event(turn_context).await;
     }
 
+    /// Adds one model response's token usage to the turn's row in the state DB.
+    async fn record_turn_usage(&self, turn_context: &TurnContext, token_usage: &TokenUsage) {
+        let Some(state_db) = self.state_db() else {
+            return;
+        };
+        let model_provider = {
+            let state = self.state.lock().await;
+            state
+                .session_configuration
+                .original_config_do_not_use
+                .model_provider_id
+                .clone()
+        };
+        let turn = codex_state::TurnUsage {
+            thread_id: self.conversation_id,
+            turn_id: turn_context.sub_id.clone(),
+            model: turn_context.client.get_model(),
+            model_provider,
+            cwd: turn_context.cwd.clone(),
+        };
+        state_db::record_turn_usage(
+            Some(state_db.as_ref()),
+            &turn,
+            token_usage,
+            "update_token_usage_info",
+        )
+        .await;
+    }
+
     pub(crate) async fn recompute_token_usage(&self, turn_context: &TurnContext) {
         let Some(estimated_total_tokens) = self
             .clone_history()
@@ -2389,8 +2611,17 @@ impl Session {
         }
     }
 
-    pub(crate) fn notifier(&self) -> &UserNotifier {
-        &self.services.notifier
+    /// Runs the hooks registered for `hook_event`, e.g. the legacy `notify` command.
+    pub(crate) async fn dispatch_hook(&self, turn_context: &TurnContext, hook_event: HookEvent) {
+        self.services
+            .hooks
+            .dispatch(HookPayload {
+                session_id: self.conversation_id,
+                cwd: turn_context.cwd.clone(),
+                triggered_at: chrono::Utc::now(),
+                hook_event,
+            })
+            .await;
     }
 
     pub(crate) fn user_shell(&self) -> Arc<shell::Shell> {
@@ -2522,6 +2753,7 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
                 cwd,
                 approval_policy,
                 sandbox_policy,
+                sandbox_profile,
                 windows_sandbox_level,
                 model,
                 subagent_model,
@@ -2552,6 +2784,7 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
                         cwd,
                         approval_policy,
                         sandbox_policy,
+                        sandbox_profile,
                         windows_sandbox_level,
                         collaboration_mode: Some(collaboration_mode),
                         reasoning_summary: summary,
@@ -2791,6 +3024,7 @@ mod handlers {
                         cwd: Some(cwd),
                         approval_policy: Some(approval_policy),
                         sandbox_policy: Some(sandbox_policy),
+                        sandbox_profile: None,
                         windows_sandbox_level: None,
                         collaboration_mode,
                         reasoning_summary: Some(summary),
@@ -3438,6 +3672,9 @@ async fn spawn_review_thread(
         history_depth: parent_turn_context.history_depth,
         terminal_size: parent_turn_context.terminal_size,
         codex_linux_sandbox_exe: parent_turn_context.codex_linux_sandbox_exe.clone(),
+        resource_limits: parent_turn_context.resource_limits,
+        sandbox_profiles: BTreeMap::new(),
+        sandbox_profile: None,
         tool_call_gate: Arc::new(ReadinessFlag::new()),
         dynamic_tools: parent_turn_context.dynamic_tools.clone(),
         truncation_policy: model_info.truncation_policy.into(),
@@ -3687,6 +3924,7 @@ pub(crate) async fn run_turn(
         let sampling_request_input: Vec<ResponseItem> = {
             sess.record_conversation_items(&turn_context, &pending_input)
                 .await;
+            sess.record_nested_project_docs(&turn_context).await;
             sess.clone_history().await.for_prompt()
         };
 
@@ -3742,14 +3980,18 @@ pub(crate) async fn run_turn(
 
                 if !needs_follow_up {
                     last_agent_message = sampling_request_last_agent_message;
-                    sess.notifier()
-                        .notify(&UserNotification::AgentTurnComplete {
-                            thread_id: sess.conversation_id.to_string(),
-                            turn_id: turn_context.sub_id.clone(),
-                            cwd: turn_context.cwd.display().to_string(),
-                            input_messages: sampling_request_input_messages,
-                            last_assistant_message: last_agent_message.clone(),
-                        });
+                    sess.dispatch_hook(
+                        &turn_context,
+                        HookEvent::AfterAgent {
+                            event: HookEventAfterAgent {
+                                thread_id: sess.conversation_id,
+                                turn_id: turn_context.sub_id.clone(),
+                                input_messages: sampling_request_input_messages,
+                                last_assistant_message: last_agent_message.clone(),
+                            },
+                        },
+                    )
+                    .await;
                     break;
                 }
                 continue;
@@ -4521,9 +4763,12 @@ async fn try_run_sampling_request(
     );
 
     sess.persist_rollout_items(&[rollout_item]).await;
+    // Covers only the model stream; tool calls started while it is open are
+    // siblings under the turn span rather than children of the request.
+    let request_span = model_request_span(&turn_context.sub_id, &turn_context.client.get_model());
     let mut stream = client_session
         .stream(prompt)
-        .instrument(trace_span!("stream_request"))
+        .instrument(trace_span!(parent: &request_span, "stream_request"))
         .or_cancel(&cancellation_token)
         .await??;
 
@@ -4541,10 +4786,9 @@ async fn try_run_sampling_request(
     let mut should_emit_turn_diff = false;
     let plan_mode = turn_context.collaboration_mode.mode == ModeKind::Plan;
     let mut plan_mode_state = plan_mode.then(|| PlanModeStreamState::new(&turn_context.sub_id));
-    let receiving_span = trace_span!("receiving_stream");
+    let receiving_span = trace_span!(parent: &request_span, "receiving_stream");
     let outcome: CodexResult<SamplingRequestResult> = loop {
         let handle_responses = trace_span!(
-            parent: &receiving_span,
             "handle_responses",
             otel.name = field::Empty,
             tool_name = field::Empty,
@@ -4553,7 +4797,7 @@ async fn try_run_sampling_request(
 
         let event = match stream
             .next()
-            .instrument(trace_span!(parent: &handle_responses, "receiving"))
+            .instrument(trace_span!(parent: &receiving_span, "receiving"))
             .or_cancel(&cancellation_token)
             .await
         {
@@ -4662,6 +4906,9 @@ async fn try_run_sampling_request(
                 if let Some(state) = plan_mode_state.as_mut() {
                     flush_proposed_plan_segments_all(&sess, &turn_context, state).await;
                 }
+                if let Some(usage) = token_usage.as_ref() {
+                    record_token_usage(&request_span, usage);
+                }
                 sess.update_token_usage_info(&turn_context, token_usage.as_ref())
                     .await;
                 should_emit_turn_diff = true;
@@ -4750,6 +4997,8 @@ async fn try_run_sampling_request(
             }
         }
     };
+    drop(receiving_span);
+    drop(request_span);
 
     drain_in_flight(&mut in_flight, sess.clone(), turn_context.clone()).await?;
 
@@ -5280,7 +5529,10 @@ mod tests {
             collaboration_mode,
             model_reasoning_summary: config.model_reasoning_summary,
             developer_instructions: config.developer_instructions.clone(),
-            user_instructions: config.user_instructions.clone(),
+            user_instructions: config
+                .user_instructions
+                .clone()
+                .map(UserInstructionsTemplate::from),
             personality: config.personality,
             max_output_tokens: None,
             history_depth: None,
@@ -5295,6 +5547,7 @@ mod tests {
             compact_prompt: config.compact_prompt.clone(),
             approval_policy: config.approval_policy.clone(),
             sandbox_policy: config.sandbox_policy.clone(),
+            sandbox_profile: None,
             windows_sandbox_level: WindowsSandboxLevel::from_config(&config),
             cwd: config.cwd.clone(),
             codex_home: config.codex_home.clone(),
@@ -5369,7 +5622,10 @@ mod tests {
             collaboration_mode,
             model_reasoning_summary: config.model_reasoning_summary,
             developer_instructions: config.developer_instructions.clone(),
-            user_instructions: config.user_instructions.clone(),
+            user_instructions: config
+                .user_instructions
+                .clone()
+                .map(UserInstructionsTemplate::from),
             personality: config.personality,
             max_output_tokens: None,
             history_depth: None,
@@ -5384,6 +5640,7 @@ mod tests {
             compact_prompt: config.compact_prompt.clone(),
             approval_policy: config.approval_policy.clone(),
             sandbox_policy: config.sandbox_policy.clone(),
+            sandbox_profile: None,
             windows_sandbox_level: WindowsSandboxLevel::from_config(&config),
             cwd: config.cwd.clone(),
             codex_home: config.codex_home.clone(),
@@ -5645,7 +5902,10 @@ mod tests {
             collaboration_mode,
             model_reasoning_summary: config.model_reasoning_summary,
             developer_instructions: config.developer_instructions.clone(),
-            user_instructions: config.user_instructions.clone(),
+            user_instructions: config
+                .user_instructions
+                .clone()
+                .map(UserInstructionsTemplate::from),
             personality: config.personality,
             max_output_tokens: None,
             history_depth: None,
@@ -5660,6 +5920,7 @@ mod tests {
             compact_prompt: config.compact_prompt.clone(),
             approval_policy: config.approval_policy.clone(),
             sandbox_policy: config.sandbox_policy.clone(),
+            sandbox_profile: None,
             windows_sandbox_level: WindowsSandboxLevel::from_config(&config),
             cwd: config.cwd.clone(),
             codex_home: config.codex_home.clone(),
@@ -5692,7 +5953,7 @@ mod tests {
                 Arc::clone(&config),
                 Arc::clone(&auth_manager),
             ),
-            notifier: UserNotifier::new(None),
+            hooks: Hooks::default(),
             rollout: Mutex::new(None),
             user_shell: Arc::new(default_user_shell()),
             show_raw_agent_reasoning: config.show_raw_agent_reasoning,
@@ -5701,6 +5962,7 @@ mod tests {
             otel_manager: otel_manager.clone(),
             models_manager: Arc::clone(&models_manager),
             tool_approvals: Mutex::new(ApprovalStore::default()),
+            web_fetch_cache: Mutex::new(WebFetchCache::default()),
             skills_manager,
             agent_control,
             state_db: None,
@@ -5771,7 +6033,10 @@ mod tests {
             collaboration_mode,
             model_reasoning_summary: config.model_reasoning_summary,
             developer_instructions: config.developer_instructions.clone(),
-            user_instructions: config.user_instructions.clone(),
+            user_instructions: config
+                .user_instructions
+                .clone()
+                .map(UserInstructionsTemplate::from),
             personality: config.personality,
             max_output_tokens: None,
             history_depth: None,
@@ -5786,6 +6051,7 @@ mod tests {
             compact_prompt: config.compact_prompt.clone(),
             approval_policy: config.approval_policy.clone(),
             sandbox_policy: config.sandbox_policy.clone(),
+            sandbox_profile: None,
             windows_sandbox_level: WindowsSandboxLevel::from_config(&config),
             cwd: config.cwd.clone(),
             codex_home: config.codex_home.clone(),
@@ -5818,7 +6084,7 @@ mod tests {
                 Arc::clone(&config),
                 Arc::clone(&auth_manager),
             ),
-            notifier: UserNotifier::new(None),
+            hooks: Hooks::default(),
             rollout: Mutex::new(None),
             user_shell: Arc::new(default_user_shell()),
             show_raw_agent_reasoning: config.show_raw_agent_reasoning,
@@ -5827,6 +6093,7 @@ mod tests {
             otel_manager: otel_manager.clone(),
             models_manager: Arc::clone(&models_manager),
             tool_approvals: Mutex::new(ApprovalStore::default()),
+            web_fetch_cache: Mutex::new(WebFetchCache::default()),
             skills_manager,
             agent_control,
             state_db: None,
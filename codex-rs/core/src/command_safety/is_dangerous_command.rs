@@ -206,6 +206,15 @@ mod tests {
         ])));
     }
 
+    #[test]
+    fn fish_git_reset_is_dangerous() {
+        assert!(command_might_be_dangerous(&vec_str(&[
+            "fish",
+            "-c",
+            "git status; or git reset --hard",
+        ])));
+    }
+
     #[test]
     fn git_status_is_not_dangerous() {
         assert!(!command_might_be_dangerous(&vec_str(&["git", "status"])));
@@ -413,6 +413,31 @@ mod tests {
         assert!(is_known_safe_command(&vec_str(&["zsh", "-lc", "ls"])));
     }
 
+    #[test]
+    fn fish_and_nu_safe_command_sequences() {
+        assert!(is_known_safe_command(&vec_str(&[
+            "fish",
+            "-l",
+            "-c",
+            "ls && git status"
+        ])));
+        assert!(is_known_safe_command(&vec_str(&[
+            "nu",
+            "-c",
+            "^git status"
+        ])));
+        assert!(!is_known_safe_command(&vec_str(&[
+            "fish",
+            "-c",
+            "ls; and rm -rf build"
+        ])));
+        assert!(!is_known_safe_command(&vec_str(&[
+            "fish",
+            "-c",
+            "echo $HOME"
+        ])));
+    }
+
     #[test]
     fn unknown_or_partial() {
         assert!(!is_safe_to_call_with_exec(&vec_str(&["foo"])));
@@ -73,10 +73,18 @@ pub fn status_line_items_edit(items: &[String]) -> ConfigEdit {
     }
 }
 
+pub fn tui_theme_edit(theme: &str) -> ConfigEdit {
+    ConfigEdit::SetPath {
+        segments: vec!["tui".to_string(), "theme".to_string()],
+        value: value(theme),
+    }
+}
+
 // TODO(jif) move to a dedicated file
 mod document_helpers {
     use crate::config::types::McpServerConfig;
     use crate::config::types::McpServerTransportConfig;
+    use serde::Serialize;
     use toml_edit::Array as TomlArray;
     use toml_edit::InlineTable;
     use toml_edit::Item as TomlItem;
@@ -218,10 +226,38 @@ mod document_helpers {
         {
             entry["scopes"] = array_from_iter(scopes.iter().cloned());
         }
+        if let Some(approval) = &config.default_tool_approval
+            && let Some(item) = item_from_serializable(approval)
+        {
+            entry["default_tool_approval"] = item;
+        }
+        if !config.tool_approvals.is_empty() {
+            let mut approvals: Vec<_> = config.tool_approvals.iter().collect();
+            approvals.sort_by(|(a, _), (b, _)| a.cmp(b));
+            let mut table = TomlTable::new();
+            table.set_implicit(false);
+            for (tool, approval) in approvals {
+                if let Some(item) = item_from_serializable(approval) {
+                    table.insert(tool, item);
+                }
+            }
+            entry["tool_approvals"] = TomlItem::Table(table);
+        }
 
         entry
     }
 
+    /// Converts a serde value into an inline TOML value, or `None` when it has
+    /// no TOML representation (e.g. JSON `null`).
+    fn item_from_serializable<T: Serialize>(value: &T) -> Option<TomlItem> {
+        let value = toml::Value::try_from(value).ok()?;
+        value
+            .to_string()
+            .parse::<toml_edit::Value>()
+            .ok()
+            .map(TomlItem::Value)
+    }
+
     pub(super) fn serialize_mcp_server(config: &McpServerConfig) -> TomlItem {
         TomlItem::Table(serialize_mcp_server_table(config))
     }
@@ -870,9 +906,12 @@ impl ConfigEditsBuilder {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::types::McpArgumentPredicate;
     use crate::config::types::McpServerTransportConfig;
+    use crate::config::types::McpToolApproval;
     use codex_protocol::openai_models::ReasoningEffort;
     use pretty_assertions::assert_eq;
+    use std::collections::HashMap;
     #[cfg(unix)]
     use std::os::unix::fs::symlink;
     use tempfile::tempdir;
@@ -1426,6 +1465,8 @@ gpt-5 = "gpt-5.1"
                 enabled_tools: Some(vec!["one".to_string(), "two".to_string()]),
                 disabled_tools: None,
                 scopes: None,
+                default_tool_approval: None,
+                tool_approvals: HashMap::new(),
             },
         );
 
@@ -1450,6 +1491,8 @@ gpt-5 = "gpt-5.1"
                 enabled_tools: None,
                 disabled_tools: Some(vec!["forbidden".to_string()]),
                 scopes: None,
+                default_tool_approval: None,
+                tool_approvals: HashMap::new(),
             },
         );
 
@@ -1485,6 +1528,57 @@ B = \"2\"
         assert_eq!(raw, expected);
     }
 
+    #[test]
+    fn blocking_replace_mcp_servers_round_trips_tool_approvals() {
+        let tmp = tempdir().expect("tmpdir");
+        let codex_home = tmp.path();
+
+        let server = McpServerConfig {
+            transport: McpServerTransportConfig::StreamableHttp {
+                url: "https://tickets.example.com/mcp".to_string(),
+                bearer_token_env_var: None,
+                http_headers: None,
+                env_http_headers: None,
+            },
+            enabled: true,
+            required: false,
+            disabled_reason: None,
+            startup_timeout_sec: None,
+            tool_timeout_sec: None,
+            enabled_tools: None,
+            disabled_tools: None,
+            scopes: None,
+            default_tool_approval: Some(McpToolApproval::OnFirstUse),
+            tool_approvals: HashMap::from([
+                ("create_issue".to_string(), McpToolApproval::Always),
+                (
+                    "update_issue".to_string(),
+                    McpToolApproval::OnArgumentMatch(vec![McpArgumentPredicate {
+                        pointer: "/project".to_string(),
+                        equals: Some(serde_json::json!("PROD")),
+                        one_of: None,
+                        contains: None,
+                    }]),
+                ),
+            ]),
+        };
+        let servers = BTreeMap::from([("tickets".to_string(), server.clone())]);
+
+        apply_blocking(codex_home, None, &[ConfigEdit::ReplaceMcpServers(servers)])
+            .expect("persist");
+
+        let raw = std::fs::read_to_string(codex_home.join(CONFIG_TOML_FILE)).expect("read config");
+        assert!(raw.contains("default_tool_approval = \"on_first_use\""));
+        assert!(raw.contains("[mcp_servers.tickets.tool_approvals]\ncreate_issue = \"always\""));
+
+        let parsed: TomlValue = toml::from_str(&raw).expect("parse config");
+        let round_tripped: McpServerConfig = parsed["mcp_servers"]["tickets"]
+            .clone()
+            .try_into()
+            .expect("deserialize server");
+        assert_eq!(round_tripped, server);
+    }
+
     #[test]
     fn blocking_replace_mcp_servers_preserves_inline_comments() {
         let tmp = tempdir().expect("tmpdir");
@@ -1517,6 +1611,8 @@ foo = { command = "cmd" }
                 enabled_tools: None,
                 disabled_tools: None,
                 scopes: None,
+                default_tool_approval: None,
+                tool_approvals: HashMap::new(),
             },
         );
 
@@ -1563,6 +1659,8 @@ foo = { command = "cmd" } # keep me
                 enabled_tools: None,
                 disabled_tools: None,
                 scopes: None,
+                default_tool_approval: None,
+                tool_approvals: HashMap::new(),
             },
         );
 
@@ -1608,6 +1706,8 @@ foo = { command = "cmd", args = ["--flag"] } # keep me
                 enabled_tools: None,
                 disabled_tools: None,
                 scopes: None,
+                default_tool_approval: None,
+                tool_approvals: HashMap::new(),
             },
         );
 
@@ -1654,6 +1754,8 @@ foo = { command = "cmd" }
                 enabled_tools: None,
                 disabled_tools: None,
                 scopes: None,
+                default_tool_approval: None,
+                tool_approvals: HashMap::new(),
             },
         );
 
@@ -0,0 +1,610 @@
+//! `[tui.keybindings]`: user overrides for the TUI's named key actions.
+//!
+//! Chords are parsed and validated here, at config load, so a typo or a
+//! conflicting binding fails fast instead of silently doing nothing in the
+//! TUI. The TUI converts the resolved [`KeyChord`]s into terminal key events.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// One chord or a list of chords. An empty list unbinds the action, except
+/// for `submit` and `interrupt`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+#[serde(untagged)]
+pub enum KeybindingToml {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl KeybindingToml {
+    fn chords(&self) -> &[String] {
+        match self {
+            KeybindingToml::One(chord) => std::slice::from_ref(chord),
+            KeybindingToml::Many(chords) => chords,
+        }
+    }
+}
+
+/// Key chords for the TUI's named actions, e.g. `open_transcript = "ctrl-o"`.
+///
+/// Chords are written as `-`/`+` separated modifiers (`ctrl`, `alt`, `shift`)
+/// followed by a key: a single character, `enter`, `tab`, `esc`, `space`,
+/// `backspace`, `delete`, `up`, `down`, `left`, `right`, `home`, `end`,
+/// `pageup`, `pagedown` or `f1`..`f12`. Unset actions keep their defaults.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, JsonSchema)]
+#[serde(deny_unknown_fields)]
+#[schemars(deny_unknown_fields)]
+pub struct KeybindingsToml {
+    /// Submit the composer. Defaults to `enter`.
+    pub submit: Option<KeybindingToml>,
+    /// Insert a newline in the composer. Defaults to `["shift-enter", "ctrl-j"]`.
+    pub newline: Option<KeybindingToml>,
+    /// Queue the composer while a turn is running. Defaults to `tab`.
+    pub queue: Option<KeybindingToml>,
+    /// Interrupt the running turn. Defaults to `esc`.
+    pub interrupt: Option<KeybindingToml>,
+    /// Open (and close) the transcript pager. Defaults to `ctrl-t`.
+    pub open_transcript: Option<KeybindingToml>,
+    /// Edit the composer in `$VISUAL`/`$EDITOR`. Defaults to `ctrl-g`.
+    pub external_editor: Option<KeybindingToml>,
+    /// Cycle the collaboration mode. Defaults to `shift-tab`.
+    pub cycle_mode: Option<KeybindingToml>,
+    /// Focus the next agent thread. Unbound by default.
+    pub cycle_agent: Option<KeybindingToml>,
+    /// Approve a request. Defaults to `y`.
+    pub approve: Option<KeybindingToml>,
+    /// Approve and stop asking for the rest of the session. Defaults to `a`.
+    pub approve_for_session: Option<KeybindingToml>,
+    /// Approve and remember the proposed command prefix. Defaults to `p`.
+    pub approve_prefix: Option<KeybindingToml>,
+    /// Deny a request. Defaults to `n`.
+    pub deny: Option<KeybindingToml>,
+    /// Cancel an MCP elicitation. Defaults to `c`.
+    pub cancel: Option<KeybindingToml>,
+}
+
+impl KeybindingsToml {
+    fn get(&self, action: KeybindingAction) -> Option<&KeybindingToml> {
+        match action {
+            KeybindingAction::Submit => self.submit.as_ref(),
+            KeybindingAction::Newline => self.newline.as_ref(),
+            KeybindingAction::Queue => self.queue.as_ref(),
+            KeybindingAction::Interrupt => self.interrupt.as_ref(),
+            KeybindingAction::OpenTranscript => self.open_transcript.as_ref(),
+            KeybindingAction::ExternalEditor => self.external_editor.as_ref(),
+            KeybindingAction::CycleMode => self.cycle_mode.as_ref(),
+            KeybindingAction::CycleAgent => self.cycle_agent.as_ref(),
+            KeybindingAction::Approve => self.approve.as_ref(),
+            KeybindingAction::ApproveForSession => self.approve_for_session.as_ref(),
+            KeybindingAction::ApprovePrefix => self.approve_prefix.as_ref(),
+            KeybindingAction::Deny => self.deny.as_ref(),
+            KeybindingAction::Cancel => self.cancel.as_ref(),
+        }
+    }
+}
+
+/// Where an action is active. Chords only conflict within one scope.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum KeybindingScope {
+    /// The chat view: composer, status indicator and app-level shortcuts.
+    Main,
+    /// The approval prompt.
+    Approval,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum KeybindingAction {
+    Submit,
+    Newline,
+    Queue,
+    Interrupt,
+    OpenTranscript,
+    ExternalEditor,
+    CycleMode,
+    CycleAgent,
+    Approve,
+    ApproveForSession,
+    ApprovePrefix,
+    Deny,
+    Cancel,
+}
+
+impl KeybindingAction {
+    pub const ALL: [KeybindingAction; 13] = [
+        KeybindingAction::Submit,
+        KeybindingAction::Newline,
+        KeybindingAction::Queue,
+        KeybindingAction::Interrupt,
+        KeybindingAction::OpenTranscript,
+        KeybindingAction::ExternalEditor,
+        KeybindingAction::CycleMode,
+        KeybindingAction::CycleAgent,
+        KeybindingAction::Approve,
+        KeybindingAction::ApproveForSession,
+        KeybindingAction::ApprovePrefix,
+        KeybindingAction::Deny,
+        KeybindingAction::Cancel,
+    ];
+
+    /// Name of the action in `[tui.keybindings]`.
+    pub fn config_key(self) -> &'static str {
+        match self {
+            KeybindingAction::Submit => "submit",
+            KeybindingAction::Newline => "newline",
+            KeybindingAction::Queue => "queue",
+            KeybindingAction::Interrupt => "interrupt",
+            KeybindingAction::OpenTranscript => "open_transcript",
+            KeybindingAction::ExternalEditor => "external_editor",
+            KeybindingAction::CycleMode => "cycle_mode",
+            KeybindingAction::CycleAgent => "cycle_agent",
+            KeybindingAction::Approve => "approve",
+            KeybindingAction::ApproveForSession => "approve_for_session",
+            KeybindingAction::ApprovePrefix => "approve_prefix",
+            KeybindingAction::Deny => "deny",
+            KeybindingAction::Cancel => "cancel",
+        }
+    }
+
+    fn scope(self) -> KeybindingScope {
+        match self {
+            KeybindingAction::Submit
+            | KeybindingAction::Newline
+            | KeybindingAction::Queue
+            | KeybindingAction::Interrupt
+            | KeybindingAction::OpenTranscript
+            | KeybindingAction::ExternalEditor
+            | KeybindingAction::CycleMode
+            | KeybindingAction::CycleAgent => KeybindingScope::Main,
+            KeybindingAction::Approve
+            | KeybindingAction::ApproveForSession
+            | KeybindingAction::ApprovePrefix
+            | KeybindingAction::Deny
+            | KeybindingAction::Cancel => KeybindingScope::Approval,
+        }
+    }
+
+    fn default_chords(self) -> &'static [&'static str] {
+        match self {
+            KeybindingAction::Submit => &["enter"],
+            KeybindingAction::Newline => &["shift-enter", "ctrl-j"],
+            KeybindingAction::Queue => &["tab"],
+            KeybindingAction::Interrupt => &["esc"],
+            KeybindingAction::OpenTranscript => &["ctrl-t"],
+            KeybindingAction::ExternalEditor => &["ctrl-g"],
+            KeybindingAction::CycleMode => &["shift-tab"],
+            KeybindingAction::CycleAgent => &[],
+            KeybindingAction::Approve => &["y"],
+            KeybindingAction::ApproveForSession => &["a"],
+            KeybindingAction::ApprovePrefix => &["p"],
+            KeybindingAction::Deny => &["n"],
+            KeybindingAction::Cancel => &["c"],
+        }
+    }
+
+    /// Actions that must keep at least one chord: without them a message
+    /// cannot be sent or a turn cannot be stopped.
+    fn is_required(self) -> bool {
+        matches!(self, KeybindingAction::Submit | KeybindingAction::Interrupt)
+    }
+
+    /// Chords the TUI handles itself in this scope and that cannot be rebound.
+    /// In the chat view these are quitting, pasting an image and the
+    /// composer's editing and history keys.
+    fn reserved_chords(self) -> &'static [&'static str] {
+        match self.scope() {
+            KeybindingScope::Main => &[
+                "ctrl-c",
+                "ctrl-d",
+                "ctrl-v",
+                "alt-v",
+                "ctrl-a",
+                "ctrl-e",
+                "ctrl-b",
+                "ctrl-f",
+                "ctrl-p",
+                "ctrl-n",
+                "ctrl-h",
+                "ctrl-w",
+                "ctrl-u",
+                "ctrl-k",
+                "ctrl-y",
+                "ctrl-alt-h",
+                "alt-b",
+                "alt-f",
+                "alt-backspace",
+                "alt-delete",
+                "alt-left",
+                "alt-right",
+                "ctrl-left",
+                "ctrl-right",
+                "backspace",
+                "delete",
+                "left",
+                "right",
+                "up",
+                "down",
+                "home",
+                "end",
+            ],
+            KeybindingScope::Approval => {
+                &["ctrl-c", "ctrl-d", "ctrl-a", "enter", "esc", "up", "down"]
+            }
+        }
+    }
+}
+
+impl fmt::Display for KeybindingAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.config_key())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChordKey {
+    /// A printable character. Letters are case-insensitive; Shift is a modifier.
+    Char(char),
+    Enter,
+    Tab,
+    Esc,
+    Backspace,
+    Delete,
+    Up,
+    Down,
+    Left,
+    Right,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    F(u8),
+}
+
+/// A single key press with modifiers, e.g. `ctrl-t` or `shift-enter`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyChord {
+    pub key: ChordKey,
+    pub ctrl: bool,
+    pub alt: bool,
+    pub shift: bool,
+}
+
+impl KeyChord {
+    /// Whether pressing this chord in the composer would type text.
+    fn types_text(&self) -> bool {
+        matches!(self.key, ChordKey::Char(_)) && !self.ctrl && !self.alt
+    }
+}
+
+impl FromStr for KeyChord {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chord = KeyChord {
+            key: ChordKey::Enter,
+            ctrl: false,
+            alt: false,
+            shift: false,
+        };
+        let mut rest = s.trim();
+        // Peel `modifier-` prefixes; a trailing separator is itself the key
+        // (`ctrl--`, `alt-+`).
+        while let Some(idx) = rest.find(['-', '+'])
+            && idx > 0
+            && idx + 1 < rest.len()
+        {
+            match rest[..idx].to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => chord.ctrl = true,
+                "alt" | "option" | "meta" => chord.alt = true,
+                "shift" => chord.shift = true,
+                other => return Err(format!("unknown modifier `{other}` in `{s}`")),
+            }
+            rest = &rest[idx + 1..];
+        }
+
+        let mut chars = rest.chars();
+        chord.key = match (chars.next(), chars.next()) {
+            (None, _) => return Err(format!("missing key in `{s}`")),
+            (Some(c), None) => ChordKey::Char(c.to_ascii_lowercase()),
+            _ => match rest.to_ascii_lowercase().as_str() {
+                "enter" | "return" => ChordKey::Enter,
+                "tab" => ChordKey::Tab,
+                "esc" | "escape" => ChordKey::Esc,
+                "space" => ChordKey::Char(' '),
+                "backspace" => ChordKey::Backspace,
+                "delete" | "del" => ChordKey::Delete,
+                "up" => ChordKey::Up,
+                "down" => ChordKey::Down,
+                "left" => ChordKey::Left,
+                "right" => ChordKey::Right,
+                "home" => ChordKey::Home,
+                "end" => ChordKey::End,
+                "pageup" | "pgup" => ChordKey::PageUp,
+                "pagedown" | "pgdn" => ChordKey::PageDown,
+                name => match name.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+                    Some(n @ 1..=12) => ChordKey::F(n),
+                    _ => return Err(format!("unknown key `{rest}` in `{s}`")),
+                },
+            },
+        };
+        Ok(chord)
+    }
+}
+
+impl fmt::Display for KeyChord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.ctrl {
+            f.write_str("ctrl-")?;
+        }
+        if self.alt {
+            f.write_str("alt-")?;
+        }
+        if self.shift {
+            f.write_str("shift-")?;
+        }
+        match self.key {
+            ChordKey::Char(' ') => f.write_str("space"),
+            ChordKey::Char(c) => write!(f, "{c}"),
+            ChordKey::Enter => f.write_str("enter"),
+            ChordKey::Tab => f.write_str("tab"),
+            ChordKey::Esc => f.write_str("esc"),
+            ChordKey::Backspace => f.write_str("backspace"),
+            ChordKey::Delete => f.write_str("delete"),
+            ChordKey::Up => f.write_str("up"),
+            ChordKey::Down => f.write_str("down"),
+            ChordKey::Left => f.write_str("left"),
+            ChordKey::Right => f.write_str("right"),
+            ChordKey::Home => f.write_str("home"),
+            ChordKey::End => f.write_str("end"),
+            ChordKey::PageUp => f.write_str("pageup"),
+            ChordKey::PageDown => f.write_str("pagedown"),
+            ChordKey::F(n) => write!(f, "f{n}"),
+        }
+    }
+}
+
+/// Resolved key chords for every action, defaults merged with `[tui.keybindings]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Keybindings {
+    bindings: BTreeMap<KeybindingAction, Vec<KeyChord>>,
+}
+
+impl Keybindings {
+    /// Merges `overrides` over the defaults and rejects unparsable, reserved
+    /// or conflicting chords.
+    pub fn resolve(overrides: &KeybindingsToml) -> Result<Self, String> {
+        let mut bindings = BTreeMap::new();
+        for action in KeybindingAction::ALL {
+            let chords = match overrides.get(action) {
+                Some(configured) => parse_chords(action, configured.chords())?,
+                None => parse_chords(action, action.default_chords())?,
+            };
+            if chords.is_empty() && action.is_required() {
+                return Err(format!("`{action}` needs at least one chord"));
+            }
+            bindings.insert(action, chords);
+        }
+
+        let mut owners: HashMap<(KeybindingScope, KeyChord), KeybindingAction> = HashMap::new();
+        for (&action, chords) in &bindings {
+            let reserved = parse_chords(action, action.reserved_chords())?;
+            for chord in chords {
+                if reserved.contains(chord) {
+                    return Err(format!(
+                        "`{chord}` is reserved and cannot be bound to `{action}`"
+                    ));
+                }
+                if action.scope() == KeybindingScope::Main && chord.types_text() {
+                    return Err(format!(
+                        "`{chord}` would shadow typing in the composer; bind `{action}` to a chord with ctrl or alt"
+                    ));
+                }
+                if let Some(other) = owners.insert((action.scope(), *chord), action) {
+                    return Err(format!(
+                        "`{chord}` is bound to both `{other}` and `{action}`"
+                    ));
+                }
+            }
+        }
+        Ok(Self { bindings })
+    }
+
+    /// Chords bound to `action`, in configuration order. The first one is
+    /// shown in key hints.
+    pub fn chords(&self, action: KeybindingAction) -> &[KeyChord] {
+        self.bindings
+            .get(&action)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Whether `action` still uses its built-in chords.
+    pub fn is_default(&self, action: KeybindingAction) -> bool {
+        parse_chords(action, action.default_chords())
+            .is_ok_and(|defaults| defaults.as_slice() == self.chords(action))
+    }
+}
+
+impl Default for Keybindings {
+    fn default() -> Self {
+        let bindings = KeybindingAction::ALL
+            .into_iter()
+            .map(|action| {
+                let chords = action
+                    .default_chords()
+                    .iter()
+                    .filter_map(|chord| chord.parse().ok())
+                    .collect();
+                (action, chords)
+            })
+            .collect();
+        Self { bindings }
+    }
+}
+
+fn parse_chords(
+    action: KeybindingAction,
+    chords: &[impl AsRef<str>],
+) -> Result<Vec<KeyChord>, String> {
+    let mut parsed: Vec<KeyChord> = Vec::with_capacity(chords.len());
+    for chord in chords {
+        let chord = chord
+            .as_ref()
+            .parse()
+            .map_err(|err| format!("invalid chord for `{action}`: {err}"))?;
+        if !parsed.contains(&chord) {
+            parsed.push(chord);
+        }
+    }
+    Ok(parsed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn chord(s: &str) -> KeyChord {
+        s.parse().expect("valid chord")
+    }
+
+    #[test]
+    fn parses_chords() {
+        assert_eq!(
+            chord("Ctrl+T"),
+            KeyChord {
+                key: ChordKey::Char('t'),
+                ctrl: true,
+                alt: false,
+                shift: false,
+            }
+        );
+        assert_eq!(chord("shift-tab").to_string(), "shift-tab");
+        assert_eq!(chord("alt-enter").to_string(), "alt-enter");
+        assert_eq!(chord("ctrl--").to_string(), "ctrl--");
+        assert_eq!(chord("Shift-A").to_string(), "shift-a");
+        assert_eq!(chord("space").key, ChordKey::Char(' '));
+        assert_eq!(chord("f5").key, ChordKey::F(5));
+        assert_eq!(chord("pgdn").key, ChordKey::PageDown);
+        assert!("hyper-x".parse::<KeyChord>().is_err());
+        assert!("ctrl-".parse::<KeyChord>().is_err());
+        assert!("f13".parse::<KeyChord>().is_err());
+        assert!("".parse::<KeyChord>().is_err());
+    }
+
+    #[test]
+    fn overrides_replace_defaults() {
+        let keybindings = Keybindings::resolve(&KeybindingsToml {
+            open_transcript: Some(KeybindingToml::One("ctrl-o".to_string())),
+            newline: Some(KeybindingToml::Many(vec!["alt-enter".to_string()])),
+            cycle_agent: Some(KeybindingToml::One("f2".to_string())),
+            queue: Some(KeybindingToml::Many(Vec::new())),
+            ..Default::default()
+        })
+        .expect("valid keybindings");
+
+        assert_eq!(
+            keybindings.chords(KeybindingAction::OpenTranscript),
+            &[chord("ctrl-o")]
+        );
+        assert_eq!(
+            keybindings.chords(KeybindingAction::Newline),
+            &[chord("alt-enter")]
+        );
+        assert_eq!(
+            keybindings.chords(KeybindingAction::CycleAgent),
+            &[chord("f2")]
+        );
+        assert_eq!(keybindings.chords(KeybindingAction::Queue), &[]);
+        assert!(keybindings.is_default(KeybindingAction::Submit));
+        assert!(!keybindings.is_default(KeybindingAction::OpenTranscript));
+        assert_eq!(
+            Keybindings::resolve(&KeybindingsToml::default()),
+            Ok(Keybindings::default())
+        );
+    }
+
+    #[test]
+    fn rejects_conflicts_within_a_scope() {
+        let err = Keybindings::resolve(&KeybindingsToml {
+            external_editor: Some(KeybindingToml::One("ctrl-t".to_string())),
+            ..Default::default()
+        })
+        .expect_err("conflict");
+        assert_eq!(
+            err,
+            "`ctrl-t` is bound to both `open_transcript` and `external_editor`"
+        );
+
+        // The same chord may be reused in a different scope.
+        Keybindings::resolve(&KeybindingsToml {
+            cycle_agent: Some(KeybindingToml::One("alt-y".to_string())),
+            approve: Some(KeybindingToml::Many(vec![
+                "y".to_string(),
+                "alt-y".to_string(),
+            ])),
+            ..Default::default()
+        })
+        .expect("different scopes do not conflict");
+    }
+
+    #[test]
+    fn rejects_reserved_and_typing_chords() {
+        let err = Keybindings::resolve(&KeybindingsToml {
+            interrupt: Some(KeybindingToml::One("ctrl-c".to_string())),
+            ..Default::default()
+        })
+        .expect_err("reserved");
+        assert_eq!(
+            err,
+            "`ctrl-c` is reserved and cannot be bound to `interrupt`"
+        );
+
+        let err = Keybindings::resolve(&KeybindingsToml {
+            open_transcript: Some(KeybindingToml::One("t".to_string())),
+            ..Default::default()
+        })
+        .expect_err("shadows typing");
+        assert!(err.starts_with("`t` would shadow typing"), "{err}");
+
+        let err = Keybindings::resolve(&KeybindingsToml {
+            deny: Some(KeybindingToml::One("escape".to_string())),
+            ..Default::default()
+        })
+        .expect_err("reserved in approvals");
+        assert_eq!(err, "`esc` is reserved and cannot be bound to `deny`");
+    }
+
+    #[test]
+    fn rejects_composer_shortcuts_and_unbinding_required_actions() {
+        for composer_chord in ["ctrl-a", "ctrl-e", "ctrl-k", "ctrl-v", "alt-b", "ctrl-left"] {
+            let err = Keybindings::resolve(&KeybindingsToml {
+                open_transcript: Some(KeybindingToml::One(composer_chord.to_string())),
+                ..Default::default()
+            })
+            .expect_err("composer shortcut");
+            assert_eq!(
+                err,
+                format!("`{composer_chord}` is reserved and cannot be bound to `open_transcript`")
+            );
+        }
+
+        let err = Keybindings::resolve(&KeybindingsToml {
+            submit: Some(KeybindingToml::Many(Vec::new())),
+            ..Default::default()
+        })
+        .expect_err("submit unbound");
+        assert_eq!(err, "`submit` needs at least one chord");
+        let err = Keybindings::resolve(&KeybindingsToml {
+            interrupt: Some(KeybindingToml::Many(Vec::new())),
+            ..Default::default()
+        })
+        .expect_err("interrupt unbound");
+        assert_eq!(err, "`interrupt` needs at least one chord");
+    }
+}
@@ -1,24 +1,33 @@
 use crate::auth::AuthCredentialsStoreMode;
 use crate::config::edit::ConfigEdit;
 use crate::config::edit::ConfigEditsBuilder;
+use crate::config::keybindings::Keybindings;
 use crate::config::types::AppsConfigToml;
 use crate::config::types::DEFAULT_OTEL_ENVIRONMENT;
 use crate::config::types::History;
+use crate::config::types::InlineImages;
 use crate::config::types::McpServerConfig;
 use crate::config::types::McpServerDisabledReason;
 use crate::config::types::McpServerTransportConfig;
+use crate::config::types::NetworkProxySettings;
+use crate::config::types::NetworkProxyToml;
 use crate::config::types::Notice;
 use crate::config::types::NotificationMethod;
 use crate::config::types::Notifications;
 use crate::config::types::OtelConfig;
 use crate::config::types::OtelConfigToml;
 use crate::config::types::OtelExporterKind;
+use crate::config::types::ResourceLimits;
+use crate::config::types::SandboxProfile;
+use crate::config::types::SandboxProfileNetwork;
+use crate::config::types::SandboxProfileToml;
 use crate::config::types::SandboxWorkspaceWrite;
 use crate::config::types::ShellEnvironmentPolicy;
 use crate::config::types::ShellEnvironmentPolicyToml;
 use crate::config::types::SkillsConfig;
 use crate::config::types::Tui;
 use crate::config::types::UriBasedFileOpener;
+use crate::config::types::WebSearchBackendToml;
 use crate::config_loader::CloudRequirementsLoader;
 use crate::config_loader::ConfigLayerStack;
 use crate::config_loader::ConfigRequirements;
@@ -67,6 +76,7 @@ use serde::Serialize;
 use similar::DiffableStr;
 use std::collections::BTreeMap;
 use std::collections::HashMap;
+use std::collections::hash_map::Entry;
 use std::io::ErrorKind;
 use std::path::Path;
 use std::path::PathBuf;
@@ -79,6 +89,7 @@ use toml_edit::DocumentMut;
 
 mod constraint;
 pub mod edit;
+pub mod keybindings;
 pub mod profile;
 pub mod schema;
 pub mod service;
@@ -147,6 +158,25 @@ pub struct Config {
 
     pub sandbox_policy: Constrained<SandboxPolicy>,
 
+    /// `sandbox_deny_read` entries from config.toml. They stay applied when
+    /// the sandbox mode is changed during a session.
+    pub sandbox_deny_read: Vec<String>,
+
+    /// Per-command resource caps for shell and unified exec commands.
+    pub sandbox_resource_limits: ResourceLimits,
+
+    /// Named sandbox profiles from `[sandbox_profiles]`, which the model can
+    /// pick per command and the user can switch to with `/permissions`.
+    pub sandbox_profiles: BTreeMap<String, SandboxProfile>,
+
+    /// Domain policy and proxy from `[network_proxy]`, applied to client-side
+    /// network tools such as `web_fetch`.
+    pub network_proxy: NetworkProxySettings,
+
+    /// Backend for the client-side `web_search` tool used with non-OpenAI
+    /// providers.
+    pub web_search_backend: Option<WebSearchBackendToml>,
+
     /// enforce_residency means web traffic cannot be routed outside of a
     /// particular geography. HTTP clients should direct their requests
     /// using backend-specific headers or URLs to enforce this.
@@ -205,6 +235,10 @@ pub struct Config {
     /// If unset the feature is disabled.
     pub notify: Option<Vec<String>>,
 
+    /// Payload types `notify` runs for, in the same shapes as
+    /// `tui.notifications`. Unset, it only runs for `agent-turn-complete`.
+    pub notify_events: Option<Notifications>,
+
     /// TUI notifications preference. When set, the TUI will send terminal notifications on
     /// approvals and turn completions when not focused.
     pub tui_notifications: Notifications,
@@ -229,6 +263,19 @@ pub struct Config {
     /// - `never`: Never use alternate screen (inline mode, preserves scrollback).
     pub tui_alternate_screen: AltScreenMode,
 
+    /// Key chords for the TUI's named actions, with `[tui.keybindings]`
+    /// merged over the defaults.
+    pub tui_keybindings: Keybindings,
+
+    /// Enable vi-style modal editing in the chat composer.
+    pub tui_vim_mode: bool,
+
+    /// Name of the TUI color theme from `tui.theme`, if one was chosen.
+    pub tui_theme: Option<String>,
+
+    /// Graphics protocol for inline image thumbnails from `tui.inline_images`.
+    pub tui_inline_images: InlineImages,
+
     /// The directory that should be treated as the current working directory
     /// for the session. All relative paths inside the business-logic layer are
     /// resolved against this path.
@@ -870,10 +917,34 @@ pub struct ConfigToml {
     /// Sandbox configuration to apply if `sandbox` is `WorkspaceWrite`.
     pub sandbox_workspace_write: Option<SandboxWorkspaceWrite>,
 
+    /// Paths that sandboxed commands may not read, in both `read-only` and
+    /// `workspace-write` mode (e.g. `~/.ssh`, `.env*`).
+    pub sandbox_deny_read: Option<Vec<String>>,
+
+    /// Resource caps (memory, CPU time, processes, file size, output) applied
+    /// to each command Codex runs.
+    pub sandbox_resource_limits: Option<ResourceLimits>,
+
+    /// Named sandbox profiles, e.g. a `build` profile with writable cargo
+    /// caches and proxied network next to a read-only `test` profile.
+    pub sandbox_profiles: Option<BTreeMap<String, SandboxProfileToml>>,
+
+    /// Settings shared with `codex-network-proxy`. Codex reads the domain
+    /// policy and proxy URL for its own client-side network tools.
+    pub network_proxy: Option<NetworkProxyToml>,
+
+    /// Search backend that gives the `web_search` tool results when the model
+    /// provider has no hosted web search, e.g. a local SearxNG instance.
+    pub web_search_backend: Option<WebSearchBackendToml>,
+
     /// Optional external command to spawn for end-user notifications.
     #[serde(default)]
     pub notify: Option<Vec<String>>,
 
+    /// Payload types `notify` runs for: `true`/`false`, a list of types, or a table of per-type
+    /// toggles. Unset, and for types a table leaves out, only `agent-turn-complete` is sent.
+    pub notify_events: Option<Notifications>,
+
     /// System instructions.
     pub instructions: Option<String>,
 
@@ -1190,13 +1261,16 @@ impl ConfigToml {
                 Some(SandboxWorkspaceWrite {
                     writable_roots,
                     network_access,
+                    allowed_loopback_ports,
                     exclude_tmpdir_env_var,
                     exclude_slash_tmp,
                 }) => SandboxPolicy::WorkspaceWrite {
                     writable_roots: writable_roots.clone(),
                     network_access: *network_access,
+                    allowed_loopback_ports: allowed_loopback_ports.clone(),
                     exclude_tmpdir_env_var: *exclude_tmpdir_env_var,
                     exclude_slash_tmp: *exclude_slash_tmp,
+                    deny_read: Vec::new(),
                 },
                 None => SandboxPolicy::new_workspace_write_policy(),
             },
@@ -1228,6 +1302,9 @@ impl ConfigToml {
             sandbox_policy = constraint.get().clone();
             downgrade_workspace_write_if_unsupported(&mut sandbox_policy);
         }
+        if let Some(deny_read) = self.sandbox_deny_read.as_ref() {
+            sandbox_policy.set_deny_read(deny_read.clone());
+        }
         SandboxPolicyResolution {
             policy: sandbox_policy,
             forced_auto_mode_downgraded_on_windows,
@@ -1361,6 +1438,110 @@ pub(crate) fn resolve_web_search_mode_for_turn(
     }
 }
 
+/// Listener of `codex-network-proxy` when `network_proxy.proxy_url` is unset.
+const DEFAULT_SANDBOX_PROFILE_PROXY_URL: &str = "http://127.0.0.1:3128";
+
+fn resolve_network_proxy(network_proxy: Option<&NetworkProxyToml>) -> NetworkProxySettings {
+    let Some(network_proxy) = network_proxy else {
+        return NetworkProxySettings::default();
+    };
+    let policy = network_proxy.policy.clone().unwrap_or_default();
+    let proxy_url = network_proxy.enabled.unwrap_or(false).then(|| {
+        network_proxy
+            .proxy_url
+            .clone()
+            .unwrap_or_else(|| DEFAULT_SANDBOX_PROFILE_PROXY_URL.to_string())
+    });
+    NetworkProxySettings {
+        proxy_url,
+        allowed_domains: policy.allowed_domains,
+        denied_domains: policy.denied_domains,
+        allow_local_binding: policy.allow_local_binding,
+    }
+}
+
+/// Resolve `[sandbox_profiles]` into sandbox policies for `cwd`.
+fn resolve_sandbox_profiles(
+    profiles: Option<&BTreeMap<String, SandboxProfileToml>>,
+    deny_read: &[String],
+    cwd: &Path,
+) -> std::io::Result<BTreeMap<String, SandboxProfile>> {
+    let invalid = |name: &str, message: String| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("invalid sandbox profile `{name}`: {message}"),
+        )
+    };
+    let mut resolved = BTreeMap::new();
+    for (name, profile) in profiles.into_iter().flatten() {
+        let writable_roots = profile
+            .writable_roots
+            .iter()
+            .map(|root| AbsolutePathBuf::resolve_path_against_base(root, cwd))
+            .collect::<std::io::Result<Vec<_>>>()
+            .map_err(|err| invalid(name, err.to_string()))?;
+        // A proxy profile keeps the network disabled and only opens the
+        // proxy's loopback port, so all egress goes through the proxy.
+        let proxy_url = (profile.network == SandboxProfileNetwork::Proxy).then(|| {
+            profile
+                .proxy_url
+                .clone()
+                .unwrap_or_else(|| DEFAULT_SANDBOX_PROFILE_PROXY_URL.to_string())
+        });
+        let allowed_loopback_ports = match &proxy_url {
+            Some(proxy_url) => {
+                vec![proxy_loopback_port(proxy_url).map_err(|message| invalid(name, message))?]
+            }
+            None => Vec::new(),
+        };
+        let network_access = profile.network == SandboxProfileNetwork::Full;
+        let mut policy = match profile.mode.unwrap_or_default() {
+            SandboxMode::ReadOnly if profile.network != SandboxProfileNetwork::None => {
+                return Err(invalid(
+                    name,
+                    "network access requires mode = \"workspace-write\"".to_string(),
+                ));
+            }
+            SandboxMode::ReadOnly => SandboxPolicy::ReadOnly {
+                writable_roots,
+                deny_read: Vec::new(),
+            },
+            SandboxMode::WorkspaceWrite => SandboxPolicy::WorkspaceWrite {
+                writable_roots,
+                network_access,
+                allowed_loopback_ports,
+                exclude_tmpdir_env_var: false,
+                exclude_slash_tmp: false,
+                deny_read: Vec::new(),
+            },
+            SandboxMode::DangerFullAccess => SandboxPolicy::DangerFullAccess,
+        };
+        policy.set_deny_read(deny_read.to_vec());
+        resolved.insert(name.clone(), SandboxProfile { policy, proxy_url });
+    }
+    Ok(resolved)
+}
+
+/// Port of a sandbox profile's proxy, which must listen on loopback since it
+/// is the only address the sandbox lets commands reach.
+fn proxy_loopback_port(proxy_url: &str) -> Result<u16, String> {
+    let url = url::Url::parse(proxy_url)
+        .map_err(|err| format!("invalid proxy_url `{proxy_url}`: {err}"))?;
+    let is_loopback = match url.host() {
+        Some(url::Host::Domain(domain)) => domain.eq_ignore_ascii_case("localhost"),
+        Some(url::Host::Ipv4(ip)) => ip.is_loopback(),
+        Some(url::Host::Ipv6(ip)) => ip.is_loopback(),
+        None => false,
+    };
+    if !is_loopback {
+        return Err(format!(
+            "proxy_url `{proxy_url}` must point at a loopback address"
+        ));
+    }
+    url.port_or_known_default()
+        .ok_or_else(|| format!("proxy_url `{proxy_url}` has no port"))
+}
+
 impl Config {
     #[cfg(test)]
     fn load_from_base_config_with_overrides(
@@ -1539,9 +1720,19 @@ impl Config {
             approval_policy_was_explicit || sandbox_mode_was_explicit;
 
         let mut model_providers = built_in_model_providers();
-        // Merge user-defined providers into the built-in list.
+        // Merge user-defined providers into the built-in list. Built-in providers only take the
+        // user's price table.
         for (key, provider) in cfg.model_providers.into_iter() {
-            model_providers.entry(key).or_insert(provider);
+            match model_providers.entry(key) {
+                Entry::Occupied(mut entry) => {
+                    if provider.pricing.is_some() {
+                        entry.get_mut().pricing = provider.pricing;
+                    }
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(provider);
+                }
+            }
         }
 
         let model_provider_id = model_provider
@@ -1692,9 +1883,42 @@ impl Config {
             &mut startup_warnings,
         )?;
 
+        let deny_read = cfg.sandbox_deny_read.as_deref().unwrap_or_default();
+        if cfg!(windows) && !deny_read.is_empty() {
+            startup_warnings.push(
+                "`sandbox_deny_read` is not enforced by the Windows sandbox yet; the listed paths stay readable."
+                    .to_string(),
+            );
+        }
+
+        let mut sandbox_profiles =
+            resolve_sandbox_profiles(cfg.sandbox_profiles.as_ref(), deny_read, &resolved_cwd)?;
+        sandbox_profiles.retain(|name, profile| {
+            match constrained_sandbox_policy.value.can_set(&profile.policy) {
+                Ok(()) => true,
+                Err(err) => {
+                    startup_warnings.push(format!("Ignoring sandbox profile `{name}`: {err}"));
+                    false
+                }
+            }
+        });
+
         let mcp_servers = constrain_mcp_servers(cfg.mcp_servers.clone(), mcp_servers.as_ref())
             .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, format!("{e}")))?;
 
+        let tui_keybindings = cfg
+            .tui
+            .as_ref()
+            .map(|t| Keybindings::resolve(&t.keybindings))
+            .transpose()
+            .map_err(|err| {
+                std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("invalid [tui.keybindings]: {err}"),
+                )
+            })?
+            .unwrap_or_default();
+
         let config = Self {
             model,
             review_model,
@@ -1706,11 +1930,17 @@ impl Config {
             startup_warnings,
             approval_policy: constrained_approval_policy.value,
             sandbox_policy: constrained_sandbox_policy.value,
+            sandbox_deny_read: cfg.sandbox_deny_read.clone().unwrap_or_default(),
+            sandbox_resource_limits: cfg.sandbox_resource_limits.unwrap_or_default(),
+            sandbox_profiles,
+            network_proxy: resolve_network_proxy(cfg.network_proxy.as_ref()),
+            web_search_backend: cfg.web_search_backend,
             enforce_residency: enforce_residency.value,
             did_user_set_custom_approval_policy_or_sandbox_mode,
             forced_auto_mode_downgraded_on_windows,
             shell_environment_policy,
             notify: cfg.notify,
+            notify_events: cfg.notify_events,
             user_instructions,
             base_instructions,
             personality,
@@ -1813,6 +2043,14 @@ impl Config {
                 .as_ref()
                 .map(|t| t.alternate_screen)
                 .unwrap_or_default(),
+            tui_keybindings,
+            tui_vim_mode: cfg.tui.as_ref().is_some_and(|t| t.vim_mode),
+            tui_theme: cfg.tui.as_ref().and_then(|t| t.theme.clone()),
+            tui_inline_images: cfg
+                .tui
+                .as_ref()
+                .map(|t| t.inline_images)
+                .unwrap_or_default(),
             otel: {
                 let t: OtelConfigToml = cfg.otel.unwrap_or_default();
                 let log_user_prompt = t.log_user_prompt.unwrap_or(false);
@@ -1945,6 +2183,8 @@ mod tests {
     use crate::config::edit::ConfigEdit;
     use crate::config::edit::ConfigEditsBuilder;
     use crate::config::edit::apply_blocking;
+    use crate::config::keybindings::KeybindingAction;
+    use crate::config::keybindings::KeybindingsToml;
     use crate::config::types::FeedbackConfigToml;
     use crate::config::types::HistoryPersistence;
     use crate::config::types::McpServerTransportConfig;
@@ -1952,6 +2192,7 @@ mod tests {
     use crate::config::types::Notifications;
     use crate::config_loader::RequirementSource;
     use crate::features::Feature;
+    use crate::model_provider_info::ModelPricing;
 
     use super::*;
     use core_test_support::test_absolute_path;
@@ -1979,6 +2220,8 @@ mod tests {
             enabled_tools: None,
             disabled_tools: None,
             scopes: None,
+            default_tool_approval: None,
+            tool_approvals: HashMap::new(),
         }
     }
 
@@ -1998,6 +2241,8 @@ mod tests {
             enabled_tools: None,
             disabled_tools: None,
             scopes: None,
+            default_tool_approval: None,
+            tool_approvals: HashMap::new(),
         }
     }
 
@@ -2052,10 +2297,103 @@ persistence = "none"
                 show_tooltips: true,
                 experimental_mode: None,
                 alternate_screen: AltScreenMode::Auto,
+                status_line: None,
+                keybindings: KeybindingsToml::default(),
+                vim_mode: false,
+                theme: None,
+                inline_images: InlineImages::Auto,
             }
         );
     }
 
+    #[test]
+    fn tui_keybindings_are_resolved_and_validated() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let cfg = toml::from_str::<ConfigToml>(
+            r#"
+[tui.keybindings]
+open_transcript = "ctrl-o"
+newline = ["alt-enter"]
+"#,
+        )
+        .expect("TOML deserialization should succeed");
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )?;
+        assert_eq!(
+            config
+                .tui_keybindings
+                .chords(KeybindingAction::OpenTranscript)
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            vec!["ctrl-o".to_string()]
+        );
+
+        let cfg = toml::from_str::<ConfigToml>(
+            r#"
+[tui.keybindings]
+external_editor = "ctrl-t"
+"#,
+        )
+        .expect("TOML deserialization should succeed");
+        let err = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )
+        .expect_err("conflicting keybindings should be rejected");
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        assert_eq!(
+            err.to_string(),
+            "invalid [tui.keybindings]: `ctrl-t` is bound to both `open_transcript` and `external_editor`"
+        );
+
+        assert!(
+            toml::from_str::<ConfigToml>("[tui.keybindings]\nopen_transscript = \"ctrl-o\"\n")
+                .is_err(),
+            "unknown actions should be rejected"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn model_provider_pricing_applies_to_built_in_providers() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let cfg = toml::from_str::<ConfigToml>(
+            r#"
+[model_providers.openai]
+name = "Ignored"
+base_url = "https://ignored.example.com"
+
+[model_providers.openai.pricing.gpt-5]
+input = 1.25
+cached_input = 0.125
+output = 10.0
+"#,
+        )
+        .expect("TOML deserialization should succeed");
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )?;
+        let openai = &config.model_providers["openai"];
+        assert_eq!(openai.name, "OpenAI");
+        assert_eq!(
+            openai.model_pricing("gpt-5"),
+            Some(&ModelPricing {
+                input: 1.25,
+                cached_input: Some(0.125),
+                output: 10.0,
+            })
+        );
+        assert_eq!(openai.model_pricing("gpt-5-mini"), None);
+        Ok(())
+    }
+
     #[test]
     fn test_sandbox_config_parsing() {
         let sandbox_full_access = r#"
@@ -2102,7 +2440,7 @@ network_access = true  # This should be ignored.
         assert_eq!(
             resolution,
             SandboxPolicyResolution {
-                policy: SandboxPolicy::ReadOnly,
+                policy: SandboxPolicy::new_read_only_policy(),
                 forced_auto_mode_downgraded_on_windows: false,
             }
         );
@@ -2136,7 +2474,7 @@ exclude_slash_tmp = true
             assert_eq!(
                 resolution,
                 SandboxPolicyResolution {
-                    policy: SandboxPolicy::ReadOnly,
+                    policy: SandboxPolicy::new_read_only_policy(),
                     forced_auto_mode_downgraded_on_windows: true,
                 }
             );
@@ -2147,8 +2485,10 @@ exclude_slash_tmp = true
                     policy: SandboxPolicy::WorkspaceWrite {
                         writable_roots: vec![writable_root.clone()],
                         network_access: false,
+                        allowed_loopback_ports: Vec::new(),
                         exclude_tmpdir_env_var: true,
                         exclude_slash_tmp: true,
+                        deny_read: Vec::new(),
                     },
                     forced_auto_mode_downgraded_on_windows: false,
                 }
@@ -2163,6 +2503,7 @@ sandbox_mode = "workspace-write"
 writable_roots = [
     {},
 ]
+allowed_loopback_ports = [5432]
 exclude_tmpdir_env_var = true
 exclude_slash_tmp = true
 
@@ -2186,7 +2527,7 @@ trust_level = "trusted"
             assert_eq!(
                 resolution,
                 SandboxPolicyResolution {
-                    policy: SandboxPolicy::ReadOnly,
+                    policy: SandboxPolicy::new_read_only_policy(),
                     forced_auto_mode_downgraded_on_windows: true,
                 }
             );
@@ -2197,8 +2538,10 @@ trust_level = "trusted"
                     policy: SandboxPolicy::WorkspaceWrite {
                         writable_roots: vec![writable_root],
                         network_access: false,
+                        allowed_loopback_ports: vec![5432],
                         exclude_tmpdir_env_var: true,
                         exclude_slash_tmp: true,
+                        deny_read: Vec::new(),
                     },
                     forced_auto_mode_downgraded_on_windows: false,
                 }
@@ -2368,7 +2711,7 @@ trust_level = "trusted"
                 "expected workspace-write request to be downgraded on Windows"
             );
             match config.sandbox_policy.get() {
-                &SandboxPolicy::ReadOnly => {}
+                &SandboxPolicy::ReadOnly { .. } => {}
                 other => panic!("expected read-only policy on Windows, got {other:?}"),
             }
         } else {
@@ -2513,7 +2856,7 @@ trust_level = "trusted"
 
     #[test]
     fn web_search_mode_for_turn_defaults_to_cached_when_unset() {
-        let mode = resolve_web_search_mode_for_turn(None, &SandboxPolicy::ReadOnly);
+        let mode = resolve_web_search_mode_for_turn(None, &SandboxPolicy::new_read_only_policy());
 
         assert_eq!(mode, WebSearchMode::Cached);
     }
@@ -2673,7 +3016,7 @@ profile = "project"
         if cfg!(target_os = "windows") {
             assert!(matches!(
                 config.sandbox_policy.get(),
-                SandboxPolicy::ReadOnly
+                SandboxPolicy::ReadOnly { .. }
             ));
             assert!(config.forced_auto_mode_downgraded_on_windows);
         } else {
@@ -2866,6 +3209,8 @@ profile = "project"
                 enabled_tools: None,
                 disabled_tools: None,
                 scopes: None,
+                default_tool_approval: None,
+                tool_approvals: HashMap::new(),
             },
         );
 
@@ -3023,6 +3368,8 @@ bearer_token = "secret"
                 enabled_tools: None,
                 disabled_tools: None,
                 scopes: None,
+                default_tool_approval: None,
+                tool_approvals: HashMap::new(),
             },
         )]);
 
@@ -3094,6 +3441,8 @@ ZIG_VAR = "3"
                 enabled_tools: None,
                 disabled_tools: None,
                 scopes: None,
+                default_tool_approval: None,
+                tool_approvals: HashMap::new(),
             },
         )]);
 
@@ -3145,6 +3494,8 @@ ZIG_VAR = "3"
                 enabled_tools: None,
                 disabled_tools: None,
                 scopes: None,
+                default_tool_approval: None,
+                tool_approvals: HashMap::new(),
             },
         )]);
 
@@ -3194,6 +3545,8 @@ ZIG_VAR = "3"
                 enabled_tools: None,
                 disabled_tools: None,
                 scopes: None,
+                default_tool_approval: None,
+                tool_approvals: HashMap::new(),
             },
         )]);
 
@@ -3259,6 +3612,8 @@ startup_timeout_sec = 2.0
                 enabled_tools: None,
                 disabled_tools: None,
                 scopes: None,
+                default_tool_approval: None,
+                tool_approvals: HashMap::new(),
             },
         )]);
         apply_blocking(
@@ -3336,6 +3691,8 @@ X-Auth = "DOCS_AUTH"
                 enabled_tools: None,
                 disabled_tools: None,
                 scopes: None,
+                default_tool_approval: None,
+                tool_approvals: HashMap::new(),
             },
         )]);
 
@@ -3366,6 +3723,8 @@ X-Auth = "DOCS_AUTH"
                 enabled_tools: None,
                 disabled_tools: None,
                 scopes: None,
+                default_tool_approval: None,
+                tool_approvals: HashMap::new(),
             },
         );
         apply_blocking(
@@ -3434,6 +3793,8 @@ url = "https://example.com/mcp"
                     enabled_tools: None,
                     disabled_tools: None,
                     scopes: None,
+                    default_tool_approval: None,
+                    tool_approvals: HashMap::new(),
                 },
             ),
             (
@@ -3454,6 +3815,8 @@ url = "https://example.com/mcp"
                     enabled_tools: None,
                     disabled_tools: None,
                     scopes: None,
+                    default_tool_approval: None,
+                    tool_approvals: HashMap::new(),
                 },
             ),
         ]);
@@ -3537,6 +3900,8 @@ url = "https://example.com/mcp"
                 enabled_tools: None,
                 disabled_tools: None,
                 scopes: None,
+                default_tool_approval: None,
+                tool_approvals: HashMap::new(),
             },
         )]);
 
@@ -3582,6 +3947,8 @@ url = "https://example.com/mcp"
                 enabled_tools: None,
                 disabled_tools: None,
                 scopes: None,
+                default_tool_approval: None,
+                tool_approvals: HashMap::new(),
             },
         )]);
 
@@ -3627,6 +3994,8 @@ url = "https://example.com/mcp"
                 enabled_tools: Some(vec!["allowed".to_string()]),
                 disabled_tools: Some(vec!["blocked".to_string()]),
                 scopes: None,
+                default_tool_approval: None,
+                tool_approvals: HashMap::new(),
             },
         )]);
 
@@ -3939,6 +4308,7 @@ model_verbosity = "high"
             stream_idle_timeout_ms: Some(300_000),
             requires_openai_auth: false,
             supports_websockets: false,
+            pricing: None,
         };
         let model_provider_map = {
             let mut model_provider_map = built_in_model_providers();
@@ -3997,12 +4367,18 @@ model_verbosity = "high"
                 model_provider: fixture.openai_provider.clone(),
                 approval_policy: Constrained::allow_any(AskForApproval::Never),
                 sandbox_policy: Constrained::allow_any(SandboxPolicy::new_read_only_policy()),
+                sandbox_deny_read: Vec::new(),
+                sandbox_resource_limits: ResourceLimits::default(),
+                sandbox_profiles: BTreeMap::new(),
+                network_proxy: NetworkProxySettings::default(),
+                web_search_backend: None,
                 enforce_residency: Constrained::allow_any(None),
                 did_user_set_custom_approval_policy_or_sandbox_mode: true,
                 forced_auto_mode_downgraded_on_windows: false,
                 shell_environment_policy: ShellEnvironmentPolicy::default(),
                 user_instructions: None,
                 notify: None,
+                notify_events: None,
                 cwd: fixture.cwd(),
                 cli_auth_credentials_store_mode: Default::default(),
                 mcp_servers: Constrained::allow_any(HashMap::new()),
@@ -4056,6 +4432,10 @@ model_verbosity = "high"
                 analytics_enabled: Some(true),
                 feedback_enabled: true,
                 tui_alternate_screen: AltScreenMode::Auto,
+                tui_keybindings: Keybindings::default(),
+                tui_vim_mode: false,
+                tui_theme: None,
+                tui_inline_images: InlineImages::Auto,
                 otel: OtelConfig::default(),
             },
             o3_profile_config
@@ -4086,12 +4466,18 @@ model_verbosity = "high"
             model_provider: fixture.openai_custom_provider.clone(),
             approval_policy: Constrained::allow_any(AskForApproval::UnlessTrusted),
             sandbox_policy: Constrained::allow_any(SandboxPolicy::new_read_only_policy()),
+            sandbox_deny_read: Vec::new(),
+            sandbox_resource_limits: ResourceLimits::default(),
+            sandbox_profiles: BTreeMap::new(),
+            network_proxy: NetworkProxySettings::default(),
+            web_search_backend: None,
             enforce_residency: Constrained::allow_any(None),
             did_user_set_custom_approval_policy_or_sandbox_mode: true,
             forced_auto_mode_downgraded_on_windows: false,
             shell_environment_policy: ShellEnvironmentPolicy::default(),
             user_instructions: None,
             notify: None,
+            notify_events: None,
             cwd: fixture.cwd(),
             cli_auth_credentials_store_mode: Default::default(),
             mcp_servers: Constrained::allow_any(HashMap::new()),
@@ -4145,6 +4531,10 @@ model_verbosity = "high"
             analytics_enabled: Some(true),
             feedback_enabled: true,
             tui_alternate_screen: AltScreenMode::Auto,
+            tui_keybindings: Keybindings::default(),
+            tui_vim_mode: false,
+            tui_theme: None,
+            tui_inline_images: InlineImages::Auto,
             otel: OtelConfig::default(),
         };
 
@@ -4190,12 +4580,18 @@ model_verbosity = "high"
             model_provider: fixture.openai_provider.clone(),
             approval_policy: Constrained::allow_any(AskForApproval::OnFailure),
             sandbox_policy: Constrained::allow_any(SandboxPolicy::new_read_only_policy()),
+            sandbox_deny_read: Vec::new(),
+            sandbox_resource_limits: ResourceLimits::default(),
+            sandbox_profiles: BTreeMap::new(),
+            network_proxy: NetworkProxySettings::default(),
+            web_search_backend: None,
             enforce_residency: Constrained::allow_any(None),
             did_user_set_custom_approval_policy_or_sandbox_mode: true,
             forced_auto_mode_downgraded_on_windows: false,
             shell_environment_policy: ShellEnvironmentPolicy::default(),
             user_instructions: None,
             notify: None,
+            notify_events: None,
             cwd: fixture.cwd(),
             cli_auth_credentials_store_mode: Default::default(),
             mcp_servers: Constrained::allow_any(HashMap::new()),
@@ -4249,6 +4645,10 @@ model_verbosity = "high"
             analytics_enabled: Some(false),
             feedback_enabled: true,
             tui_alternate_screen: AltScreenMode::Auto,
+            tui_keybindings: Keybindings::default(),
+            tui_vim_mode: false,
+            tui_theme: None,
+            tui_inline_images: InlineImages::Auto,
             otel: OtelConfig::default(),
         };
 
@@ -4280,12 +4680,18 @@ model_verbosity = "high"
             model_provider: fixture.openai_provider.clone(),
             approval_policy: Constrained::allow_any(AskForApproval::OnFailure),
             sandbox_policy: Constrained::allow_any(SandboxPolicy::new_read_only_policy()),
+            sandbox_deny_read: Vec::new(),
+            sandbox_resource_limits: ResourceLimits::default(),
+            sandbox_profiles: BTreeMap::new(),
+            network_proxy: NetworkProxySettings::default(),
+            web_search_backend: None,
             enforce_residency: Constrained::allow_any(None),
             did_user_set_custom_approval_policy_or_sandbox_mode: true,
             forced_auto_mode_downgraded_on_windows: false,
             shell_environment_policy: ShellEnvironmentPolicy::default(),
             user_instructions: None,
             notify: None,
+            notify_events: None,
             cwd: fixture.cwd(),
             cli_auth_credentials_store_mode: Default::default(),
             mcp_servers: Constrained::allow_any(HashMap::new()),
@@ -4339,6 +4745,10 @@ model_verbosity = "high"
             analytics_enabled: Some(true),
             feedback_enabled: true,
             tui_alternate_screen: AltScreenMode::Auto,
+            tui_keybindings: Keybindings::default(),
+            tui_vim_mode: false,
+            tui_theme: None,
+            tui_inline_images: InlineImages::Auto,
             otel: OtelConfig::default(),
         };
 
@@ -4623,7 +5033,7 @@ trust_level = "untrusted"
         // Verify that untrusted projects get WorkspaceWrite (or ReadOnly on Windows due to downgrade)
         if cfg!(target_os = "windows") {
             assert!(
-                matches!(resolution.policy, SandboxPolicy::ReadOnly),
+                matches!(resolution.policy, SandboxPolicy::ReadOnly { .. }),
                 "Expected ReadOnly on Windows, got {:?}",
                 resolution.policy
             );
@@ -4678,6 +5088,104 @@ trust_level = "untrusted"
         Ok(())
     }
 
+    #[test]
+    fn sandbox_profiles_resolve_against_cwd() -> anyhow::Result<()> {
+        let cfg = toml::from_str::<ConfigToml>(
+            r#"
+sandbox_deny_read = [".env"]
+
+[sandbox_profiles.build]
+mode = "workspace-write"
+writable_roots = ["target", "/opt/cargo/registry"]
+network = "proxy"
+
+[sandbox_profiles.test]
+writable_roots = ["/tmp"]
+"#,
+        )?;
+        let cwd = Path::new("/workspace/repo");
+
+        let profiles = resolve_sandbox_profiles(
+            cfg.sandbox_profiles.as_ref(),
+            cfg.sandbox_deny_read.as_deref().unwrap_or_default(),
+            cwd,
+        )?;
+
+        assert_eq!(
+            profiles,
+            BTreeMap::from([
+                (
+                    "build".to_string(),
+                    SandboxProfile {
+                        policy: SandboxPolicy::WorkspaceWrite {
+                            writable_roots: vec![
+                                AbsolutePathBuf::from_absolute_path("/workspace/repo/target")?,
+                                AbsolutePathBuf::from_absolute_path("/opt/cargo/registry")?,
+                            ],
+                            network_access: false,
+                            allowed_loopback_ports: vec![3128],
+                            exclude_tmpdir_env_var: false,
+                            exclude_slash_tmp: false,
+                            deny_read: vec![".env".to_string()],
+                        },
+                        proxy_url: Some("http://127.0.0.1:3128".to_string()),
+                    },
+                ),
+                (
+                    "test".to_string(),
+                    SandboxProfile {
+                        policy: SandboxPolicy::ReadOnly {
+                            writable_roots: vec![AbsolutePathBuf::from_absolute_path("/tmp")?],
+                            deny_read: vec![".env".to_string()],
+                        },
+                        proxy_url: None,
+                    },
+                ),
+            ])
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn sandbox_profiles_reject_network_in_read_only_mode() -> anyhow::Result<()> {
+        let cfg = toml::from_str::<ConfigToml>(
+            r#"
+[sandbox_profiles.fetch]
+network = "full"
+"#,
+        )?;
+
+        let err = resolve_sandbox_profiles(cfg.sandbox_profiles.as_ref(), &[], Path::new("/"))
+            .expect_err("read-only profiles cannot have network access");
+
+        assert_eq!(
+            err.to_string(),
+            "invalid sandbox profile `fetch`: network access requires mode = \"workspace-write\""
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn sandbox_profiles_reject_non_loopback_proxy() -> anyhow::Result<()> {
+        let cfg = toml::from_str::<ConfigToml>(
+            r#"
+[sandbox_profiles.fetch]
+mode = "workspace-write"
+network = "proxy"
+proxy_url = "http://proxy.internal:3128"
+"#,
+        )?;
+
+        let err = resolve_sandbox_profiles(cfg.sandbox_profiles.as_ref(), &[], Path::new("/"))
+            .expect_err("the sandbox can only reach a loopback proxy");
+
+        assert_eq!(
+            err.to_string(),
+            "invalid sandbox profile `fetch`: proxy_url `http://proxy.internal:3128` must point at a loopback address"
+        );
+        Ok(())
+    }
+
     #[test]
     fn derive_sandbox_policy_preserves_windows_downgrade_for_unsupported_fallback()
     -> anyhow::Result<()> {
@@ -4719,7 +5227,7 @@ trust_level = "untrusted"
             assert_eq!(
                 resolution,
                 SandboxPolicyResolution {
-                    policy: SandboxPolicy::ReadOnly,
+                    policy: SandboxPolicy::new_read_only_policy(),
                     forced_auto_mode_downgraded_on_windows: true,
                 }
             );
@@ -4875,7 +5383,7 @@ mcp_oauth_callback_port = 5678
         // Verify that untrusted projects still get WorkspaceWrite sandbox (or ReadOnly on Windows)
         if cfg!(target_os = "windows") {
             assert!(
-                matches!(config.sandbox_policy.get(), SandboxPolicy::ReadOnly),
+                matches!(config.sandbox_policy.get(), SandboxPolicy::ReadOnly { .. }),
                 "Expected ReadOnly on Windows"
             );
         } else {
@@ -4909,7 +5417,10 @@ mcp_oauth_callback_port = 5678
             .build()
             .await?;
 
-        assert_eq!(*config.sandbox_policy.get(), SandboxPolicy::ReadOnly);
+        assert_eq!(
+            *config.sandbox_policy.get(),
+            SandboxPolicy::new_read_only_policy()
+        );
         Ok(())
     }
 
@@ -4941,7 +5452,10 @@ mcp_oauth_callback_port = 5678
             ))
             .build()
             .await?;
-        assert_eq!(*config.sandbox_policy.get(), SandboxPolicy::ReadOnly);
+        assert_eq!(
+            *config.sandbox_policy.get(),
+            SandboxPolicy::new_read_only_policy()
+        );
         Ok(())
     }
 
@@ -5009,6 +5523,7 @@ mod notifications_tests {
     use crate::config::types::Notifications;
     use assert_matches::assert_matches;
     use serde::Deserialize;
+    use std::collections::BTreeMap;
 
     #[derive(Deserialize, Debug, PartialEq)]
     struct TuiTomlTest {
@@ -5047,6 +5562,23 @@ mod notifications_tests {
         );
     }
 
+    #[test]
+    fn test_tui_notifications_per_event_table() {
+        let toml = r#"
+            [tui.notifications]
+            approval-requested = true
+            command-finished = false
+        "#;
+        let parsed: RootTomlTest = toml::from_str(toml).expect("deserialize notifications table");
+        assert_eq!(
+            parsed.tui.notifications,
+            Notifications::Events(BTreeMap::from([
+                ("approval-requested".to_string(), true),
+                ("command-finished".to_string(), false),
+            ]))
+        );
+    }
+
     #[test]
     fn test_tui_notification_method() {
         let toml = r#"
@@ -3,16 +3,20 @@
 // Note this file should generally be restricted to simple struct/enum
 // definitions that do not contain business logic.
 
+use crate::config::keybindings::KeybindingsToml;
 use crate::config_loader::RequirementSource;
 pub use codex_protocol::config_types::AltScreenMode;
 pub use codex_protocol::config_types::ModeKind;
 pub use codex_protocol::config_types::Personality;
+use codex_protocol::config_types::SandboxMode;
 pub use codex_protocol::config_types::WebSearchMode;
+use codex_protocol::protocol::SandboxPolicy;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::fmt;
 use std::path::PathBuf;
+use std::str::FromStr;
 use std::time::Duration;
 use wildmatch::WildMatchPattern;
 
@@ -81,6 +85,49 @@ pub struct McpServerConfig {
     /// Optional OAuth scopes to request during MCP login.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub scopes: Option<Vec<String>>,
+
+    /// Approval policy for tools on this server that have no entry in
+    /// `tool_approvals`. When unset, Codex falls back to the tool's own
+    /// annotations.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_tool_approval: Option<McpToolApproval>,
+
+    /// Per-tool approval policies, keyed by the tool name reported by the server.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub tool_approvals: HashMap<String, McpToolApproval>,
+}
+
+/// When Codex asks the user before calling an MCP tool.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum McpToolApproval {
+    /// Ask before every call.
+    Always,
+    /// Never ask.
+    Never,
+    /// Ask until the user allows the tool for the rest of the session.
+    OnFirstUse,
+    /// Ask only when one of the predicates matches the call arguments.
+    OnArgumentMatch(Vec<McpArgumentPredicate>),
+}
+
+/// Predicate over MCP tool-call arguments. With no condition set it matches
+/// whenever `pointer` resolves; otherwise every condition set must hold.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema)]
+#[serde(deny_unknown_fields)]
+#[schemars(deny_unknown_fields)]
+pub struct McpArgumentPredicate {
+    /// JSON pointer (RFC 6901) into the arguments, e.g. `/project/key`.
+    pub pointer: String,
+    /// The value equals this one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub equals: Option<serde_json::Value>,
+    /// The value equals one of these.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub one_of: Option<Vec<serde_json::Value>>,
+    /// The value is a string containing this substring.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contains: Option<String>,
 }
 
 // Raw MCP config shape used for deserialization and JSON Schema generation.
@@ -128,6 +175,10 @@ pub(crate) struct RawMcpServerConfig {
     pub disabled_tools: Option<Vec<String>>,
     #[serde(default)]
     pub scopes: Option<Vec<String>>,
+    #[serde(default)]
+    pub default_tool_approval: Option<McpToolApproval>,
+    #[serde(default)]
+    pub tool_approvals: Option<HashMap<String, McpToolApproval>>,
 }
 
 impl<'de> Deserialize<'de> for McpServerConfig {
@@ -151,6 +202,8 @@ impl<'de> Deserialize<'de> for McpServerConfig {
         let enabled_tools = raw.enabled_tools.clone();
         let disabled_tools = raw.disabled_tools.clone();
         let scopes = raw.scopes.clone();
+        let default_tool_approval = raw.default_tool_approval.take();
+        let tool_approvals = raw.tool_approvals.take().unwrap_or_default();
 
         fn throw_if_set<E, T>(transport: &str, field: &str, value: Option<&T>) -> Result<(), E>
         where
@@ -226,6 +279,8 @@ impl<'de> Deserialize<'de> for McpServerConfig {
             enabled_tools,
             disabled_tools,
             scopes,
+            default_tool_approval,
+            tool_approvals,
         })
     }
 }
@@ -506,7 +561,10 @@ impl Default for OtelConfig {
 #[serde(untagged)]
 pub enum Notifications {
     Enabled(bool),
+    /// Only the listed notification types.
     Custom(Vec<String>),
+    /// Per-type toggles; types left out stay enabled.
+    Events(BTreeMap<String, bool>),
 }
 
 impl Default for Notifications {
@@ -515,12 +573,28 @@ impl Default for Notifications {
     }
 }
 
+impl Notifications {
+    /// Whether notifications of `event_type` (e.g. `agent-turn-complete`) are enabled.
+    /// `default` applies to types a per-type table leaves out.
+    pub fn allows(&self, event_type: &str, default: bool) -> bool {
+        match self {
+            Notifications::Enabled(enabled) => *enabled,
+            Notifications::Custom(allowed) => allowed.iter().any(|a| a == event_type),
+            Notifications::Events(toggles) => toggles.get(event_type).copied().unwrap_or(default),
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema, Default)]
 #[serde(rename_all = "lowercase")]
 pub enum NotificationMethod {
     #[default]
     Auto,
     Osc9,
+    /// `OSC 777;notify`, which carries a title and a body (rxvt-unicode, foot, Ghostty, VTE).
+    Osc777,
+    /// `org.freedesktop.Notifications` over the D-Bus session bus (Linux desktops).
+    Dbus,
     Bel,
 }
 
@@ -529,11 +603,36 @@ impl fmt::Display for NotificationMethod {
         match self {
             NotificationMethod::Auto => write!(f, "auto"),
             NotificationMethod::Osc9 => write!(f, "osc9"),
+            NotificationMethod::Osc777 => write!(f, "osc777"),
+            NotificationMethod::Dbus => write!(f, "dbus"),
             NotificationMethod::Bel => write!(f, "bel"),
         }
     }
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum InlineImages {
+    #[default]
+    Auto,
+    Kitty,
+    Iterm2,
+    Sixel,
+    Off,
+}
+
+impl fmt::Display for InlineImages {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InlineImages::Auto => write!(f, "auto"),
+            InlineImages::Kitty => write!(f, "kitty"),
+            InlineImages::Iterm2 => write!(f, "iterm2"),
+            InlineImages::Sixel => write!(f, "sixel"),
+            InlineImages::Off => write!(f, "off"),
+        }
+    }
+}
+
 /// Collection of settings that are specific to the TUI.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema)]
 #[schemars(deny_unknown_fields)]
@@ -579,6 +678,28 @@ pub struct Tui {
     /// When set, the TUI renders the selected items as the status line.
     #[serde(default)]
     pub status_line: Option<Vec<String>>,
+
+    /// Key chords for named TUI actions, e.g. `open_transcript = "ctrl-o"`.
+    /// Conflicting chords are rejected when the config is loaded.
+    #[serde(default)]
+    pub keybindings: KeybindingsToml,
+
+    /// Enable vi-style modal editing (normal/insert/visual) in the chat composer.
+    /// Defaults to `false`.
+    #[serde(default)]
+    pub vim_mode: bool,
+
+    /// Color theme: `dark`, `light`, `high-contrast`, `colorblind`, or the file stem of
+    /// a theme in `$CODEX_HOME/themes/`. Defaults to `dark` or `light` based on the
+    /// detected terminal background.
+    #[serde(default)]
+    pub theme: Option<String>,
+
+    /// Graphics protocol for image thumbnails in the transcript: `auto`, `kitty`, `iterm2`,
+    /// `sixel` or `off`. `auto` picks one from the terminal's environment and falls back to
+    /// text. Defaults to `auto`.
+    #[serde(default)]
+    pub inline_images: InlineImages,
 }
 
 const fn default_true() -> bool {
@@ -633,6 +754,8 @@ pub struct SandboxWorkspaceWrite {
     #[serde(default)]
     pub network_access: bool,
     #[serde(default)]
+    pub allowed_loopback_ports: Vec<u16>,
+    #[serde(default)]
     pub exclude_tmpdir_env_var: bool,
     #[serde(default)]
     pub exclude_slash_tmp: bool,
@@ -649,6 +772,156 @@ impl From<SandboxWorkspaceWrite> for codex_app_server_protocol::SandboxSettings
     }
 }
 
+/// Per-command resource caps for commands Codex runs on the model's behalf.
+/// Unset fields are unlimited.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct ResourceLimits {
+    /// Memory cap in MiB: the cgroup `memory.max` where available, otherwise
+    /// the per-process address space (`RLIMIT_AS`).
+    pub memory_mb: Option<u64>,
+    /// CPU time per process, in seconds (`RLIMIT_CPU`).
+    pub cpu_time_secs: Option<u64>,
+    /// Maximum number of processes: the cgroup `pids.max` where available,
+    /// otherwise `RLIMIT_NPROC`, which counts all of the user's processes.
+    pub max_processes: Option<u64>,
+    /// Largest file a command may write, in MiB (`RLIMIT_FSIZE`).
+    pub max_file_size_mb: Option<u64>,
+    /// Stop the command once stdout and stderr together exceed this many bytes.
+    pub max_output_bytes: Option<u64>,
+}
+
+impl ResourceLimits {
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+impl FromStr for ResourceLimits {
+    type Err = serde_json::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_json::from_str(s)
+    }
+}
+
+/// Network preset for a sandbox profile.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum SandboxProfileNetwork {
+    /// No outbound network access.
+    #[default]
+    None,
+    /// No direct network access: only the loopback port of
+    /// `codex-network-proxy` is reachable, and `HTTP_PROXY`/`HTTPS_PROXY`
+    /// point at it. Its `[network_proxy.policy]` decides which domains are
+    /// reachable.
+    Proxy,
+    /// Unrestricted network access.
+    Full,
+}
+
+/// A named sandbox profile from `[sandbox_profiles.<name>]`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct SandboxProfileToml {
+    /// Base sandbox mode. Defaults to `read-only`.
+    pub mode: Option<SandboxMode>,
+    /// Folders that stay writable under this profile. `~/` expands to the
+    /// home directory and relative paths resolve against the cwd.
+    #[serde(default)]
+    pub writable_roots: Vec<String>,
+    #[serde(default)]
+    pub network: SandboxProfileNetwork,
+    /// Proxy used when `network = "proxy"`; must listen on loopback.
+    /// Defaults to `http://127.0.0.1:3128`, the `codex-network-proxy` default.
+    pub proxy_url: Option<String>,
+}
+
+/// A sandbox profile resolved against the session cwd.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SandboxProfile {
+    pub policy: SandboxPolicy,
+    /// Set when `network = "proxy"`.
+    pub proxy_url: Option<String>,
+}
+
+/// The parts of the `[network_proxy]` table (owned by `codex-network-proxy`)
+/// that client-side network tools such as `web_fetch` honor. Other keys in
+/// the table are accepted and left to the proxy.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema)]
+pub struct NetworkProxyToml {
+    /// Route client-side requests through `proxy_url`.
+    pub enabled: Option<bool>,
+    /// Defaults to `http://127.0.0.1:3128`.
+    pub proxy_url: Option<String>,
+    pub policy: Option<NetworkProxyPolicyToml>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema)]
+pub struct NetworkProxyPolicyToml {
+    /// Domains fetched without asking: `example.com` (exact),
+    /// `*.example.com` (subdomains only), `**.example.com` (apex and
+    /// subdomains) or `*` (any host).
+    #[serde(default)]
+    pub allowed_domains: Vec<String>,
+    /// Domains that are never fetched. Takes precedence over
+    /// `allowed_domains`.
+    #[serde(default)]
+    pub denied_domains: Vec<String>,
+    /// Allow hosts that resolve to loopback or private addresses. When off,
+    /// IP literals are reachable only if `allowed_domains` names them exactly,
+    /// and hostnames that resolve to such addresses are never fetched.
+    #[serde(default)]
+    pub allow_local_binding: bool,
+}
+
+/// Search backend for the client-side `web_search` tool, used instead of the
+/// hosted tool when the model provider is not OpenAI.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[schemars(deny_unknown_fields)]
+pub enum WebSearchBackendToml {
+    /// A SearxNG instance with the `json` output format enabled.
+    Searxng {
+        /// Base URL of the instance, e.g. `http://127.0.0.1:8888`.
+        url: String,
+        /// Maximum number of results returned to the model. Defaults to 8.
+        max_results: Option<usize>,
+    },
+    /// Any HTTP search API that answers a GET request with JSON.
+    Http {
+        /// Request URL. `{query}` is replaced with the URL-encoded query and
+        /// `{max_results}` with `max_results`.
+        url: String,
+        /// Maximum number of results returned to the model. Defaults to 8.
+        max_results: Option<usize>,
+        /// Header name to value.
+        http_headers: Option<HashMap<String, String>>,
+        /// Header name to the environment variable holding its value, for
+        /// API keys that should stay out of config.toml.
+        env_http_headers: Option<HashMap<String, String>>,
+        /// JSON pointer to the array of results. Defaults to `/results`.
+        results_pointer: Option<String>,
+        /// Result field with the title. Defaults to `title`.
+        title_field: Option<String>,
+        /// Result field with the URL. Defaults to `url`.
+        url_field: Option<String>,
+        /// Result field with the snippet. Defaults to `snippet`.
+        snippet_field: Option<String>,
+    },
+}
+
+/// `[network_proxy]` resolved for client-side network tools.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct NetworkProxySettings {
+    /// Set when `enabled = true`.
+    pub proxy_url: Option<String>,
+    pub allowed_domains: Vec<String>,
+    pub denied_domains: Vec<String>,
+    pub allow_local_binding: bool,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema)]
 #[serde(rename_all = "kebab-case")]
 pub enum ShellEnvironmentPolicyInherit {
@@ -397,7 +397,7 @@ allowed_sandbox_modes = ["read-only"]
     );
     assert_eq!(
         *state.requirements().sandbox_policy.get(),
-        SandboxPolicy::ReadOnly
+        SandboxPolicy::new_read_only_policy()
     );
     assert!(
         state
@@ -413,8 +413,10 @@ allowed_sandbox_modes = ["read-only"]
             .can_set(&SandboxPolicy::WorkspaceWrite {
                 writable_roots: Vec::new(),
                 network_access: false,
+                allowed_loopback_ports: Vec::new(),
                 exclude_tmpdir_env_var: false,
                 exclude_slash_tmp: false,
+                deny_read: Vec::new(),
             })
             .is_err()
     );
@@ -1,4 +1,5 @@
 use codex_protocol::custom_prompts::CustomPrompt;
+use std::collections::HashMap;
 use std::collections::HashSet;
 use std::path::Path;
 use std::path::PathBuf;
@@ -75,6 +76,175 @@ pub async fn discover_prompts_in_excluding(
     out
 }
 
+/// Name of the aggregate positional placeholder, `$ARGUMENTS`.
+pub const PROMPT_ARGUMENTS_PLACEHOLDER: &str = "ARGUMENTS";
+
+/// A positional placeholder in a prompt that has no named placeholders.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PositionalPlaceholder {
+    /// `$1..$9`, zero-based.
+    Index(usize),
+    /// `$ARGUMENTS`: every positional argument joined by single spaces.
+    All,
+}
+
+/// Extracts the unique placeholder variable names from a prompt template.
+///
+/// A placeholder is any token that matches the pattern `$[A-Z][A-Z0-9_]*`
+/// (for example `$USER`); `$$USER` is an escape and `$ARGUMENTS` is positional.
+/// The function returns the variable names without the leading `$`,
+/// de-duplicated and in the order of first appearance.
+pub fn prompt_argument_names(content: &str) -> Vec<String> {
+    let mut seen = HashSet::new();
+    let mut names = Vec::new();
+    for (start, end) in named_placeholders(content) {
+        let name = &content[start + 1..end];
+        if name != PROMPT_ARGUMENTS_PLACEHOLDER && seen.insert(name) {
+            names.push(name.to_string());
+        }
+    }
+    names
+}
+
+/// Detect whether `content` contains numeric placeholders ($1..$9) or `$ARGUMENTS`.
+pub fn prompt_has_numeric_placeholders(content: &str) -> bool {
+    content.contains("$ARGUMENTS")
+        || content
+            .as_bytes()
+            .windows(2)
+            .any(|pair| pair[0] == b'$' && (b'1'..=b'9').contains(&pair[1]))
+}
+
+/// Replaces each `$NAME` placeholder for which `expand` writes a value into the output.
+///
+/// `expand` receives the output buffer and the name without `$`, and returns `false` to
+/// keep the placeholder verbatim. Escaped `$$NAME` placeholders are kept as they are.
+pub fn expand_named_placeholders(
+    content: &str,
+    mut expand: impl FnMut(&mut String, &str) -> bool,
+) -> String {
+    let mut out = String::with_capacity(content.len());
+    let mut cursor = 0;
+    for (start, end) in named_placeholders(content) {
+        out.push_str(&content[cursor..start]);
+        cursor = end;
+        if !expand(&mut out, &content[start + 1..end]) {
+            out.push_str(&content[start..end]);
+        }
+    }
+    out.push_str(&content[cursor..]);
+    out
+}
+
+/// Replaces `$1..$9` and `$ARGUMENTS` through `expand`, which writes the value (if any) into
+/// the output. `$$` is kept as an escape and any other `$` is copied through.
+pub fn expand_positional_placeholders(
+    content: &str,
+    mut expand: impl FnMut(&mut String, PositionalPlaceholder),
+) -> String {
+    let mut out = String::with_capacity(content.len());
+    let mut i = 0;
+    while let Some(off) = content[i..].find('$') {
+        let j = i + off;
+        out.push_str(&content[i..j]);
+        let rest = &content[j + 1..];
+        match rest.as_bytes().first() {
+            Some(b'$') => {
+                out.push_str("$$");
+                i = j + 2;
+            }
+            Some(digit @ b'1'..=b'9') => {
+                expand(
+                    &mut out,
+                    PositionalPlaceholder::Index(usize::from(digit - b'1')),
+                );
+                i = j + 2;
+            }
+            _ if rest.starts_with(PROMPT_ARGUMENTS_PLACEHOLDER) => {
+                expand(&mut out, PositionalPlaceholder::All);
+                i = j + 1 + PROMPT_ARGUMENTS_PLACEHOLDER.len();
+            }
+            _ => {
+                out.push('$');
+                i = j + 1;
+            }
+        }
+    }
+    out.push_str(&content[i..]);
+    out
+}
+
+/// Expands a custom prompt body the way `/prompts:<name>` does in the TUI.
+///
+/// Prompts with named placeholders take `named` values only; the names returned in the
+/// error are the ones missing from `named`. Other prompts expand `$1..$9` and `$ARGUMENTS`
+/// from `positional`.
+pub fn expand_prompt_arguments(
+    content: &str,
+    named: &HashMap<String, String>,
+    positional: &[String],
+) -> Result<String, Vec<String>> {
+    let required = prompt_argument_names(content);
+    if !required.is_empty() {
+        let missing: Vec<String> = required
+            .into_iter()
+            .filter(|name| !named.contains_key(name))
+            .collect();
+        if !missing.is_empty() {
+            return Err(missing);
+        }
+        return Ok(expand_named_placeholders(content, |out, name| {
+            named.get(name).map(|value| out.push_str(value)).is_some()
+        }));
+    }
+    Ok(expand_positional_placeholders(
+        content,
+        |out, placeholder| match placeholder {
+            PositionalPlaceholder::Index(index) => {
+                if let Some(arg) = positional.get(index) {
+                    out.push_str(arg);
+                }
+            }
+            PositionalPlaceholder::All => out.push_str(&positional.join(" ")),
+        },
+    ))
+}
+
+/// Byte ranges of the unescaped `$[A-Z][A-Z0-9_]*` placeholders in `content`, including `$`.
+fn named_placeholders(content: &str) -> Vec<(usize, usize)> {
+    let bytes = content.as_bytes();
+    let mut ranges = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] != b'$' {
+            i += 1;
+            continue;
+        }
+        if bytes.get(i + 1) == Some(&b'$') {
+            // `$$NAME` is an escape: skip the whole placeholder.
+            i += 2;
+            while i < bytes.len() && is_placeholder_byte(bytes[i]) {
+                i += 1;
+            }
+            continue;
+        }
+        let start = i;
+        i += 1;
+        if !bytes.get(i).is_some_and(u8::is_ascii_uppercase) {
+            continue;
+        }
+        while i < bytes.len() && is_placeholder_byte(bytes[i]) {
+            i += 1;
+        }
+        ranges.push((start, i));
+    }
+    ranges
+}
+
+fn is_placeholder_byte(byte: u8) -> bool {
+    byte.is_ascii_uppercase() || byte.is_ascii_digit() || byte == b'_'
+}
+
 /// Parse optional YAML-like frontmatter at the beginning of `content`.
 /// Supported keys:
 /// - `description`: short description shown in the slash popup
@@ -241,4 +411,33 @@ mod tests {
         assert_eq!(hint.as_deref(), Some("[arg]"));
         assert_eq!(body, "First line\r\nSecond line\r\n");
     }
+
+    #[test]
+    fn argument_names_skip_escapes_and_positional() {
+        let names = prompt_argument_names("Review $FILE for $USER_1 ($$HOME) $ARGUMENTS $FILE");
+        assert_eq!(names, vec!["FILE".to_string(), "USER_1".to_string()]);
+    }
+
+    #[test]
+    fn expands_named_or_positional_arguments() {
+        let named = HashMap::from([("FILE".to_string(), "src/lib.rs".to_string())]);
+        assert_eq!(
+            expand_prompt_arguments("Check $FILE ($$FILE) and $OTHER_X", &named, &[]),
+            Err(vec!["OTHER_X".to_string()])
+        );
+        assert_eq!(
+            expand_prompt_arguments("Check $FILE ($$FILE) with $1", &named, &[]),
+            Ok("Check src/lib.rs ($$FILE) with $1".to_string())
+        );
+
+        let positional = vec!["first".to_string(), "second arg".to_string()];
+        assert_eq!(
+            expand_prompt_arguments(
+                "Use $1 and $2; all: $ARGUMENTS; missing $3; cost $$5 $",
+                &HashMap::new(),
+                &positional,
+            ),
+            Ok("Use first and second arg; all: first second arg; missing ; cost $$5 $".to_string())
+        );
+    }
 }
@@ -1,4 +1,5 @@
 use crate::exec::ExecToolCallOutput;
+use crate::resource_limits::ResourceLimitKind;
 use crate::token_data::KnownPlan;
 use crate::token_data::PlanType;
 use crate::truncate::TruncationPolicy;
@@ -47,6 +48,13 @@ pub enum SandboxErr {
     #[error("command timed out")]
     Timeout { output: Box<ExecToolCallOutput> },
 
+    /// Command was stopped by one of the configured per-command resource limits
+    #[error("command exceeded its {kind} limit")]
+    ResourceLimit {
+        kind: ResourceLimitKind,
+        output: Box<ExecToolCallOutput>,
+    },
+
     /// Command was killed by a signal
     #[error("command was killed by a signal")]
     Signal(i32),
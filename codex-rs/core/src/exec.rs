@@ -6,6 +6,9 @@ use std::io;
 use std::path::Path;
 use std::path::PathBuf;
 use std::process::ExitStatus;
+use std::sync::Arc;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
 use std::time::Duration;
 use std::time::Instant;
 
@@ -15,7 +18,9 @@ use tokio::io::AsyncReadExt;
 use tokio::io::BufReader;
 use tokio::process::Child;
 use tokio_util::sync::CancellationToken;
+use tracing::Instrument;
 
+use crate::config::types::ResourceLimits;
 use crate::error::CodexErr;
 use crate::error::Result;
 use crate::error::SandboxErr;
@@ -25,6 +30,7 @@ use crate::protocol::EventMsg;
 use crate::protocol::ExecCommandOutputDeltaEvent;
 use crate::protocol::ExecOutputStream;
 use crate::protocol::SandboxPolicy;
+use crate::resource_limits::ResourceLimitKind;
 use crate::sandboxing::CommandSpec;
 use crate::sandboxing::ExecEnv;
 use crate::sandboxing::SandboxManager;
@@ -32,6 +38,9 @@ use crate::sandboxing::SandboxPermissions;
 use crate::spawn::StdioPolicy;
 use crate::spawn::spawn_child_async;
 use crate::text_encoding::bytes_to_string_smart;
+use codex_otel::traces::spans::exec_span;
+use codex_otel::traces::spans::record_exec_result;
+use codex_otel::traces::spans::trace_context_env;
 use codex_utils_pty::process_group::kill_child_process_group;
 
 pub const DEFAULT_EXEC_COMMAND_TIMEOUT_MS: u64 = 10_000;
@@ -204,6 +213,7 @@ pub async fn process_exec_tool_call(
             codex_linux_sandbox_exe: codex_linux_sandbox_exe.as_ref(),
             use_linux_sandbox_bwrap,
             windows_sandbox_level,
+            resource_limits: ResourceLimits::default(),
         })
         .map_err(CodexErr::from)?;
 
@@ -219,15 +229,20 @@ pub(crate) async fn execute_exec_env(
     let ExecEnv {
         command,
         cwd,
-        env,
+        mut env,
         expiration,
         sandbox,
         windows_sandbox_level,
         sandbox_permissions,
         justification,
         arg0,
+        resource_limits,
     } = env;
 
+    // Export the span's trace context so traced child processes join the trace.
+    let span = exec_span(command.first().map(String::as_str).unwrap_or_default());
+    env.extend(trace_context_env(&span));
+
     let params = ExecParams {
         command,
         cwd,
@@ -240,9 +255,30 @@ pub(crate) async fn execute_exec_env(
     };
 
     let start = Instant::now();
-    let raw_output_result = exec(params, sandbox, sandbox_policy, stdout_stream).await;
+    let raw_output_result = exec(
+        params,
+        sandbox,
+        sandbox_policy,
+        &resource_limits,
+        stdout_stream,
+    )
+    .instrument(span.clone())
+    .await;
     let duration = start.elapsed();
-    finalize_exec_result(raw_output_result, sandbox, duration)
+    let result = finalize_exec_result(raw_output_result, sandbox, &resource_limits, duration);
+    let output = match &result {
+        Ok(output) => Some(output),
+        Err(CodexErr::Sandbox(
+            SandboxErr::Timeout { output }
+            | SandboxErr::Denied { output }
+            | SandboxErr::ResourceLimit { output, .. },
+        )) => Some(output.as_ref()),
+        Err(_) => None,
+    };
+    if let Some(output) = output {
+        record_exec_result(&span, output.exit_code, output.timed_out, duration);
+    }
+    result
 }
 
 #[cfg(target_os = "windows")]
@@ -416,26 +452,35 @@ async fn exec_windows_sandbox(
         stderr,
         aggregated_output,
         timed_out: capture.timed_out,
+        resource_limit: None,
     })
 }
 
+#[cfg_attr(not(unix), allow(unused_variables))]
 fn finalize_exec_result(
     raw_output_result: std::result::Result<RawExecToolCallOutput, CodexErr>,
     sandbox_type: SandboxType,
+    resource_limits: &ResourceLimits,
     duration: Duration,
 ) -> Result<ExecToolCallOutput> {
     match raw_output_result {
         Ok(raw_output) => {
             #[allow(unused_mut)]
             let mut timed_out = raw_output.timed_out;
+            #[allow(unused_mut)]
+            let mut resource_limit = raw_output.resource_limit;
 
             #[cfg(target_family = "unix")]
             {
                 if let Some(signal) = raw_output.exit_status.signal() {
                     if signal == TIMEOUT_CODE {
                         timed_out = true;
-                    } else {
-                        return Err(CodexErr::Sandbox(SandboxErr::Signal(signal)));
+                    } else if resource_limit.is_none() {
+                        resource_limit =
+                            crate::resource_limits::limit_for_signal(resource_limits, signal);
+                        if resource_limit.is_none() {
+                            return Err(CodexErr::Sandbox(SandboxErr::Signal(signal)));
+                        }
                     }
                 }
             }
@@ -445,6 +490,15 @@ fn finalize_exec_result(
                 exit_code = EXEC_TIMEOUT_EXIT_CODE;
             }
 
+            // Shells report a child killed by a signal as `128 + signal`.
+            #[cfg(target_family = "unix")]
+            if resource_limit.is_none() && exit_code > EXIT_CODE_SIGNAL_BASE {
+                resource_limit = crate::resource_limits::limit_for_signal(
+                    resource_limits,
+                    exit_code - EXIT_CODE_SIGNAL_BASE,
+                );
+            }
+
             let stdout = raw_output.stdout.from_utf8_lossy();
             let stderr = raw_output.stderr.from_utf8_lossy();
             let aggregated_output = raw_output.aggregated_output.from_utf8_lossy();
@@ -463,6 +517,13 @@ fn finalize_exec_result(
                 }));
             }
 
+            if let Some(kind) = resource_limit {
+                return Err(CodexErr::Sandbox(SandboxErr::ResourceLimit {
+                    kind,
+                    output: Box::new(exec_output),
+                }));
+            }
+
             if is_likely_sandbox_denied(sandbox_type, &exec_output) {
                 return Err(CodexErr::Sandbox(SandboxErr::Denied {
                     output: Box::new(exec_output),
@@ -572,6 +633,8 @@ struct RawExecToolCallOutput {
     pub stderr: StreamOutput<Vec<u8>>,
     pub aggregated_output: StreamOutput<Vec<u8>>,
     pub timed_out: bool,
+    /// Limit the command was stopped for, when known before exit-status checks.
+    pub resource_limit: Option<ResourceLimitKind>,
 }
 
 impl StreamOutput<String> {
@@ -663,6 +726,7 @@ async fn exec(
     params: ExecParams,
     sandbox: SandboxType,
     sandbox_policy: &SandboxPolicy,
+    resource_limits: &ResourceLimits,
     stdout_stream: Option<StdoutStream>,
 ) -> Result<RawExecToolCallOutput> {
     #[cfg(target_os = "windows")]
@@ -691,17 +755,64 @@ async fn exec(
         ))
     })?;
     let arg0_ref = arg0.as_deref();
+    // `codex-linux-sandbox` applies the limits itself, right before it execs
+    // the command, so they must not bind the helper or bubblewrap.
+    let spawn_limits = if sandbox == SandboxType::LinuxSeccomp {
+        ResourceLimits::default()
+    } else {
+        *resource_limits
+    };
     let child = spawn_child_async(
         PathBuf::from(program),
         args.into(),
         arg0_ref,
         cwd,
         sandbox_policy,
+        &spawn_limits,
         StdioPolicy::RedirectForShellTool,
         env,
     )
     .await?;
-    consume_truncated_output(child, expiration, stdout_stream).await
+    #[cfg(target_os = "linux")]
+    let pid = child.id();
+    #[allow(unused_mut)]
+    let mut raw_output = consume_truncated_output(
+        child,
+        expiration,
+        resource_limits.max_output_bytes,
+        stdout_stream,
+    )
+    .await?;
+
+    // `codex-linux-sandbox` runs the command in its own cgroup when memory or
+    // process limits are set; its event counters say which one was hit.
+    #[cfg(target_os = "linux")]
+    if sandbox == SandboxType::LinuxSeccomp
+        && let Some(pid) = pid
+        && let Some(kind) = crate::resource_limits::collect_exec_cgroup(pid)
+        && !raw_output.exit_status.success()
+    {
+        raw_output.resource_limit = raw_output.resource_limit.or(Some(kind));
+    }
+    Ok(raw_output)
+}
+
+/// Shared stdout/stderr byte counter that trips `exceeded` once the command
+/// has written more than `max_bytes` in total.
+#[derive(Clone)]
+struct OutputLimit {
+    max_bytes: u64,
+    total: Arc<AtomicU64>,
+    exceeded: CancellationToken,
+}
+
+impl OutputLimit {
+    fn record(&self, bytes: usize) {
+        let total = self.total.fetch_add(bytes as u64, Ordering::Relaxed) + bytes as u64;
+        if total > self.max_bytes {
+            self.exceeded.cancel();
+        }
+    }
 }
 
 /// Consumes the output of a child process, truncating it so it is suitable for
@@ -709,6 +820,7 @@ async fn exec(
 async fn consume_truncated_output(
     mut child: Child,
     expiration: ExecExpiration,
+    max_output_bytes: Option<u64>,
     stdout_stream: Option<StdoutStream>,
 ) -> Result<RawExecToolCallOutput> {
     // Both stdout and stderr were configured with `Stdio::piped()`
@@ -726,22 +838,39 @@ async fn consume_truncated_output(
         ))
     })?;
 
+    // Without a limit the token is never cancelled.
+    let output_exceeded = CancellationToken::new();
+    let output_limit = max_output_bytes.map(|max_bytes| OutputLimit {
+        max_bytes,
+        total: Arc::new(AtomicU64::new(0)),
+        exceeded: output_exceeded.clone(),
+    });
+
     let stdout_handle = tokio::spawn(read_capped(
         BufReader::new(stdout_reader),
         stdout_stream.clone(),
         false,
+        output_limit.clone(),
     ));
     let stderr_handle = tokio::spawn(read_capped(
         BufReader::new(stderr_reader),
         stdout_stream.clone(),
         true,
+        output_limit,
     ));
 
+    let mut resource_limit = None;
     let (exit_status, timed_out) = tokio::select! {
         status_result = child.wait() => {
             let exit_status = status_result?;
             (exit_status, false)
         }
+        _ = output_exceeded.cancelled() => {
+            kill_child_process_group(&mut child)?;
+            child.start_kill()?;
+            resource_limit = Some(ResourceLimitKind::Output);
+            (synthetic_exit_status(EXIT_CODE_SIGNAL_BASE + SIGKILL_CODE), false)
+        }
         _ = expiration.wait() => {
             kill_child_process_group(&mut child)?;
             child.start_kill()?;
@@ -808,6 +937,7 @@ async fn consume_truncated_output(
         stderr,
         aggregated_output,
         timed_out,
+        resource_limit,
     })
 }
 
@@ -815,6 +945,7 @@ async fn read_capped<R: AsyncRead + Unpin + Send + 'static>(
     mut reader: R,
     stream: Option<StdoutStream>,
     is_stderr: bool,
+    output_limit: Option<OutputLimit>,
 ) -> io::Result<StreamOutput<Vec<u8>>> {
     let mut buf = Vec::with_capacity(AGGREGATE_BUFFER_INITIAL_CAPACITY.min(EXEC_OUTPUT_MAX_BYTES));
     let mut tmp = [0u8; READ_CHUNK_SIZE];
@@ -848,6 +979,9 @@ async fn read_capped<R: AsyncRead + Unpin + Send + 'static>(
             emitted_deltas += 1;
         }
 
+        if let Some(output_limit) = &output_limit {
+            output_limit.record(n);
+        }
         append_capped(&mut buf, &tmp[..n], EXEC_OUTPUT_MAX_BYTES);
         // Continue reading to EOF to avoid back-pressure
     }
@@ -947,7 +1081,7 @@ mod tests {
             writer.write_all(&bytes).await.expect("write");
         });
 
-        let out = read_capped(reader, None, false).await.expect("read");
+        let out = read_capped(reader, None, false, None).await.expect("read");
         assert_eq!(out.text.len(), EXEC_OUTPUT_MAX_BYTES);
     }
 
@@ -1067,7 +1201,14 @@ mod tests {
             arg0: None,
         };
 
-        let output = exec(params, SandboxType::None, &SandboxPolicy::ReadOnly, None).await?;
+        let output = exec(
+            params,
+            SandboxType::None,
+            &SandboxPolicy::new_read_only_policy(),
+            &ResourceLimits::default(),
+            None,
+        )
+        .await?;
         assert!(output.timed_out);
 
         let stdout = output.stdout.from_utf8_lossy().text;
@@ -1095,6 +1236,65 @@ mod tests {
         Ok(())
     }
 
+    #[cfg(unix)]
+    async fn exec_with_limits(script: &str, limits: ResourceLimits) -> Result<ExecToolCallOutput> {
+        let params = ExecParams {
+            command: vec!["/bin/sh".to_string(), "-c".to_string(), script.to_string()],
+            cwd: std::env::current_dir()?,
+            expiration: 10_000.into(),
+            env: std::env::vars().collect(),
+            sandbox_permissions: SandboxPermissions::UseDefault,
+            windows_sandbox_level: codex_protocol::config_types::WindowsSandboxLevel::Disabled,
+            justification: None,
+            arg0: None,
+        };
+        let start = Instant::now();
+        let raw_output = exec(
+            params,
+            SandboxType::None,
+            &SandboxPolicy::DangerFullAccess,
+            &limits,
+            None,
+        )
+        .await;
+        finalize_exec_result(raw_output, SandboxType::None, &limits, start.elapsed())
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn output_limit_stops_command_with_resource_limit_error() -> Result<()> {
+        let limits = ResourceLimits {
+            max_output_bytes: Some(64 * 1024),
+            ..Default::default()
+        };
+
+        match exec_with_limits("yes", limits).await {
+            Err(CodexErr::Sandbox(SandboxErr::ResourceLimit { kind, output })) => {
+                assert_eq!(kind, ResourceLimitKind::Output);
+                assert!(output.stdout.text.len() >= 64 * 1024);
+            }
+            other => panic!("expected output limit error, got {other:?}"),
+        }
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn cpu_time_limit_is_reported_as_resource_limit() -> Result<()> {
+        let limits = ResourceLimits {
+            cpu_time_secs: Some(1),
+            ..Default::default()
+        };
+
+        match exec_with_limits("while :; do :; done", limits).await {
+            Err(CodexErr::Sandbox(SandboxErr::ResourceLimit { kind, .. })) => {
+                assert_eq!(kind, ResourceLimitKind::CpuTime);
+            }
+            other => panic!("expected CPU time limit error, got {other:?}"),
+        }
+        Ok(())
+    }
+
     #[tokio::test]
     async fn process_exec_tool_call_respects_cancellation_token() -> Result<()> {
         let command = long_running_command();
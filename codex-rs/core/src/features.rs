@@ -87,6 +87,8 @@ pub enum Feature {
     /// Allow the model to request web searches that fetch cached content.
     /// Takes precedence over `WebSearchRequest`.
     WebSearchCached,
+    /// Expose the client-side `web_fetch` tool.
+    WebFetch,
     /// Gate the execpolicy enforcement for shell/unified exec.
     ExecPolicy,
     /// Allow the model to request approval and propose exec rules.
@@ -447,6 +449,16 @@ pub const FEATURES: &[FeatureSpec] = &[
         },
         default_enabled: false,
     },
+    FeatureSpec {
+        id: Feature::WebFetch,
+        key: "web_fetch",
+        stage: Stage::Experimental {
+            name: "Web fetch",
+            menu_description: "Let Codex read web pages itself, following your [network_proxy] domain policy. Works with any model provider.",
+            announcement: "NEW! Codex can fetch web pages with any model provider. Enable in /experimental!",
+        },
+        default_enabled: false,
+    },
     FeatureSpec {
         id: Feature::ShellSnapshot,
         key: "shell_snapshot",
@@ -0,0 +1,186 @@
+use std::time::Duration;
+
+use codex_protocol::ThreadId;
+use codex_protocol::protocol::EventMsg;
+
+use super::types::ApprovalKind;
+use super::types::HookEvent;
+use super::types::HookEventApprovalRequested;
+use super::types::HookEventCommandFinished;
+use super::types::HookEventTurnError;
+use super::types::HookEventUserInputRequested;
+
+/// Commands that run at least this long fire `command_finished` hooks when they end.
+pub const LONG_COMMAND_THRESHOLD: Duration = Duration::from_secs(30);
+
+/// Notification daemons truncate long bodies anyway; keep summaries to a short paragraph.
+const SUMMARY_MAX_CHARS: usize = 200;
+
+/// Maps a session event to the hook event it triggers, if any.
+pub(crate) fn hook_event_for(
+    thread_id: ThreadId,
+    turn_id: &str,
+    msg: &EventMsg,
+) -> Option<HookEvent> {
+    let hook_event = match msg {
+        EventMsg::ExecApprovalRequest(ev) => HookEvent::ApprovalRequested {
+            event: HookEventApprovalRequested {
+                thread_id,
+                turn_id: turn_id.to_string(),
+                call_id: ev.call_id.clone(),
+                kind: ApprovalKind::Exec,
+                summary: summarize(&format!(
+                    "Approval requested: {}",
+                    join_command(&ev.command)
+                )),
+            },
+        },
+        EventMsg::ApplyPatchApprovalRequest(ev) => {
+            let target = match ev.changes.keys().next() {
+                Some(path) if ev.changes.len() == 1 => path.display().to_string(),
+                _ => format!("{} files", ev.changes.len()),
+            };
+            HookEvent::ApprovalRequested {
+                event: HookEventApprovalRequested {
+                    thread_id,
+                    turn_id: turn_id.to_string(),
+                    call_id: ev.call_id.clone(),
+                    kind: ApprovalKind::Patch,
+                    summary: summarize(&format!("Codex wants to edit {target}")),
+                },
+            }
+        }
+        EventMsg::RequestUserInput(ev) => {
+            let questions: Vec<String> = ev
+                .questions
+                .iter()
+                .map(|question| question.question.clone())
+                .collect();
+            let summary = match questions.first() {
+                Some(first) => summarize(first),
+                None => "Codex has a question".to_string(),
+            };
+            HookEvent::UserInputRequested {
+                event: HookEventUserInputRequested {
+                    thread_id,
+                    turn_id: turn_id.to_string(),
+                    call_id: ev.call_id.clone(),
+                    questions,
+                    summary,
+                },
+            }
+        }
+        EventMsg::ExecCommandEnd(ev) if ev.duration >= LONG_COMMAND_THRESHOLD => {
+            let command = join_command(&ev.command);
+            let outcome = if ev.exit_code == 0 {
+                "finished".to_string()
+            } else {
+                format!("failed (exit {})", ev.exit_code)
+            };
+            HookEvent::CommandFinished {
+                event: HookEventCommandFinished {
+                    thread_id,
+                    turn_id: turn_id.to_string(),
+                    call_id: ev.call_id.clone(),
+                    summary: summarize(&format!(
+                        "{command} {outcome} after {}s",
+                        ev.duration.as_secs()
+                    )),
+                    command,
+                    exit_code: ev.exit_code,
+                    duration_ms: u64::try_from(ev.duration.as_millis()).unwrap_or(u64::MAX),
+                },
+            }
+        }
+        EventMsg::Error(ev) => HookEvent::TurnError {
+            event: HookEventTurnError {
+                thread_id,
+                turn_id: turn_id.to_string(),
+                message: ev.message.clone(),
+                summary: summarize(&ev.message),
+            },
+        },
+        _ => return None,
+    };
+    Some(hook_event)
+}
+
+fn join_command(command: &[String]) -> String {
+    shlex::try_join(command.iter().map(String::as_str)).unwrap_or_else(|_| command.join(" "))
+}
+
+/// Collapses whitespace and truncates `text` to a single short line.
+pub(crate) fn summarize(text: &str) -> String {
+    let collapsed = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if collapsed.chars().count() <= SUMMARY_MAX_CHARS {
+        return collapsed;
+    }
+    let mut truncated: String = collapsed.chars().take(SUMMARY_MAX_CHARS - 1).collect();
+    truncated.push('…');
+    truncated
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use codex_protocol::protocol::ErrorEvent;
+    use codex_protocol::protocol::ExecCommandEndEvent;
+    use codex_protocol::protocol::ExecCommandSource;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn exec_end(duration: Duration, exit_code: i32) -> EventMsg {
+        EventMsg::ExecCommandEnd(ExecCommandEndEvent {
+            call_id: "call-1".to_string(),
+            process_id: None,
+            turn_id: "turn-1".to_string(),
+            command: vec!["cargo".to_string(), "test".to_string()],
+            cwd: PathBuf::from("/tmp"),
+            parsed_cmd: Vec::new(),
+            source: ExecCommandSource::Agent,
+            interaction_input: None,
+            stdout: String::new(),
+            stderr: String::new(),
+            aggregated_output: String::new(),
+            exit_code,
+            duration,
+            formatted_output: String::new(),
+        })
+    }
+
+    #[test]
+    fn only_long_commands_fire_command_finished() {
+        let thread_id = ThreadId::new();
+        assert!(
+            hook_event_for(thread_id, "turn-1", &exec_end(Duration::from_secs(5), 0)).is_none()
+        );
+
+        let Some(HookEvent::CommandFinished { event }) =
+            hook_event_for(thread_id, "turn-1", &exec_end(Duration::from_secs(95), 101))
+        else {
+            panic!("expected a command_finished hook event");
+        };
+        assert_eq!(event.summary, "cargo test failed (exit 101) after 95s");
+        assert_eq!(event.duration_ms, 95_000);
+    }
+
+    #[test]
+    fn turn_errors_are_summarized_on_one_line() {
+        let message = format!("stream disconnected\n{}", "x".repeat(300));
+        let Some(HookEvent::TurnError { event }) = hook_event_for(
+            ThreadId::new(),
+            "turn-1",
+            &EventMsg::Error(ErrorEvent {
+                message,
+                codex_error_info: None,
+            }),
+        ) else {
+            panic!("expected a turn_error hook event");
+        };
+        assert_eq!(event.summary.chars().count(), SUMMARY_MAX_CHARS);
+        assert!(event.summary.starts_with("stream disconnected x"));
+        assert!(event.summary.ends_with('…'));
+    }
+}
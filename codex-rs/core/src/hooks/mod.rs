@@ -1,7 +1,11 @@
+mod events;
 mod registry;
 mod types;
 mod user_notification;
 
+pub use events::LONG_COMMAND_THRESHOLD;
+pub(crate) use events::hook_event_for;
+pub(crate) use events::summarize;
 pub(crate) use registry::Hooks;
 pub(crate) use types::HookEvent;
 pub(crate) use types::HookEventAfterAgent;
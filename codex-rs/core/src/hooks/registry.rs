@@ -7,9 +7,17 @@ use super::types::HookPayload;
 use super::user_notification::notify_hook;
 use crate::config::Config;
 
+/// Legacy `notify` payload type of [`HookEvent::AfterAgent`], the only one
+/// sent when `notify_events` is unset.
+const AGENT_TURN_COMPLETE: &str = "agent-turn-complete";
+
 #[derive(Default, Clone)]
 pub(crate) struct Hooks {
     after_agent: Vec<Hook>,
+    approval_requested: Vec<Hook>,
+    user_input_requested: Vec<Hook>,
+    command_finished: Vec<Hook>,
+    turn_error: Vec<Hook>,
 }
 
 fn get_notify_hook(config: &Config) -> Option<Hook> {
@@ -25,15 +33,37 @@ fn get_notify_hook(config: &Config) -> Option<Hook> {
 impl Hooks {
     // new creates a new Hooks instance from config.
     // For legacy compatibility, if config.notify is set, it will be added to
-    // the after_agent hooks.
+    // the hooks of the events `notify_events` enables (by default only
+    // `agent-turn-complete`); the payload's `type` tells the script which one fired.
     pub(crate) fn new(config: &Config) -> Self {
-        let after_agent = get_notify_hook(config).into_iter().collect();
-        Self { after_agent }
+        let notify = get_notify_hook(config);
+        let notify_for = |event_type: &str| -> Vec<Hook> {
+            let enabled = match &config.notify_events {
+                Some(events) => events.allows(event_type, event_type == AGENT_TURN_COMPLETE),
+                None => event_type == AGENT_TURN_COMPLETE,
+            };
+            if enabled {
+                notify.iter().cloned().collect()
+            } else {
+                Vec::new()
+            }
+        };
+        Self {
+            after_agent: notify_for(AGENT_TURN_COMPLETE),
+            approval_requested: notify_for("approval-requested"),
+            user_input_requested: notify_for("user-input-requested"),
+            command_finished: notify_for("command-finished"),
+            turn_error: notify_for("turn-error"),
+        }
     }
 
     fn hooks_for_event(&self, hook_event: &HookEvent) -> &[Hook] {
         match hook_event {
             HookEvent::AfterAgent { .. } => &self.after_agent,
+            HookEvent::ApprovalRequested { .. } => &self.approval_requested,
+            HookEvent::UserInputRequested { .. } => &self.user_input_requested,
+            HookEvent::CommandFinished { .. } => &self.command_finished,
+            HookEvent::TurnError { .. } => &self.turn_error,
         }
     }
 
@@ -60,6 +90,7 @@ pub(super) fn command_from_argv(argv: &[String]) -> Option<Command> {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeMap;
     use std::fs;
     use std::path::PathBuf;
     use std::process::Stdio;
@@ -78,6 +109,7 @@ mod tests {
     use tokio::time::timeout;
 
     use crate::config::test_config;
+    use crate::config::types::Notifications;
 
     use super::super::types::Hook;
     use super::super::types::HookEvent;
@@ -124,7 +156,10 @@ mod tests {
     }
 
     fn hooks_for_after_agent(hooks: Vec<Hook>) -> Hooks {
-        Hooks { after_agent: hooks }
+        Hooks {
+            after_agent: hooks,
+            ..Default::default()
+        }
     }
 
     #[test]
@@ -167,6 +202,35 @@ mod tests {
         assert!(get_notify_hook(&config).is_some());
     }
 
+    #[test]
+    fn notify_runs_after_turns_unless_notify_events_selects_more() {
+        let mut config = test_config();
+        config.notify = Some(vec!["notify-send".to_string()]);
+        let counts = |hooks: &Hooks| {
+            [
+                hooks.after_agent.len(),
+                hooks.approval_requested.len(),
+                hooks.user_input_requested.len(),
+                hooks.command_finished.len(),
+                hooks.turn_error.len(),
+            ]
+        };
+
+        assert_eq!(counts(&Hooks::new(&config)), [1, 0, 0, 0, 0]);
+
+        config.notify_events = Some(Notifications::Events(BTreeMap::from([(
+            "approval-requested".to_string(),
+            true,
+        )])));
+        assert_eq!(counts(&Hooks::new(&config)), [1, 1, 0, 0, 0]);
+
+        config.notify_events = Some(Notifications::Custom(vec!["turn-error".to_string()]));
+        assert_eq!(counts(&Hooks::new(&config)), [0, 0, 0, 0, 1]);
+
+        config.notify_events = Some(Notifications::Enabled(true));
+        assert_eq!(counts(&Hooks::new(&config)), [1, 1, 1, 1, 1]);
+    }
+
     #[tokio::test]
     async fn dispatch_executes_hook() {
         let calls = Arc::new(AtomicUsize::new(0));
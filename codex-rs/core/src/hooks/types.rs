@@ -50,6 +50,55 @@ pub(crate) struct HookEventAfterAgent {
     pub last_assistant_message: Option<String>,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum ApprovalKind {
+    Exec,
+    Patch,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) struct HookEventApprovalRequested {
+    pub thread_id: ThreadId,
+    pub turn_id: String,
+    pub call_id: String,
+    pub kind: ApprovalKind,
+    /// One line describing what needs approval, e.g. the command.
+    pub summary: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) struct HookEventUserInputRequested {
+    pub thread_id: ThreadId,
+    pub turn_id: String,
+    pub call_id: String,
+    pub questions: Vec<String>,
+    pub summary: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) struct HookEventCommandFinished {
+    pub thread_id: ThreadId,
+    pub turn_id: String,
+    pub call_id: String,
+    pub command: String,
+    pub exit_code: i32,
+    pub duration_ms: u64,
+    pub summary: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) struct HookEventTurnError {
+    pub thread_id: ThreadId,
+    pub turn_id: String,
+    pub message: String,
+    pub summary: String,
+}
+
 fn serialize_triggered_at<S>(value: &DateTime<Utc>, serializer: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
@@ -64,6 +113,23 @@ pub(crate) enum HookEvent {
         #[serde(flatten)]
         event: HookEventAfterAgent,
     },
+    ApprovalRequested {
+        #[serde(flatten)]
+        event: HookEventApprovalRequested,
+    },
+    UserInputRequested {
+        #[serde(flatten)]
+        event: HookEventUserInputRequested,
+    },
+    /// A command that ran for at least [`super::LONG_COMMAND_THRESHOLD`] finished.
+    CommandFinished {
+        #[serde(flatten)]
+        event: HookEventCommandFinished,
+    },
+    TurnError {
+        #[serde(flatten)]
+        event: HookEventTurnError,
+    },
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -4,7 +4,9 @@ use serde::Serialize;
 use std::path::Path;
 use std::process::Stdio;
 
+use super::events::summarize;
 use super::registry::command_from_argv;
+use super::types::ApprovalKind;
 use super::types::Hook;
 use super::types::HookEvent;
 use super::types::HookOutcome;
@@ -25,6 +27,47 @@ enum UserNotification {
 
         /// The last message sent by the assistant in the turn.
         last_assistant_message: Option<String>,
+
+        /// One line for notification daemons to display.
+        summary: String,
+    },
+    #[serde(rename_all = "kebab-case")]
+    ApprovalRequested {
+        thread_id: String,
+        turn_id: String,
+        cwd: String,
+        call_id: String,
+        /// `exec` or `patch`.
+        kind: ApprovalKind,
+        summary: String,
+    },
+    #[serde(rename_all = "kebab-case")]
+    UserInputRequested {
+        thread_id: String,
+        turn_id: String,
+        cwd: String,
+        call_id: String,
+        questions: Vec<String>,
+        summary: String,
+    },
+    #[serde(rename_all = "kebab-case")]
+    CommandFinished {
+        thread_id: String,
+        turn_id: String,
+        cwd: String,
+        call_id: String,
+        command: String,
+        exit_code: i32,
+        duration_ms: u64,
+        summary: String,
+    },
+    #[serde(rename_all = "kebab-case")]
+    TurnError {
+        thread_id: String,
+        turn_id: String,
+        cwd: String,
+        message: String,
+        summary: String,
     },
 }
 
@@ -32,13 +75,53 @@ pub(super) fn legacy_notify_json(
     hook_event: &HookEvent,
     cwd: &Path,
 ) -> Result<String, serde_json::Error> {
+    let cwd = cwd.display().to_string();
     serde_json::to_string(&match hook_event {
         HookEvent::AfterAgent { event } => UserNotification::AgentTurnComplete {
             thread_id: event.thread_id.to_string(),
             turn_id: event.turn_id.clone(),
-            cwd: cwd.display().to_string(),
+            cwd,
             input_messages: event.input_messages.clone(),
             last_assistant_message: event.last_assistant_message.clone(),
+            summary: event
+                .last_assistant_message
+                .as_deref()
+                .map(summarize)
+                .filter(|summary| !summary.is_empty())
+                .unwrap_or_else(|| "Agent turn complete".to_string()),
+        },
+        HookEvent::ApprovalRequested { event } => UserNotification::ApprovalRequested {
+            thread_id: event.thread_id.to_string(),
+            turn_id: event.turn_id.clone(),
+            cwd,
+            call_id: event.call_id.clone(),
+            kind: event.kind,
+            summary: event.summary.clone(),
+        },
+        HookEvent::UserInputRequested { event } => UserNotification::UserInputRequested {
+            thread_id: event.thread_id.to_string(),
+            turn_id: event.turn_id.clone(),
+            cwd,
+            call_id: event.call_id.clone(),
+            questions: event.questions.clone(),
+            summary: event.summary.clone(),
+        },
+        HookEvent::CommandFinished { event } => UserNotification::CommandFinished {
+            thread_id: event.thread_id.to_string(),
+            turn_id: event.turn_id.clone(),
+            cwd,
+            call_id: event.call_id.clone(),
+            command: event.command.clone(),
+            exit_code: event.exit_code,
+            duration_ms: event.duration_ms,
+            summary: event.summary.clone(),
+        },
+        HookEvent::TurnError { event } => UserNotification::TurnError {
+            thread_id: event.thread_id.to_string(),
+            turn_id: event.turn_id.clone(),
+            cwd,
+            message: event.message.clone(),
+            summary: event.summary.clone(),
         },
     })
 }
@@ -89,6 +172,7 @@ mod tests {
             "cwd": "/Users/example/project",
             "input-messages": ["Rename `foo` to `bar` and update the callsites."],
             "last-assistant-message": "Rename complete and verified `cargo build` succeeds.",
+            "summary": "Rename complete and verified `cargo build` succeeds.",
         })
     }
 
@@ -102,6 +186,7 @@ mod tests {
             last_assistant_message: Some(
                 "Rename complete and verified `cargo build` succeeds.".to_string(),
             ),
+            summary: "Rename complete and verified `cargo build` succeeds.".to_string(),
         };
         let serialized = serde_json::to_string(&notification)?;
         let actual: Value = serde_json::from_str(&serialized)?;
@@ -129,4 +214,35 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn legacy_notify_json_reports_approval_requests_with_summary() -> Result<()> {
+        let hook_event = HookEvent::ApprovalRequested {
+            event: super::super::types::HookEventApprovalRequested {
+                thread_id: ThreadId::from_string("b5f6c1c2-1111-2222-3333-444455556666")
+                    .expect("valid thread id"),
+                turn_id: "12345".to_string(),
+                call_id: "call-7".to_string(),
+                kind: ApprovalKind::Exec,
+                summary: "Approval requested: rm -rf target".to_string(),
+            },
+        };
+
+        let serialized = legacy_notify_json(&hook_event, Path::new("/Users/example/project"))?;
+        let actual: Value = serde_json::from_str(&serialized)?;
+        assert_eq!(
+            actual,
+            json!({
+                "type": "approval-requested",
+                "thread-id": "b5f6c1c2-1111-2222-3333-444455556666",
+                "turn-id": "12345",
+                "cwd": "/Users/example/project",
+                "call-id": "call-7",
+                "kind": "exec",
+                "summary": "Approval requested: rm -rf target",
+            })
+        );
+
+        Ok(())
+    }
 }
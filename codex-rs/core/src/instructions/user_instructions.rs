@@ -24,6 +24,22 @@ impl UserInstructions {
             false
         }
     }
+
+    /// Parses a message built from [`UserInstructions`]. Legacy
+    /// `<user_instructions>` messages carry no directory and yield `None`.
+    pub(crate) fn from_message(message: &[ContentItem]) -> Option<Self> {
+        let [ContentItem::InputText { text }] = message else {
+            return None;
+        };
+        let (directory, rest) = text
+            .strip_prefix(USER_INSTRUCTIONS_PREFIX)?
+            .split_once("\n\n<INSTRUCTIONS>\n")?;
+        let text = rest.strip_suffix("\n</INSTRUCTIONS>")?;
+        Some(Self {
+            directory: directory.to_string(),
+            text: text.to_string(),
+        })
+    }
 }
 
 impl From<UserInstructions> for ResponseItem {
@@ -127,6 +143,29 @@ mod tests {
         ]));
     }
 
+    #[test]
+    fn test_user_instructions_from_message() {
+        let user_instructions = UserInstructions {
+            directory: "/repo/api".to_string(),
+            text: "line one\n\nline two".to_string(),
+        };
+        let ResponseItem::Message { content, .. } = ResponseItem::from(user_instructions.clone())
+        else {
+            panic!("expected ResponseItem::Message");
+        };
+
+        assert_eq!(
+            UserInstructions::from_message(&content),
+            Some(user_instructions)
+        );
+        assert_eq!(
+            UserInstructions::from_message(&[ContentItem::InputText {
+                text: "<user_instructions>test_text</user_instructions>".to_string(),
+            }]),
+            None
+        );
+    }
+
     #[test]
     fn test_skill_instructions() {
         let skill_instructions = SkillInstructions {
@@ -1,3 +1,4 @@
+use crate::config::types::ResourceLimits;
 use crate::protocol::SandboxPolicy;
 use crate::spawn::StdioPolicy;
 use crate::spawn::spawn_child_async;
@@ -28,11 +29,13 @@ pub async fn spawn_command_under_linux_sandbox<P>(
 where
     P: AsRef<Path>,
 {
+    let resource_limits = ResourceLimits::default();
     let args = create_linux_sandbox_command_args(
         command,
         sandbox_policy,
         sandbox_policy_cwd,
         use_bwrap_sandbox,
+        &resource_limits,
     );
     let arg0 = Some("codex-linux-sandbox");
     spawn_child_async(
@@ -41,6 +44,7 @@ where
         arg0,
         command_cwd,
         sandbox_policy,
+        &resource_limits,
         stdio_policy,
         env,
     )
@@ -56,6 +60,7 @@ pub(crate) fn create_linux_sandbox_command_args(
     sandbox_policy: &SandboxPolicy,
     sandbox_policy_cwd: &Path,
     use_bwrap_sandbox: bool,
+    resource_limits: &ResourceLimits,
 ) -> Vec<String> {
     #[expect(clippy::expect_used)]
     let sandbox_policy_cwd = sandbox_policy_cwd
@@ -76,6 +81,13 @@ pub(crate) fn create_linux_sandbox_command_args(
     if use_bwrap_sandbox {
         linux_cmd.push("--use-bwrap-sandbox".to_string());
     }
+    if !resource_limits.is_empty() {
+        #[expect(clippy::expect_used)]
+        let resource_limits_json = serde_json::to_string(resource_limits)
+            .expect("Failed to serialize ResourceLimits to JSON");
+        linux_cmd.push("--resource-limits".to_string());
+        linux_cmd.push(resource_limits_json);
+    }
 
     // Separator so that command arguments starting with `-` are not parsed as
     // options of the helper itself.
@@ -96,18 +108,53 @@ mod tests {
     fn bwrap_flags_are_feature_gated() {
         let command = vec!["/bin/true".to_string()];
         let cwd = Path::new("/tmp");
-        let policy = SandboxPolicy::ReadOnly;
+        let policy = SandboxPolicy::new_read_only_policy();
+
+        let limits = ResourceLimits::default();
 
-        let with_bwrap = create_linux_sandbox_command_args(command.clone(), &policy, cwd, true);
+        let with_bwrap =
+            create_linux_sandbox_command_args(command.clone(), &policy, cwd, true, &limits);
         assert_eq!(
             with_bwrap.contains(&"--use-bwrap-sandbox".to_string()),
             true
         );
 
-        let without_bwrap = create_linux_sandbox_command_args(command, &policy, cwd, false);
+        let without_bwrap =
+            create_linux_sandbox_command_args(command, &policy, cwd, false, &limits);
         assert_eq!(
             without_bwrap.contains(&"--use-bwrap-sandbox".to_string()),
             false
         );
     }
+
+    #[test]
+    fn resource_limits_are_passed_only_when_set() {
+        let command = vec!["/bin/true".to_string()];
+        let cwd = Path::new("/tmp");
+        let policy = SandboxPolicy::new_read_only_policy();
+
+        let unlimited = create_linux_sandbox_command_args(
+            command.clone(),
+            &policy,
+            cwd,
+            true,
+            &ResourceLimits::default(),
+        );
+        assert_eq!(unlimited.contains(&"--resource-limits".to_string()), false);
+
+        let limits = ResourceLimits {
+            memory_mb: Some(512),
+            max_processes: Some(64),
+            ..Default::default()
+        };
+        let limited = create_linux_sandbox_command_args(command, &policy, cwd, true, &limits);
+        let flag = limited
+            .iter()
+            .position(|arg| arg == "--resource-limits")
+            .expect("--resource-limits flag");
+        assert_eq!(
+            limited[flag + 1].parse::<ResourceLimits>().ok(),
+            Some(limits)
+        );
+    }
 }
@@ -26,6 +26,7 @@ pub mod config_loader;
 pub mod connectors;
 mod context_manager;
 pub mod custom_prompts;
+pub mod debug_bundle;
 pub mod env;
 mod environment_context;
 pub mod error;
@@ -55,6 +56,7 @@ pub mod personality_migration;
 pub mod powershell;
 mod prompt_suggestions;
 mod proposed_plan_parser;
+pub mod resource_limits;
 pub mod sandboxing;
 mod session_prefix;
 mod stream_events_utils;
@@ -68,6 +70,7 @@ pub use client::X_RESPONSESAPI_INCLUDE_TIMING_METRICS_HEADER;
 pub use model_provider_info::DEFAULT_LMSTUDIO_PORT;
 pub use model_provider_info::DEFAULT_OLLAMA_PORT;
 pub use model_provider_info::LMSTUDIO_OSS_PROVIDER_ID;
+pub use model_provider_info::ModelPricing;
 pub use model_provider_info::ModelProviderInfo;
 pub use model_provider_info::OLLAMA_OSS_PROVIDER_ID;
 pub use model_provider_info::WireApi;
@@ -104,6 +107,7 @@ pub mod terminal;
 mod tools;
 pub mod turn_diff_tracker;
 mod turn_metadata;
+pub mod usage;
 pub use rollout::ARCHIVED_SESSIONS_SUBDIR;
 pub use rollout::INTERACTIVE_SESSION_SOURCES;
 pub use rollout::RolloutRecorder;
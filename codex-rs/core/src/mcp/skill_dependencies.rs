@@ -394,6 +394,8 @@ fn mcp_dependency_to_server_config(
             enabled_tools: None,
             disabled_tools: None,
             scopes: None,
+            default_tool_approval: None,
+            tool_approvals: HashMap::new(),
         });
     }
 
@@ -418,6 +420,8 @@ fn mcp_dependency_to_server_config(
             enabled_tools: None,
             disabled_tools: None,
             scopes: None,
+            default_tool_approval: None,
+            tool_approvals: HashMap::new(),
         });
     }
     if transport.eq_ignore_ascii_case("sse") {
@@ -440,6 +444,8 @@ fn mcp_dependency_to_server_config(
             enabled_tools: None,
             disabled_tools: None,
             scopes: None,
+            default_tool_approval: None,
+            tool_approvals: HashMap::new(),
         });
     }
 
@@ -494,6 +500,8 @@ mod tests {
                 enabled_tools: None,
                 disabled_tools: None,
                 scopes: None,
+                default_tool_approval: None,
+                tool_approvals: HashMap::new(),
             },
         )]);
 
@@ -542,6 +550,8 @@ mod tests {
                 enabled_tools: None,
                 disabled_tools: None,
                 scopes: None,
+                default_tool_approval: None,
+                tool_approvals: HashMap::new(),
             },
         )]);
 
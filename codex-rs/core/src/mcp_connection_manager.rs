@@ -14,6 +14,8 @@ use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::LazyLock;
 use std::sync::Mutex as StdMutex;
+use std::sync::RwLock as StdRwLock;
+use std::sync::Weak;
 use std::time::Duration;
 use std::time::Instant;
 
@@ -25,18 +27,23 @@ use anyhow::anyhow;
 use async_channel::Sender;
 use codex_async_utils::CancelErr;
 use codex_async_utils::OrCancelExt;
+use codex_otel::traces::spans::trace_context_headers;
 use codex_protocol::approvals::ElicitationRequestEvent;
 use codex_protocol::mcp::CallToolResult;
 use codex_protocol::mcp::RequestId as ProtocolRequestId;
 use codex_protocol::protocol::Event;
 use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::McpListChangedKind;
 use codex_protocol::protocol::McpStartupCompleteEvent;
 use codex_protocol::protocol::McpStartupFailure;
 use codex_protocol::protocol::McpStartupStatus;
 use codex_protocol::protocol::McpStartupUpdateEvent;
+use codex_protocol::protocol::McpToolsChangedEvent;
 use codex_protocol::protocol::SandboxPolicy;
 use codex_rmcp_client::ElicitationResponse;
+use codex_rmcp_client::ListChangedKind;
 use codex_rmcp_client::OAuthCredentialsStoreMode;
+use codex_rmcp_client::OnListChanged;
 use codex_rmcp_client::RmcpClient;
 use codex_rmcp_client::SendElicitation;
 use futures::future::BoxFuture;
@@ -62,15 +69,18 @@ use serde::Serialize;
 use sha1::Digest;
 use sha1::Sha1;
 use tokio::sync::Mutex;
+use tokio::sync::mpsc;
 use tokio::sync::oneshot;
 use tokio::task::JoinSet;
 use tokio_util::sync::CancellationToken;
+use tracing::Span;
 use tracing::instrument;
 use tracing::warn;
 
 use crate::codex::INITIAL_SUBMIT_ID;
 use crate::config::types::McpServerConfig;
 use crate::config::types::McpServerTransportConfig;
+use crate::config::types::McpToolApproval;
 
 /// Delimiter used to separate the server name from the tool name in a fully
 /// qualified tool name.
@@ -238,13 +248,22 @@ impl ElicitationRequestManager {
 #[derive(Clone)]
 struct ManagedClient {
     client: Arc<RmcpClient>,
-    tools: Vec<ToolInfo>,
+    /// Shared with the list-changed listener so refreshed tools are visible
+    /// to every clone of this client.
+    tools: Arc<StdRwLock<Vec<ToolInfo>>>,
     tool_filter: ToolFilter,
     tool_timeout: Option<Duration>,
     server_supports_sandbox_state_capability: bool,
 }
 
 impl ManagedClient {
+    fn tools(&self) -> Vec<ToolInfo> {
+        self.tools
+            .read()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .clone()
+    }
+
     /// Returns once the server has ack'd the sandbox state update.
     async fn notify_sandbox_state_change(&self, sandbox_state: &SandboxState) -> Result<()> {
         if !self.server_supports_sandbox_state_capability {
@@ -336,6 +355,14 @@ pub struct SandboxState {
 pub(crate) struct McpConnectionManager {
     clients: HashMap<String, AsyncManagedClient>,
     elicitation_requests: ElicitationRequestManager,
+    tool_approvals: HashMap<String, ServerToolApprovals>,
+}
+
+/// Approval policies configured for one server's tools.
+#[derive(Clone, Default)]
+struct ServerToolApprovals {
+    default: Option<McpToolApproval>,
+    tools: HashMap<String, McpToolApproval>,
 }
 
 impl McpConnectionManager {
@@ -355,6 +382,16 @@ impl McpConnectionManager {
         let mut join_set = JoinSet::new();
         let elicitation_requests = ElicitationRequestManager::default();
         let mcp_servers = mcp_servers.clone();
+        self.tool_approvals = mcp_servers
+            .iter()
+            .map(|(server_name, cfg)| {
+                let approvals = ServerToolApprovals {
+                    default: cfg.default_tool_approval.clone(),
+                    tools: cfg.tool_approvals.clone(),
+                };
+                (server_name.clone(), approvals)
+            })
+            .collect();
         for (server_name, cfg) in mcp_servers.into_iter().filter(|(_, cfg)| cfg.enabled) {
             let cancel_token = cancel_token.child_token();
             let _ = emit_update(
@@ -507,10 +544,10 @@ impl McpConnectionManager {
         for (server_name, managed_client) in &self.clients {
             let client = managed_client.client().await.ok();
             if let Some(client) = client {
+                let mut server_tools = client.tools();
                 let rmcp_client = client.client;
                 let tool_timeout = client.tool_timeout;
                 let tool_filter = client.tool_filter;
-                let mut server_tools = client.tools;
 
                 if server_name == CODEX_APPS_MCP_SERVER_NAME {
                     match list_tools_for_client(server_name, &rmcp_client, tool_timeout).await {
@@ -665,6 +702,17 @@ impl McpConnectionManager {
         aggregated
     }
 
+    /// Returns the approval policy configured for `tool` on `server`, falling
+    /// back to the server's `default_tool_approval`.
+    pub(crate) fn tool_approval(&self, server: &str, tool: &str) -> Option<McpToolApproval> {
+        let approvals = self.tool_approvals.get(server)?;
+        approvals
+            .tools
+            .get(tool)
+            .or(approvals.default.as_ref())
+            .cloned()
+    }
+
     /// Invoke the tool indicated by the (server, tool) pair.
     pub async fn call_tool(
         &self,
@@ -681,7 +729,12 @@ impl McpConnectionManager {
 
         let result: rmcp::model::CallToolResult = client
             .client
-            .call_tool(tool.to_string(), arguments, client.tool_timeout)
+            .call_tool(
+                tool.to_string(),
+                arguments,
+                client.tool_timeout,
+                &trace_context_headers(&Span::current()),
+            )
             .await
             .with_context(|| format!("tool call failed for `{server}/{tool}`"))?;
 
@@ -942,16 +995,36 @@ async fn start_server_task(
         protocol_version: ProtocolVersion::V_2025_06_18,
     };
 
-    let send_elicitation = elicitation_requests.make_sender(server_name.clone(), tx_event);
+    let send_elicitation = elicitation_requests.make_sender(server_name.clone(), tx_event.clone());
+    let (list_changed_tx, list_changed_rx) = mpsc::unbounded_channel();
+    let on_list_changed: OnListChanged = Box::new(move |kind| {
+        let _ = list_changed_tx.send(kind);
+    });
 
     let initialize_result = client
-        .initialize(params, startup_timeout, send_elicitation)
+        .initialize(
+            params,
+            startup_timeout,
+            send_elicitation,
+            Some(on_list_changed),
+        )
         .await
         .map_err(StartupOutcomeError::from)?;
 
     let tools = list_tools_for_client(&server_name, &client, startup_timeout)
         .await
         .map_err(StartupOutcomeError::from)?;
+    let tools = Arc::new(StdRwLock::new(tools));
+
+    spawn_list_changed_listener(
+        server_name,
+        Arc::downgrade(&client),
+        Arc::clone(&tools),
+        tool_filter.clone(),
+        Some(tool_timeout),
+        list_changed_rx,
+        tx_event,
+    );
 
     let server_supports_sandbox_state_capability = initialize_result
         .capabilities
@@ -971,6 +1044,68 @@ async fn start_server_task(
     Ok(managed)
 }
 
+/// Keeps the cached tool list of a server in sync with its `list_changed`
+/// notifications. Tool specs are rebuilt from this cache for every sampling
+/// request, so the next model request sees the refreshed tools without a
+/// full `Op::RefreshMcpServers`.
+fn spawn_list_changed_listener(
+    server_name: String,
+    client: Weak<RmcpClient>,
+    tools: Arc<StdRwLock<Vec<ToolInfo>>>,
+    tool_filter: ToolFilter,
+    timeout: Option<Duration>,
+    mut list_changed_rx: mpsc::UnboundedReceiver<ListChangedKind>,
+    tx_event: Sender<Event>,
+) {
+    tokio::spawn(async move {
+        while let Some(kind) = list_changed_rx.recv().await {
+            let kind = match kind {
+                ListChangedKind::Tools => McpListChangedKind::Tools,
+                ListChangedKind::Resources => McpListChangedKind::Resources,
+                // Prompts are not surfaced to the model.
+                ListChangedKind::Prompts => continue,
+            };
+            if kind == McpListChangedKind::Tools {
+                let Some(client) = client.upgrade() else {
+                    break;
+                };
+                if server_name == CODEX_APPS_MCP_SERVER_NAME {
+                    invalidate_cached_codex_apps_tools();
+                }
+                match list_tools_for_client(&server_name, &client, timeout).await {
+                    Ok(fresh_tools) => {
+                        *tools
+                            .write()
+                            .unwrap_or_else(std::sync::PoisonError::into_inner) = fresh_tools;
+                    }
+                    Err(err) => {
+                        warn!(
+                            "Failed to refresh tools for MCP server '{server_name}' after list_changed: {err:#}"
+                        );
+                        continue;
+                    }
+                }
+            }
+
+            let current_tools = tools
+                .read()
+                .unwrap_or_else(std::sync::PoisonError::into_inner)
+                .clone();
+            let tool_count = filter_tools(current_tools, tool_filter.clone()).len();
+            let _ = tx_event
+                .send(Event {
+                    id: INITIAL_SUBMIT_ID.to_owned(),
+                    msg: EventMsg::McpToolsChanged(McpToolsChangedEvent {
+                        server: server_name.clone(),
+                        kind,
+                        tool_count,
+                    }),
+                })
+                .await;
+        }
+    });
+}
+
 async fn make_rmcp_client(
     server_name: &str,
     transport: McpServerTransportConfig,
@@ -1071,6 +1206,13 @@ fn read_cached_codex_apps_tools() -> Option<Vec<ToolInfo>> {
     None
 }
 
+fn invalidate_cached_codex_apps_tools() {
+    let mut cache_guard = CODEX_APPS_TOOLS_CACHE
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    *cache_guard = None;
+}
+
 fn write_cached_codex_apps_tools(tools: &[ToolInfo]) {
     let mut cache_guard = CODEX_APPS_TOOLS_CACHE
         .lock()
@@ -1367,6 +1509,68 @@ mod tests {
         assert_eq!(filtered[0].tool_name, "tool_a");
     }
 
+    #[tokio::test]
+    async fn list_changed_listener_reports_resource_changes_with_filtered_tool_count() {
+        let tools = Arc::new(StdRwLock::new(vec![
+            create_test_tool("server1", "tool_a"),
+            create_test_tool("server1", "tool_b"),
+        ]));
+        let tool_filter = ToolFilter {
+            enabled: None,
+            disabled: HashSet::from(["tool_b".to_string()]),
+        };
+        let (list_changed_tx, list_changed_rx) = mpsc::unbounded_channel();
+        let (tx_event, rx_event) = async_channel::unbounded();
+        spawn_list_changed_listener(
+            "server1".to_string(),
+            Weak::new(),
+            tools,
+            tool_filter,
+            None,
+            list_changed_rx,
+            tx_event,
+        );
+
+        list_changed_tx
+            .send(ListChangedKind::Prompts)
+            .expect("send prompts notification");
+        list_changed_tx
+            .send(ListChangedKind::Resources)
+            .expect("send resources notification");
+
+        let event = rx_event.recv().await.expect("event");
+        let EventMsg::McpToolsChanged(changed) = event.msg else {
+            panic!("expected McpToolsChanged, got {:?}", event.msg);
+        };
+        assert_eq!(changed.server, "server1");
+        assert_eq!(changed.kind, McpListChangedKind::Resources);
+        assert_eq!(changed.tool_count, 1);
+
+        drop(list_changed_tx);
+        assert!(rx_event.recv().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn list_changed_listener_stops_when_client_is_gone() {
+        let (list_changed_tx, list_changed_rx) = mpsc::unbounded_channel();
+        let (tx_event, rx_event) = async_channel::unbounded();
+        spawn_list_changed_listener(
+            "server1".to_string(),
+            Weak::new(),
+            Arc::new(StdRwLock::new(Vec::new())),
+            ToolFilter::default(),
+            None,
+            list_changed_rx,
+            tx_event,
+        );
+
+        list_changed_tx
+            .send(ListChangedKind::Tools)
+            .expect("send tools notification");
+
+        assert!(rx_event.recv().await.is_err());
+    }
+
     #[test]
     fn mcp_init_error_display_prompts_for_github_pat() {
         let server_name = "github";
@@ -1386,6 +1590,8 @@ mod tests {
                 enabled_tools: None,
                 disabled_tools: None,
                 scopes: None,
+                default_tool_approval: None,
+                tool_approvals: HashMap::new(),
             },
             auth_status: McpAuthStatus::Unsupported,
         };
@@ -1433,6 +1639,8 @@ mod tests {
                 enabled_tools: None,
                 disabled_tools: None,
                 scopes: None,
+                default_tool_approval: None,
+                tool_approvals: HashMap::new(),
             },
             auth_status: McpAuthStatus::Unsupported,
         };
@@ -1,25 +1,35 @@
 use std::time::Duration;
 use std::time::Instant;
 
+use tracing::Instrument;
 use tracing::error;
 
 use crate::codex::Session;
 use crate::codex::TurnContext;
+use crate::config::types::McpArgumentPredicate;
+use crate::config::types::McpToolApproval;
 use crate::mcp::CODEX_APPS_MCP_SERVER_NAME;
 use crate::protocol::EventMsg;
 use crate::protocol::McpInvocation;
 use crate::protocol::McpToolCallBeginEvent;
 use crate::protocol::McpToolCallEndEvent;
+use crate::tools::approval_question::ApprovalAnswer;
+use crate::tools::approval_question::ApprovalOptions;
+use crate::tools::approval_question::request_approval;
+use codex_otel::ToolDecisionSource;
+use codex_otel::traces::spans::mcp_call_span;
+use codex_otel::traces::spans::record_approval_decision;
+use codex_otel::traces::spans::record_outcome;
 use codex_protocol::mcp::CallToolResult;
 use codex_protocol::models::FunctionCallOutputPayload;
 use codex_protocol::models::ResponseInputItem;
 use codex_protocol::protocol::AskForApproval;
+use codex_protocol::protocol::ReviewDecision;
 use codex_protocol::protocol::SandboxPolicy;
-use codex_protocol::request_user_input::RequestUserInputArgs;
 use codex_protocol::request_user_input::RequestUserInputQuestion;
 use codex_protocol::request_user_input::RequestUserInputQuestionOption;
-use codex_protocol::request_user_input::RequestUserInputResponse;
 use rmcp::model::ToolAnnotations;
+use serde::Serialize;
 use std::sync::Arc;
 
 /// Handles the specified tool call dispatches the appropriate
@@ -61,13 +71,22 @@ pub(crate) async fn handle_mcp_tool_call(
         tool: tool_name.clone(),
         arguments: arguments_value.clone(),
     };
-
-    if let Some(decision) =
-        maybe_request_mcp_tool_approval(sess.as_ref(), turn_context, &call_id, &server, &tool_name)
-            .await
+    let mcp_span = mcp_call_span(&server, &tool_name, &call_id);
+
+    if let Some(decision) = maybe_request_mcp_tool_approval(
+        sess.as_ref(),
+        turn_context,
+        &call_id,
+        &server,
+        &tool_name,
+        arguments_value.as_ref(),
+    )
+    .await
     {
+        let (review_decision, source) = decision.review_decision();
+        record_approval_decision(&mcp_span, &review_decision, &source);
         let result = match decision {
-            McpToolApprovalDecision::Accept => {
+            McpToolApprovalDecision::Accept | McpToolApprovalDecision::AcceptForSession => {
                 let tool_call_begin_event = EventMsg::McpToolCallBegin(McpToolCallBeginEvent {
                     call_id: call_id.clone(),
                     invocation: invocation.clone(),
@@ -78,8 +97,10 @@ pub(crate) async fn handle_mcp_tool_call(
                 let start = Instant::now();
                 let result: Result<CallToolResult, String> = sess
                     .call_tool(&server, &tool_name, arguments_value.clone())
+                    .instrument(mcp_span.clone())
                     .await
                     .map_err(|e| format!("tool call error: {e:?}"));
+                record_outcome(&mcp_span, result.is_ok(), start.elapsed());
                 if let Err(e) = &result {
                     tracing::warn!("MCP tool call error: {e:?}");
                 }
@@ -119,6 +140,19 @@ pub(crate) async fn handle_mcp_tool_call(
                 )
                 .await
             }
+            McpToolApprovalDecision::Forbidden => {
+                let message = format!(
+                    "MCP tool {server}::{tool_name} requires approval, but approval_policy is `never`"
+                );
+                notify_mcp_tool_call_skip(
+                    sess.as_ref(),
+                    turn_context,
+                    &call_id,
+                    invocation,
+                    message,
+                )
+                .await
+            }
         };
 
         let status = if result.is_ok() { "ok" } else { "error" };
@@ -140,8 +174,10 @@ pub(crate) async fn handle_mcp_tool_call(
     // Perform the tool call.
     let result: Result<CallToolResult, String> = sess
         .call_tool(&server, &tool_name, arguments_value.clone())
+        .instrument(mcp_span.clone())
         .await
         .map_err(|e| format!("tool call error: {e:?}"));
+    record_outcome(&mcp_span, result.is_ok(), start.elapsed());
     if let Err(e) = &result {
         tracing::warn!("MCP tool call error: {e:?}");
     }
@@ -170,8 +206,49 @@ async fn notify_mcp_tool_call_event(sess: &Session, turn_context: &TurnContext,
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum McpToolApprovalDecision {
     Accept,
+    AcceptForSession,
     Decline,
     Cancel,
+    /// A configured policy requires approval but the user cannot be asked.
+    Forbidden,
+}
+
+impl McpToolApprovalDecision {
+    /// The equivalent exec-approval decision, for telemetry.
+    fn review_decision(self) -> (ReviewDecision, ToolDecisionSource) {
+        match self {
+            Self::Accept => (ReviewDecision::Approved, ToolDecisionSource::User),
+            Self::AcceptForSession => {
+                (ReviewDecision::ApprovedForSession, ToolDecisionSource::User)
+            }
+            Self::Decline => (ReviewDecision::Denied, ToolDecisionSource::User),
+            Self::Cancel => (ReviewDecision::Abort, ToolDecisionSource::User),
+            Self::Forbidden => (ReviewDecision::Denied, ToolDecisionSource::Config),
+        }
+    }
+}
+
+impl From<ApprovalAnswer> for McpToolApprovalDecision {
+    fn from(answer: ApprovalAnswer) -> Self {
+        match answer {
+            ApprovalAnswer::Accept => Self::Accept,
+            ApprovalAnswer::AcceptForSession => Self::AcceptForSession,
+            ApprovalAnswer::Decline => Self::Decline,
+            ApprovalAnswer::Cancel => Self::Cancel,
+        }
+    }
+}
+
+/// Session approval cache key for "accept for session" on an MCP tool.
+///
+/// Calls gated by argument predicates are keyed by their exact arguments, so
+/// approving one matching call does not approve other matching calls.
+#[derive(Serialize)]
+struct McpToolApprovalKey<'a> {
+    mcp_server: &'a str,
+    mcp_tool: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    arguments: Option<&'a serde_json::Value>,
 }
 
 struct McpToolApprovalMetadata {
@@ -181,9 +258,6 @@ struct McpToolApprovalMetadata {
 }
 
 const MCP_TOOL_APPROVAL_QUESTION_ID_PREFIX: &str = "mcp_tool_call_approval";
-const MCP_TOOL_APPROVAL_ACCEPT: &str = "Accept";
-const MCP_TOOL_APPROVAL_DECLINE: &str = "Decline";
-const MCP_TOOL_APPROVAL_CANCEL: &str = "Cancel";
 
 async fn maybe_request_mcp_tool_approval(
     sess: &Session,
@@ -191,34 +265,110 @@ async fn maybe_request_mcp_tool_approval(
     call_id: &str,
     server: &str,
     tool_name: &str,
+    arguments: Option<&serde_json::Value>,
 ) -> Option<McpToolApprovalDecision> {
-    if is_full_access_mode(turn_context) {
-        return None;
-    }
-    if server != CODEX_APPS_MCP_SERVER_NAME {
-        return None;
-    }
+    let configured = sess
+        .services
+        .mcp_connection_manager
+        .read()
+        .await
+        .tool_approval(server, tool_name);
+    let question_id = format!("{MCP_TOOL_APPROVAL_QUESTION_ID_PREFIX}_{call_id}");
+    let mut key = McpToolApprovalKey {
+        mcp_server: server,
+        mcp_tool: tool_name,
+        arguments: None,
+    };
+    let (question, allow_for_session) = match configured {
+        Some(policy) => {
+            let matched = match &policy {
+                McpToolApproval::Never => return None,
+                McpToolApproval::Always | McpToolApproval::OnFirstUse => None,
+                McpToolApproval::OnArgumentMatch(predicates) => {
+                    let matched = matching_argument_predicate(predicates, arguments)?;
+                    key.arguments = arguments;
+                    Some(matched)
+                }
+            };
+            if matches!(turn_context.approval_policy, AskForApproval::Never) {
+                return Some(McpToolApprovalDecision::Forbidden);
+            }
+            let allow_for_session = !matches!(policy, McpToolApproval::Always);
+            let question = build_configured_tool_approval_question(
+                question_id,
+                server,
+                tool_name,
+                matched,
+                allow_for_session,
+            );
+            (question, allow_for_session)
+        }
+        None => {
+            if is_full_access_mode(turn_context) {
+                return None;
+            }
+            if server != CODEX_APPS_MCP_SERVER_NAME {
+                return None;
+            }
 
-    let metadata = lookup_mcp_tool_metadata(sess, server, tool_name).await?;
-    if !requires_mcp_tool_approval(&metadata.annotations) {
-        return None;
-    }
+            let metadata = lookup_mcp_tool_metadata(sess, server, tool_name).await?;
+            if !requires_mcp_tool_approval(&metadata.annotations) {
+                return None;
+            }
+            let question = build_mcp_tool_approval_question(
+                question_id,
+                tool_name,
+                metadata.tool_title.as_deref(),
+                metadata.connector_name.as_deref(),
+                &metadata.annotations,
+            );
+            (question, true)
+        }
+    };
 
-    let question_id = format!("{MCP_TOOL_APPROVAL_QUESTION_ID_PREFIX}_{call_id}");
-    let question = build_mcp_tool_approval_question(
-        question_id.clone(),
-        tool_name,
-        metadata.tool_title.as_deref(),
-        metadata.connector_name.as_deref(),
-        &metadata.annotations,
-    );
-    let args = RequestUserInputArgs {
-        questions: vec![question],
+    request_approval(
+        sess,
+        turn_context,
+        call_id,
+        question,
+        allow_for_session.then_some(key),
+    )
+    .await
+    .map(McpToolApprovalDecision::from)
+}
+
+/// Returns the first predicate that matches `arguments`, if any.
+fn matching_argument_predicate<'a>(
+    predicates: &'a [McpArgumentPredicate],
+    arguments: Option<&serde_json::Value>,
+) -> Option<&'a McpArgumentPredicate> {
+    predicates
+        .iter()
+        .find(|predicate| argument_predicate_matches(predicate, arguments))
+}
+
+/// A predicate matches when its pointer resolves and every condition it sets
+/// holds for the resolved value.
+fn argument_predicate_matches(
+    predicate: &McpArgumentPredicate,
+    arguments: Option<&serde_json::Value>,
+) -> bool {
+    let Some(value) = arguments.and_then(|arguments| arguments.pointer(&predicate.pointer)) else {
+        return false;
     };
-    let response = sess
-        .request_user_input(turn_context, call_id.to_string(), args)
-        .await;
-    Some(parse_mcp_tool_approval_response(response, &question_id))
+    predicate
+        .equals
+        .as_ref()
+        .is_none_or(|expected| value == expected)
+        && predicate
+            .one_of
+            .as_ref()
+            .is_none_or(|candidates| candidates.contains(value))
+        && predicate.contains.as_ref().is_none_or(|needle| {
+            value
+                .as_str()
+                .is_some_and(|haystack| haystack.contains(needle.as_str()))
+        })
 }
 
 fn is_full_access_mode(turn_context: &TurnContext) -> bool {
@@ -288,52 +438,59 @@ fn build_mcp_tool_approval_question(
         question,
         is_other: false,
         is_secret: false,
-        options: Some(vec![
-            RequestUserInputQuestionOption {
-                label: MCP_TOOL_APPROVAL_ACCEPT.to_string(),
-                description: "Run the tool and continue.".to_string(),
-            },
-            RequestUserInputQuestionOption {
-                label: MCP_TOOL_APPROVAL_DECLINE.to_string(),
-                description: "Decline this tool call and continue.".to_string(),
-            },
-            RequestUserInputQuestionOption {
-                label: MCP_TOOL_APPROVAL_CANCEL.to_string(),
-                description: "Cancel this tool call".to_string(),
-            },
-        ]),
+        options: Some(mcp_tool_approval_options(Some(
+            "Run the tool and don't ask again for it this session.",
+        ))),
     }
 }
 
-fn parse_mcp_tool_approval_response(
-    response: Option<RequestUserInputResponse>,
-    question_id: &str,
-) -> McpToolApprovalDecision {
-    let Some(response) = response else {
-        return McpToolApprovalDecision::Cancel;
-    };
-    let answers = response
-        .answers
-        .get(question_id)
-        .map(|answer| answer.answers.as_slice());
-    let Some(answers) = answers else {
-        return McpToolApprovalDecision::Cancel;
+fn build_configured_tool_approval_question(
+    question_id: String,
+    server: &str,
+    tool_name: &str,
+    matched: Option<&McpArgumentPredicate>,
+    allow_for_session: bool,
+) -> RequestUserInputQuestion {
+    let question = match matched {
+        Some(predicate) => format!(
+            "MCP server `{server}` wants to run the tool \"{tool_name}\" with `{}` matching your approval rule. Allow this action?",
+            predicate.pointer
+        ),
+        None => format!(
+            "MCP server `{server}` wants to run the tool \"{tool_name}\". Allow this action?"
+        ),
     };
-    if answers
-        .iter()
-        .any(|answer| answer == MCP_TOOL_APPROVAL_ACCEPT)
-    {
-        McpToolApprovalDecision::Accept
-    } else if answers
-        .iter()
-        .any(|answer| answer == MCP_TOOL_APPROVAL_CANCEL)
-    {
-        McpToolApprovalDecision::Cancel
-    } else {
-        McpToolApprovalDecision::Decline
+
+    RequestUserInputQuestion {
+        id: question_id,
+        header: "Approve MCP tool call?".to_string(),
+        question,
+        is_other: false,
+        is_secret: false,
+        options: Some(mcp_tool_approval_options(allow_for_session.then_some(
+            if matched.is_some() {
+                "Run the tool and don't ask again for these exact arguments this session."
+            } else {
+                "Run the tool and don't ask again for it this session."
+            },
+        ))),
     }
 }
 
+/// `accept_for_session` describes the "Accept for session" option, which is
+/// omitted when `None`.
+fn mcp_tool_approval_options(
+    accept_for_session: Option<&str>,
+) -> Vec<RequestUserInputQuestionOption> {
+    ApprovalOptions {
+        accept: "Run the tool and continue.",
+        accept_for_session,
+        decline: "Decline this tool call and continue.",
+        cancel: "Cancel this tool call",
+    }
+    .into_options()
+}
+
 fn requires_mcp_tool_approval(annotations: &ToolAnnotations) -> bool {
     annotations.read_only_hint == Some(false)
         && (annotations.destructive_hint == Some(true) || annotations.open_world_hint == Some(true))
@@ -366,6 +523,7 @@ async fn notify_mcp_tool_call_skip(
 mod tests {
     use super::*;
     use pretty_assertions::assert_eq;
+    use serde_json::json;
 
     fn annotations(
         read_only: Option<bool>,
@@ -398,4 +556,87 @@ mod tests {
         let annotations = annotations(Some(true), Some(true), Some(true));
         assert_eq!(requires_mcp_tool_approval(&annotations), false);
     }
+
+    fn predicate(pointer: &str) -> McpArgumentPredicate {
+        McpArgumentPredicate {
+            pointer: pointer.to_string(),
+            equals: None,
+            one_of: None,
+            contains: None,
+        }
+    }
+
+    #[test]
+    fn argument_predicate_requires_pointer_to_resolve() {
+        let arguments = json!({"project": "OPS", "labels": ["prod"]});
+        assert_eq!(
+            argument_predicate_matches(&predicate("/project"), Some(&arguments)),
+            true
+        );
+        assert_eq!(
+            argument_predicate_matches(&predicate("/priority"), Some(&arguments)),
+            false
+        );
+        assert_eq!(
+            argument_predicate_matches(&predicate("/project"), None),
+            false
+        );
+    }
+
+    #[test]
+    fn argument_predicate_checks_every_condition() {
+        let arguments = json!({"project": "OPS", "summary": "restart prod db"});
+        let equals = McpArgumentPredicate {
+            equals: Some(json!("OPS")),
+            ..predicate("/project")
+        };
+        let one_of = McpArgumentPredicate {
+            one_of: Some(vec![json!("DEV"), json!("QA")]),
+            ..predicate("/project")
+        };
+        let contains = McpArgumentPredicate {
+            contains: Some("prod".to_string()),
+            ..predicate("/summary")
+        };
+        let equals_and_contains = McpArgumentPredicate {
+            equals: Some(json!("restart staging db")),
+            contains: Some("prod".to_string()),
+            ..predicate("/summary")
+        };
+
+        assert_eq!(argument_predicate_matches(&equals, Some(&arguments)), true);
+        assert_eq!(argument_predicate_matches(&one_of, Some(&arguments)), false);
+        assert_eq!(
+            argument_predicate_matches(&contains, Some(&arguments)),
+            true
+        );
+        assert_eq!(
+            argument_predicate_matches(&equals_and_contains, Some(&arguments)),
+            false
+        );
+
+        let predicates = [one_of, contains];
+        assert_eq!(
+            matching_argument_predicate(&predicates, Some(&arguments))
+                .map(|predicate| predicate.pointer.as_str()),
+            Some("/summary")
+        );
+    }
+
+    #[test]
+    fn argument_matched_approvals_are_keyed_by_arguments() {
+        let first = json!({"branch": "main"});
+        let second = json!({"branch": "release"});
+        let key = |arguments| {
+            serde_json::to_string(&McpToolApprovalKey {
+                mcp_server: "git",
+                mcp_tool: "push",
+                arguments,
+            })
+            .expect("serialize key")
+        };
+
+        assert_ne!(key(Some(&first)), key(Some(&second)));
+        assert_eq!(key(None), r#"{"mcp_server":"git","mcp_tool":"push"}"#);
+    }
 }
@@ -9,6 +9,7 @@ use crate::auth::AuthMode;
 use crate::error::EnvVarError;
 use codex_api::Provider as ApiProvider;
 use codex_api::provider::RetryConfig as ApiRetryConfig;
+use codex_protocol::protocol::TokenUsage;
 use http::HeaderMap;
 use http::header::HeaderName;
 use http::header::HeaderValue;
@@ -112,6 +113,37 @@ pub struct ModelProviderInfo {
     /// Whether this provider supports the Responses API WebSocket transport.
     #[serde(default)]
     pub supports_websockets: bool,
+
+    /// Prices per model slug, used to estimate the cost of recorded token usage.
+    pub pricing: Option<HashMap<String, ModelPricing>>,
+}
+
+/// Price of a model in US dollars per million tokens.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct ModelPricing {
+    /// Price of uncached input tokens.
+    pub input: f64,
+    /// Price of cached input tokens. Defaults to `input`.
+    pub cached_input: Option<f64>,
+    /// Price of output tokens, including reasoning tokens.
+    pub output: f64,
+}
+
+impl ModelPricing {
+    /// Estimated cost in US dollars. Cached and reasoning tokens are subsets of the input and
+    /// output counts, as the Responses API reports them.
+    pub fn cost(&self, usage: &TokenUsage) -> f64 {
+        let cached = usage
+            .cached_input_tokens
+            .clamp(0, usage.input_tokens.max(0));
+        let uncached = usage.input_tokens.max(0) - cached;
+        let cached_price = self.cached_input.unwrap_or(self.input);
+        (uncached as f64 * self.input
+            + cached as f64 * cached_price
+            + usage.output_tokens.max(0) as f64 * self.output)
+            / 1_000_000.0
+    }
 }
 
 impl ModelProviderInfo {
@@ -257,12 +289,18 @@ impl ModelProviderInfo {
             stream_idle_timeout_ms: None,
             requires_openai_auth: true,
             supports_websockets: true,
+            pricing: None,
         }
     }
 
     pub fn is_openai(&self) -> bool {
         self.name == OPENAI_PROVIDER_NAME
     }
+
+    /// Configured price for `model`, if any.
+    pub fn model_pricing(&self, model: &str) -> Option<&ModelPricing> {
+        self.pricing.as_ref()?.get(model)
+    }
 }
 
 pub const DEFAULT_LMSTUDIO_PORT: u16 = 1234;
@@ -331,6 +369,7 @@ pub fn create_oss_provider_with_base_url(base_url: &str, wire_api: WireApi) -> M
         stream_idle_timeout_ms: None,
         requires_openai_auth: false,
         supports_websockets: false,
+        pricing: None,
     }
 }
 
@@ -360,6 +399,7 @@ base_url = "http://localhost:11434/v1"
             stream_idle_timeout_ms: None,
             requires_openai_auth: false,
             supports_websockets: false,
+            pricing: None,
         };
 
         let provider: ModelProviderInfo = toml::from_str(azure_provider_toml).unwrap();
@@ -391,6 +431,7 @@ query_params = { api-version = "2025-04-01-preview" }
             stream_idle_timeout_ms: None,
             requires_openai_auth: false,
             supports_websockets: false,
+            pricing: None,
         };
 
         let provider: ModelProviderInfo = toml::from_str(azure_provider_toml).unwrap();
@@ -425,6 +466,7 @@ env_http_headers = { "X-Example-Env-Header" = "EXAMPLE_ENV_VAR" }
             stream_idle_timeout_ms: None,
             requires_openai_auth: false,
             supports_websockets: false,
+            pricing: None,
         };
 
         let provider: ModelProviderInfo = toml::from_str(azure_provider_toml).unwrap();
@@ -1,4 +1,5 @@
 use crate::bash::extract_bash_command;
+use crate::bash::extract_fish_or_nu_command;
 use crate::bash::try_parse_shell;
 use crate::bash::try_parse_word_only_commands_sequence;
 use crate::powershell::extract_powershell_command;
@@ -14,7 +15,9 @@ pub fn shlex_join(tokens: &[String]) -> String {
 
 /// Extracts the shell and script from a command, regardless of platform
 pub fn extract_shell_command(command: &[String]) -> Option<(&str, &str)> {
-    extract_bash_command(command).or_else(|| extract_powershell_command(command))
+    extract_bash_command(command)
+        .or_else(|| extract_powershell_command(command))
+        .or_else(|| extract_fish_or_nu_command(command))
 }
 
 /// DO NOT REVIEW THIS CODE BY HAND
@@ -12,15 +12,27 @@
 //!     current working directory (inclusive) and concatenate their contents in
 //!     that order.
 //! 3.  We do **not** walk past the Git root.
+//!
+//! Docs in directories below the current working directory are loaded lazily:
+//! [`NestedProjectDocs`] queues them when the agent reads or edits a file in
+//! their scope, and they are sent with the next model request. Like the docs
+//! above, their `when:` sections are re-evaluated on every turn.
 
 use crate::config::Config;
 use crate::features::Feature;
 use crate::skills::SkillMetadata;
 use crate::skills::render_skills_section;
+use codex_protocol::config_types::ModeKind;
+use dirs::home_dir;
 use dunce::canonicalize as normalize_path;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::path::Path;
 use std::path::PathBuf;
 use tokio::io::AsyncReadExt;
 use tracing::error;
+use wildmatch::WildMatchPattern;
 
 pub(crate) const HIERARCHICAL_AGENTS_MESSAGE: &str =
     include_str!("../hierarchical_agents_message.md");
@@ -34,112 +46,526 @@ pub const LOCAL_PROJECT_DOC_FILENAME: &str = "AGENTS.override.md";
 /// be concatenated with the following separator.
 const PROJECT_DOC_SEPARATOR: &str = "\n\n--- project-doc ---\n\n";
 
-/// Combines `Config::instructions` and `AGENTS.md` (if present) into a single
-/// string of instructions.
+/// Combines `Config::instructions` and `AGENTS.md` (if present) into user
+/// instructions that are rendered for each turn with
+/// [`UserInstructionsTemplate::render`].
 pub(crate) async fn get_user_instructions(
     config: &Config,
     skills: Option<&[SkillMetadata]>,
-) -> Option<String> {
-    let project_docs = read_project_docs(config).await;
-
-    let mut output = String::new();
-
-    if let Some(instructions) = config.user_instructions.clone() {
-        output.push_str(&instructions);
+) -> Option<UserInstructionsTemplate> {
+    let mut loader = InstructionLoader::new(
+        config.project_doc_max_bytes as u64,
+        IncludeScope::new(config),
+    );
+
+    if let Some(instructions) = config.user_instructions.as_deref() {
+        loader
+            .add_text(
+                instructions,
+                &config.codex_home.join(DEFAULT_PROJECT_DOC_FILENAME),
+                SectionSource::UserInstructions,
+                &[],
+            )
+            .await;
     }
 
-    match project_docs {
-        Ok(Some(docs)) => {
-            if !output.is_empty() {
-                output.push_str(PROJECT_DOC_SEPARATOR);
-            }
-            output.push_str(&docs);
-        }
-        Ok(None) => {}
-        Err(e) => {
-            error!("error trying to find project doc: {e:#}");
-        }
-    };
+    if let Err(e) = read_project_docs(config, &mut loader).await {
+        error!("error trying to find project doc: {e:#}");
+    }
 
     let skills_section = skills.and_then(render_skills_section);
     if let Some(skills_section) = skills_section {
-        if !output.is_empty() {
-            output.push_str("\n\n");
-        }
-        output.push_str(&skills_section);
+        loader.push_section(SectionSource::Skills, &[], skills_section);
     }
 
     if config.features.enabled(Feature::ChildAgentsMd) {
-        if !output.is_empty() {
-            output.push_str("\n\n");
-        }
-        output.push_str(HIERARCHICAL_AGENTS_MESSAGE);
+        loader.push_section(
+            SectionSource::HierarchicalAgents,
+            &[],
+            HIERARCHICAL_AGENTS_MESSAGE.to_string(),
+        );
     }
 
-    if !output.is_empty() {
-        Some(output)
-    } else {
+    let template = loader.into_template();
+    if template.sections.is_empty() {
         None
+    } else {
+        Some(template)
     }
 }
 
-/// Attempt to locate and load the project documentation.
+/// Attempt to locate and load the project documentation into `loader`.
 ///
-/// On success returns `Ok(Some(contents))` where `contents` is the
-/// concatenation of all discovered docs. If no documentation file is found the
-/// function returns `Ok(None)`. Unexpected I/O failures bubble up as `Err` so
-/// callers can decide how to handle them.
-pub async fn read_project_docs(config: &Config) -> std::io::Result<Option<String>> {
-    let max_total = config.project_doc_max_bytes;
-
-    if max_total == 0 {
-        return Ok(None);
+/// Docs are read from the repository root down to the current working
+/// directory until `project_doc_max_bytes` is used up. Unexpected I/O
+/// failures bubble up as `Err` so callers can decide how to handle them.
+async fn read_project_docs(config: &Config, loader: &mut InstructionLoader) -> std::io::Result<()> {
+    if config.project_doc_max_bytes == 0 {
+        return Ok(());
     }
 
     let paths = discover_project_doc_paths(config)?;
-    if paths.is_empty() {
-        return Ok(None);
+    for (index, p) in paths.iter().enumerate() {
+        if loader.remaining == 0 {
+            break;
+        }
+        loader
+            .load_file(p, SectionSource::ProjectDoc(index), &[])
+            .await?;
     }
+    Ok(())
+}
 
-    let mut remaining: u64 = max_total as u64;
-    let mut parts: Vec<String> = Vec::new();
+/// What `when:` frontmatter in instruction files is matched against.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct InstructionContext<'a> {
+    pub model: &'a str,
+    pub os: &'a str,
+    pub collaboration_mode: ModeKind,
+}
 
-    for p in paths {
-        if remaining == 0 {
-            break;
+impl<'a> InstructionContext<'a> {
+    pub(crate) fn new(model: &'a str, collaboration_mode: ModeKind) -> Self {
+        Self {
+            model,
+            os: std::env::consts::OS,
+            collaboration_mode,
         }
+    }
+}
 
-        let file = match tokio::fs::File::open(&p).await {
-            Ok(f) => f,
-            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
-            Err(e) => return Err(e),
+/// User instructions assembled at session start. Sections guarded by `when:`
+/// frontmatter are kept apart so that each turn only renders the ones that
+/// apply to its model, OS, and collaboration mode.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct UserInstructionsTemplate {
+    sections: Vec<InstructionSection>,
+    project_doc_bytes: u64,
+}
+
+impl UserInstructionsTemplate {
+    /// Bytes of project docs (and their includes) read into this template,
+    /// counted against `project_doc_max_bytes`.
+    pub(crate) fn project_doc_bytes(&self) -> u64 {
+        self.project_doc_bytes
+    }
+
+    /// Instructions for a turn running in `context`, or `None` when no
+    /// section applies.
+    pub(crate) fn render(&self, context: &InstructionContext<'_>) -> Option<String> {
+        let mut output = String::new();
+        let mut previous_source: Option<SectionSource> = None;
+        let mut saw_project_doc = false;
+        for section in &self.sections {
+            if !section
+                .conditions
+                .iter()
+                .all(|condition| condition.matches(context))
+            {
+                continue;
+            }
+            if let Some(previous) = previous_source
+                && previous != section.source
+            {
+                match section.source {
+                    SectionSource::ProjectDoc(_) if !saw_project_doc => {
+                        output.push_str(PROJECT_DOC_SEPARATOR);
+                    }
+                    _ => output.push_str("\n\n"),
+                }
+            }
+            if matches!(section.source, SectionSource::ProjectDoc(_)) {
+                saw_project_doc = true;
+            }
+            previous_source = Some(section.source);
+            output.push_str(&section.text);
+        }
+
+        if output.is_empty() {
+            None
+        } else {
+            Some(output)
+        }
+    }
+}
+
+impl From<String> for UserInstructionsTemplate {
+    fn from(text: String) -> Self {
+        Self {
+            sections: vec![InstructionSection {
+                source: SectionSource::UserInstructions,
+                conditions: Vec::new(),
+                text,
+            }],
+            project_doc_bytes: 0,
+        }
+    }
+}
+
+/// Top-level source of an [`InstructionSection`]. Sections from the same
+/// source are concatenated as-is; different sources are separated by a blank
+/// line, or by [`PROJECT_DOC_SEPARATOR`] before the first project doc.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SectionSource {
+    UserInstructions,
+    ProjectDoc(usize),
+    Skills,
+    HierarchicalAgents,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct InstructionSection {
+    source: SectionSource,
+    /// Conditions of the file the text came from and of every file that
+    /// included it; all of them must match.
+    conditions: Vec<InstructionCondition>,
+    text: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct InstructionFrontmatter {
+    #[serde(default)]
+    when: Option<InstructionCondition>,
+}
+
+/// The `when:` frontmatter of an instruction file, e.g.
+/// `when: { model: "gpt-5*", os: linux, collaboration_mode: plan }`. Every
+/// key that is set must match; a key holds one glob or a list of them.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct InstructionCondition {
+    #[serde(default)]
+    model: Option<ConditionPatterns>,
+    #[serde(default)]
+    os: Option<ConditionPatterns>,
+    #[serde(default)]
+    collaboration_mode: Option<ConditionPatterns>,
+}
+
+impl InstructionCondition {
+    fn matches(&self, context: &InstructionContext<'_>) -> bool {
+        let matches = |patterns: &Option<ConditionPatterns>, value: &str| {
+            patterns
+                .as_ref()
+                .is_none_or(|patterns| patterns.matches(value))
         };
+        matches(&self.model, context.model)
+            && matches(&self.os, context.os)
+            && matches(
+                &self.collaboration_mode,
+                mode_kind_name(context.collaboration_mode),
+            )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(untagged)]
+enum ConditionPatterns {
+    One(String),
+    Any(Vec<String>),
+}
+
+impl ConditionPatterns {
+    fn matches(&self, value: &str) -> bool {
+        let patterns = match self {
+            Self::One(pattern) => std::slice::from_ref(pattern),
+            Self::Any(patterns) => patterns.as_slice(),
+        };
+        patterns.iter().any(|pattern| {
+            WildMatchPattern::<'*', '?'>::new_case_insensitive(pattern).matches(value)
+        })
+    }
+}
 
-        let size = file.metadata().await?.len();
-        let mut reader = tokio::io::BufReader::new(file).take(remaining);
-        let mut data: Vec<u8> = Vec::new();
-        reader.read_to_end(&mut data).await?;
-
-        if size > remaining {
-            tracing::warn!(
-                "Project doc `{}` exceeds remaining budget ({} bytes) - truncating.",
-                p.display(),
-                remaining,
-            );
+/// The name `collaboration_mode` conditions are matched against, as spelled
+/// in config.
+fn mode_kind_name(mode: ModeKind) -> &'static str {
+    match mode {
+        ModeKind::Plan => "plan",
+        ModeKind::Default => "default",
+        ModeKind::PairProgramming => "pair_programming",
+        ModeKind::Execute => "execute",
+    }
+}
+
+/// Splits `text` into its `when:` condition and body. Frontmatter without a
+/// `when` key, or that fails to parse, is left in the body.
+fn split_frontmatter<'a>(text: &'a str, origin: &Path) -> (Option<InstructionCondition>, &'a str) {
+    let Some(rest) = text
+        .strip_prefix("---\n")
+        .or_else(|| text.strip_prefix("---\r\n"))
+    else {
+        return (None, text);
+    };
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        if line.trim() == "---" {
+            let yaml = &rest[..offset];
+            let body = &rest[offset + line.len()..];
+            return match serde_yaml::from_str::<InstructionFrontmatter>(yaml) {
+                Ok(InstructionFrontmatter {
+                    when: Some(condition),
+                }) => (Some(condition), body),
+                Ok(InstructionFrontmatter { when: None }) => (None, text),
+                Err(e) => {
+                    tracing::warn!(
+                        "Ignoring invalid frontmatter in `{}`: {e}",
+                        origin.display()
+                    );
+                    (None, text)
+                }
+            };
         }
+        offset += line.len();
+    }
+    (None, text)
+}
 
-        let text = String::from_utf8_lossy(&data).to_string();
-        if !text.trim().is_empty() {
-            parts.push(text);
-            remaining = remaining.saturating_sub(data.len() as u64);
+/// The target of an `@include <path>` line, if `line` is one.
+fn include_target(line: &str) -> Option<&str> {
+    let target = line.trim().strip_prefix("@include")?;
+    if !target.starts_with(char::is_whitespace) {
+        return None;
+    }
+    let target = target.trim();
+    let target = target
+        .strip_prefix('"')
+        .and_then(|target| target.strip_suffix('"'))
+        .unwrap_or(target);
+    (!target.is_empty()).then_some(target)
+}
+
+/// Resolves an `@include` target against the directory of the including
+/// file; `~/` expands to the home directory.
+fn resolve_include(target: &str, base_dir: &Path) -> PathBuf {
+    if let Some(rest) = target.strip_prefix("~/")
+        && let Some(home) = home_dir()
+    {
+        return home.join(rest);
+    }
+    base_dir.join(target)
+}
+
+/// Where `@include` targets may live: inside the project root or
+/// `codex_home`, and outside every `sandbox_deny_read` path.
+#[derive(Debug, Clone, Default)]
+struct IncludeScope {
+    roots: Vec<PathBuf>,
+    denied: Vec<PathBuf>,
+}
+
+impl IncludeScope {
+    fn new(config: &Config) -> Self {
+        let canonical = |path: PathBuf| normalize_path(&path).unwrap_or(path);
+        let mut roots = vec![canonical(config.codex_home.clone())];
+        match project_doc_search_dirs(&config.cwd) {
+            Ok(dirs) => roots.extend(dirs.into_iter().next().map(canonical)),
+            Err(e) => error!("error trying to find project doc root: {e:#}"),
         }
+        let denied = config
+            .sandbox_policy
+            .get()
+            .get_deny_read_paths_with_cwd(&config.cwd)
+            .into_iter()
+            .map(|path| canonical(path.into_path_buf()))
+            .collect();
+        Self { roots, denied }
     }
 
-    if parts.is_empty() {
-        Ok(None)
-    } else {
-        Ok(Some(parts.join("\n\n")))
+    /// The canonical path of `path` when an include may read it, otherwise
+    /// why not.
+    fn check(&self, path: &Path) -> Result<PathBuf, &'static str> {
+        let canonical = normalize_path(path).map_err(|_| "it does not exist")?;
+        if !self.roots.iter().any(|root| canonical.starts_with(root)) {
+            return Err("it is outside the project and CODEX_HOME");
+        }
+        if self
+            .denied
+            .iter()
+            .any(|denied| canonical.starts_with(denied))
+        {
+            return Err("it is listed in sandbox_deny_read");
+        }
+        Ok(canonical)
+    }
+}
+
+/// Reads instruction files into sections, expanding `@include` lines. Every
+/// file read, included ones too, counts against the byte budget.
+struct InstructionLoader {
+    remaining: u64,
+    include_scope: IncludeScope,
+    /// Canonical paths of the files being expanded, to detect include cycles.
+    include_stack: Vec<PathBuf>,
+    sections: Vec<InstructionSection>,
+    bytes_read: u64,
+    truncated: bool,
+}
+
+impl InstructionLoader {
+    fn new(budget: u64, include_scope: IncludeScope) -> Self {
+        Self {
+            remaining: budget,
+            include_scope,
+            include_stack: Vec::new(),
+            sections: Vec::new(),
+            bytes_read: 0,
+            truncated: false,
+        }
+    }
+
+    fn into_template(self) -> UserInstructionsTemplate {
+        UserInstructionsTemplate {
+            sections: self.sections,
+            project_doc_bytes: self.bytes_read,
+        }
+    }
+
+    fn push_section(
+        &mut self,
+        source: SectionSource,
+        conditions: &[InstructionCondition],
+        text: String,
+    ) {
+        if text.trim().is_empty() {
+            return;
+        }
+        self.sections.push(InstructionSection {
+            source,
+            conditions: conditions.to_vec(),
+            text,
+        });
+    }
+
+    /// Reads `path` within the remaining budget and adds its sections.
+    /// Missing and blank files are skipped.
+    async fn load_file(
+        &mut self,
+        path: &Path,
+        source: SectionSource,
+        conditions: &[InstructionCondition],
+    ) -> std::io::Result<()> {
+        let canonical = normalize_path(path).unwrap_or_else(|_| path.to_path_buf());
+        if self.include_stack.contains(&canonical) {
+            tracing::warn!("Skipping cyclic @include of `{}`", path.display());
+            return Ok(());
+        }
+        if self.remaining == 0 {
+            return Ok(());
+        }
+        let Some(doc) = read_project_doc(path, self.remaining).await? else {
+            return Ok(());
+        };
+        self.remaining = self.remaining.saturating_sub(doc.bytes);
+        self.bytes_read += doc.bytes;
+        self.truncated |= doc.truncated;
+
+        self.include_stack.push(canonical);
+        self.add_text(&doc.text, path, source, conditions).await;
+        self.include_stack.pop();
+        Ok(())
+    }
+
+    /// Adds `text`, the contents of `origin`, replacing each `@include` line
+    /// outside code fences with the sections of the file it names.
+    async fn add_text(
+        &mut self,
+        text: &str,
+        origin: &Path,
+        source: SectionSource,
+        conditions: &[InstructionCondition],
+    ) {
+        let base_dir = origin.parent().unwrap_or(origin);
+        let (condition, body) = split_frontmatter(text, origin);
+        let mut conditions = conditions.to_vec();
+        conditions.extend(condition);
+
+        let mut pending = String::new();
+        let mut in_code_fence = false;
+        for line in body.split_inclusive('\n') {
+            let trimmed = line.trim_start();
+            if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+                in_code_fence = !in_code_fence;
+            }
+            let Some(target) = include_target(line).filter(|_| !in_code_fence) else {
+                pending.push_str(line);
+                continue;
+            };
+
+            self.push_section(source, &conditions, std::mem::take(&mut pending));
+            let included = resolve_include(target, base_dir);
+            let first_included = self.sections.len();
+            match self.include_scope.check(&included) {
+                Ok(canonical) => {
+                    if let Err(e) = Box::pin(self.load_file(&canonical, source, &conditions)).await
+                    {
+                        tracing::warn!("Failed to read @include `{}`: {e:#}", included.display());
+                    }
+                }
+                Err(reason) => {
+                    tracing::warn!("Skipping @include `{}`: {reason}", included.display());
+                }
+            }
+            // Keep the line break that ended the `@include` line.
+            if line.ends_with('\n')
+                && let Some(last) = self
+                    .sections
+                    .get_mut(first_included..)
+                    .and_then(<[InstructionSection]>::last_mut)
+                && !last.text.ends_with('\n')
+            {
+                last.text.push('\n');
+            }
+        }
+        self.push_section(source, &conditions, pending);
+    }
+}
+
+/// Text of one instruction file, read within the `project_doc_max_bytes`
+/// budget.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ProjectDocContents {
+    text: String,
+    /// Bytes read from the file, counted against the budget.
+    bytes: u64,
+    truncated: bool,
+}
+
+/// Reads at most `remaining` bytes of `path`. Returns `Ok(None)` for missing
+/// or blank files.
+async fn read_project_doc(
+    path: &Path,
+    remaining: u64,
+) -> std::io::Result<Option<ProjectDocContents>> {
+    let file = match tokio::fs::File::open(path).await {
+        Ok(f) => f,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e),
+    };
+
+    let size = file.metadata().await?.len();
+    let mut reader = tokio::io::BufReader::new(file).take(remaining);
+    let mut data: Vec<u8> = Vec::new();
+    reader.read_to_end(&mut data).await?;
+
+    let truncated = size > remaining;
+    if truncated {
+        tracing::warn!(
+            "Project doc `{}` exceeds remaining budget ({} bytes) - truncating.",
+            path.display(),
+            remaining,
+        );
+    }
+
+    let text = String::from_utf8_lossy(&data).to_string();
+    if text.trim().is_empty() {
+        return Ok(None);
     }
+    Ok(Some(ProjectDocContents {
+        text,
+        bytes: data.len() as u64,
+        truncated,
+    }))
 }
 
 /// Discover the list of AGENTS.md files using the same search rules as
@@ -148,7 +574,22 @@ pub async fn read_project_docs(config: &Config) -> std::io::Result<Option<String
 /// directory (inclusive). Symlinks are allowed. When `project_doc_max_bytes`
 /// is zero, returns an empty list.
 pub fn discover_project_doc_paths(config: &Config) -> std::io::Result<Vec<PathBuf>> {
-    let mut dir = config.cwd.clone();
+    let search_dirs = project_doc_search_dirs(&config.cwd)?;
+    let candidate_filenames = candidate_filenames(config);
+    let mut found: Vec<PathBuf> = Vec::new();
+    for d in search_dirs {
+        if let Some(doc) = find_project_doc(&d, &candidate_filenames)? {
+            found.push(doc);
+        }
+    }
+
+    Ok(found)
+}
+
+/// Directories searched for project docs at session start, from the project
+/// root (the Git root, or `cwd` outside a repository) down to `cwd`.
+fn project_doc_search_dirs(cwd: &Path) -> std::io::Result<Vec<PathBuf>> {
+    let mut dir = cwd.to_path_buf();
     if let Ok(canon) = normalize_path(&dir) {
         dir = canon;
     }
@@ -189,30 +630,307 @@ pub fn discover_project_doc_paths(config: &Config) -> std::io::Result<Vec<PathBu
         }
         dirs
     } else {
-        vec![config.cwd.clone()]
+        vec![cwd.to_path_buf()]
     };
 
-    let mut found: Vec<PathBuf> = Vec::new();
-    let candidate_filenames = candidate_filenames(config);
-    for d in search_dirs {
-        for name in &candidate_filenames {
-            let candidate = d.join(name);
-            match std::fs::symlink_metadata(&candidate) {
-                Ok(md) => {
-                    let ft = md.file_type();
-                    // Allow regular files and symlinks; opening will later fail for dangling links.
-                    if ft.is_file() || ft.is_symlink() {
-                        found.push(candidate);
-                        break;
-                    }
+    Ok(search_dirs)
+}
+
+/// The first of `candidate_filenames` present in `dir`.
+fn find_project_doc<S: AsRef<str>>(
+    dir: &Path,
+    candidate_filenames: &[S],
+) -> std::io::Result<Option<PathBuf>> {
+    for name in candidate_filenames {
+        let candidate = dir.join(name.as_ref());
+        match std::fs::symlink_metadata(&candidate) {
+            Ok(md) => {
+                let ft = md.file_type();
+                // Allow regular files and symlinks; opening will later fail for dangling links.
+                if ft.is_file() || ft.is_symlink() {
+                    return Ok(Some(candidate));
                 }
-                Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
-                Err(e) => return Err(e),
             }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(e),
         }
     }
+    Ok(None)
+}
 
-    Ok(found)
+/// Project docs below the initial search path, discovered as the agent reads
+/// or edits files. Each directory is checked once per session, and loaded
+/// docs share the `project_doc_max_bytes` budget with the initial ones.
+#[derive(Debug, Default)]
+pub(crate) struct NestedProjectDocs {
+    /// `None` when project docs are disabled or the root could not be found.
+    root: Option<PathBuf>,
+    candidate_filenames: Vec<String>,
+    checked_dirs: HashSet<PathBuf>,
+    pending: Vec<PathBuf>,
+    remaining_bytes: u64,
+    include_scope: IncludeScope,
+    loaded: Vec<LoadedNestedDoc>,
+}
+
+/// A nested doc in the conversation, rendered again on every turn.
+#[derive(Debug)]
+struct LoadedNestedDoc {
+    path: PathBuf,
+    template: UserInstructionsTemplate,
+    /// Text last sent to the model, if any section applied so far.
+    sent: Option<String>,
+}
+
+impl NestedProjectDocs {
+    /// `initial_bytes` is what the docs loaded at session start consumed, see
+    /// [`UserInstructionsTemplate::project_doc_bytes`].
+    pub(crate) fn new(config: &Config, initial_bytes: u64) -> Self {
+        if config.project_doc_max_bytes == 0 {
+            return Self::default();
+        }
+        let search_dirs = match project_doc_search_dirs(&config.cwd) {
+            Ok(dirs) => dirs,
+            Err(e) => {
+                error!("error trying to find project doc root: {e:#}");
+                return Self::default();
+            }
+        };
+        let Some(root) = search_dirs.first() else {
+            return Self::default();
+        };
+        let root = normalize_path(root).unwrap_or_else(|_| root.clone());
+        Self {
+            root: Some(root),
+            candidate_filenames: candidate_filenames(config)
+                .into_iter()
+                .map(str::to_string)
+                .collect(),
+            checked_dirs: search_dirs
+                .iter()
+                .map(|dir| normalize_path(dir).unwrap_or_else(|_| dir.clone()))
+                .collect(),
+            pending: Vec::new(),
+            remaining_bytes: (config.project_doc_max_bytes as u64).saturating_sub(initial_bytes),
+            include_scope: IncludeScope::new(config),
+            loaded: Vec::new(),
+        }
+    }
+
+    /// Queues every doc between the project root and `path` that has not
+    /// been considered yet. Paths outside the root are ignored.
+    pub(crate) fn note_path(&mut self, path: &Path) {
+        let Some(root) = self.root.as_ref() else {
+            return;
+        };
+        let path = normalize_path(path).unwrap_or_else(|_| path.to_path_buf());
+        let dir = if path.is_dir() {
+            path.as_path()
+        } else {
+            match path.parent() {
+                Some(parent) => parent,
+                None => return,
+            }
+        };
+        let Ok(relative) = dir.strip_prefix(root) else {
+            return;
+        };
+        let mut current = root.clone();
+        for component in relative.components() {
+            current.push(component);
+            if !self.checked_dirs.insert(current.clone()) {
+                continue;
+            }
+            match find_project_doc(&current, &self.candidate_filenames) {
+                Ok(Some(doc)) => self.pending.push(doc),
+                Ok(None) => {}
+                Err(e) => error!("error trying to find project doc: {e:#}"),
+            }
+        }
+    }
+
+    /// Records docs that are already part of the conversation, e.g. when
+    /// resuming a session, given their paths and the bytes they used. Read
+    /// them again with [`RestoredProjectDocs::load`] and hand them back with
+    /// [`NestedProjectDocs::add_restored`] so their `when:` sections keep being
+    /// evaluated on later turns.
+    pub(crate) fn mark_loaded(&mut self, docs: &[(PathBuf, u64)]) -> RestoredProjectDocs {
+        let mut restored = Vec::new();
+        for (path, bytes) in docs {
+            let path = normalize_path(path).unwrap_or_else(|_| path.clone());
+            if let Some(dir) = path.parent() {
+                self.checked_dirs.insert(dir.to_path_buf());
+            }
+            self.pending.retain(|pending| pending != &path);
+            self.remaining_bytes = self.remaining_bytes.saturating_sub(*bytes);
+            restored.push((path, *bytes));
+        }
+        RestoredProjectDocs {
+            docs: restored,
+            include_scope: self.include_scope.clone(),
+        }
+    }
+
+    /// Adds docs read by [`RestoredProjectDocs::load`] to the conversation.
+    /// `sent` maps a doc's directory to the text the restored history last
+    /// carried for it, so a doc is only resent once its text changes.
+    pub(crate) fn add_restored(
+        &mut self,
+        docs: &[NestedProjectDoc],
+        sent: &HashMap<String, String>,
+    ) {
+        self.loaded.extend(docs.iter().map(|doc| {
+            let directory = doc.path.parent().unwrap_or(doc.path.as_path());
+            LoadedNestedDoc {
+                path: doc.path.clone(),
+                template: doc.template.clone(),
+                sent: sent.get(directory.to_string_lossy().as_ref()).cloned(),
+            }
+        }));
+    }
+
+    /// Takes the queued docs together with the remaining budget, so they can
+    /// be read without holding the session lock. Hand the unused budget back
+    /// with [`NestedProjectDocs::return_budget`].
+    pub(crate) fn take_pending(&mut self) -> PendingProjectDocs {
+        PendingProjectDocs {
+            paths: std::mem::take(&mut self.pending),
+            remaining_bytes: std::mem::take(&mut self.remaining_bytes),
+            include_scope: self.include_scope.clone(),
+        }
+    }
+
+    /// Returns the budget `pending` did not use.
+    pub(crate) fn return_budget(&mut self, pending: PendingProjectDocs) {
+        self.remaining_bytes += pending.remaining_bytes;
+    }
+
+    /// Adds docs read by [`PendingProjectDocs::load`] to the conversation.
+    pub(crate) fn add_loaded(&mut self, docs: &[NestedProjectDoc]) {
+        self.loaded.extend(docs.iter().map(|doc| LoadedNestedDoc {
+            path: doc.path.clone(),
+            template: doc.template.clone(),
+            sent: None,
+        }));
+    }
+
+    /// Renders the loaded docs for a turn running in `context` and returns
+    /// the ones whose text differs from what was last sent, like top-level
+    /// docs when a model or collaboration mode change selects different
+    /// `when:` sections.
+    pub(crate) fn render_updates(
+        &mut self,
+        context: &InstructionContext<'_>,
+    ) -> Vec<(PathBuf, String)> {
+        let mut updates = Vec::new();
+        for doc in &mut self.loaded {
+            let Some(text) = doc.template.render(context) else {
+                continue;
+            };
+            if doc.sent.as_ref() != Some(&text) {
+                doc.sent = Some(text.clone());
+                updates.push((doc.path.clone(), text));
+            }
+        }
+        updates
+    }
+}
+
+/// Docs taken from [`NestedProjectDocs`], with the budget they may use.
+#[derive(Debug, Default)]
+pub(crate) struct PendingProjectDocs {
+    paths: Vec<PathBuf>,
+    remaining_bytes: u64,
+    include_scope: IncludeScope,
+}
+
+impl PendingProjectDocs {
+    /// Reads the docs, shallowest first, within the budget.
+    pub(crate) async fn load(&mut self) -> Vec<NestedProjectDoc> {
+        let mut loaded = Vec::new();
+        for path in std::mem::take(&mut self.paths) {
+            if self.remaining_bytes == 0 {
+                break;
+            }
+            let mut loader =
+                InstructionLoader::new(self.remaining_bytes, self.include_scope.clone());
+            if let Err(e) = loader
+                .load_file(&path, SectionSource::ProjectDoc(0), &[])
+                .await
+            {
+                error!(
+                    "error trying to read project doc `{}`: {e:#}",
+                    path.display()
+                );
+                continue;
+            }
+            self.remaining_bytes = loader.remaining;
+            if loader.bytes_read == 0 {
+                continue;
+            }
+            let truncated = loader.truncated;
+            loaded.push(NestedProjectDoc {
+                path,
+                template: loader.into_template(),
+                truncated,
+            });
+        }
+        loaded
+    }
+}
+
+/// Docs recorded by [`NestedProjectDocs::mark_loaded`], to be read again.
+#[derive(Debug, Default)]
+pub(crate) struct RestoredProjectDocs {
+    docs: Vec<(PathBuf, u64)>,
+    include_scope: IncludeScope,
+}
+
+impl RestoredProjectDocs {
+    /// Reads the docs again, each within the bytes it used when it was first
+    /// loaded. Docs that can no longer be read are skipped.
+    pub(crate) async fn load(self) -> Vec<NestedProjectDoc> {
+        let mut loaded = Vec::new();
+        for (path, bytes) in self.docs {
+            let mut loader = InstructionLoader::new(bytes, self.include_scope.clone());
+            if let Err(e) = loader
+                .load_file(&path, SectionSource::ProjectDoc(0), &[])
+                .await
+            {
+                error!(
+                    "error trying to read project doc `{}`: {e:#}",
+                    path.display()
+                );
+                continue;
+            }
+            if loader.bytes_read == 0 {
+                continue;
+            }
+            let truncated = loader.truncated;
+            loaded.push(NestedProjectDoc {
+                path,
+                template: loader.into_template(),
+                truncated,
+            });
+        }
+        loaded
+    }
+}
+
+/// A nested project doc read within the `project_doc_max_bytes` budget,
+/// with its includes expanded and its `when:` sections kept apart.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct NestedProjectDoc {
+    pub path: PathBuf,
+    pub template: UserInstructionsTemplate,
+    pub truncated: bool,
+}
+
+impl NestedProjectDoc {
+    /// Bytes read from the file and its includes, counted against the budget.
+    pub(crate) fn bytes(&self) -> u64 {
+        self.template.project_doc_bytes()
+    }
 }
 
 fn candidate_filenames<'a>(config: &'a Config) -> Vec<&'a str> {
@@ -236,7 +954,10 @@ fn candidate_filenames<'a>(config: &'a Config) -> Vec<&'a str> {
 mod tests {
     use super::*;
     use crate::config::ConfigBuilder;
+    use crate::config::Constrained;
+    use crate::protocol::SandboxPolicy;
     use crate::skills::load_skills;
+    use pretty_assertions::assert_eq;
     use std::fs;
     use std::path::PathBuf;
     use tempfile::TempDir;
@@ -261,6 +982,33 @@ mod tests {
         config
     }
 
+    fn default_context() -> InstructionContext<'static> {
+        InstructionContext::new("gpt-5.2-codex", ModeKind::Default)
+    }
+
+    /// Loads the queued nested docs the way the session does and returns
+    /// what a turn running in `context` sends.
+    async fn load_pending(
+        nested: &mut NestedProjectDocs,
+        context: &InstructionContext<'_>,
+    ) -> Vec<(PathBuf, String)> {
+        let mut pending = nested.take_pending();
+        let docs = pending.load().await;
+        nested.return_budget(pending);
+        nested.add_loaded(&docs);
+        nested.render_updates(context)
+    }
+
+    /// User instructions as rendered for a default-mode turn.
+    async fn user_instructions(
+        config: &Config,
+        skills: Option<&[SkillMetadata]>,
+    ) -> Option<String> {
+        get_user_instructions(config, skills)
+            .await
+            .and_then(|instructions| instructions.render(&default_context()))
+    }
+
     async fn make_config_with_fallback(
         root: &TempDir,
         limit: usize,
@@ -280,7 +1028,7 @@ mod tests {
     async fn no_doc_file_returns_none() {
         let tmp = tempfile::tempdir().expect("tempdir");
 
-        let res = get_user_instructions(&make_config(&tmp, 4096, None).await, None).await;
+        let res = user_instructions(&make_config(&tmp, 4096, None).await, None).await;
         assert!(
             res.is_none(),
             "Expected None when AGENTS.md is absent and no system instructions provided"
@@ -294,7 +1042,7 @@ mod tests {
         let tmp = tempfile::tempdir().expect("tempdir");
         fs::write(tmp.path().join("AGENTS.md"), "hello world").unwrap();
 
-        let res = get_user_instructions(&make_config(&tmp, 4096, None).await, None)
+        let res = user_instructions(&make_config(&tmp, 4096, None).await, None)
             .await
             .expect("doc expected");
 
@@ -313,7 +1061,7 @@ mod tests {
         let huge = "A".repeat(LIMIT * 2); // 2 KiB
         fs::write(tmp.path().join("AGENTS.md"), &huge).unwrap();
 
-        let res = get_user_instructions(&make_config(&tmp, LIMIT, None).await, None)
+        let res = user_instructions(&make_config(&tmp, LIMIT, None).await, None)
             .await
             .expect("doc expected");
 
@@ -345,9 +1093,7 @@ mod tests {
         let mut cfg = make_config(&repo, 4096, None).await;
         cfg.cwd = nested;
 
-        let res = get_user_instructions(&cfg, None)
-            .await
-            .expect("doc expected");
+        let res = user_instructions(&cfg, None).await.expect("doc expected");
         assert_eq!(res, "root level doc");
     }
 
@@ -357,13 +1103,147 @@ mod tests {
         let tmp = tempfile::tempdir().expect("tempdir");
         fs::write(tmp.path().join("AGENTS.md"), "something").unwrap();
 
-        let res = get_user_instructions(&make_config(&tmp, 0, None).await, None).await;
+        let res = user_instructions(&make_config(&tmp, 0, None).await, None).await;
         assert!(
             res.is_none(),
             "With limit 0 the function should return None"
         );
     }
 
+    /// `@include` lines are replaced by the named file, resolved against the
+    /// including file, except inside code fences.
+    #[tokio::test]
+    async fn include_directives_expand_relative_to_the_including_file() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        std::fs::write(tmp.path().join(".git"), "gitdir: /path/to/actual/git/dir\n").unwrap();
+        let repo = tmp.path().join("repo");
+        std::fs::create_dir_all(&repo).unwrap();
+        std::fs::create_dir_all(tmp.path().join("shared")).unwrap();
+        fs::write(tmp.path().join("shared/guidelines.md"), "shared rules").unwrap();
+        fs::write(
+            repo.join("AGENTS.md"),
+            "intro\n@include ../shared/guidelines.md\n```\n@include missing.md\n```\noutro",
+        )
+        .unwrap();
+
+        let mut cfg = make_config(&tmp, 4096, None).await;
+        cfg.cwd = repo;
+
+        let res = user_instructions(&cfg, None).await.expect("doc expected");
+        assert_eq!(
+            res,
+            "intro\nshared rules\n```\n@include missing.md\n```\noutro"
+        );
+    }
+
+    /// Includes may only read files in the project or CODEX_HOME, and never
+    /// `sandbox_deny_read` paths.
+    #[tokio::test]
+    async fn includes_outside_the_project_or_denied_are_skipped() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let repo = tmp.path().join("repo");
+        std::fs::create_dir_all(&repo).unwrap();
+        fs::write(tmp.path().join("outside.md"), "outside").unwrap();
+        fs::write(repo.join(".env"), "SECRET=1").unwrap();
+        fs::write(repo.join("rules.md"), "rules").unwrap();
+        fs::write(
+            repo.join("AGENTS.md"),
+            "a\n@include ../outside.md\n@include .env\n@include rules.md\n",
+        )
+        .unwrap();
+
+        let mut cfg = make_config(&tmp, 4096, None).await;
+        cfg.cwd = repo;
+        let mut policy = SandboxPolicy::new_read_only_policy();
+        policy.set_deny_read(vec![".env".to_string()]);
+        cfg.sandbox_policy = Constrained::allow_any(policy);
+
+        let res = user_instructions(&cfg, None).await.expect("doc expected");
+        assert_eq!(res, "a\nrules\n");
+    }
+
+    /// A file that includes itself, directly or indirectly, is expanded once.
+    #[tokio::test]
+    async fn include_cycles_are_skipped() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        fs::write(tmp.path().join("AGENTS.md"), "a\n@include b.md\n").unwrap();
+        fs::write(tmp.path().join("b.md"), "b\n@include AGENTS.md\n").unwrap();
+
+        let res = user_instructions(&make_config(&tmp, 4096, None).await, None)
+            .await
+            .expect("doc expected");
+        assert_eq!(res, "a\nb\n");
+    }
+
+    /// Included files share the `project_doc_max_bytes` budget.
+    #[tokio::test]
+    async fn included_files_count_against_the_byte_limit() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        // 16 bytes.
+        fs::write(tmp.path().join("AGENTS.md"), "a\n@include b.md\n").unwrap();
+        fs::write(tmp.path().join("b.md"), "bbbbbb\n").unwrap();
+
+        let res = user_instructions(&make_config(&tmp, 19, None).await, None)
+            .await
+            .expect("doc expected");
+        assert_eq!(res, "a\nbbb\n");
+    }
+
+    /// Sections with `when:` frontmatter are only rendered for turns that
+    /// match every condition.
+    #[tokio::test]
+    async fn conditional_sections_render_per_turn() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        fs::write(tmp.path().join("AGENTS.md"), "always\n@include plan.md\n").unwrap();
+        fs::write(
+            tmp.path().join("plan.md"),
+            "---\nwhen:\n  model: [\"gpt-5*\", o3]\n  os: linux\n  collaboration_mode: plan\n---\nplan only\n",
+        )
+        .unwrap();
+
+        let instructions = get_user_instructions(&make_config(&tmp, 4096, None).await, None)
+            .await
+            .expect("doc expected");
+        let plan = InstructionContext {
+            model: "gpt-5.2-codex",
+            os: "linux",
+            collaboration_mode: ModeKind::Plan,
+        };
+
+        assert_eq!(
+            instructions.render(&plan),
+            Some("always\nplan only\n".to_string())
+        );
+        assert_eq!(
+            instructions.render(&InstructionContext {
+                model: "O3",
+                ..plan
+            }),
+            Some("always\nplan only\n".to_string())
+        );
+        assert_eq!(
+            instructions.render(&InstructionContext {
+                model: "o4-mini",
+                ..plan
+            }),
+            Some("always\n".to_string())
+        );
+        assert_eq!(
+            instructions.render(&InstructionContext {
+                os: "macos",
+                ..plan
+            }),
+            Some("always\n".to_string())
+        );
+        assert_eq!(
+            instructions.render(&InstructionContext {
+                collaboration_mode: ModeKind::Default,
+                ..plan
+            }),
+            Some("always\n".to_string())
+        );
+    }
+
     /// When both system instructions *and* a project doc are present the two
     /// should be concatenated with the separator.
     #[tokio::test]
@@ -373,7 +1253,7 @@ mod tests {
 
         const INSTRUCTIONS: &str = "base instructions";
 
-        let res = get_user_instructions(&make_config(&tmp, 4096, Some(INSTRUCTIONS)).await, None)
+        let res = user_instructions(&make_config(&tmp, 4096, Some(INSTRUCTIONS)).await, None)
             .await
             .expect("should produce a combined instruction string");
 
@@ -390,8 +1270,7 @@ mod tests {
 
         const INSTRUCTIONS: &str = "some instructions";
 
-        let res =
-            get_user_instructions(&make_config(&tmp, 4096, Some(INSTRUCTIONS)).await, None).await;
+        let res = user_instructions(&make_config(&tmp, 4096, Some(INSTRUCTIONS)).await, None).await;
 
         assert_eq!(res, Some(INSTRUCTIONS.to_string()));
     }
@@ -420,12 +1299,155 @@ mod tests {
         let mut cfg = make_config(&repo, 4096, None).await;
         cfg.cwd = nested;
 
-        let res = get_user_instructions(&cfg, None)
-            .await
-            .expect("doc expected");
+        let res = user_instructions(&cfg, None).await.expect("doc expected");
         assert_eq!(res, "root doc\n\ncrate doc");
     }
 
+    /// Docs below cwd are queued once a file in their scope is touched and
+    /// share the byte budget with the docs loaded at session start.
+    #[tokio::test]
+    async fn nested_docs_load_when_a_file_below_them_is_touched() {
+        let repo = tempfile::tempdir().expect("tempdir");
+        std::fs::write(
+            repo.path().join(".git"),
+            "gitdir: /path/to/actual/git/dir\n",
+        )
+        .unwrap();
+        fs::write(repo.path().join("AGENTS.md"), "root doc").unwrap();
+        let payments = repo.path().join("services/payments");
+        std::fs::create_dir_all(payments.join("src")).unwrap();
+        fs::write(payments.join("AGENTS.md"), "payments doc").unwrap();
+        let billing = repo.path().join("services/billing");
+        std::fs::create_dir_all(&billing).unwrap();
+        fs::write(billing.join("AGENTS.md"), "billing doc").unwrap();
+
+        // 8 bytes for the root doc leave room for the first 6 of the next one.
+        let cfg = make_config(&repo, 14, None).await;
+        let initial_bytes = get_user_instructions(&cfg, None)
+            .await
+            .map_or(0, |instructions| instructions.project_doc_bytes());
+        assert_eq!(initial_bytes, 8);
+        let mut nested = NestedProjectDocs::new(&cfg, initial_bytes);
+
+        let context = default_context();
+        nested.note_path(&repo.path().join("README.md"));
+        assert_eq!(load_pending(&mut nested, &context).await, Vec::new());
+
+        let payments_doc = normalize_path(payments.join("AGENTS.md")).unwrap();
+        nested.note_path(&payments.join("src/lib.rs"));
+        nested.note_path(&payments.join("src/main.rs"));
+        let mut pending = nested.take_pending();
+        let docs = pending.load().await;
+        nested.return_budget(pending);
+        assert_eq!(
+            docs.iter()
+                .map(|doc| (doc.path.clone(), doc.bytes(), doc.truncated))
+                .collect::<Vec<_>>(),
+            vec![(payments_doc.clone(), 6, true)]
+        );
+        nested.add_loaded(&docs);
+        assert_eq!(
+            nested.render_updates(&context),
+            vec![(payments_doc, "paymen".to_string())]
+        );
+
+        nested.note_path(&billing.join("lib.rs"));
+        assert_eq!(load_pending(&mut nested, &context).await, Vec::new());
+    }
+
+    /// `when:` sections of nested docs are evaluated on every turn, and a
+    /// doc is resent once its text for the turn changes.
+    #[tokio::test]
+    async fn nested_doc_conditions_are_evaluated_per_turn() {
+        let repo = tempfile::tempdir().expect("tempdir");
+        std::fs::write(
+            repo.path().join(".git"),
+            "gitdir: /path/to/actual/git/dir
+",
+        )
+        .unwrap();
+        let api = repo.path().join("api");
+        std::fs::create_dir_all(&api).unwrap();
+        fs::write(
+            api.join("AGENTS.md"),
+            "---\nwhen:\n  collaboration_mode: plan\n---\nplan the api\n",
+        )
+        .unwrap();
+
+        let cfg = make_config(&repo, 4096, None).await;
+        let mut nested = NestedProjectDocs::new(&cfg, 0);
+        nested.note_path(&api.join("lib.rs"));
+
+        assert_eq!(
+            load_pending(&mut nested, &default_context()).await,
+            Vec::new()
+        );
+        let plan = InstructionContext::new("gpt-5.2-codex", ModeKind::Plan);
+        assert_eq!(
+            nested.render_updates(&plan),
+            vec![(
+                normalize_path(api.join("AGENTS.md")).unwrap(),
+                "plan the api\n".to_string()
+            )]
+        );
+        assert_eq!(nested.render_updates(&plan), Vec::new());
+    }
+
+    /// Nested docs recorded in a resumed rollout are read again, so their
+    /// `when:` sections still apply to later turns, and are only resent once
+    /// their text differs from what the restored history carries.
+    #[tokio::test]
+    async fn nested_docs_are_restored_on_resume() {
+        let repo = tempfile::tempdir().expect("tempdir");
+        std::fs::write(
+            repo.path().join(".git"),
+            "gitdir: /path/to/actual/git/dir\n",
+        )
+        .unwrap();
+        let api = repo.path().join("api");
+        std::fs::create_dir_all(&api).unwrap();
+        fs::write(
+            api.join("AGENTS.md"),
+            "---\nwhen:\n  collaboration_mode: plan\n---\nplan the api\n",
+        )
+        .unwrap();
+        let api_doc = normalize_path(api.join("AGENTS.md")).unwrap();
+        let cfg = make_config(&repo, 4096, None).await;
+
+        // The first run loads the doc during a default-mode turn.
+        let mut first_run = NestedProjectDocs::new(&cfg, 0);
+        first_run.note_path(&api.join("lib.rs"));
+        let mut pending = first_run.take_pending();
+        let docs = pending.load().await;
+        let bytes = docs[0].bytes();
+        let loaded = vec![(repo.path().join("api/../api/AGENTS.md"), bytes)];
+
+        let plan = InstructionContext::new("gpt-5.2-codex", ModeKind::Plan);
+        let mut resumed = NestedProjectDocs::new(&cfg, 0);
+        let docs = resumed.mark_loaded(&loaded).load().await;
+        resumed.add_restored(&docs, &HashMap::new());
+        resumed.note_path(&api.join("lib.rs"));
+        assert_eq!(
+            load_pending(&mut resumed, &default_context()).await,
+            Vec::new()
+        );
+        assert_eq!(
+            resumed.render_updates(&plan),
+            vec![(api_doc.clone(), "plan the api\n".to_string())]
+        );
+
+        // A history that already carries the plan text does not get it again.
+        let sent = HashMap::from([(
+            api_doc.parent().unwrap().to_string_lossy().into_owned(),
+            "plan the api\n".to_string(),
+        )]);
+        let mut resumed = NestedProjectDocs::new(&cfg, 0);
+        let docs = resumed.mark_loaded(&loaded).load().await;
+        resumed.add_restored(&docs, &sent);
+        assert_eq!(resumed.render_updates(&plan), Vec::new());
+        assert_eq!(resumed.take_pending().remaining_bytes, 4096 - bytes);
+    }
+
     /// AGENTS.override.md is preferred over AGENTS.md when both are present.
     #[tokio::test]
     async fn agents_local_md_preferred() {
@@ -435,7 +1457,7 @@ mod tests {
 
         let cfg = make_config(&tmp, 4096, None).await;
 
-        let res = get_user_instructions(&cfg, None)
+        let res = user_instructions(&cfg, None)
             .await
             .expect("local doc expected");
 
@@ -457,7 +1479,7 @@ mod tests {
 
         let cfg = make_config_with_fallback(&tmp, 4096, None, &["EXAMPLE.md"]).await;
 
-        let res = get_user_instructions(&cfg, None)
+        let res = user_instructions(&cfg, None)
             .await
             .expect("fallback doc expected");
 
@@ -473,7 +1495,7 @@ mod tests {
 
         let cfg = make_config_with_fallback(&tmp, 4096, None, &["EXAMPLE.md", ".example.md"]).await;
 
-        let res = get_user_instructions(&cfg, None)
+        let res = user_instructions(&cfg, None)
             .await
             .expect("AGENTS.md should win");
 
@@ -503,7 +1525,7 @@ mod tests {
         );
 
         let skills = load_skills(&cfg);
-        let res = get_user_instructions(
+        let res = user_instructions(
             &cfg,
             skills.errors.is_empty().then_some(skills.skills.as_slice()),
         )
@@ -530,7 +1552,7 @@ mod tests {
         create_skill(cfg.codex_home.clone(), "linting", "run clippy");
 
         let skills = load_skills(&cfg);
-        let res = get_user_instructions(
+        let res = user_instructions(
             &cfg,
             skills.errors.is_empty().then_some(skills.skills.as_slice()),
         )
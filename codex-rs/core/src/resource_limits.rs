@@ -0,0 +1,311 @@
+//! Enforcement of per-command [`ResourceLimits`].
+//!
+//! Limits are applied with `setrlimit(2)` right before the command itself is
+//! exec'd. On Linux, `codex-linux-sandbox` first moves itself into a cgroup v2
+//! child of the slice Codex runs in, so memory and process caps cover the
+//! whole command tree; `RLIMIT_AS` and `RLIMIT_NPROC` are then left unset.
+//! Once the command exits, the cgroup's event counters tell us which limit
+//! (if any) was hit.
+
+use std::fmt;
+use std::io;
+#[cfg(target_os = "linux")]
+use std::path::Path;
+#[cfg(target_os = "linux")]
+use std::path::PathBuf;
+
+use crate::config::types::ResourceLimits;
+
+const BYTES_PER_MIB: u64 = 1024 * 1024;
+
+/// Which per-command limit stopped a command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResourceLimitKind {
+    Memory,
+    CpuTime,
+    Processes,
+    FileSize,
+    Output,
+}
+
+impl fmt::Display for ResourceLimitKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ResourceLimitKind::Memory => "memory",
+            ResourceLimitKind::CpuTime => "CPU time",
+            ResourceLimitKind::Processes => "process count",
+            ResourceLimitKind::FileSize => "file size",
+            ResourceLimitKind::Output => "output size",
+        };
+        f.write_str(name)
+    }
+}
+
+// glibc declares the `RLIMIT_*` constants with their own type.
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+type RlimitResource = libc::__rlimit_resource_t;
+#[cfg(all(unix, not(all(target_os = "linux", target_env = "gnu"))))]
+type RlimitResource = libc::c_int;
+
+/// Applies the rlimit-backed subset of `limits` to the current process.
+///
+/// Meant to run in `pre_exec` or right before `execvp`, so it only makes raw
+/// syscalls. Limits are clamped to the current hard limits since an
+/// unprivileged process cannot raise them.
+#[cfg(unix)]
+pub fn apply_rlimits(limits: &ResourceLimits) -> io::Result<()> {
+    if let Some(memory_mb) = limits.memory_mb {
+        let bytes = memory_mb.saturating_mul(BYTES_PER_MIB);
+        set_rlimit(libc::RLIMIT_AS, bytes, bytes)?;
+    }
+    if let Some(cpu_time_secs) = limits.cpu_time_secs {
+        // The soft limit delivers SIGXCPU, which we can attribute to this
+        // limit; the hard limit backs it with SIGKILL.
+        set_rlimit(
+            libc::RLIMIT_CPU,
+            cpu_time_secs,
+            cpu_time_secs.saturating_add(1),
+        )?;
+    }
+    if let Some(max_processes) = limits.max_processes {
+        set_rlimit(libc::RLIMIT_NPROC, max_processes, max_processes)?;
+    }
+    if let Some(max_file_size_mb) = limits.max_file_size_mb {
+        let bytes = max_file_size_mb.saturating_mul(BYTES_PER_MIB);
+        set_rlimit(libc::RLIMIT_FSIZE, bytes, bytes)?;
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+pub fn apply_rlimits(_limits: &ResourceLimits) -> io::Result<()> {
+    Ok(())
+}
+
+#[cfg(unix)]
+fn set_rlimit(resource: RlimitResource, soft: u64, hard: u64) -> io::Result<()> {
+    let mut current = libc::rlimit {
+        rlim_cur: 0,
+        rlim_max: 0,
+    };
+    if unsafe { libc::getrlimit(resource, &mut current) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    let hard = (hard as libc::rlim_t).min(current.rlim_max);
+    let limit = libc::rlimit {
+        rlim_cur: (soft as libc::rlim_t).min(hard),
+        rlim_max: hard,
+    };
+    if unsafe { libc::setrlimit(resource, &limit) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+/// The part of `limits` that still needs rlimits once the command runs in an
+/// exec cgroup, which already caps memory and processes for the whole tree.
+/// `RLIMIT_AS` would count every mapping of every process separately, and
+/// `RLIMIT_NPROC` every process the user owns.
+pub fn limits_outside_exec_cgroup(limits: &ResourceLimits) -> ResourceLimits {
+    ResourceLimits {
+        memory_mb: None,
+        max_processes: None,
+        ..*limits
+    }
+}
+
+/// Attributes a signal (or a shell's `128 + signal` exit code) to the
+/// rlimit that raises it.
+#[cfg(unix)]
+pub(crate) fn limit_for_signal(limits: &ResourceLimits, signal: i32) -> Option<ResourceLimitKind> {
+    if signal == libc::SIGXCPU && limits.cpu_time_secs.is_some() {
+        Some(ResourceLimitKind::CpuTime)
+    } else if signal == libc::SIGXFSZ && limits.max_file_size_mb.is_some() {
+        Some(ResourceLimitKind::FileSize)
+    } else {
+        None
+    }
+}
+
+#[cfg(target_os = "linux")]
+const CGROUP_ROOT: &str = "/sys/fs/cgroup";
+
+#[cfg(target_os = "linux")]
+const EXEC_CGROUP_PREFIX: &str = "codex-exec-";
+
+/// Path of the cgroup `codex-linux-sandbox` creates for the helper process
+/// `pid`: a sibling of this process' own cgroup, i.e. a child of the slice
+/// delegated to the user.
+#[cfg(target_os = "linux")]
+fn exec_cgroup_path(pid: u32) -> Option<PathBuf> {
+    let contents = std::fs::read_to_string("/proc/self/cgroup").ok()?;
+    // cgroup v2 exposes a single `0::<path>` entry.
+    let own = contents.lines().find_map(|line| line.strip_prefix("0::"))?;
+    let slice = Path::new(own.trim()).parent()?.strip_prefix("/").ok()?;
+    Some(
+        Path::new(CGROUP_ROOT)
+            .join(slice)
+            .join(format!("{EXEC_CGROUP_PREFIX}{pid}")),
+    )
+}
+
+/// Moves the current process into a fresh cgroup that enforces the memory
+/// and process caps in `limits`.
+///
+/// Returns `false` without side effects when cgroup v2 is unavailable or the
+/// slice is not delegated to us; rlimits still apply in that case.
+#[cfg(target_os = "linux")]
+pub fn enter_exec_cgroup(limits: &ResourceLimits) -> bool {
+    if limits.memory_mb.is_none() && limits.max_processes.is_none() {
+        return false;
+    }
+    let pid = std::process::id();
+    let Some(path) = exec_cgroup_path(pid) else {
+        return false;
+    };
+    let Some(slice) = path.parent() else {
+        return false;
+    };
+    remove_stale_exec_cgroups(slice);
+    if std::fs::create_dir(&path).is_err() {
+        return false;
+    }
+
+    if configure_exec_cgroup(&path, limits, pid).is_err() {
+        let _ = std::fs::remove_dir(&path);
+        return false;
+    }
+    true
+}
+
+#[cfg(target_os = "linux")]
+fn configure_exec_cgroup(path: &Path, limits: &ResourceLimits, pid: u32) -> io::Result<()> {
+    if let Some(memory_mb) = limits.memory_mb {
+        let bytes = memory_mb.saturating_mul(BYTES_PER_MIB);
+        std::fs::write(path.join("memory.max"), bytes.to_string())?;
+        // Swapping out would let the command keep growing past the cap.
+        let _ = std::fs::write(path.join("memory.swap.max"), "0");
+    }
+    if let Some(max_processes) = limits.max_processes {
+        std::fs::write(path.join("pids.max"), max_processes.to_string())?;
+    }
+    std::fs::write(path.join("cgroup.procs"), pid.to_string())
+}
+
+/// Removes the cgroup created for the helper process `pid` and reports which
+/// of its limits, if any, was hit while the command ran.
+#[cfg(target_os = "linux")]
+pub(crate) fn collect_exec_cgroup(pid: u32) -> Option<ResourceLimitKind> {
+    let path = exec_cgroup_path(pid)?;
+    if !path.exists() {
+        return None;
+    }
+    let kind = if cgroup_event_count(&path.join("memory.events"), "oom_kill") > 0 {
+        Some(ResourceLimitKind::Memory)
+    } else if cgroup_event_count(&path.join("pids.events"), "max") > 0 {
+        Some(ResourceLimitKind::Processes)
+    } else {
+        None
+    };
+    // Fails while stray background processes are still alive; those cgroups
+    // are swept by a later `enter_exec_cgroup`.
+    let _ = std::fs::remove_dir(&path);
+    kind
+}
+
+/// Removes exec cgroups left behind by helpers that have exited, e.g. unified
+/// exec sessions whose results are never collected. `rmdir` only succeeds on
+/// cgroups without live processes.
+#[cfg(target_os = "linux")]
+fn remove_stale_exec_cgroups(slice: &Path) {
+    let Ok(entries) = std::fs::read_dir(slice) else {
+        return;
+    };
+    for entry in entries.filter_map(|entry| entry.ok()) {
+        let name = entry.file_name();
+        let Some(pid) = name
+            .to_str()
+            .and_then(|name| name.strip_prefix(EXEC_CGROUP_PREFIX))
+        else {
+            continue;
+        };
+        if !Path::new("/proc").join(pid).exists() {
+            let _ = std::fs::remove_dir(entry.path());
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn cgroup_event_count(path: &Path, key: &str) -> u64 {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|contents| {
+            contents.lines().find_map(|line| {
+                let (name, count) = line.split_once(' ')?;
+                if name == key {
+                    count.trim().parse().ok()
+                } else {
+                    None
+                }
+            })
+        })
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[cfg(unix)]
+    #[test]
+    fn signals_are_attributed_only_to_configured_limits() {
+        let limits = ResourceLimits {
+            cpu_time_secs: Some(5),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            limit_for_signal(&limits, libc::SIGXCPU),
+            Some(ResourceLimitKind::CpuTime)
+        );
+        assert_eq!(limit_for_signal(&limits, libc::SIGXFSZ), None);
+        assert_eq!(limit_for_signal(&limits, libc::SIGKILL), None);
+    }
+
+    #[test]
+    fn exec_cgroup_takes_over_memory_and_process_caps() {
+        let limits = ResourceLimits {
+            memory_mb: Some(512),
+            cpu_time_secs: Some(30),
+            max_processes: Some(64),
+            max_file_size_mb: Some(10),
+            max_output_bytes: Some(1024),
+        };
+
+        assert_eq!(
+            limits_outside_exec_cgroup(&limits),
+            ResourceLimits {
+                memory_mb: None,
+                max_processes: None,
+                ..limits
+            }
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn cgroup_event_count_matches_whole_keys() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let events = temp.path().join("memory.events");
+        std::fs::write(
+            &events,
+            "low 0\nhigh 0\nmax 12\noom 1\noom_kill 1\noom_group_kill 0\n",
+        )
+        .expect("write memory.events");
+
+        assert_eq!(cgroup_event_count(&events, "oom_kill"), 1);
+        assert_eq!(cgroup_event_count(&events, "max"), 12);
+        assert_eq!(cgroup_event_count(&events, "missing"), 0);
+    }
+}
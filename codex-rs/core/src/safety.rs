@@ -108,7 +108,7 @@ fn is_write_patch_constrained_to_writable_paths(
 ) -> bool {
     // Early‑exit if there are no declared writable roots.
     let writable_roots = match sandbox_policy {
-        SandboxPolicy::ReadOnly => {
+        SandboxPolicy::ReadOnly { .. } => {
             return false;
         }
         SandboxPolicy::DangerFullAccess | SandboxPolicy::ExternalSandbox { .. } => {
@@ -196,8 +196,10 @@ mod tests {
         let policy_workspace_only = SandboxPolicy::WorkspaceWrite {
             writable_roots: vec![],
             network_access: false,
+            allowed_loopback_ports: Vec::new(),
             exclude_tmpdir_env_var: true,
             exclude_slash_tmp: true,
+            deny_read: Vec::new(),
         };
 
         assert!(is_write_patch_constrained_to_writable_paths(
@@ -217,8 +219,10 @@ mod tests {
         let policy_with_parent = SandboxPolicy::WorkspaceWrite {
             writable_roots: vec![AbsolutePathBuf::try_from(parent).unwrap()],
             network_access: false,
+            allowed_loopback_ports: Vec::new(),
             exclude_tmpdir_env_var: true,
             exclude_slash_tmp: true,
+            deny_read: Vec::new(),
         };
         assert!(is_write_patch_constrained_to_writable_paths(
             &add_outside,
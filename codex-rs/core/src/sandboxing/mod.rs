@@ -6,6 +6,7 @@ sandbox placement and transformation of portable CommandSpec into a
 ready‑to‑spawn environment.
 */
 
+use crate::config::types::ResourceLimits;
 use crate::exec::ExecExpiration;
 use crate::exec::ExecToolCallOutput;
 use crate::exec::SandboxType;
@@ -49,6 +50,7 @@ pub struct ExecEnv {
     pub sandbox_permissions: SandboxPermissions,
     pub justification: Option<String>,
     pub arg0: Option<String>,
+    pub resource_limits: ResourceLimits,
 }
 
 /// Bundled arguments for sandbox transformation.
@@ -62,6 +64,7 @@ pub(crate) struct SandboxTransformRequest<'a> {
     pub codex_linux_sandbox_exe: Option<&'a PathBuf>,
     pub use_linux_sandbox_bwrap: bool,
     pub windows_sandbox_level: WindowsSandboxLevel,
+    pub resource_limits: ResourceLimits,
 }
 
 pub enum SandboxPreference {
@@ -127,6 +130,7 @@ impl SandboxManager {
             codex_linux_sandbox_exe,
             use_linux_sandbox_bwrap,
             windows_sandbox_level,
+            resource_limits,
         } = request;
         let mut env = spec.env;
         if !policy.has_full_network_access() {
@@ -163,6 +167,7 @@ impl SandboxManager {
                     policy,
                     sandbox_policy_cwd,
                     use_linux_sandbox_bwrap,
+                    &resource_limits,
                 );
                 let mut full_command = Vec::with_capacity(1 + args.len());
                 full_command.push(exe.to_string_lossy().to_string());
@@ -195,6 +200,7 @@ impl SandboxManager {
             sandbox_permissions: spec.sandbox_permissions,
             justification: spec.justification,
             arg0: arg0_override,
+            resource_limits,
         })
     }
 
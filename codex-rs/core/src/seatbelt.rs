@@ -6,6 +6,7 @@ use std::path::Path;
 use std::path::PathBuf;
 use tokio::process::Child;
 
+use crate::config::types::ResourceLimits;
 use crate::protocol::SandboxPolicy;
 use crate::spawn::CODEX_SANDBOX_ENV_VAR;
 use crate::spawn::StdioPolicy;
@@ -37,6 +38,7 @@ pub async fn spawn_command_under_seatbelt(
         arg0,
         command_cwd,
         sandbox_policy,
+        &ResourceLimits::default(),
         stdio_policy,
         env,
     )
@@ -105,24 +107,46 @@ pub(crate) fn create_seatbelt_command_args(
         }
     };
 
-    let file_read_policy = if sandbox_policy.has_full_disk_read_access() {
-        "; allow read-only file operations\n(allow file-read*)"
+    // Allow reads everywhere except the `deny_read` paths.
+    let deny_read_paths = sandbox_policy.get_deny_read_paths_with_cwd(sandbox_policy_cwd);
+    let (file_read_policy, file_read_params) = if deny_read_paths.is_empty() {
+        (
+            "; allow read-only file operations\n(allow file-read*)".to_string(),
+            Vec::new(),
+        )
     } else {
-        ""
+        let mut require_parts: Vec<String> = Vec::new();
+        let mut file_read_params = Vec::new();
+        for (index, denied) in deny_read_paths.into_iter().enumerate() {
+            let deny_param = format!("READ_DENY_{index}");
+            require_parts.push(format!("(require-not (subpath (param \"{deny_param}\")))"));
+            file_read_params.push((deny_param, denied.to_path_buf()));
+        }
+        (
+            format!(
+                "; allow read-only file operations outside deny_read\n(allow file-read* (require-all {} ))",
+                require_parts.join(" ")
+            ),
+            file_read_params,
+        )
     };
 
     // TODO(mbolin): apply_patch calls must also honor the SandboxPolicy.
+    let loopback_policy;
     let network_policy = if sandbox_policy.has_full_network_access() {
         MACOS_SEATBELT_NETWORK_POLICY
-    } else {
+    } else if sandbox_policy.allowed_loopback_ports().is_empty() {
         ""
+    } else {
+        loopback_policy = loopback_network_policy(sandbox_policy.allowed_loopback_ports());
+        &loopback_policy
     };
 
     let full_policy = format!(
         "{MACOS_SEATBELT_BASE_POLICY}\n{file_read_policy}\n{file_write_policy}\n{network_policy}"
     );
 
-    let dir_params = [file_write_dir_params, macos_dir_params()].concat();
+    let dir_params = [file_write_dir_params, file_read_params, macos_dir_params()].concat();
 
     let mut seatbelt_args: Vec<String> = vec!["-p".to_string(), full_policy];
     let definition_args = dir_params
@@ -134,6 +158,23 @@ pub(crate) fn create_seatbelt_command_args(
     seatbelt_args
 }
 
+/// Allows TCP to and from `localhost` on the given ports only.
+fn loopback_network_policy(ports: &[u16]) -> String {
+    let addresses: Vec<String> = ports
+        .iter()
+        .map(|port| format!("(remote tcp \"localhost:{port}\")"))
+        .collect();
+    let local_addresses: Vec<String> = ports
+        .iter()
+        .map(|port| format!("(local tcp \"localhost:{port}\")"))
+        .collect();
+    format!(
+        "; allow loopback TCP on allowed_loopback_ports\n(allow system-socket)\n(allow network-outbound {})\n(allow network-bind network-inbound {})",
+        addresses.join(" "),
+        local_addresses.join(" ")
+    )
+}
+
 /// Wraps libc::confstr to return a String.
 fn confstr(name: libc::c_int) -> Option<String> {
     let mut buf = vec![0_i8; (libc::PATH_MAX as usize) + 1];
@@ -164,6 +205,7 @@ fn macos_dir_params() -> Vec<(String, PathBuf)> {
 mod tests {
     use super::MACOS_SEATBELT_BASE_POLICY;
     use super::create_seatbelt_command_args;
+    use super::loopback_network_policy;
     use super::macos_dir_params;
     use crate::protocol::SandboxPolicy;
     use crate::seatbelt::MACOS_PATH_TO_SEATBELT_EXECUTABLE;
@@ -184,6 +226,17 @@ mod tests {
         );
     }
 
+    #[test]
+    fn loopback_network_policy_lists_each_port() {
+        assert_eq!(
+            loopback_network_policy(&[5432, 3000]),
+            "; allow loopback TCP on allowed_loopback_ports
+(allow system-socket)
+(allow network-outbound (remote tcp \"localhost:5432\") (remote tcp \"localhost:3000\"))
+(allow network-bind network-inbound (local tcp \"localhost:5432\") (local tcp \"localhost:3000\"))"
+        );
+    }
+
     #[test]
     fn create_seatbelt_args_with_read_only_git_and_codex_subpaths() {
         // Create a temporary workspace with two writable roots: one containing
@@ -208,8 +261,10 @@ mod tests {
                 .map(|p| p.try_into().unwrap())
                 .collect(),
             network_access: false,
+            allowed_loopback_ports: Vec::new(),
             exclude_tmpdir_env_var: true,
             exclude_slash_tmp: true,
+            deny_read: Vec::new(),
         };
 
         // Create the Seatbelt command to wrap a shell command that tries to
@@ -392,8 +447,10 @@ mod tests {
         let policy = SandboxPolicy::WorkspaceWrite {
             writable_roots: vec![worktree_root.try_into().expect("worktree_root is absolute")],
             network_access: false,
+            allowed_loopback_ports: Vec::new(),
             exclude_tmpdir_env_var: true,
             exclude_slash_tmp: true,
+            deny_read: Vec::new(),
         };
 
         let shell_command: Vec<String> = [
@@ -475,8 +532,10 @@ mod tests {
         let policy = SandboxPolicy::WorkspaceWrite {
             writable_roots: vec![],
             network_access: false,
+            allowed_loopback_ports: Vec::new(),
             exclude_tmpdir_env_var: false,
             exclude_slash_tmp: false,
+            deny_read: Vec::new(),
         };
 
         let shell_command: Vec<String> = [
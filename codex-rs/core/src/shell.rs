@@ -12,6 +12,8 @@ pub enum ShellType {
     PowerShell,
     Sh,
     Cmd,
+    Fish,
+    Nu,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -34,6 +36,8 @@ impl Shell {
             ShellType::PowerShell => "powershell",
             ShellType::Sh => "sh",
             ShellType::Cmd => "cmd",
+            ShellType::Fish => "fish",
+            ShellType::Nu => "nu",
         }
     }
 
@@ -65,6 +69,15 @@ impl Shell {
                 args.push(command.to_string());
                 args
             }
+            ShellType::Fish | ShellType::Nu => {
+                let mut args = vec![self.shell_path.to_string_lossy().to_string()];
+                if use_login_shell {
+                    args.push("-l".to_string());
+                }
+                args.push("-c".to_string());
+                args.push(command.to_string());
+                args
+            }
         }
     }
 
@@ -120,14 +133,18 @@ fn get_user_shell_path() -> Option<PathBuf> {
     None
 }
 
+/// Picks the login shell from `/etc/passwd`, except that `$SHELL` wins on
+/// WSL and whenever it names fish or Nushell, which are often started from
+/// the terminal rather than set as the login shell. See
+/// [`user_environment_shell_type`] for how those two are used.
 fn resolve_user_shell_path(
     is_wsl: bool,
     shell_env: Option<PathBuf>,
     passwd_shell: Option<PathBuf>,
 ) -> Option<PathBuf> {
-    if is_wsl
-        && let Some(shell_env) = shell_env
-        && detect_shell_type(&shell_env).is_some()
+    if let Some(shell_env) = shell_env
+        && let Some(shell_type) = detect_shell_type(&shell_env)
+        && (is_wsl || matches!(shell_type, ShellType::Fish | ShellType::Nu))
         && file_exists(&shell_env).is_some()
     {
         return Some(shell_env);
@@ -209,6 +226,40 @@ fn get_sh_shell(path: Option<&PathBuf>) -> Option<Shell> {
     })
 }
 
+fn get_fish_shell(path: Option<&PathBuf>) -> Option<Shell> {
+    let shell_path = get_shell_path(
+        ShellType::Fish,
+        path,
+        "fish",
+        vec![
+            "/usr/bin/fish",
+            "/usr/local/bin/fish",
+            "/opt/homebrew/bin/fish",
+        ],
+    );
+
+    shell_path.map(|shell_path| Shell {
+        shell_type: ShellType::Fish,
+        shell_path,
+        shell_snapshot: empty_shell_snapshot_receiver(),
+    })
+}
+
+fn get_nu_shell(path: Option<&PathBuf>) -> Option<Shell> {
+    let shell_path = get_shell_path(
+        ShellType::Nu,
+        path,
+        "nu",
+        vec!["/usr/bin/nu", "/usr/local/bin/nu", "/opt/homebrew/bin/nu"],
+    );
+
+    shell_path.map(|shell_path| Shell {
+        shell_type: ShellType::Nu,
+        shell_path,
+        shell_snapshot: empty_shell_snapshot_receiver(),
+    })
+}
+
 fn get_powershell_shell(path: Option<&PathBuf>) -> Option<Shell> {
     let shell_path = get_shell_path(
         ShellType::PowerShell,
@@ -264,6 +315,8 @@ pub fn get_shell(shell_type: ShellType, path: Option<&PathBuf>) -> Option<Shell>
         ShellType::PowerShell => get_powershell_shell(path),
         ShellType::Sh => get_sh_shell(path),
         ShellType::Cmd => get_cmd_shell(path),
+        ShellType::Fish => get_fish_shell(path),
+        ShellType::Nu => get_nu_shell(path),
     }
 }
 
@@ -275,6 +328,8 @@ pub fn detect_shell_type(shell_path: &PathBuf) -> Option<ShellType> {
         Some("bash") => Some(ShellType::Bash),
         Some("pwsh") => Some(ShellType::PowerShell),
         Some("powershell") => Some(ShellType::PowerShell),
+        Some("fish") => Some(ShellType::Fish),
+        Some("nu") => Some(ShellType::Nu),
         _ => {
             let shell_name = shell_path.file_stem();
             if let Some(shell_name) = shell_name
@@ -292,12 +347,26 @@ pub fn default_user_shell() -> Shell {
     default_user_shell_from_path(get_user_shell_path())
 }
 
+/// fish or Nushell when that is the user's shell. Model commands are written
+/// for POSIX shells, so they never run under either one; the shell snapshot
+/// captures its environment for the shell that does run them.
+pub(crate) fn user_environment_shell_type() -> Option<ShellType> {
+    environment_shell_type_from_path(get_user_shell_path())
+}
+
+fn environment_shell_type_from_path(user_shell_path: Option<PathBuf>) -> Option<ShellType> {
+    user_shell_path
+        .and_then(|shell| detect_shell_type(&shell))
+        .filter(|shell_type| matches!(shell_type, ShellType::Fish | ShellType::Nu))
+}
+
 fn default_user_shell_from_path(user_shell_path: Option<PathBuf>) -> Shell {
     if cfg!(windows) {
         get_shell(ShellType::PowerShell, None).unwrap_or(ultimate_fallback_shell())
     } else {
         let user_default_shell = user_shell_path
             .and_then(|shell| detect_shell_type(&shell))
+            .filter(|shell_type| !matches!(shell_type, ShellType::Fish | ShellType::Nu))
             .and_then(|shell_type| get_shell(shell_type, None));
 
         let shell_with_fallback = if cfg!(target_os = "macos") {
@@ -336,7 +405,19 @@ mod detect_shell_type_tests {
             detect_shell_type(&PathBuf::from("powershell")),
             Some(ShellType::PowerShell)
         );
-        assert_eq!(detect_shell_type(&PathBuf::from("fish")), None);
+        assert_eq!(
+            detect_shell_type(&PathBuf::from("fish")),
+            Some(ShellType::Fish)
+        );
+        assert_eq!(
+            detect_shell_type(&PathBuf::from("/opt/homebrew/bin/fish")),
+            Some(ShellType::Fish)
+        );
+        assert_eq!(detect_shell_type(&PathBuf::from("nu")), Some(ShellType::Nu));
+        assert_eq!(
+            detect_shell_type(&PathBuf::from("nu.exe")),
+            Some(ShellType::Nu)
+        );
         assert_eq!(detect_shell_type(&PathBuf::from("other")), None);
         assert_eq!(
             detect_shell_type(&PathBuf::from("/bin/zsh")),
@@ -404,12 +485,37 @@ mod tests {
 
     #[test]
     #[cfg(target_os = "macos")]
-    fn fish_fallback_to_zsh() {
-        let zsh_shell = default_user_shell_from_path(Some(PathBuf::from("/bin/fish")));
+    fn fish_user_shell_runs_commands_under_zsh() {
+        let shell = default_user_shell_from_path(Some(PathBuf::from("/bin/fish")));
 
-        let shell_path = zsh_shell.shell_path;
+        assert_eq!(shell.shell_type, ShellType::Zsh);
+    }
 
-        assert_eq!(shell_path, Path::new("/bin/zsh"));
+    #[test]
+    #[cfg(unix)]
+    fn fish_and_nu_only_supply_the_environment() {
+        for (path, shell_type) in [
+            ("/usr/bin/fish", ShellType::Fish),
+            ("/usr/bin/nu", ShellType::Nu),
+        ] {
+            let shell = default_user_shell_from_path(Some(PathBuf::from(path)));
+            assert!(
+                matches!(
+                    shell.shell_type,
+                    ShellType::Bash | ShellType::Zsh | ShellType::Sh
+                ),
+                "{path} should run commands under a POSIX shell, got {:?}",
+                shell.shell_type
+            );
+            assert_eq!(
+                environment_shell_type_from_path(Some(PathBuf::from(path))),
+                Some(shell_type)
+            );
+        }
+        assert_eq!(
+            environment_shell_type_from_path(Some(PathBuf::from("/bin/bash"))),
+            None
+        );
     }
 
     #[test]
@@ -512,6 +618,20 @@ mod tests {
             test_powershell_shell.derive_exec_args("echo hello", true),
             vec!["pwsh.exe", "-Command", "echo hello"]
         );
+
+        let test_fish_shell = Shell {
+            shell_type: ShellType::Fish,
+            shell_path: PathBuf::from("/usr/bin/fish"),
+            shell_snapshot: empty_shell_snapshot_receiver(),
+        };
+        assert_eq!(
+            test_fish_shell.derive_exec_args("echo hello", false),
+            vec!["/usr/bin/fish", "-c", "echo hello"]
+        );
+        assert_eq!(
+            test_fish_shell.derive_exec_args("echo hello", true),
+            vec!["/usr/bin/fish", "-l", "-c", "echo hello"]
+        );
     }
 
     fn create_shell_file(temp_dir: &TempDir, name: &str) -> PathBuf {
@@ -534,7 +654,7 @@ mod tests {
     #[test]
     fn resolve_user_shell_path_wsl_invalid_env_falls_back() {
         let temp_dir = TempDir::new().expect("tempdir");
-        let shell_env = create_shell_file(&temp_dir, "fish");
+        let shell_env = create_shell_file(&temp_dir, "tcsh");
         let passwd_shell = create_shell_file(&temp_dir, "bash");
 
         let resolved = resolve_user_shell_path(true, Some(shell_env), Some(passwd_shell.clone()));
@@ -542,6 +662,19 @@ mod tests {
         assert_eq!(resolved, Some(passwd_shell));
     }
 
+    #[test]
+    fn resolve_user_shell_path_prefers_fish_and_nu_from_env() {
+        let temp_dir = TempDir::new().expect("tempdir");
+        let passwd_shell = create_shell_file(&temp_dir, "bash");
+
+        for name in ["fish", "nu"] {
+            let shell_env = create_shell_file(&temp_dir, name);
+            let resolved =
+                resolve_user_shell_path(false, Some(shell_env.clone()), Some(passwd_shell.clone()));
+            assert_eq!(resolved, Some(shell_env));
+        }
+    }
+
     #[test]
     fn resolve_user_shell_path_non_wsl_ignores_env() {
         let temp_dir = TempDir::new().expect("tempdir");
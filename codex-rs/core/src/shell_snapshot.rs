@@ -10,6 +10,7 @@ use crate::rollout::list::find_thread_path_by_id_str;
 use crate::shell::Shell;
 use crate::shell::ShellType;
 use crate::shell::get_shell;
+use crate::shell::user_environment_shell_type;
 use anyhow::Context;
 use anyhow::Result;
 use anyhow::anyhow;
@@ -32,6 +33,8 @@ const SNAPSHOT_TIMEOUT: Duration = Duration::from_secs(10);
 const SNAPSHOT_RETENTION: Duration = Duration::from_secs(60 * 60 * 24 * 3); // 3 days retention.
 const SNAPSHOT_DIR: &str = "shell_snapshots";
 const EXCLUDED_EXPORT_VARS: &[&str] = &["PWD", "OLDPWD"];
+/// Variables Nushell sets itself and refuses to have assigned.
+const NU_AUTOMATIC_ENV_VARS: &[&str] = &["FILE_PWD", "CURRENT_FILE", "PROCESS_PATH"];
 
 impl ShellSnapshot {
     pub fn start_snapshotting(
@@ -44,15 +47,21 @@ impl ShellSnapshot {
         shell.shell_snapshot = shell_snapshot_rx;
 
         let snapshot_shell = shell.clone();
+        let source_shell_type =
+            snapshot_source_shell_type(&shell.shell_type, user_environment_shell_type());
         let snapshot_session_id = session_id;
         let snapshot_span = info_span!("shell_snapshot", thread_id = %snapshot_session_id);
         tokio::spawn(
             async move {
                 let timer = otel_manager.start_timer("codex.shell_snapshot.duration_ms", &[]);
-                let snapshot =
-                    ShellSnapshot::try_new(&codex_home, snapshot_session_id, &snapshot_shell)
-                        .await
-                        .map(Arc::new);
+                let snapshot = ShellSnapshot::try_new(
+                    &codex_home,
+                    snapshot_session_id,
+                    &snapshot_shell,
+                    source_shell_type,
+                )
+                .await
+                .map(Arc::new);
                 let success = if snapshot.is_some() { "true" } else { "false" };
                 let _ = timer.map(|timer| timer.record(&[("success", success)]));
                 otel_manager.counter("codex.shell_snapshot", 1, &[("success", success)]);
@@ -62,7 +71,14 @@ impl ShellSnapshot {
         );
     }
 
-    async fn try_new(codex_home: &Path, session_id: ThreadId, shell: &Shell) -> Option<Self> {
+    /// Captures the environment of `source_shell_type` into a script that
+    /// `shell` sources before each command.
+    async fn try_new(
+        codex_home: &Path,
+        session_id: ThreadId,
+        shell: &Shell,
+        source_shell_type: ShellType,
+    ) -> Option<Self> {
         // File to store the snapshot
         let extension = match shell.shell_type {
             ShellType::PowerShell => "ps1",
@@ -82,7 +98,7 @@ impl ShellSnapshot {
         });
 
         // Make the new snapshot.
-        let snapshot = match write_shell_snapshot(shell.shell_type.clone(), &path).await {
+        let snapshot = match write_shell_snapshot(source_shell_type, &path).await {
             Ok(path) => {
                 tracing::info!("Shell snapshot successfully created: {}", path.display());
                 Some(Self { path })
@@ -118,6 +134,20 @@ impl Drop for ShellSnapshot {
     }
 }
 
+/// POSIX shells snapshot the user's fish or Nushell environment when there is
+/// one, since fish and Nushell print their snapshot as POSIX `export` lines.
+fn snapshot_source_shell_type(
+    shell_type: &ShellType,
+    environment_shell_type: Option<ShellType>,
+) -> ShellType {
+    match (shell_type, environment_shell_type) {
+        (ShellType::Zsh | ShellType::Bash | ShellType::Sh, Some(environment_shell_type)) => {
+            environment_shell_type
+        }
+        _ => shell_type.clone(),
+    }
+}
+
 async fn write_shell_snapshot(shell_type: ShellType, output_path: &Path) -> Result<PathBuf> {
     if shell_type == ShellType::PowerShell || shell_type == ShellType::Cmd {
         bail!("Shell snapshot not supported yet for {shell_type:?}");
@@ -150,6 +180,8 @@ async fn capture_snapshot(shell: &Shell) -> Result<String> {
         ShellType::Bash => run_shell_script(shell, &bash_snapshot_script()).await,
         ShellType::Sh => run_shell_script(shell, &sh_snapshot_script()).await,
         ShellType::PowerShell => run_shell_script(shell, powershell_snapshot_script()).await,
+        ShellType::Fish => run_shell_script(shell, &fish_snapshot_script()).await,
+        ShellType::Nu => run_shell_script(shell, &nu_snapshot_script()).await,
         ShellType::Cmd => bail!("Shell snapshotting is not yet supported for {shell_type:?}"),
     }
 }
@@ -371,6 +403,52 @@ fi
     script.replace("EXCLUDED_EXPORTS", &excluded)
 }
 
+/// Prints the exported variables of a fish session as POSIX `export` lines.
+/// fish functions cannot be sourced by the POSIX shell running commands, so
+/// they are left out.
+fn fish_snapshot_script() -> String {
+    let excluded = EXCLUDED_EXPORT_VARS.join(" ");
+    let script = r##"echo '# Snapshot file'
+set -l export_names
+for name in (set --names --export)
+  if contains -- $name EXCLUDED_EXPORTS
+    continue
+  end
+  if string match -qr '^[A-Za-z_][A-Za-z0-9_]*$' -- $name
+    set -a export_names $name
+  end
+end
+echo "# exports "(count $export_names)
+for name in $export_names
+  printf "export %s='%s'\n" $name (string replace --all "'" "'\"'\"'" -- "$$name")
+end
+"##;
+    script.replace("EXCLUDED_EXPORTS", &excluded)
+}
+
+/// Prints the exported variables of a Nushell session as POSIX `export`
+/// lines, joining list values such as `PATH` with the platform separator.
+fn nu_snapshot_script() -> String {
+    let excluded = EXCLUDED_EXPORT_VARS
+        .iter()
+        .chain(NU_AUTOMATIC_ENV_VARS)
+        .map(|name| format!("'{name}'"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let script = r##"print '# Snapshot file'
+let exports = ($env | transpose name value | where {|row|
+  ($row.name =~ '^[A-Za-z_][A-Za-z0-9_]*$') and ($row.name not-in [EXCLUDED_EXPORTS]) and (($row.value | describe) in ['string', 'list<string>'])
+})
+print $"# exports ($exports | length)"
+for row in $exports {
+  let value = if (($row.value | describe) == 'string') { $row.value } else { $row.value | str join (char esep) }
+  let escaped = ($value | str replace --all "'" "'\"'\"'")
+  print $"export ($row.name)='($escaped)'"
+}
+"##;
+    script.replace("EXCLUDED_EXPORTS", &excluded)
+}
+
 fn powershell_snapshot_script() -> &'static str {
     r##"$ErrorActionPreference = 'Stop'
 Write-Output '# Snapshot file'
@@ -602,7 +680,7 @@ mod tests {
             shell_snapshot: crate::shell::empty_shell_snapshot_receiver(),
         };
 
-        let snapshot = ShellSnapshot::try_new(dir.path(), ThreadId::new(), &shell)
+        let snapshot = ShellSnapshot::try_new(dir.path(), ThreadId::new(), &shell, ShellType::Bash)
             .await
             .expect("snapshot should be created");
         let path = snapshot.path.clone();
@@ -723,6 +801,67 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn posix_shells_snapshot_the_environment_shell() {
+        assert_eq!(
+            snapshot_source_shell_type(&ShellType::Bash, Some(ShellType::Fish)),
+            ShellType::Fish
+        );
+        assert_eq!(
+            snapshot_source_shell_type(&ShellType::Zsh, Some(ShellType::Nu)),
+            ShellType::Nu
+        );
+        assert_eq!(
+            snapshot_source_shell_type(&ShellType::Bash, None),
+            ShellType::Bash
+        );
+        assert_eq!(
+            snapshot_source_shell_type(&ShellType::PowerShell, Some(ShellType::Fish)),
+            ShellType::PowerShell
+        );
+    }
+
+    /// fish and Nushell snapshots are sourced by the POSIX shell that runs
+    /// commands.
+    #[cfg(unix)]
+    async fn assert_environment_snapshot_is_posix(shell_type: ShellType) -> Result<()> {
+        let dir = tempdir()?;
+        let path = dir.path().join("snapshot.sh");
+        write_shell_snapshot(shell_type, &path).await?;
+        let snapshot = fs::read_to_string(&path).await?;
+        assert!(snapshot.contains("# Snapshot file"));
+        assert!(snapshot.contains("# exports "));
+        assert!(
+            snapshot.contains("export PATH='"),
+            "snapshot should capture a PATH export"
+        );
+        assert!(
+            !snapshot.contains("export PWD="),
+            "snapshot should skip excluded variables"
+        );
+
+        let bash = get_shell(ShellType::Bash, None).context("bash is required")?;
+        validate_snapshot(&bash, &path).await
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn fish_snapshot_exports_are_posix() -> Result<()> {
+        if get_shell(ShellType::Fish, None).is_none() {
+            return Ok(());
+        }
+        assert_environment_snapshot_is_posix(ShellType::Fish).await
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn nu_snapshot_exports_are_posix() -> Result<()> {
+        if get_shell(ShellType::Nu, None).is_none() {
+            return Ok(());
+        }
+        assert_environment_snapshot_is_posix(ShellType::Nu).await
+    }
+
     #[cfg(target_os = "windows")]
     #[ignore]
     #[tokio::test]
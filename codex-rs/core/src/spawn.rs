@@ -5,6 +5,7 @@ use tokio::process::Child;
 use tokio::process::Command;
 use tracing::trace;
 
+use crate::config::types::ResourceLimits;
 use crate::protocol::SandboxPolicy;
 
 /// Experimental environment variable that will be set to some non-empty value
@@ -35,12 +36,16 @@ pub enum StdioPolicy {
 /// For now, we take `SandboxPolicy` as a parameter to spawn_child() because
 /// we need to determine whether to set the
 /// `CODEX_SANDBOX_NETWORK_DISABLED_ENV_VAR` environment variable.
+///
+/// `resource_limits` are applied as rlimits in the child before it execs.
+#[allow(clippy::too_many_arguments)]
 pub(crate) async fn spawn_child_async(
     program: PathBuf,
     args: Vec<String>,
     #[cfg_attr(not(unix), allow(unused_variables))] arg0: Option<&str>,
     cwd: PathBuf,
     sandbox_policy: &SandboxPolicy,
+    #[cfg_attr(not(unix), allow(unused_variables))] resource_limits: &ResourceLimits,
     stdio_policy: StdioPolicy,
     env: HashMap<String, String>,
 ) -> std::io::Result<Child> {
@@ -69,6 +74,7 @@ pub(crate) async fn spawn_child_async(
         let detach_from_tty = matches!(stdio_policy, StdioPolicy::RedirectForShellTool);
         #[cfg(target_os = "linux")]
         let parent_pid = libc::getpid();
+        let resource_limits = *resource_limits;
         cmd.pre_exec(move || {
             if detach_from_tty {
                 codex_utils_pty::process_group::detach_from_tty()?;
@@ -81,6 +87,8 @@ pub(crate) async fn spawn_child_async(
                 // current parent dies."
                 codex_utils_pty::process_group::set_parent_death_signal(parent_pid)?;
             }
+
+            crate::resource_limits::apply_rlimits(&resource_limits)?;
             Ok(())
         });
     }
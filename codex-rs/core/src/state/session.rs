@@ -6,6 +6,7 @@ use std::collections::HashSet;
 
 use crate::codex::SessionConfiguration;
 use crate::context_manager::ContextManager;
+use crate::project_doc::NestedProjectDocs;
 use crate::protocol::RateLimitSnapshot;
 use crate::protocol::TokenUsage;
 use crate::protocol::TokenUsageInfo;
@@ -25,6 +26,7 @@ pub(crate) struct SessionState {
     /// timestamp when resuming a session. Remove this once SQLite is in place.
     pub(crate) initial_context_seeded: bool,
     pub(crate) pending_resumed_rollout: Option<std::path::PathBuf>,
+    pub(crate) nested_project_docs: NestedProjectDocs,
 }
 
 impl SessionState {
@@ -40,6 +42,7 @@ impl SessionState {
             mcp_dependency_prompted: HashSet::new(),
             initial_context_seeded: false,
             pending_resumed_rollout: None,
+            nested_project_docs: NestedProjectDocs::default(),
         }
     }
 
@@ -12,6 +12,7 @@ use codex_protocol::ThreadId;
 use codex_protocol::dynamic_tools::DynamicToolSpec;
 use codex_protocol::protocol::RolloutItem;
 use codex_protocol::protocol::SessionSource;
+use codex_protocol::protocol::TokenUsage;
 use codex_state::DB_METRIC_COMPARE_ERROR;
 pub use codex_state::LogEntry;
 use codex_state::STATE_DB_VERSION;
@@ -364,6 +365,21 @@ pub async fn get_last_n_thread_memories_for_cwd(
     }
 }
 
+/// Add a model response's token usage to its turn in SQLite.
+pub async fn record_turn_usage(
+    context: Option<&codex_state::StateRuntime>,
+    turn: &codex_state::TurnUsage,
+    usage: &TokenUsage,
+    stage: &str,
+) {
+    let Some(ctx) = context else {
+        return;
+    };
+    if let Err(err) = ctx.record_turn_usage(turn, usage).await {
+        warn!("state db record_turn_usage failed during {stage}: {err}");
+    }
+}
+
 /// Reconcile rollout items into SQLite, falling back to scanning the rollout file.
 pub async fn reconcile_rollout(
     context: Option<&codex_state::StateRuntime>,
@@ -4,10 +4,10 @@ use crate::codex::TurnContext;
 use crate::codex::run_turn;
 use crate::state::TaskKind;
 use async_trait::async_trait;
+use codex_otel::traces::spans::turn_span;
 use codex_protocol::user_input::UserInput;
 use tokio_util::sync::CancellationToken;
 use tracing::Instrument;
-use tracing::trace_span;
 
 use super::SessionTask;
 use super::SessionTaskContext;
@@ -29,11 +29,8 @@ impl SessionTask for RegularTask {
         cancellation_token: CancellationToken,
     ) -> Option<String> {
         let sess = session.clone_session();
-        let run_turn_span = trace_span!("run_turn");
+        let run_turn_span = turn_span(&ctx.sub_id, &ctx.client.get_model());
         sess.set_server_reasoning_included(false).await;
-        sess.services
-            .otel_manager
-            .apply_traceparent_parent(&run_turn_span);
         run_turn(sess, ctx, input, cancellation_token)
             .instrument(run_turn_span)
             .await
@@ -10,6 +10,7 @@ use tracing::error;
 use uuid::Uuid;
 
 use crate::codex::TurnContext;
+use crate::config::types::ResourceLimits;
 use crate::exec::ExecToolCallOutput;
 use crate::exec::SandboxType;
 use crate::exec::StdoutStream;
@@ -151,6 +152,8 @@ pub(crate) async fn execute_user_shell_command(
         sandbox_permissions: SandboxPermissions::UseDefault,
         justification: None,
         arg0: None,
+        // Commands the user runs directly are not capped.
+        resource_limits: ResourceLimits::default(),
     };
 
     let stdout_stream = Some(StdoutStream {
@@ -0,0 +1,153 @@
+//! Approval prompts asked through `request_user_input`, for tools such as MCP
+//! calls and `web_fetch` that have no exec-style approval request.
+
+use codex_protocol::protocol::ReviewDecision;
+use codex_protocol::request_user_input::RequestUserInputArgs;
+use codex_protocol::request_user_input::RequestUserInputQuestion;
+use codex_protocol::request_user_input::RequestUserInputQuestionOption;
+use codex_protocol::request_user_input::RequestUserInputResponse;
+use serde::Serialize;
+
+use crate::codex::Session;
+use crate::codex::TurnContext;
+
+pub(crate) const APPROVAL_ACCEPT: &str = "Accept";
+pub(crate) const APPROVAL_ACCEPT_FOR_SESSION: &str = "Accept for session";
+pub(crate) const APPROVAL_DECLINE: &str = "Decline";
+pub(crate) const APPROVAL_CANCEL: &str = "Cancel";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ApprovalAnswer {
+    Accept,
+    AcceptForSession,
+    Decline,
+    Cancel,
+}
+
+/// Descriptions of the options offered by an approval question.
+pub(crate) struct ApprovalOptions<'a> {
+    pub accept: &'a str,
+    /// The "Accept for session" option is omitted when `None`.
+    pub accept_for_session: Option<&'a str>,
+    pub decline: &'a str,
+    pub cancel: &'a str,
+}
+
+impl ApprovalOptions<'_> {
+    pub(crate) fn into_options(self) -> Vec<RequestUserInputQuestionOption> {
+        let option = |label: &str, description: &str| RequestUserInputQuestionOption {
+            label: label.to_string(),
+            description: description.to_string(),
+        };
+        let mut options = vec![option(APPROVAL_ACCEPT, self.accept)];
+        if let Some(description) = self.accept_for_session {
+            options.push(option(APPROVAL_ACCEPT_FOR_SESSION, description));
+        }
+        options.extend([
+            option(APPROVAL_DECLINE, self.decline),
+            option(APPROVAL_CANCEL, self.cancel),
+        ]);
+        options
+    }
+}
+
+/// Asks `question` unless `session_key` was already accepted for the session,
+/// and remembers `session_key` when the user accepts it for the session.
+/// Returns `None` when it was already accepted.
+pub(crate) async fn request_approval<K: Serialize>(
+    sess: &Session,
+    turn_context: &TurnContext,
+    call_id: &str,
+    question: RequestUserInputQuestion,
+    session_key: Option<K>,
+) -> Option<ApprovalAnswer> {
+    if let Some(key) = &session_key
+        && matches!(
+            sess.services.tool_approvals.lock().await.get(key),
+            Some(ReviewDecision::ApprovedForSession)
+        )
+    {
+        return None;
+    }
+
+    let question_id = question.id.clone();
+    let args = RequestUserInputArgs {
+        questions: vec![question],
+    };
+    let response = sess
+        .request_user_input(turn_context, call_id.to_string(), args)
+        .await;
+    let answer = parse_approval_response(response, &question_id);
+    if answer == ApprovalAnswer::AcceptForSession
+        && let Some(key) = session_key
+    {
+        sess.services
+            .tool_approvals
+            .lock()
+            .await
+            .put(key, ReviewDecision::ApprovedForSession);
+    }
+    Some(answer)
+}
+
+/// A missing answer counts as cancelling; an unrecognized one as declining.
+pub(crate) fn parse_approval_response(
+    response: Option<RequestUserInputResponse>,
+    question_id: &str,
+) -> ApprovalAnswer {
+    let Some(answers) = response
+        .as_ref()
+        .and_then(|response| response.answers.get(question_id))
+        .map(|answer| answer.answers.as_slice())
+    else {
+        return ApprovalAnswer::Cancel;
+    };
+    let answered = |label: &str| answers.iter().any(|answer| answer == label);
+    if answered(APPROVAL_ACCEPT_FOR_SESSION) {
+        ApprovalAnswer::AcceptForSession
+    } else if answered(APPROVAL_ACCEPT) {
+        ApprovalAnswer::Accept
+    } else if answered(APPROVAL_CANCEL) {
+        ApprovalAnswer::Cancel
+    } else {
+        ApprovalAnswer::Decline
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    #[test]
+    fn parses_accept_for_session_answer() {
+        let response: RequestUserInputResponse = serde_json::from_value(json!({
+            "answers": {"q": {"answers": [APPROVAL_ACCEPT_FOR_SESSION]}}
+        }))
+        .expect("valid response");
+        assert_eq!(
+            parse_approval_response(Some(response), "q"),
+            ApprovalAnswer::AcceptForSession
+        );
+        assert_eq!(parse_approval_response(None, "q"), ApprovalAnswer::Cancel);
+    }
+
+    #[test]
+    fn omits_accept_for_session_when_not_offered() {
+        let options = ApprovalOptions {
+            accept: "a",
+            accept_for_session: None,
+            decline: "d",
+            cancel: "c",
+        }
+        .into_options();
+        assert_eq!(
+            options
+                .iter()
+                .map(|option| option.label.as_str())
+                .collect::<Vec<_>>(),
+            vec![APPROVAL_ACCEPT, APPROVAL_DECLINE, APPROVAL_CANCEL]
+        );
+    }
+}
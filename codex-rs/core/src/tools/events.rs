@@ -146,6 +146,9 @@ impl ToolEmitter {
     }
 
     pub async fn emit(&self, ctx: ToolEventCtx<'_>, stage: ToolEventStage) {
+        if matches!(stage, ToolEventStage::Begin) {
+            ctx.session.note_touched_paths(self.touched_paths()).await;
+        }
         match (self, stage) {
             (
                 Self::Shell {
@@ -251,6 +254,37 @@ impl ToolEmitter {
         }
     }
 
+    /// Files this call reads or edits, used to discover nested project docs.
+    fn touched_paths(&self) -> Vec<PathBuf> {
+        match self {
+            Self::Shell {
+                cwd, parsed_cmd, ..
+            }
+            | Self::UnifiedExec {
+                cwd, parsed_cmd, ..
+            } => parsed_cmd
+                .iter()
+                .filter_map(|parsed| match parsed {
+                    ParsedCommand::Read { path, .. } => Some(cwd.join(path)),
+                    _ => None,
+                })
+                .collect(),
+            Self::ApplyPatch { changes, .. } => changes
+                .iter()
+                .flat_map(|(path, change)| {
+                    let moved = match change {
+                        FileChange::Update {
+                            move_path: Some(move_path),
+                            ..
+                        } => Some(move_path.clone()),
+                        _ => None,
+                    };
+                    std::iter::once(path.clone()).chain(moved)
+                })
+                .collect(),
+        }
+    }
+
     pub async fn begin(&self, ctx: ToolEventCtx<'_>) {
         self.emit(ctx, ToolEventStage::Begin).await;
     }
@@ -292,6 +326,21 @@ impl ToolEmitter {
                 let result = Err(FunctionCallError::RespondToModel(response));
                 (event, result)
             }
+            Err(ToolError::Codex(CodexErr::Sandbox(SandboxErr::ResourceLimit {
+                kind,
+                mut output,
+            }))) => {
+                // Tell the model why the command died so it can adjust (e.g.
+                // fewer parallel jobs) instead of retrying blindly.
+                output.aggregated_output.text = format!(
+                    "command exceeded its {kind} limit\n{}",
+                    output.aggregated_output.text
+                );
+                let response = self.format_exec_output_for_model(&output, ctx);
+                let event = ToolEventStage::Failure(ToolEventFailure::Output(*output));
+                let result = Err(FunctionCallError::RespondToModel(response));
+                (event, result)
+            }
             Err(ToolError::Codex(err)) => {
                 let message = format!("execution error: {err:?}");
                 let event = ToolEventStage::Failure(ToolEventFailure::Message(message.clone()));
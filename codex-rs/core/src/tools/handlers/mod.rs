@@ -14,10 +14,14 @@ mod shell;
 mod test_sync;
 mod unified_exec;
 mod view_image;
+mod web_fetch;
+mod web_search;
 
 pub use plan::PLAN_TOOL;
 use serde::Deserialize;
 
+use crate::codex::TurnContext;
+use crate::config::types::SandboxProfile;
 use crate::function_tool::FunctionCallError;
 pub use apply_patch::ApplyPatchHandler;
 pub use collab::CollabHandler;
@@ -37,6 +41,9 @@ pub use shell::ShellHandler;
 pub use test_sync::TestSyncHandler;
 pub use unified_exec::UnifiedExecHandler;
 pub use view_image::ViewImageHandler;
+pub(crate) use web_fetch::WebFetchCache;
+pub use web_fetch::WebFetchHandler;
+pub use web_search::WebSearchHandler;
 
 fn parse_arguments<T>(arguments: &str) -> Result<T, FunctionCallError>
 where
@@ -46,3 +53,20 @@ where
         FunctionCallError::RespondToModel(format!("failed to parse function arguments: {err}"))
     })
 }
+
+fn resolve_sandbox_profile<'a>(
+    turn: &'a TurnContext,
+    name: &str,
+) -> Result<&'a SandboxProfile, FunctionCallError> {
+    turn.sandbox_profiles.get(name).ok_or_else(|| {
+        let known = turn
+            .sandbox_profiles
+            .keys()
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join(", ");
+        FunctionCallError::RespondToModel(format!(
+            "unknown sandbox profile `{name}`; configured profiles: {known}"
+        ))
+    })
+}
@@ -20,12 +20,15 @@ use crate::tools::events::ToolEmitter;
 use crate::tools::events::ToolEventCtx;
 use crate::tools::handlers::apply_patch::intercept_apply_patch;
 use crate::tools::handlers::parse_arguments;
+use crate::tools::handlers::resolve_sandbox_profile;
 use crate::tools::orchestrator::ToolOrchestrator;
 use crate::tools::registry::ToolHandler;
 use crate::tools::registry::ToolKind;
 use crate::tools::runtimes::shell::ShellRequest;
 use crate::tools::runtimes::shell::ShellRuntime;
 use crate::tools::sandboxing::ToolCtx;
+use crate::tools::sandboxing::require_approval_for_sandbox_profile;
+use crate::tools::sandboxing::set_sandbox_profile_proxy_env;
 
 pub struct ShellHandler;
 
@@ -40,6 +43,7 @@ struct RunExecLikeArgs {
     tracker: crate::tools::context::SharedTurnDiffTracker,
     call_id: String,
     freeform: bool,
+    sandbox_profile: Option<String>,
 }
 
 impl ShellHandler {
@@ -139,6 +143,7 @@ impl ToolHandler for ShellHandler {
                     tracker,
                     call_id,
                     freeform: false,
+                    sandbox_profile: params.sandbox_profile,
                 })
                 .await
             }
@@ -154,6 +159,7 @@ impl ToolHandler for ShellHandler {
                     tracker,
                     call_id,
                     freeform: false,
+                    sandbox_profile: None,
                 })
                 .await
             }
@@ -221,6 +227,7 @@ impl ToolHandler for ShellCommandHandler {
             tracker,
             call_id,
             freeform: true,
+            sandbox_profile: params.sandbox_profile,
         })
         .await
     }
@@ -237,6 +244,7 @@ impl ShellHandler {
             tracker,
             call_id,
             freeform,
+            sandbox_profile,
         } = args;
 
         let features = session.features();
@@ -253,6 +261,40 @@ impl ShellHandler {
             exec_params.env.extend(dependency_env);
         }
 
+        let sandbox_profile = match sandbox_profile {
+            Some(name) => {
+                let profile = resolve_sandbox_profile(turn.as_ref(), &name)?;
+                Some((name, profile))
+            }
+            None => None,
+        };
+        let sandbox_policy = sandbox_profile
+            .as_ref()
+            .map_or(&turn.sandbox_policy, |(_, profile)| &profile.policy);
+        set_sandbox_profile_proxy_env(
+            &mut exec_params.env,
+            turn.as_ref(),
+            sandbox_profile.as_ref().map(|(_, profile)| *profile),
+        );
+        let escalating_profile = sandbox_profile
+            .as_ref()
+            .filter(|(_, profile)| {
+                profile
+                    .policy
+                    .grants_more_than(&turn.sandbox_policy, &turn.cwd)
+            })
+            .map(|(name, _)| name.as_str());
+        if let Some(name) = escalating_profile
+            && matches!(
+                turn.approval_policy,
+                codex_protocol::protocol::AskForApproval::Never
+            )
+        {
+            return Err(FunctionCallError::RespondToModel(format!(
+                "sandbox profile `{name}` grants more than the current sandbox, which needs approval, but the approval policy is Never"
+            )));
+        }
+
         // Approval policy guard for explicit escalation in non-OnRequest modes.
         if exec_params
             .sandbox_permissions
@@ -294,17 +336,21 @@ impl ShellHandler {
         let event_ctx = ToolEventCtx::new(session.as_ref(), turn.as_ref(), &call_id, None);
         emitter.begin(event_ctx).await;
 
-        let exec_approval_requirement = session
+        let mut exec_approval_requirement = session
             .services
             .exec_policy
             .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                 command: &exec_params.command,
                 approval_policy: turn.approval_policy,
-                sandbox_policy: &turn.sandbox_policy,
+                sandbox_policy,
                 sandbox_permissions: exec_params.sandbox_permissions,
                 prefix_rule,
             })
             .await;
+        if let Some(name) = escalating_profile {
+            exec_approval_requirement =
+                require_approval_for_sandbox_profile(exec_approval_requirement, name);
+        }
 
         let req = ShellRequest {
             command: exec_params.command.clone(),
@@ -314,6 +360,7 @@ impl ShellHandler {
             sandbox_permissions: exec_params.sandbox_permissions,
             justification: exec_params.justification.clone(),
             exec_approval_requirement,
+            sandbox_profile: sandbox_profile.map(|(name, profile)| (name, profile.policy.clone())),
         };
         let mut orchestrator = ToolOrchestrator::new();
         let mut runtime = ShellRuntime::new();
@@ -428,6 +475,7 @@ mod tests {
             sandbox_permissions: Some(sandbox_permissions),
             prefix_rule: None,
             justification: justification.clone(),
+            sandbox_profile: None,
         };
 
         let exec_params = ShellCommandHandler::to_exec_params(
@@ -10,6 +10,7 @@ use crate::tools::context::ToolOutput;
 use crate::tools::context::ToolPayload;
 use crate::tools::handlers::apply_patch::intercept_apply_patch;
 use crate::tools::handlers::parse_arguments;
+use crate::tools::handlers::resolve_sandbox_profile;
 use crate::tools::registry::ToolHandler;
 use crate::tools::registry::ToolKind;
 use crate::unified_exec::ExecCommandRequest;
@@ -52,6 +53,8 @@ struct ExecCommandArgs {
     justification: Option<String>,
     #[serde(default)]
     prefix_rule: Option<Vec<String>>,
+    #[serde(default)]
+    sandbox_profile: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -173,6 +176,7 @@ impl ToolHandler for UnifiedExecHandler {
                     sandbox_permissions,
                     justification,
                     prefix_rule,
+                    sandbox_profile,
                     ..
                 } = args;
 
@@ -197,6 +201,31 @@ impl ToolHandler for UnifiedExecHandler {
                     )));
                 }
 
+                let sandbox_profile = match sandbox_profile {
+                    Some(name) => match resolve_sandbox_profile(context.turn.as_ref(), &name) {
+                        Ok(profile) => Some((name, profile.clone())),
+                        Err(err) => {
+                            manager.release_process_id(&process_id).await;
+                            return Err(err);
+                        }
+                    },
+                    None => None,
+                };
+                if let Some((name, profile)) = &sandbox_profile
+                    && profile
+                        .policy
+                        .grants_more_than(&context.turn.sandbox_policy, &context.turn.cwd)
+                    && matches!(
+                        context.turn.approval_policy,
+                        codex_protocol::protocol::AskForApproval::Never
+                    )
+                {
+                    manager.release_process_id(&process_id).await;
+                    return Err(FunctionCallError::RespondToModel(format!(
+                        "sandbox profile `{name}` grants more than the current sandbox, which needs approval, but the approval policy is Never"
+                    )));
+                }
+
                 let workdir = workdir.filter(|value| !value.is_empty());
 
                 let workdir = workdir.map(|dir| context.turn.resolve_path(Some(dir)));
@@ -231,6 +260,7 @@ impl ToolHandler for UnifiedExecHandler {
                             sandbox_permissions,
                             justification,
                             prefix_rule,
+                            sandbox_profile,
                         },
                         &context,
                     )
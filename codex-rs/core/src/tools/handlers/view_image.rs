@@ -14,13 +14,33 @@ use crate::tools::registry::ToolHandler;
 use crate::tools::registry::ToolKind;
 use codex_protocol::models::ContentItem;
 use codex_protocol::models::ResponseInputItem;
+use codex_protocol::models::image_close_tag_text;
 use codex_protocol::models::local_image_content_items_with_label_number;
+use codex_protocol::models::local_image_named_open_tag_text;
+use codex_utils_image::ImagePart;
+use codex_utils_image::ImageRegion;
+use codex_utils_image::ImageSelection;
+use codex_utils_image::PageRange;
+use codex_utils_image::error::ImageProcessingError;
+use codex_utils_image::load_image_parts;
 
 pub struct ViewImageHandler;
 
 #[derive(Deserialize)]
 struct ViewImageArgs {
     path: String,
+    #[serde(default)]
+    pages: Option<String>,
+    #[serde(default)]
+    region: Option<ViewImageRegion>,
+}
+
+#[derive(Deserialize)]
+struct ViewImageRegion {
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
 }
 
 #[async_trait]
@@ -48,6 +68,21 @@ impl ToolHandler for ViewImageHandler {
         };
 
         let args: ViewImageArgs = parse_arguments(&arguments)?;
+        let pages = args
+            .pages
+            .as_deref()
+            .map(str::parse::<PageRange>)
+            .transpose()
+            .map_err(|err| FunctionCallError::RespondToModel(err.to_string()))?;
+        let selection = ImageSelection {
+            pages,
+            region: args.region.map(|region| ImageRegion {
+                x: region.x,
+                y: region.y,
+                width: region.width,
+                height: region.height,
+            }),
+        };
 
         let abs_path = turn.resolve_path(Some(args.path));
 
@@ -66,8 +101,28 @@ impl ToolHandler for ViewImageHandler {
         }
         let event_path = abs_path.clone();
 
-        let content: Vec<ContentItem> =
-            local_image_content_items_with_label_number(&abs_path, None);
+        // Rendering PDF and SVG pages is CPU-bound.
+        let load_path = abs_path.clone();
+        let loaded = tokio::task::spawn_blocking(move || load_image_parts(&load_path, &selection))
+            .await
+            .map_err(|err| {
+                FunctionCallError::RespondToModel(format!("unable to load image: {err}"))
+            })?;
+        let (content, output) = match loaded {
+            Ok(parts) => {
+                let output = describe_parts(&parts);
+                (image_parts_content(parts), output)
+            }
+            Err(
+                err @ (ImageProcessingError::PageOutOfRange { .. }
+                | ImageProcessingError::RegionOutOfBounds { .. }),
+            ) => return Err(FunctionCallError::RespondToModel(err.to_string())),
+            // Files that are not images are reported through a placeholder.
+            Err(_) => (
+                local_image_content_items_with_label_number(&abs_path, None),
+                "attached local image path".to_string(),
+            ),
+        };
         let input = ResponseInputItem::Message {
             role: "user".to_string(),
             content,
@@ -93,8 +148,57 @@ impl ToolHandler for ViewImageHandler {
             .await;
 
         Ok(ToolOutput::Function {
-            body: FunctionCallOutputBody::Text("attached local image path".to_string()),
+            body: FunctionCallOutputBody::Text(output),
             success: Some(true),
         })
     }
 }
+
+/// Tags each labelled part so the model can tell pages and tiles apart.
+fn image_parts_content(parts: Vec<ImagePart>) -> Vec<ContentItem> {
+    let mut content = Vec::with_capacity(parts.len() * 3);
+    for part in parts {
+        let label = part.label;
+        if let Some(label) = &label {
+            content.push(ContentItem::InputText {
+                text: local_image_named_open_tag_text(label),
+            });
+        }
+        content.push(ContentItem::InputImage {
+            image_url: part.image.into_data_url(),
+        });
+        if label.is_some() {
+            content.push(ContentItem::InputText {
+                text: image_close_tag_text(),
+            });
+        }
+    }
+    content
+}
+
+/// Tool output listing what was attached, with any PDF page text.
+fn describe_parts(parts: &[ImagePart]) -> String {
+    if parts
+        .iter()
+        .all(|part| part.label.is_none() && part.text.is_none())
+    {
+        return "attached local image path".to_string();
+    }
+    let mut output = String::from("attached:");
+    for part in parts {
+        let label = part.label.as_deref().unwrap_or("image");
+        output.push_str(&format!(
+            "\n- {label} ({}x{} px)",
+            part.source_width, part.source_height
+        ));
+    }
+    output.push_str(
+        "\nRegions are in pixels of the full image or page. Call view_image with `pages` or `region` to see other pages or more detail.",
+    );
+    for part in parts {
+        if let (Some(label), Some(text)) = (&part.label, &part.text) {
+            output.push_str(&format!("\n\nText of {label}:\n{text}"));
+        }
+    }
+    output
+}
@@ -0,0 +1,764 @@
+use std::collections::VecDeque;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
+
+use async_trait::async_trait;
+use codex_protocol::models::FunctionCallOutputBody;
+use codex_protocol::protocol::AskForApproval;
+use codex_protocol::request_user_input::RequestUserInputQuestion;
+use codex_utils_host_policy::compile_globset;
+use codex_utils_host_policy::is_explicit_local_allowlisted;
+use codex_utils_host_policy::is_local_literal;
+use codex_utils_host_policy::is_non_public_ip;
+use codex_utils_host_policy::normalize_host;
+use codex_utils_host_policy::resolve_host;
+use reqwest::header::CONTENT_TYPE;
+use reqwest::header::LOCATION;
+use reqwest::redirect;
+use serde::Deserialize;
+use serde::Serialize;
+use url::Url;
+
+use crate::codex::Session;
+use crate::codex::TurnContext;
+use crate::config::types::NetworkProxySettings;
+use crate::default_client::get_codex_user_agent;
+use crate::function_tool::FunctionCallError;
+use crate::tools::approval_question::ApprovalAnswer;
+use crate::tools::approval_question::ApprovalOptions;
+use crate::tools::approval_question::request_approval;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::handlers::parse_arguments;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+const MAX_REDIRECTS: usize = 5;
+/// Bodies beyond this are cut before conversion.
+const MAX_BODY_BYTES: usize = 4 * 1024 * 1024;
+/// Room left in each page for the header lines.
+const PAGE_HEADER_BYTES: usize = 256;
+/// Documents kept per session so later pages are served without fetching
+/// the URL again.
+const CACHED_DOCUMENTS: usize = 4;
+const CACHED_DOCUMENT_TTL: Duration = Duration::from_secs(5 * 60);
+
+/// Elements dropped before HTML is converted to markdown.
+const STRIPPED_ELEMENTS: [&str; 5] = ["script", "style", "noscript", "svg", "template"];
+
+const WEB_FETCH_APPROVAL_QUESTION_ID_PREFIX: &str = "web_fetch_approval";
+
+pub struct WebFetchHandler;
+
+#[derive(Deserialize)]
+struct WebFetchArgs {
+    url: String,
+    #[serde(default = "default_page")]
+    page: usize,
+}
+
+fn default_page() -> usize {
+    1
+}
+
+/// Session approval cache key for "accept for session" on a host.
+#[derive(Serialize)]
+struct WebFetchApprovalKey<'a> {
+    web_fetch_host: &'a str,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DomainPolicy {
+    Allowed,
+    Denied,
+    /// Not covered by `[network_proxy.policy]`; the approval policy decides.
+    Unlisted,
+}
+
+/// Documents fetched earlier in the session, keyed by the requested URL.
+#[derive(Default)]
+pub(crate) struct WebFetchCache {
+    entries: VecDeque<CachedDocument>,
+}
+
+struct CachedDocument {
+    url: Url,
+    fetched_at: Instant,
+    page: Arc<FetchedPage>,
+}
+
+impl WebFetchCache {
+    fn get(&mut self, url: &Url) -> Option<Arc<FetchedPage>> {
+        self.entries
+            .retain(|entry| entry.fetched_at.elapsed() < CACHED_DOCUMENT_TTL);
+        self.entries
+            .iter()
+            .find(|entry| &entry.url == url)
+            .map(|entry| Arc::clone(&entry.page))
+    }
+
+    fn insert(&mut self, url: Url, page: Arc<FetchedPage>) {
+        self.entries.retain(|entry| entry.url != url);
+        if self.entries.len() == CACHED_DOCUMENTS {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(CachedDocument {
+            url,
+            fetched_at: Instant::now(),
+            page,
+        });
+    }
+}
+
+#[async_trait]
+impl ToolHandler for WebFetchHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation {
+            session,
+            turn,
+            payload,
+            call_id,
+            ..
+        } = invocation;
+
+        let arguments = match payload {
+            ToolPayload::Function { arguments } => arguments,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(
+                    "web_fetch handler received unsupported payload".to_string(),
+                ));
+            }
+        };
+
+        let args: WebFetchArgs = parse_arguments(&arguments)?;
+        if args.page == 0 {
+            return Err(FunctionCallError::RespondToModel(
+                "page must be 1 or greater".to_string(),
+            ));
+        }
+        let url = Url::parse(&args.url).map_err(|err| {
+            FunctionCallError::RespondToModel(format!("invalid url `{}`: {err}", args.url))
+        })?;
+        if !matches!(url.scheme(), "http" | "https") {
+            return Err(FunctionCallError::RespondToModel(format!(
+                "unsupported url scheme `{}`; only http and https are supported",
+                url.scheme()
+            )));
+        }
+        let host = url_host(&url)
+            .ok_or_else(|| FunctionCallError::RespondToModel(format!("url `{url}` has no host")))?;
+
+        let config = turn.client.config();
+        let settings = &config.network_proxy;
+        let checked = check_host(settings, &url, &host).await?;
+        match checked.policy {
+            DomainPolicy::Allowed => {}
+            DomainPolicy::Denied => {
+                return Err(FunctionCallError::RespondToModel(format!(
+                    "`{host}` is blocked by network_proxy.policy.denied_domains"
+                )));
+            }
+            DomainPolicy::Unlisted => {
+                request_web_fetch_approval(&session, &turn, &call_id, &host).await?;
+            }
+        }
+
+        let cached = session.services.web_fetch_cache.lock().await.get(&url);
+        let fetched = match cached {
+            Some(fetched) => fetched,
+            None => {
+                let fetched = Arc::new(fetch(settings, &url, checked, &host).await?);
+                session
+                    .services
+                    .web_fetch_cache
+                    .lock()
+                    .await
+                    .insert(url.clone(), Arc::clone(&fetched));
+                fetched
+            }
+        };
+        let page_bytes = turn
+            .truncation_policy
+            .byte_budget()
+            .saturating_sub(PAGE_HEADER_BYTES)
+            .max(PAGE_HEADER_BYTES);
+        let pages = paginate(&fetched.content, page_bytes);
+        let total = pages.len();
+        let Some(content) = pages.get(args.page - 1) else {
+            return Err(FunctionCallError::RespondToModel(format!(
+                "page {} is out of range; `{}` has {total} page(s)",
+                args.page, fetched.url
+            )));
+        };
+
+        let mut output = format!("URL: {}\n", fetched.url);
+        if total > 1 {
+            output.push_str(&format!("Page {} of {total}", args.page));
+            if args.page < total {
+                output.push_str(&format!(
+                    " (call web_fetch with page = {} for more)",
+                    args.page + 1
+                ));
+            }
+            output.push('\n');
+        }
+        if fetched.truncated {
+            output.push_str(&format!(
+                "Note: the response exceeded {MAX_BODY_BYTES} bytes and was cut off.\n"
+            ));
+        }
+        output.push('\n');
+        output.push_str(content);
+
+        Ok(ToolOutput::Function {
+            body: FunctionCallOutputBody::Text(output),
+            success: Some(true),
+        })
+    }
+}
+
+/// Ask the user before fetching from a host outside the domain policy. The
+/// sandbox's network access does not apply: the fetch runs in Codex itself.
+async fn request_web_fetch_approval(
+    session: &Session,
+    turn: &TurnContext,
+    call_id: &str,
+    host: &str,
+) -> Result<(), FunctionCallError> {
+    if matches!(turn.approval_policy, AskForApproval::Never) {
+        return Err(FunctionCallError::RespondToModel(format!(
+            "`{host}` is not in network_proxy.policy.allowed_domains and approvals are disabled"
+        )));
+    }
+
+    let key = WebFetchApprovalKey {
+        web_fetch_host: host,
+    };
+    let question_id = format!("{WEB_FETCH_APPROVAL_QUESTION_ID_PREFIX}_{call_id}");
+    let question = build_web_fetch_approval_question(question_id, host);
+    match request_approval(session, turn, call_id, question, Some(key)).await {
+        None | Some(ApprovalAnswer::Accept | ApprovalAnswer::AcceptForSession) => Ok(()),
+        Some(ApprovalAnswer::Decline) => Err(FunctionCallError::RespondToModel(format!(
+            "user declined fetching from `{host}`"
+        ))),
+        Some(ApprovalAnswer::Cancel) => Err(FunctionCallError::RespondToModel(format!(
+            "user cancelled fetching from `{host}`"
+        ))),
+    }
+}
+
+fn build_web_fetch_approval_question(question_id: String, host: &str) -> RequestUserInputQuestion {
+    RequestUserInputQuestion {
+        id: question_id,
+        header: "Allow web fetch?".to_string(),
+        question: format!(
+            "Codex wants to fetch a page from `{host}`, which is not in your network allowlist. Allow this request?"
+        ),
+        is_other: false,
+        is_secret: false,
+        options: Some(
+            ApprovalOptions {
+                accept: "Fetch the page and continue.",
+                accept_for_session: Some("Fetch and don't ask again for this host this session."),
+                decline: "Skip this fetch and continue.",
+                cancel: "Cancel this fetch",
+            }
+            .into_options(),
+        ),
+    }
+}
+
+/// The normalized host of `url`, as `codex-network-proxy` matches it.
+fn url_host(url: &Url) -> Option<String> {
+    let host = normalize_host(url.host_str()?);
+    (!host.is_empty()).then_some(host)
+}
+
+/// Evaluate `host` against `[network_proxy.policy]` with the proxy's
+/// matcher. Denies win over allows.
+fn domain_policy(
+    settings: &NetworkProxySettings,
+    host: &str,
+) -> Result<DomainPolicy, FunctionCallError> {
+    let compile = |patterns: &[String]| {
+        compile_globset(patterns).map_err(|err| {
+            FunctionCallError::RespondToModel(format!(
+                "invalid network_proxy.policy domain pattern: {err}"
+            ))
+        })
+    };
+    Ok(if compile(&settings.denied_domains)?.is_match(host) {
+        DomainPolicy::Denied
+    } else if compile(&settings.allowed_domains)?.is_match(host) {
+        DomainPolicy::Allowed
+    } else {
+        DomainPolicy::Unlisted
+    })
+}
+
+/// A host that passed [`check_host`].
+struct CheckedHost {
+    policy: DomainPolicy,
+    /// Addresses the host resolved to when it was checked. The connection is
+    /// pinned to them so a second DNS answer cannot point it somewhere else.
+    /// Empty for denied hosts and when only the proxy can resolve the host.
+    addrs: Vec<SocketAddr>,
+}
+
+/// [`domain_policy`] plus the proxy's local-address check: unless
+/// `allow_local_binding` is set, local IP literals need an exact
+/// `allowed_domains` entry and hostnames that resolve to local or private
+/// addresses are refused.
+async fn check_host(
+    settings: &NetworkProxySettings,
+    url: &Url,
+    host: &str,
+) -> Result<CheckedHost, FunctionCallError> {
+    let policy = domain_policy(settings, host)?;
+    if policy == DomainPolicy::Denied {
+        return Ok(CheckedHost {
+            policy,
+            addrs: Vec::new(),
+        });
+    }
+    let local_error = || {
+        FunctionCallError::RespondToModel(format!(
+            "`{host}` is a local or private address; set network_proxy.policy.allow_local_binding to fetch it"
+        ))
+    };
+    let local_literal = is_local_literal(host);
+    if !settings.allow_local_binding
+        && local_literal
+        && !is_explicit_local_allowlisted(&settings.allowed_domains, host)
+    {
+        return Err(local_error());
+    }
+    let port = url.port_or_known_default().unwrap_or(443);
+    let addrs = match resolve_host(host, port).await {
+        Some(addrs) => addrs,
+        // The proxy resolves the host itself and applies the same checks.
+        None if settings.proxy_url.is_some() => Vec::new(),
+        None => {
+            return Err(FunctionCallError::RespondToModel(format!(
+                "failed to resolve `{host}`"
+            )));
+        }
+    };
+    if !settings.allow_local_binding
+        && !local_literal
+        && addrs.iter().any(|addr| is_non_public_ip(addr.ip()))
+    {
+        return Err(local_error());
+    }
+    Ok(CheckedHost { policy, addrs })
+}
+
+struct FetchedPage {
+    url: Url,
+    content: String,
+    truncated: bool,
+}
+
+/// The checked host of a redirect target, or `None` when the redirect must
+/// not be followed without a new approval.
+async fn redirect_followable(
+    settings: &NetworkProxySettings,
+    approved_host: &str,
+    next: &Url,
+) -> Option<CheckedHost> {
+    if !matches!(next.scheme(), "http" | "https") {
+        return None;
+    }
+    let host = url_host(next)?;
+    let checked = check_host(settings, next, &host).await.ok()?;
+    match checked.policy {
+        DomainPolicy::Allowed => Some(checked),
+        DomainPolicy::Unlisted if host == approved_host => Some(checked),
+        DomainPolicy::Unlisted | DomainPolicy::Denied => None,
+    }
+}
+
+/// A client that connects to `url`'s host only at `addrs`.
+fn build_client(
+    settings: &NetworkProxySettings,
+    url: &Url,
+    addrs: &[SocketAddr],
+) -> Result<reqwest::Client, FunctionCallError> {
+    let mut builder = reqwest::Client::builder()
+        .user_agent(get_codex_user_agent())
+        .timeout(REQUEST_TIMEOUT)
+        .redirect(redirect::Policy::none());
+    if let Some(domain) = url.domain()
+        && !addrs.is_empty()
+    {
+        builder = builder.resolve_to_addrs(domain, addrs);
+    }
+    if let Some(proxy_url) = &settings.proxy_url {
+        let proxy = reqwest::Proxy::all(proxy_url).map_err(|err| {
+            FunctionCallError::RespondToModel(format!(
+                "invalid network_proxy.proxy_url `{proxy_url}`: {err}"
+            ))
+        })?;
+        builder = builder.proxy(proxy);
+    }
+    builder.build().map_err(|err| {
+        FunctionCallError::RespondToModel(format!("failed to build http client: {err}"))
+    })
+}
+
+async fn fetch(
+    settings: &NetworkProxySettings,
+    url: &Url,
+    checked: CheckedHost,
+    host: &str,
+) -> Result<FetchedPage, FunctionCallError> {
+    // Redirects are followed here so each hop is checked like the first
+    // request: they stay on the approved host or go to allowlisted ones, and
+    // never to a local address. Anything else is handed back to the model so
+    // the next fetch is checked again.
+    let mut current = url.clone();
+    let mut addrs = checked.addrs;
+    let mut redirects = 0;
+    let mut response = loop {
+        let response = build_client(settings, &current, &addrs)?
+            .get(current.clone())
+            .header(
+                reqwest::header::ACCEPT,
+                "text/html, text/markdown;q=0.9, text/plain;q=0.8, */*;q=0.5",
+            )
+            .send()
+            .await
+            .map_err(|err| {
+                FunctionCallError::RespondToModel(format!("failed to fetch {current}: {err}"))
+            })?;
+        let status = response.status();
+        if !status.is_redirection() {
+            break response;
+        }
+        let Some(location) = response
+            .headers()
+            .get(LOCATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|location| current.join(location).ok())
+        else {
+            return Err(FunctionCallError::RespondToModel(format!(
+                "{current} returned {status} without a usable Location header"
+            )));
+        };
+        if redirects == MAX_REDIRECTS {
+            return Err(FunctionCallError::RespondToModel(format!(
+                "{url} redirected more than {MAX_REDIRECTS} times"
+            )));
+        }
+        let Some(next) = redirect_followable(settings, host, &location).await else {
+            return Err(FunctionCallError::RespondToModel(format!(
+                "{current} redirects to {location}, which needs its own check; call web_fetch with that url to continue"
+            )));
+        };
+        redirects += 1;
+        addrs = next.addrs;
+        current = location;
+    };
+
+    let status = response.status();
+    let final_url = current;
+    if !status.is_success() {
+        return Err(FunctionCallError::RespondToModel(format!(
+            "{final_url} returned {status}"
+        )));
+    }
+
+    let content_type = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| {
+            value
+                .split(';')
+                .next()
+                .unwrap_or_default()
+                .trim()
+                .to_ascii_lowercase()
+        })
+        .unwrap_or_default();
+    let is_html = content_type.is_empty()
+        || content_type == "text/html"
+        || content_type == "application/xhtml+xml";
+    let is_text = content_type.starts_with("text/")
+        || content_type.ends_with("+json")
+        || content_type.ends_with("+xml")
+        || matches!(
+            content_type.as_str(),
+            "application/json" | "application/xml" | "application/javascript"
+        );
+    if !is_html && !is_text {
+        return Err(FunctionCallError::RespondToModel(format!(
+            "{final_url} returned unsupported content type `{content_type}`"
+        )));
+    }
+
+    let mut body = Vec::new();
+    let mut truncated = false;
+    while let Some(chunk) = response.chunk().await.map_err(|err| {
+        FunctionCallError::RespondToModel(format!("failed to read {final_url}: {err}"))
+    })? {
+        let remaining = MAX_BODY_BYTES - body.len();
+        if chunk.len() > remaining {
+            body.extend_from_slice(&chunk[..remaining]);
+            truncated = true;
+            break;
+        }
+        body.extend_from_slice(&chunk);
+    }
+    let text = String::from_utf8_lossy(&body);
+    let content = if is_html {
+        html_to_markdown(&text)
+    } else {
+        text.into_owned()
+    };
+
+    Ok(FetchedPage {
+        url: final_url,
+        content,
+        truncated,
+    })
+}
+
+fn html_to_markdown(html: &str) -> String {
+    let markdown = html2md::parse_html(&strip_elements(html, &STRIPPED_ELEMENTS));
+    let mut out = String::with_capacity(markdown.len());
+    let mut blank_lines = 0;
+    for line in markdown.lines() {
+        let line = line.trim_end();
+        if line.is_empty() {
+            blank_lines += 1;
+            if blank_lines > 1 || out.is_empty() {
+                continue;
+            }
+        } else {
+            blank_lines = 0;
+        }
+        out.push_str(line);
+        out.push('\n');
+    }
+    out.trim_end().to_string()
+}
+
+/// Remove `<tag ...>...</tag>` for each of `tags`, matching case-insensitively.
+/// An unclosed element swallows the rest of the document, like browsers do for
+/// `<script>`.
+fn strip_elements(html: &str, tags: &[&str]) -> String {
+    let lower = html.to_ascii_lowercase();
+    let mut out = String::with_capacity(html.len());
+    let mut pos = 0;
+    'scan: while pos < html.len() {
+        let mut next: Option<(usize, &str)> = None;
+        for tag in tags {
+            let open = format!("<{tag}");
+            let mut from = pos;
+            while let Some(found) = lower[from..].find(&open) {
+                let start = from + found;
+                let after = lower.as_bytes().get(start + open.len()).copied();
+                if after
+                    .is_none_or(|byte| byte == b'>' || byte == b'/' || byte.is_ascii_whitespace())
+                {
+                    if next.is_none_or(|(best, _)| start < best) {
+                        next = Some((start, tag));
+                    }
+                    break;
+                }
+                from = start + open.len();
+            }
+        }
+        let Some((start, tag)) = next else {
+            break 'scan;
+        };
+        out.push_str(&html[pos..start]);
+        let close = format!("</{tag}");
+        pos = match lower[start..].find(&close) {
+            Some(found) => {
+                let close_start = start + found;
+                lower[close_start..]
+                    .find('>')
+                    .map_or(html.len(), |end| close_start + end + 1)
+            }
+            None => html.len(),
+        };
+    }
+    if pos < html.len() {
+        out.push_str(&html[pos..]);
+    }
+    out
+}
+
+/// Split `text` into pages of at most `page_bytes`, breaking on line
+/// boundaries where possible and on char boundaries otherwise.
+fn paginate(text: &str, page_bytes: usize) -> Vec<&str> {
+    let page_bytes = page_bytes.max(1);
+    let mut pages = Vec::new();
+    let mut rest = text;
+    while rest.len() > page_bytes {
+        let mut end = page_bytes;
+        while !rest.is_char_boundary(end) {
+            end -= 1;
+        }
+        if let Some(newline) = rest[..end].rfind('\n') {
+            end = newline + 1;
+        } else if end == 0 {
+            end = rest
+                .char_indices()
+                .nth(1)
+                .map_or(rest.len(), |(index, _)| index);
+        }
+        pages.push(&rest[..end]);
+        rest = &rest[end..];
+    }
+    if !rest.is_empty() || pages.is_empty() {
+        pages.push(rest);
+    }
+    pages
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn settings(allowed: &[&str], denied: &[&str]) -> NetworkProxySettings {
+        NetworkProxySettings {
+            proxy_url: None,
+            allowed_domains: allowed.iter().map(ToString::to_string).collect(),
+            denied_domains: denied.iter().map(ToString::to_string).collect(),
+            allow_local_binding: false,
+        }
+    }
+
+    #[test]
+    fn domain_policy_follows_proxy_pattern_syntax() {
+        let settings = settings(&["docs.rs", "*.github.io", "**.python.org"], &[]);
+        let domain_policy = |settings: &NetworkProxySettings, host: &str| {
+            domain_policy(settings, host).expect("valid patterns")
+        };
+        assert_eq!(domain_policy(&settings, "docs.rs"), DomainPolicy::Allowed);
+        assert_eq!(
+            domain_policy(&settings, "www.docs.rs"),
+            DomainPolicy::Unlisted
+        );
+        assert_eq!(
+            domain_policy(&settings, "user.github.io"),
+            DomainPolicy::Allowed
+        );
+        assert_eq!(
+            domain_policy(&settings, "github.io"),
+            DomainPolicy::Unlisted
+        );
+        assert_eq!(
+            domain_policy(&settings, "python.org"),
+            DomainPolicy::Allowed
+        );
+        assert_eq!(
+            domain_policy(&settings, "docs.python.org"),
+            DomainPolicy::Allowed
+        );
+    }
+
+    #[tokio::test]
+    async fn denied_domains_win_and_local_hosts_need_an_exact_entry() {
+        let mut settings = settings(&["*", "192.168.1.10"], &["**.evil.example"]);
+        let check = |settings: NetworkProxySettings, url: &'static str| async move {
+            let url = Url::parse(url).expect("valid url");
+            let host = url_host(&url).expect("host");
+            check_host(&settings, &url, &host)
+                .await
+                .ok()
+                .map(|checked| checked.policy)
+        };
+        assert_eq!(
+            check(settings.clone(), "https://a.evil.example/").await,
+            Some(DomainPolicy::Denied)
+        );
+        assert_eq!(
+            check(settings.clone(), "http://192.168.1.10/").await,
+            Some(DomainPolicy::Allowed)
+        );
+        assert_eq!(
+            check(settings.clone(), "http://localhost:8080/").await,
+            None
+        );
+        assert_eq!(check(settings.clone(), "http://[::1]/").await, None);
+        assert_eq!(check(settings.clone(), "http://10.0.0.1/").await, None);
+
+        settings.allow_local_binding = true;
+        assert_eq!(
+            check(settings, "http://10.0.0.1/").await,
+            Some(DomainPolicy::Allowed)
+        );
+    }
+
+    #[test]
+    fn cache_serves_recent_documents_and_evicts_the_oldest() {
+        let url = |n: usize| Url::parse(&format!("https://docs.rs/{n}")).expect("valid url");
+        let page = |n: usize| {
+            Arc::new(FetchedPage {
+                url: url(n),
+                content: n.to_string(),
+                truncated: false,
+            })
+        };
+        let mut cache = WebFetchCache::default();
+        for n in 0..=CACHED_DOCUMENTS {
+            cache.insert(url(n), page(n));
+        }
+        assert!(cache.get(&url(0)).is_none());
+        assert_eq!(
+            cache.get(&url(1)).map(|page| page.content.clone()),
+            Some("1".to_string())
+        );
+    }
+
+    #[test]
+    fn url_host_normalizes_case_brackets_and_trailing_dot() {
+        let host = |url: &str| url_host(&Url::parse(url).expect("valid url"));
+        assert_eq!(host("https://Docs.RS./crate"), Some("docs.rs".to_string()));
+        assert_eq!(host("http://[::1]:8080/"), Some("::1".to_string()));
+    }
+
+    #[test]
+    fn html_is_converted_without_scripts_or_styles() {
+        let html = "<html><head><title>T</title><STYLE>p { color: red }</STYLE>\
+            <script type=\"text/javascript\">var x = 1;</script></head>\
+            <body><h2>Install</h2><p>Run <code>cargo add</code>, see \
+            <a href=\"/docs\">docs</a>.</p><noscript>enable js</noscript></body></html>";
+        assert_eq!(
+            html_to_markdown(html),
+            "T\n\nInstall\n----------\n\nRun `cargo add`, see [docs](/docs)."
+        );
+    }
+
+    #[test]
+    fn strip_elements_ignores_tags_with_matching_prefixes() {
+        assert_eq!(
+            strip_elements("<scripts>a</scripts><script>b</script>c", &["script"]),
+            "<scripts>a</scripts>c"
+        );
+        assert_eq!(strip_elements("a<style>b", &["style"]), "a");
+    }
+
+    #[test]
+    fn paginate_breaks_on_lines_and_char_boundaries() {
+        assert_eq!(
+            paginate("one\ntwo\nthree\n", 9),
+            vec!["one\ntwo\n", "three\n"]
+        );
+        assert_eq!(paginate("ééé", 3), vec!["é", "é", "é"]);
+        assert_eq!(paginate("", 10), vec![""]);
+    }
+}
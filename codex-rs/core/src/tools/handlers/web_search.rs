@@ -0,0 +1,88 @@
+use async_trait::async_trait;
+use codex_protocol::items::TurnItem;
+use codex_protocol::items::WebSearchItem;
+use codex_protocol::models::FunctionCallOutputBody;
+use codex_protocol::models::WebSearchAction;
+use serde::Deserialize;
+
+use crate::function_tool::FunctionCallError;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::handlers::parse_arguments;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+use crate::web_search::format_web_search_results;
+use crate::web_search::web_search_backend;
+
+/// Serves `web_search` from the configured `web_search_backend` for
+/// providers without hosted web search. Emits the same turn items as the
+/// hosted tool so clients render both alike.
+pub struct WebSearchHandler;
+
+#[derive(Deserialize)]
+struct WebSearchArgs {
+    query: String,
+}
+
+#[async_trait]
+impl ToolHandler for WebSearchHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation {
+            session,
+            turn,
+            payload,
+            call_id,
+            ..
+        } = invocation;
+
+        let arguments = match payload {
+            ToolPayload::Function { arguments } => arguments,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(
+                    "web_search handler received unsupported payload".to_string(),
+                ));
+            }
+        };
+
+        let args: WebSearchArgs = parse_arguments(&arguments)?;
+        let query = args.query.trim().to_string();
+        if query.is_empty() {
+            return Err(FunctionCallError::RespondToModel(
+                "query must not be empty".to_string(),
+            ));
+        }
+
+        let config = turn.client.config();
+        let Some(backend_config) = config.web_search_backend.as_ref() else {
+            return Err(FunctionCallError::RespondToModel(
+                "web_search is not configured for this session".to_string(),
+            ));
+        };
+        let backend = web_search_backend(backend_config);
+
+        let item = TurnItem::WebSearch(WebSearchItem {
+            id: call_id,
+            query: query.clone(),
+            action: WebSearchAction::Search {
+                query: Some(query.clone()),
+                queries: None,
+            },
+        });
+        session.emit_turn_item_started(turn.as_ref(), &item).await;
+        let results = backend.search(&query).await;
+        session.emit_turn_item_completed(turn.as_ref(), item).await;
+
+        let results = results.map_err(|err| {
+            FunctionCallError::RespondToModel(format!("web search failed: {err:#}"))
+        })?;
+        Ok(ToolOutput::Function {
+            body: FunctionCallOutputBody::Text(format_web_search_results(&query, &results)),
+            success: Some(true),
+        })
+    }
+}
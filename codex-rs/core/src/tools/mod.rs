@@ -1,3 +1,4 @@
+pub(crate) mod approval_question;
 pub mod context;
 pub mod events;
 pub(crate) mod handlers;
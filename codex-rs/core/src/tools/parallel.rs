@@ -8,7 +8,6 @@ use tokio_util::sync::CancellationToken;
 use tokio_util::task::AbortOnDropHandle;
 use tracing::Instrument;
 use tracing::instrument;
-use tracing::trace_span;
 
 use crate::codex::Session;
 use crate::codex::TurnContext;
@@ -18,6 +17,8 @@ use crate::tools::context::SharedTurnDiffTracker;
 use crate::tools::context::ToolPayload;
 use crate::tools::router::ToolCall;
 use crate::tools::router::ToolRouter;
+use codex_otel::traces::spans::record_outcome;
+use codex_otel::traces::spans::tool_call_span;
 use codex_protocol::models::FunctionCallOutputBody;
 use codex_protocol::models::FunctionCallOutputPayload;
 use codex_protocol::models::ResponseInputItem;
@@ -68,13 +69,7 @@ impl ToolCallRuntime {
         let ask_user_lock = Arc::clone(&self.ask_user_serial);
         let started = Instant::now();
 
-        let dispatch_span = trace_span!(
-            "dispatch_tool_call",
-            otel.name = call.tool_name.as_str(),
-            tool_name = call.tool_name.as_str(),
-            call_id = call.call_id.as_str(),
-            aborted = false,
-        );
+        let dispatch_span = tool_call_span(&call.tool_name, &call.call_id);
 
         let handle: AbortOnDropHandle<Result<ResponseInputItem, FunctionCallError>> =
             AbortOnDropHandle::new(tokio::spawn(async move {
@@ -96,10 +91,12 @@ impl ToolCallRuntime {
                             Either::Right(lock.write().await)
                         };
 
-                        router
+                        let res = router
                             .dispatch_tool_call(session, turn, tracker, call.clone())
                             .instrument(dispatch_span.clone())
-                            .await
+                            .await;
+                        record_outcome(&dispatch_span, res.is_ok(), started.elapsed());
+                        res
                     } => res,
                 }
             }));
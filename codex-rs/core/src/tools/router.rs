@@ -118,6 +118,7 @@ impl ToolRouter {
                             sandbox_permissions: Some(SandboxPermissions::UseDefault),
                             prefix_rule: None,
                             justification: None,
+                            sandbox_profile: None,
                         };
                         Ok(Some(ToolCall {
                             tool_name: "local_shell".to_string(),
@@ -20,6 +20,8 @@ use crate::tools::sandboxing::ToolError;
 use crate::tools::sandboxing::ToolRuntime;
 use crate::tools::sandboxing::with_cached_approval;
 use codex_apply_patch::ApplyPatchAction;
+use codex_otel::traces::spans::patch_apply_span;
+use codex_otel::traces::spans::record_outcome;
 use codex_protocol::protocol::AskForApproval;
 use codex_protocol::protocol::FileChange;
 use codex_protocol::protocol::ReviewDecision;
@@ -27,6 +29,8 @@ use codex_utils_absolute_path::AbsolutePathBuf;
 use futures::future::BoxFuture;
 use std::collections::HashMap;
 use std::path::PathBuf;
+use std::time::Instant;
+use tracing::Instrument;
 
 #[derive(Debug)]
 pub struct ApplyPatchRequest {
@@ -149,13 +153,17 @@ impl ToolRuntime<ApplyPatchRequest, ExecToolCallOutput> for ApplyPatchRuntime {
         attempt: &SandboxAttempt<'_>,
         ctx: &ToolCtx<'_>,
     ) -> Result<ExecToolCallOutput, ToolError> {
+        let span = patch_apply_span(&ctx.call_id, req.changes.len());
         let spec = Self::build_command_spec(req)?;
         let env = attempt
             .env_for(spec)
             .map_err(|err| ToolError::Codex(err.into()))?;
-        let out = execute_env(env, attempt.policy, Self::stdout_stream(ctx))
-            .await
-            .map_err(ToolError::Codex)?;
-        Ok(out)
+        let start = Instant::now();
+        let result = execute_env(env, attempt.policy, Self::stdout_stream(ctx))
+            .instrument(span.clone())
+            .await;
+        let success = matches!(&result, Ok(output) if output.exit_code == 0);
+        record_outcome(&span, success, start.elapsed());
+        result.map_err(ToolError::Codex)
     }
 }
@@ -7,6 +7,7 @@ builds a CommandSpec, and runs it under the current SandboxAttempt.
 use crate::exec::ExecToolCallOutput;
 use crate::features::Feature;
 use crate::powershell::prefix_powershell_script_with_utf8;
+use crate::protocol::SandboxPolicy;
 use crate::sandboxing::SandboxPermissions;
 use crate::sandboxing::execute_env;
 use crate::shell::ShellType;
@@ -36,6 +37,9 @@ pub struct ShellRequest {
     pub sandbox_permissions: SandboxPermissions,
     pub justification: Option<String>,
     pub exec_approval_requirement: ExecApprovalRequirement,
+    /// Name and policy of the sandbox profile the command was run under, if
+    /// it replaces the turn's sandbox policy.
+    pub sandbox_profile: Option<(String, SandboxPolicy)>,
 }
 
 #[derive(Default)]
@@ -46,6 +50,7 @@ pub(crate) struct ApprovalKey {
     command: Vec<String>,
     cwd: PathBuf,
     sandbox_permissions: SandboxPermissions,
+    sandbox_profile: Option<String>,
 }
 
 impl ShellRuntime {
@@ -79,6 +84,7 @@ impl Approvable<ShellRequest> for ShellRuntime {
             command: req.command.clone(),
             cwd: req.cwd.clone(),
             sandbox_permissions: req.sandbox_permissions,
+            sandbox_profile: req.sandbox_profile.as_ref().map(|(name, _)| name.clone()),
         }]
     }
 
@@ -120,6 +126,10 @@ impl Approvable<ShellRequest> for ShellRuntime {
         Some(req.exec_approval_requirement.clone())
     }
 
+    fn sandbox_policy<'a>(&self, req: &'a ShellRequest) -> Option<&'a SandboxPolicy> {
+        req.sandbox_profile.as_ref().map(|(_, policy)| policy)
+    }
+
     fn sandbox_mode_for_first_attempt(&self, req: &ShellRequest) -> SandboxOverride {
         if req.sandbox_permissions.requires_escalated_permissions()
             || matches!(
@@ -9,6 +9,7 @@ use crate::error::SandboxErr;
 use crate::exec::ExecExpiration;
 use crate::features::Feature;
 use crate::powershell::prefix_powershell_script_with_utf8;
+use crate::protocol::SandboxPolicy;
 use crate::sandboxing::SandboxPermissions;
 use crate::shell::ShellType;
 use crate::tools::runtimes::build_command_spec;
@@ -43,6 +44,9 @@ pub struct UnifiedExecRequest {
     pub sandbox_permissions: SandboxPermissions,
     pub justification: Option<String>,
     pub exec_approval_requirement: ExecApprovalRequirement,
+    /// Name and policy of the sandbox profile the command was run under, if
+    /// it replaces the turn's sandbox policy.
+    pub sandbox_profile: Option<(String, SandboxPolicy)>,
 }
 
 #[derive(serde::Serialize, Clone, Debug, Eq, PartialEq, Hash)]
@@ -51,6 +55,7 @@ pub struct UnifiedExecApprovalKey {
     pub cwd: PathBuf,
     pub tty: bool,
     pub sandbox_permissions: SandboxPermissions,
+    pub sandbox_profile: Option<String>,
 }
 
 pub struct UnifiedExecRuntime<'a> {
@@ -58,6 +63,7 @@ pub struct UnifiedExecRuntime<'a> {
 }
 
 impl UnifiedExecRequest {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         command: Vec<String>,
         cwd: PathBuf,
@@ -67,6 +73,7 @@ impl UnifiedExecRequest {
         sandbox_permissions: SandboxPermissions,
         justification: Option<String>,
         exec_approval_requirement: ExecApprovalRequirement,
+        sandbox_profile: Option<(String, SandboxPolicy)>,
     ) -> Self {
         Self {
             command,
@@ -77,6 +84,7 @@ impl UnifiedExecRequest {
             sandbox_permissions,
             justification,
             exec_approval_requirement,
+            sandbox_profile,
         }
     }
 }
@@ -106,6 +114,7 @@ impl Approvable<UnifiedExecRequest> for UnifiedExecRuntime<'_> {
             cwd: req.cwd.clone(),
             tty: req.tty,
             sandbox_permissions: req.sandbox_permissions,
+            sandbox_profile: req.sandbox_profile.as_ref().map(|(name, _)| name.clone()),
         }]
     }
 
@@ -150,6 +159,10 @@ impl Approvable<UnifiedExecRequest> for UnifiedExecRuntime<'_> {
         Some(req.exec_approval_requirement.clone())
     }
 
+    fn sandbox_policy<'b>(&self, req: &'b UnifiedExecRequest) -> Option<&'b SandboxPolicy> {
+        req.sandbox_profile.as_ref().map(|(_, policy)| policy)
+    }
+
     fn sandbox_mode_for_first_attempt(&self, req: &UnifiedExecRequest) -> SandboxOverride {
         if req.sandbox_permissions.requires_escalated_permissions()
             || matches!(
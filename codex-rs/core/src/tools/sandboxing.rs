@@ -6,6 +6,7 @@
 
 use crate::codex::Session;
 use crate::codex::TurnContext;
+use crate::config::types::SandboxProfile;
 use crate::error::CodexErr;
 use crate::protocol::SandboxPolicy;
 use crate::sandboxing::CommandSpec;
@@ -179,6 +180,55 @@ pub(crate) fn default_exec_approval_requirement(
     }
 }
 
+/// Variables that point HTTP clients at the proxy of a `network = "proxy"`
+/// sandbox profile. Lowercase variants are what curl reads.
+const PROXY_ENV_VARS: [&str; 4] = ["HTTP_PROXY", "HTTPS_PROXY", "http_proxy", "https_proxy"];
+
+/// Points `env` at the proxy of the sandbox profile a command runs under:
+/// the one the call requested, else the one the session was switched to.
+pub(crate) fn set_sandbox_profile_proxy_env(
+    env: &mut HashMap<String, String>,
+    turn: &TurnContext,
+    requested: Option<&SandboxProfile>,
+) {
+    let profile = requested.or_else(|| {
+        turn.sandbox_profile
+            .as_deref()
+            .and_then(|name| turn.sandbox_profiles.get(name))
+    });
+    if let Some(proxy_url) = profile.and_then(|profile| profile.proxy_url.as_ref()) {
+        for key in PROXY_ENV_VARS {
+            env.insert(key.to_string(), proxy_url.clone());
+        }
+    }
+}
+
+/// Running under a profile that grants more than the turn's sandbox always
+/// needs the user's approval, even for commands the exec policy trusts.
+pub(crate) fn require_approval_for_sandbox_profile(
+    requirement: ExecApprovalRequirement,
+    name: &str,
+) -> ExecApprovalRequirement {
+    let reason = format!("run under sandbox profile `{name}`");
+    match requirement {
+        ExecApprovalRequirement::Skip {
+            bypass_sandbox: false,
+            proposed_execpolicy_amendment,
+        } => ExecApprovalRequirement::NeedsApproval {
+            reason: Some(reason),
+            proposed_execpolicy_amendment,
+        },
+        ExecApprovalRequirement::NeedsApproval {
+            reason: None,
+            proposed_execpolicy_amendment,
+        } => ExecApprovalRequirement::NeedsApproval {
+            reason: Some(reason),
+            proposed_execpolicy_amendment,
+        },
+        requirement => requirement,
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub(crate) enum SandboxOverride {
     NoOverride,
@@ -204,6 +254,12 @@ pub(crate) trait Approvable<Req> {
         SandboxOverride::NoOverride
     }
 
+    /// Sandbox policy to run the request under instead of the turn's, e.g. a
+    /// sandbox profile picked for a single command. Defaults to `None`.
+    fn sandbox_policy<'a>(&self, _req: &'a Req) -> Option<&'a SandboxPolicy> {
+        None
+    }
+
     fn should_bypass_approval(&self, policy: AskForApproval, already_approved: bool) -> bool {
         if already_approved {
             // We do not ask one more time
@@ -276,6 +332,7 @@ pub(crate) struct SandboxAttempt<'a> {
     pub codex_linux_sandbox_exe: Option<&'a std::path::PathBuf>,
     pub use_linux_sandbox_bwrap: bool,
     pub windows_sandbox_level: codex_protocol::config_types::WindowsSandboxLevel,
+    pub resource_limits: crate::config::types::ResourceLimits,
 }
 
 impl<'a> SandboxAttempt<'a> {
@@ -292,6 +349,7 @@ impl<'a> SandboxAttempt<'a> {
                 codex_linux_sandbox_exe: self.codex_linux_sandbox_exe,
                 use_linux_sandbox_bwrap: self.use_linux_sandbox_bwrap,
                 windows_sandbox_level: self.windows_sandbox_level,
+                resource_limits: self.resource_limits,
             })
     }
 }
@@ -321,11 +379,39 @@ mod tests {
     #[test]
     fn restricted_sandbox_requires_exec_approval_on_request() {
         assert_eq!(
-            default_exec_approval_requirement(AskForApproval::OnRequest, &SandboxPolicy::ReadOnly),
+            default_exec_approval_requirement(
+                AskForApproval::OnRequest,
+                &SandboxPolicy::new_read_only_policy()
+            ),
             ExecApprovalRequirement::NeedsApproval {
                 reason: None,
                 proposed_execpolicy_amendment: None,
             }
         );
     }
+
+    #[test]
+    fn escalating_sandbox_profile_requires_approval() {
+        assert_eq!(
+            require_approval_for_sandbox_profile(
+                ExecApprovalRequirement::Skip {
+                    bypass_sandbox: false,
+                    proposed_execpolicy_amendment: None,
+                },
+                "build",
+            ),
+            ExecApprovalRequirement::NeedsApproval {
+                reason: Some("run under sandbox profile `build`".to_string()),
+                proposed_execpolicy_amendment: None,
+            }
+        );
+
+        let forbidden = ExecApprovalRequirement::Forbidden {
+            reason: "blocked by policy".to_string(),
+        };
+        assert_eq!(
+            require_approval_for_sandbox_profile(forbidden.clone(), "build"),
+            forbidden
+        );
+    }
 }
@@ -37,6 +37,12 @@ pub(crate) struct ToolsConfig {
     pub experimental_supported_tools: Vec<String>,
     pub mcp_search_enabled: bool,
     pub request_user_input_enabled: bool,
+    pub web_fetch: bool,
+    /// Serve `web_search` from the configured `web_search_backend` instead
+    /// of the hosted tool.
+    pub local_web_search: bool,
+    /// Names of the configured sandbox profiles the shell tools may select.
+    pub sandbox_profiles: Vec<String>,
 }
 
 pub(crate) struct ToolsConfigParams<'a> {
@@ -57,6 +63,7 @@ impl ToolsConfig {
         let include_apply_patch_tool = features.enabled(Feature::ApplyPatchFreeform);
         let include_collab_tools = features.enabled(Feature::Collab);
         let request_rule_enabled = features.enabled(Feature::RequestRule);
+        let web_fetch = features.enabled(Feature::WebFetch);
 
         let shell_type = if !features.enabled(Feature::ShellTool) {
             ConfigShellToolType::Disabled
@@ -104,8 +111,21 @@ impl ToolsConfig {
             experimental_supported_tools: model_info.experimental_supported_tools.clone(),
             mcp_search_enabled,
             request_user_input_enabled,
+            web_fetch,
+            local_web_search: false,
+            sandbox_profiles: Vec::new(),
         }
     }
+
+    pub fn with_sandbox_profiles(mut self, sandbox_profiles: Vec<String>) -> Self {
+        self.sandbox_profiles = sandbox_profiles;
+        self
+    }
+
+    pub fn with_local_web_search(mut self, local_web_search: bool) -> Self {
+        self.local_web_search = local_web_search;
+        self
+    }
 }
 
 /// Generic JSON‑Schema subset needed for our tool definitions
@@ -206,7 +226,26 @@ fn create_approval_parameters(include_prefix_rule: bool) -> BTreeMap<String, Jso
     properties
 }
 
-fn create_exec_command_tool(include_prefix_rule: bool) -> ToolSpec {
+fn create_sandbox_profile_parameter(sandbox_profiles: &[String]) -> Option<(String, JsonSchema)> {
+    if sandbox_profiles.is_empty() {
+        return None;
+    }
+    let names = sandbox_profiles
+        .iter()
+        .map(|name| format!("\"{name}\""))
+        .collect::<Vec<_>>()
+        .join(", ");
+    Some((
+        "sandbox_profile".to_string(),
+        JsonSchema::String {
+            description: Some(format!(
+                "Run the command under a named sandbox profile instead of the current sandbox, e.g. to write to build caches. One of {names}. Profiles that grant more than the current sandbox require user approval."
+            )),
+        },
+    ))
+}
+
+fn create_exec_command_tool(include_prefix_rule: bool, sandbox_profiles: &[String]) -> ToolSpec {
     let mut properties = BTreeMap::from([
         (
             "cmd".to_string(),
@@ -289,6 +328,7 @@ fn create_exec_command_tool(include_prefix_rule: bool) -> ToolSpec {
         ),
     ]);
     properties.extend(create_approval_parameters(include_prefix_rule));
+    properties.extend(create_sandbox_profile_parameter(sandbox_profiles));
 
     ToolSpec::Function(ResponsesApiTool {
         name: "exec_command".to_string(),
@@ -367,7 +407,7 @@ fn create_write_stdin_tool() -> ToolSpec {
     })
 }
 
-fn create_shell_tool(include_prefix_rule: bool) -> ToolSpec {
+fn create_shell_tool(include_prefix_rule: bool, sandbox_profiles: &[String]) -> ToolSpec {
     let mut properties = BTreeMap::from([
         (
             "command".to_string(),
@@ -390,6 +430,7 @@ fn create_shell_tool(include_prefix_rule: bool) -> ToolSpec {
         ),
     ]);
     properties.extend(create_approval_parameters(include_prefix_rule));
+    properties.extend(create_sandbox_profile_parameter(sandbox_profiles));
 
     let description  = if cfg!(windows) {
         r#"Runs a Powershell command (Windows) and returns its output. Arguments to `shell` will be passed to CreateProcessW(). Most commands should be prefixed with ["powershell.exe", "-Command"].
@@ -420,7 +461,7 @@ Examples of valid command strings:
     })
 }
 
-fn create_shell_command_tool(include_prefix_rule: bool) -> ToolSpec {
+fn create_shell_command_tool(include_prefix_rule: bool, sandbox_profiles: &[String]) -> ToolSpec {
     let mut properties = BTreeMap::from([
         (
             "command".to_string(),
@@ -453,6 +494,7 @@ fn create_shell_command_tool(include_prefix_rule: bool) -> ToolSpec {
         ),
     ]);
     properties.extend(create_approval_parameters(include_prefix_rule));
+    properties.extend(create_sandbox_profile_parameter(sandbox_profiles));
 
     let description = if cfg!(windows) {
         r#"Runs a Powershell command (Windows) and returns its output.
@@ -484,21 +526,124 @@ Examples of valid command strings:
 
 fn create_view_image_tool() -> ToolSpec {
     // Support only local filesystem path.
+    let region_properties = BTreeMap::from([
+        (
+            "x".to_string(),
+            JsonSchema::Number {
+                description: Some("Left edge in pixels.".to_string()),
+            },
+        ),
+        (
+            "y".to_string(),
+            JsonSchema::Number {
+                description: Some("Top edge in pixels.".to_string()),
+            },
+        ),
+        (
+            "width".to_string(),
+            JsonSchema::Number {
+                description: Some("Width in pixels.".to_string()),
+            },
+        ),
+        (
+            "height".to_string(),
+            JsonSchema::Number {
+                description: Some("Height in pixels.".to_string()),
+            },
+        ),
+    ]);
+    let properties = BTreeMap::from([
+        (
+            "path".to_string(),
+            JsonSchema::String {
+                description: Some("Local filesystem path to an image, PDF or SVG file".to_string()),
+            },
+        ),
+        (
+            "pages".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "PDF pages to view, such as \"3\" or \"2-5\". Defaults to the first 10 pages."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "region".to_string(),
+            JsonSchema::Object {
+                properties: region_properties,
+                required: Some(vec![
+                    "x".to_string(),
+                    "y".to_string(),
+                    "width".to_string(),
+                    "height".to_string(),
+                ]),
+                additional_properties: Some(false.into()),
+            },
+        ),
+    ]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: VIEW_IMAGE_TOOL_NAME.to_string(),
+        description: "View a local image, PDF or SVG from the filesystem (only use if given a full filepath by the user, and the image isn't already attached to the thread context within <image ...> tags). Large images are split into labelled tiles; pass `region`, in pixels of the full image or page, to zoom into part of one."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["path".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+fn create_web_fetch_tool() -> ToolSpec {
+    let properties = BTreeMap::from([
+        (
+            "url".to_string(),
+            JsonSchema::String {
+                description: Some("Absolute http(s) URL of the page to fetch.".to_string()),
+            },
+        ),
+        (
+            "page".to_string(),
+            JsonSchema::Number {
+                description: Some(
+                    "1-indexed page of the converted content to return. Long pages are split; the response says how many pages there are."
+                        .to_string(),
+                ),
+            },
+        ),
+    ]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "web_fetch".to_string(),
+        description: "Fetch a web page and return its content as markdown. Use it to read documentation or other pages the user points you to; requests follow the user's network domain policy and may need their approval."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["url".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+fn create_local_web_search_tool() -> ToolSpec {
     let properties = BTreeMap::from([(
-        "path".to_string(),
+        "query".to_string(),
         JsonSchema::String {
-            description: Some("Local filesystem path to an image file".to_string()),
+            description: Some("Search query.".to_string()),
         },
     )]);
 
     ToolSpec::Function(ResponsesApiTool {
-        name: VIEW_IMAGE_TOOL_NAME.to_string(),
-        description: "View a local image from the filesystem (only use if given a full filepath by the user, and the image isn't already attached to the thread context within <image ...> tags)."
+        name: "web_search".to_string(),
+        description: "Search the web and return a list of matching pages with titles, URLs and snippets. Use it for up-to-date information or documentation that is not in the workspace."
             .to_string(),
         strict: false,
         parameters: JsonSchema::Object {
             properties,
-            required: Some(vec!["path".to_string()]),
+            required: Some(vec!["query".to_string()]),
             additional_properties: Some(false.into()),
         },
     })
@@ -1446,6 +1591,8 @@ pub(crate) fn build_specs(
     use crate::tools::handlers::TestSyncHandler;
     use crate::tools::handlers::UnifiedExecHandler;
     use crate::tools::handlers::ViewImageHandler;
+    use crate::tools::handlers::WebFetchHandler;
+    use crate::tools::handlers::WebSearchHandler;
     use std::sync::Arc;
 
     let mut builder = ToolRegistryBuilder::new();
@@ -1467,7 +1614,7 @@ pub(crate) fn build_specs(
     match &config.shell_type {
         ConfigShellToolType::Default => {
             builder.push_spec_with_parallel_support(
-                create_shell_tool(config.request_rule_enabled),
+                create_shell_tool(config.request_rule_enabled, &config.sandbox_profiles),
                 true,
             );
         }
@@ -1476,7 +1623,7 @@ pub(crate) fn build_specs(
         }
         ConfigShellToolType::UnifiedExec => {
             builder.push_spec_with_parallel_support(
-                create_exec_command_tool(config.request_rule_enabled),
+                create_exec_command_tool(config.request_rule_enabled, &config.sandbox_profiles),
                 true,
             );
             builder.push_spec(create_write_stdin_tool());
@@ -1488,7 +1635,7 @@ pub(crate) fn build_specs(
         }
         ConfigShellToolType::ShellCommand => {
             builder.push_spec_with_parallel_support(
-                create_shell_command_tool(config.request_rule_enabled),
+                create_shell_command_tool(config.request_rule_enabled, &config.sandbox_profiles),
                 true,
             );
         }
@@ -1569,6 +1716,10 @@ pub(crate) fn build_specs(
     }
 
     match config.web_search_mode {
+        Some(WebSearchMode::Cached | WebSearchMode::Live) if config.local_web_search => {
+            builder.push_spec_with_parallel_support(create_local_web_search_tool(), true);
+            builder.register_handler("web_search", Arc::new(WebSearchHandler));
+        }
         Some(WebSearchMode::Cached) => {
             builder.push_spec(ToolSpec::WebSearch {
                 external_web_access: Some(false),
@@ -1585,6 +1736,11 @@ pub(crate) fn build_specs(
     builder.push_spec_with_parallel_support(create_view_image_tool(), true);
     builder.register_handler("view_image", view_image_handler);
 
+    if config.web_fetch {
+        builder.push_spec_with_parallel_support(create_web_fetch_tool(), true);
+        builder.register_handler("web_fetch", Arc::new(WebFetchHandler));
+    }
+
     if config.collab_tools {
         let collab_handler = Arc::new(CollabHandler);
         builder.push_spec(create_spawn_agent_tool());
@@ -1814,7 +1970,7 @@ mod tests {
         // Build expected from the same helpers used by the builder.
         let mut expected: BTreeMap<String, ToolSpec> = BTreeMap::from([]);
         for spec in [
-            create_exec_command_tool(true),
+            create_exec_command_tool(true, &[]),
             create_write_stdin_tool(),
             PLAN_TOOL.clone(),
             create_request_user_input_tool(),
@@ -1862,6 +2018,66 @@ mod tests {
         );
     }
 
+    #[test]
+    fn local_web_search_replaces_hosted_tool() {
+        let config = test_config();
+        let model_info = ModelsManager::construct_model_info_offline("gpt-5-codex", &config);
+        let features = Features::with_defaults();
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_info: &model_info,
+            features: &features,
+            web_search_mode: Some(WebSearchMode::Live),
+            disallowed_tools: &config.disallowed_tools,
+        })
+        .with_local_web_search(true);
+        let (tools, _) = build_specs(&tools_config, None, &[]).build();
+        let web_search = find_tool(&tools, "web_search");
+        assert!(matches!(web_search.spec, ToolSpec::Function(_)));
+
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_info: &model_info,
+            features: &features,
+            web_search_mode: Some(WebSearchMode::Disabled),
+            disallowed_tools: &config.disallowed_tools,
+        })
+        .with_local_web_search(true);
+        let (tools, _) = build_specs(&tools_config, None, &[]).build();
+        assert!(
+            !tools
+                .iter()
+                .any(|tool| tool_name(&tool.spec) == "web_search")
+        );
+    }
+
+    #[test]
+    fn web_fetch_tool_requires_feature() {
+        let config = test_config();
+        let model_info = ModelsManager::construct_model_info_offline("gpt-5-codex", &config);
+        let mut features = Features::with_defaults();
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_info: &model_info,
+            features: &features,
+            web_search_mode: Some(WebSearchMode::Cached),
+            disallowed_tools: &config.disallowed_tools,
+        });
+        let (tools, _) = build_specs(&tools_config, None, &[]).build();
+        assert!(
+            !tools
+                .iter()
+                .any(|tool| tool_name(&tool.spec) == "web_fetch")
+        );
+
+        features.enable(Feature::WebFetch);
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_info: &model_info,
+            features: &features,
+            web_search_mode: Some(WebSearchMode::Cached),
+            disallowed_tools: &config.disallowed_tools,
+        });
+        let (tools, _) = build_specs(&tools_config, None, &[]).build();
+        assert_contains_tool_names(&tools, &["web_fetch"]);
+    }
+
     #[test]
     fn request_user_input_available_without_collaboration_modes_feature() {
         let config = test_config();
@@ -2685,7 +2901,7 @@ mod tests {
 
     #[test]
     fn test_shell_tool() {
-        let tool = super::create_shell_tool(true);
+        let tool = super::create_shell_tool(true, &[]);
         let ToolSpec::Function(ResponsesApiTool {
             description, name, ..
         }) = &tool
@@ -2713,9 +2929,52 @@ Examples of valid command strings:
         assert_eq!(description, &expected);
     }
 
+    #[test]
+    fn exec_command_tool_lists_sandbox_profiles() {
+        let ToolSpec::Function(ResponsesApiTool {
+            parameters: JsonSchema::Object { properties, .. },
+            ..
+        }) = super::create_exec_command_tool(false, &["build".to_string()])
+        else {
+            panic!("expected function tool");
+        };
+        assert!(properties.contains_key("sandbox_profile"));
+    }
+
+    #[test]
+    fn shell_command_tool_lists_sandbox_profiles() {
+        let tool = super::create_shell_command_tool(false, &["build".to_string()]);
+        let ToolSpec::Function(ResponsesApiTool {
+            parameters: JsonSchema::Object { properties, .. },
+            ..
+        }) = &tool
+        else {
+            panic!("expected function tool");
+        };
+
+        assert_eq!(
+            properties.get("sandbox_profile"),
+            Some(&JsonSchema::String {
+                description: Some(
+                    "Run the command under a named sandbox profile instead of the current sandbox, e.g. to write to build caches. One of \"build\". Profiles that grant more than the current sandbox require user approval."
+                        .to_string()
+                ),
+            })
+        );
+
+        let ToolSpec::Function(ResponsesApiTool {
+            parameters: JsonSchema::Object { properties, .. },
+            ..
+        }) = super::create_shell_command_tool(false, &[])
+        else {
+            panic!("expected function tool");
+        };
+        assert!(!properties.contains_key("sandbox_profile"));
+    }
+
     #[test]
     fn test_shell_command_tool() {
-        let tool = super::create_shell_command_tool(true);
+        let tool = super::create_shell_command_tool(true, &[]);
         let ToolSpec::Function(ResponsesApiTool {
             description, name, ..
         }) = &tool
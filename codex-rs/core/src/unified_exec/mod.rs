@@ -33,6 +33,7 @@ use tokio::sync::Mutex;
 
 use crate::codex::Session;
 use crate::codex::TurnContext;
+use crate::config::types::SandboxProfile;
 use crate::sandboxing::SandboxPermissions;
 use codex_protocol::protocol::TerminalSize;
 
@@ -85,6 +86,9 @@ pub(crate) struct ExecCommandRequest {
     pub sandbox_permissions: SandboxPermissions,
     pub justification: Option<String>,
     pub prefix_rule: Option<Vec<String>>,
+    /// Sandbox profile the call asked to run under, replacing the turn's
+    /// sandbox policy.
+    pub sandbox_profile: Option<(String, SandboxProfile)>,
 }
 
 #[derive(Debug)]
@@ -213,6 +217,7 @@ mod tests {
                     sandbox_permissions: SandboxPermissions::UseDefault,
                     justification: None,
                     prefix_rule: None,
+                    sandbox_profile: None,
                 },
                 &context,
             )
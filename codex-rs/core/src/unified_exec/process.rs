@@ -13,6 +13,7 @@ use crate::exec::ExecToolCallOutput;
 use crate::exec::SandboxType;
 use crate::exec::StreamOutput;
 use crate::exec::is_likely_sandbox_denied;
+use crate::resource_limits::ResourceLimitKind;
 use crate::truncate::TruncationPolicy;
 use crate::truncate::formatted_truncate_text;
 use anyhow::Result;
@@ -32,7 +33,7 @@ pub(crate) struct OutputHandles {
 
 #[derive(Debug)]
 pub(crate) struct UnifiedExecProcess {
-    process_handle: ExecCommandSession,
+    process_handle: Arc<ExecCommandSession>,
     output_buffer: OutputBuffer,
     output_notify: Arc<Notify>,
     cancellation_token: CancellationToken,
@@ -46,7 +47,9 @@ impl UnifiedExecProcess {
         process_handle: ExecCommandSession,
         initial_output_rx: tokio::sync::broadcast::Receiver<Vec<u8>>,
         sandbox_type: SandboxType,
+        max_output_bytes: Option<u64>,
     ) -> Self {
+        let process_handle = Arc::new(process_handle);
         let output_buffer = Arc::new(Mutex::new(HeadTailBuffer::default()));
         let output_notify = Arc::new(Notify::new());
         let cancellation_token = CancellationToken::new();
@@ -54,14 +57,30 @@ impl UnifiedExecProcess {
         let mut receiver = initial_output_rx;
         let buffer_clone = Arc::clone(&output_buffer);
         let notify_clone = Arc::clone(&output_notify);
+        let process_clone = Arc::clone(&process_handle);
         let output_task = tokio::spawn(async move {
+            let mut total_bytes: u64 = 0;
             loop {
                 match receiver.recv().await {
                     Ok(chunk) => {
+                        total_bytes = total_bytes.saturating_add(chunk.len() as u64);
+                        let exceeded = max_output_bytes.is_some_and(|max| total_bytes > max);
                         let mut guard = buffer_clone.lock().await;
                         guard.push_chunk(chunk);
+                        if exceeded {
+                            let kind = ResourceLimitKind::Output;
+                            guard.push_chunk(
+                                format!("\ncommand exceeded its {kind} limit\n").into_bytes(),
+                            );
+                        }
                         drop(guard);
                         notify_clone.notify_waiters();
+                        if exceeded {
+                            // Terminating closes the exit channel, which ends
+                            // the session like any other exit.
+                            process_clone.terminate();
+                            break;
+                        }
                     }
                     Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
                     Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
@@ -181,13 +200,14 @@ impl UnifiedExecProcess {
     pub(super) async fn from_spawned(
         spawned: SpawnedPty,
         sandbox_type: SandboxType,
+        max_output_bytes: Option<u64>,
     ) -> Result<Self, UnifiedExecError> {
         let SpawnedPty {
             session: process_handle,
             output_rx,
             mut exit_rx,
         } = spawned;
-        let managed = Self::new(process_handle, output_rx, sandbox_type);
+        let managed = Self::new(process_handle, output_rx, sandbox_type, max_output_bytes);
 
         let exit_ready = matches!(exit_rx.try_recv(), Ok(_) | Err(TryRecvError::Closed));
 
@@ -10,6 +10,7 @@ use tokio::time::Duration;
 use tokio::time::Instant;
 use tokio_util::sync::CancellationToken;
 
+use crate::exec::SandboxType;
 use crate::exec_env::create_env;
 use crate::exec_policy::ExecApprovalRequest;
 use crate::protocol::ExecCommandSource;
@@ -21,6 +22,8 @@ use crate::tools::orchestrator::ToolOrchestrator;
 use crate::tools::runtimes::unified_exec::UnifiedExecRequest as UnifiedExecToolRequest;
 use crate::tools::runtimes::unified_exec::UnifiedExecRuntime;
 use crate::tools::sandboxing::ToolCtx;
+use crate::tools::sandboxing::require_approval_for_sandbox_profile;
+use crate::tools::sandboxing::set_sandbox_profile_proxy_env;
 use crate::truncate::TruncationPolicy;
 use crate::truncate::approx_token_count;
 use crate::truncate::formatted_truncate_text;
@@ -46,6 +49,8 @@ use crate::unified_exec::process::OutputBuffer;
 use crate::unified_exec::process::OutputHandles;
 use crate::unified_exec::process::UnifiedExecProcess;
 use crate::unified_exec::resolve_max_tokens;
+use codex_otel::traces::spans::exec_span;
+use codex_otel::traces::spans::trace_context_env;
 use codex_protocol::protocol::TerminalSize;
 
 const UNIFIED_EXEC_ENV: [(&str, &str); 10] = [
@@ -489,30 +494,46 @@ impl UnifiedExecProcessManager {
             .command
             .split_first()
             .ok_or(UnifiedExecError::MissingCommandLine)?;
+        // The process outlives this call, so the span only covers the spawn;
+        // its trace context still lets the child join the trace.
+        let span = exec_span(program);
+        let mut child_env = env.env.clone();
+        child_env.extend(trace_context_env(&span));
+
+        // `codex-linux-sandbox` applies the limits itself, right before it
+        // execs the command.
+        let resource_limits = env.resource_limits;
+        let pre_exec = (env.sandbox != SandboxType::LinuxSeccomp && !resource_limits.is_empty())
+            .then(|| -> codex_utils_pty::PreExec {
+                Arc::new(move || crate::resource_limits::apply_rlimits(&resource_limits))
+            });
 
         let spawn_result = if tty {
-            codex_utils_pty::pty::spawn_process(
+            codex_utils_pty::pty::spawn_process_with_pre_exec(
                 program,
                 args,
                 env.cwd.as_path(),
-                &env.env,
+                &child_env,
                 &env.arg0,
                 terminal_size.map(|size| (size.rows, size.cols)),
+                pre_exec,
             )
             .await
         } else {
-            codex_utils_pty::pipe::spawn_process_no_stdin(
+            codex_utils_pty::pipe::spawn_process_no_stdin_with_pre_exec(
                 program,
                 args,
                 env.cwd.as_path(),
-                &env.env,
+                &child_env,
                 &env.arg0,
+                pre_exec,
             )
             .await
         };
         let spawned =
             spawn_result.map_err(|err| UnifiedExecError::create_process(err.to_string()))?;
-        UnifiedExecProcess::from_spawned(spawned, env.sandbox).await
+        UnifiedExecProcess::from_spawned(spawned, env.sandbox, resource_limits.max_output_bytes)
+            .await
     }
 
     pub(super) async fn open_session_with_sandbox(
@@ -521,24 +542,39 @@ impl UnifiedExecProcessManager {
         cwd: PathBuf,
         context: &UnifiedExecContext,
     ) -> Result<UnifiedExecProcess, UnifiedExecError> {
-        let env = apply_unified_exec_env(create_env(
+        let mut env = apply_unified_exec_env(create_env(
             &context.turn.shell_environment_policy,
             Some(context.session.conversation_id),
         ));
+        let sandbox_profile = request.sandbox_profile.as_ref();
+        set_sandbox_profile_proxy_env(
+            &mut env,
+            context.turn.as_ref(),
+            sandbox_profile.map(|(_, profile)| profile),
+        );
         let mut orchestrator = ToolOrchestrator::new();
         let mut runtime = UnifiedExecRuntime::new(self);
-        let exec_approval_requirement = context
+        let mut exec_approval_requirement = context
             .session
             .services
             .exec_policy
             .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                 command: &request.command,
                 approval_policy: context.turn.approval_policy,
-                sandbox_policy: &context.turn.sandbox_policy,
+                sandbox_policy: sandbox_profile
+                    .map_or(&context.turn.sandbox_policy, |(_, profile)| &profile.policy),
                 sandbox_permissions: request.sandbox_permissions,
                 prefix_rule: request.prefix_rule.clone(),
             })
             .await;
+        if let Some((name, _)) = sandbox_profile.filter(|(_, profile)| {
+            profile
+                .policy
+                .grants_more_than(&context.turn.sandbox_policy, &context.turn.cwd)
+        }) {
+            exec_approval_requirement =
+                require_approval_for_sandbox_profile(exec_approval_requirement, name);
+        }
         let req = UnifiedExecToolRequest::new(
             request.command.clone(),
             cwd,
@@ -548,6 +584,7 @@ impl UnifiedExecProcessManager {
             request.sandbox_permissions,
             request.justification.clone(),
             exec_approval_requirement,
+            sandbox_profile.map(|(name, profile)| (name.clone(), profile.policy.clone())),
         );
         let tool_ctx = ToolCtx {
             session: context.session.as_ref(),
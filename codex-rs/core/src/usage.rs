@@ -0,0 +1,369 @@
+//! Usage reports over the per-turn token usage recorded in the state DB.
+//!
+//! Costs are estimates from the `pricing` tables of the configured model providers. This module
+//! stays UI-agnostic: the CLI and TUI render the plain strings it returns.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+
+use anyhow::Context;
+use chrono::DateTime;
+use chrono::Local;
+use chrono::NaiveDate;
+use chrono::TimeZone;
+use chrono::Utc;
+use codex_protocol::protocol::TokenUsage;
+pub use codex_state::UsageGroupBy;
+use codex_state::UsageQuery;
+use codex_state::UsageRow;
+use serde::Serialize;
+
+use crate::ModelProviderInfo;
+use crate::config::Config;
+use crate::state_db;
+
+/// Token totals and estimated cost for one group of turns.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct UsageTotals {
+    pub group: String,
+    pub turns: i64,
+    pub input_tokens: i64,
+    pub cached_input_tokens: i64,
+    pub output_tokens: i64,
+    pub reasoning_output_tokens: i64,
+    pub total_tokens: i64,
+    /// Estimated cost in US dollars, or `None` when no model in the group has a price.
+    pub cost_usd: Option<f64>,
+    /// Tokens of models without a configured price, which `cost_usd` leaves out.
+    pub unpriced_tokens: i64,
+}
+
+impl UsageTotals {
+    fn add(&mut self, row: &UsageRow, pricing: Option<f64>) {
+        self.turns += row.turns;
+        self.input_tokens += row.input_tokens;
+        self.cached_input_tokens += row.cached_input_tokens;
+        self.output_tokens += row.output_tokens;
+        self.reasoning_output_tokens += row.reasoning_output_tokens;
+        self.total_tokens += row.total_tokens;
+        match pricing {
+            Some(cost) => self.cost_usd = Some(self.cost_usd.unwrap_or(0.0) + cost),
+            None => self.unpriced_tokens += row.total_tokens,
+        }
+    }
+
+    fn add_totals(&mut self, other: &UsageTotals) {
+        self.turns += other.turns;
+        self.input_tokens += other.input_tokens;
+        self.cached_input_tokens += other.cached_input_tokens;
+        self.output_tokens += other.output_tokens;
+        self.reasoning_output_tokens += other.reasoning_output_tokens;
+        self.total_tokens += other.total_tokens;
+        if let Some(cost) = other.cost_usd {
+            self.cost_usd = Some(self.cost_usd.unwrap_or(0.0) + cost);
+        }
+        self.unpriced_tokens += other.unpriced_tokens;
+    }
+}
+
+/// Usage grouped by one dimension, with a grand total.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct UsageReport {
+    pub group_by: UsageGroupBy,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+    pub rows: Vec<UsageTotals>,
+    pub total: UsageTotals,
+}
+
+/// Loads recorded usage from the state DB and prices it with the configured providers.
+pub async fn load_usage_report(
+    config: &Config,
+    group_by: UsageGroupBy,
+    since: Option<DateTime<Utc>>,
+    until: Option<DateTime<Utc>>,
+) -> anyhow::Result<UsageReport> {
+    let Some(state_db) = state_db::get_state_db(config, None).await else {
+        anyhow::bail!(
+            "usage history is not available: it is recorded in the state database, which needs the `sqlite` feature"
+        );
+    };
+    let rows = state_db
+        .query_usage(&UsageQuery {
+            group_by,
+            since,
+            until,
+        })
+        .await
+        .context("failed to read usage history")?;
+    Ok(UsageReport::from_rows(
+        group_by,
+        since,
+        until,
+        &rows,
+        &config.model_providers,
+    ))
+}
+
+impl UsageReport {
+    pub fn from_rows(
+        group_by: UsageGroupBy,
+        since: Option<DateTime<Utc>>,
+        until: Option<DateTime<Utc>>,
+        rows: &[UsageRow],
+        providers: &HashMap<String, ModelProviderInfo>,
+    ) -> Self {
+        let mut groups: BTreeMap<&str, UsageTotals> = BTreeMap::new();
+        for row in rows {
+            let cost = providers
+                .get(&row.model_provider)
+                .and_then(|provider| provider.model_pricing(&row.model))
+                .map(|pricing| pricing.cost(&token_usage(row)));
+            groups
+                .entry(row.group.as_str())
+                .or_insert_with(|| UsageTotals {
+                    group: row.group.clone(),
+                    ..Default::default()
+                })
+                .add(row, cost);
+        }
+        let rows: Vec<UsageTotals> = groups.into_values().collect();
+        let mut total = UsageTotals {
+            group: "total".to_string(),
+            ..Default::default()
+        };
+        for row in &rows {
+            total.add_totals(row);
+        }
+        Self {
+            group_by,
+            since,
+            until,
+            rows,
+            total,
+        }
+    }
+
+    /// Aligned plain-text table with one line per group and a total line.
+    pub fn table_lines(&self) -> Vec<String> {
+        let header = [
+            self.group_by.as_str().to_uppercase(),
+            "TURNS".to_string(),
+            "INPUT".to_string(),
+            "CACHED".to_string(),
+            "OUTPUT".to_string(),
+            "TOTAL".to_string(),
+            "EST. COST".to_string(),
+        ];
+        let cells: Vec<[String; 7]> = self
+            .rows
+            .iter()
+            .chain(std::iter::once(&self.total))
+            .map(|row| {
+                [
+                    row.group.clone(),
+                    row.turns.to_string(),
+                    format_tokens(row.input_tokens),
+                    format_tokens(row.cached_input_tokens),
+                    format_tokens(row.output_tokens),
+                    format_tokens(row.total_tokens),
+                    format_cost(row),
+                ]
+            })
+            .collect();
+        let mut widths = header.each_ref().map(String::len);
+        for row in &cells {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+        let format_row = |row: &[String; 7]| {
+            let mut line = format!("{:<width$}", row[0], width = widths[0]);
+            for (cell, width) in row.iter().zip(widths).skip(1) {
+                line.push_str(&format!("  {cell:>width$}"));
+            }
+            line
+        };
+
+        let mut lines = vec![format_row(&header)];
+        let (total, groups) = cells.split_last().unwrap_or((&header, &[]));
+        lines.extend(groups.iter().map(format_row));
+        lines.push(format_row(total));
+        if self.total.unpriced_tokens > 0 {
+            lines.push(String::new());
+            lines.push(format!(
+                "* excludes {} tokens of models without a price in `model_providers.<id>.pricing`",
+                format_tokens(self.total.unpriced_tokens)
+            ));
+        }
+        lines
+    }
+
+    /// One CSV line per group, without the total.
+    pub fn to_csv(&self) -> String {
+        let mut csv = format!(
+            "{},turns,input_tokens,cached_input_tokens,output_tokens,reasoning_output_tokens,total_tokens,cost_usd,unpriced_tokens\n",
+            self.group_by.as_str()
+        );
+        for row in &self.rows {
+            csv.push_str(&format!(
+                "{},{},{},{},{},{},{},{},{}\n",
+                csv_field(&row.group),
+                row.turns,
+                row.input_tokens,
+                row.cached_input_tokens,
+                row.output_tokens,
+                row.reasoning_output_tokens,
+                row.total_tokens,
+                row.cost_usd
+                    .map(|cost| format!("{cost:.4}"))
+                    .unwrap_or_default(),
+                row.unpriced_tokens,
+            ));
+        }
+        csv
+    }
+
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+/// Parses `YYYY-MM-DD` or `YYYY-MM` as the start of that local day or month.
+pub fn parse_usage_date(value: &str) -> anyhow::Result<DateTime<Utc>> {
+    let date = NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .or_else(|_| NaiveDate::parse_from_str(&format!("{value}-01"), "%Y-%m-%d"))
+        .with_context(|| format!("invalid date `{value}`: expected YYYY-MM-DD or YYYY-MM"))?;
+    let midnight = date.and_hms_opt(0, 0, 0).unwrap_or_default();
+    Local
+        .from_local_datetime(&midnight)
+        .earliest()
+        .map(|local| local.with_timezone(&Utc))
+        .with_context(|| format!("`{value}` does not exist in the local time zone"))
+}
+
+fn token_usage(row: &UsageRow) -> TokenUsage {
+    TokenUsage {
+        input_tokens: row.input_tokens,
+        cached_input_tokens: row.cached_input_tokens,
+        output_tokens: row.output_tokens,
+        reasoning_output_tokens: row.reasoning_output_tokens,
+        total_tokens: row.total_tokens,
+    }
+}
+
+fn format_tokens(tokens: i64) -> String {
+    let digits = tokens.unsigned_abs().to_string();
+    let mut out = String::new();
+    for (idx, ch) in digits.chars().enumerate() {
+        if idx > 0 && (digits.len() - idx) % 3 == 0 {
+            out.push(',');
+        }
+        out.push(ch);
+    }
+    if tokens < 0 { format!("-{out}") } else { out }
+}
+
+fn format_cost(totals: &UsageTotals) -> String {
+    match totals.cost_usd {
+        Some(cost) if totals.unpriced_tokens > 0 => format!("${cost:.2}*"),
+        Some(cost) => format!("${cost:.2}"),
+        None => "-".to_string(),
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::built_in_model_providers;
+    use crate::model_provider_info::ModelPricing;
+    use pretty_assertions::assert_eq;
+
+    fn row(group: &str, model: &str, input_tokens: i64, output_tokens: i64) -> UsageRow {
+        UsageRow {
+            group: group.to_string(),
+            model_provider: "openai".to_string(),
+            model: model.to_string(),
+            turns: 1,
+            input_tokens,
+            cached_input_tokens: 0,
+            output_tokens,
+            reasoning_output_tokens: 0,
+            total_tokens: input_tokens + output_tokens,
+        }
+    }
+
+    fn report() -> UsageReport {
+        let mut providers = built_in_model_providers();
+        if let Some(openai) = providers.get_mut("openai") {
+            openai.pricing = Some(HashMap::from([(
+                "gpt-5".to_string(),
+                ModelPricing {
+                    input: 1.0,
+                    cached_input: None,
+                    output: 10.0,
+                },
+            )]));
+        }
+        UsageReport::from_rows(
+            UsageGroupBy::Repo,
+            None,
+            None,
+            &[
+                row("https://example.com/a,b.git", "gpt-5", 1_000_000, 100_000),
+                row("https://example.com/a,b.git", "local-model", 500, 50),
+                row("https://example.com/c.git", "gpt-5", 2_000_000, 0),
+            ],
+            &providers,
+        )
+    }
+
+    #[test]
+    fn report_prices_known_models_and_counts_the_rest() {
+        let report = report();
+        assert_eq!(report.rows.len(), 2);
+        assert_eq!(report.rows[0].turns, 2);
+        assert_eq!(report.rows[0].cost_usd, Some(2.0));
+        assert_eq!(report.rows[0].unpriced_tokens, 550);
+        assert_eq!(report.total.total_tokens, 3_100_550);
+        assert_eq!(report.total.cost_usd, Some(4.0));
+    }
+
+    #[test]
+    fn report_renders_table_and_csv() {
+        let report = report();
+        assert_eq!(
+            report.table_lines(),
+            vec![
+                "REPO                         TURNS      INPUT  CACHED   OUTPUT      TOTAL  EST. COST",
+                "https://example.com/a,b.git      2  1,000,500       0  100,050  1,100,550     $2.00*",
+                "https://example.com/c.git        1  2,000,000       0        0  2,000,000      $2.00",
+                "total                            3  3,000,500       0  100,050  3,100,550     $4.00*",
+                "",
+                "* excludes 550 tokens of models without a price in `model_providers.<id>.pricing`",
+            ]
+        );
+        assert_eq!(
+            report.to_csv(),
+            "repo,turns,input_tokens,cached_input_tokens,output_tokens,reasoning_output_tokens,total_tokens,cost_usd,unpriced_tokens\n\
+             \"https://example.com/a,b.git\",2,1000500,0,100050,0,1100550,2.0000,550\n\
+             https://example.com/c.git,1,2000000,0,0,0,2000000,2.0000,0\n"
+        );
+    }
+
+    #[test]
+    fn parse_usage_date_accepts_days_and_months() {
+        let day = parse_usage_date("2026-10-01").expect("day");
+        let month = parse_usage_date("2026-10").expect("month");
+        assert_eq!(day, month);
+        assert!(parse_usage_date("October").is_err());
+    }
+}
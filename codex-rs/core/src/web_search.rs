@@ -1,3 +1,8 @@
+mod backend;
+
+pub(crate) use backend::format_web_search_results;
+pub(crate) use backend::web_search_backend;
+
 use codex_protocol::models::WebSearchAction;
 
 fn search_action_detail(query: &Option<String>, queries: &Option<Vec<String>>) -> String {
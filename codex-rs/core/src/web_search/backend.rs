@@ -0,0 +1,360 @@
+//! Client-side search backends for the `web_search` tool, used when the model
+//! provider has no hosted web search.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use anyhow::Context;
+use anyhow::Result;
+use async_trait::async_trait;
+use reqwest::header::HeaderMap;
+use reqwest::header::HeaderName;
+use reqwest::header::HeaderValue;
+use serde_json::Value;
+use url::Url;
+use url::form_urlencoded;
+
+use crate::config::types::WebSearchBackendToml;
+use crate::default_client::get_codex_user_agent;
+
+const DEFAULT_MAX_RESULTS: usize = 8;
+const SEARCH_TIMEOUT: Duration = Duration::from_secs(20);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct WebSearchResult {
+    pub title: String,
+    pub url: String,
+    pub snippet: String,
+}
+
+#[async_trait]
+pub(crate) trait WebSearchBackend: Send + Sync {
+    async fn search(&self, query: &str) -> Result<Vec<WebSearchResult>>;
+}
+
+pub(crate) fn web_search_backend(config: &WebSearchBackendToml) -> Box<dyn WebSearchBackend> {
+    match config {
+        WebSearchBackendToml::Searxng { url, max_results } => Box::new(SearxngBackend {
+            url: url.clone(),
+            max_results: max_results.unwrap_or(DEFAULT_MAX_RESULTS),
+        }),
+        WebSearchBackendToml::Http {
+            url,
+            max_results,
+            http_headers,
+            env_http_headers,
+            results_pointer,
+            title_field,
+            url_field,
+            snippet_field,
+        } => Box::new(HttpTemplateBackend {
+            url: url.clone(),
+            max_results: max_results.unwrap_or(DEFAULT_MAX_RESULTS),
+            headers: build_header_map(http_headers.as_ref(), env_http_headers.as_ref()),
+            results_pointer: results_pointer
+                .clone()
+                .unwrap_or_else(|| "/results".to_string()),
+            title_field: title_field.clone().unwrap_or_else(|| "title".to_string()),
+            url_field: url_field.clone().unwrap_or_else(|| "url".to_string()),
+            snippet_field: snippet_field
+                .clone()
+                .unwrap_or_else(|| "snippet".to_string()),
+        }),
+    }
+}
+
+/// Queries `GET {url}/search?q=...&format=json` on a SearxNG instance.
+struct SearxngBackend {
+    url: String,
+    max_results: usize,
+}
+
+#[async_trait]
+impl WebSearchBackend for SearxngBackend {
+    async fn search(&self, query: &str) -> Result<Vec<WebSearchResult>> {
+        let base = self.url.trim_end_matches('/');
+        let base = base.strip_suffix("/search").unwrap_or(base);
+        let mut url = Url::parse(&format!("{base}/search"))
+            .with_context(|| format!("Invalid SearxNG url {}", self.url))?;
+        url.query_pairs_mut()
+            .append_pair("q", query)
+            .append_pair("format", "json");
+        let body = get_json(url, HeaderMap::new()).await?;
+        Ok(extract_results(
+            &body,
+            "/results",
+            "title",
+            "url",
+            "content",
+            self.max_results,
+        ))
+    }
+}
+
+/// Fills `{query}` and `{max_results}` into a URL template and maps fields of
+/// the JSON response to results.
+struct HttpTemplateBackend {
+    url: String,
+    max_results: usize,
+    headers: HeaderMap,
+    results_pointer: String,
+    title_field: String,
+    url_field: String,
+    snippet_field: String,
+}
+
+#[async_trait]
+impl WebSearchBackend for HttpTemplateBackend {
+    async fn search(&self, query: &str) -> Result<Vec<WebSearchResult>> {
+        let encoded: String = form_urlencoded::byte_serialize(query.as_bytes()).collect();
+        let url = self
+            .url
+            .replace("{query}", &encoded)
+            .replace("{max_results}", &self.max_results.to_string());
+        let url = Url::parse(&url).with_context(|| format!("Invalid search url {url}"))?;
+        let body = get_json(url, self.headers.clone()).await?;
+        Ok(extract_results(
+            &body,
+            &self.results_pointer,
+            &self.title_field,
+            &self.url_field,
+            &self.snippet_field,
+            self.max_results,
+        ))
+    }
+}
+
+async fn get_json(url: Url, headers: HeaderMap) -> Result<Value> {
+    let client = reqwest::Client::builder()
+        .user_agent(get_codex_user_agent())
+        .timeout(SEARCH_TIMEOUT)
+        .build()
+        .context("Failed to build http client")?;
+    let response = client
+        .get(url.clone())
+        .headers(headers)
+        .header(reqwest::header::ACCEPT, "application/json")
+        .send()
+        .await
+        .with_context(|| format!("Failed to send request to {url}"))?;
+
+    let status = response.status();
+    let body = response.text().await.unwrap_or_default();
+    if !status.is_success() {
+        anyhow::bail!("Search request failed with status {status} from {url}: {body}");
+    }
+    serde_json::from_str(&body).with_context(|| format!("Failed to parse response from {url}"))
+}
+
+fn extract_results(
+    body: &Value,
+    results_pointer: &str,
+    title_field: &str,
+    url_field: &str,
+    snippet_field: &str,
+    max_results: usize,
+) -> Vec<WebSearchResult> {
+    let field = |item: &Value, name: &str| {
+        item.get(name)
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .unwrap_or_default()
+            .to_string()
+    };
+    body.pointer(results_pointer)
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .map(|item| WebSearchResult {
+            title: field(item, title_field),
+            url: field(item, url_field),
+            snippet: field(item, snippet_field),
+        })
+        .filter(|result| !result.url.is_empty())
+        .take(max_results)
+        .collect()
+}
+
+fn build_header_map(
+    http_headers: Option<&HashMap<String, String>>,
+    env_http_headers: Option<&HashMap<String, String>>,
+) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    for (k, v) in http_headers.into_iter().flatten() {
+        if let (Ok(name), Ok(value)) = (HeaderName::try_from(k), HeaderValue::try_from(v)) {
+            headers.insert(name, value);
+        }
+    }
+    for (header, env_var) in env_http_headers.into_iter().flatten() {
+        if let Ok(val) = std::env::var(env_var)
+            && !val.trim().is_empty()
+            && let (Ok(name), Ok(value)) =
+                (HeaderName::try_from(header), HeaderValue::try_from(val))
+        {
+            headers.insert(name, value);
+        }
+    }
+    headers
+}
+
+/// Render results as the `web_search` tool output.
+pub(crate) fn format_web_search_results(query: &str, results: &[WebSearchResult]) -> String {
+    if results.is_empty() {
+        return format!("No results for \"{query}\".");
+    }
+    let mut out = format!("Results for \"{query}\":\n");
+    for (index, result) in results.iter().enumerate() {
+        let title = if result.title.is_empty() {
+            result.url.as_str()
+        } else {
+            result.title.as_str()
+        };
+        out.push_str(&format!("\n{}. [{title}]({})\n", index + 1, result.url));
+        if !result.snippet.is_empty() {
+            out.push_str(&format!("   {}\n", result.snippet));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use wiremock::Mock;
+    use wiremock::MockServer;
+    use wiremock::ResponseTemplate;
+    use wiremock::matchers::header;
+    use wiremock::matchers::method;
+    use wiremock::matchers::path;
+    use wiremock::matchers::query_param;
+
+    #[tokio::test]
+    async fn searxng_backend_queries_json_format() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/search"))
+            .and(query_param("q", "rust async"))
+            .and(query_param("format", "json"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "results": [
+                    {"title": "Async book", "url": "https://rust-lang.github.io/async-book/", "content": "Asynchronous Programming in Rust"},
+                    {"title": "No url"},
+                    {"title": "Tokio", "url": "https://tokio.rs", "content": "A runtime"},
+                    {"title": "Third", "url": "https://example.com", "content": ""}
+                ]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let backend = web_search_backend(&WebSearchBackendToml::Searxng {
+            url: format!("{}/", server.uri()),
+            max_results: Some(2),
+        });
+        let results = backend.search("rust async").await.expect("search succeeds");
+        assert_eq!(
+            results,
+            vec![
+                WebSearchResult {
+                    title: "Async book".to_string(),
+                    url: "https://rust-lang.github.io/async-book/".to_string(),
+                    snippet: "Asynchronous Programming in Rust".to_string(),
+                },
+                WebSearchResult {
+                    title: "Tokio".to_string(),
+                    url: "https://tokio.rs".to_string(),
+                    snippet: "A runtime".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn http_backend_fills_template_and_maps_fields() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/search"))
+            .and(query_param("term", "a&b c"))
+            .and(query_param("limit", "3"))
+            .and(header("x-api-key", "secret"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "data": {"hits": [
+                    {"name": "Hit", "link": "https://example.com/hit", "summary": "  text  "}
+                ]}
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let backend = web_search_backend(&WebSearchBackendToml::Http {
+            url: format!(
+                "{}/api/search?term={{query}}&limit={{max_results}}",
+                server.uri()
+            ),
+            max_results: Some(3),
+            http_headers: Some(HashMap::from([(
+                "x-api-key".to_string(),
+                "secret".to_string(),
+            )])),
+            env_http_headers: None,
+            results_pointer: Some("/data/hits".to_string()),
+            title_field: Some("name".to_string()),
+            url_field: Some("link".to_string()),
+            snippet_field: Some("summary".to_string()),
+        });
+        let results = backend.search("a&b c").await.expect("search succeeds");
+        assert_eq!(
+            results,
+            vec![WebSearchResult {
+                title: "Hit".to_string(),
+                url: "https://example.com/hit".to_string(),
+                snippet: "text".to_string(),
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn backend_errors_include_status() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(403).set_body_string("json format disabled"))
+            .mount(&server)
+            .await;
+
+        let backend = web_search_backend(&WebSearchBackendToml::Searxng {
+            url: server.uri(),
+            max_results: None,
+        });
+        let err = backend.search("x").await.expect_err("403 fails");
+        assert!(
+            err.to_string().contains("403") && err.to_string().contains("json format disabled"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn formats_numbered_markdown_results() {
+        let results = vec![
+            WebSearchResult {
+                title: "Tokio".to_string(),
+                url: "https://tokio.rs".to_string(),
+                snippet: "A runtime".to_string(),
+            },
+            WebSearchResult {
+                title: String::new(),
+                url: "https://example.com".to_string(),
+                snippet: String::new(),
+            },
+        ];
+        assert_eq!(
+            format_web_search_results("tokio", &results),
+            "Results for \"tokio\":\n\n1. [Tokio](https://tokio.rs)\n   A runtime\n\n2. [https://example.com](https://example.com)\n"
+        );
+        assert_eq!(
+            format_web_search_results("nothing", &[]),
+            "No results for \"nothing\"."
+        );
+    }
+}
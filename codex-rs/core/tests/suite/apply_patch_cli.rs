@@ -581,8 +581,10 @@ async fn apply_patch_cli_rejects_path_traversal_outside_workspace(
     let sandbox_policy = SandboxPolicy::WorkspaceWrite {
         writable_roots: vec![],
         network_access: false,
+        allowed_loopback_ports: Vec::new(),
         exclude_tmpdir_env_var: true,
         exclude_slash_tmp: true,
+        deny_read: Vec::new(),
     };
     harness
         .submit_with_policy(
@@ -637,8 +639,10 @@ async fn apply_patch_cli_rejects_move_path_traversal_outside_workspace(
     let sandbox_policy = SandboxPolicy::WorkspaceWrite {
         writable_roots: vec![],
         network_access: false,
+        allowed_loopback_ports: Vec::new(),
         exclude_tmpdir_env_var: true,
         exclude_slash_tmp: true,
+        deny_read: Vec::new(),
     };
     harness
         .submit_with_policy("attempt move traversal via apply_patch", sandbox_policy)
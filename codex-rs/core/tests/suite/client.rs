@@ -1240,6 +1240,7 @@ async fn azure_responses_request_includes_store_and_reasoning_ids() {
         stream_idle_timeout_ms: Some(5_000),
         requires_openai_auth: false,
         supports_websockets: false,
+        pricing: None,
     };
 
     let codex_home = TempDir::new().unwrap();
@@ -1767,6 +1768,7 @@ async fn azure_overrides_assign_properties_used_for_responses_url() {
         stream_idle_timeout_ms: None,
         requires_openai_auth: false,
         supports_websockets: false,
+        pricing: None,
     };
 
     // Init session
@@ -1851,6 +1853,7 @@ async fn env_var_overrides_loaded_auth() {
         stream_idle_timeout_ms: None,
         requires_openai_auth: false,
         supports_websockets: false,
+        pricing: None,
     };
 
     // Init session
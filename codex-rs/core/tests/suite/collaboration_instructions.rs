@@ -111,6 +111,7 @@ async fn user_input_includes_collaboration_instructions_after_override() -> Resu
             cwd: None,
             approval_policy: None,
             sandbox_policy: None,
+            sandbox_profile: None,
             windows_sandbox_level: None,
             model: None,
             subagent_model: None,
@@ -201,6 +202,7 @@ async fn override_then_next_turn_uses_updated_collaboration_instructions() -> Re
             cwd: None,
             approval_policy: None,
             sandbox_policy: None,
+            sandbox_profile: None,
             windows_sandbox_level: None,
             model: None,
             subagent_model: None,
@@ -253,6 +255,7 @@ async fn user_turn_overrides_collaboration_instructions_after_override() -> Resu
             cwd: None,
             approval_policy: None,
             sandbox_policy: None,
+            sandbox_profile: None,
             windows_sandbox_level: None,
             model: None,
             subagent_model: None,
@@ -316,6 +319,7 @@ async fn collaboration_mode_update_emits_new_instruction_message() -> Result<()>
             cwd: None,
             approval_policy: None,
             sandbox_policy: None,
+            sandbox_profile: None,
             windows_sandbox_level: None,
             model: None,
             subagent_model: None,
@@ -347,6 +351,7 @@ async fn collaboration_mode_update_emits_new_instruction_message() -> Result<()>
             cwd: None,
             approval_policy: None,
             sandbox_policy: None,
+            sandbox_profile: None,
             windows_sandbox_level: None,
             model: None,
             subagent_model: None,
@@ -399,6 +404,7 @@ async fn collaboration_mode_update_noop_does_not_append() -> Result<()> {
             cwd: None,
             approval_policy: None,
             sandbox_policy: None,
+            sandbox_profile: None,
             windows_sandbox_level: None,
             model: None,
             subagent_model: None,
@@ -430,6 +436,7 @@ async fn collaboration_mode_update_noop_does_not_append() -> Result<()> {
             cwd: None,
             approval_policy: None,
             sandbox_policy: None,
+            sandbox_profile: None,
             windows_sandbox_level: None,
             model: None,
             subagent_model: None,
@@ -481,6 +488,7 @@ async fn collaboration_mode_update_emits_new_instruction_message_when_mode_chang
             cwd: None,
             approval_policy: None,
             sandbox_policy: None,
+            sandbox_profile: None,
             windows_sandbox_level: None,
             model: None,
             subagent_model: None,
@@ -515,6 +523,7 @@ async fn collaboration_mode_update_emits_new_instruction_message_when_mode_chang
             cwd: None,
             approval_policy: None,
             sandbox_policy: None,
+            sandbox_profile: None,
             windows_sandbox_level: None,
             model: None,
             subagent_model: None,
@@ -570,6 +579,7 @@ async fn collaboration_mode_update_noop_does_not_append_when_mode_is_unchanged()
             cwd: None,
             approval_policy: None,
             sandbox_policy: None,
+            sandbox_profile: None,
             windows_sandbox_level: None,
             model: None,
             subagent_model: None,
@@ -604,6 +614,7 @@ async fn collaboration_mode_update_noop_does_not_append_when_mode_is_unchanged()
             cwd: None,
             approval_policy: None,
             sandbox_policy: None,
+            sandbox_profile: None,
             windows_sandbox_level: None,
             model: None,
             subagent_model: None,
@@ -665,6 +676,7 @@ async fn resume_replays_collaboration_instructions() -> Result<()> {
             cwd: None,
             approval_policy: None,
             sandbox_policy: None,
+            sandbox_profile: None,
             windows_sandbox_level: None,
             model: None,
             subagent_model: None,
@@ -727,6 +739,7 @@ async fn empty_collaboration_instructions_are_ignored() -> Result<()> {
             cwd: None,
             approval_policy: None,
             sandbox_policy: None,
+            sandbox_profile: None,
             windows_sandbox_level: None,
             model: None,
             subagent_model: None,
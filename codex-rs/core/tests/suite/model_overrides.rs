@@ -29,6 +29,7 @@ async fn override_turn_context_does_not_persist_when_config_exists() {
             cwd: None,
             approval_policy: None,
             sandbox_policy: None,
+            sandbox_profile: None,
             windows_sandbox_level: None,
             model: Some("o3".to_string()),
             subagent_model: None,
@@ -71,6 +72,7 @@ async fn override_turn_context_does_not_create_config_file() {
             cwd: None,
             approval_policy: None,
             sandbox_policy: None,
+            sandbox_profile: None,
             windows_sandbox_level: None,
             model: Some("o3".to_string()),
             subagent_model: None,
@@ -117,6 +117,7 @@ async fn override_turn_context_without_user_turn_does_not_record_permissions_upd
             cwd: None,
             approval_policy: Some(AskForApproval::Never),
             sandbox_policy: None,
+            sandbox_profile: None,
             windows_sandbox_level: None,
             model: None,
             subagent_model: None,
@@ -163,6 +164,7 @@ async fn override_turn_context_without_user_turn_does_not_record_environment_upd
             cwd: Some(new_cwd.path().to_path_buf()),
             approval_policy: None,
             sandbox_policy: None,
+            sandbox_profile: None,
             windows_sandbox_level: None,
             model: None,
             subagent_model: None,
@@ -206,6 +208,7 @@ async fn override_turn_context_without_user_turn_does_not_record_collaboration_u
             cwd: None,
             approval_policy: None,
             sandbox_policy: None,
+            sandbox_profile: None,
             windows_sandbox_level: None,
             model: None,
             subagent_model: None,
@@ -116,6 +116,7 @@ async fn permissions_message_added_on_override_change() -> Result<()> {
             cwd: None,
             approval_policy: Some(AskForApproval::Never),
             sandbox_policy: None,
+            sandbox_profile: None,
             windows_sandbox_level: None,
             model: None,
             subagent_model: None,
@@ -264,6 +265,7 @@ async fn resume_replays_permissions_messages() -> Result<()> {
             cwd: None,
             approval_policy: Some(AskForApproval::Never),
             sandbox_policy: None,
+            sandbox_profile: None,
             windows_sandbox_level: None,
             model: None,
             subagent_model: None,
@@ -369,6 +371,7 @@ async fn resume_and_fork_append_permissions_messages() -> Result<()> {
             cwd: None,
             approval_policy: Some(AskForApproval::Never),
             sandbox_policy: None,
+            sandbox_profile: None,
             windows_sandbox_level: None,
             model: None,
             subagent_model: None,
@@ -476,8 +479,10 @@ async fn permissions_message_includes_writable_roots() -> Result<()> {
     let sandbox_policy = SandboxPolicy::WorkspaceWrite {
         writable_roots: vec![writable_root],
         network_access: false,
+        allowed_loopback_ports: Vec::new(),
         exclude_tmpdir_env_var: false,
         exclude_slash_tmp: false,
+        deny_read: Vec::new(),
     };
     let sandbox_policy_for_config = sandbox_policy.clone();
 
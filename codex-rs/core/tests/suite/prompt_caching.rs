@@ -340,14 +340,17 @@ async fn overrides_turn_context_but_keeps_cached_prefix_and_key_constant() -> an
     let new_policy = SandboxPolicy::WorkspaceWrite {
         writable_roots: vec![writable.path().try_into().unwrap()],
         network_access: true,
+        allowed_loopback_ports: Vec::new(),
         exclude_tmpdir_env_var: true,
         exclude_slash_tmp: true,
+        deny_read: Vec::new(),
     };
     codex
         .submit(Op::OverrideTurnContext {
             cwd: None,
             approval_policy: Some(AskForApproval::Never),
             sandbox_policy: Some(new_policy.clone()),
+            sandbox_profile: None,
             windows_sandbox_level: None,
             model: Some("o3".to_string()),
             subagent_model: None,
@@ -429,6 +432,7 @@ async fn override_before_first_turn_emits_environment_context() -> anyhow::Resul
             cwd: None,
             approval_policy: Some(AskForApproval::Never),
             sandbox_policy: None,
+            sandbox_profile: None,
             windows_sandbox_level: None,
             model: Some("gpt-5.1-codex".to_string()),
             subagent_model: None,
@@ -581,8 +585,10 @@ async fn per_turn_overrides_keep_cached_prefix_and_key_constant() -> anyhow::Res
     let new_policy = SandboxPolicy::WorkspaceWrite {
         writable_roots: vec![AbsolutePathBuf::try_from(writable.path()).unwrap()],
         network_access: true,
+        allowed_loopback_ports: Vec::new(),
         exclude_tmpdir_env_var: true,
         exclude_slash_tmp: true,
+        deny_read: Vec::new(),
     };
     codex
         .submit(Op::UserTurn {
@@ -140,6 +140,7 @@ async fn remote_models_remote_model_uses_unified_exec() -> Result<()> {
             cwd: None,
             approval_policy: None,
             sandbox_policy: None,
+            sandbox_profile: None,
             windows_sandbox_level: None,
             model: Some(REMOTE_MODEL_SLUG.to_string()),
             subagent_model: None,
@@ -379,6 +380,7 @@ async fn remote_models_apply_remote_base_instructions() -> Result<()> {
             cwd: None,
             approval_policy: None,
             sandbox_policy: None,
+            sandbox_profile: None,
             windows_sandbox_level: None,
             model: Some(model.to_string()),
             subagent_model: None,
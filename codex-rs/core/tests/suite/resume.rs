@@ -322,6 +322,7 @@ async fn resume_model_switch_is_not_duplicated_after_pre_turn_override() -> Resu
             cwd: None,
             approval_policy: None,
             sandbox_policy: None,
+            sandbox_profile: None,
             windows_sandbox_level: None,
             model: Some("gpt-5.1-codex-max".to_string()),
             effort: None,
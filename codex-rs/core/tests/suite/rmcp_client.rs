@@ -14,6 +14,7 @@ use codex_core::config::types::McpServerTransportConfig;
 use codex_core::protocol::AskForApproval;
 use codex_core::protocol::EventMsg;
 use codex_core::protocol::McpInvocation;
+use codex_core::protocol::McpListChangedKind;
 use codex_core::protocol::McpToolCallBeginEvent;
 use codex_core::protocol::Op;
 use codex_core::protocol::SandboxPolicy;
@@ -96,6 +97,8 @@ async fn stdio_server_round_trip() -> anyhow::Result<()> {
                     enabled_tools: None,
                     disabled_tools: None,
                     scopes: None,
+                    default_tool_approval: None,
+                    tool_approvals: HashMap::new(),
                 },
             );
             config
@@ -117,7 +120,7 @@ async fn stdio_server_round_trip() -> anyhow::Result<()> {
             final_output_json_schema: None,
             cwd: fixture.cwd.path().to_path_buf(),
             approval_policy: AskForApproval::Never,
-            sandbox_policy: SandboxPolicy::ReadOnly,
+            sandbox_policy: SandboxPolicy::new_read_only_policy(),
             model: session_model,
             effort: None,
             summary: ReasoningSummary::Auto,
@@ -182,6 +185,149 @@ async fn stdio_server_round_trip() -> anyhow::Result<()> {
     Ok(())
 }
 
+fn tool_names(body: &Value) -> Vec<String> {
+    body["tools"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|tool| tool.get("name").and_then(Value::as_str))
+        .map(str::to_string)
+        .collect()
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+#[serial(mcp_test_value)]
+async fn stdio_server_tool_list_changed_updates_next_turn_tools() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = responses::start_mock_server().await;
+
+    let server_name = "rmcp";
+    let late_tool_name = format!("mcp__{server_name}__late");
+
+    let first_request = mount_sse_once(
+        &server,
+        responses::sse(vec![
+            responses::ev_response_created("resp-1"),
+            responses::ev_function_call("call-1", &format!("mcp__{server_name}__add_tool"), "{}"),
+            responses::ev_completed("resp-1"),
+        ]),
+    )
+    .await;
+    mount_sse_once(
+        &server,
+        responses::sse(vec![
+            responses::ev_assistant_message("msg-1", "Added the late tool."),
+            responses::ev_completed("resp-2"),
+        ]),
+    )
+    .await;
+    let next_turn_request = mount_sse_once(
+        &server,
+        responses::sse(vec![
+            responses::ev_assistant_message("msg-2", "The late tool is available."),
+            responses::ev_completed("resp-3"),
+        ]),
+    )
+    .await;
+
+    let rmcp_test_server_bin = stdio_server_bin()?;
+
+    let fixture = test_codex()
+        .with_config(move |config| {
+            disallow_mcp_search(config);
+            let mut servers = config.mcp_servers.get().clone();
+            servers.insert(
+                server_name.to_string(),
+                McpServerConfig {
+                    transport: McpServerTransportConfig::Stdio {
+                        command: rmcp_test_server_bin,
+                        args: Vec::new(),
+                        env: None,
+                        env_vars: Vec::new(),
+                        cwd: None,
+                    },
+                    enabled: true,
+                    required: false,
+                    disabled_reason: None,
+                    startup_timeout_sec: Some(Duration::from_secs(10)),
+                    tool_timeout_sec: None,
+                    enabled_tools: None,
+                    disabled_tools: None,
+                    scopes: None,
+                    default_tool_approval: None,
+                    tool_approvals: HashMap::new(),
+                },
+            );
+            config
+                .mcp_servers
+                .set(servers)
+                .expect("test mcp servers should accept any configuration");
+        })
+        .build(&server)
+        .await?;
+    let session_model = fixture.session_configured.model.clone();
+
+    let submit = |text: &str| Op::UserTurn {
+        items: vec![UserInput::Text {
+            text: text.into(),
+            text_elements: Vec::new(),
+        }],
+        final_output_json_schema: None,
+        cwd: fixture.cwd.path().to_path_buf(),
+        approval_policy: AskForApproval::Never,
+        sandbox_policy: SandboxPolicy::new_read_only_policy(),
+        model: session_model.clone(),
+        effort: None,
+        summary: ReasoningSummary::Auto,
+        max_output_tokens: None,
+        history_depth: None,
+        collaboration_mode: None,
+        personality: None,
+    };
+
+    fixture
+        .codex
+        .submit(submit("call the rmcp add_tool tool"))
+        .await?;
+
+    // The refresh can finish before or after the turn, so wait for both.
+    let mut tools_changed = None;
+    let mut turn_complete = false;
+    while tools_changed.is_none() || !turn_complete {
+        match wait_for_event(&fixture.codex, |ev| {
+            matches!(ev, EventMsg::McpToolsChanged(_) | EventMsg::TurnComplete(_))
+        })
+        .await
+        {
+            EventMsg::McpToolsChanged(changed) => tools_changed = Some(changed),
+            EventMsg::TurnComplete(_) => turn_complete = true,
+            other => unreachable!("event guard excludes {other:?}"),
+        }
+    }
+    let tools_changed = tools_changed.expect("tools changed event");
+    assert_eq!(tools_changed.server, server_name);
+    assert_eq!(tools_changed.kind, McpListChangedKind::Tools);
+
+    assert!(
+        !tool_names(&first_request.single_request().body_json()).contains(&late_tool_name),
+        "{late_tool_name} should not exist before add_tool runs"
+    );
+
+    fixture.codex.submit(submit("use the late tool")).await?;
+    wait_for_event(&fixture.codex, |ev| matches!(ev, EventMsg::TurnComplete(_))).await;
+
+    let tools = tool_names(&next_turn_request.single_request().body_json());
+    assert!(
+        tools.contains(&late_tool_name),
+        "next turn should include {late_tool_name}, got {tools:?}"
+    );
+
+    server.verify().await;
+
+    Ok(())
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 #[serial(mcp_test_value)]
 async fn stdio_image_responses_round_trip() -> anyhow::Result<()> {
@@ -241,6 +387,8 @@ async fn stdio_image_responses_round_trip() -> anyhow::Result<()> {
                     enabled_tools: None,
                     disabled_tools: None,
                     scopes: None,
+                    default_tool_approval: None,
+                    tool_approvals: HashMap::new(),
                 },
             );
             config
@@ -262,7 +410,7 @@ async fn stdio_image_responses_round_trip() -> anyhow::Result<()> {
             final_output_json_schema: None,
             cwd: fixture.cwd.path().to_path_buf(),
             approval_policy: AskForApproval::Never,
-            sandbox_policy: SandboxPolicy::ReadOnly,
+            sandbox_policy: SandboxPolicy::new_read_only_policy(),
             model: session_model,
             effort: None,
             summary: ReasoningSummary::Auto,
@@ -393,6 +541,8 @@ async fn stdio_server_propagates_whitelisted_env_vars() -> anyhow::Result<()> {
                     enabled_tools: None,
                     disabled_tools: None,
                     scopes: None,
+                    default_tool_approval: None,
+                    tool_approvals: HashMap::new(),
                 },
             );
             config
@@ -414,7 +564,7 @@ async fn stdio_server_propagates_whitelisted_env_vars() -> anyhow::Result<()> {
             final_output_json_schema: None,
             cwd: fixture.cwd.path().to_path_buf(),
             approval_policy: AskForApproval::Never,
-            sandbox_policy: SandboxPolicy::ReadOnly,
+            sandbox_policy: SandboxPolicy::new_read_only_policy(),
             model: session_model,
             effort: None,
             summary: ReasoningSummary::Auto,
@@ -555,6 +705,8 @@ async fn streamable_http_tool_call_round_trip() -> anyhow::Result<()> {
                     enabled_tools: None,
                     disabled_tools: None,
                     scopes: None,
+                    default_tool_approval: None,
+                    tool_approvals: HashMap::new(),
                 },
             );
             config
@@ -576,7 +728,7 @@ async fn streamable_http_tool_call_round_trip() -> anyhow::Result<()> {
             final_output_json_schema: None,
             cwd: fixture.cwd.path().to_path_buf(),
             approval_policy: AskForApproval::Never,
-            sandbox_policy: SandboxPolicy::ReadOnly,
+            sandbox_policy: SandboxPolicy::new_read_only_policy(),
             model: session_model,
             effort: None,
             summary: ReasoningSummary::Auto,
@@ -749,6 +901,8 @@ async fn streamable_http_with_oauth_round_trip() -> anyhow::Result<()> {
                     enabled_tools: None,
                     disabled_tools: None,
                     scopes: None,
+                    default_tool_approval: None,
+                    tool_approvals: HashMap::new(),
                 },
             );
             config
@@ -770,7 +924,7 @@ async fn streamable_http_with_oauth_round_trip() -> anyhow::Result<()> {
             final_output_json_schema: None,
             cwd: fixture.cwd.path().to_path_buf(),
             approval_policy: AskForApproval::Never,
-            sandbox_policy: SandboxPolicy::ReadOnly,
+            sandbox_policy: SandboxPolicy::new_read_only_policy(),
             model: session_model,
             effort: None,
             summary: ReasoningSummary::Auto,
@@ -78,8 +78,10 @@ async fn if_parent_of_repo_is_writable_then_dot_git_folder_is_writable() {
     let policy = SandboxPolicy::WorkspaceWrite {
         writable_roots: vec![test_scenario.repo_parent.as_path().try_into().unwrap()],
         network_access: false,
+        allowed_loopback_ports: Vec::new(),
         exclude_tmpdir_env_var: true,
         exclude_slash_tmp: true,
+        deny_read: Vec::new(),
     };
 
     test_scenario
@@ -104,8 +106,10 @@ async fn if_git_repo_is_writable_root_then_dot_git_folder_is_read_only() {
     let policy = SandboxPolicy::WorkspaceWrite {
         writable_roots: vec![test_scenario.repo_root.as_path().try_into().unwrap()],
         network_access: false,
+        allowed_loopback_ports: Vec::new(),
         exclude_tmpdir_env_var: true,
         exclude_slash_tmp: true,
+        deny_read: Vec::new(),
     };
 
     test_scenario
@@ -145,7 +149,7 @@ async fn danger_full_access_allows_all_writes() {
 async fn read_only_forbids_all_writes() {
     let tmp = TempDir::new().expect("should be able to create temp dir");
     let test_scenario = create_test_scenario(&tmp);
-    let policy = SandboxPolicy::ReadOnly;
+    let policy = SandboxPolicy::new_read_only_policy();
 
     test_scenario
         .run_test(
@@ -171,7 +175,7 @@ async fn openpty_works_under_seatbelt() {
         return;
     }
 
-    let policy = SandboxPolicy::ReadOnly;
+    let policy = SandboxPolicy::new_read_only_policy();
     let command_cwd = std::env::current_dir().expect("getcwd");
     let sandbox_cwd = command_cwd.clone();
 
@@ -228,7 +232,7 @@ async fn java_home_finds_runtime_under_seatbelt() {
         return;
     }
 
-    let policy = SandboxPolicy::ReadOnly;
+    let policy = SandboxPolicy::new_read_only_policy();
     let command_cwd = std::env::current_dir().expect("getcwd");
     let sandbox_cwd = command_cwd.clone();
 
@@ -265,6 +269,46 @@ async fn java_home_finds_runtime_under_seatbelt() {
     );
 }
 
+/// `deny_read` hides a file, a directory and glob matches from `file-read*`
+/// while the rest of the disk stays readable.
+#[tokio::test]
+async fn deny_read_blocks_reads_of_denied_paths() {
+    if std::env::var(CODEX_SANDBOX_ENV_VAR) == Ok("seatbelt".to_string()) {
+        eprintln!("{CODEX_SANDBOX_ENV_VAR} is set to 'seatbelt', skipping test.");
+        return;
+    }
+
+    let tmp = TempDir::new().expect("should be able to create temp dir");
+    let root = tmp.path();
+    let secret_file = root.join("secret.txt");
+    let secret_dir = root.join("secrets");
+    let file_in_secret_dir = secret_dir.join("key");
+    let env_file = root.join(".env.local");
+    let public_file = root.join("README.md");
+    std::fs::create_dir(&secret_dir).expect("should be able to create secrets dir");
+    for path in [&secret_file, &file_in_secret_dir, &env_file, &public_file] {
+        std::fs::write(path, "data").expect("should be able to write test file");
+    }
+
+    let mut policy = SandboxPolicy::new_read_only_policy();
+    policy.set_deny_read(vec![
+        secret_file.to_string_lossy().to_string(),
+        secret_dir.to_string_lossy().to_string(),
+        root.join(".env*").to_string_lossy().to_string(),
+    ]);
+
+    for denied in [&secret_file, &file_in_secret_dir, &env_file] {
+        assert!(
+            !cat(denied, &policy).await,
+            "{denied:?} should not be readable"
+        );
+    }
+    assert!(
+        cat(&public_file, &policy).await,
+        "README.md should be readable"
+    );
+}
+
 #[expect(clippy::expect_used)]
 fn create_test_scenario(tmp: &TempDir) -> TestScenario {
     let repo_parent = tmp.path().to_path_buf();
@@ -308,3 +352,26 @@ async fn touch(path: &Path, policy: &SandboxPolicy) -> bool {
         .expect("should be able to wait for child process")
         .success()
 }
+
+#[expect(clippy::expect_used)]
+/// Note that `path` must be absolute.
+async fn cat(path: &Path, policy: &SandboxPolicy) -> bool {
+    assert!(path.is_absolute(), "Path must be absolute: {path:?}");
+    let command_cwd = std::env::current_dir().expect("getcwd");
+    let sandbox_cwd = command_cwd.clone();
+    let mut child = spawn_command_under_seatbelt(
+        vec!["/bin/cat".to_string(), path.to_string_lossy().to_string()],
+        command_cwd,
+        policy,
+        sandbox_cwd.as_path(),
+        StdioPolicy::RedirectForShellTool,
+        HashMap::new(),
+    )
+    .await
+    .expect("should be able to spawn command under seatbelt");
+    child
+        .wait()
+        .await
+        .expect("should be able to wait for child process")
+        .success()
+}
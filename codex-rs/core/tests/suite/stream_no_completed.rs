@@ -86,6 +86,7 @@ async fn retries_on_early_close() {
         stream_idle_timeout_ms: Some(2000),
         requires_openai_auth: false,
         supports_websockets: false,
+        pricing: None,
     };
 
     let TestCodex { codex, .. } = test_codex()
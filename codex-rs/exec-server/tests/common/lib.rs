@@ -88,7 +88,7 @@ where
     S: Service<RoleClient> + ClientHandler,
 {
     let sandbox_state = SandboxState {
-        sandbox_policy: SandboxPolicy::ReadOnly,
+        sandbox_policy: SandboxPolicy::new_read_only_policy(),
         codex_linux_sandbox_exe,
         sandbox_cwd: sandbox_cwd.as_ref().to_path_buf(),
         use_linux_sandbox_bwrap: false,
@@ -111,11 +111,13 @@ where
             // when the sandbox policy is expanded.
             writable_roots: vec![],
             network_access: false,
+            allowed_loopback_ports: Vec::new(),
             // Disable writes to temp dir because this is a test, so
             // writable_folder is likely also under /tmp and we want to be
             // strict about what is writable.
             exclude_tmpdir_env_var: true,
             exclude_slash_tmp: true,
+            deny_read: Vec::new(),
         },
         codex_linux_sandbox_exe,
         sandbox_cwd: writable_folder.as_ref().to_path_buf(),
@@ -774,6 +774,7 @@ impl EventProcessor for EventProcessorWithHumanOutput {
             | EventMsg::ExecCommandOutputDelta(_)
             | EventMsg::GetHistoryEntryResponse(_)
             | EventMsg::McpListToolsResponse(_)
+            | EventMsg::McpToolsChanged(_)
             | EventMsg::ListCustomPromptsResponse(_)
             | EventMsg::ListSkillsResponse(_)
             | EventMsg::ListRemoteSkillsResponse(_)
@@ -795,6 +796,7 @@ impl EventProcessor for EventProcessorWithHumanOutput {
             | EventMsg::UndoCompleted(_)
             | EventMsg::UndoStarted(_)
             | EventMsg::ThreadRolledBack(_)
+            | EventMsg::ProjectDocLoaded(_)
             | EventMsg::RequestUserInput(_)
             | EventMsg::PromptSuggestion(_)
             | EventMsg::ToolsListResponse(_)
@@ -206,7 +206,7 @@ pub struct CodexLogSnapshot {
 }
 
 impl CodexLogSnapshot {
-    pub(crate) fn as_bytes(&self) -> &[u8] {
+    pub fn as_bytes(&self) -> &[u8] {
         &self.bytes
     }
 
@@ -2,9 +2,10 @@
 //!
 //! This module mirrors the semantics used by the macOS Seatbelt sandbox:
 //! - the filesystem is read-only by default,
-//! - explicit writable roots are layered on top, and
+//! - explicit writable roots are layered on top,
 //! - sensitive subpaths such as `.git` and `.codex` remain read-only even when
-//!   their parent root is writable.
+//!   their parent root is writable, and
+//! - `deny_read` paths are masked so they cannot be read at all.
 //!
 //! The overall Linux sandbox is composed of:
 //! - seccomp + `PR_SET_NO_NEW_PRIVS` applied in-process, and
@@ -65,6 +66,11 @@ fn create_bwrap_flags(
     args.extend(create_filesystem_args(sandbox_policy, cwd)?);
     // Isolate the PID namespace.
     args.push("--unshare-pid".to_string());
+    // Allowed loopback ports are relayed into a private network namespace,
+    // so the command cannot reach the same ports on other hosts.
+    if !sandbox_policy.allowed_loopback_ports().is_empty() {
+        args.push("--unshare-net".to_string());
+    }
     // Mount a fresh /proc unless the caller explicitly disables it.
     if options.mount_proc {
         args.push("--proc".to_string());
@@ -82,7 +88,9 @@ fn create_bwrap_flags(
 /// 2. `--bind <root> <root>` re-enables writes for allowed roots.
 /// 3. `--ro-bind <subpath> <subpath>` re-applies read-only protections under
 ///    those writable roots so protected subpaths win.
-/// 4. `--dev-bind /dev/null /dev/null` preserves the common sink even under a
+/// 4. `deny_read` paths are masked last so they win over every bind above:
+///    directories with an empty read-only tmpfs, files with `/dev/null`.
+/// 5. `--dev-bind /dev/null /dev/null` preserves the common sink even under a
 ///    read-only root.
 fn create_filesystem_args(sandbox_policy: &SandboxPolicy, cwd: &Path) -> Result<Vec<String>> {
     let writable_roots = sandbox_policy.get_writable_roots_with_cwd(cwd);
@@ -134,6 +142,21 @@ fn create_filesystem_args(sandbox_policy: &SandboxPolicy, cwd: &Path) -> Result<
         }
     }
 
+    // Mask `deny_read` paths last so they also hide anything bound above.
+    for denied in sandbox_policy.get_deny_read_paths_with_cwd(cwd) {
+        let denied = denied.as_path();
+        if denied.is_dir() {
+            args.push("--tmpfs".to_string());
+            args.push(path_to_string(denied));
+            args.push("--remount-ro".to_string());
+            args.push(path_to_string(denied));
+        } else {
+            args.push("--ro-bind".to_string());
+            args.push("/dev/null".to_string());
+            args.push(path_to_string(denied));
+        }
+    }
+
     // Ensure `/dev/null` remains usable regardless of the root bind.
     args.push("--dev-bind".to_string());
     args.push("/dev/null".to_string());
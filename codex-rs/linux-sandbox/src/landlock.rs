@@ -15,11 +15,14 @@ use landlock::ABI;
 #[allow(unused_imports)]
 use landlock::Access;
 use landlock::AccessFs;
+use landlock::AccessNet;
 use landlock::CompatLevel;
 use landlock::Compatible;
+use landlock::NetPort;
 use landlock::Ruleset;
 use landlock::RulesetAttr;
 use landlock::RulesetCreatedAttr;
+use landlock::RulesetError;
 use seccompiler::BpfProgram;
 use seccompiler::SeccompAction;
 use seccompiler::SeccompCmpArgLen;
@@ -35,13 +38,19 @@ use seccompiler::apply_filter;
 ///
 /// This function is responsible for:
 /// - enabling `PR_SET_NO_NEW_PRIVS` when restrictions apply, and
-/// - installing the network seccomp filter when network access is disabled.
+/// - installing the network seccomp filter when network access is disabled,
+///   which also allows TCP when `loopback_relayed` says the thread runs in a
+///   private network namespace whose loopback only holds the relays for
+///   `allowed_loopback_ports`, and
+/// - otherwise, with no such namespace, limiting TCP to
+///   `allowed_loopback_ports` with Landlock network rules instead.
 ///
 /// Filesystem restrictions are intentionally handled by bubblewrap.
 pub(crate) fn apply_sandbox_policy_to_current_thread(
     sandbox_policy: &SandboxPolicy,
     cwd: &Path,
     apply_landlock_fs: bool,
+    loopback_relayed: bool,
 ) -> Result<()> {
     // `PR_SET_NO_NEW_PRIVS` is required for seccomp, but it also prevents
     // setuid privilege elevation. Many `bwrap` deployments rely on setuid, so
@@ -54,10 +63,27 @@ pub(crate) fn apply_sandbox_policy_to_current_thread(
     }
 
     if !sandbox_policy.has_full_network_access() {
-        install_network_seccomp_filter_on_current_thread()?;
+        let ports = sandbox_policy.allowed_loopback_ports();
+        let mode = if loopback_relayed {
+            NetworkSeccompMode::TcpOnly
+        } else if !ports.is_empty() {
+            install_network_landlock_rules_on_current_thread(ports)?;
+            NetworkSeccompMode::TcpOnly
+        } else {
+            NetworkSeccompMode::DenyAll
+        };
+        install_network_seccomp_filter_on_current_thread(mode)?;
     }
 
     if apply_landlock_fs && !sandbox_policy.has_full_disk_write_access() {
+        // Landlock can only grant access, and a grant on a directory also
+        // covers files created in it later, so it cannot keep `deny_read`
+        // paths unreadable next to readable ones. Only bubblewrap can.
+        if !sandbox_policy.get_deny_read_paths_with_cwd(cwd).is_empty() {
+            return Err(CodexErr::UnsupportedOperation(
+                "sandbox_deny_read requires the bubblewrap sandbox".to_string(),
+            ));
+        }
         let writable_roots = sandbox_policy
             .get_writable_roots_with_cwd(cwd)
             .into_iter()
@@ -66,9 +92,6 @@ pub(crate) fn apply_sandbox_policy_to_current_thread(
         install_filesystem_landlock_rules_on_current_thread(writable_roots)?;
     }
 
-    // TODO(ragona): Add appropriate restrictions if
-    // `sandbox_policy.has_full_disk_read_access()` is `false`.
-
     Ok(())
 }
 
@@ -118,12 +141,89 @@ fn install_filesystem_landlock_rules_on_current_thread(
     Ok(())
 }
 
+/// Installs Landlock network rules on the current thread that allow TCP
+/// `connect` and `bind` only on `ports`.
+///
+/// Landlock matches ports, not addresses, so the ports are reachable on other
+/// hosts too; the bubblewrap path relays them into a private network
+/// namespace instead and has no such gap.
+///
+/// # Errors
+/// Network rules need Landlock ABI v4 (Linux 6.7). Older kernels return
+/// [`CodexErr::Sandbox`] rather than leaving TCP open.
+fn install_network_landlock_rules_on_current_thread(ports: &[u16]) -> Result<()> {
+    let access = AccessNet::from_all(ABI::V4);
+    let status = Ruleset::default()
+        .set_compatibility(CompatLevel::HardRequirement)
+        .handle_access(access)?
+        .create()?
+        .add_rules(
+            ports
+                .iter()
+                .map(|port| Ok::<_, RulesetError>(NetPort::new(*port, access))),
+        )?
+        .set_no_new_privs(true)
+        .restrict_self()?;
+
+    if status.ruleset != landlock::RulesetStatus::FullyEnforced {
+        return Err(CodexErr::Sandbox(SandboxErr::LandlockRestrict));
+    }
+
+    Ok(())
+}
+
+/// How much of the network the seccomp filter leaves reachable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NetworkSeccompMode {
+    /// Only AF_UNIX domain sockets.
+    DenyAll,
+    /// AF_UNIX sockets plus IPv4/IPv6 TCP, for a private network namespace
+    /// where TCP can only reach the loopback relays, or a thread whose TCP
+    /// ports are limited by Landlock. Other socket types (UDP, raw, netlink,
+    /// ...) stay blocked.
+    TcpOnly,
+}
+
 /// Installs a seccomp filter that blocks outbound network access except for
-/// AF_UNIX domain sockets.
+/// AF_UNIX domain sockets and, in [`NetworkSeccompMode::TcpOnly`], TCP.
 ///
 /// The filter is applied to the current thread so only the sandboxed child
 /// inherits it.
-fn install_network_seccomp_filter_on_current_thread() -> std::result::Result<(), SandboxErr> {
+fn install_network_seccomp_filter_on_current_thread(
+    mode: NetworkSeccompMode,
+) -> std::result::Result<(), SandboxErr> {
+    let prog = network_seccomp_program(mode)?;
+
+    apply_filter(&prog)?;
+
+    Ok(())
+}
+
+fn network_seccomp_program(
+    mode: NetworkSeccompMode,
+) -> std::result::Result<BpfProgram, SandboxErr> {
+    let rules = match mode {
+        NetworkSeccompMode::DenyAll => deny_all_network_rules()?,
+        NetworkSeccompMode::TcpOnly => tcp_only_network_rules()?,
+    };
+
+    let filter = SeccompFilter::new(
+        rules,
+        SeccompAction::Allow,                     // default – allow
+        SeccompAction::Errno(libc::EPERM as u32), // when rule matches – return EPERM
+        if cfg!(target_arch = "x86_64") {
+            TargetArch::x86_64
+        } else if cfg!(target_arch = "aarch64") {
+            TargetArch::aarch64
+        } else {
+            unimplemented!("unsupported architecture for seccomp filter");
+        },
+    )?;
+
+    Ok(filter.try_into()?)
+}
+
+fn deny_all_network_rules() -> std::result::Result<BTreeMap<i64, Vec<SeccompRule>>, SandboxErr> {
     // Build rule map.
     let mut rules: BTreeMap<i64, Vec<SeccompRule>> = BTreeMap::new();
 
@@ -164,22 +264,100 @@ fn install_network_seccomp_filter_on_current_thread() -> std::result::Result<(),
     rules.insert(libc::SYS_socket, vec![unix_only_rule.clone()]);
     rules.insert(libc::SYS_socketpair, vec![unix_only_rule]); // always deny (Unix can use socketpair but fine, keep open?)
 
-    let filter = SeccompFilter::new(
-        rules,
-        SeccompAction::Allow,                     // default – allow
-        SeccompAction::Errno(libc::EPERM as u32), // when rule matches – return EPERM
-        if cfg!(target_arch = "x86_64") {
-            TargetArch::x86_64
-        } else if cfg!(target_arch = "aarch64") {
-            TargetArch::aarch64
-        } else {
-            unimplemented!("unsupported architecture for seccomp filter");
-        },
-    )?;
+    Ok(rules)
+}
 
-    let prog: BpfProgram = filter.try_into()?;
+/// Rules for [`NetworkSeccompMode::TcpOnly`]. Socket syscalls stay available
+/// so TCP clients and servers work; the network namespace or the Landlock
+/// port rules decide what they reach.
+fn tcp_only_network_rules() -> std::result::Result<BTreeMap<i64, Vec<SeccompRule>>, SandboxErr> {
+    let mut rules: BTreeMap<i64, Vec<SeccompRule>> = BTreeMap::new();
 
-    apply_filter(&prog)?;
+    for nr in [
+        libc::SYS_ptrace,
+        libc::SYS_io_uring_setup,
+        libc::SYS_io_uring_enter,
+        libc::SYS_io_uring_register,
+    ] {
+        rules.insert(nr, vec![]);
+    }
 
-    Ok(())
+    // Deny every domain other than AF_UNIX, AF_INET and AF_INET6.
+    let mut socket_rules = vec![SeccompRule::new(
+        [libc::AF_UNIX, libc::AF_INET, libc::AF_INET6]
+            .into_iter()
+            .map(|domain| {
+                SeccompCondition::new(0, SeccompCmpArgLen::Dword, SeccompCmpOp::Ne, domain as u64)
+            })
+            .collect::<std::result::Result<Vec<_>, _>>()?,
+    )?];
+    // The relays only carry TCP, so IP sockets must be SOCK_STREAM with the
+    // default or TCP protocol. The low bits of the type hold the socket type;
+    // the rest are SOCK_NONBLOCK/SOCK_CLOEXEC flags.
+    const SOCK_TYPE_MASK: u64 = 0xf;
+    for domain in [libc::AF_INET, libc::AF_INET6] {
+        let is_domain =
+            SeccompCondition::new(0, SeccompCmpArgLen::Dword, SeccompCmpOp::Eq, domain as u64)?;
+        for sock_type in (0..=SOCK_TYPE_MASK).filter(|t| *t != libc::SOCK_STREAM as u64) {
+            socket_rules.push(SeccompRule::new(vec![
+                is_domain.clone(),
+                SeccompCondition::new(
+                    1,
+                    SeccompCmpArgLen::Dword,
+                    SeccompCmpOp::MaskedEq(SOCK_TYPE_MASK),
+                    sock_type,
+                )?,
+            ])?);
+        }
+        socket_rules.push(SeccompRule::new(vec![
+            is_domain,
+            SeccompCondition::new(2, SeccompCmpArgLen::Dword, SeccompCmpOp::Ne, 0)?,
+            SeccompCondition::new(
+                2,
+                SeccompCmpArgLen::Dword,
+                SeccompCmpOp::Ne,
+                libc::IPPROTO_TCP as u64,
+            )?,
+        ])?);
+    }
+    rules.insert(libc::SYS_socket, socket_rules);
+
+    rules.insert(
+        libc::SYS_socketpair,
+        vec![SeccompRule::new(vec![SeccompCondition::new(
+            0,
+            SeccompCmpArgLen::Dword,
+            SeccompCmpOp::Ne,
+            libc::AF_UNIX as u64,
+        )?])?],
+    );
+
+    // TCP Fast Open connects from `sendto`/`sendmsg` without going through
+    // `connect`; refuse MSG_FASTOPEN so `connect` stays the only way out.
+    let fastopen = |flags_arg: u8| -> std::result::Result<Vec<SeccompRule>, SandboxErr> {
+        Ok(vec![SeccompRule::new(vec![SeccompCondition::new(
+            flags_arg,
+            SeccompCmpArgLen::Dword,
+            SeccompCmpOp::MaskedEq(libc::MSG_FASTOPEN as u64),
+            libc::MSG_FASTOPEN as u64,
+        )?])?])
+    };
+    rules.insert(libc::SYS_sendto, fastopen(3)?);
+    rules.insert(libc::SYS_sendmsg, fastopen(2)?);
+    rules.insert(libc::SYS_sendmmsg, fastopen(3)?);
+
+    Ok(rules)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn network_seccomp_programs_compile_for_both_modes() {
+        for mode in [NetworkSeccompMode::DenyAll, NetworkSeccompMode::TcpOnly] {
+            let program = network_seccomp_program(mode).expect("seccomp program");
+            assert!(!program.is_empty(), "{mode:?} produced an empty program");
+        }
+    }
 }
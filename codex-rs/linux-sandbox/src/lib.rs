@@ -1,8 +1,10 @@
 //! Linux sandbox helper entry point.
 //!
 //! On Linux, `codex-linux-sandbox` applies:
-//! - in-process restrictions (`no_new_privs` + seccomp), and
-//! - bubblewrap for filesystem isolation.
+//! - in-process restrictions (`no_new_privs` + seccomp),
+//! - bubblewrap for filesystem isolation, plus a private network namespace
+//!   relaying `allowed_loopback_ports` to the host, and
+//! - per-command resource limits (a cgroup v2 child where delegated, plus rlimits).
 #[cfg(target_os = "linux")]
 mod bwrap;
 #[cfg(target_os = "linux")]
@@ -10,6 +12,8 @@ mod landlock;
 #[cfg(target_os = "linux")]
 mod linux_run_main;
 #[cfg(target_os = "linux")]
+mod loopback_relay;
+#[cfg(target_os = "linux")]
 mod vendored_bwrap;
 
 #[cfg(target_os = "linux")]
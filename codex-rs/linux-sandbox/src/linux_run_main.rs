@@ -3,12 +3,20 @@ use std::ffi::CString;
 use std::fs::File;
 use std::io::Read;
 use std::os::fd::FromRawFd;
+use std::os::fd::RawFd;
 use std::path::Path;
 use std::path::PathBuf;
 
+use codex_core::config::types::ResourceLimits;
+use codex_core::resource_limits::apply_rlimits;
+use codex_core::resource_limits::enter_exec_cgroup;
+use codex_core::resource_limits::limits_outside_exec_cgroup;
+
 use crate::bwrap::BwrapOptions;
 use crate::bwrap::create_bwrap_command_args;
 use crate::landlock::apply_sandbox_policy_to_current_thread;
+use crate::loopback_relay::spawn_host_relay;
+use crate::loopback_relay::spawn_sandbox_relay;
 use crate::vendored_bwrap::exec_vendored_bwrap;
 use crate::vendored_bwrap::run_vendored_bwrap_main;
 
@@ -46,6 +54,18 @@ pub struct LandlockCommand {
     #[arg(long = "no-proc", default_value_t = false)]
     pub no_proc: bool,
 
+    /// Per-command resource limits as JSON. The outer stage enters the exec
+    /// cgroup before bubblewrap so it covers everything the command spawns;
+    /// rlimits are set only right before the final command is exec'd.
+    #[arg(long = "resource-limits", hide = true)]
+    pub resource_limits: Option<ResourceLimits>,
+
+    /// Internal: inherited fd of the relay that connects
+    /// `allowed_loopback_ports` in the sandbox's private network namespace to
+    /// the host's loopback.
+    #[arg(long = "loopback-relay-fd", hide = true)]
+    pub loopback_relay_fd: Option<i32>,
+
     /// Full command args to run under the Linux sandbox helper.
     #[arg(trailing_var_arg = true)]
     pub command: Vec<String>,
@@ -54,10 +74,13 @@ pub struct LandlockCommand {
 /// Entry point for the Linux sandbox helper.
 ///
 /// The sequence is:
-/// 1. When needed, wrap the command with bubblewrap to construct the
-///    filesystem view.
-/// 2. Apply in-process restrictions (no_new_privs + seccomp).
-/// 3. `execvp` into the final command.
+/// 1. Enter an exec cgroup for the resource limits, where delegated.
+/// 2. When needed, wrap the command with bubblewrap to construct the
+///    filesystem view, relaying `allowed_loopback_ports` into a private
+///    network namespace.
+/// 3. Apply in-process restrictions (no_new_privs + seccomp).
+/// 4. Set the remaining resource limits as rlimits and `execvp` into the
+///    final command.
 pub fn run_main() -> ! {
     let LandlockCommand {
         sandbox_policy_cwd,
@@ -65,6 +88,8 @@ pub fn run_main() -> ! {
         use_bwrap_sandbox,
         apply_seccomp_then_exec,
         no_proc,
+        resource_limits,
+        loopback_relay_fd,
         command,
     } = LandlockCommand::parse();
 
@@ -72,23 +97,44 @@ pub fn run_main() -> ! {
         panic!("No command specified to execute.");
     }
 
+    // The inner stage runs in the cgroup its outer stage already entered and
+    // receives only the limits that remain for rlimits.
+    let rlimits = match resource_limits {
+        Some(limits) if !apply_seccomp_then_exec && enter_exec_cgroup(&limits) => {
+            limits_outside_exec_cgroup(&limits)
+        }
+        Some(limits) => limits,
+        None => ResourceLimits::default(),
+    };
+
     // Inner stage: apply seccomp/no_new_privs after bubblewrap has already
     // established the filesystem view.
     if apply_seccomp_then_exec {
-        if let Err(e) =
-            apply_sandbox_policy_to_current_thread(&sandbox_policy, &sandbox_policy_cwd, false)
-        {
+        if let Some(relay_fd) = loopback_relay_fd {
+            spawn_sandbox_relay(relay_fd, sandbox_policy.allowed_loopback_ports());
+        }
+        if let Err(e) = apply_sandbox_policy_to_current_thread(
+            &sandbox_policy,
+            &sandbox_policy_cwd,
+            false,
+            loopback_relay_fd.is_some(),
+        ) {
             panic!("error applying Linux sandbox restrictions: {e:?}");
         }
+        apply_rlimits_or_panic(&rlimits);
         exec_or_panic(command);
     }
 
     if sandbox_policy.has_full_disk_write_access() {
-        if let Err(e) =
-            apply_sandbox_policy_to_current_thread(&sandbox_policy, &sandbox_policy_cwd, false)
-        {
+        if let Err(e) = apply_sandbox_policy_to_current_thread(
+            &sandbox_policy,
+            &sandbox_policy_cwd,
+            false,
+            false,
+        ) {
             panic!("error applying Linux sandbox restrictions: {e:?}");
         }
+        apply_rlimits_or_panic(&rlimits);
         exec_or_panic(command);
     }
 
@@ -96,10 +142,14 @@ pub fn run_main() -> ! {
         // Outer stage: bubblewrap first, then re-enter this binary in the
         // sandboxed environment to apply seccomp. This path never falls back
         // to legacy Landlock on failure.
+        let ports = sandbox_policy.allowed_loopback_ports();
+        let loopback_relay_fd = (!ports.is_empty()).then(|| spawn_host_relay(ports));
         let inner = build_inner_seccomp_command(
             &sandbox_policy_cwd,
             &sandbox_policy,
             use_bwrap_sandbox,
+            &rlimits,
+            loopback_relay_fd,
             command,
         );
         run_bwrap_with_proc_fallback(&sandbox_policy_cwd, &sandbox_policy, inner, !no_proc);
@@ -107,13 +157,22 @@ pub fn run_main() -> ! {
 
     // Legacy path: Landlock enforcement only, when bwrap sandboxing is not enabled.
     if let Err(e) =
-        apply_sandbox_policy_to_current_thread(&sandbox_policy, &sandbox_policy_cwd, true)
+        apply_sandbox_policy_to_current_thread(&sandbox_policy, &sandbox_policy_cwd, true, false)
     {
         panic!("error applying legacy Linux sandbox restrictions: {e:?}");
     }
+    apply_rlimits_or_panic(&rlimits);
     exec_or_panic(command);
 }
 
+/// Sets rlimits right before exec'ing the final command, so they bind the
+/// command and its children but not this helper or bubblewrap.
+fn apply_rlimits_or_panic(rlimits: &ResourceLimits) {
+    if let Err(err) = apply_rlimits(rlimits) {
+        panic!("error applying resource limits: {err}");
+    }
+}
+
 fn run_bwrap_with_proc_fallback(
     sandbox_policy_cwd: &Path,
     sandbox_policy: &codex_core::protocol::SandboxPolicy,
@@ -268,6 +327,8 @@ fn build_inner_seccomp_command(
     sandbox_policy_cwd: &Path,
     sandbox_policy: &codex_core::protocol::SandboxPolicy,
     use_bwrap_sandbox: bool,
+    rlimits: &ResourceLimits,
+    loopback_relay_fd: Option<RawFd>,
     command: Vec<String>,
 ) -> Vec<String> {
     let current_exe = match std::env::current_exe() {
@@ -290,6 +351,18 @@ fn build_inner_seccomp_command(
         inner.push("--use-bwrap-sandbox".to_string());
         inner.push("--apply-seccomp-then-exec".to_string());
     }
+    if !rlimits.is_empty() {
+        let rlimits_json = match serde_json::to_string(rlimits) {
+            Ok(json) => json,
+            Err(err) => panic!("failed to serialize resource limits: {err}"),
+        };
+        inner.push("--resource-limits".to_string());
+        inner.push(rlimits_json);
+    }
+    if let Some(fd) = loopback_relay_fd {
+        inner.push("--loopback-relay-fd".to_string());
+        inner.push(fd.to_string());
+    }
     inner.push("--".to_string());
     inner.extend(command);
     inner
@@ -340,7 +413,7 @@ mod tests {
     fn inserts_bwrap_argv0_before_command_separator() {
         let argv = build_bwrap_argv(
             vec!["/bin/true".to_string()],
-            &SandboxPolicy::ReadOnly,
+            &SandboxPolicy::new_read_only_policy(),
             Path::new("/"),
             BwrapOptions { mount_proc: true },
         );
@@ -366,4 +439,31 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn loopback_ports_unshare_the_network() {
+        let policy = SandboxPolicy::WorkspaceWrite {
+            writable_roots: Vec::new(),
+            network_access: false,
+            allowed_loopback_ports: vec![3128],
+            exclude_tmpdir_env_var: true,
+            exclude_slash_tmp: true,
+            deny_read: Vec::new(),
+        };
+        let argv = build_bwrap_argv(
+            vec!["/bin/true".to_string()],
+            &policy,
+            Path::new("/"),
+            BwrapOptions { mount_proc: true },
+        );
+        assert!(argv.contains(&"--unshare-net".to_string()));
+
+        let argv = build_bwrap_argv(
+            vec!["/bin/true".to_string()],
+            &SandboxPolicy::new_read_only_policy(),
+            Path::new("/"),
+            BwrapOptions { mount_proc: true },
+        );
+        assert!(!argv.contains(&"--unshare-net".to_string()));
+    }
 }
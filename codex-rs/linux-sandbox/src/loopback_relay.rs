@@ -0,0 +1,285 @@
+//! Relay for `allowed_loopback_ports` out of a private network namespace.
+//!
+//! Bubblewrap runs the command with `--unshare-net`, so its loopback is empty
+//! and nothing outside the sandbox is reachable. For each allowed port:
+//! - the sandbox half listens on that port inside the namespace and hands
+//!   every accepted connection over a Unix socket pair (`SCM_RIGHTS`), and
+//! - the host half, started before bubblewrap in the host namespace, connects
+//!   each one to the same port on the host's loopback and copies bytes both
+//!   ways.
+//!
+//! The host half exits once every copy of the sandbox end is closed, i.e.
+//! when the sandbox is gone.
+use std::io;
+use std::net::Ipv4Addr;
+use std::net::Ipv6Addr;
+use std::net::Shutdown;
+use std::net::SocketAddr;
+use std::net::TcpListener;
+use std::net::TcpStream;
+use std::os::fd::AsFd;
+use std::os::fd::AsRawFd;
+use std::os::fd::BorrowedFd;
+use std::os::fd::FromRawFd;
+use std::os::fd::OwnedFd;
+use std::os::fd::RawFd;
+use std::sync::Arc;
+
+/// Outer stage: starts the host half of the relay in a child process and
+/// returns the sandbox end, which is left inheritable so it survives the exec
+/// into bubblewrap and the inner stage.
+pub(crate) fn spawn_host_relay(ports: &[u16]) -> RawFd {
+    let mut fds = [0; 2];
+    let res = unsafe { libc::socketpair(libc::AF_UNIX, libc::SOCK_SEQPACKET, 0, fds.as_mut_ptr()) };
+    if res < 0 {
+        let err = io::Error::last_os_error();
+        panic!("failed to create loopback relay socket: {err}");
+    }
+    let [host_end, sandbox_end] = fds;
+
+    let pid = unsafe { libc::fork() };
+    if pid < 0 {
+        let err = io::Error::last_os_error();
+        panic!("failed to fork loopback relay: {err}");
+    }
+    if pid == 0 {
+        unsafe { libc::close(sandbox_end) };
+        // SAFETY: `host_end` is a freshly created fd owned by this process.
+        let host_end = unsafe { OwnedFd::from_raw_fd(host_end) };
+        serve_host_relay(host_end, ports);
+        std::process::exit(0);
+    }
+
+    unsafe { libc::close(host_end) };
+    sandbox_end
+}
+
+/// Inner stage, inside the sandbox's network namespace: listens on `ports`
+/// and passes connections to the host half over `relay_fd`.
+///
+/// The listeners are bound before this returns, so the command can connect
+/// right away. The serving process is detached so it is reparented to
+/// bubblewrap's init and dies with the PID namespace instead of becoming a
+/// child of the command.
+pub(crate) fn spawn_sandbox_relay(relay_fd: RawFd, ports: &[u16]) {
+    // SAFETY: the outer stage passes an fd it created for this process.
+    let relay = unsafe { OwnedFd::from_raw_fd(relay_fd) };
+    let mut listeners = Vec::new();
+    for port in ports {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, *port))
+            .unwrap_or_else(|err| panic!("failed to listen on loopback port {port}: {err}"));
+        listeners.push((*port, listener));
+        // IPv6 may be disabled in the namespace; IPv4 is enough then.
+        if let Ok(listener) = TcpListener::bind((Ipv6Addr::LOCALHOST, *port)) {
+            listeners.push((*port, listener));
+        }
+    }
+
+    let pid = unsafe { libc::fork() };
+    if pid < 0 {
+        let err = io::Error::last_os_error();
+        panic!("failed to fork loopback relay: {err}");
+    }
+    if pid == 0 {
+        if unsafe { libc::fork() } != 0 {
+            unsafe { libc::_exit(0) };
+        }
+        serve_sandbox_relay(relay, listeners);
+        unsafe { libc::_exit(0) };
+    }
+
+    let mut status: libc::c_int = 0;
+    if unsafe { libc::waitpid(pid, &mut status as *mut libc::c_int, 0) } < 0 {
+        let err = io::Error::last_os_error();
+        panic!("waitpid failed for loopback relay: {err}");
+    }
+    // `relay` and `listeners` drop here, so the command does not inherit them.
+}
+
+fn serve_host_relay(relay: OwnedFd, ports: &[u16]) {
+    loop {
+        match recv_stream(relay.as_fd()) {
+            Ok(Some((port, stream))) if ports.contains(&port) => {
+                std::thread::spawn(move || relay_to_host(stream, port));
+            }
+            Ok(Some(_)) => {}
+            Ok(None) => return,
+            Err(err) => {
+                eprintln!("codex-linux-sandbox: loopback relay failed: {err}");
+                return;
+            }
+        }
+    }
+}
+
+fn serve_sandbox_relay(relay: OwnedFd, listeners: Vec<(u16, TcpListener)>) {
+    let relay = Arc::new(relay);
+    let handles = listeners
+        .into_iter()
+        .map(|(port, listener)| {
+            let relay = Arc::clone(&relay);
+            std::thread::spawn(move || {
+                for stream in listener.incoming().flatten() {
+                    if send_stream(relay.as_fd(), port, &stream).is_err() {
+                        // The host half is gone, so the sandbox is too.
+                        unsafe { libc::_exit(0) };
+                    }
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+    for handle in handles {
+        let _ = handle.join();
+    }
+}
+
+/// Connects a relayed connection to `port` on the host's loopback and copies
+/// bytes both ways until either side closes.
+fn relay_to_host(sandboxed: TcpStream, port: u16) {
+    let addrs = [
+        SocketAddr::from((Ipv4Addr::LOCALHOST, port)),
+        SocketAddr::from((Ipv6Addr::LOCALHOST, port)),
+    ];
+    let Ok(host) = TcpStream::connect(&addrs[..]) else {
+        return;
+    };
+    let (Ok(mut sandboxed_reader), Ok(mut host_reader)) = (sandboxed.try_clone(), host.try_clone())
+    else {
+        return;
+    };
+    let upstream = std::thread::spawn(move || {
+        let _ = io::copy(&mut sandboxed_reader, &mut &host);
+        let _ = host.shutdown(Shutdown::Write);
+    });
+    let _ = io::copy(&mut host_reader, &mut &sandboxed);
+    let _ = sandboxed.shutdown(Shutdown::Write);
+    let _ = upstream.join();
+}
+
+const FD_CONTROL_LEN: u32 = std::mem::size_of::<RawFd>() as u32;
+
+/// Sends `stream` and the port it was accepted on as one message.
+fn send_stream(relay: BorrowedFd<'_>, port: u16, stream: &TcpStream) -> io::Result<()> {
+    let payload = port.to_ne_bytes();
+    let mut iov = libc::iovec {
+        iov_base: payload.as_ptr() as *mut libc::c_void,
+        iov_len: payload.len(),
+    };
+    let control_len = unsafe { libc::CMSG_SPACE(FD_CONTROL_LEN) } as usize;
+    let mut control = vec![0_u8; control_len];
+    // SAFETY: an all-zero msghdr is a valid empty message.
+    let mut msg: libc::msghdr = unsafe { std::mem::zeroed() };
+    msg.msg_iov = &mut iov;
+    msg.msg_iovlen = 1;
+    msg.msg_control = control.as_mut_ptr().cast();
+    msg.msg_controllen = control_len as _;
+    // SAFETY: `control` has room for one cmsg carrying one fd.
+    unsafe {
+        let cmsg = libc::CMSG_FIRSTHDR(&msg);
+        (*cmsg).cmsg_level = libc::SOL_SOCKET;
+        (*cmsg).cmsg_type = libc::SCM_RIGHTS;
+        (*cmsg).cmsg_len = libc::CMSG_LEN(FD_CONTROL_LEN) as _;
+        std::ptr::write_unaligned(libc::CMSG_DATA(cmsg).cast::<RawFd>(), stream.as_raw_fd());
+    }
+    loop {
+        if unsafe { libc::sendmsg(relay.as_raw_fd(), &msg, libc::MSG_NOSIGNAL) } >= 0 {
+            return Ok(());
+        }
+        let err = io::Error::last_os_error();
+        if err.kind() != io::ErrorKind::Interrupted {
+            return Err(err);
+        }
+    }
+}
+
+/// Receives a stream sent by [`send_stream`]; `None` once every sender has
+/// closed its end.
+fn recv_stream(relay: BorrowedFd<'_>) -> io::Result<Option<(u16, TcpStream)>> {
+    let mut payload = [0_u8; 2];
+    let mut iov = libc::iovec {
+        iov_base: payload.as_mut_ptr().cast(),
+        iov_len: payload.len(),
+    };
+    let control_len = unsafe { libc::CMSG_SPACE(FD_CONTROL_LEN) } as usize;
+    let mut control = vec![0_u8; control_len];
+    // SAFETY: an all-zero msghdr is a valid empty message.
+    let mut msg: libc::msghdr = unsafe { std::mem::zeroed() };
+    msg.msg_iov = &mut iov;
+    msg.msg_iovlen = 1;
+    msg.msg_control = control.as_mut_ptr().cast();
+    msg.msg_controllen = control_len as _;
+    let received = loop {
+        let received =
+            unsafe { libc::recvmsg(relay.as_raw_fd(), &mut msg, libc::MSG_CMSG_CLOEXEC) };
+        if received >= 0 {
+            break received as usize;
+        }
+        let err = io::Error::last_os_error();
+        if err.kind() != io::ErrorKind::Interrupted {
+            return Err(err);
+        }
+    };
+    if received == 0 {
+        return Ok(None);
+    }
+
+    // SAFETY: `msg` was filled in by `recvmsg` and `control` is still alive.
+    let fd = unsafe {
+        let cmsg = libc::CMSG_FIRSTHDR(&msg);
+        if cmsg.is_null()
+            || (*cmsg).cmsg_level != libc::SOL_SOCKET
+            || (*cmsg).cmsg_type != libc::SCM_RIGHTS
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "loopback relay message without a connection",
+            ));
+        }
+        OwnedFd::from_raw_fd(std::ptr::read_unaligned(
+            libc::CMSG_DATA(cmsg).cast::<RawFd>(),
+        ))
+    };
+    if received != payload.len() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "loopback relay message without a port",
+        ));
+    }
+    Ok(Some((u16::from_ne_bytes(payload), TcpStream::from(fd))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::io::Read;
+    use std::io::Write;
+
+    #[test]
+    fn passes_connections_with_their_port() {
+        let mut fds = [0; 2];
+        let res =
+            unsafe { libc::socketpair(libc::AF_UNIX, libc::SOCK_SEQPACKET, 0, fds.as_mut_ptr()) };
+        assert_eq!(res, 0);
+        let (sender, receiver) =
+            unsafe { (OwnedFd::from_raw_fd(fds[0]), OwnedFd::from_raw_fd(fds[1])) };
+
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).expect("bind");
+        let mut client = TcpStream::connect(listener.local_addr().expect("addr")).expect("connect");
+        let (accepted, _) = listener.accept().expect("accept");
+        send_stream(sender.as_fd(), 3128, &accepted).expect("send");
+        drop(accepted);
+
+        let (port, mut relayed) = recv_stream(receiver.as_fd())
+            .expect("recv")
+            .expect("message");
+        assert_eq!(port, 3128);
+        relayed.write_all(b"ping").expect("write");
+        let mut buf = [0_u8; 4];
+        client.read_exact(&mut buf).expect("read");
+        assert_eq!(&buf, b"ping");
+
+        drop(sender);
+        assert!(recv_stream(receiver.as_fd()).expect("recv").is_none());
+    }
+}
@@ -34,6 +34,7 @@ const NETWORK_TIMEOUT_MS: u64 = 2_000;
 const NETWORK_TIMEOUT_MS: u64 = 10_000;
 
 const BWRAP_UNAVAILABLE_ERR: &str = "build-time bubblewrap is not available in this build.";
+const LEGACY_SANDBOX_APPLY_ERR: &str = "error applying legacy Linux sandbox restrictions";
 
 fn create_env_from_core_vars() -> HashMap<String, String> {
     let policy = ShellEnvironmentPolicy::default();
@@ -61,12 +62,39 @@ async fn run_cmd_output(
         .expect("sandboxed command should execute")
 }
 
-#[expect(clippy::expect_used)]
 async fn run_cmd_result_with_writable_roots(
     cmd: &[&str],
     writable_roots: &[PathBuf],
     timeout_ms: u64,
     use_bwrap_sandbox: bool,
+) -> Result<codex_core::exec::ExecToolCallOutput> {
+    let sandbox_policy = workspace_write_policy(writable_roots);
+    run_cmd_result_with_policy(cmd, &sandbox_policy, timeout_ms, use_bwrap_sandbox).await
+}
+
+fn workspace_write_policy(writable_roots: &[PathBuf]) -> SandboxPolicy {
+    SandboxPolicy::WorkspaceWrite {
+        writable_roots: writable_roots
+            .iter()
+            .map(|p| AbsolutePathBuf::try_from(p.as_path()).unwrap())
+            .collect(),
+        network_access: false,
+        allowed_loopback_ports: Vec::new(),
+        // Exclude tmp-related folders from writable roots because we need a
+        // folder that is writable by tests but that we intentionally disallow
+        // writing to in the sandbox.
+        exclude_tmpdir_env_var: true,
+        exclude_slash_tmp: true,
+        deny_read: Vec::new(),
+    }
+}
+
+#[expect(clippy::expect_used)]
+async fn run_cmd_result_with_policy(
+    cmd: &[&str],
+    sandbox_policy: &SandboxPolicy,
+    timeout_ms: u64,
+    use_bwrap_sandbox: bool,
 ) -> Result<codex_core::exec::ExecToolCallOutput> {
     let cwd = std::env::current_dir().expect("cwd should exist");
     let sandbox_cwd = cwd.clone();
@@ -81,24 +109,12 @@ async fn run_cmd_result_with_writable_roots(
         arg0: None,
     };
 
-    let sandbox_policy = SandboxPolicy::WorkspaceWrite {
-        writable_roots: writable_roots
-            .iter()
-            .map(|p| AbsolutePathBuf::try_from(p.as_path()).unwrap())
-            .collect(),
-        network_access: false,
-        // Exclude tmp-related folders from writable roots because we need a
-        // folder that is writable by tests but that we intentionally disallow
-        // writing to in the sandbox.
-        exclude_tmpdir_env_var: true,
-        exclude_slash_tmp: true,
-    };
     let sandbox_program = env!("CARGO_BIN_EXE_codex-linux-sandbox");
     let codex_linux_sandbox_exe = Some(PathBuf::from(sandbox_program));
 
     process_exec_tool_call(
         params,
-        &sandbox_policy,
+        sandbox_policy,
         sandbox_cwd.as_path(),
         &codex_linux_sandbox_exe,
         use_bwrap_sandbox,
@@ -407,3 +423,179 @@ async fn sandbox_blocks_dev_tcp_redirection() {
     // all images ship bash, so we guard against 127 as well.
     assert_network_blocked(&["bash", "-c", "echo hi > /dev/tcp/127.0.0.1/80"]).await;
 }
+
+/// Connects from the sandbox to an allowed and a non-allowed loopback listener
+/// and checks that only the allowed one is reachable. Returns `false` when the
+/// sandbox could not be applied on this host, so the caller can skip.
+async fn assert_only_allowed_loopback_port_connects(use_bwrap_sandbox: bool) -> bool {
+    let allowed = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let blocked = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let allowed_port = allowed.local_addr().unwrap().port();
+    let blocked_port = blocked.local_addr().unwrap().port();
+    let mut sandbox_policy = workspace_write_policy(&[]);
+    if let SandboxPolicy::WorkspaceWrite {
+        allowed_loopback_ports,
+        ..
+    } = &mut sandbox_policy
+    {
+        *allowed_loopback_ports = vec![allowed_port];
+    }
+
+    let connect = |port: u16| format!("exec 3<>/dev/tcp/127.0.0.1/{port}");
+    let result = run_cmd_result_with_policy(
+        &["bash", "-c", &connect(allowed_port)],
+        &sandbox_policy,
+        NETWORK_TIMEOUT_MS,
+        use_bwrap_sandbox,
+    )
+    .await;
+    let output = match result {
+        Ok(output) => output,
+        Err(CodexErr::Sandbox(SandboxErr::Denied { output })) => *output,
+        Err(err) => panic!("allowed port: {err:?}"),
+    };
+    if output.stderr.text.contains(LEGACY_SANDBOX_APPLY_ERR) {
+        // Landlock network rules need Linux 6.7 or newer.
+        return false;
+    }
+    assert_eq!(
+        output.exit_code, 0,
+        "allowed port should connect, stderr: {}",
+        output.stderr.text
+    );
+
+    let result = run_cmd_result_with_policy(
+        &["bash", "-c", &connect(blocked_port)],
+        &sandbox_policy,
+        NETWORK_TIMEOUT_MS,
+        use_bwrap_sandbox,
+    )
+    .await;
+    expect_denied(result, "non-allowed port");
+    true
+}
+
+#[tokio::test]
+async fn legacy_landlock_allows_only_allowed_loopback_ports() {
+    if !assert_only_allowed_loopback_port_connects(false).await {
+        eprintln!("skipping legacy loopback port test: Landlock network rules are unavailable");
+    }
+}
+
+#[tokio::test]
+async fn bwrap_allows_only_allowed_loopback_ports() {
+    if should_skip_bwrap_tests().await {
+        eprintln!("skipping bwrap test: vendored bwrap was not built in this environment");
+        return;
+    }
+    assert!(assert_only_allowed_loopback_port_connects(true).await);
+}
+
+const DENIED_CONTENTS: &str = "codex-deny-read-secret";
+
+/// A workspace-write policy for a temp dir holding a denied file, a denied
+/// directory, a `.env*` match and an unrelated `README.md`, all containing
+/// [`DENIED_CONTENTS`].
+#[expect(clippy::expect_used)]
+fn deny_read_fixture() -> (tempfile::TempDir, SandboxPolicy) {
+    let tmpdir = tempfile::tempdir().expect("tempdir");
+    let root = tmpdir.path();
+    let secret_file = root.join("secret.txt");
+    let secret_dir = root.join("secrets");
+    std::fs::create_dir(&secret_dir).expect("create secrets dir");
+    for path in [
+        &secret_file,
+        &secret_dir.join("key"),
+        &root.join(".env.local"),
+        &root.join("README.md"),
+    ] {
+        std::fs::write(path, DENIED_CONTENTS).expect("write test file");
+    }
+
+    let mut sandbox_policy = workspace_write_policy(&[root.to_path_buf()]);
+    sandbox_policy.set_deny_read(vec![
+        secret_file.to_string_lossy().to_string(),
+        secret_dir.to_string_lossy().to_string(),
+        root.join(".env*").to_string_lossy().to_string(),
+    ]);
+    (tmpdir, sandbox_policy)
+}
+
+async fn run_deny_read_cmd(
+    cmd: &[&str],
+    sandbox_policy: &SandboxPolicy,
+    use_bwrap_sandbox: bool,
+) -> codex_core::exec::ExecToolCallOutput {
+    match run_cmd_result_with_policy(cmd, sandbox_policy, LONG_TIMEOUT_MS, use_bwrap_sandbox).await
+    {
+        Ok(output) => output,
+        Err(CodexErr::Sandbox(SandboxErr::Denied { output })) => *output,
+        Err(err) => panic!("{cmd:?}: {err:?}"),
+    }
+}
+
+#[tokio::test]
+async fn legacy_landlock_refuses_deny_read_paths() {
+    // Landlock can only grant access, and a grant on a directory also covers
+    // files created in it later, so without bubblewrap the helper must refuse
+    // to run rather than leave denied paths readable.
+    let (tmpdir, sandbox_policy) = deny_read_fixture();
+    let path = tmpdir.path().join(".env.local");
+    let path = path.to_string_lossy();
+    let output = run_deny_read_cmd(&["cat", path.as_ref()], &sandbox_policy, false).await;
+
+    assert_ne!(output.exit_code, 0);
+    assert!(
+        output.stderr.text.contains(LEGACY_SANDBOX_APPLY_ERR),
+        "stderr: {}",
+        output.stderr.text
+    );
+    assert!(!output.stdout.text.contains(DENIED_CONTENTS));
+}
+
+/// Reads a denied file, a file in a denied directory and a `.env*` match from
+/// the sandbox and checks that none of their contents come back, while an
+/// unrelated file next to them and a file created next to them by the command
+/// stay readable.
+#[tokio::test]
+async fn bwrap_blocks_deny_read_paths() {
+    if should_skip_bwrap_tests().await {
+        eprintln!("skipping bwrap test: vendored bwrap was not built in this environment");
+        return;
+    }
+    let (tmpdir, sandbox_policy) = deny_read_fixture();
+    let root = tmpdir.path();
+
+    for denied in [
+        root.join("secret.txt"),
+        root.join("secrets/key"),
+        root.join(".env.local"),
+    ] {
+        let path = denied.to_string_lossy();
+        let output = run_deny_read_cmd(&["cat", path.as_ref()], &sandbox_policy, true).await;
+        // Bubblewrap masks denied files with an empty `/dev/null`, so `cat`
+        // may succeed; what matters is that the contents never come back.
+        assert!(
+            !output.stdout.text.contains(DENIED_CONTENTS),
+            "{path} should not be readable"
+        );
+    }
+
+    let path = root.join("README.md");
+    let path = path.to_string_lossy();
+    let output = run_deny_read_cmd(&["cat", path.as_ref()], &sandbox_policy, true).await;
+    assert_eq!(output.stdout.text, DENIED_CONTENTS);
+
+    let new_file = root.join("notes.txt");
+    let script = format!(
+        "printf created > {0} && cat {0}",
+        new_file.to_string_lossy()
+    );
+    let output = run_deny_read_cmd(&["bash", "-c", &script], &sandbox_policy, true).await;
+    assert_eq!(
+        (output.exit_code, output.stdout.text.as_str()),
+        (0, "created"),
+        "stderr: {}",
+        output.stderr.text
+    );
+}
@@ -0,0 +1,25 @@
+//! Command-line arguments shared by `codex mcp-server` and the standalone
+//! `codex-mcp-server` binary.
+
+use clap::Parser;
+
+use crate::transport::McpServerTransport;
+
+/// Run the Codex MCP server (stdio or streamable HTTP transport).
+#[derive(Parser, Debug)]
+pub struct Cli {
+    /// Transport endpoint URL. Supported values: `stdio://` (default),
+    /// `http://IP:PORT` (streamable HTTP served at `/mcp`).
+    #[arg(
+        long = "listen",
+        value_name = "URL",
+        default_value = McpServerTransport::DEFAULT_LISTEN_URL
+    )]
+    pub listen: McpServerTransport,
+
+    /// Environment variable holding the bearer token that HTTP clients must
+    /// send. Required when listening on a non-loopback address; without it,
+    /// only loopback `Host`/`Origin` headers are accepted.
+    #[arg(long = "bearer-token-env-var", value_name = "ENV_VAR")]
+    pub bearer_token_env_var: Option<String>,
+}
@@ -9,6 +9,8 @@ use crate::exec_approval::handle_exec_approval_request;
 use crate::outgoing_message::OutgoingMessageSender;
 use crate::outgoing_message::OutgoingNotificationMeta;
 use crate::patch_approval::handle_patch_approval_request;
+use crate::request_user_input::handle_request_user_input;
+use crate::resources::SessionResources;
 use codex_core::CodexThread;
 use codex_core::NewThread;
 use codex_core::ThreadManager;
@@ -63,6 +65,7 @@ pub async fn run_codex_tool_session(
     outgoing: Arc<OutgoingMessageSender>,
     thread_manager: Arc<ThreadManager>,
     running_requests_id_to_codex_uuid: Arc<Mutex<HashMap<RequestId, ThreadId>>>,
+    resources: Arc<SessionResources>,
 ) {
     let NewThread {
         thread_id,
@@ -136,6 +139,7 @@ pub async fn run_codex_tool_session(
         outgoing,
         id,
         running_requests_id_to_codex_uuid,
+        resources,
     )
     .await;
 }
@@ -147,6 +151,7 @@ pub async fn run_codex_tool_session_reply(
     request_id: RequestId,
     prompt: String,
     running_requests_id_to_codex_uuid: Arc<Mutex<HashMap<RequestId, ThreadId>>>,
+    resources: Arc<SessionResources>,
 ) {
     running_requests_id_to_codex_uuid
         .lock()
@@ -184,6 +189,7 @@ pub async fn run_codex_tool_session_reply(
         outgoing,
         request_id,
         running_requests_id_to_codex_uuid,
+        resources,
     )
     .await;
 }
@@ -194,6 +200,7 @@ async fn run_codex_tool_session_inner(
     outgoing: Arc<OutgoingMessageSender>,
     request_id: RequestId,
     running_requests_id_to_codex_uuid: Arc<Mutex<HashMap<RequestId, ThreadId>>>,
+    resources: Arc<SessionResources>,
 ) {
     let request_id_str = request_id.to_string();
 
@@ -211,6 +218,7 @@ async fn run_codex_tool_session_inner(
                         }),
                     )
                     .await;
+                resources.observe_event(thread_id, &event.msg).await;
 
                 match event.msg {
                     EventMsg::ExecApprovalRequest(ExecApprovalRequestEvent {
@@ -237,6 +245,18 @@ async fn run_codex_tool_session_inner(
                         .await;
                         continue;
                     }
+                    EventMsg::RequestUserInput(request) => {
+                        handle_request_user_input(
+                            request,
+                            outgoing.clone(),
+                            thread.clone(),
+                            request_id_str.clone(),
+                            event.id.clone(),
+                            thread_id,
+                        )
+                        .await;
+                        continue;
+                    }
                     EventMsg::PlanDelta(_) => {
                         continue;
                     }
@@ -305,7 +325,9 @@ async fn run_codex_tool_session_inner(
                     EventMsg::AgentReasoningDelta(_) => {
                         // TODO: think how we want to support this in the MCP
                     }
-                    EventMsg::McpStartupUpdate(_) | EventMsg::McpStartupComplete(_) => {
+                    EventMsg::McpStartupUpdate(_)
+                    | EventMsg::McpStartupComplete(_)
+                    | EventMsg::McpToolsChanged(_) => {
                         // Ignored in MCP tool runner.
                     }
                     EventMsg::AgentMessage(AgentMessageEvent { .. }) => {
@@ -352,10 +374,10 @@ async fn run_codex_tool_session_inner(
                     | EventMsg::UndoStarted(_)
                     | EventMsg::UndoCompleted(_)
                     | EventMsg::ExitedReviewMode(_)
-                    | EventMsg::RequestUserInput(_)
                     | EventMsg::DynamicToolCallRequest(_)
                     | EventMsg::ContextCompacted(_)
                     | EventMsg::ThreadRolledBack(_)
+                    | EventMsg::ProjectDocLoaded(_)
                     | EventMsg::CollabAgentSpawnBegin(_)
                     | EventMsg::CollabAgentSpawnEnd(_)
                     | EventMsg::CollabAgentInteractionBegin(_)
@@ -383,6 +405,7 @@ async fn run_codex_tool_session_inner(
                     Some(true),
                 );
                 outgoing.send_response(request_id.clone(), result).await;
+                resources.thread_ended(thread_id).await;
                 break;
             }
         }
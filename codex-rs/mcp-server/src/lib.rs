@@ -4,6 +4,7 @@
 use std::io::ErrorKind;
 use std::io::Result as IoResult;
 use std::path::PathBuf;
+use std::sync::Arc;
 
 use codex_common::CliConfigOverrides;
 use codex_core::config::Config;
@@ -17,29 +18,41 @@ use tokio::io::AsyncWriteExt;
 use tokio::io::BufReader;
 use tokio::io::{self};
 use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
 use tracing::debug;
 use tracing::error;
 use tracing::info;
 use tracing_subscriber::EnvFilter;
 
+mod cli;
 mod codex_tool_config;
 mod codex_tool_runner;
 mod exec_approval;
 pub(crate) mod message_processor;
 mod outgoing_message;
 mod patch_approval;
+mod prompts;
+mod request_user_input;
+mod resources;
+mod streamable_http;
+mod transport;
 
 use crate::message_processor::MessageProcessor;
 use crate::outgoing_message::OutgoingJsonRpcMessage;
 use crate::outgoing_message::OutgoingMessage;
 use crate::outgoing_message::OutgoingMessageSender;
+use crate::resources::ThreadResources;
+use crate::streamable_http::run_streamable_http_server;
 
+pub use crate::cli::Cli;
 pub use crate::codex_tool_config::CodexToolCallParam;
 pub use crate::codex_tool_config::CodexToolCallReplyParam;
 pub use crate::exec_approval::ExecApprovalElicitRequestParams;
 pub use crate::exec_approval::ExecApprovalResponse;
 pub use crate::patch_approval::PatchApprovalElicitRequestParams;
 pub use crate::patch_approval::PatchApprovalResponse;
+pub use crate::transport::McpServerTransport;
+pub use crate::transport::McpServerTransportParseError;
 
 /// Size of the bounded channels used to communicate between tasks. The value
 /// is a balance between throughput and memory usage – 128 messages should be
@@ -51,6 +64,26 @@ type IncomingMessage = JsonRpcMessage<ClientRequest, Value, ClientNotification>;
 pub async fn run_main(
     codex_linux_sandbox_exe: Option<PathBuf>,
     cli_config_overrides: CliConfigOverrides,
+) -> IoResult<()> {
+    run_main_with_transport(
+        codex_linux_sandbox_exe,
+        cli_config_overrides,
+        McpServerTransport::Stdio,
+        None,
+    )
+    .await
+}
+
+/// Runs the MCP server over `transport`. When serving streamable HTTP with
+/// `bearer_token_env_var` set, requests must carry
+/// `Authorization: Bearer <token>` with the token read from that variable. A
+/// token is required unless the server binds a loopback address, in which
+/// case requests must come from loopback `Host` and `Origin` headers.
+pub async fn run_main_with_transport(
+    codex_linux_sandbox_exe: Option<PathBuf>,
+    cli_config_overrides: CliConfigOverrides,
+    transport: McpServerTransport,
+    bearer_token_env_var: Option<String>,
 ) -> IoResult<()> {
     // Install a simple subscriber so `tracing` output is visible.  Users can
     // control the log level with `RUST_LOG`.
@@ -59,8 +92,83 @@ pub async fn run_main(
         .with_env_filter(EnvFilter::from_default_env())
         .init();
 
+    // Parse CLI overrides once and derive the base Config eagerly so later
+    // components do not need to work with raw TOML values.
+    let cli_kv_overrides = cli_config_overrides.parse_overrides().map_err(|e| {
+        std::io::Error::new(
+            ErrorKind::InvalidInput,
+            format!("error parsing -c overrides: {e}"),
+        )
+    })?;
+    let config = Config::load_with_cli_overrides(cli_kv_overrides)
+        .await
+        .map_err(|e| {
+            std::io::Error::new(ErrorKind::InvalidData, format!("error loading config: {e}"))
+        })?;
+    let config = Arc::new(config);
+
+    match transport {
+        McpServerTransport::Stdio => run_stdio(codex_linux_sandbox_exe, config).await,
+        McpServerTransport::StreamableHttp { bind_address } => {
+            let bearer_token = match bearer_token_env_var {
+                Some(env_var) => match std::env::var(&env_var) {
+                    Ok(token) if !token.is_empty() => Some(token),
+                    _ => {
+                        return Err(std::io::Error::new(
+                            ErrorKind::InvalidInput,
+                            format!("environment variable {env_var} must hold the bearer token"),
+                        ));
+                    }
+                },
+                None => None,
+            };
+            if bearer_token.is_none() && !bind_address.ip().is_loopback() {
+                return Err(std::io::Error::new(
+                    ErrorKind::InvalidInput,
+                    format!(
+                        "refusing to serve MCP on non-loopback address {bind_address} without a bearer token"
+                    ),
+                ));
+            }
+            run_streamable_http_server(bind_address, bearer_token, codex_linux_sandbox_exe, config)
+                .await
+        }
+    }
+}
+
+/// Spawns a [`MessageProcessor`] that handles `incoming_rx` until the channel
+/// closes, writing replies to `outgoing_tx`.
+pub(crate) fn spawn_message_processor(
+    mut incoming_rx: mpsc::Receiver<IncomingMessage>,
+    outgoing_tx: mpsc::UnboundedSender<OutgoingMessage>,
+    codex_linux_sandbox_exe: Option<PathBuf>,
+    config: Arc<Config>,
+    thread_resources: Arc<ThreadResources>,
+) -> JoinHandle<()> {
+    let outgoing_message_sender = OutgoingMessageSender::new(outgoing_tx);
+    let mut processor = MessageProcessor::new(
+        outgoing_message_sender,
+        codex_linux_sandbox_exe,
+        config,
+        thread_resources,
+    );
+    tokio::spawn(async move {
+        while let Some(msg) = incoming_rx.recv().await {
+            match msg {
+                JsonRpcMessage::Request(r) => processor.process_request(r).await,
+                JsonRpcMessage::Response(r) => processor.process_response(r).await,
+                JsonRpcMessage::Notification(n) => processor.process_notification(n).await,
+                JsonRpcMessage::Error(e) => processor.process_error(e),
+            }
+        }
+
+        info!("processor task exited (channel closed)");
+    })
+}
+
+async fn run_stdio(codex_linux_sandbox_exe: Option<PathBuf>, config: Arc<Config>) -> IoResult<()> {
     // Set up channels.
-    let (incoming_tx, mut incoming_rx) = mpsc::channel::<IncomingMessage>(CHANNEL_CAPACITY);
+    let (incoming_tx, incoming_rx) = mpsc::channel::<IncomingMessage>(CHANNEL_CAPACITY);
     let (outgoing_tx, mut outgoing_rx) = mpsc::unbounded_channel::<OutgoingMessage>();
 
     // Task: read from stdin, push to `incoming_tx`.
@@ -86,41 +194,15 @@ pub async fn run_main(
         }
     });
 
-    // Parse CLI overrides once and derive the base Config eagerly so later
-    // components do not need to work with raw TOML values.
-    let cli_kv_overrides = cli_config_overrides.parse_overrides().map_err(|e| {
-        std::io::Error::new(
-            ErrorKind::InvalidInput,
-            format!("error parsing -c overrides: {e}"),
-        )
-    })?;
-    let config = Config::load_with_cli_overrides(cli_kv_overrides)
-        .await
-        .map_err(|e| {
-            std::io::Error::new(ErrorKind::InvalidData, format!("error loading config: {e}"))
-        })?;
-
     // Task: process incoming messages.
-    let processor_handle = tokio::spawn({
-        let outgoing_message_sender = OutgoingMessageSender::new(outgoing_tx);
-        let mut processor = MessageProcessor::new(
-            outgoing_message_sender,
-            codex_linux_sandbox_exe,
-            std::sync::Arc::new(config),
-        );
-        async move {
-            while let Some(msg) = incoming_rx.recv().await {
-                match msg {
-                    JsonRpcMessage::Request(r) => processor.process_request(r).await,
-                    JsonRpcMessage::Response(r) => processor.process_response(r).await,
-                    JsonRpcMessage::Notification(n) => processor.process_notification(n).await,
-                    JsonRpcMessage::Error(e) => processor.process_error(e),
-                }
-            }
-
-            info!("processor task exited (channel closed)");
-        }
-    });
+    let thread_resources = Arc::new(ThreadResources::new(&config));
+    let processor_handle = spawn_message_processor(
+        incoming_rx,
+        outgoing_tx,
+        codex_linux_sandbox_exe,
+        config,
+        thread_resources,
+    );
 
     // Task: write outgoing messages to stdout.
     let stdout_writer_handle = tokio::spawn(async move {
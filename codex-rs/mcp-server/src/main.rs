@@ -1,10 +1,19 @@
+use clap::Parser;
 use codex_arg0::arg0_dispatch_or_else;
 use codex_common::CliConfigOverrides;
-use codex_mcp_server::run_main;
+use codex_mcp_server::Cli;
+use codex_mcp_server::run_main_with_transport;
 
 fn main() -> anyhow::Result<()> {
     arg0_dispatch_or_else(|codex_linux_sandbox_exe| async move {
-        run_main(codex_linux_sandbox_exe, CliConfigOverrides::default()).await?;
+        let args = Cli::parse();
+        run_main_with_transport(
+            codex_linux_sandbox_exe,
+            CliConfigOverrides::default(),
+            args.listen,
+            args.bearer_token_env_var,
+        )
+        .await?;
         Ok(())
     })
 }
@@ -21,7 +21,9 @@ use rmcp::model::JsonRpcError;
 use rmcp::model::JsonRpcNotification;
 use rmcp::model::JsonRpcRequest;
 use rmcp::model::JsonRpcResponse;
+use rmcp::model::PromptsCapability;
 use rmcp::model::RequestId;
+use rmcp::model::ResourcesCapability;
 use rmcp::model::ServerCapabilities;
 use rmcp::model::ToolsCapability;
 use serde_json::json;
@@ -34,6 +36,9 @@ use crate::codex_tool_config::CodexToolCallReplyParam;
 use crate::codex_tool_config::create_tool_for_codex_tool_call_param;
 use crate::codex_tool_config::create_tool_for_codex_tool_call_reply_param;
 use crate::outgoing_message::OutgoingMessageSender;
+use crate::prompts::SessionPrompts;
+use crate::resources::SessionResources;
+use crate::resources::ThreadResources;
 
 pub(crate) struct MessageProcessor {
     outgoing: Arc<OutgoingMessageSender>,
@@ -41,6 +46,8 @@ pub(crate) struct MessageProcessor {
     codex_linux_sandbox_exe: Option<PathBuf>,
     thread_manager: Arc<ThreadManager>,
     running_requests_id_to_codex_uuid: Arc<Mutex<HashMap<RequestId, ThreadId>>>,
+    resources: Arc<SessionResources>,
+    prompts: SessionPrompts,
 }
 
 impl MessageProcessor {
@@ -50,6 +57,7 @@ impl MessageProcessor {
         outgoing: OutgoingMessageSender,
         codex_linux_sandbox_exe: Option<PathBuf>,
         config: Arc<Config>,
+        thread_resources: Arc<ThreadResources>,
     ) -> Self {
         let outgoing = Arc::new(outgoing);
         let auth_manager = AuthManager::shared(
@@ -62,12 +70,16 @@ impl MessageProcessor {
             auth_manager,
             SessionSource::Mcp,
         ));
+        let resources = Arc::new(SessionResources::new(thread_resources, outgoing.clone()));
+        let prompts = SessionPrompts::new(&config, thread_manager.clone());
         Self {
             outgoing,
             initialized: false,
             codex_linux_sandbox_exe,
             thread_manager,
             running_requests_id_to_codex_uuid: Arc::new(Mutex::new(HashMap::new())),
+            resources,
+            prompts,
         }
     }
 
@@ -83,25 +95,26 @@ impl MessageProcessor {
                 self.handle_ping(request_id).await;
             }
             ClientRequest::ListResourcesRequest(params) => {
-                self.handle_list_resources(params.params);
+                self.handle_list_resources(request_id, params.params).await;
             }
             ClientRequest::ListResourceTemplatesRequest(params) => {
-                self.handle_list_resource_templates(params.params);
+                self.handle_list_resource_templates(request_id, params.params)
+                    .await;
             }
             ClientRequest::ReadResourceRequest(params) => {
-                self.handle_read_resource(params.params);
+                self.handle_read_resource(request_id, params.params).await;
             }
             ClientRequest::SubscribeRequest(params) => {
-                self.handle_subscribe(params.params);
+                self.handle_subscribe(request_id, params.params).await;
             }
             ClientRequest::UnsubscribeRequest(params) => {
-                self.handle_unsubscribe(params.params);
+                self.handle_unsubscribe(request_id, params.params).await;
             }
             ClientRequest::ListPromptsRequest(params) => {
-                self.handle_list_prompts(params.params);
+                self.handle_list_prompts(request_id, params.params).await;
             }
             ClientRequest::GetPromptRequest(params) => {
-                self.handle_get_prompt(params.params);
+                self.handle_get_prompt(request_id, params.params).await;
             }
             ClientRequest::ListToolsRequest(params) => {
                 self.handle_list_tools(request_id, params.params).await;
@@ -222,6 +235,13 @@ impl MessageProcessor {
                 tools: Some(ToolsCapability {
                     list_changed: Some(true),
                 }),
+                resources: Some(ResourcesCapability {
+                    subscribe: Some(true),
+                    list_changed: Some(false),
+                }),
+                prompts: Some(PromptsCapability {
+                    list_changed: Some(false),
+                }),
                 ..Default::default()
             },
             instructions: None,
@@ -256,32 +276,76 @@ impl MessageProcessor {
         self.outgoing.send_response(id, json!({})).await;
     }
 
-    fn handle_list_resources(&self, params: Option<rmcp::model::PaginatedRequestParam>) {
+    async fn handle_list_resources(
+        &self,
+        id: RequestId,
+        params: Option<rmcp::model::PaginatedRequestParam>,
+    ) {
         tracing::info!("resources/list -> params: {:?}", params);
+        let cursor = params.and_then(|params| params.cursor);
+        match self.resources.list_resources(cursor).await {
+            Ok(result) => self.outgoing.send_response(id, result).await,
+            Err(err) => self.outgoing.send_error(id, err).await,
+        }
     }
 
-    fn handle_list_resource_templates(&self, params: Option<rmcp::model::PaginatedRequestParam>) {
+    async fn handle_list_resource_templates(
+        &self,
+        id: RequestId,
+        params: Option<rmcp::model::PaginatedRequestParam>,
+    ) {
         tracing::info!("resources/templates/list -> params: {:?}", params);
+        let result = self.resources.list_resource_templates();
+        self.outgoing.send_response(id, result).await;
     }
 
-    fn handle_read_resource(&self, params: rmcp::model::ReadResourceRequestParam) {
+    async fn handle_read_resource(
+        &self,
+        id: RequestId,
+        params: rmcp::model::ReadResourceRequestParam,
+    ) {
         tracing::info!("resources/read -> params: {:?}", params);
+        match self.resources.read_resource(&params.uri).await {
+            Ok(result) => self.outgoing.send_response(id, result).await,
+            Err(err) => self.outgoing.send_error(id, err).await,
+        }
     }
 
-    fn handle_subscribe(&self, params: rmcp::model::SubscribeRequestParam) {
+    async fn handle_subscribe(&self, id: RequestId, params: rmcp::model::SubscribeRequestParam) {
         tracing::info!("resources/subscribe -> params: {:?}", params);
+        match self.resources.subscribe(&params.uri) {
+            Ok(()) => self.outgoing.send_response(id, json!({})).await,
+            Err(err) => self.outgoing.send_error(id, err).await,
+        }
     }
 
-    fn handle_unsubscribe(&self, params: rmcp::model::UnsubscribeRequestParam) {
+    async fn handle_unsubscribe(
+        &self,
+        id: RequestId,
+        params: rmcp::model::UnsubscribeRequestParam,
+    ) {
         tracing::info!("resources/unsubscribe -> params: {:?}", params);
+        self.resources.unsubscribe(&params.uri);
+        self.outgoing.send_response(id, json!({})).await;
     }
 
-    fn handle_list_prompts(&self, params: Option<rmcp::model::PaginatedRequestParam>) {
+    async fn handle_list_prompts(
+        &self,
+        id: RequestId,
+        params: Option<rmcp::model::PaginatedRequestParam>,
+    ) {
         tracing::info!("prompts/list -> params: {:?}", params);
+        let result = self.prompts.list_prompts().await;
+        self.outgoing.send_response(id, result).await;
     }
 
-    fn handle_get_prompt(&self, params: rmcp::model::GetPromptRequestParam) {
+    async fn handle_get_prompt(&self, id: RequestId, params: rmcp::model::GetPromptRequestParam) {
         tracing::info!("prompts/get -> params: {:?}", params);
+        let rmcp::model::GetPromptRequestParam { name, arguments } = params;
+        match self.prompts.get_prompt(&name, arguments).await {
+            Ok(result) => self.outgoing.send_response(id, result).await,
+            Err(err) => self.outgoing.send_error(id, err).await,
+        }
     }
 
     async fn handle_list_tools(
@@ -381,6 +445,7 @@ impl MessageProcessor {
         let outgoing = self.outgoing.clone();
         let thread_manager = self.thread_manager.clone();
         let running_requests_id_to_codex_uuid = self.running_requests_id_to_codex_uuid.clone();
+        let resources = self.resources.clone();
 
         // Spawn an async task to handle the Codex session so that we do not
         // block the synchronous message-processing loop.
@@ -393,6 +458,7 @@ impl MessageProcessor {
                 outgoing,
                 thread_manager,
                 running_requests_id_to_codex_uuid,
+                resources,
             )
             .await;
         });
@@ -481,6 +547,7 @@ impl MessageProcessor {
         tokio::spawn({
             let outgoing = outgoing.clone();
             let running_requests_id_to_codex_uuid = running_requests_id_to_codex_uuid.clone();
+            let resources = self.resources.clone();
 
             async move {
                 crate::codex_tool_runner::run_codex_tool_session_reply(
@@ -490,6 +557,7 @@ impl MessageProcessor {
                     request_id,
                     prompt,
                     running_requests_id_to_codex_uuid,
+                    resources,
                 )
                 .await;
             }
@@ -0,0 +1,253 @@
+//! MCP prompts exposed by `codex mcp-server`.
+//!
+//! Custom prompts from `$CODEX_HOME/prompts` are listed as `prompts:<name>`
+//! and enabled skills for the server's working directory as `skills:<name>`.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use codex_core::ThreadManager;
+use codex_core::config::Config;
+use codex_core::custom_prompts::PROMPT_ARGUMENTS_PLACEHOLDER;
+use codex_core::custom_prompts::discover_prompts_in;
+use codex_core::custom_prompts::expand_prompt_arguments;
+use codex_core::custom_prompts::prompt_argument_names;
+use codex_core::skills::SkillMetadata;
+use codex_protocol::custom_prompts::CustomPrompt;
+use codex_protocol::custom_prompts::PROMPTS_CMD_PREFIX;
+use rmcp::model::ErrorData;
+use rmcp::model::GetPromptResult;
+use rmcp::model::JsonObject;
+use rmcp::model::ListPromptsResult;
+use rmcp::model::Prompt;
+use rmcp::model::PromptArgument;
+use rmcp::model::PromptMessage;
+use rmcp::model::PromptMessageRole;
+
+const SKILLS_PROMPT_PREFIX: &str = "skills";
+
+pub(crate) struct SessionPrompts {
+    prompts_dir: PathBuf,
+    cwd: PathBuf,
+    thread_manager: Arc<ThreadManager>,
+}
+
+impl SessionPrompts {
+    pub(crate) fn new(config: &Config, thread_manager: Arc<ThreadManager>) -> Self {
+        Self {
+            prompts_dir: config.codex_home.join("prompts"),
+            cwd: config.cwd.clone(),
+            thread_manager,
+        }
+    }
+
+    pub(crate) async fn list_prompts(&self) -> ListPromptsResult {
+        let mut prompts: Vec<Prompt> = discover_prompts_in(&self.prompts_dir)
+            .await
+            .iter()
+            .map(custom_prompt_to_mcp)
+            .collect();
+        prompts.extend(self.skills().await.iter().map(skill_to_mcp));
+        ListPromptsResult::with_all_items(prompts)
+    }
+
+    pub(crate) async fn get_prompt(
+        &self,
+        name: &str,
+        arguments: Option<JsonObject>,
+    ) -> Result<GetPromptResult, ErrorData> {
+        let arguments = arguments.unwrap_or_default();
+        if let Some(prompt_name) = name
+            .strip_prefix(PROMPTS_CMD_PREFIX)
+            .and_then(|rest| rest.strip_prefix(':'))
+        {
+            let prompt = discover_prompts_in(&self.prompts_dir)
+                .await
+                .into_iter()
+                .find(|prompt| prompt.name == prompt_name)
+                .ok_or_else(|| prompt_not_found(name))?;
+            let text = expand_prompt(name, &prompt.content, &arguments)?;
+            return Ok(GetPromptResult {
+                description: prompt.description,
+                messages: vec![PromptMessage::new_text(PromptMessageRole::User, text)],
+            });
+        }
+
+        if let Some(skill_name) = name
+            .strip_prefix(SKILLS_PROMPT_PREFIX)
+            .and_then(|rest| rest.strip_prefix(':'))
+        {
+            let skill = self
+                .skills()
+                .await
+                .into_iter()
+                .find(|skill| skill.name == skill_name)
+                .ok_or_else(|| prompt_not_found(name))?;
+            let contents = tokio::fs::read_to_string(&skill.path)
+                .await
+                .map_err(|err| {
+                    ErrorData::internal_error(
+                        format!("failed to read skill {}: {err}", skill.path.display()),
+                        None,
+                    )
+                })?;
+            let mut text = format!(
+                "<skill>\n<name>{}</name>\n<path>{}</path>\n{contents}\n</skill>",
+                skill.name,
+                skill.path.display()
+            );
+            if let Some(serde_json::Value::String(request)) =
+                arguments.get(PROMPT_ARGUMENTS_PLACEHOLDER)
+                && !request.is_empty()
+            {
+                text.push_str("\n\n");
+                text.push_str(request);
+            }
+            return Ok(GetPromptResult {
+                description: Some(skill.description),
+                messages: vec![PromptMessage::new_text(PromptMessageRole::User, text)],
+            });
+        }
+
+        Err(prompt_not_found(name))
+    }
+
+    async fn skills(&self) -> Vec<SkillMetadata> {
+        self.thread_manager
+            .skills_manager()
+            .skills_for_cwd(&self.cwd, false)
+            .await
+            .enabled_skills()
+    }
+}
+
+fn custom_prompt_to_mcp(prompt: &CustomPrompt) -> Prompt {
+    let named = prompt_argument_names(&prompt.content);
+    let arguments: Vec<PromptArgument> =
+        if named.is_empty() {
+            vec![PromptArgument {
+                name: PROMPT_ARGUMENTS_PLACEHOLDER.to_string(),
+                title: None,
+                description: Some(prompt.argument_hint.clone().unwrap_or_else(|| {
+                    "Positional arguments for $1..$9 and $ARGUMENTS".to_string()
+                })),
+                required: Some(false),
+            }]
+        } else {
+            named
+                .into_iter()
+                .map(|name| PromptArgument {
+                    name,
+                    title: None,
+                    description: None,
+                    required: Some(true),
+                })
+                .collect()
+        };
+    Prompt {
+        name: format!("{PROMPTS_CMD_PREFIX}:{}", prompt.name),
+        title: None,
+        description: prompt.description.clone(),
+        arguments: Some(arguments),
+        icons: None,
+        meta: None,
+    }
+}
+
+fn skill_to_mcp(skill: &SkillMetadata) -> Prompt {
+    Prompt {
+        name: format!("{SKILLS_PROMPT_PREFIX}:{}", skill.name),
+        title: None,
+        description: Some(
+            skill
+                .short_description
+                .clone()
+                .unwrap_or_else(|| skill.description.clone()),
+        ),
+        arguments: Some(vec![PromptArgument {
+            name: PROMPT_ARGUMENTS_PLACEHOLDER.to_string(),
+            title: None,
+            description: Some("Request to append after the skill instructions".to_string()),
+            required: Some(false),
+        }]),
+        icons: None,
+        meta: None,
+    }
+}
+
+fn prompt_not_found(name: &str) -> ErrorData {
+    ErrorData::invalid_params(format!("unknown prompt: {name}"), None)
+}
+
+/// Expands a custom prompt with the same rules as `/prompts:<name>` in the TUI: named
+/// `$NAME` arguments, or `$1..$9` and `$ARGUMENTS` from the shell-quoted `ARGUMENTS` value.
+fn expand_prompt(name: &str, content: &str, arguments: &JsonObject) -> Result<String, ErrorData> {
+    let mut named = HashMap::new();
+    let mut positional_raw = String::new();
+    for (key, value) in arguments {
+        let value = match value {
+            serde_json::Value::String(value) => value.clone(),
+            other => other.to_string(),
+        };
+        if key == PROMPT_ARGUMENTS_PLACEHOLDER {
+            positional_raw = value;
+        } else {
+            named.insert(key.clone(), value);
+        }
+    }
+    let positional = shlex::split(&positional_raw).unwrap_or_else(|| {
+        positional_raw
+            .split_whitespace()
+            .map(str::to_string)
+            .collect()
+    });
+    expand_prompt_arguments(content, &named, &positional).map_err(|missing| {
+        ErrorData::invalid_params(
+            format!(
+                "missing required arguments for {name}: {}",
+                missing.join(", ")
+            ),
+            None,
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    fn object(value: serde_json::Value) -> JsonObject {
+        match value {
+            serde_json::Value::Object(map) => map,
+            _ => JsonObject::new(),
+        }
+    }
+
+    #[test]
+    fn expands_positional_arguments_from_shell_words() {
+        let arguments = object(json!({ "ARGUMENTS": "first \"second arg\"" }));
+        let expanded = expand_prompt(
+            "prompts:review",
+            "Check $1 and $2; all: $ARGUMENTS",
+            &arguments,
+        );
+        assert_eq!(
+            expanded.ok(),
+            Some("Check first and second arg; all: first second arg".to_string())
+        );
+    }
+
+    #[test]
+    fn named_prompts_require_every_argument() {
+        let arguments = object(json!({ "FILE": "src/lib.rs" }));
+        let err = expand_prompt("prompts:review", "Check $FILE for $USER", &arguments)
+            .expect_err("USER is missing");
+        assert_eq!(
+            err.message,
+            "missing required arguments for prompts:review: USER"
+        );
+    }
+}
@@ -0,0 +1,247 @@
+//! Forwards `request_user_input` prompts (including MCP tool approvals) to the
+//! client as `elicitation/create` requests.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use codex_core::CodexThread;
+use codex_core::protocol::Op;
+use codex_protocol::ThreadId;
+use codex_protocol::request_user_input::RequestUserInputAnswer;
+use codex_protocol::request_user_input::RequestUserInputEvent;
+use codex_protocol::request_user_input::RequestUserInputQuestion;
+use codex_protocol::request_user_input::RequestUserInputResponse;
+use rmcp::model::ElicitationAction;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value;
+use serde_json::json;
+use tracing::error;
+
+use crate::outgoing_message::OutgoingMessageSender;
+
+/// Conforms to the MCP elicitation request params shape, so it can be used as
+/// the `params` field of an `elicitation/create` request.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct RequestUserInputElicitRequestParams {
+    pub message: String,
+
+    #[serde(rename = "requestedSchema")]
+    pub requested_schema: Value,
+
+    #[serde(rename = "threadId")]
+    pub thread_id: ThreadId,
+    pub codex_elicitation: String,
+    pub codex_mcp_tool_call_id: String,
+    pub codex_event_id: String,
+    pub codex_call_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct RequestUserInputElicitResult {
+    action: ElicitationAction,
+    #[serde(default)]
+    content: Option<serde_json::Map<String, Value>>,
+}
+
+pub(crate) async fn handle_request_user_input(
+    event: RequestUserInputEvent,
+    outgoing: Arc<OutgoingMessageSender>,
+    codex: Arc<CodexThread>,
+    tool_call_id: String,
+    event_id: String,
+    thread_id: ThreadId,
+) {
+    let message = event
+        .questions
+        .iter()
+        .map(|question| question.question.as_str())
+        .collect::<Vec<_>>()
+        .join("\n");
+    let params = RequestUserInputElicitRequestParams {
+        message,
+        requested_schema: requested_schema(&event.questions),
+        thread_id,
+        codex_elicitation: "request-user-input".to_string(),
+        codex_mcp_tool_call_id: tool_call_id,
+        codex_event_id: event_id,
+        codex_call_id: event.call_id,
+    };
+    let turn_id = event.turn_id;
+    let params_json = match serde_json::to_value(&params) {
+        Ok(value) => value,
+        Err(err) => {
+            error!("Failed to serialize RequestUserInputElicitRequestParams: {err}");
+            submit_answer(&codex, turn_id, no_answers()).await;
+            return;
+        }
+    };
+
+    let on_response = outgoing
+        .send_request("elicitation/create", Some(params_json))
+        .await;
+
+    // Listen for the response on a separate task so we don't block the main agent loop.
+    tokio::spawn(async move {
+        let response = match on_response.await {
+            Ok(value) => response_from_elicit_result(value),
+            Err(err) => {
+                error!("request failed: {err:?}");
+                no_answers()
+            }
+        };
+        submit_answer(&codex, turn_id, response).await;
+    });
+}
+
+/// One string property per question, constrained to the option labels when
+/// the question offers options.
+fn requested_schema(questions: &[RequestUserInputQuestion]) -> Value {
+    let mut properties = serde_json::Map::new();
+    for question in questions {
+        let mut property = json!({
+            "type": "string",
+            "title": question.header,
+            "description": question.question,
+        });
+        if let Some(options) = &question.options
+            && !question.is_other
+        {
+            property["enum"] = json!(
+                options
+                    .iter()
+                    .map(|option| option.label.as_str())
+                    .collect::<Vec<_>>()
+            );
+        }
+        properties.insert(question.id.clone(), property);
+    }
+    let required: Vec<&str> = questions
+        .iter()
+        .map(|question| question.id.as_str())
+        .collect();
+    json!({
+        "type": "object",
+        "properties": properties,
+        "required": required,
+    })
+}
+
+/// Anything other than an accepted elicitation yields no answers, which core
+/// treats as the user cancelling.
+fn response_from_elicit_result(value: Value) -> RequestUserInputResponse {
+    let result = match serde_json::from_value::<RequestUserInputElicitResult>(value) {
+        Ok(result) => result,
+        Err(err) => {
+            error!("failed to deserialize elicitation result: {err}");
+            return no_answers();
+        }
+    };
+    if result.action != ElicitationAction::Accept {
+        return no_answers();
+    }
+    let answers: HashMap<String, RequestUserInputAnswer> = result
+        .content
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|(id, value)| {
+            let answer = match value {
+                Value::String(answer) => answer,
+                Value::Null => return None,
+                other => other.to_string(),
+            };
+            Some((
+                id,
+                RequestUserInputAnswer {
+                    answers: vec![answer],
+                },
+            ))
+        })
+        .collect();
+    RequestUserInputResponse { answers }
+}
+
+fn no_answers() -> RequestUserInputResponse {
+    RequestUserInputResponse {
+        answers: HashMap::new(),
+    }
+}
+
+async fn submit_answer(codex: &CodexThread, turn_id: String, response: RequestUserInputResponse) {
+    if let Err(err) = codex
+        .submit(Op::UserInputAnswer {
+            id: turn_id,
+            response,
+        })
+        .await
+    {
+        error!("failed to submit UserInputAnswer: {err}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::request_user_input::RequestUserInputQuestionOption;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn schema_restricts_answers_to_option_labels() {
+        let questions = vec![RequestUserInputQuestion {
+            id: "approve".to_string(),
+            header: "Approve MCP tool call?".to_string(),
+            question: "Allow this action?".to_string(),
+            is_other: false,
+            is_secret: false,
+            options: Some(vec![
+                RequestUserInputQuestionOption {
+                    label: "Accept".to_string(),
+                    description: "Run the tool and continue.".to_string(),
+                },
+                RequestUserInputQuestionOption {
+                    label: "Decline".to_string(),
+                    description: "Decline this tool call and continue.".to_string(),
+                },
+            ]),
+        }];
+
+        assert_eq!(
+            requested_schema(&questions),
+            json!({
+                "type": "object",
+                "properties": {
+                    "approve": {
+                        "type": "string",
+                        "title": "Approve MCP tool call?",
+                        "description": "Allow this action?",
+                        "enum": ["Accept", "Decline"],
+                    },
+                },
+                "required": ["approve"],
+            })
+        );
+    }
+
+    #[test]
+    fn only_accepted_elicitations_produce_answers() {
+        let accepted = response_from_elicit_result(json!({
+            "action": "accept",
+            "content": {"approve": "Accept"},
+        }));
+        assert_eq!(
+            accepted.answers,
+            HashMap::from([(
+                "approve".to_string(),
+                RequestUserInputAnswer {
+                    answers: vec!["Accept".to_string()],
+                },
+            )])
+        );
+
+        let declined = response_from_elicit_result(json!({
+            "action": "decline",
+            "content": {"approve": "Accept"},
+        }));
+        assert_eq!(declined, no_answers());
+    }
+}
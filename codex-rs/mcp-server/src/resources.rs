@@ -0,0 +1,740 @@
+//! MCP resources exposed by `codex mcp-server`.
+//!
+//! Every thread recorded under `$CODEX_HOME/sessions` for the server's project
+//! (the git repository containing its working directory, or the working
+//! directory itself) is exposed as `codex://thread/<id>`, whose contents are
+//! the rollout JSONL. Threads that are running inside this server additionally
+//! expose `codex://thread/<id>/diff` with the aggregated diff of the current
+//! turn. Each client may subscribe to either URI and receives
+//! `notifications/resources/updated` whenever the running thread changes.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fmt;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+use std::sync::PoisonError;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+
+use codex_core::RolloutRecorder;
+use codex_core::ThreadItem;
+use codex_core::ThreadSortKey;
+use codex_core::config::Config;
+use codex_core::find_thread_path_by_id_str;
+use codex_core::git_info::get_git_repo_root;
+use codex_core::parse_cursor;
+use codex_core::path_utils::normalize_for_path_comparison;
+use codex_core::protocol::EventMsg;
+use codex_core::read_session_meta_line;
+use codex_protocol::ThreadId;
+use rmcp::model::AnnotateAble;
+use rmcp::model::ErrorData;
+use rmcp::model::ListResourceTemplatesResult;
+use rmcp::model::ListResourcesResult;
+use rmcp::model::RawResource;
+use rmcp::model::RawResourceTemplate;
+use rmcp::model::ReadResourceResult;
+use rmcp::model::Resource;
+use rmcp::model::ResourceContents;
+use serde_json::json;
+
+use crate::outgoing_message::OutgoingMessageSender;
+use crate::outgoing_message::OutgoingNotification;
+
+const THREAD_URI_PREFIX: &str = "codex://thread/";
+const DIFF_URI_SUFFIX: &str = "/diff";
+const THREAD_LIST_PAGE_SIZE: usize = 50;
+/// Upper bound on the rollout store pages a single `resources/list` call reads.
+const MAX_STORE_PAGES_PER_LIST: usize = 4;
+const ROLLOUT_MIME_TYPE: &str = "application/x-ndjson";
+const DIFF_MIME_TYPE: &str = "text/x-diff";
+const RESOURCE_UPDATED_METHOD: &str = "notifications/resources/updated";
+
+/// A parsed `codex://` resource URI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CodexResourceUri {
+    /// `codex://thread/<id>`: the rollout of a thread.
+    Thread(ThreadId),
+    /// `codex://thread/<id>/diff`: the current diff of a running thread.
+    ThreadDiff(ThreadId),
+}
+
+impl CodexResourceUri {
+    pub(crate) fn parse(uri: &str) -> Option<Self> {
+        let rest = uri.strip_prefix(THREAD_URI_PREFIX)?;
+        match rest.strip_suffix(DIFF_URI_SUFFIX) {
+            Some(id) => ThreadId::from_string(id).ok().map(Self::ThreadDiff),
+            None => ThreadId::from_string(rest).ok().map(Self::Thread),
+        }
+    }
+}
+
+impl fmt::Display for CodexResourceUri {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Thread(id) => write!(f, "{THREAD_URI_PREFIX}{id}"),
+            Self::ThreadDiff(id) => write!(f, "{THREAD_URI_PREFIX}{id}{DIFF_URI_SUFFIX}"),
+        }
+    }
+}
+
+/// State tracked for threads running inside this MCP server.
+#[derive(Debug, Clone)]
+struct RunningThread {
+    /// The client session whose `codex` tool call started the thread.
+    owner: SubscriberId,
+    unified_diff: Option<String>,
+}
+
+type SubscriberId = u64;
+
+/// A connected client and the resources it subscribed to.
+struct Subscriber {
+    outgoing: Arc<OutgoingMessageSender>,
+    uris: HashSet<CodexResourceUri>,
+}
+
+/// Resource state shared by every client session of the server.
+pub(crate) struct ThreadResources {
+    codex_home: PathBuf,
+    /// Only threads started at or below this directory are exposed.
+    project_root: PathBuf,
+    model_provider_id: String,
+    running_threads: Mutex<HashMap<ThreadId, RunningThread>>,
+    subscribers: Mutex<HashMap<SubscriberId, Subscriber>>,
+    next_subscriber_id: AtomicU64,
+}
+
+impl ThreadResources {
+    pub(crate) fn new(config: &Config) -> Self {
+        let project_root = get_git_repo_root(&config.cwd).unwrap_or_else(|| config.cwd.clone());
+        Self::with_project_root(
+            config.codex_home.clone(),
+            project_root,
+            config.model_provider_id.clone(),
+        )
+    }
+
+    fn with_project_root(
+        codex_home: PathBuf,
+        project_root: PathBuf,
+        model_provider_id: String,
+    ) -> Self {
+        Self {
+            codex_home,
+            project_root: normalize_for_path_comparison(&project_root).unwrap_or(project_root),
+            model_provider_id,
+            running_threads: Mutex::new(HashMap::new()),
+            subscribers: Mutex::new(HashMap::new()),
+            next_subscriber_id: AtomicU64::new(0),
+        }
+    }
+
+    fn in_project(&self, cwd: &Path) -> bool {
+        normalize_for_path_comparison(cwd)
+            .unwrap_or_else(|_| cwd.to_path_buf())
+            .starts_with(&self.project_root)
+    }
+
+    fn running_threads(&self) -> MutexGuard<'_, HashMap<ThreadId, RunningThread>> {
+        self.running_threads
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn subscribers(&self) -> MutexGuard<'_, HashMap<SubscriberId, Subscriber>> {
+        self.subscribers
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Sends `notifications/resources/updated` for each of `uris` to the
+    /// clients subscribed to it.
+    async fn notify_updated(&self, uris: &[CodexResourceUri]) {
+        let notifications: Vec<_> = {
+            let subscribers = self.subscribers();
+            uris.iter()
+                .flat_map(|uri| {
+                    subscribers
+                        .values()
+                        .filter(|subscriber| subscriber.uris.contains(uri))
+                        .map(move |subscriber| (subscriber.outgoing.clone(), *uri))
+                })
+                .collect()
+        };
+        for (outgoing, uri) in notifications {
+            outgoing
+                .send_notification(OutgoingNotification {
+                    method: RESOURCE_UPDATED_METHOD.to_string(),
+                    params: Some(json!({ "uri": uri.to_string() })),
+                })
+                .await;
+        }
+    }
+}
+
+/// One client session's view of the server's [`ThreadResources`]. Dropping it
+/// drops the session's subscriptions and forgets the threads it was running.
+pub(crate) struct SessionResources {
+    shared: Arc<ThreadResources>,
+    subscriber_id: SubscriberId,
+}
+
+impl SessionResources {
+    pub(crate) fn new(shared: Arc<ThreadResources>, outgoing: Arc<OutgoingMessageSender>) -> Self {
+        let subscriber_id = shared.next_subscriber_id.fetch_add(1, Ordering::Relaxed);
+        shared.subscribers().insert(
+            subscriber_id,
+            Subscriber {
+                outgoing,
+                uris: HashSet::new(),
+            },
+        );
+        Self {
+            shared,
+            subscriber_id,
+        }
+    }
+
+    /// Lists the project's recorded threads newest first. Threads from other
+    /// projects share the rollout store, so each call reads at most
+    /// [`MAX_STORE_PAGES_PER_LIST`] pages of it and returns a cursor for the
+    /// rest; a page may therefore hold fewer than [`THREAD_LIST_PAGE_SIZE`]
+    /// threads. Running threads also list their diff resource on the first
+    /// page.
+    pub(crate) async fn list_resources(
+        &self,
+        cursor: Option<String>,
+    ) -> Result<ListResourcesResult, ErrorData> {
+        let shared = &self.shared;
+        let cursor = match cursor.as_deref() {
+            Some(cursor_str) => Some(parse_cursor(cursor_str).ok_or_else(|| {
+                ErrorData::invalid_params(format!("invalid cursor: {cursor_str}"), None)
+            })?),
+            None => None,
+        };
+
+        let mut resources = Vec::new();
+        if cursor.is_none() {
+            let mut running_ids: Vec<_> = shared.running_threads().keys().copied().collect();
+            running_ids.sort_by_key(ToString::to_string);
+            resources.extend(running_ids.into_iter().map(thread_diff_resource));
+        }
+
+        let provider_filter = [shared.model_provider_id.clone()];
+        let mut cursor = cursor;
+        let mut project_threads = 0;
+        let mut store_pages = 0;
+        let next_cursor = loop {
+            let page = RolloutRecorder::list_threads(
+                &shared.codex_home,
+                THREAD_LIST_PAGE_SIZE,
+                cursor.as_ref(),
+                ThreadSortKey::UpdatedAt,
+                &[],
+                Some(&provider_filter),
+                shared.model_provider_id.as_str(),
+            )
+            .await
+            .map_err(|err| {
+                ErrorData::internal_error(format!("failed to list threads: {err}"), None)
+            })?;
+            store_pages += 1;
+
+            for item in page.items {
+                if !item
+                    .cwd
+                    .as_deref()
+                    .is_some_and(|cwd| shared.in_project(cwd))
+                {
+                    continue;
+                }
+                let size = tokio::fs::metadata(&item.path)
+                    .await
+                    .ok()
+                    .and_then(|metadata| u32::try_from(metadata.len()).ok());
+                resources.extend(thread_resource(item, size));
+                project_threads += 1;
+            }
+
+            match page.next_cursor {
+                Some(next)
+                    if project_threads < THREAD_LIST_PAGE_SIZE
+                        && store_pages < MAX_STORE_PAGES_PER_LIST =>
+                {
+                    cursor = Some(next);
+                }
+                next => break next,
+            }
+        };
+        let next_cursor = next_cursor
+            .and_then(|cursor| serde_json::to_value(cursor).ok())
+            .and_then(|value| value.as_str().map(str::to_string));
+
+        Ok(ListResourcesResult {
+            meta: None,
+            next_cursor,
+            resources,
+        })
+    }
+
+    pub(crate) fn list_resource_templates(&self) -> ListResourceTemplatesResult {
+        ListResourceTemplatesResult::with_all_items(vec![
+            RawResourceTemplate {
+                uri_template: format!("{THREAD_URI_PREFIX}{{thread_id}}"),
+                name: "thread".to_string(),
+                title: Some("Codex thread rollout".to_string()),
+                description: Some(
+                    "Every event and response item recorded for a Codex thread, as JSONL."
+                        .to_string(),
+                ),
+                mime_type: Some(ROLLOUT_MIME_TYPE.to_string()),
+            }
+            .no_annotation(),
+            RawResourceTemplate {
+                uri_template: format!("{THREAD_URI_PREFIX}{{thread_id}}{DIFF_URI_SUFFIX}"),
+                name: "thread-diff".to_string(),
+                title: Some("Codex thread diff".to_string()),
+                description: Some(
+                    "Unified diff of the changes made by the current turn of a running thread."
+                        .to_string(),
+                ),
+                mime_type: Some(DIFF_MIME_TYPE.to_string()),
+            }
+            .no_annotation(),
+        ])
+    }
+
+    pub(crate) async fn read_resource(&self, uri: &str) -> Result<ReadResourceResult, ErrorData> {
+        let shared = &self.shared;
+        let parsed = CodexResourceUri::parse(uri).ok_or_else(|| resource_not_found(uri))?;
+        let contents = match parsed {
+            CodexResourceUri::Thread(thread_id) => {
+                let path = find_thread_path_by_id_str(&shared.codex_home, &thread_id.to_string())
+                    .await
+                    .map_err(|err| {
+                        ErrorData::internal_error(
+                            format!("failed to locate rollout for {uri}: {err}"),
+                            None,
+                        )
+                    })?
+                    .ok_or_else(|| resource_not_found(uri))?;
+                let is_running = shared.running_threads().contains_key(&thread_id);
+                if !is_running {
+                    let meta = read_session_meta_line(&path)
+                        .await
+                        .map_err(|_| resource_not_found(uri))?;
+                    if !shared.in_project(&meta.meta.cwd) {
+                        return Err(resource_not_found(uri));
+                    }
+                }
+                let text = tokio::fs::read_to_string(&path).await.map_err(|err| {
+                    ErrorData::internal_error(
+                        format!("failed to read rollout {}: {err}", path.display()),
+                        None,
+                    )
+                })?;
+                ResourceContents::TextResourceContents {
+                    uri: uri.to_string(),
+                    mime_type: Some(ROLLOUT_MIME_TYPE.to_string()),
+                    text,
+                    meta: None,
+                }
+            }
+            CodexResourceUri::ThreadDiff(thread_id) => {
+                let unified_diff = shared
+                    .running_threads()
+                    .get(&thread_id)
+                    .ok_or_else(|| resource_not_found(uri))?
+                    .unified_diff
+                    .clone();
+                ResourceContents::TextResourceContents {
+                    uri: uri.to_string(),
+                    mime_type: Some(DIFF_MIME_TYPE.to_string()),
+                    text: unified_diff.unwrap_or_default(),
+                    meta: None,
+                }
+            }
+        };
+        Ok(ReadResourceResult {
+            contents: vec![contents],
+        })
+    }
+
+    pub(crate) fn subscribe(&self, uri: &str) -> Result<(), ErrorData> {
+        let parsed = CodexResourceUri::parse(uri).ok_or_else(|| resource_not_found(uri))?;
+        if let Some(subscriber) = self.shared.subscribers().get_mut(&self.subscriber_id) {
+            subscriber.uris.insert(parsed);
+        }
+        Ok(())
+    }
+
+    pub(crate) fn unsubscribe(&self, uri: &str) {
+        if let Some(parsed) = CodexResourceUri::parse(uri)
+            && let Some(subscriber) = self.shared.subscribers().get_mut(&self.subscriber_id)
+        {
+            subscriber.uris.remove(&parsed);
+        }
+    }
+
+    /// Records the effect of `msg` on a thread this session is running and
+    /// notifies subscribers of any resource it changed.
+    pub(crate) async fn observe_event(&self, thread_id: ThreadId, msg: &EventMsg) {
+        let updated = {
+            let mut running_threads = self.shared.running_threads();
+            match msg {
+                EventMsg::TurnStarted(_) => {
+                    running_threads.insert(
+                        thread_id,
+                        RunningThread {
+                            owner: self.subscriber_id,
+                            unified_diff: None,
+                        },
+                    );
+                    vec![
+                        CodexResourceUri::Thread(thread_id),
+                        CodexResourceUri::ThreadDiff(thread_id),
+                    ]
+                }
+                EventMsg::TurnDiff(ev) => match running_threads.get_mut(&thread_id) {
+                    Some(running) => {
+                        running.unified_diff = Some(ev.unified_diff.clone());
+                        vec![CodexResourceUri::ThreadDiff(thread_id)]
+                    }
+                    None => Vec::new(),
+                },
+                EventMsg::TurnComplete(_) | EventMsg::TurnAborted(_) | EventMsg::Error(_) => {
+                    vec![CodexResourceUri::Thread(thread_id)]
+                }
+                EventMsg::ShutdownComplete => {
+                    running_threads.remove(&thread_id);
+                    vec![
+                        CodexResourceUri::Thread(thread_id),
+                        CodexResourceUri::ThreadDiff(thread_id),
+                    ]
+                }
+                _ => Vec::new(),
+            }
+        };
+        self.shared.notify_updated(&updated).await;
+    }
+
+    /// Forgets a thread whose event stream ended without `ShutdownComplete`.
+    pub(crate) async fn thread_ended(&self, thread_id: ThreadId) {
+        if self.shared.running_threads().remove(&thread_id).is_some() {
+            self.shared
+                .notify_updated(&[
+                    CodexResourceUri::Thread(thread_id),
+                    CodexResourceUri::ThreadDiff(thread_id),
+                ])
+                .await;
+        }
+    }
+}
+
+impl Drop for SessionResources {
+    fn drop(&mut self) {
+        self.shared.subscribers().remove(&self.subscriber_id);
+        self.shared
+            .running_threads()
+            .retain(|_, running| running.owner != self.subscriber_id);
+    }
+}
+
+fn thread_resource(item: ThreadItem, size: Option<u32>) -> Option<Resource> {
+    let thread_id = item.thread_id?;
+    let title = item
+        .first_user_message
+        .as_deref()
+        .map(|message| message.lines().next().unwrap_or_default().to_string());
+    let mut description = Vec::new();
+    if let Some(cwd) = &item.cwd {
+        description.push(format!("cwd: {}", cwd.display()));
+    }
+    if let Some(branch) = &item.git_branch {
+        description.push(format!("branch: {branch}"));
+    }
+    if let Some(updated_at) = item.updated_at.as_ref().or(item.created_at.as_ref()) {
+        description.push(format!("updated: {updated_at}"));
+    }
+    Some(
+        RawResource {
+            uri: CodexResourceUri::Thread(thread_id).to_string(),
+            name: thread_id.to_string(),
+            title,
+            description: (!description.is_empty()).then(|| description.join(", ")),
+            mime_type: Some(ROLLOUT_MIME_TYPE.to_string()),
+            size,
+            icons: None,
+            meta: None,
+        }
+        .no_annotation(),
+    )
+}
+
+fn thread_diff_resource(thread_id: ThreadId) -> Resource {
+    RawResource {
+        uri: CodexResourceUri::ThreadDiff(thread_id).to_string(),
+        name: format!("{thread_id}-diff"),
+        title: Some(format!("Current diff of running thread {thread_id}")),
+        description: None,
+        mime_type: Some(DIFF_MIME_TYPE.to_string()),
+        size: None,
+        icons: None,
+        meta: None,
+    }
+    .no_annotation()
+}
+
+fn resource_not_found(uri: &str) -> ErrorData {
+    ErrorData::resource_not_found("resource_not_found", Some(json!({ "uri": uri })))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::outgoing_message::OutgoingMessage;
+    use codex_core::protocol::ErrorEvent;
+    use codex_core::protocol::TurnDiffEvent;
+    use codex_core::protocol::TurnStartedEvent;
+    use codex_protocol::config_types::ModeKind;
+    use codex_protocol::protocol::SessionMeta;
+    use codex_protocol::protocol::SessionMetaLine;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+    use tokio::sync::mpsc;
+
+    const TEST_PROVIDER: &str = "test-provider";
+
+    /// Writes a minimal rollout for a thread started in `cwd`.
+    fn write_rollout(codex_home: &Path, filename_ts: &str, cwd: &Path) -> anyhow::Result<ThreadId> {
+        let thread_id = ThreadId::new();
+        let dir = codex_home
+            .join("sessions")
+            .join(&filename_ts[0..4])
+            .join(&filename_ts[5..7])
+            .join(&filename_ts[8..10]);
+        std::fs::create_dir_all(&dir)?;
+        let timestamp = "2025-01-02T03:04:05Z";
+        let meta = SessionMetaLine {
+            meta: SessionMeta {
+                id: thread_id,
+                timestamp: timestamp.to_string(),
+                cwd: cwd.to_path_buf(),
+                originator: "codex".to_string(),
+                cli_version: "0.0.0".to_string(),
+                model_provider: Some(TEST_PROVIDER.to_string()),
+                ..SessionMeta::default()
+            },
+            git: None,
+        };
+        let lines = [
+            json!({ "timestamp": timestamp, "type": "session_meta", "payload": meta }),
+            json!({
+                "timestamp": timestamp,
+                "type": "response_item",
+                "payload": {
+                    "type": "message",
+                    "role": "user",
+                    "content": [{ "type": "input_text", "text": "hello" }]
+                }
+            }),
+            json!({
+                "timestamp": timestamp,
+                "type": "event_msg",
+                "payload": { "type": "user_message", "message": "hello", "kind": "plain" }
+            }),
+        ];
+        let contents = lines
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join("\n");
+        std::fs::write(
+            dir.join(format!("rollout-{filename_ts}-{thread_id}.jsonl")),
+            contents + "\n",
+        )?;
+        Ok(thread_id)
+    }
+
+    fn shared_resources(codex_home: &Path, project_root: &Path) -> Arc<ThreadResources> {
+        Arc::new(ThreadResources::with_project_root(
+            codex_home.to_path_buf(),
+            project_root.to_path_buf(),
+            TEST_PROVIDER.to_string(),
+        ))
+    }
+
+    fn session(
+        shared: &Arc<ThreadResources>,
+    ) -> (SessionResources, mpsc::UnboundedReceiver<OutgoingMessage>) {
+        let (outgoing_tx, outgoing_rx) = mpsc::unbounded_channel();
+        let outgoing = Arc::new(OutgoingMessageSender::new(outgoing_tx));
+        (SessionResources::new(shared.clone(), outgoing), outgoing_rx)
+    }
+
+    fn updated_uris(outgoing_rx: &mut mpsc::UnboundedReceiver<OutgoingMessage>) -> Vec<String> {
+        let mut uris = Vec::new();
+        while let Ok(message) = outgoing_rx.try_recv() {
+            if let OutgoingMessage::Notification(notification) = message
+                && notification.method == RESOURCE_UPDATED_METHOD
+                && let Some(uri) = notification
+                    .params
+                    .and_then(|params| params["uri"].as_str().map(str::to_string))
+            {
+                uris.push(uri);
+            }
+        }
+        uris
+    }
+
+    fn turn_started() -> EventMsg {
+        EventMsg::TurnStarted(TurnStartedEvent {
+            model_context_window: None,
+            collaboration_mode_kind: ModeKind::default(),
+        })
+    }
+
+    #[tokio::test]
+    async fn lists_and_reads_only_project_threads() -> anyhow::Result<()> {
+        let codex_home = TempDir::new()?;
+        let workspace = TempDir::new()?;
+        let project = workspace.path().join("project");
+        let other = workspace.path().join("other");
+        std::fs::create_dir_all(project.join("sub"))?;
+        std::fs::create_dir_all(&other)?;
+        let project_thread = write_rollout(
+            codex_home.path(),
+            "2025-01-02T03-04-05",
+            &project.join("sub"),
+        )?;
+        let other_thread = write_rollout(codex_home.path(), "2025-01-02T03-04-06", &other)?;
+
+        let shared = shared_resources(codex_home.path(), &project);
+        let (resources, _outgoing_rx) = session(&shared);
+
+        let listed = resources.list_resources(None).await?;
+        let uris: Vec<_> = listed
+            .resources
+            .iter()
+            .map(|resource| resource.uri.clone())
+            .collect();
+        assert_eq!(uris, vec![format!("codex://thread/{project_thread}")]);
+        assert_eq!(listed.next_cursor, None);
+
+        let read = resources
+            .read_resource(&format!("codex://thread/{project_thread}"))
+            .await?;
+        let [ResourceContents::TextResourceContents { text, .. }] = read.contents.as_slice() else {
+            panic!("expected a single text resource");
+        };
+        assert!(text.contains("\"session_meta\""));
+
+        assert!(
+            resources
+                .read_resource(&format!("codex://thread/{other_thread}"))
+                .await
+                .is_err()
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn notifies_only_sessions_subscribed_to_the_resource() -> anyhow::Result<()> {
+        let codex_home = TempDir::new()?;
+        let shared = shared_resources(codex_home.path(), codex_home.path());
+        let (runner, mut runner_rx) = session(&shared);
+        let (watcher, mut watcher_rx) = session(&shared);
+        let thread_id = ThreadId::new();
+        let diff_uri = CodexResourceUri::ThreadDiff(thread_id).to_string();
+        watcher.subscribe(&diff_uri)?;
+
+        runner.observe_event(thread_id, &turn_started()).await;
+        runner
+            .observe_event(
+                thread_id,
+                &EventMsg::TurnDiff(TurnDiffEvent {
+                    unified_diff: "--- a\n+++ b\n".to_string(),
+                }),
+            )
+            .await;
+
+        assert_eq!(updated_uris(&mut watcher_rx), vec![diff_uri.clone(); 2]);
+        assert_eq!(updated_uris(&mut runner_rx), Vec::<String>::new());
+        let read = watcher.read_resource(&diff_uri).await?;
+        let [ResourceContents::TextResourceContents { text, .. }] = read.contents.as_slice() else {
+            panic!("expected a single text resource");
+        };
+        assert_eq!(text, "--- a\n+++ b\n");
+
+        watcher.unsubscribe(&diff_uri);
+        runner.observe_event(thread_id, &turn_started()).await;
+        assert_eq!(updated_uris(&mut watcher_rx), Vec::<String>::new());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn forgets_threads_when_they_end() -> anyhow::Result<()> {
+        let codex_home = TempDir::new()?;
+        let shared = shared_resources(codex_home.path(), codex_home.path());
+        let (runner, _runner_rx) = session(&shared);
+        let shut_down = ThreadId::new();
+        let failed = ThreadId::new();
+        let orphaned = ThreadId::new();
+        for thread_id in [shut_down, failed, orphaned] {
+            runner.observe_event(thread_id, &turn_started()).await;
+        }
+
+        runner
+            .observe_event(shut_down, &EventMsg::ShutdownComplete)
+            .await;
+        runner
+            .observe_event(
+                shut_down,
+                &EventMsg::Error(ErrorEvent {
+                    message: "late error".to_string(),
+                    codex_error_info: None,
+                }),
+            )
+            .await;
+        runner.thread_ended(failed).await;
+        let running: HashSet<_> = shared.running_threads().keys().copied().collect();
+        assert_eq!(running, HashSet::from([orphaned]));
+
+        drop(runner);
+        assert!(shared.running_threads().is_empty());
+        assert!(shared.subscribers().is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn parses_thread_and_diff_uris() {
+        let thread_id = ThreadId::new();
+        let thread_uri = format!("codex://thread/{thread_id}");
+        let diff_uri = format!("codex://thread/{thread_id}/diff");
+
+        assert_eq!(
+            CodexResourceUri::parse(&thread_uri),
+            Some(CodexResourceUri::Thread(thread_id))
+        );
+        assert_eq!(
+            CodexResourceUri::parse(&diff_uri),
+            Some(CodexResourceUri::ThreadDiff(thread_id))
+        );
+        assert_eq!(CodexResourceUri::Thread(thread_id).to_string(), thread_uri);
+        assert_eq!(
+            CodexResourceUri::ThreadDiff(thread_id).to_string(),
+            diff_uri
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_uris() {
+        assert_eq!(CodexResourceUri::parse("codex://thread/not-a-uuid"), None);
+        assert_eq!(CodexResourceUri::parse("file:///tmp/rollout.jsonl"), None);
+    }
+}
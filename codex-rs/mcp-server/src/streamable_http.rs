@@ -0,0 +1,762 @@
+//! Streamable HTTP transport for `codex mcp-server --listen http://IP:PORT`.
+//!
+//! An `initialize` request without an `Mcp-Session-Id` header starts a new
+//! session backed by its own `MessageProcessor`, so every client gets its
+//! own set of Codex threads. Responses are returned in the body of the `POST`
+//! that carried the request. Notifications and server-initiated requests
+//! (e.g. approval elicitations) are delivered on the session's `GET` SSE
+//! stream and buffered until the client opens it.
+//!
+//! Without a bearer token only loopback `Host` and `Origin` headers are
+//! accepted, so web pages cannot reach the server through DNS rebinding or
+//! cross-origin requests. Sessions that see no requests, have no open stream
+//! and wait on no responses for [`SESSION_IDLE_TIMEOUT`] are shut down.
+
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::convert::Infallible;
+use std::io::Result as IoResult;
+use std::net::IpAddr;
+use std::net::Ipv6Addr;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::PoisonError;
+use std::time::Duration;
+use std::time::Instant;
+
+use axum::Router;
+use axum::body::Body;
+use axum::body::Bytes;
+use axum::extract::State;
+use axum::http::HeaderMap;
+use axum::http::HeaderValue;
+use axum::http::Request;
+use axum::http::StatusCode;
+use axum::http::header::AUTHORIZATION;
+use axum::http::header::CONTENT_TYPE;
+use axum::http::header::HOST;
+use axum::http::header::ORIGIN;
+use axum::middleware;
+use axum::middleware::Next;
+use axum::response::IntoResponse;
+use axum::response::Response;
+use axum::response::sse::Event as SseEvent;
+use axum::response::sse::KeepAlive;
+use axum::response::sse::Sse;
+use axum::routing::post;
+use codex_core::config::Config;
+use codex_utils_string::constant_time_eq;
+use futures::StreamExt;
+use rmcp::model::ClientRequest;
+use rmcp::model::JsonRpcMessage;
+use rmcp::model::RequestId;
+use tokio::net::TcpListener;
+use tokio::sync::mpsc;
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+use tokio_stream::wrappers::UnboundedReceiverStream;
+use tracing::info;
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::CHANNEL_CAPACITY;
+use crate::IncomingMessage;
+use crate::outgoing_message::OutgoingJsonRpcMessage;
+use crate::outgoing_message::OutgoingMessage;
+use crate::resources::ThreadResources;
+use crate::spawn_message_processor;
+
+const MCP_PATH: &str = "/mcp";
+const SESSION_ID_HEADER: &str = "mcp-session-id";
+/// Upper bound on messages held for a session whose SSE stream is closed.
+const MAX_BUFFERED_MESSAGES: usize = 1024;
+/// How long a session may go without requests or an open SSE stream.
+const SESSION_IDLE_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+const IDLE_SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+/// How long a `POST` waits for its response before handing it to the SSE
+/// stream instead.
+const POST_RESPONSE_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+#[derive(Clone)]
+struct AppState {
+    sessions: Arc<Mutex<HashMap<String, Arc<HttpSession>>>>,
+    codex_linux_sandbox_exe: Option<PathBuf>,
+    config: Arc<Config>,
+    thread_resources: Arc<ThreadResources>,
+    bearer_token: Option<Arc<str>>,
+}
+
+struct HttpSession {
+    incoming_tx: mpsc::Sender<IncomingMessage>,
+    routing: Arc<SessionRouting>,
+    tasks: Vec<JoinHandle<()>>,
+    last_activity: Mutex<Instant>,
+}
+
+impl HttpSession {
+    fn touch(&self) {
+        *self
+            .last_activity
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = Instant::now();
+    }
+
+    fn is_idle(&self, now: Instant, idle_timeout: Duration) -> bool {
+        let last_activity = *self
+            .last_activity
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        !self.routing.has_open_stream()
+            && !self.routing.has_pending_responses()
+            && now.saturating_duration_since(last_activity) >= idle_timeout
+    }
+}
+
+impl Drop for HttpSession {
+    fn drop(&mut self) {
+        for task in &self.tasks {
+            task.abort();
+        }
+    }
+}
+
+/// Routes outgoing messages either to the `POST` waiting for a response or to
+/// the session's SSE stream.
+#[derive(Default)]
+struct SessionRouting {
+    pending_responses: Mutex<HashMap<RequestId, oneshot::Sender<OutgoingJsonRpcMessage>>>,
+    stream: Mutex<SessionStream>,
+}
+
+#[derive(Default)]
+struct SessionStream {
+    sender: Option<mpsc::UnboundedSender<OutgoingJsonRpcMessage>>,
+    backlog: VecDeque<OutgoingJsonRpcMessage>,
+}
+
+impl SessionRouting {
+    fn route(&self, message: OutgoingJsonRpcMessage) {
+        let response_id = match &message {
+            JsonRpcMessage::Response(response) => Some(&response.id),
+            JsonRpcMessage::Error(error) => Some(&error.id),
+            JsonRpcMessage::Request(_) | JsonRpcMessage::Notification(_) => None,
+        };
+        let waiter = response_id.and_then(|id| {
+            self.pending_responses
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .remove(id)
+        });
+        match waiter {
+            Some(waiter) => {
+                if let Err(message) = waiter.send(message) {
+                    // The HTTP client went away; keep the response on the stream.
+                    self.push_to_stream(message);
+                }
+            }
+            None => self.push_to_stream(message),
+        }
+    }
+
+    fn push_to_stream(&self, message: OutgoingJsonRpcMessage) {
+        let mut stream = self.stream.lock().unwrap_or_else(PoisonError::into_inner);
+        let message = match stream.sender.as_ref() {
+            Some(sender) => match sender.send(message) {
+                Ok(()) => return,
+                Err(mpsc::error::SendError(message)) => {
+                    stream.sender = None;
+                    message
+                }
+            },
+            None => message,
+        };
+        if stream.backlog.len() >= MAX_BUFFERED_MESSAGES {
+            warn!("dropping oldest buffered MCP message; no SSE stream is attached");
+            stream.backlog.pop_front();
+        }
+        stream.backlog.push_back(message);
+    }
+
+    fn has_pending_responses(&self) -> bool {
+        !self
+            .pending_responses
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .is_empty()
+    }
+
+    fn has_open_stream(&self) -> bool {
+        self.stream
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .sender
+            .as_ref()
+            .is_some_and(|sender| !sender.is_closed())
+    }
+
+    fn attach_stream(&self) -> mpsc::UnboundedReceiver<OutgoingJsonRpcMessage> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let mut stream = self.stream.lock().unwrap_or_else(PoisonError::into_inner);
+        for message in stream.backlog.drain(..) {
+            let _ = tx.send(message);
+        }
+        // Replacing the sender ends any previously attached stream.
+        stream.sender = Some(tx);
+        rx
+    }
+}
+
+pub(crate) async fn run_streamable_http_server(
+    bind_address: SocketAddr,
+    bearer_token: Option<String>,
+    codex_linux_sandbox_exe: Option<PathBuf>,
+    config: Arc<Config>,
+) -> IoResult<()> {
+    let listener = TcpListener::bind(bind_address).await?;
+    let local_addr = listener.local_addr()?;
+    info!("codex mcp-server listening on http://{local_addr}{MCP_PATH}");
+
+    let state = AppState {
+        sessions: Arc::new(Mutex::new(HashMap::new())),
+        codex_linux_sandbox_exe,
+        thread_resources: Arc::new(ThreadResources::new(&config)),
+        config,
+        bearer_token: bearer_token.map(Arc::from),
+    };
+    let sweeper = tokio::spawn({
+        let state = state.clone();
+        async move {
+            let mut interval = tokio::time::interval(IDLE_SWEEP_INTERVAL);
+            loop {
+                interval.tick().await;
+                remove_idle_sessions(&state, Instant::now(), SESSION_IDLE_TIMEOUT);
+            }
+        }
+    });
+    let result = axum::serve(listener, router(state))
+        .with_graceful_shutdown(async {
+            let _ = tokio::signal::ctrl_c().await;
+        })
+        .await;
+    sweeper.abort();
+    result
+}
+
+/// Shuts down sessions that have been idle for at least `idle_timeout`.
+fn remove_idle_sessions(state: &AppState, now: Instant, idle_timeout: Duration) {
+    state
+        .sessions
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .retain(|session_id, session| {
+            let idle = session.is_idle(now, idle_timeout);
+            if idle {
+                info!("closing idle MCP session {session_id}");
+            }
+            !idle
+        });
+}
+
+fn router(state: AppState) -> Router {
+    Router::new()
+        .route(
+            MCP_PATH,
+            post(handle_post).get(handle_get).delete(handle_delete),
+        )
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            authorize_request,
+        ))
+        .with_state(state)
+}
+
+/// Checks the bearer token when one is configured; otherwise only accepts
+/// requests whose `Host` and `Origin` (if any) name a loopback address.
+async fn authorize_request(
+    State(state): State<AppState>,
+    request: Request<Body>,
+    next: Next,
+) -> Response {
+    let headers = request.headers();
+    match state.bearer_token.as_deref() {
+        Some(expected) => {
+            let authorized = headers
+                .get(AUTHORIZATION)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.strip_prefix("Bearer "))
+                .is_some_and(|token| constant_time_eq(token.as_bytes(), expected.as_bytes()));
+            if !authorized {
+                return StatusCode::UNAUTHORIZED.into_response();
+            }
+        }
+        None => {
+            let host_allowed = headers
+                .get(HOST)
+                .and_then(|value| value.to_str().ok())
+                .is_some_and(is_loopback_authority);
+            let origin_allowed = headers.get(ORIGIN).is_none_or(|value| {
+                value
+                    .to_str()
+                    .ok()
+                    .and_then(|origin| origin.split_once("://"))
+                    .is_some_and(|(_, rest)| {
+                        is_loopback_authority(rest.split('/').next().unwrap_or_default())
+                    })
+            });
+            if !host_allowed || !origin_allowed {
+                return (StatusCode::FORBIDDEN, "only loopback origins may connect")
+                    .into_response();
+            }
+        }
+    }
+    next.run(request).await
+}
+
+/// Whether `authority` (`host[:port]`) names `localhost` or a loopback IP.
+fn is_loopback_authority(authority: &str) -> bool {
+    let host = match authority.strip_prefix('[') {
+        Some(rest) => {
+            return rest
+                .split_once(']')
+                .and_then(|(ip, _)| ip.parse::<Ipv6Addr>().ok())
+                .is_some_and(|ip| ip.is_loopback());
+        }
+        None => authority
+            .rsplit_once(':')
+            .map_or(authority, |(host, _port)| host),
+    };
+    host.eq_ignore_ascii_case("localhost")
+        || host.parse::<IpAddr>().is_ok_and(|ip| ip.is_loopback())
+}
+
+async fn handle_post(State(state): State<AppState>, headers: HeaderMap, body: Bytes) -> Response {
+    let message = match serde_json::from_slice::<IncomingMessage>(&body) {
+        Ok(message) => message,
+        Err(err) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                format!("invalid JSON-RPC message: {err}"),
+            )
+                .into_response();
+        }
+    };
+
+    let (session_id, session) = match session_id_from_headers(&headers) {
+        Some(session_id) => match lookup_session(&state, &session_id) {
+            Some(session) => (session_id, session),
+            None => return (StatusCode::NOT_FOUND, "unknown MCP session").into_response(),
+        },
+        None => {
+            let is_initialize = matches!(
+                &message,
+                JsonRpcMessage::Request(request)
+                    if matches!(request.request, ClientRequest::InitializeRequest(_))
+            );
+            if !is_initialize {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    format!("missing {SESSION_ID_HEADER} header"),
+                )
+                    .into_response();
+            }
+            start_session(&state)
+        }
+    };
+
+    let request_id = match &message {
+        JsonRpcMessage::Request(request) => Some(request.id.clone()),
+        JsonRpcMessage::Response(_)
+        | JsonRpcMessage::Notification(_)
+        | JsonRpcMessage::Error(_) => None,
+    };
+    let response_rx = request_id.clone().map(|id| {
+        let (tx, rx) = oneshot::channel();
+        session
+            .routing
+            .pending_responses
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(id, tx);
+        rx
+    });
+
+    if session.incoming_tx.send(message).await.is_err() {
+        return (StatusCode::GONE, "MCP session has shut down").into_response();
+    }
+
+    let mut response = match response_rx {
+        Some(rx) => match tokio::time::timeout(POST_RESPONSE_TIMEOUT, rx).await {
+            Ok(Ok(outgoing)) => match serde_json::to_vec(&outgoing) {
+                Ok(json) => (
+                    StatusCode::OK,
+                    [(CONTENT_TYPE, HeaderValue::from_static("application/json"))],
+                    json,
+                )
+                    .into_response(),
+                Err(err) => (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    format!("failed to serialize JSON-RPC message: {err}"),
+                )
+                    .into_response(),
+            },
+            Ok(Err(_)) => (StatusCode::GONE, "MCP session has shut down").into_response(),
+            Err(_) => {
+                // The response is routed to the SSE stream once it arrives.
+                if let Some(id) = &request_id {
+                    session
+                        .routing
+                        .pending_responses
+                        .lock()
+                        .unwrap_or_else(PoisonError::into_inner)
+                        .remove(id);
+                }
+                (
+                    StatusCode::GATEWAY_TIMEOUT,
+                    "no response yet; it will be sent on the session's SSE stream",
+                )
+                    .into_response()
+            }
+        },
+        None => StatusCode::ACCEPTED.into_response(),
+    };
+    if let Ok(value) = HeaderValue::from_str(&session_id) {
+        response.headers_mut().insert(SESSION_ID_HEADER, value);
+    }
+    response
+}
+
+async fn handle_get(State(state): State<AppState>, headers: HeaderMap) -> Response {
+    let Some(session) = session_id_from_headers(&headers)
+        .and_then(|session_id| lookup_session(&state, &session_id))
+    else {
+        return (StatusCode::NOT_FOUND, "unknown MCP session").into_response();
+    };
+
+    let stream = UnboundedReceiverStream::new(session.routing.attach_stream()).map(|message| {
+        let event = match serde_json::to_string(&message) {
+            Ok(json) => SseEvent::default().event("message").data(json),
+            Err(err) => SseEvent::default().comment(format!("serialize error: {err}")),
+        };
+        Ok::<_, Infallible>(event)
+    });
+    Sse::new(stream)
+        .keep_alive(KeepAlive::default())
+        .into_response()
+}
+
+async fn handle_delete(State(state): State<AppState>, headers: HeaderMap) -> StatusCode {
+    let removed = session_id_from_headers(&headers).and_then(|session_id| {
+        state
+            .sessions
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(&session_id)
+    });
+    match removed {
+        Some(_) => StatusCode::NO_CONTENT,
+        None => StatusCode::NOT_FOUND,
+    }
+}
+
+fn session_id_from_headers(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get(SESSION_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string)
+}
+
+fn lookup_session(state: &AppState, session_id: &str) -> Option<Arc<HttpSession>> {
+    let session = state
+        .sessions
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get(session_id)
+        .cloned()?;
+    session.touch();
+    Some(session)
+}
+
+fn start_session(state: &AppState) -> (String, Arc<HttpSession>) {
+    let session_id = Uuid::new_v4().to_string();
+    let (incoming_tx, incoming_rx) = mpsc::channel::<IncomingMessage>(CHANNEL_CAPACITY);
+    let (outgoing_tx, mut outgoing_rx) = mpsc::unbounded_channel::<OutgoingMessage>();
+    let routing = Arc::new(SessionRouting::default());
+
+    let processor_handle = spawn_message_processor(
+        incoming_rx,
+        outgoing_tx,
+        state.codex_linux_sandbox_exe.clone(),
+        state.config.clone(),
+        state.thread_resources.clone(),
+    );
+    let router_handle = tokio::spawn({
+        let routing = routing.clone();
+        async move {
+            while let Some(outgoing_message) = outgoing_rx.recv().await {
+                routing.route(outgoing_message.into());
+            }
+        }
+    });
+
+    let session = Arc::new(HttpSession {
+        incoming_tx,
+        routing,
+        tasks: vec![processor_handle, router_handle],
+        last_activity: Mutex::new(Instant::now()),
+    });
+    state
+        .sessions
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .insert(session_id.clone(), session.clone());
+    info!("started MCP session {session_id}");
+    (session_id, session)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core_test_support::load_default_config_for_test;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    async fn spawn_server(bearer_token: Option<&str>) -> (SocketAddr, TempDir) {
+        let codex_home = TempDir::new().expect("create temp dir");
+        let config = load_default_config_for_test(&codex_home).await;
+        let state = AppState {
+            sessions: Arc::new(Mutex::new(HashMap::new())),
+            codex_linux_sandbox_exe: None,
+            thread_resources: Arc::new(ThreadResources::new(&config)),
+            config: Arc::new(config),
+            bearer_token: bearer_token.map(Arc::from),
+        };
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind test listener");
+        let addr = listener.local_addr().expect("listener address");
+        tokio::spawn(async move {
+            let _ = axum::serve(listener, router(state)).await;
+        });
+        (addr, codex_home)
+    }
+
+    fn initialize_request() -> serde_json::Value {
+        json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "initialize",
+            "params": {
+                "protocolVersion": "2025-03-26",
+                "capabilities": {},
+                "clientInfo": { "name": "test-client", "version": "0.0.0" }
+            }
+        })
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn rejects_requests_without_bearer_token() {
+        let (addr, _codex_home) = spawn_server(Some("secret")).await;
+        let client = reqwest::Client::new();
+        let url = format!("http://{addr}{MCP_PATH}");
+
+        let unauthorized = client
+            .post(&url)
+            .json(&initialize_request())
+            .send()
+            .await
+            .expect("send request");
+        assert_eq!(unauthorized.status(), reqwest::StatusCode::UNAUTHORIZED);
+
+        let authorized = client
+            .post(&url)
+            .bearer_auth("secret")
+            .json(&initialize_request())
+            .send()
+            .await
+            .expect("send request");
+        assert_eq!(authorized.status(), reqwest::StatusCode::OK);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn rejects_foreign_origins_without_bearer_token() {
+        let (addr, _codex_home) = spawn_server(None).await;
+        let client = reqwest::Client::new();
+        let url = format!("http://{addr}{MCP_PATH}");
+
+        let cross_origin = client
+            .post(&url)
+            .header(ORIGIN, "https://evil.example")
+            .json(&initialize_request())
+            .send()
+            .await
+            .expect("send request");
+        assert_eq!(cross_origin.status(), reqwest::StatusCode::FORBIDDEN);
+
+        let rebound_host = client
+            .post(&url)
+            .header(HOST, format!("rebind.example:{}", addr.port()))
+            .json(&initialize_request())
+            .send()
+            .await
+            .expect("send request");
+        assert_eq!(rebound_host.status(), reqwest::StatusCode::FORBIDDEN);
+
+        let local_origin = client
+            .post(&url)
+            .header(ORIGIN, format!("http://localhost:{}", addr.port()))
+            .json(&initialize_request())
+            .send()
+            .await
+            .expect("send request");
+        assert_eq!(local_origin.status(), reqwest::StatusCode::OK);
+    }
+
+    #[test]
+    fn recognizes_loopback_authorities() {
+        assert!(is_loopback_authority("127.0.0.1:8080"));
+        assert!(is_loopback_authority("localhost"));
+        assert!(is_loopback_authority("[::1]:8080"));
+        assert!(!is_loopback_authority("192.168.1.10:8080"));
+        assert!(!is_loopback_authority("localhost.evil.example"));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn idle_sessions_are_removed() {
+        let codex_home = TempDir::new().expect("create temp dir");
+        let config = load_default_config_for_test(&codex_home).await;
+        let state = AppState {
+            sessions: Arc::new(Mutex::new(HashMap::new())),
+            codex_linux_sandbox_exe: None,
+            thread_resources: Arc::new(ThreadResources::new(&config)),
+            config: Arc::new(config),
+            bearer_token: None,
+        };
+        let (session_id, session) = start_session(&state);
+        drop(session);
+
+        let has_session = |state: &AppState| {
+            state
+                .sessions
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .contains_key(&session_id)
+        };
+        let now = Instant::now();
+        remove_idle_sessions(&state, now, SESSION_IDLE_TIMEOUT);
+        assert!(has_session(&state));
+
+        remove_idle_sessions(&state, now + SESSION_IDLE_TIMEOUT, SESSION_IDLE_TIMEOUT);
+        assert!(!has_session(&state));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn sessions_waiting_on_a_response_are_not_idle() {
+        let codex_home = TempDir::new().expect("create temp dir");
+        let config = load_default_config_for_test(&codex_home).await;
+        let state = AppState {
+            sessions: Arc::new(Mutex::new(HashMap::new())),
+            codex_linux_sandbox_exe: None,
+            thread_resources: Arc::new(ThreadResources::new(&config)),
+            config: Arc::new(config),
+            bearer_token: None,
+        };
+        let (session_id, session) = start_session(&state);
+        let (tx, _rx) = oneshot::channel();
+        session
+            .routing
+            .pending_responses
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(RequestId::Number(7), tx);
+
+        let has_session = |state: &AppState| {
+            state
+                .sessions
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .contains_key(&session_id)
+        };
+        let later = Instant::now() + SESSION_IDLE_TIMEOUT;
+        remove_idle_sessions(&state, later, SESSION_IDLE_TIMEOUT);
+        assert!(has_session(&state));
+
+        session
+            .routing
+            .pending_responses
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clear();
+        drop(session);
+        remove_idle_sessions(&state, later, SESSION_IDLE_TIMEOUT);
+        assert!(!has_session(&state));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn initialize_assigns_distinct_sessions() {
+        let (addr, _codex_home) = spawn_server(None).await;
+        let client = reqwest::Client::new();
+        let url = format!("http://{addr}{MCP_PATH}");
+
+        let mut session_ids = Vec::new();
+        for _ in 0..2 {
+            let response = client
+                .post(&url)
+                .json(&initialize_request())
+                .send()
+                .await
+                .expect("send initialize");
+            assert_eq!(response.status(), reqwest::StatusCode::OK);
+            let session_id = response
+                .headers()
+                .get(SESSION_ID_HEADER)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string)
+                .expect("session id header");
+            let body: serde_json::Value = response.json().await.expect("json body");
+            assert_eq!(body["id"], json!(1));
+            assert_eq!(
+                body["result"]["serverInfo"]["name"],
+                json!("codex-mcp-server")
+            );
+            session_ids.push(session_id);
+        }
+        assert_ne!(session_ids[0], session_ids[1]);
+
+        let ping = client
+            .post(&url)
+            .header(SESSION_ID_HEADER, &session_ids[0])
+            .json(&json!({ "jsonrpc": "2.0", "id": 2, "method": "ping" }))
+            .send()
+            .await
+            .expect("send ping");
+        assert_eq!(ping.status(), reqwest::StatusCode::OK);
+
+        let missing_session = client
+            .post(&url)
+            .json(&json!({ "jsonrpc": "2.0", "id": 3, "method": "ping" }))
+            .send()
+            .await
+            .expect("send ping");
+        assert_eq!(missing_session.status(), reqwest::StatusCode::BAD_REQUEST);
+
+        let deleted = client
+            .delete(&url)
+            .header(SESSION_ID_HEADER, &session_ids[0])
+            .send()
+            .await
+            .expect("send delete");
+        assert_eq!(deleted.status(), reqwest::StatusCode::NO_CONTENT);
+
+        let unknown_session = client
+            .post(&url)
+            .header(SESSION_ID_HEADER, &session_ids[0])
+            .json(&json!({ "jsonrpc": "2.0", "id": 4, "method": "ping" }))
+            .send()
+            .await
+            .expect("send ping");
+        assert_eq!(unknown_session.status(), reqwest::StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,93 @@
+use std::net::SocketAddr;
+use std::str::FromStr;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum McpServerTransport {
+    Stdio,
+    StreamableHttp { bind_address: SocketAddr },
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum McpServerTransportParseError {
+    UnsupportedListenUrl(String),
+    InvalidHttpListenUrl(String),
+}
+
+impl std::fmt::Display for McpServerTransportParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            McpServerTransportParseError::UnsupportedListenUrl(listen_url) => write!(
+                f,
+                "unsupported --listen URL `{listen_url}`; expected `stdio://` or `http://IP:PORT`"
+            ),
+            McpServerTransportParseError::InvalidHttpListenUrl(listen_url) => write!(
+                f,
+                "invalid http --listen URL `{listen_url}`; expected `http://IP:PORT`"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for McpServerTransportParseError {}
+
+impl McpServerTransport {
+    pub const DEFAULT_LISTEN_URL: &'static str = "stdio://";
+
+    pub fn from_listen_url(listen_url: &str) -> Result<Self, McpServerTransportParseError> {
+        if listen_url == Self::DEFAULT_LISTEN_URL {
+            return Ok(Self::Stdio);
+        }
+
+        if let Some(socket_addr) = listen_url.strip_prefix("http://") {
+            let socket_addr = socket_addr.strip_suffix('/').unwrap_or(socket_addr);
+            let bind_address = socket_addr.parse::<SocketAddr>().map_err(|_| {
+                McpServerTransportParseError::InvalidHttpListenUrl(listen_url.to_string())
+            })?;
+            return Ok(Self::StreamableHttp { bind_address });
+        }
+
+        Err(McpServerTransportParseError::UnsupportedListenUrl(
+            listen_url.to_string(),
+        ))
+    }
+}
+
+impl FromStr for McpServerTransport {
+    type Err = McpServerTransportParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_listen_url(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parses_listen_urls() {
+        assert_eq!(
+            McpServerTransport::from_listen_url("stdio://"),
+            Ok(McpServerTransport::Stdio)
+        );
+        assert_eq!(
+            McpServerTransport::from_listen_url("http://127.0.0.1:8765/"),
+            Ok(McpServerTransport::StreamableHttp {
+                bind_address: "127.0.0.1:8765".parse().expect("valid socket address"),
+            })
+        );
+        assert_eq!(
+            McpServerTransport::from_listen_url("http://localhost"),
+            Err(McpServerTransportParseError::InvalidHttpListenUrl(
+                "http://localhost".to_string()
+            ))
+        );
+        assert_eq!(
+            McpServerTransport::from_listen_url("ws://127.0.0.1:8765"),
+            Err(McpServerTransportParseError::UnsupportedListenUrl(
+                "ws://127.0.0.1:8765".to_string()
+            ))
+        );
+    }
+}
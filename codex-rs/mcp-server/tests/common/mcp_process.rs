@@ -167,6 +167,13 @@ impl McpProcess {
                     "tools": {
                         "listChanged": true
                     },
+                    "resources": {
+                        "subscribe": true,
+                        "listChanged": false
+                    },
+                    "prompts": {
+                        "listChanged": false
+                    },
                 },
                 "serverInfo": {
                     "name": "codex-mcp-server",
@@ -208,6 +215,26 @@ impl McpProcess {
         .await
     }
 
+    pub async fn send_list_prompts_request(&mut self) -> anyhow::Result<i64> {
+        self.send_request("prompts/list", None).await
+    }
+
+    pub async fn send_get_prompt_request(
+        &mut self,
+        name: &str,
+        arguments: serde_json::Value,
+    ) -> anyhow::Result<i64> {
+        self.send_request(
+            "prompts/get",
+            Some(json!({ "name": name, "arguments": arguments })),
+        )
+        .await
+    }
+
+    pub async fn send_list_resource_templates_request(&mut self) -> anyhow::Result<i64> {
+        self.send_request("resources/templates/list", None).await
+    }
+
     async fn send_request(
         &mut self,
         method: &str,
@@ -0,0 +1,107 @@
+use std::time::Duration;
+
+use mcp_test_support::McpProcess;
+use pretty_assertions::assert_eq;
+use rmcp::model::RequestId;
+use serde_json::json;
+use tempfile::TempDir;
+use tokio::time::timeout;
+
+const DEFAULT_READ_TIMEOUT: Duration = Duration::from_secs(20);
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn custom_prompts_are_listed_and_expanded() -> anyhow::Result<()> {
+    let codex_home = TempDir::new()?;
+    let prompts_dir = codex_home.path().join("prompts");
+    std::fs::create_dir_all(&prompts_dir)?;
+    std::fs::write(
+        prompts_dir.join("review.md"),
+        "---\ndescription: Review a file\n---\nReview $FILE focusing on $FOCUS.\n",
+    )?;
+
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+
+    let list_id = mcp.send_list_prompts_request().await?;
+    let list_response = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Number(list_id)),
+    )
+    .await??;
+    let prompts = list_response.result["prompts"]
+        .as_array()
+        .cloned()
+        .unwrap_or_default();
+    let review = prompts
+        .iter()
+        .find(|prompt| prompt["name"] == "prompts:review")
+        .cloned();
+    assert_eq!(
+        review,
+        Some(json!({
+            "name": "prompts:review",
+            "description": "Review a file",
+            "arguments": [
+                { "name": "FILE", "required": true },
+                { "name": "FOCUS", "required": true }
+            ]
+        }))
+    );
+
+    let get_id = mcp
+        .send_get_prompt_request(
+            "prompts:review",
+            json!({ "FILE": "src/lib.rs", "FOCUS": "error handling" }),
+        )
+        .await?;
+    let get_response = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Number(get_id)),
+    )
+    .await??;
+    assert_eq!(
+        get_response.result,
+        json!({
+            "description": "Review a file",
+            "messages": [{
+                "role": "user",
+                "content": {
+                    "type": "text",
+                    "text": "Review src/lib.rs focusing on error handling.\n"
+                }
+            }]
+        })
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn thread_resource_templates_are_listed() -> anyhow::Result<()> {
+    let codex_home = TempDir::new()?;
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+
+    let request_id = mcp.send_list_resource_templates_request().await?;
+    let response = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Number(request_id)),
+    )
+    .await??;
+    let uri_templates: Vec<_> = response.result["resourceTemplates"]
+        .as_array()
+        .cloned()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|template| template["uriTemplate"].clone())
+        .collect();
+    assert_eq!(
+        uri_templates,
+        vec![
+            json!("codex://thread/{thread_id}"),
+            json!("codex://thread/{thread_id}/diff"),
+        ]
+    );
+
+    Ok(())
+}
@@ -1,9 +1,6 @@
 use crate::config::NetworkMode;
 use crate::config::NetworkProxyConfig;
 use crate::policy::Host;
-use crate::policy::is_loopback_host;
-use crate::policy::is_non_public_ip;
-use crate::policy::normalize_host;
 use crate::reasons::REASON_DENIED;
 use crate::reasons::REASON_NOT_ALLOWED;
 use crate::reasons::REASON_NOT_ALLOWED_LOCAL;
@@ -13,25 +10,21 @@ use crate::state::validate_policy_against_constraints;
 use anyhow::Context;
 use anyhow::Result;
 use codex_utils_absolute_path::AbsolutePathBuf;
+use codex_utils_host_policy::is_blocked_local_host;
 use globset::GlobSet;
 use serde::Serialize;
 use std::collections::HashSet;
 use std::collections::VecDeque;
-use std::net::IpAddr;
 use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
-use std::time::Duration;
 use std::time::SystemTime;
 use time::OffsetDateTime;
-use tokio::net::lookup_host;
 use tokio::sync::RwLock;
-use tokio::time::timeout;
 use tracing::info;
 use tracing::warn;
 
 const MAX_BLOCKED_EVENTS: usize = 200;
-const DNS_LOOKUP_TIMEOUT: Duration = Duration::from_secs(2);
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum HostBlockReason {
@@ -226,36 +219,8 @@ impl NetworkProxyState {
         }
 
         let is_allowlisted = allow_set.is_match(host_str);
-        if !allow_local_binding {
-            // If the intent is "prevent access to local/internal networks", we must not rely solely
-            // on string checks like `localhost` / `127.0.0.1`. Attackers can use DNS rebinding or
-            // public suffix services that map hostnames onto private IPs.
-            //
-            // We therefore do a best-effort DNS + IP classification check before allowing the
-            // request. Explicit local/loopback literals are allowed only when explicitly
-            // allowlisted; hostnames that resolve to local/private IPs are blocked even if
-            // allowlisted.
-            let local_literal = {
-                let host_no_scope = host_str
-                    .split_once('%')
-                    .map(|(ip, _)| ip)
-                    .unwrap_or(host_str);
-                if is_loopback_host(&host) {
-                    true
-                } else if let Ok(ip) = host_no_scope.parse::<IpAddr>() {
-                    is_non_public_ip(ip)
-                } else {
-                    false
-                }
-            };
-
-            if local_literal {
-                if !is_explicit_local_allowlisted(&allowed_domains, &host) {
-                    return Ok(HostBlockDecision::Blocked(HostBlockReason::NotAllowedLocal));
-                }
-            } else if host_resolves_to_non_public_ip(host_str, port).await {
-                return Ok(HostBlockDecision::Blocked(HostBlockReason::NotAllowedLocal));
-            }
+        if !allow_local_binding && is_blocked_local_host(host_str, port, &allowed_domains).await {
+            return Ok(HostBlockDecision::Blocked(HostBlockReason::NotAllowedLocal));
         }
 
         if allowed_domains_empty || !is_allowlisted {
@@ -392,28 +357,6 @@ pub(crate) fn unix_socket_permissions_supported() -> bool {
     cfg!(target_os = "macos")
 }
 
-async fn host_resolves_to_non_public_ip(host: &str, port: u16) -> bool {
-    if let Ok(ip) = host.parse::<IpAddr>() {
-        return is_non_public_ip(ip);
-    }
-
-    // If DNS lookup fails, default to "not local/private" rather than blocking. In practice, the
-    // subsequent connect attempt will fail anyway, and blocking on transient resolver issues would
-    // make the proxy fragile. The allowlist/denylist remains the primary control plane.
-    let addrs = match timeout(DNS_LOOKUP_TIMEOUT, lookup_host((host, port))).await {
-        Ok(Ok(addrs)) => addrs,
-        Ok(Err(_)) | Err(_) => return false,
-    };
-
-    for addr in addrs {
-        if is_non_public_ip(addr.ip()) {
-            return true;
-        }
-    }
-
-    false
-}
-
 fn log_policy_changes(previous: &NetworkProxyConfig, next: &NetworkProxyConfig) {
     log_domain_list_changes(
         "allowlist",
@@ -463,20 +406,6 @@ fn log_domain_list_changes(list_name: &str, previous: &[String], next: &[String]
     }
 }
 
-fn is_explicit_local_allowlisted(allowed_domains: &[String], host: &Host) -> bool {
-    let normalized_host = host.as_str();
-    allowed_domains.iter().any(|pattern| {
-        let pattern = pattern.trim();
-        if pattern == "*" || pattern.starts_with("*.") || pattern.starts_with("**.") {
-            return false;
-        }
-        if pattern.contains('*') || pattern.contains('?') {
-            return false;
-        }
-        normalize_host(pattern) == normalized_host
-    })
-}
-
 fn unix_timestamp() -> i64 {
     OffsetDateTime::now_utc().unix_timestamp()
 }
@@ -40,8 +40,8 @@ use tracing_subscriber::Layer;
 use tracing_subscriber::registry::LookupSpan;
 
 const ENV_ATTRIBUTE: &str = "env";
-const TRACEPARENT_ENV_VAR: &str = "TRACEPARENT";
-const TRACESTATE_ENV_VAR: &str = "TRACESTATE";
+pub(crate) const TRACEPARENT_ENV_VAR: &str = "TRACEPARENT";
+pub(crate) const TRACESTATE_ENV_VAR: &str = "TRACESTATE";
 static TRACEPARENT_CONTEXT: OnceLock<Option<Context>> = OnceLock::new();
 
 thread_local! {
@@ -16,6 +16,7 @@ use crate::metrics::names::WEBSOCKET_EVENT_DURATION_METRIC;
 use crate::metrics::names::WEBSOCKET_REQUEST_COUNT_METRIC;
 use crate::metrics::names::WEBSOCKET_REQUEST_DURATION_METRIC;
 use crate::otel_provider::traceparent_context_from_env;
+use crate::traces::spans::record_approval_decision;
 use chrono::SecondsFormat;
 use chrono::Utc;
 use codex_api::ApiError;
@@ -585,6 +586,7 @@ impl OtelManager {
             decision = %decision.clone().to_string().to_lowercase(),
             source = %source.to_string(),
         );
+        record_approval_decision(&Span::current(), decision, &source);
     }
 
     pub async fn log_tool_result_with_tags<F, Fut, E>(
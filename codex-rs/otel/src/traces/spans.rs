@@ -0,0 +1,168 @@
+//! Span hierarchy for a Codex session and W3C trace-context propagation.
+//!
+//! `codex.session` → `codex.turn` → `codex.model_request` / `codex.tool_call`,
+//! and under a tool call `codex.mcp_call`, `codex.exec` and `codex.patch_apply`.
+//! Spans are plain `tracing` spans; `OtelProvider::tracing_layer` exports them.
+//! Fields that are only known once the work finishes are declared `Empty` and
+//! filled in by the `record_*` helpers.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use codex_protocol::protocol::ReviewDecision;
+use codex_protocol::protocol::TokenUsage;
+use opentelemetry::propagation::TextMapPropagator;
+use opentelemetry::trace::TraceContextExt;
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use tracing::Span;
+use tracing::field::Empty;
+use tracing::info_span;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+use crate::OtelManager;
+use crate::ToolDecisionSource;
+use crate::otel_provider::TRACEPARENT_ENV_VAR;
+use crate::otel_provider::TRACESTATE_ENV_VAR;
+
+pub const TRACEPARENT_HEADER: &str = "traceparent";
+pub const TRACESTATE_HEADER: &str = "tracestate";
+
+impl OtelManager {
+    /// Root span for a session. Continues the trace from `TRACEPARENT` when
+    /// one was inherited from the environment.
+    pub fn session_span(&self) -> Span {
+        let span = info_span!(
+            "codex.session",
+            conversation.id = %self.metadata.conversation_id,
+            model = %self.metadata.model,
+            originator = %self.metadata.originator,
+            session_source = %self.metadata.session_source,
+            app.version = %self.metadata.app_version,
+        );
+        self.apply_traceparent_parent(&span);
+        span
+    }
+}
+
+pub fn turn_span(turn_id: &str, model: &str) -> Span {
+    info_span!("codex.turn", turn.id = %turn_id, model = %model)
+}
+
+pub fn model_request_span(turn_id: &str, model: &str) -> Span {
+    info_span!(
+        "codex.model_request",
+        turn.id = %turn_id,
+        model = %model,
+        input_tokens = Empty,
+        cached_input_tokens = Empty,
+        output_tokens = Empty,
+        reasoning_output_tokens = Empty,
+        total_tokens = Empty,
+    )
+}
+
+pub fn tool_call_span(tool_name: &str, call_id: &str) -> Span {
+    info_span!(
+        "codex.tool_call",
+        tool_name = %tool_name,
+        call_id = %call_id,
+        aborted = false,
+        approval.decision = Empty,
+        approval.source = Empty,
+        success = Empty,
+        duration_ms = Empty,
+    )
+}
+
+pub fn mcp_call_span(server: &str, tool: &str, call_id: &str) -> Span {
+    info_span!(
+        "codex.mcp_call",
+        mcp.server = %server,
+        mcp.tool = %tool,
+        call_id = %call_id,
+        approval.decision = Empty,
+        approval.source = Empty,
+        success = Empty,
+        duration_ms = Empty,
+    )
+}
+
+pub fn exec_span(program: &str) -> Span {
+    info_span!(
+        "codex.exec",
+        process.executable = %program,
+        exit_code = Empty,
+        timed_out = Empty,
+        duration_ms = Empty,
+    )
+}
+
+pub fn patch_apply_span(call_id: &str, files: usize) -> Span {
+    info_span!(
+        "codex.patch_apply",
+        call_id = %call_id,
+        files = files,
+        success = Empty,
+        duration_ms = Empty,
+    )
+}
+
+pub fn record_token_usage(span: &Span, usage: &TokenUsage) {
+    span.record("input_tokens", usage.input_tokens);
+    span.record("cached_input_tokens", usage.cached_input_tokens);
+    span.record("output_tokens", usage.output_tokens);
+    span.record("reasoning_output_tokens", usage.reasoning_output_tokens);
+    span.record("total_tokens", usage.total_tokens);
+}
+
+pub fn record_approval_decision(
+    span: &Span,
+    decision: &ReviewDecision,
+    source: &ToolDecisionSource,
+) {
+    span.record(
+        "approval.decision",
+        decision.to_string().to_lowercase().as_str(),
+    );
+    span.record("approval.source", source.to_string().as_str());
+}
+
+pub fn record_outcome(span: &Span, success: bool, duration: Duration) {
+    span.record("success", success);
+    span.record("duration_ms", duration_ms(duration));
+}
+
+pub fn record_exec_result(span: &Span, exit_code: i32, timed_out: bool, duration: Duration) {
+    span.record("exit_code", exit_code);
+    span.record("timed_out", timed_out);
+    span.record("duration_ms", duration_ms(duration));
+}
+
+/// W3C `traceparent`/`tracestate` headers for `span`. Empty when `span` is
+/// not exported (no tracing layer installed, or the span is disabled).
+pub fn trace_context_headers(span: &Span) -> HashMap<String, String> {
+    let context = span.context();
+    let mut headers = HashMap::new();
+    if context.span().span_context().is_valid() {
+        TraceContextPropagator::new().inject_context(&context, &mut headers);
+    }
+    headers.retain(|_, value| !value.is_empty());
+    headers
+}
+
+/// `TRACEPARENT`/`TRACESTATE` environment variables that let a child process
+/// continue the trace from `span`.
+pub fn trace_context_env(span: &Span) -> HashMap<String, String> {
+    trace_context_headers(span)
+        .into_iter()
+        .filter_map(|(key, value)| match key.as_str() {
+            TRACEPARENT_HEADER => Some((TRACEPARENT_ENV_VAR.to_string(), value)),
+            TRACESTATE_HEADER => Some((TRACESTATE_ENV_VAR.to_string(), value)),
+            _ => None,
+        })
+        .collect()
+}
+
+fn duration_ms(duration: Duration) -> i64 {
+    i64::try_from(duration.as_millis()).unwrap_or(i64::MAX)
+}
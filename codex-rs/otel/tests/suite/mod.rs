@@ -4,4 +4,5 @@ mod runtime_summary;
 mod send;
 mod snapshot;
 mod timing;
+mod traces;
 mod validation;
@@ -0,0 +1,143 @@
+use codex_otel::OtelManager;
+use codex_otel::ToolDecisionSource;
+use codex_otel::traces::spans::exec_span;
+use codex_otel::traces::spans::model_request_span;
+use codex_otel::traces::spans::record_exec_result;
+use codex_otel::traces::spans::record_token_usage;
+use codex_otel::traces::spans::tool_call_span;
+use codex_otel::traces::spans::trace_context_env;
+use codex_otel::traces::spans::trace_context_headers;
+use codex_otel::traces::spans::turn_span;
+use codex_protocol::ThreadId;
+use codex_protocol::protocol::ReviewDecision;
+use codex_protocol::protocol::SessionSource;
+use codex_protocol::protocol::TokenUsage;
+use opentelemetry::Value;
+use opentelemetry::trace::SpanId;
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry_sdk::trace::InMemorySpanExporter;
+use opentelemetry_sdk::trace::SdkTracerProvider;
+use opentelemetry_sdk::trace::SpanData;
+use pretty_assertions::assert_eq;
+use std::collections::HashMap;
+use std::time::Duration;
+use tracing::Span;
+use tracing_subscriber::layer::SubscriberExt;
+
+fn manager() -> OtelManager {
+    OtelManager::new(
+        ThreadId::new(),
+        "gpt-5.1",
+        "gpt-5.1",
+        None,
+        None,
+        None,
+        "test_originator".to_string(),
+        false,
+        "tty".to_string(),
+        SessionSource::Cli,
+    )
+}
+
+fn span<'a>(spans: &'a [SpanData], name: &str) -> &'a SpanData {
+    spans
+        .iter()
+        .find(|span| span.name == name)
+        .unwrap_or_else(|| panic!("span {name} missing"))
+}
+
+fn attribute(span: &SpanData, key: &str) -> Option<Value> {
+    span.attributes
+        .iter()
+        .find(|kv| kv.key.as_str() == key)
+        .map(|kv| kv.value.clone())
+}
+
+// Ensures a turn forms one trace: session → turn → model request / tool call → exec.
+#[test]
+fn turn_spans_form_a_single_trace() {
+    let exporter = InMemorySpanExporter::default();
+    let provider = SdkTracerProvider::builder()
+        .with_simple_exporter(exporter.clone())
+        .build();
+    let subscriber = tracing_subscriber::registry()
+        .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("codex-test")));
+    let manager = manager();
+
+    let child_env = tracing::subscriber::with_default(subscriber, || {
+        let session = manager.session_span();
+        let _session = session.enter();
+        let turn = turn_span("turn-1", "gpt-5.1");
+        let _turn = turn.enter();
+
+        let request = model_request_span("turn-1", "gpt-5.1");
+        record_token_usage(
+            &request,
+            &TokenUsage {
+                input_tokens: 120,
+                cached_input_tokens: 20,
+                output_tokens: 30,
+                reasoning_output_tokens: 10,
+                total_tokens: 150,
+            },
+        );
+        drop(request);
+
+        let tool = tool_call_span("shell", "call-1");
+        let _tool = tool.enter();
+        manager.tool_decision(
+            "shell",
+            "call-1",
+            &ReviewDecision::Approved,
+            ToolDecisionSource::User,
+        );
+        let exec = exec_span("bash");
+        record_exec_result(&exec, 2, false, Duration::from_millis(15));
+        trace_context_env(&exec)
+    });
+    provider.force_flush().expect("flush spans");
+
+    let spans = exporter.get_finished_spans().expect("finished spans");
+    let session = span(&spans, "codex.session");
+    let turn = span(&spans, "codex.turn");
+    let request = span(&spans, "codex.model_request");
+    let tool = span(&spans, "codex.tool_call");
+    let exec = span(&spans, "codex.exec");
+
+    let trace_id = session.span_context.trace_id();
+    for span in [turn, request, tool, exec] {
+        assert_eq!(span.span_context.trace_id(), trace_id);
+    }
+    assert_eq!(session.parent_span_id, SpanId::INVALID);
+    assert_eq!(turn.parent_span_id, session.span_context.span_id());
+    assert_eq!(request.parent_span_id, turn.span_context.span_id());
+    assert_eq!(tool.parent_span_id, turn.span_context.span_id());
+    assert_eq!(exec.parent_span_id, tool.span_context.span_id());
+
+    assert_eq!(attribute(request, "input_tokens"), Some(Value::I64(120)));
+    assert_eq!(attribute(request, "total_tokens"), Some(Value::I64(150)));
+    assert_eq!(
+        attribute(tool, "approval.decision"),
+        Some(Value::from("approved"))
+    );
+    assert_eq!(attribute(exec, "exit_code"), Some(Value::I64(2)));
+    assert_eq!(attribute(exec, "duration_ms"), Some(Value::I64(15)));
+
+    assert_eq!(
+        child_env,
+        HashMap::from([(
+            "TRACEPARENT".to_string(),
+            format!(
+                "00-{}-{}-01",
+                exec.span_context.trace_id(),
+                exec.span_context.span_id()
+            ),
+        )])
+    );
+}
+
+#[test]
+fn trace_context_is_empty_without_an_exported_span() {
+    assert_eq!(trace_context_headers(&Span::none()), HashMap::new());
+    assert_eq!(trace_context_env(&Span::current()), HashMap::new());
+}
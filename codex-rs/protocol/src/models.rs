@@ -1,7 +1,9 @@
 use std::collections::HashMap;
 use std::path::Path;
 
-use codex_utils_image::load_and_resize_to_fit;
+use codex_utils_image::ImageSelection;
+use codex_utils_image::load_image_parts;
+use codex_utils_image::split_page_suffix;
 use serde::Deserialize;
 use serde::Deserializer;
 use serde::Serialize;
@@ -302,7 +304,7 @@ impl DeveloperInstructions {
 
         let (sandbox_mode, writable_roots) = match sandbox_policy {
             SandboxPolicy::DangerFullAccess => (SandboxMode::DangerFullAccess, None),
-            SandboxPolicy::ReadOnly => (SandboxMode::ReadOnly, None),
+            SandboxPolicy::ReadOnly { .. } => (SandboxMode::ReadOnly, None),
             SandboxPolicy::ExternalSandbox { .. } => (SandboxMode::DangerFullAccess, None),
             SandboxPolicy::WorkspaceWrite { .. } => {
                 let roots = sandbox_policy.get_writable_roots_with_cwd(cwd);
@@ -519,6 +521,12 @@ pub fn local_image_open_tag_text(label_number: usize) -> String {
     format!("{LOCAL_IMAGE_OPEN_TAG_PREFIX}{label}{LOCAL_IMAGE_OPEN_TAG_SUFFIX}")
 }
 
+/// Open tag for one page or tile of a local image, e.g.
+/// `<image name=[Image #1, page 2 of 5]>`.
+pub fn local_image_named_open_tag_text(name: &str) -> String {
+    format!("{LOCAL_IMAGE_OPEN_TAG_PREFIX}[{name}]{LOCAL_IMAGE_OPEN_TAG_SUFFIX}")
+}
+
 pub fn is_local_image_open_tag_text(text: &str) -> bool {
     text.strip_prefix(LOCAL_IMAGE_OPEN_TAG_PREFIX)
         .is_some_and(|rest| rest.ends_with(LOCAL_IMAGE_OPEN_TAG_SUFFIX))
@@ -563,21 +571,33 @@ pub fn local_image_content_items_with_label_number(
     path: &std::path::Path,
     label_number: Option<usize>,
 ) -> Vec<ContentItem> {
-    match load_and_resize_to_fit(path) {
-        Ok(image) => {
-            let mut items = Vec::with_capacity(3);
-            if let Some(label_number) = label_number {
-                items.push(ContentItem::InputText {
-                    text: local_image_open_tag_text(label_number),
-                });
-            }
-            items.push(ContentItem::InputImage {
-                image_url: image.into_data_url(),
-            });
-            if label_number.is_some() {
-                items.push(ContentItem::InputText {
-                    text: LOCAL_IMAGE_CLOSE_TAG.to_string(),
+    // `design.pdf#pages=2-3` attaches only those pages.
+    let (file_path, pages) = split_page_suffix(path);
+    let selection = ImageSelection {
+        pages,
+        region: None,
+    };
+    match load_image_parts(&file_path, &selection) {
+        Ok(parts) => {
+            let mut items = Vec::with_capacity(parts.len() * 3);
+            for part in parts {
+                if let Some(label_number) = label_number {
+                    let text = match &part.label {
+                        Some(part_label) => local_image_named_open_tag_text(&format!(
+                            "Image #{label_number}, {part_label}"
+                        )),
+                        None => local_image_open_tag_text(label_number),
+                    };
+                    items.push(ContentItem::InputText { text });
+                }
+                items.push(ContentItem::InputImage {
+                    image_url: part.image.into_data_url(),
                 });
+                if label_number.is_some() {
+                    items.push(ContentItem::InputText {
+                        text: LOCAL_IMAGE_CLOSE_TAG.to_string(),
+                    });
+                }
             }
             items
         }
@@ -749,6 +769,10 @@ pub struct ShellToolCallParams {
     pub prefix_rule: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub justification: Option<String>,
+    /// Named sandbox profile to run this command under.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub sandbox_profile: Option<String>,
 }
 
 /// If the `name` of a `ResponseItem::FunctionCall` is `shell_command`, the
@@ -772,6 +796,10 @@ pub struct ShellCommandToolCallParams {
     pub prefix_rule: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub justification: Option<String>,
+    /// Named sandbox profile to run this command under.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub sandbox_profile: Option<String>,
 }
 
 /// Responses API compatible content items that can be returned by a tool call.
@@ -1214,8 +1242,10 @@ mod tests {
         let policy = SandboxPolicy::WorkspaceWrite {
             writable_roots: vec![],
             network_access: true,
+            allowed_loopback_ports: Vec::new(),
             exclude_tmpdir_env_var: false,
             exclude_slash_tmp: false,
+            deny_read: Vec::new(),
         };
 
         let instructions = DeveloperInstructions::from_policy(
@@ -1565,6 +1595,7 @@ mod tests {
                 sandbox_permissions: None,
                 prefix_rule: None,
                 justification: None,
+                sandbox_profile: None,
             },
             params
         );
@@ -1667,23 +1698,19 @@ mod tests {
     #[test]
     fn local_image_unsupported_image_format_adds_placeholder() -> Result<()> {
         let dir = tempdir()?;
-        let svg_path = dir.path().join("example.svg");
-        std::fs::write(
-            &svg_path,
-            br#"<?xml version="1.0" encoding="UTF-8"?>
-<svg xmlns="http://www.w3.org/2000/svg" width="1" height="1"></svg>"#,
-        )?;
+        let gif_path = dir.path().join("example.gif");
+        std::fs::write(&gif_path, b"GIF89a\x01\x00\x01\x00\x00\x00\x00;")?;
 
         let item = ResponseInputItem::from(vec![UserInput::LocalImage {
-            path: svg_path.clone(),
+            path: gif_path.clone(),
         }]);
 
         match item {
             ResponseInputItem::Message { content, .. } => {
                 assert_eq!(content.len(), 1);
                 let expected = format!(
-                    "Codex cannot attach image at `{}`: unsupported image format `image/svg+xml`.",
-                    svg_path.display()
+                    "Codex cannot attach image at `{}`: unsupported image format `image/gif`.",
+                    gif_path.display()
                 );
                 match &content[0] {
                     ContentItem::InputText { text } => assert_eq!(text, &expected),
@@ -1695,4 +1722,33 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn local_svg_is_rendered_with_tags() -> Result<()> {
+        let dir = tempdir()?;
+        let svg_path = dir.path().join("example.svg");
+        std::fs::write(
+            &svg_path,
+            br#"<?xml version="1.0" encoding="UTF-8"?>
+<svg xmlns="http://www.w3.org/2000/svg" width="1" height="1"></svg>"#,
+        )?;
+
+        let content = local_image_content_items_with_label_number(&svg_path, Some(1));
+        assert_eq!(content.len(), 3);
+        assert_eq!(
+            content[0],
+            ContentItem::InputText {
+                text: local_image_open_tag_text(1),
+            }
+        );
+        assert!(matches!(
+            &content[1],
+            ContentItem::InputImage { image_url } if image_url.starts_with("data:image/png;base64,")
+        ));
+        assert!(is_local_image_open_tag_text(
+            &local_image_named_open_tag_text("Image #1, page 2 of 5")
+        ));
+
+        Ok(())
+    }
 }
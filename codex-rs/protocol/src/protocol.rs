@@ -48,6 +48,7 @@ use serde_with::serde_as;
 use strum_macros::Display;
 use tracing::error;
 use ts_rs::TS;
+use wildmatch::WildMatch;
 
 pub use crate::approvals::ApplyPatchApprovalRequestEvent;
 pub use crate::approvals::ElicitationAction;
@@ -174,6 +175,13 @@ pub enum Op {
         #[serde(skip_serializing_if = "Option::is_none")]
         sandbox_policy: Option<SandboxPolicy>,
 
+        /// Named `[sandbox_profiles]` entry to run tool calls under. Its
+        /// policy replaces `sandbox_policy`, and a `network = "proxy"`
+        /// profile sends commands through its proxy. Setting `sandbox_policy`
+        /// without a profile leaves the current profile.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        sandbox_profile: Option<String>,
+
         /// Updated Windows sandbox mode for tool execution.
         #[serde(skip_serializing_if = "Option::is_none")]
         windows_sandbox_level: Option<WindowsSandboxLevel>,
@@ -436,9 +444,19 @@ pub enum SandboxPolicy {
     #[serde(rename = "danger-full-access")]
     DangerFullAccess,
 
-    /// Read-only access to the entire file-system.
+    /// Read-only access to the entire file-system, except for `deny_read`.
     #[serde(rename = "read-only")]
-    ReadOnly,
+    ReadOnly {
+        /// Folders that stay writable, e.g. `/tmp` for test runs. Unlike
+        /// `WorkspaceWrite`, neither cwd nor the temp dirs are added
+        /// implicitly.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        writable_roots: Vec<AbsolutePathBuf>,
+
+        /// Paths the sandbox may not read. See [`SandboxPolicy::get_deny_read_paths_with_cwd`].
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        deny_read: Vec<String>,
+    },
 
     /// Indicates the process is already in an external sandbox. Allows full
     /// disk access while honoring the provided network setting.
@@ -463,6 +481,11 @@ pub enum SandboxPolicy {
         #[serde(default)]
         network_access: bool,
 
+        /// TCP ports on loopback that stay reachable when `network_access` is
+        /// `false`, e.g. a local database or dev server.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        allowed_loopback_ports: Vec<u16>,
+
         /// When set to `true`, will NOT include the per-user `TMPDIR`
         /// environment variable among the default writable roots. Defaults to
         /// `false`.
@@ -473,6 +496,11 @@ pub enum SandboxPolicy {
         /// writable roots on UNIX. Defaults to `false`.
         #[serde(default)]
         exclude_slash_tmp: bool,
+
+        /// Paths the sandbox may not read, even under a writable root. See
+        /// [`SandboxPolicy::get_deny_read_paths_with_cwd`].
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        deny_read: Vec<String>,
     },
 }
 
@@ -518,7 +546,10 @@ impl FromStr for SandboxPolicy {
 impl SandboxPolicy {
     /// Returns a policy with read-only disk access and no network.
     pub fn new_read_only_policy() -> Self {
-        SandboxPolicy::ReadOnly
+        SandboxPolicy::ReadOnly {
+            writable_roots: Vec::new(),
+            deny_read: Vec::new(),
+        }
     }
 
     /// Returns a policy that can read the entire disk, but can only write to
@@ -528,21 +559,70 @@ impl SandboxPolicy {
         SandboxPolicy::WorkspaceWrite {
             writable_roots: vec![],
             network_access: false,
+            allowed_loopback_ports: Vec::new(),
             exclude_tmpdir_env_var: false,
             exclude_slash_tmp: false,
+            deny_read: Vec::new(),
         }
     }
 
-    /// Always returns `true`; restricting read access is not supported.
+    /// Returns `false` when the policy denies reading any paths.
     pub fn has_full_disk_read_access(&self) -> bool {
-        true
+        self.deny_read().is_empty()
+    }
+
+    /// The configured `deny_read` entries, unresolved.
+    pub fn deny_read(&self) -> &[String] {
+        match self {
+            SandboxPolicy::DangerFullAccess | SandboxPolicy::ExternalSandbox { .. } => &[],
+            SandboxPolicy::ReadOnly { deny_read, .. } => deny_read,
+            SandboxPolicy::WorkspaceWrite { deny_read, .. } => deny_read,
+        }
+    }
+
+    /// Replaces the `deny_read` entries. No-op for policies without a
+    /// filesystem sandbox.
+    pub fn set_deny_read(&mut self, entries: Vec<String>) {
+        match self {
+            SandboxPolicy::DangerFullAccess | SandboxPolicy::ExternalSandbox { .. } => {}
+            SandboxPolicy::ReadOnly { deny_read, .. } => *deny_read = entries,
+            SandboxPolicy::WorkspaceWrite { deny_read, .. } => *deny_read = entries,
+        }
+    }
+
+    /// Resolves `deny_read` into the existing paths the sandbox must hide.
+    ///
+    /// `~` and a leading `~/` expand to the home directory and relative
+    /// entries are resolved against `cwd`. The last component may use `*` and
+    /// `?` wildcards (e.g. `.env*`), matched against the entries of its parent
+    /// directory when the policy is resolved. Paths that do not exist are
+    /// skipped, since there is nothing to hide.
+    ///
+    /// Sandboxes call this when each command starts, so files created while a
+    /// command runs are not hidden from it.
+    pub fn get_deny_read_paths_with_cwd(&self, cwd: &Path) -> Vec<AbsolutePathBuf> {
+        let mut paths: Vec<AbsolutePathBuf> = Vec::new();
+        for entry in self.deny_read() {
+            for path in resolve_deny_read_entry(entry, cwd) {
+                // Hide symlink targets too, so the data is unreachable by any name.
+                let path = std::fs::canonicalize(&path).unwrap_or(path);
+                match AbsolutePathBuf::from_absolute_path(&path) {
+                    Ok(path) if !paths.contains(&path) => paths.push(path),
+                    Ok(_) => {}
+                    Err(e) => {
+                        error!("Ignoring invalid deny_read path {path:?}: {e}");
+                    }
+                }
+            }
+        }
+        paths
     }
 
     pub fn has_full_disk_write_access(&self) -> bool {
         match self {
             SandboxPolicy::DangerFullAccess => true,
             SandboxPolicy::ExternalSandbox { .. } => true,
-            SandboxPolicy::ReadOnly => false,
+            SandboxPolicy::ReadOnly { .. } => false,
             SandboxPolicy::WorkspaceWrite { .. } => false,
         }
     }
@@ -551,11 +631,55 @@ impl SandboxPolicy {
         match self {
             SandboxPolicy::DangerFullAccess => true,
             SandboxPolicy::ExternalSandbox { network_access } => network_access.is_enabled(),
-            SandboxPolicy::ReadOnly => false,
+            SandboxPolicy::ReadOnly { .. } => false,
             SandboxPolicy::WorkspaceWrite { network_access, .. } => *network_access,
         }
     }
 
+    /// Loopback TCP ports reachable without full network access. Empty when
+    /// the policy already allows full network access.
+    pub fn allowed_loopback_ports(&self) -> &[u16] {
+        match self {
+            SandboxPolicy::WorkspaceWrite {
+                network_access: false,
+                allowed_loopback_ports,
+                ..
+            } => allowed_loopback_ports,
+            _ => &[],
+        }
+    }
+
+    /// Returns `true` when `self` allows something `current` does not:
+    /// network access, or writes outside of `current`'s writable roots.
+    /// Switching from `current` to such a policy is an escalation.
+    pub fn grants_more_than(&self, current: &SandboxPolicy, cwd: &Path) -> bool {
+        if !current.has_full_network_access() {
+            if self.has_full_network_access() {
+                return true;
+            }
+            let current_ports = current.allowed_loopback_ports();
+            if self
+                .allowed_loopback_ports()
+                .iter()
+                .any(|port| !current_ports.contains(port))
+            {
+                return true;
+            }
+        }
+        if current.has_full_disk_write_access() {
+            return false;
+        }
+        if self.has_full_disk_write_access() {
+            return true;
+        }
+        let current_roots = current.get_writable_roots_with_cwd(cwd);
+        self.get_writable_roots_with_cwd(cwd).iter().any(|root| {
+            !current_roots
+                .iter()
+                .any(|current_root| current_root.is_path_writable(root.root.as_path()))
+        })
+    }
+
     /// Returns the list of writable roots (tailored to the current working
     /// directory) together with subpaths that should remain read‑only under
     /// each writable root.
@@ -563,12 +687,16 @@ impl SandboxPolicy {
         match self {
             SandboxPolicy::DangerFullAccess => Vec::new(),
             SandboxPolicy::ExternalSandbox { .. } => Vec::new(),
-            SandboxPolicy::ReadOnly => Vec::new(),
+            SandboxPolicy::ReadOnly { writable_roots, .. } => {
+                writable_roots_with_read_only_subpaths(writable_roots.clone())
+            }
             SandboxPolicy::WorkspaceWrite {
                 writable_roots,
                 exclude_tmpdir_env_var,
                 exclude_slash_tmp,
                 network_access: _,
+                allowed_loopback_ports: _,
+                deny_read: _,
             } => {
                 // Start from explicitly configured writable roots.
                 let mut roots: Vec<AbsolutePathBuf> = writable_roots.clone();
@@ -623,55 +751,111 @@ impl SandboxPolicy {
                     }
                 }
 
-                // For each root, compute subpaths that should remain read-only.
-                roots
-                    .into_iter()
-                    .map(|writable_root| {
-                        let mut subpaths: Vec<AbsolutePathBuf> = Vec::new();
-                        #[allow(clippy::expect_used)]
-                        let top_level_git = writable_root
-                            .join(".git")
-                            .expect(".git is a valid relative path");
-                        // This applies to typical repos (directory .git), worktrees/submodules
-                        // (file .git with gitdir pointer), and bare repos when the gitdir is the
-                        // writable root itself.
-                        let top_level_git_is_file = top_level_git.as_path().is_file();
-                        let top_level_git_is_dir = top_level_git.as_path().is_dir();
-                        if top_level_git_is_dir || top_level_git_is_file {
-                            if top_level_git_is_file
-                                && is_git_pointer_file(&top_level_git)
-                                && let Some(gitdir) = resolve_gitdir_from_file(&top_level_git)
-                                && !subpaths
-                                    .iter()
-                                    .any(|subpath| subpath.as_path() == gitdir.as_path())
-                            {
-                                subpaths.push(gitdir);
-                            }
-                            subpaths.push(top_level_git);
-                        }
-
-                        // Make .agents/skills and .codex/config.toml and
-                        // related files read-only to the agent, by default.
-                        for subdir in &[".agents", ".codex"] {
-                            #[allow(clippy::expect_used)]
-                            let top_level_codex =
-                                writable_root.join(subdir).expect("valid relative path");
-                            if top_level_codex.as_path().is_dir() {
-                                subpaths.push(top_level_codex);
-                            }
-                        }
-
-                        WritableRoot {
-                            root: writable_root,
-                            read_only_subpaths: subpaths,
-                        }
-                    })
-                    .collect()
+                writable_roots_with_read_only_subpaths(roots)
             }
         }
     }
 }
 
+fn resolve_deny_read_entry(entry: &str, cwd: &Path) -> Vec<PathBuf> {
+    let home_relative = if entry == "~" {
+        Some("")
+    } else {
+        entry.strip_prefix("~/")
+    };
+    let path = match home_relative {
+        Some(rest) => match std::env::home_dir() {
+            Some(home) if rest.is_empty() => home,
+            Some(home) => home.join(rest),
+            None => {
+                error!("Ignoring deny_read entry {entry:?}: home directory is unknown");
+                return Vec::new();
+            }
+        },
+        None => cwd.join(entry),
+    };
+    let Some(file_name) = path.file_name().and_then(OsStr::to_str) else {
+        return if path.exists() {
+            vec![path]
+        } else {
+            Vec::new()
+        };
+    };
+    if !file_name.contains(['*', '?']) {
+        return if path.exists() {
+            vec![path]
+        } else {
+            Vec::new()
+        };
+    }
+    let Some(parent) = path.parent() else {
+        return Vec::new();
+    };
+    let Ok(read_dir) = std::fs::read_dir(parent) else {
+        return Vec::new();
+    };
+    let pattern = WildMatch::new(file_name);
+    let mut matches: Vec<PathBuf> = read_dir
+        .filter_map(Result::ok)
+        .filter(|dir_entry| {
+            dir_entry
+                .file_name()
+                .to_str()
+                .is_some_and(|name| pattern.matches(name))
+        })
+        .map(|dir_entry| dir_entry.path())
+        .collect();
+    matches.sort();
+    matches
+}
+
+/// Pairs each writable root with the subpaths that should remain read-only
+/// under it.
+fn writable_roots_with_read_only_subpaths(roots: Vec<AbsolutePathBuf>) -> Vec<WritableRoot> {
+    roots
+        .into_iter()
+        .map(|writable_root| {
+            let mut subpaths: Vec<AbsolutePathBuf> = Vec::new();
+            #[allow(clippy::expect_used)]
+            let top_level_git = writable_root
+                .join(".git")
+                .expect(".git is a valid relative path");
+            // This applies to typical repos (directory .git), worktrees/submodules
+            // (file .git with gitdir pointer), and bare repos when the gitdir is the
+            // writable root itself.
+            let top_level_git_is_file = top_level_git.as_path().is_file();
+            let top_level_git_is_dir = top_level_git.as_path().is_dir();
+            if top_level_git_is_dir || top_level_git_is_file {
+                if top_level_git_is_file
+                    && is_git_pointer_file(&top_level_git)
+                    && let Some(gitdir) = resolve_gitdir_from_file(&top_level_git)
+                    && !subpaths
+                        .iter()
+                        .any(|subpath| subpath.as_path() == gitdir.as_path())
+                {
+                    subpaths.push(gitdir);
+                }
+                subpaths.push(top_level_git);
+            }
+
+            // Make .agents/skills and .codex/config.toml and
+            // related files read-only to the agent, by default.
+            for subdir in &[".agents", ".codex"] {
+                #[allow(clippy::expect_used)]
+                let top_level_codex = writable_root.join(subdir).expect("valid relative path");
+                if top_level_codex.as_path().is_dir() {
+                    subpaths.push(top_level_codex);
+                }
+            }
+
+            WritableRoot {
+                root: writable_root,
+                read_only_subpaths: subpaths,
+            }
+        })
+        .collect()
+}
+
 fn is_git_pointer_file(path: &AbsolutePathBuf) -> bool {
     path.as_path().is_file() && path.as_path().file_name() == Some(OsStr::new(".git"))
 }
@@ -766,6 +950,10 @@ pub enum EventMsg {
     /// Conversation history was rolled back by dropping the last N user turns.
     ThreadRolledBack(ThreadRolledBackEvent),
 
+    /// An `AGENTS.md` below the project root was added to the instructions
+    /// because the agent read or edited a file in its directory.
+    ProjectDocLoaded(ProjectDocLoadedEvent),
+
     /// Agent has started a turn.
     /// v1 wire format uses `task_started`; accept `turn_started` for v2 interop.
     #[serde(rename = "task_started", alias = "turn_started")]
@@ -818,6 +1006,10 @@ pub enum EventMsg {
     /// Aggregate MCP startup completion summary.
     McpStartupComplete(McpStartupCompleteEvent),
 
+    /// An MCP server reported that its tool or resource list changed and the
+    /// cached tool list has been refreshed.
+    McpToolsChanged(McpToolsChangedEvent),
+
     McpToolCallBegin(McpToolCallBeginEvent),
 
     McpToolCallEnd(McpToolCallEndEvent),
@@ -2100,6 +2292,15 @@ pub struct ThreadRolledBackEvent {
     pub num_turns: u32,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct ProjectDocLoadedEvent {
+    pub path: PathBuf,
+    /// Bytes counted against `project_doc_max_bytes`.
+    pub bytes: u64,
+    /// Whether the file was cut short because the budget ran out.
+    pub truncated: bool,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct StreamErrorEvent {
     pub message: String,
@@ -2207,6 +2408,24 @@ pub struct McpStartupCompleteEvent {
     pub cancelled: Vec<String>,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct McpToolsChangedEvent {
+    /// Server whose tool or resource list changed.
+    pub server: String,
+    /// Which list the server reported as changed.
+    pub kind: McpListChangedKind,
+    /// Number of tools exposed by the server after the refresh.
+    pub tool_count: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, JsonSchema, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(rename_all = "snake_case")]
+pub enum McpListChangedKind {
+    Tools,
+    Resources,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct McpStartupFailure {
     pub server: String,
@@ -2606,6 +2825,108 @@ mod tests {
         assert!(enabled.has_full_network_access());
     }
 
+    #[test]
+    fn deny_read_paths_resolve_globs_and_skip_missing_entries() -> Result<()> {
+        let temp = tempfile::tempdir()?;
+        let cwd = temp.path().canonicalize()?;
+        std::fs::write(cwd.join(".env"), "")?;
+        std::fs::write(cwd.join(".env.local"), "")?;
+        std::fs::write(cwd.join("README.md"), "")?;
+        std::fs::create_dir(cwd.join("secrets"))?;
+
+        let mut policy = SandboxPolicy::new_read_only_policy();
+        assert!(policy.has_full_disk_read_access());
+        policy.set_deny_read(vec![
+            ".env*".to_string(),
+            "secrets".to_string(),
+            "missing".to_string(),
+        ]);
+        assert!(!policy.has_full_disk_read_access());
+
+        let denied: Vec<PathBuf> = policy
+            .get_deny_read_paths_with_cwd(&cwd)
+            .into_iter()
+            .map(|path| path.as_path().to_path_buf())
+            .collect();
+        assert_eq!(
+            denied,
+            vec![
+                cwd.join(".env"),
+                cwd.join(".env.local"),
+                cwd.join("secrets")
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn deny_read_paths_expand_bare_tilde_to_home() {
+        let Some(home) = std::env::home_dir().filter(|home| home.exists()) else {
+            return;
+        };
+        let home = home.canonicalize().unwrap_or(home);
+        let cwd = Path::new("/nonexistent");
+
+        let mut policy = SandboxPolicy::new_read_only_policy();
+        policy.set_deny_read(vec!["~".to_string()]);
+
+        let denied: Vec<PathBuf> = policy
+            .get_deny_read_paths_with_cwd(cwd)
+            .into_iter()
+            .map(|path| path.as_path().to_path_buf())
+            .collect();
+        assert_eq!(denied, vec![home]);
+    }
+
+    #[test]
+    fn grants_more_than_compares_network_and_writable_roots() -> Result<()> {
+        let temp = tempfile::tempdir()?;
+        let cwd = temp.path().canonicalize()?;
+        let target = AbsolutePathBuf::from_absolute_path(cwd.join("target"))?;
+        // Outside of cwd and of the temp dirs workspace-write adds.
+        let elsewhere = AbsolutePathBuf::from_absolute_path("/nonexistent/elsewhere")?;
+
+        let read_only_target = SandboxPolicy::ReadOnly {
+            writable_roots: vec![target],
+            deny_read: Vec::new(),
+        };
+        let read_only_elsewhere = SandboxPolicy::ReadOnly {
+            writable_roots: vec![elsewhere],
+            deny_read: Vec::new(),
+        };
+        let workspace_write = SandboxPolicy::new_workspace_write_policy();
+        let workspace_write_with_network = SandboxPolicy::WorkspaceWrite {
+            writable_roots: Vec::new(),
+            network_access: true,
+            allowed_loopback_ports: Vec::new(),
+            exclude_tmpdir_env_var: false,
+            exclude_slash_tmp: false,
+            deny_read: Vec::new(),
+        };
+
+        // `target/` lives under cwd, which workspace-write already covers.
+        assert!(!read_only_target.grants_more_than(&workspace_write, &cwd));
+        assert!(read_only_elsewhere.grants_more_than(&workspace_write, &cwd));
+        assert!(read_only_target.grants_more_than(&SandboxPolicy::new_read_only_policy(), &cwd));
+        assert!(workspace_write_with_network.grants_more_than(&workspace_write, &cwd));
+        assert!(!workspace_write.grants_more_than(&workspace_write_with_network, &cwd));
+        assert!(!workspace_write.grants_more_than(&SandboxPolicy::DangerFullAccess, &cwd));
+        let workspace_write_with_postgres = SandboxPolicy::WorkspaceWrite {
+            writable_roots: Vec::new(),
+            network_access: false,
+            allowed_loopback_ports: vec![5432],
+            exclude_tmpdir_env_var: false,
+            exclude_slash_tmp: false,
+            deny_read: Vec::new(),
+        };
+        assert!(workspace_write_with_postgres.grants_more_than(&workspace_write, &cwd));
+        assert!(
+            !workspace_write_with_postgres.grants_more_than(&workspace_write_with_network, &cwd)
+        );
+        assert!(SandboxPolicy::DangerFullAccess.grants_more_than(&workspace_write, &cwd));
+        Ok(())
+    }
+
     #[test]
     fn item_started_event_from_web_search_emits_begin_event() {
         let event = ItemStartedEvent {
@@ -2753,7 +3074,7 @@ mod tests {
                 model: "codex-mini-latest".to_string(),
                 model_provider_id: "openai".to_string(),
                 approval_policy: AskForApproval::Never,
-                sandbox_policy: SandboxPolicy::ReadOnly,
+                sandbox_policy: SandboxPolicy::new_read_only_policy(),
                 cwd: PathBuf::from("/home/user/project"),
                 reasoning_effort: Some(ReasoningEffortConfig::default()),
                 history_log_id: 0,
@@ -1,6 +1,8 @@
 use std::borrow::Cow;
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
 
 use rmcp::ErrorData as McpError;
 use rmcp::ServiceExt;
@@ -31,6 +33,8 @@ struct TestToolServer {
     tools: Arc<Vec<Tool>>,
     resources: Arc<Vec<Resource>>,
     resource_templates: Arc<Vec<ResourceTemplate>>,
+    /// Set by the `add_tool` tool; `list_tools` then also returns `late`.
+    late_tool_added: Arc<AtomicBool>,
 }
 
 const MEMO_URI: &str = "memo://codex/example-note";
@@ -47,6 +51,7 @@ impl TestToolServer {
             Self::echo_tool(),
             Self::image_tool(),
             Self::image_scenario_tool(),
+            Self::add_tool_tool(),
         ];
         let resources = vec![Self::memo_resource()];
         let resource_templates = vec![Self::memo_template()];
@@ -54,6 +59,7 @@ impl TestToolServer {
             tools: Arc::new(tools),
             resources: Arc::new(resources),
             resource_templates: Arc::new(resource_templates),
+            late_tool_added: Arc::new(AtomicBool::new(false)),
         }
     }
 
@@ -93,6 +99,35 @@ impl TestToolServer {
         )
     }
 
+    fn empty_schema() -> Arc<JsonObject> {
+        #[expect(clippy::expect_used)]
+        let schema: JsonObject = serde_json::from_value(serde_json::json!({
+            "type": "object",
+            "properties": {},
+            "additionalProperties": false
+        }))
+        .expect("empty tool schema should deserialize");
+        Arc::new(schema)
+    }
+
+    /// Registers [`Self::late_tool`] and sends `notifications/tools/list_changed`, so tests can
+    /// check that clients pick up tools added after startup.
+    fn add_tool_tool() -> Tool {
+        Tool::new(
+            Cow::Borrowed("add_tool"),
+            Cow::Borrowed("Register the `late` tool and notify the client that tools changed."),
+            Self::empty_schema(),
+        )
+    }
+
+    fn late_tool() -> Tool {
+        Tool::new(
+            Cow::Borrowed("late"),
+            Cow::Borrowed("Tool that only exists after `add_tool` was called."),
+            Self::empty_schema(),
+        )
+    }
+
     /// Tool intended for manual testing of Codex TUI rendering for MCP image tool results.
     ///
     /// This exists to exercise edge cases where a `CallToolResult.content` includes image blocks
@@ -230,10 +265,13 @@ impl ServerHandler for TestToolServer {
         _request: Option<PaginatedRequestParam>,
         _context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
     ) -> impl std::future::Future<Output = Result<ListToolsResult, McpError>> + Send + '_ {
-        let tools = self.tools.clone();
+        let mut tools = (*self.tools).clone();
+        if self.late_tool_added.load(Ordering::SeqCst) {
+            tools.push(Self::late_tool());
+        }
         async move {
             Ok(ListToolsResult {
-                tools: (*tools).clone(),
+                tools,
                 next_cursor: None,
                 meta: None,
             })
@@ -292,7 +330,7 @@ impl ServerHandler for TestToolServer {
     async fn call_tool(
         &self,
         request: CallToolRequestParam,
-        _context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
+        context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
     ) -> Result<CallToolResult, McpError> {
         match request.name.as_ref() {
             "echo" => {
@@ -347,6 +385,20 @@ impl ServerHandler for TestToolServer {
                 let args = Self::parse_call_args::<ImageScenarioArgs>(&request, "image_scenario")?;
                 Self::image_scenario_result(args)
             }
+            "add_tool" => {
+                self.late_tool_added.store(true, Ordering::SeqCst);
+                context
+                    .peer
+                    .notify_tool_list_changed()
+                    .await
+                    .map_err(|err| McpError::internal_error(err.to_string(), None))?;
+                Ok(CallToolResult::success(vec![rmcp::model::Content::text(
+                    "added the late tool",
+                )]))
+            }
+            "late" if self.late_tool_added.load(Ordering::SeqCst) => Ok(CallToolResult::success(
+                vec![rmcp::model::Content::text("late tool called")],
+            )),
             other => Err(McpError::invalid_params(
                 format!("unknown tool: {other}"),
                 None,
@@ -5,6 +5,7 @@ mod perform_oauth_login;
 mod program_resolver;
 mod rmcp_client;
 mod sse_transport;
+mod trace_context;
 mod utils;
 
 pub use auth_status::determine_streamable_http_auth_status;
@@ -22,7 +23,9 @@ pub use perform_oauth_login::perform_oauth_login_return_url;
 pub use rmcp::model::ElicitationAction;
 pub use rmcp_client::Elicitation;
 pub use rmcp_client::ElicitationResponse;
+pub use rmcp_client::ListChangedKind;
 pub use rmcp_client::ListToolsWithConnectorIdResult;
+pub use rmcp_client::OnListChanged;
 pub use rmcp_client::RmcpClient;
 pub use rmcp_client::SendElicitation;
 pub use rmcp_client::ToolWithConnectorId;
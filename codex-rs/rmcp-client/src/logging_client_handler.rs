@@ -17,19 +17,33 @@ use tracing::error;
 use tracing::info;
 use tracing::warn;
 
+use crate::rmcp_client::ListChangedKind;
+use crate::rmcp_client::OnListChanged;
 use crate::rmcp_client::SendElicitation;
 
 #[derive(Clone)]
 pub(crate) struct LoggingClientHandler {
     client_info: ClientInfo,
     send_elicitation: Arc<SendElicitation>,
+    on_list_changed: Option<Arc<OnListChanged>>,
 }
 
 impl LoggingClientHandler {
-    pub(crate) fn new(client_info: ClientInfo, send_elicitation: SendElicitation) -> Self {
+    pub(crate) fn new(
+        client_info: ClientInfo,
+        send_elicitation: SendElicitation,
+        on_list_changed: Option<OnListChanged>,
+    ) -> Self {
         Self {
             client_info,
             send_elicitation: Arc::new(send_elicitation),
+            on_list_changed: on_list_changed.map(Arc::new),
+        }
+    }
+
+    fn notify_list_changed(&self, kind: ListChangedKind) {
+        if let Some(on_list_changed) = &self.on_list_changed {
+            on_list_changed(kind);
         }
     }
 }
@@ -77,14 +91,17 @@ impl ClientHandler for LoggingClientHandler {
 
     async fn on_resource_list_changed(&self, _context: NotificationContext<RoleClient>) {
         info!("MCP server resource list changed");
+        self.notify_list_changed(ListChangedKind::Resources);
     }
 
     async fn on_tool_list_changed(&self, _context: NotificationContext<RoleClient>) {
         info!("MCP server tool list changed");
+        self.notify_list_changed(ListChangedKind::Tools);
     }
 
     async fn on_prompt_list_changed(&self, _context: NotificationContext<RoleClient>) {
         info!("MCP server prompt list changed");
+        self.notify_list_changed(ListChangedKind::Prompts);
     }
 
     fn get_info(&self) -> ClientInfo {
@@ -11,6 +11,7 @@ use anyhow::anyhow;
 use futures::FutureExt;
 use futures::future::BoxFuture;
 use reqwest::header::HeaderMap;
+use rmcp::model::CallToolRequest;
 use rmcp::model::CallToolRequestParam;
 use rmcp::model::CallToolResult;
 use rmcp::model::ClientNotification;
@@ -55,6 +56,8 @@ use crate::oauth::OAuthPersistor;
 use crate::oauth::StoredOAuthTokens;
 use crate::program_resolver;
 use crate::sse_transport::SseClientTransport;
+use crate::trace_context::TraceContextHttpClient;
+use crate::trace_context::trace_context_meta;
 use crate::utils::apply_default_headers;
 use crate::utils::build_default_headers;
 use crate::utils::create_env_for_mcp_server;
@@ -63,10 +66,10 @@ use crate::utils::run_with_timeout;
 enum PendingTransport {
     ChildProcess(TokioChildProcess),
     StreamableHttp {
-        transport: StreamableHttpClientTransport<reqwest::Client>,
+        transport: StreamableHttpClientTransport<TraceContextHttpClient>,
     },
     StreamableHttpWithOAuth {
-        transport: StreamableHttpClientTransport<AuthClient<reqwest::Client>>,
+        transport: StreamableHttpClientTransport<AuthClient<TraceContextHttpClient>>,
         oauth_persistor: OAuthPersistor,
     },
     Sse {
@@ -92,6 +95,18 @@ pub type SendElicitation = Box<
     dyn Fn(RequestId, Elicitation) -> BoxFuture<'static, Result<ElicitationResponse>> + Send + Sync,
 >;
 
+/// Which server-side list a `notifications/*/list_changed` message refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListChangedKind {
+    Tools,
+    Resources,
+    Prompts,
+}
+
+/// Callback invoked whenever the server reports that one of its lists changed.
+/// It runs on the client's notification loop, so it must not block.
+pub type OnListChanged = Box<dyn Fn(ListChangedKind) + Send + Sync>;
+
 pub struct ToolWithConnectorId {
     pub tool: Tool,
     pub connector_id: Option<String>,
@@ -210,7 +225,10 @@ impl RmcpClient {
             let http_client =
                 apply_default_headers(reqwest::Client::builder(), &default_headers).build()?;
 
-            let transport = StreamableHttpClientTransport::with_client(http_client, http_config);
+            let transport = StreamableHttpClientTransport::with_client(
+                TraceContextHttpClient(http_client),
+                http_config,
+            );
             PendingTransport::StreamableHttp { transport }
         };
         Ok(Self {
@@ -252,8 +270,10 @@ impl RmcpClient {
         params: InitializeRequestParam,
         timeout: Option<Duration>,
         send_elicitation: SendElicitation,
+        on_list_changed: Option<OnListChanged>,
     ) -> Result<InitializeResult> {
-        let client_handler = LoggingClientHandler::new(params.clone(), send_elicitation);
+        let client_handler =
+            LoggingClientHandler::new(params.clone(), send_elicitation, on_list_changed);
 
         let (transport, oauth_persistor) = {
             let mut guard = self.state.lock().await;
@@ -411,11 +431,14 @@ impl RmcpClient {
         Ok(result)
     }
 
+    /// Calls `name` on the server. `trace_context` holds the W3C
+    /// `traceparent`/`tracestate` of the caller's span, if any.
     pub async fn call_tool(
         &self,
         name: String,
         arguments: Option<serde_json::Value>,
         timeout: Option<Duration>,
+        trace_context: &HashMap<String, String>,
     ) -> Result<CallToolResult> {
         self.refresh_oauth_if_needed().await;
         let service = self.service().await?;
@@ -432,8 +455,18 @@ impl RmcpClient {
             name: name.into(),
             arguments,
         };
-        let fut = service.call_tool(rmcp_params);
-        let result = run_with_timeout(fut, timeout, "tools/call").await?;
+        let mut extensions = Extensions::new();
+        extensions.insert(trace_context_meta(trace_context));
+        let request = ClientRequest::CallToolRequest(CallToolRequest {
+            method: Default::default(),
+            params: rmcp_params,
+            extensions,
+        });
+        let fut = service.send_request(request);
+        let response = run_with_timeout(fut, timeout, "tools/call").await?;
+        let ServerResult::CallToolResult(result) = response else {
+            return Err(anyhow!("unexpected response to tools/call: {response:?}"));
+        };
         self.persist_oauth_tokens().await;
         Ok(result)
     }
@@ -513,7 +546,7 @@ async fn create_oauth_transport_and_runtime(
     credentials_store: OAuthCredentialsStoreMode,
     default_headers: HeaderMap,
 ) -> Result<(
-    StreamableHttpClientTransport<AuthClient<reqwest::Client>>,
+    StreamableHttpClientTransport<AuthClient<TraceContextHttpClient>>,
     OAuthPersistor,
 )> {
     let http_client =
@@ -535,7 +568,7 @@ async fn create_oauth_transport_and_runtime(
         }
     };
 
-    let auth_client = AuthClient::new(http_client, manager);
+    let auth_client = AuthClient::new(TraceContextHttpClient(http_client), manager);
     let auth_manager = auth_client.auth_manager.clone();
 
     let transport = StreamableHttpClientTransport::with_client(
@@ -20,6 +20,8 @@ use tokio::sync::mpsc;
 use tokio::task::JoinHandle;
 use tracing::warn;
 
+use crate::trace_context::trace_context_headers;
+
 const EVENT_STREAM_MIME_TYPE: &str = "text/event-stream";
 const JSON_MIME_TYPE: &str = "application/json";
 const ENDPOINT_EVENT: &str = "endpoint";
@@ -59,16 +61,17 @@ impl SseClientTransport {
         }
 
         let closed = Arc::new(AtomicBool::new(false));
-        let reader_handle = spawn_sse_reader(
-            client.clone(),
-            sse_url.clone(),
-            bearer_token.clone(),
+        let reader_handle = SseReader {
+            client: client.clone(),
+            sse_url,
+            bearer_token: bearer_token.clone(),
             base_url,
-            Arc::clone(&message_url),
-            Arc::clone(&message_url_ready),
+            message_url: Arc::clone(&message_url),
+            message_url_ready: Arc::clone(&message_url_ready),
             tx,
-            Arc::clone(&closed),
-        )?;
+            closed: Arc::clone(&closed),
+        }
+        .spawn();
 
         Ok(Self {
             client,
@@ -100,6 +103,9 @@ impl Transport<RoleClient> for SseClientTransport {
             if let Some(token) = bearer_token.as_ref() {
                 request = request.bearer_auth(token);
             }
+            for (name, value) in trace_context_headers(&item) {
+                request = request.header(name, value);
+            }
             let response = request
                 .json(&item)
                 .send()
@@ -166,7 +172,8 @@ async fn wait_for_message_url(
     }
 }
 
-fn spawn_sse_reader(
+/// State owned by the task that reads the server's event stream.
+struct SseReader {
     client: reqwest::Client,
     sse_url: String,
     bearer_token: Option<String>,
@@ -175,16 +182,23 @@ fn spawn_sse_reader(
     message_url_ready: Arc<Notify>,
     tx: mpsc::Sender<RxJsonRpcMessage<RoleClient>>,
     closed: Arc<AtomicBool>,
-) -> Result<JoinHandle<()>> {
-    let handle = tokio::spawn(async move {
-        let mut request = client.get(&sse_url).header(
+}
+
+impl SseReader {
+    fn spawn(self) -> JoinHandle<()> {
+        tokio::spawn(self.run())
+    }
+
+    async fn run(self) {
+        let mut request = self.client.get(&self.sse_url).header(
             ACCEPT,
             format!("{EVENT_STREAM_MIME_TYPE}, {JSON_MIME_TYPE}"),
         );
-        if let Some(token) = bearer_token.as_ref() {
+        if let Some(token) = self.bearer_token.as_ref() {
             request = request.bearer_auth(token);
         }
 
+        let sse_url = &self.sse_url;
         let response = match request.send().await {
             Ok(response) => response,
             Err(error) => {
@@ -200,22 +214,13 @@ fn spawn_sse_reader(
 
         let mut events = response.bytes_stream().eventsource();
         while let Some(event) = events.next().await {
-            if closed.load(Ordering::SeqCst) {
+            if self.closed.load(Ordering::SeqCst) {
                 break;
             }
 
             match event {
                 Ok(event) => {
-                    if let Err(error) = handle_event(
-                        event,
-                        &sse_url,
-                        &base_url,
-                        &message_url,
-                        &message_url_ready,
-                        &tx,
-                    )
-                    .await
-                    {
+                    if let Err(error) = self.handle_event(event).await {
                         warn!("SSE event handling failed for {sse_url}: {error}");
                     }
                 }
@@ -225,58 +230,50 @@ fn spawn_sse_reader(
                 }
             }
         }
-        closed.store(true, Ordering::SeqCst);
-        message_url_ready.notify_waiters();
-    });
-
-    Ok(handle)
-}
+        self.closed.store(true, Ordering::SeqCst);
+        self.message_url_ready.notify_waiters();
+    }
 
-async fn handle_event(
-    event: Event,
-    sse_url: &str,
-    base_url: &Url,
-    message_url: &Arc<RwLock<Option<Arc<str>>>>,
-    message_url_ready: &Arc<Notify>,
-    tx: &mpsc::Sender<RxJsonRpcMessage<RoleClient>>,
-) -> Result<()> {
-    let event_name = event.event.trim();
-    let payload = event.data.trim();
+    async fn handle_event(&self, event: Event) -> Result<()> {
+        let sse_url = &self.sse_url;
+        let event_name = event.event.trim();
+        let payload = event.data.trim();
 
-    if event_name == ENDPOINT_EVENT {
-        if payload.is_empty() {
-            return Ok(());
-        }
-        match resolve_message_url(base_url, payload) {
-            Ok(resolved) => {
-                *message_url.write().await = Some(Arc::from(resolved));
-                message_url_ready.notify_waiters();
+        if event_name == ENDPOINT_EVENT {
+            if payload.is_empty() {
+                return Ok(());
             }
-            Err(error) => {
-                warn!("invalid SSE endpoint from {sse_url}: {error}");
+            match resolve_message_url(&self.base_url, payload) {
+                Ok(resolved) => {
+                    *self.message_url.write().await = Some(Arc::from(resolved));
+                    self.message_url_ready.notify_waiters();
+                }
+                Err(error) => {
+                    warn!("invalid SSE endpoint from {sse_url}: {error}");
+                }
             }
+            return Ok(());
         }
-        return Ok(());
-    }
 
-    if event_name == PING_EVENT {
-        return Ok(());
-    }
+        if event_name == PING_EVENT {
+            return Ok(());
+        }
 
-    if payload.is_empty() || payload == DONE_PAYLOAD {
-        return Ok(());
-    }
+        if payload.is_empty() || payload == DONE_PAYLOAD {
+            return Ok(());
+        }
 
-    match serde_json::from_str::<RxJsonRpcMessage<RoleClient>>(payload) {
-        Ok(message) => {
-            if tx.send(message).await.is_err() {
-                warn!("SSE message channel closed for {sse_url}");
+        match serde_json::from_str::<RxJsonRpcMessage<RoleClient>>(payload) {
+            Ok(message) => {
+                if self.tx.send(message).await.is_err() {
+                    warn!("SSE message channel closed for {sse_url}");
+                }
+            }
+            Err(error) => {
+                warn!("Failed to parse SSE message from {sse_url}: {error}");
             }
         }
-        Err(error) => {
-            warn!("Failed to parse SSE message from {sse_url}: {error}");
-        }
-    }
 
-    Ok(())
+        Ok(())
+    }
 }
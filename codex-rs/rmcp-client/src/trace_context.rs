@@ -0,0 +1,342 @@
+//! W3C trace-context propagation for outgoing MCP requests.
+//!
+//! Callers pass the `traceparent`/`tracestate` of their active span to
+//! [`crate::RmcpClient::call_tool`]. It travels in the request's `_meta`, which
+//! every transport carries. The HTTP transports also send it as headers via
+//! [`trace_context_headers`]: the SSE transport builds its own requests, and
+//! streamable HTTP posts through [`TraceContextHttpClient`].
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use futures::StreamExt;
+use futures::stream::BoxStream;
+use reqwest::StatusCode;
+use reqwest::header::ACCEPT;
+use reqwest::header::CONTENT_TYPE;
+use reqwest::header::WWW_AUTHENTICATE;
+use rmcp::model::ClientJsonRpcMessage;
+use rmcp::model::GetMeta;
+use rmcp::model::JsonRpcMessage;
+use rmcp::model::Meta;
+use rmcp::model::ServerJsonRpcMessage;
+use rmcp::transport::common::http_header::EVENT_STREAM_MIME_TYPE;
+use rmcp::transport::common::http_header::HEADER_SESSION_ID;
+use rmcp::transport::common::http_header::JSON_MIME_TYPE;
+use rmcp::transport::streamable_http_client::AuthRequiredError;
+use rmcp::transport::streamable_http_client::SseError;
+use rmcp::transport::streamable_http_client::StreamableHttpClient;
+use rmcp::transport::streamable_http_client::StreamableHttpError;
+use rmcp::transport::streamable_http_client::StreamableHttpPostResponse;
+use serde_json::Value;
+use sse_stream::Sse;
+use sse_stream::SseStream;
+
+const TRACE_CONTEXT_KEYS: [&str; 2] = ["traceparent", "tracestate"];
+
+/// `_meta` entries for the W3C trace-context headers in `trace_context`.
+pub(crate) fn trace_context_meta(trace_context: &HashMap<String, String>) -> Meta {
+    let mut meta = Meta::new();
+    for key in TRACE_CONTEXT_KEYS {
+        if let Some(value) = trace_context.get(key) {
+            meta.insert(key.to_string(), Value::String(value.clone()));
+        }
+    }
+    meta
+}
+
+/// Header hook for transports that build their own requests: the trace-context
+/// headers carried in `message`'s `_meta`, if it is a request.
+pub(crate) fn trace_context_headers(message: &ClientJsonRpcMessage) -> Vec<(&'static str, String)> {
+    let JsonRpcMessage::Request(request) = message else {
+        return Vec::new();
+    };
+    let meta = request.request.get_meta();
+    TRACE_CONTEXT_KEYS
+        .into_iter()
+        .filter_map(|key| {
+            meta.get(key)
+                .and_then(Value::as_str)
+                .map(|value| (key, value.to_string()))
+        })
+        .collect()
+}
+
+/// Client for rmcp's streamable HTTP transport. rmcp's own `reqwest` client
+/// only varies the bearer token per request, so this one posts messages itself
+/// to add their trace-context headers; everything else is delegated.
+#[derive(Clone)]
+pub(crate) struct TraceContextHttpClient(pub(crate) reqwest::Client);
+
+impl StreamableHttpClient for TraceContextHttpClient {
+    type Error = reqwest::Error;
+
+    async fn post_message(
+        &self,
+        uri: Arc<str>,
+        message: ClientJsonRpcMessage,
+        session_id: Option<Arc<str>>,
+        auth_token: Option<String>,
+    ) -> Result<StreamableHttpPostResponse, StreamableHttpError<Self::Error>> {
+        let mut request = self.0.post(uri.as_ref()).header(
+            ACCEPT,
+            format!("{EVENT_STREAM_MIME_TYPE}, {JSON_MIME_TYPE}"),
+        );
+        if let Some(token) = auth_token {
+            request = request.bearer_auth(token);
+        }
+        if let Some(session_id) = session_id {
+            request = request.header(HEADER_SESSION_ID, session_id.as_ref());
+        }
+        for (name, value) in trace_context_headers(&message) {
+            request = request.header(name, value);
+        }
+
+        let response = request.json(&message).send().await?;
+        if response.status() == StatusCode::UNAUTHORIZED
+            && let Some(header) = response.headers().get(WWW_AUTHENTICATE)
+        {
+            let www_authenticate_header = header
+                .to_str()
+                .map_err(|_| {
+                    StreamableHttpError::UnexpectedServerResponse(Cow::from(
+                        "invalid www-authenticate header value",
+                    ))
+                })?
+                .to_string();
+            return Err(StreamableHttpError::AuthRequired(AuthRequiredError {
+                www_authenticate_header,
+            }));
+        }
+
+        let status = response.status();
+        let response = response.error_for_status()?;
+        if matches!(status, StatusCode::ACCEPTED | StatusCode::NO_CONTENT) {
+            return Ok(StreamableHttpPostResponse::Accepted);
+        }
+
+        let session_id = response
+            .headers()
+            .get(HEADER_SESSION_ID)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+        let content_type = response.headers().get(CONTENT_TYPE).cloned();
+        match content_type {
+            Some(ct) if ct.as_bytes().starts_with(EVENT_STREAM_MIME_TYPE.as_bytes()) => {
+                let events = SseStream::from_byte_stream(response.bytes_stream()).boxed();
+                Ok(StreamableHttpPostResponse::Sse(events, session_id))
+            }
+            Some(ct) if ct.as_bytes().starts_with(JSON_MIME_TYPE.as_bytes()) => {
+                let message: ServerJsonRpcMessage = response.json().await?;
+                Ok(StreamableHttpPostResponse::Json(message, session_id))
+            }
+            other => Err(StreamableHttpError::UnexpectedContentType(
+                other.map(|ct| String::from_utf8_lossy(ct.as_bytes()).to_string()),
+            )),
+        }
+    }
+
+    async fn delete_session(
+        &self,
+        uri: Arc<str>,
+        session_id: Arc<str>,
+        auth_token: Option<String>,
+    ) -> Result<(), StreamableHttpError<Self::Error>> {
+        self.0.delete_session(uri, session_id, auth_token).await
+    }
+
+    async fn get_stream(
+        &self,
+        uri: Arc<str>,
+        session_id: Arc<str>,
+        last_event_id: Option<String>,
+        auth_token: Option<String>,
+    ) -> Result<BoxStream<'static, Result<Sse, SseError>>, StreamableHttpError<Self::Error>> {
+        self.0
+            .get_stream(uri, session_id, last_event_id, auth_token)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sse_transport::SseClientTransport;
+    use pretty_assertions::assert_eq;
+    use rmcp::model::CallToolRequest;
+    use rmcp::model::CallToolRequestParam;
+    use rmcp::model::ClientRequest;
+    use rmcp::model::Extensions;
+    use rmcp::model::JsonRpcRequest;
+    use rmcp::model::JsonRpcVersion2_0;
+    use rmcp::model::NumberOrString;
+    use rmcp::transport::Transport;
+    use std::io::Read;
+    use std::io::Write;
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    const TRACEPARENT: &str = "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01";
+    const TRACESTATE: &str = "vendor=abc";
+
+    fn traced_call(trace_context: &HashMap<String, String>) -> ClientJsonRpcMessage {
+        let mut extensions = Extensions::new();
+        extensions.insert(trace_context_meta(trace_context));
+        ClientJsonRpcMessage::Request(JsonRpcRequest {
+            jsonrpc: JsonRpcVersion2_0,
+            id: NumberOrString::Number(1),
+            request: ClientRequest::CallToolRequest(CallToolRequest {
+                method: Default::default(),
+                params: CallToolRequestParam {
+                    name: "echo".into(),
+                    arguments: None,
+                },
+                extensions,
+            }),
+        })
+    }
+
+    fn full_trace_context() -> HashMap<String, String> {
+        HashMap::from([
+            ("traceparent".to_string(), TRACEPARENT.to_string()),
+            ("tracestate".to_string(), TRACESTATE.to_string()),
+        ])
+    }
+
+    /// Serves HTTP on a local port and reports the lowercased head of each
+    /// request. GETs get an event stream that stays open; everything else is
+    /// answered with `202 Accepted`.
+    fn spawn_recording_server() -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind");
+        let url = format!("http://{}", listener.local_addr().expect("local addr"));
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else { break };
+                let tx = tx.clone();
+                std::thread::spawn(move || {
+                    let mut buf = Vec::new();
+                    let mut chunk = [0u8; 1024];
+                    let head_end = loop {
+                        if let Some(pos) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+                            break pos + 4;
+                        }
+                        match stream.read(&mut chunk) {
+                            Ok(0) | Err(_) => return,
+                            Ok(n) => buf.extend_from_slice(&chunk[..n]),
+                        }
+                    };
+                    let head = String::from_utf8_lossy(&buf[..head_end]).to_lowercase();
+                    let content_length = head
+                        .lines()
+                        .find_map(|line| line.strip_prefix("content-length:"))
+                        .and_then(|value| value.trim().parse::<usize>().ok())
+                        .unwrap_or(0);
+                    while buf.len() < head_end + content_length {
+                        match stream.read(&mut chunk) {
+                            Ok(0) | Err(_) => return,
+                            Ok(n) => buf.extend_from_slice(&chunk[..n]),
+                        }
+                    }
+                    let is_get = head.starts_with("get ");
+                    let _ = tx.send(head);
+                    if is_get {
+                        let _ = stream.write_all(
+                            b"HTTP/1.1 200 OK\r\ncontent-type: text/event-stream\r\n\r\n",
+                        );
+                        std::thread::sleep(Duration::from_secs(30));
+                    } else {
+                        let _ =
+                            stream.write_all(b"HTTP/1.1 202 Accepted\r\ncontent-length: 0\r\n\r\n");
+                    }
+                });
+            }
+        });
+        (url, rx)
+    }
+
+    fn next_post(requests: &mpsc::Receiver<String>) -> String {
+        loop {
+            let head = requests
+                .recv_timeout(Duration::from_secs(10))
+                .expect("request head");
+            if head.starts_with("post ") {
+                return head;
+            }
+        }
+    }
+
+    fn assert_trace_headers(head: &str) {
+        assert!(
+            head.contains(&format!("traceparent: {TRACEPARENT}\r\n")),
+            "missing traceparent in {head}"
+        );
+        assert!(
+            head.contains(&format!("tracestate: {TRACESTATE}\r\n")),
+            "missing tracestate in {head}"
+        );
+    }
+
+    #[test]
+    fn trace_context_round_trips_through_request_meta() {
+        let trace_context = HashMap::from([
+            ("traceparent".to_string(), TRACEPARENT.to_string()),
+            ("baggage".to_string(), "ignored=1".to_string()),
+        ]);
+        let message = traced_call(&trace_context);
+
+        assert_eq!(
+            trace_context_headers(&message),
+            vec![("traceparent", TRACEPARENT.to_string())]
+        );
+        let serialized = serde_json::to_value(&message).expect("serialize request");
+        assert_eq!(
+            serialized["params"]["_meta"]["traceparent"],
+            Value::String(TRACEPARENT.to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn streamable_http_posts_send_trace_context_headers() {
+        let (url, requests) = spawn_recording_server();
+        let client = TraceContextHttpClient(reqwest::Client::new());
+
+        let response = client
+            .post_message(
+                Arc::from(format!("{url}/mcp")),
+                traced_call(&full_trace_context()),
+                Some(Arc::from("session-1")),
+                None,
+            )
+            .await
+            .expect("post message");
+
+        assert!(matches!(response, StreamableHttpPostResponse::Accepted));
+        let head = next_post(&requests);
+        assert_trace_headers(&head);
+        assert!(head.contains("mcp-session-id: session-1\r\n"), "{head}");
+    }
+
+    #[tokio::test]
+    async fn sse_posts_send_trace_context_headers() {
+        let (url, requests) = spawn_recording_server();
+        let mut transport = SseClientTransport::connect(
+            reqwest::Client::new(),
+            format!("{url}/sse"),
+            Some("/messages".to_string()),
+            None,
+        )
+        .await
+        .expect("connect");
+
+        transport
+            .send(traced_call(&full_trace_context()))
+            .await
+            .expect("send message");
+
+        assert_trace_headers(&next_post(&requests));
+        transport.close().await.expect("close");
+    }
+}
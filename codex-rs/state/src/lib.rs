@@ -31,6 +31,10 @@ pub use model::ThreadMemory;
 pub use model::ThreadMetadata;
 pub use model::ThreadMetadataBuilder;
 pub use model::ThreadsPage;
+pub use model::TurnUsage;
+pub use model::UsageGroupBy;
+pub use model::UsageQuery;
+pub use model::UsageRow;
 pub use runtime::STATE_DB_FILENAME;
 pub use runtime::STATE_DB_VERSION;
 pub use runtime::state_db_filename;
@@ -2,6 +2,7 @@ mod backfill_state;
 mod log;
 mod thread_memory;
 mod thread_metadata;
+mod turn_usage;
 
 pub use backfill_state::BackfillState;
 pub use backfill_state::BackfillStatus;
@@ -16,6 +17,10 @@ pub use thread_metadata::SortKey;
 pub use thread_metadata::ThreadMetadata;
 pub use thread_metadata::ThreadMetadataBuilder;
 pub use thread_metadata::ThreadsPage;
+pub use turn_usage::TurnUsage;
+pub use turn_usage::UsageGroupBy;
+pub use turn_usage::UsageQuery;
+pub use turn_usage::UsageRow;
 
 pub(crate) use thread_memory::ThreadMemoryRow;
 pub(crate) use thread_metadata::ThreadRow;
@@ -0,0 +1,109 @@
+use anyhow::Result;
+use chrono::DateTime;
+use chrono::Utc;
+use codex_protocol::ThreadId;
+use serde::Serialize;
+use sqlx::Row;
+use sqlx::sqlite::SqliteRow;
+use std::path::PathBuf;
+
+/// Identifies the turn a model response's token usage belongs to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TurnUsage {
+    pub thread_id: ThreadId,
+    pub turn_id: String,
+    pub model: String,
+    pub model_provider: String,
+    pub cwd: PathBuf,
+}
+
+/// Dimension that usage totals are grouped by.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UsageGroupBy {
+    /// Local calendar day of the turn, as `YYYY-MM-DD`.
+    #[default]
+    Day,
+    /// Local calendar month of the turn, as `YYYY-MM`.
+    Month,
+    /// Git origin URL of the thread, or its working directory outside a repository.
+    Repo,
+    Model,
+    Thread,
+}
+
+impl UsageGroupBy {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            UsageGroupBy::Day => "day",
+            UsageGroupBy::Month => "month",
+            UsageGroupBy::Repo => "repo",
+            UsageGroupBy::Model => "model",
+            UsageGroupBy::Thread => "thread",
+        }
+    }
+
+    pub fn parse(value: &str) -> Result<Self> {
+        match value {
+            "day" => Ok(Self::Day),
+            "month" => Ok(Self::Month),
+            "repo" => Ok(Self::Repo),
+            "model" => Ok(Self::Model),
+            "thread" => Ok(Self::Thread),
+            _ => Err(anyhow::anyhow!(
+                "invalid usage grouping: {value} (expected day, month, repo, model or thread)"
+            )),
+        }
+    }
+
+    /// SQL expression for the group key over `turn_usage AS u LEFT JOIN threads AS t`.
+    pub(crate) const fn sql_key(self) -> &'static str {
+        match self {
+            UsageGroupBy::Day => "strftime('%Y-%m-%d', u.created_at, 'unixepoch', 'localtime')",
+            UsageGroupBy::Month => "strftime('%Y-%m', u.created_at, 'unixepoch', 'localtime')",
+            UsageGroupBy::Repo => "COALESCE(NULLIF(t.git_origin_url, ''), u.cwd)",
+            UsageGroupBy::Model => "u.model",
+            UsageGroupBy::Thread => "u.thread_id",
+        }
+    }
+}
+
+/// Filters and grouping for [`crate::StateRuntime::query_usage`].
+#[derive(Debug, Clone, Default)]
+pub struct UsageQuery {
+    pub group_by: UsageGroupBy,
+    /// Only turns that started at or after this time.
+    pub since: Option<DateTime<Utc>>,
+    /// Only turns that started before this time.
+    pub until: Option<DateTime<Utc>>,
+}
+
+/// Token totals for one group, split by provider and model so callers can price them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UsageRow {
+    pub group: String,
+    pub model_provider: String,
+    pub model: String,
+    pub turns: i64,
+    pub input_tokens: i64,
+    pub cached_input_tokens: i64,
+    pub output_tokens: i64,
+    pub reasoning_output_tokens: i64,
+    pub total_tokens: i64,
+}
+
+impl UsageRow {
+    pub(crate) fn try_from_row(row: &SqliteRow) -> Result<Self> {
+        Ok(Self {
+            group: row.try_get("group_key")?,
+            model_provider: row.try_get("model_provider")?,
+            model: row.try_get("model")?,
+            turns: row.try_get("turns")?,
+            input_tokens: row.try_get("input_tokens")?,
+            cached_input_tokens: row.try_get("cached_input_tokens")?,
+            output_tokens: row.try_get("output_tokens")?,
+            reasoning_output_tokens: row.try_get("reasoning_output_tokens")?,
+            total_tokens: row.try_get("total_tokens")?,
+        })
+    }
+}
@@ -7,6 +7,9 @@ use crate::ThreadMemory;
 use crate::ThreadMetadata;
 use crate::ThreadMetadataBuilder;
 use crate::ThreadsPage;
+use crate::TurnUsage;
+use crate::UsageQuery;
+use crate::UsageRow;
 use crate::apply_rollout_item;
 use crate::migrations::MIGRATOR;
 use crate::model::ThreadMemoryRow;
@@ -20,6 +23,7 @@ use codex_otel::OtelManager;
 use codex_protocol::ThreadId;
 use codex_protocol::dynamic_tools::DynamicToolSpec;
 use codex_protocol::protocol::RolloutItem;
+use codex_protocol::protocol::TokenUsage;
 use log::LevelFilter;
 use serde_json::Value;
 use sqlx::ConnectOptions;
@@ -717,6 +721,94 @@ ON CONFLICT(thread_id, position) DO NOTHING
         self.upsert_thread(&metadata).await
     }
 
+    /// Add one model response's token usage to its turn's row.
+    ///
+    /// A turn makes several model requests, so repeated calls for the same turn accumulate. The
+    /// model and provider of the first request are kept.
+    pub async fn record_turn_usage(
+        &self,
+        turn: &TurnUsage,
+        usage: &TokenUsage,
+    ) -> anyhow::Result<()> {
+        let now = Utc::now().timestamp();
+        sqlx::query(
+            r#"
+INSERT INTO turn_usage (
+    thread_id,
+    turn_id,
+    created_at,
+    updated_at,
+    model,
+    model_provider,
+    cwd,
+    input_tokens,
+    cached_input_tokens,
+    output_tokens,
+    reasoning_output_tokens,
+    total_tokens
+) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+ON CONFLICT(thread_id, turn_id) DO UPDATE SET
+    updated_at = excluded.updated_at,
+    input_tokens = turn_usage.input_tokens + excluded.input_tokens,
+    cached_input_tokens = turn_usage.cached_input_tokens + excluded.cached_input_tokens,
+    output_tokens = turn_usage.output_tokens + excluded.output_tokens,
+    reasoning_output_tokens = turn_usage.reasoning_output_tokens + excluded.reasoning_output_tokens,
+    total_tokens = turn_usage.total_tokens + excluded.total_tokens
+            "#,
+        )
+        .bind(turn.thread_id.to_string())
+        .bind(turn.turn_id.as_str())
+        .bind(now)
+        .bind(now)
+        .bind(turn.model.as_str())
+        .bind(turn.model_provider.as_str())
+        .bind(turn.cwd.display().to_string())
+        .bind(usage.input_tokens)
+        .bind(usage.cached_input_tokens)
+        .bind(usage.output_tokens)
+        .bind(usage.reasoning_output_tokens)
+        .bind(usage.total_tokens)
+        .execute(self.pool.as_ref())
+        .await?;
+        Ok(())
+    }
+
+    /// Sum recorded turn usage per group, provider and model, ordered by group.
+    pub async fn query_usage(&self, query: &UsageQuery) -> anyhow::Result<Vec<UsageRow>> {
+        let mut builder = QueryBuilder::<Sqlite>::new("SELECT ");
+        builder.push(query.group_by.sql_key()).push(
+            r#" AS group_key,
+    u.model_provider,
+    u.model,
+    COUNT(*) AS turns,
+    SUM(u.input_tokens) AS input_tokens,
+    SUM(u.cached_input_tokens) AS cached_input_tokens,
+    SUM(u.output_tokens) AS output_tokens,
+    SUM(u.reasoning_output_tokens) AS reasoning_output_tokens,
+    SUM(u.total_tokens) AS total_tokens
+FROM turn_usage AS u
+LEFT JOIN threads AS t ON t.id = u.thread_id
+WHERE 1 = 1"#,
+        );
+        if let Some(since) = query.since {
+            builder
+                .push(" AND u.created_at >= ")
+                .push_bind(datetime_to_epoch_seconds(since));
+        }
+        if let Some(until) = query.until {
+            builder
+                .push(" AND u.created_at < ")
+                .push_bind(datetime_to_epoch_seconds(until));
+        }
+        builder.push(" GROUP BY group_key, u.model_provider, u.model");
+        builder.push(" ORDER BY group_key, u.model_provider, u.model");
+
+        let rows = builder.build().fetch_all(self.pool.as_ref()).await?;
+        rows.into_iter()
+            .map(|row| UsageRow::try_from_row(&row))
+            .collect()
+    }
+
     async fn ensure_backfill_state_row(&self) -> anyhow::Result<()> {
         sqlx::query(
             r#"
@@ -966,12 +1058,16 @@ mod tests {
     use super::STATE_DB_VERSION;
     use super::StateRuntime;
     use super::ThreadMetadata;
+    use super::TurnUsage;
+    use super::UsageQuery;
     use super::state_db_filename;
+    use crate::UsageGroupBy;
     use chrono::DateTime;
     use chrono::Utc;
     use codex_protocol::ThreadId;
     use codex_protocol::protocol::AskForApproval;
     use codex_protocol::protocol::SandboxPolicy;
+    use codex_protocol::protocol::TokenUsage;
     use pretty_assertions::assert_eq;
     use sqlx::Row;
     use std::path::Path;
@@ -1382,6 +1478,108 @@ mod tests {
         let _ = tokio::fs::remove_dir_all(codex_home).await;
     }
 
+    #[tokio::test]
+    async fn turn_usage_accumulates_and_groups_by_repo_and_model() {
+        let codex_home = unique_temp_dir();
+        let runtime = StateRuntime::init(codex_home.clone(), "test-provider".to_string(), None)
+            .await
+            .expect("initialize runtime");
+
+        let in_repo = ThreadId::from_string(&Uuid::new_v4().to_string()).expect("thread id");
+        let outside = ThreadId::from_string(&Uuid::new_v4().to_string()).expect("thread id");
+        let repo_cwd = codex_home.join("repo");
+        let plain_cwd = codex_home.join("scratch");
+        let mut metadata = test_thread_metadata(&codex_home, in_repo, repo_cwd.clone());
+        metadata.git_origin_url = Some("https://example.com/repo.git".to_string());
+        runtime
+            .upsert_thread(&metadata)
+            .await
+            .expect("upsert thread");
+
+        let usage = |input_tokens, output_tokens| TokenUsage {
+            input_tokens,
+            cached_input_tokens: 0,
+            output_tokens,
+            reasoning_output_tokens: 0,
+            total_tokens: input_tokens + output_tokens,
+        };
+        let turn = |thread_id, turn_id: &str, model: &str, cwd: &PathBuf| TurnUsage {
+            thread_id,
+            turn_id: turn_id.to_string(),
+            model: model.to_string(),
+            model_provider: "openai".to_string(),
+            cwd: cwd.clone(),
+        };
+        for (turn, usage) in [
+            (turn(in_repo, "turn-1", "gpt-5", &repo_cwd), usage(100, 10)),
+            (turn(in_repo, "turn-1", "gpt-5", &repo_cwd), usage(50, 5)),
+            (
+                turn(in_repo, "turn-2", "gpt-5-mini", &repo_cwd),
+                usage(20, 2),
+            ),
+            (turn(outside, "turn-1", "gpt-5", &plain_cwd), usage(7, 1)),
+        ] {
+            runtime
+                .record_turn_usage(&turn, &usage)
+                .await
+                .expect("record usage");
+        }
+
+        let by_model = runtime
+            .query_usage(&UsageQuery {
+                group_by: UsageGroupBy::Model,
+                ..Default::default()
+            })
+            .await
+            .expect("query by model");
+        let summary: Vec<_> = by_model
+            .iter()
+            .map(|row| {
+                (
+                    row.group.as_str(),
+                    row.turns,
+                    row.input_tokens,
+                    row.total_tokens,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![("gpt-5", 2, 157, 173), ("gpt-5-mini", 1, 20, 22)]
+        );
+
+        let by_repo = runtime
+            .query_usage(&UsageQuery {
+                group_by: UsageGroupBy::Repo,
+                ..Default::default()
+            })
+            .await
+            .expect("query by repo");
+        let summary: Vec<_> = by_repo
+            .iter()
+            .map(|row| (row.group.clone(), row.model.as_str(), row.total_tokens))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (plain_cwd.display().to_string(), "gpt-5", 8),
+                ("https://example.com/repo.git".to_string(), "gpt-5", 165),
+                ("https://example.com/repo.git".to_string(), "gpt-5-mini", 22),
+            ]
+        );
+
+        let future = runtime
+            .query_usage(&UsageQuery {
+                since: Some(Utc::now() + chrono::Duration::days(1)),
+                ..Default::default()
+            })
+            .await
+            .expect("query future");
+        assert_eq!(future, Vec::new());
+
+        let _ = tokio::fs::remove_dir_all(codex_home).await;
+    }
+
     fn test_thread_metadata(
         codex_home: &Path,
         thread_id: ThreadId,
@@ -1398,7 +1596,7 @@ mod tests {
             cwd,
             cli_version: "0.0.0".to_string(),
             title: String::new(),
-            sandbox_policy: crate::extract::enum_to_string(&SandboxPolicy::ReadOnly),
+            sandbox_policy: crate::extract::enum_to_string(&SandboxPolicy::new_read_only_policy()),
             approval_mode: crate::extract::enum_to_string(&AskForApproval::OnRequest),
             tokens_used: 0,
             first_user_message: Some("hello".to_string()),
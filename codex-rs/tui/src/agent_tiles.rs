@@ -0,0 +1,453 @@
+//! Tiled overview of the orchestrator thread and its collab sub-agents.
+//!
+//! The app feeds every thread's events into [`AgentTiles`], whether or not that thread is the one
+//! on screen, so each tile can show its agent's status, token usage and latest output side by
+//! side. The chat widget renders the tiles above the composer once a second thread exists; input
+//! and approvals keep going to the focused thread, which `/agent` (or the `cycle_agent` binding)
+//! switches.
+
+use std::collections::HashSet;
+use std::collections::VecDeque;
+use std::time::Duration;
+use std::time::Instant;
+
+use codex_core::config::keybindings::KeybindingAction;
+use codex_core::protocol::AgentStatus;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::TurnCompleteEvent;
+use codex_protocol::ThreadId;
+use codex_protocol::num_format::format_si_suffix;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::widgets::Block;
+use ratatui::widgets::Paragraph;
+use ratatui::widgets::Widget;
+
+use crate::collab;
+use crate::exec_command::strip_bash_lc_and_escape;
+use crate::keymap::keymap;
+use crate::render::renderable::Renderable;
+
+/// Output lines kept per tile; also the number of output rows each tile shows.
+const TILE_OUTPUT_LINES: usize = 3;
+/// Borders, the status row and the output rows.
+const TILE_HEIGHT: u16 = TILE_OUTPUT_LINES as u16 + 3;
+const MIN_TILE_WIDTH: u16 = 32;
+/// A running agent with no events for this long is flagged as idle.
+const IDLE_THRESHOLD: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct AgentTiles {
+    /// Tiles in creation order, so the orchestrator always comes first.
+    tiles: Vec<AgentTile>,
+    focused: Option<ThreadId>,
+}
+
+#[derive(Debug, Clone)]
+struct AgentTile {
+    thread_id: ThreadId,
+    status: AgentStatus,
+    total_tokens: Option<i64>,
+    /// Call ids of approval requests that have not been answered yet.
+    pending_approvals: HashSet<String>,
+    output: VecDeque<String>,
+    /// Agent message text after the last newline, still being streamed.
+    partial_line: String,
+    last_activity: Instant,
+}
+
+impl AgentTile {
+    fn new(thread_id: ThreadId) -> Self {
+        Self {
+            thread_id,
+            status: AgentStatus::PendingInit,
+            total_tokens: None,
+            pending_approvals: HashSet::new(),
+            output: VecDeque::new(),
+            partial_line: String::new(),
+            last_activity: Instant::now(),
+        }
+    }
+
+    fn on_event(&mut self, msg: &EventMsg) {
+        self.last_activity = Instant::now();
+        match msg {
+            EventMsg::TurnStarted(_) => {
+                self.status = AgentStatus::Running;
+                self.pending_approvals.clear();
+            }
+            EventMsg::TurnComplete(TurnCompleteEvent { last_agent_message }) => {
+                self.flush_partial_line();
+                self.status = AgentStatus::Completed(last_agent_message.clone());
+                self.pending_approvals.clear();
+            }
+            EventMsg::TurnAborted(ev) => {
+                // Mirrors `agent_status_from_event` in core so tiles agree with collab tool results.
+                self.flush_partial_line();
+                self.status = AgentStatus::Errored(format!("{:?}", ev.reason));
+                self.pending_approvals.clear();
+            }
+            EventMsg::Error(ev) => {
+                self.flush_partial_line();
+                self.status = AgentStatus::Errored(ev.message.clone());
+                self.push_output(ev.message.clone());
+            }
+            EventMsg::ShutdownComplete => {
+                self.status = AgentStatus::Shutdown;
+                self.pending_approvals.clear();
+            }
+            EventMsg::TokenCount(ev) => {
+                if let Some(info) = &ev.info {
+                    self.total_tokens = Some(info.total_token_usage.blended_total());
+                }
+            }
+            EventMsg::AgentMessageDelta(ev) => {
+                // The turn blocks on approvals, so streaming again means they were answered.
+                self.pending_approvals.clear();
+                self.partial_line.push_str(&ev.delta);
+                while let Some(newline) = self.partial_line.find('\n') {
+                    let line = self.partial_line[..newline].to_string();
+                    self.partial_line.drain(..=newline);
+                    self.push_output(line);
+                }
+            }
+            EventMsg::AgentMessage(_) => self.flush_partial_line(),
+            EventMsg::AgentReasoningDelta(_) => self.pending_approvals.clear(),
+            EventMsg::ExecCommandBegin(ev) => {
+                self.pending_approvals.remove(&ev.call_id);
+                self.push_output(format!("$ {}", strip_bash_lc_and_escape(&ev.command)));
+            }
+            EventMsg::PatchApplyBegin(ev) => {
+                self.pending_approvals.remove(&ev.call_id);
+                self.push_output("applying patch".to_string());
+            }
+            EventMsg::ExecApprovalRequest(ev) => {
+                self.pending_approvals.insert(ev.call_id.clone());
+            }
+            EventMsg::ApplyPatchApprovalRequest(ev) => {
+                self.pending_approvals.insert(ev.call_id.clone());
+            }
+            _ => {}
+        }
+    }
+
+    fn flush_partial_line(&mut self) {
+        if !self.partial_line.is_empty() {
+            let line = std::mem::take(&mut self.partial_line);
+            self.push_output(line);
+        }
+    }
+
+    fn push_output(&mut self, line: String) {
+        let line = line.trim_end();
+        if line.is_empty() {
+            return;
+        }
+        self.output.push_back(line.to_string());
+        while self.output.len() > TILE_OUTPUT_LINES {
+            self.output.pop_front();
+        }
+    }
+
+    fn is_idle(&self, now: Instant) -> bool {
+        matches!(self.status, AgentStatus::Running)
+            && self.pending_approvals.is_empty()
+            && now.saturating_duration_since(self.last_activity) >= IDLE_THRESHOLD
+    }
+
+    fn render(&self, label: String, focused: bool, now: Instant, area: Rect, buf: &mut Buffer) {
+        let title = if focused {
+            Line::from(vec![" ▶ ".cyan(), label.bold(), " ".into()])
+        } else {
+            Line::from(vec![" ".into(), label.into(), " ".into()])
+        };
+        let border_style = if focused {
+            Style::new().cyan()
+        } else {
+            Style::new().dim()
+        };
+        let block = Block::bordered().border_style(border_style).title(title);
+
+        let mut status = vec![collab::status_span(&self.status)];
+        if let Some(tokens) = self.total_tokens {
+            status.push(" · ".dim());
+            status.push(format!("{} tokens", format_si_suffix(tokens)).dim());
+        }
+        if !self.pending_approvals.is_empty() {
+            status.push(" · ".dim());
+            status.push("needs approval".magenta().bold());
+        } else if self.is_idle(now) {
+            let idle = now.saturating_duration_since(self.last_activity).as_secs();
+            status.push(" · ".dim());
+            status.push(format!("idle {idle}s").magenta());
+        }
+
+        let mut lines = vec![Line::from(status)];
+        let partial = (!self.partial_line.trim().is_empty()).then_some(&self.partial_line);
+        let skip =
+            (self.output.len() + usize::from(partial.is_some())).saturating_sub(TILE_OUTPUT_LINES);
+        lines.extend(
+            self.output
+                .iter()
+                .chain(partial)
+                .skip(skip)
+                .map(|line| Line::from(line.clone())),
+        );
+        Paragraph::new(lines).block(block).render(area, buf);
+    }
+}
+
+impl AgentTiles {
+    /// Whether `msg` changes a tile, including plain activity that resets its idle timer.
+    pub(crate) fn tracks(msg: &EventMsg) -> bool {
+        matches!(
+            msg,
+            EventMsg::TurnStarted(_)
+                | EventMsg::TurnComplete(_)
+                | EventMsg::TurnAborted(_)
+                | EventMsg::Error(_)
+                | EventMsg::ShutdownComplete
+                | EventMsg::TokenCount(_)
+                | EventMsg::AgentMessageDelta(_)
+                | EventMsg::AgentMessage(_)
+                | EventMsg::AgentReasoningDelta(_)
+                | EventMsg::ExecCommandBegin(_)
+                | EventMsg::ExecCommandOutputDelta(_)
+                | EventMsg::ExecCommandEnd(_)
+                | EventMsg::PatchApplyBegin(_)
+                | EventMsg::PatchApplyEnd(_)
+                | EventMsg::ExecApprovalRequest(_)
+                | EventMsg::ApplyPatchApprovalRequest(_)
+        )
+    }
+
+    /// Starts tracking `thread_id`; a no-op when it already has a tile.
+    pub(crate) fn ensure(&mut self, thread_id: ThreadId) {
+        if !self.tiles.iter().any(|tile| tile.thread_id == thread_id) {
+            self.tiles.push(AgentTile::new(thread_id));
+        }
+    }
+
+    pub(crate) fn on_event(&mut self, thread_id: ThreadId, msg: &EventMsg) {
+        self.ensure(thread_id);
+        if let Some(tile) = self
+            .tiles
+            .iter_mut()
+            .find(|tile| tile.thread_id == thread_id)
+        {
+            tile.on_event(msg);
+        }
+    }
+
+    pub(crate) fn set_focused(&mut self, thread_id: Option<ThreadId>) {
+        self.focused = thread_id;
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.tiles.clear();
+        self.focused = None;
+    }
+
+    /// Tiles are only worth the space once a sub-agent exists.
+    pub(crate) fn is_visible(&self) -> bool {
+        self.tiles.len() > 1
+    }
+
+    /// Whether any tile shows elapsed time and therefore needs periodic redraws.
+    pub(crate) fn has_running_agent(&self) -> bool {
+        self.tiles
+            .iter()
+            .any(|tile| matches!(tile.status, AgentStatus::Running))
+    }
+
+    fn label(&self, idx: usize, tile: &AgentTile) -> String {
+        if idx == 0 {
+            return "main".to_string();
+        }
+        let id = tile.thread_id.to_string();
+        let short: String = id.chars().take(8).collect();
+        format!("agent {short}")
+    }
+
+    fn columns(&self, width: u16) -> usize {
+        let fit = usize::from((width / MIN_TILE_WIDTH).max(1));
+        fit.min(self.tiles.len().max(1))
+    }
+
+    fn hint_line(&self) -> Line<'static> {
+        let mut hint = vec!["/agent".cyan(), " to focus an agent".dim()];
+        if let Some(key) = keymap().hint_label(KeybindingAction::CycleAgent) {
+            hint.push(" · ".dim());
+            hint.push(key.cyan());
+            hint.push(" next agent".dim());
+        }
+        Line::from(hint)
+    }
+}
+
+impl Renderable for AgentTiles {
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        if !self.is_visible() || area.is_empty() {
+            return;
+        }
+        let now = Instant::now();
+        let columns = self.columns(area.width);
+        let tile_width = area.width / columns as u16;
+        for (idx, tile) in self.tiles.iter().enumerate() {
+            let row = (idx / columns) as u16;
+            let col = (idx % columns) as u16;
+            let width = if col as usize == columns - 1 {
+                area.width - tile_width * col
+            } else {
+                tile_width
+            };
+            let tile_area = Rect::new(
+                area.x + tile_width * col,
+                area.y + row * TILE_HEIGHT,
+                width,
+                TILE_HEIGHT,
+            )
+            .intersection(area);
+            tile.render(
+                self.label(idx, tile),
+                self.focused == Some(tile.thread_id),
+                now,
+                tile_area,
+                buf,
+            );
+        }
+        let rows = self.tiles.len().div_ceil(columns) as u16;
+        let hint_area = Rect::new(area.x, area.y + rows * TILE_HEIGHT, area.width, 1);
+        self.hint_line().render(hint_area.intersection(area), buf);
+    }
+
+    fn desired_height(&self, width: u16) -> u16 {
+        if !self.is_visible() {
+            return 0;
+        }
+        let rows = self.tiles.len().div_ceil(self.columns(width)) as u16;
+        rows * TILE_HEIGHT + 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_core::protocol::AgentMessageDeltaEvent;
+    use codex_core::protocol::ExecApprovalRequestEvent;
+    use codex_core::protocol::TurnStartedEvent;
+    use insta::assert_snapshot;
+    use pretty_assertions::assert_eq;
+    use std::path::PathBuf;
+
+    fn delta(text: &str) -> EventMsg {
+        EventMsg::AgentMessageDelta(AgentMessageDeltaEvent {
+            delta: text.to_string(),
+        })
+    }
+
+    fn turn_started() -> EventMsg {
+        EventMsg::TurnStarted(TurnStartedEvent {
+            model_context_window: None,
+            collaboration_mode_kind: Default::default(),
+        })
+    }
+
+    fn render_lines(tiles: &AgentTiles, width: u16) -> String {
+        let area = Rect::new(0, 0, width, tiles.desired_height(width));
+        let mut buf = Buffer::empty(area);
+        tiles.render(area, &mut buf);
+        (0..area.height)
+            .map(|row| {
+                (0..area.width)
+                    .map(|col| buf[(col, row)].symbol())
+                    .collect::<String>()
+                    .trim_end()
+                    .to_string()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn tiles_render_approval_and_idle_states() {
+        let main = ThreadId::from_string("00000000-0000-4000-8000-000000000000").unwrap();
+        let approver = ThreadId::from_string("aaaaaaaa-0000-4000-8000-000000000000").unwrap();
+        let idler = ThreadId::from_string("bbbbbbbb-0000-4000-8000-000000000000").unwrap();
+        let mut tiles = AgentTiles::default();
+        for (thread_id, text) in [
+            (main, "spawned two workers\n"),
+            (approver, "running the test suite\n"),
+            (idler, "waiting on cargo build\n"),
+        ] {
+            tiles.on_event(thread_id, &turn_started());
+            tiles.on_event(thread_id, &delta(text));
+        }
+        tiles.on_event(
+            approver,
+            &EventMsg::ExecApprovalRequest(ExecApprovalRequestEvent {
+                call_id: "call-1".to_string(),
+                turn_id: String::new(),
+                command: vec!["cargo".to_string(), "test".to_string()],
+                cwd: PathBuf::from("/repo"),
+                reason: None,
+                proposed_execpolicy_amendment: None,
+                parsed_cmd: Vec::new(),
+            }),
+        );
+        tiles.tiles[2].last_activity = Instant::now() - Duration::from_secs(45);
+        tiles.set_focused(Some(main));
+
+        assert_snapshot!(render_lines(&tiles, 96));
+    }
+
+    #[test]
+    fn tiles_track_status_and_latest_output_per_thread() {
+        let main = ThreadId::new();
+        let worker = ThreadId::new();
+        let mut tiles = AgentTiles::default();
+        tiles.ensure(main);
+        assert!(!tiles.is_visible());
+
+        tiles.on_event(
+            worker,
+            &EventMsg::TurnStarted(TurnStartedEvent {
+                model_context_window: None,
+                collaboration_mode_kind: Default::default(),
+            }),
+        );
+        for text in ["one\ntwo\n", "three\nfo", "ur"] {
+            tiles.on_event(worker, &delta(text));
+        }
+        assert!(tiles.is_visible());
+        assert!(tiles.has_running_agent());
+
+        let tile = &tiles.tiles[1];
+        assert_eq!(tile.thread_id, worker);
+        assert_eq!(
+            tile.output,
+            VecDeque::from(["one", "two", "three"].map(String::from))
+        );
+        assert_eq!(tile.partial_line, "four");
+
+        tiles.on_event(
+            worker,
+            &EventMsg::TurnComplete(TurnCompleteEvent {
+                last_agent_message: Some("done".to_string()),
+            }),
+        );
+        let tile = &tiles.tiles[1];
+        assert_eq!(
+            tile.status,
+            AgentStatus::Completed(Some("done".to_string()))
+        );
+        assert_eq!(
+            tile.output,
+            VecDeque::from(["two", "three", "four"].map(String::from))
+        );
+        assert!(!tiles.has_running_agent());
+    }
+}
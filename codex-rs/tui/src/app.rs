@@ -1,3 +1,4 @@
+use crate::agent_tiles::AgentTiles;
 use crate::app_backtrack::BacktrackState;
 use crate::app_event::AppEvent;
 use crate::app_event::ExitMode;
@@ -22,6 +23,7 @@ use crate::history_cell;
 use crate::history_cell::HistoryCell;
 #[cfg(not(debug_assertions))]
 use crate::history_cell::UpdateAvailableHistoryCell;
+use crate::keymap::keymap;
 use crate::model_migration::ModelMigrationOutcome;
 use crate::model_migration::migration_copy_for_models;
 use crate::model_migration::run_model_migration_prompt;
@@ -29,10 +31,20 @@ use crate::pager_overlay::Overlay;
 use crate::render::highlight::highlight_bash_to_lines;
 use crate::render::renderable::Renderable;
 use crate::resume_picker::SessionSelection;
+use crate::session_share::ShareRole;
+use crate::session_share::ShareSettings;
+use crate::session_share::share_started_message;
+use crate::session_share::spawn_viewer;
+use crate::session_share::viewer_decision_message;
+use crate::session_share::viewer_placeholder;
+use crate::terminal_graphics;
+use crate::theme;
 use crate::tui;
 use crate::tui::TuiEvent;
 use crate::update_action::UpdateAction;
 use codex_ansi_escape::ansi_escape_line;
+use codex_app_server::ShareServer;
+use codex_app_server::ShareViewer;
 use codex_app_server_protocol::ConfigLayerSource;
 use codex_core::AuthManager;
 use codex_core::CodexAuth;
@@ -42,6 +54,7 @@ use codex_core::config::ConfigBuilder;
 use codex_core::config::ConfigOverrides;
 use codex_core::config::edit::ConfigEdit;
 use codex_core::config::edit::ConfigEditsBuilder;
+use codex_core::config::keybindings::KeybindingAction;
 use codex_core::config_loader::ConfigLayerStackOrdering;
 use codex_core::features::Feature;
 use codex_core::models_manager::manager::RefreshStrategy;
@@ -315,10 +328,45 @@ impl ThreadEventStore {
         }
     }
 
+    /// Buffered events for replay, minus approval requests that were already answered so
+    /// switching to an agent only re-prompts for the approvals it is still waiting on.
+    ///
+    /// An approval counts as answered once its call starts, or once anything later shows the
+    /// turn moved on: the model streaming again, or the turn ending.
     fn snapshot(&self) -> ThreadEventSnapshot {
+        let mut resolved_call_ids = HashSet::new();
+        let mut moved_on = false;
+        let mut events: Vec<Event> = Vec::with_capacity(self.buffer.len());
+        for event in self.buffer.iter().rev() {
+            match &event.msg {
+                EventMsg::ExecCommandBegin(ev) => {
+                    resolved_call_ids.insert(ev.call_id.as_str());
+                }
+                EventMsg::PatchApplyBegin(ev) => {
+                    resolved_call_ids.insert(ev.call_id.as_str());
+                }
+                EventMsg::TurnComplete(_)
+                | EventMsg::TurnAborted(_)
+                | EventMsg::AgentMessageDelta(_)
+                | EventMsg::AgentReasoningDelta(_) => moved_on = true,
+                EventMsg::ExecApprovalRequest(ev)
+                    if moved_on || resolved_call_ids.contains(ev.call_id.as_str()) =>
+                {
+                    continue;
+                }
+                EventMsg::ApplyPatchApprovalRequest(ev)
+                    if moved_on || resolved_call_ids.contains(ev.call_id.as_str()) =>
+                {
+                    continue;
+                }
+                _ => {}
+            }
+            events.push(event.clone());
+        }
+        events.reverse();
         ThreadEventSnapshot {
             session_configured: self.session_configured.clone(),
-            events: self.buffer.iter().cloned().collect(),
+            events,
         }
     }
 }
@@ -566,6 +614,10 @@ pub(crate) struct App {
     primary_thread_id: Option<ThreadId>,
     primary_session_configured: Option<SessionConfiguredEvent>,
     pending_primary_events: VecDeque<Event>,
+    /// Status and recent output of every thread, shown side by side once sub-agents exist.
+    agent_tiles: AgentTiles,
+    /// Whether this session is shared with viewers, or is itself watching a shared one.
+    share_role: ShareRole,
 }
 
 #[derive(Default)]
@@ -683,6 +735,7 @@ impl App {
         };
         self.active_thread_id = Some(thread_id);
         self.active_thread_rx = receiver;
+        self.agent_tiles.set_focused(Some(thread_id));
     }
 
     async fn store_active_thread_receiver(&mut self) {
@@ -730,6 +783,11 @@ impl App {
             guard.active
         };
 
+        if AgentTiles::tracks(&event.msg) {
+            self.agent_tiles.on_event(thread_id, &event.msg);
+            self.sync_agent_tiles();
+        }
+
         if should_send {
             // Never await a bounded channel send on the main TUI loop: if the receiver falls behind,
             // `send().await` can block and the UI stops drawing. If the channel is full, wait in a
@@ -774,6 +832,28 @@ impl App {
         Ok(())
     }
 
+    /// Submits an approval decision sent by a viewer to the shared (primary) thread.
+    async fn submit_viewer_op(&mut self, op: Op) {
+        let Some(thread_id) = self.primary_thread_id else {
+            return;
+        };
+        let message = viewer_decision_message(&op);
+        let thread = match self.server.get_thread(thread_id).await {
+            Ok(thread) => thread,
+            Err(err) => {
+                tracing::warn!("failed to find shared thread {thread_id}: {err}");
+                return;
+            }
+        };
+        if let Err(err) = thread.submit(op).await {
+            tracing::warn!("failed to submit viewer decision: {err}");
+            return;
+        }
+        if let Some(message) = message {
+            self.chat_widget.add_info_message(message, None);
+        }
+    }
+
     fn open_agent_picker(&mut self) {
         if self.thread_event_channels.is_empty() {
             self.chat_widget
@@ -816,6 +896,21 @@ impl App {
         });
     }
 
+    /// The agent thread after the active one, in the same order as the agent picker.
+    fn next_agent_thread(&self) -> Option<ThreadId> {
+        let mut thread_ids: Vec<ThreadId> = self.thread_event_channels.keys().cloned().collect();
+        thread_ids.sort_by_key(ToString::to_string);
+        let next = self
+            .active_thread_id
+            .and_then(|active| thread_ids.iter().position(|id| *id == active))
+            .map_or(0, |idx| idx + 1);
+        thread_ids
+            .get(next)
+            .or_else(|| thread_ids.first())
+            .copied()
+            .filter(|id| self.active_thread_id != Some(*id))
+    }
+
     async fn select_agent_thread(&mut self, tui: &mut tui::Tui, thread_id: ThreadId) -> Result<()> {
         if self.active_thread_id == Some(thread_id) {
             return Ok(());
@@ -851,6 +946,8 @@ impl App {
         self.chat_widget = ChatWidget::new_with_op_sender(init, codex_op_tx);
 
         self.reset_for_thread_switch(tui)?;
+        self.agent_tiles.set_focused(Some(thread_id));
+        self.sync_agent_tiles();
         self.replay_thread_snapshot(snapshot);
         self.drain_active_thread_events(tui).await?;
 
@@ -875,6 +972,14 @@ impl App {
         self.active_thread_rx = None;
         self.primary_thread_id = None;
         self.pending_primary_events.clear();
+        self.agent_tiles.clear();
+    }
+
+    /// Hands the current tiles to the chat widget, which is rebuilt on every thread switch.
+    fn sync_agent_tiles(&mut self) {
+        if self.agent_tiles.is_visible() {
+            self.chat_widget.set_agent_tiles(self.agent_tiles.clone());
+        }
     }
 
     async fn drain_active_thread_events(&mut self, tui: &mut tui::Tui) -> Result<()> {
@@ -927,6 +1032,7 @@ impl App {
         initial_prompt: Option<String>,
         initial_images: Vec<PathBuf>,
         session_selection: SessionSelection,
+        share: Option<ShareSettings>,
         feedback: codex_feedback::CodexFeedback,
         is_first_run: bool,
     ) -> Result<AppExitInfo> {
@@ -1005,6 +1111,7 @@ impl App {
         let status_line_invalid_items_warned = Arc::new(AtomicBool::new(false));
 
         let enhanced_keys_supported = tui.enhanced_keys_supported();
+        let attached = matches!(session_selection, SessionSelection::Attach(_));
         let mut chat_widget = match session_selection {
             SessionSelection::StartFresh | SessionSelection::Exit => {
                 let init = crate::chatwidget::ChatWidgetInit {
@@ -1090,6 +1197,31 @@ impl App {
                 };
                 ChatWidget::new_from_existing(init, forked.thread, forked.session_configured)
             }
+            SessionSelection::Attach(url) => {
+                let viewer = ShareViewer::connect(&url)
+                    .await
+                    .wrap_err("Failed to attach to the shared session")?;
+                let placeholder = viewer_placeholder(viewer.can_approve);
+                let init = crate::chatwidget::ChatWidgetInit {
+                    config: config.clone(),
+                    frame_requester: tui.frame_requester(),
+                    app_event_tx: app_event_tx.clone(),
+                    initial_user_message: None,
+                    enhanced_keys_supported,
+                    auth_manager: auth_manager.clone(),
+                    models_manager: thread_manager.get_models_manager(),
+                    feedback: feedback.clone(),
+                    is_first_run: false,
+                    feedback_audience,
+                    model: config.model.clone(),
+                    status_line_invalid_items_warned: status_line_invalid_items_warned.clone(),
+                    otel_manager: otel_manager.clone(),
+                };
+                let codex_op_tx = spawn_viewer(viewer, app_event_tx.clone());
+                let mut chat_widget = ChatWidget::new_with_op_sender(init, codex_op_tx);
+                chat_widget.set_read_only(placeholder);
+                chat_widget
+            }
         };
 
         chat_widget.maybe_prompt_windows_sandbox_enable();
@@ -1131,6 +1263,12 @@ impl App {
             primary_thread_id: None,
             primary_session_configured: None,
             pending_primary_events: VecDeque::new(),
+            agent_tiles: AgentTiles::default(),
+            share_role: if attached {
+                ShareRole::Viewer
+            } else {
+                ShareRole::None
+            },
         };
 
         // On startup, if Agent mode (workspace-write) or ReadOnly is active, warn about world-writable dirs on Windows.
@@ -1141,7 +1279,7 @@ impl App {
                 && matches!(
                     app.config.sandbox_policy.get(),
                     codex_core::protocol::SandboxPolicy::WorkspaceWrite { .. }
-                        | codex_core::protocol::SandboxPolicy::ReadOnly
+                        | codex_core::protocol::SandboxPolicy::ReadOnly { .. }
                 )
                 && !app
                     .config
@@ -1180,6 +1318,17 @@ impl App {
             }
         }
 
+        let mut viewer_ops_rx = None;
+        if let Some(share) = share {
+            let (server, ops_rx) = ShareServer::start(share.bind_address, share.allow_approvals)
+                .await
+                .wrap_err("Failed to start sharing the session")?;
+            let (message, hint) = share_started_message(server.url(), share.allow_approvals);
+            app.chat_widget.add_info_message(message, Some(hint));
+            app.share_role = ShareRole::Owner(server);
+            viewer_ops_rx = Some(ops_rx);
+        }
+
         let tui_events = tui.event_stream();
         tokio::pin!(tui_events);
 
@@ -1210,6 +1359,19 @@ impl App {
                 Some(event) = tui_events.next() => {
                     app.handle_tui_event(tui, event).await?
                 }
+                viewer_op = async {
+                    if let Some(rx) = viewer_ops_rx.as_mut() {
+                        rx.recv().await
+                    } else {
+                        None
+                    }
+                }, if viewer_ops_rx.is_some() => {
+                    match viewer_op {
+                        Some(op) => app.submit_viewer_op(op).await,
+                        None => viewer_ops_rx = None,
+                    }
+                    AppRunControl::Continue
+                }
                 // Listen on new thread creation due to collab tools.
                 created = thread_created_rx.recv(), if listen_for_threads => {
                     match created {
@@ -1232,9 +1394,15 @@ impl App {
             }
         };
         tui.terminal.clear()?;
+        // A viewer's thread lives on the owner's machine, so there is nothing to resume here.
+        let thread_id = if matches!(app.share_role, ShareRole::Viewer) {
+            None
+        } else {
+            app.chat_widget.thread_id()
+        };
         Ok(AppExitInfo {
             token_usage: app.token_usage(),
-            thread_id: app.chat_widget.thread_id(),
+            thread_id,
             thread_name: app.chat_widget.thread_name(),
             update_action: app.pending_update_action,
             exit_reason,
@@ -1280,6 +1448,11 @@ impl App {
                     {
                         return Ok(AppRunControl::Continue);
                     }
+                    if self.agent_tiles.is_visible() && self.agent_tiles.has_running_agent() {
+                        // Keep idle timers on the agent tiles ticking.
+                        tui.frame_requester()
+                            .schedule_frame_in(Duration::from_secs(1));
+                    }
                     tui.draw(
                         self.chat_widget.desired_height(tui.terminal.size()?.width),
                         |frame| {
@@ -1431,7 +1604,8 @@ impl App {
                     }
                     SessionSelection::Exit
                     | SessionSelection::StartFresh
-                    | SessionSelection::Fork(_) => {}
+                    | SessionSelection::Fork(_)
+                    | SessionSelection::Attach(_) => {}
                 }
 
                 // Leaving alt-screen may blank the inline viewport; force a redraw either way.
@@ -1515,6 +1689,11 @@ impl App {
                         self.deferred_history_lines.extend(display);
                     } else {
                         tui.insert_history_lines(display);
+                        if terminal_graphics::protocol().is_some() {
+                            for image in cell.inline_images() {
+                                tui.insert_history_image(image);
+                            }
+                        }
                     }
                 }
             }
@@ -1541,9 +1720,16 @@ impl App {
                 self.chat_widget.on_commit_tick();
             }
             AppEvent::CodexEvent(event) => {
+                if let ShareRole::Owner(server) = &self.share_role {
+                    server.publish(event.clone());
+                }
                 self.enqueue_primary_event(event).await?;
             }
             AppEvent::Exit(mode) => match mode {
+                // Viewers have no thread of their own to shut down.
+                ExitMode::ShutdownFirst if matches!(self.share_role, ShareRole::Viewer) => {
+                    return Ok(AppRunControl::Exit(ExitReason::UserRequested));
+                }
                 ExitMode::ShutdownFirst => self.chat_widget.submit_op(Op::Shutdown),
                 ExitMode::Immediate => {
                     return Ok(AppRunControl::Exit(ExitReason::UserRequested));
@@ -1571,6 +1757,17 @@ impl App {
                 ));
                 tui.frame_requester().schedule_frame();
             }
+            AppEvent::UsageResult(result) => match result {
+                Ok(report) => {
+                    let _ = tui.enter_alt_screen();
+                    self.overlay = Some(Overlay::new_static_with_lines(
+                        crate::usage::pager_lines(&report),
+                        "U S A G E".to_string(),
+                    ));
+                    tui.frame_requester().schedule_frame();
+                }
+                Err(message) => self.chat_widget.add_error_message(message),
+            },
             AppEvent::SubmitPromptSuggestion(text) => {
                 self.chat_widget.submit_prompt_suggestion(text);
             }
@@ -1584,6 +1781,7 @@ impl App {
                     cwd: None,
                     approval_policy: None,
                     sandbox_policy: None,
+                    sandbox_profile: None,
                     windows_sandbox_level: None,
                     model: None,
                     subagent_model: None,
@@ -1696,6 +1894,18 @@ impl App {
             AppEvent::OpenFeedbackConsent { category } => {
                 self.chat_widget.open_feedback_consent(category);
             }
+            AppEvent::OpenDebugBundlePreview { bundle } => {
+                self.chat_widget.open_debug_bundle_preview(bundle);
+            }
+            AppEvent::SaveDebugBundle { bundle, path } => match bundle.write_tarball(&path) {
+                Ok(()) => self.chat_widget.add_info_message(
+                    format!("Saved debug bundle to {}", path.display()),
+                    Some("Nothing was uploaded. Share the file with support.".to_string()),
+                ),
+                Err(err) => self
+                    .chat_widget
+                    .add_error_message(format!("Failed to save debug bundle: {err}")),
+            },
             AppEvent::LaunchExternalEditor => {
                 if self.chat_widget.external_editor_state() == ExternalEditorState::Active {
                     self.launch_external_editor(tui).await;
@@ -1855,6 +2065,7 @@ impl App {
                                         cwd: None,
                                         approval_policy: None,
                                         sandbox_policy: None,
+                                        sandbox_profile: None,
                                         windows_sandbox_level: Some(windows_sandbox_level),
                                         model: None,
                                         subagent_model: None,
@@ -1883,6 +2094,7 @@ impl App {
                                         cwd: None,
                                         approval_policy: Some(preset.approval),
                                         sandbox_policy: Some(preset.sandbox.clone()),
+                                        sandbox_profile: None,
                                         windows_sandbox_level: Some(windows_sandbox_level),
                                         model: None,
                                         subagent_model: None,
@@ -2017,7 +2229,7 @@ impl App {
                 let policy_is_workspace_write_or_ro = matches!(
                     &policy,
                     codex_core::protocol::SandboxPolicy::WorkspaceWrite { .. }
-                        | codex_core::protocol::SandboxPolicy::ReadOnly
+                        | codex_core::protocol::SandboxPolicy::ReadOnly { .. }
                 );
 
                 if let Err(err) = self.config.sandbox_policy.set(policy.clone()) {
@@ -2027,9 +2239,11 @@ impl App {
                     return Ok(AppRunControl::Continue);
                 }
                 #[cfg(target_os = "windows")]
-                if !matches!(&policy, codex_core::protocol::SandboxPolicy::ReadOnly)
-                    || WindowsSandboxLevel::from_config(&self.config)
-                        != WindowsSandboxLevel::Disabled
+                if !matches!(
+                    &policy,
+                    codex_core::protocol::SandboxPolicy::ReadOnly { .. }
+                ) || WindowsSandboxLevel::from_config(&self.config)
+                    != WindowsSandboxLevel::Disabled
                 {
                     self.config.forced_auto_mode_downgraded_on_windows = false;
                 }
@@ -2116,6 +2330,7 @@ impl App {
                                 cwd: None,
                                 approval_policy: None,
                                 sandbox_policy: None,
+                                sandbox_profile: None,
                                 windows_sandbox_level: Some(windows_sandbox_level),
                                 model: None,
                                 subagent_model: None,
@@ -2157,6 +2372,7 @@ impl App {
                     cwd: None,
                     approval_policy: None,
                     sandbox_policy: None,
+                    sandbox_profile: None,
                     windows_sandbox_level: None,
                     model: None,
                     subagent_model: None,
@@ -2279,6 +2495,10 @@ impl App {
             AppEvent::SelectAgentThread(thread_id) => {
                 self.select_agent_thread(tui, thread_id).await?;
             }
+            AppEvent::AgentThreadActivity { thread_id, msg } => {
+                self.agent_tiles.on_event(thread_id, &msg);
+                self.sync_agent_tiles();
+            }
             AppEvent::OpenSkillsList => {
                 self.chat_widget.open_skills_list();
             }
@@ -2394,6 +2614,33 @@ impl App {
             AppEvent::StatusLineSetupCancelled => {
                 self.chat_widget.cancel_status_line_setup();
             }
+            AppEvent::PreviewTheme(theme) => {
+                theme::set_current(theme);
+                tui.frame_requester().schedule_frame();
+            }
+            AppEvent::PersistThemeSelection { theme } => {
+                let id = theme.id.clone();
+                let name = theme.name.clone();
+                theme::set_current(theme);
+                tui.frame_requester().schedule_frame();
+                let edit = codex_core::config::edit::tui_theme_edit(&id);
+                match ConfigEditsBuilder::new(&self.config.codex_home)
+                    .with_edits([edit])
+                    .apply()
+                    .await
+                {
+                    Ok(()) => {
+                        self.config.tui_theme = Some(id);
+                        self.chat_widget
+                            .add_info_message(format!("Theme set to {name}"), None);
+                    }
+                    Err(err) => {
+                        tracing::error!(error = %err, "failed to persist theme selection");
+                        self.chat_widget
+                            .add_error_message(format!("Failed to save theme: {err}"));
+                    }
+                }
+            }
         }
         Ok(AppRunControl::Continue)
     }
@@ -2463,10 +2710,13 @@ impl App {
                 rollout_path: thread.rollout_path(),
             }),
         };
+        self.agent_tiles.ensure(thread_id);
+        self.sync_agent_tiles();
         let channel =
             ThreadEventChannel::new_with_session_configured(THREAD_EVENT_CHANNEL_CAPACITY, event);
         let sender = channel.sender.clone();
         let store = Arc::clone(&channel.store);
+        let app_event_tx = self.app_event_tx.clone();
         self.thread_event_channels.insert(thread_id, channel);
         tokio::spawn(async move {
             loop {
@@ -2477,6 +2727,12 @@ impl App {
                         break;
                     }
                 };
+                if AgentTiles::tracks(&event.msg) {
+                    app_event_tx.send(AppEvent::AgentThreadActivity {
+                        thread_id,
+                        msg: event.msg.clone(),
+                    });
+                }
                 let should_send = {
                     let mut guard = store.lock().await;
                     guard.push_event(event.clone());
@@ -2598,23 +2854,13 @@ impl App {
 
     async fn handle_key_event(&mut self, tui: &mut tui::Tui, key_event: KeyEvent) {
         match key_event {
-            KeyEvent {
-                code: KeyCode::Char('t'),
-                modifiers: crossterm::event::KeyModifiers::CONTROL,
-                kind: KeyEventKind::Press,
-                ..
-            } => {
+            _ if keymap().is_press(KeybindingAction::OpenTranscript, key_event) => {
                 // Enter alternate screen and set viewport to full size.
                 let _ = tui.enter_alt_screen();
                 self.overlay = Some(Overlay::new_transcript(self.transcript_cells.clone()));
                 tui.frame_requester().schedule_frame();
             }
-            KeyEvent {
-                code: KeyCode::Char('g'),
-                modifiers: crossterm::event::KeyModifiers::CONTROL,
-                kind: KeyEventKind::Press,
-                ..
-            } => {
+            _ if keymap().is_press(KeybindingAction::ExternalEditor, key_event) => {
                 // Only launch the external editor if there is no overlay and the bottom pane is not in use.
                 // Note that it can be launched while a task is running to enable editing while the previous turn is ongoing.
                 if self.overlay.is_none()
@@ -2624,6 +2870,12 @@ impl App {
                     self.request_external_editor_launch(tui);
                 }
             }
+            _ if keymap().is_press(KeybindingAction::CycleAgent, key_event) => {
+                if let Some(thread_id) = self.next_agent_thread() {
+                    self.app_event_tx
+                        .send(AppEvent::SelectAgentThread(thread_id));
+                }
+            }
             // Esc primes/advances backtracking only in normal (not working) mode
             // with the composer focused and empty. In any other state, forward
             // Esc so the active UI (e.g. status indicator, modals, popups)
@@ -2800,6 +3052,42 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn thread_snapshot_drops_answered_approvals() {
+        let approval = |call_id: &str| Event {
+            id: "turn-1".to_string(),
+            msg: EventMsg::ExecApprovalRequest(codex_core::protocol::ExecApprovalRequestEvent {
+                call_id: call_id.to_string(),
+                turn_id: "turn-1".to_string(),
+                command: vec!["cargo".to_string(), "test".to_string()],
+                cwd: PathBuf::from("/tmp"),
+                reason: None,
+                proposed_execpolicy_amendment: None,
+                parsed_cmd: Vec::new(),
+            }),
+        };
+        let mut store = ThreadEventStore::new(8);
+        store.push_event(approval("call-1"));
+        store.push_event(Event {
+            id: "turn-1".to_string(),
+            msg: EventMsg::AgentMessageDelta(codex_core::protocol::AgentMessageDeltaEvent {
+                delta: "retrying".to_string(),
+            }),
+        });
+        store.push_event(approval("call-2"));
+
+        let call_ids: Vec<Option<String>> = store
+            .snapshot()
+            .events
+            .iter()
+            .map(|event| match &event.msg {
+                EventMsg::ExecApprovalRequest(ev) => Some(ev.call_id.clone()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(call_ids, vec![None, Some("call-2".to_string())]);
+    }
+
     async fn make_test_app() -> App {
         let (chat_widget, app_event_tx, _rx, _op_rx) = make_chatwidget_manual_with_sender().await;
         let config = chat_widget.config_ref().clone();
@@ -2846,6 +3134,8 @@ mod tests {
             primary_thread_id: None,
             primary_session_configured: None,
             pending_primary_events: VecDeque::new(),
+            agent_tiles: AgentTiles::default(),
+            share_role: ShareRole::None,
         }
     }
 
@@ -2900,6 +3190,8 @@ mod tests {
                 primary_thread_id: None,
                 primary_session_configured: None,
                 pending_primary_events: VecDeque::new(),
+                agent_tiles: AgentTiles::default(),
+                share_role: ShareRole::None,
             },
             rx,
             op_rx,
@@ -3130,7 +3422,7 @@ mod tests {
                 model: "gpt-test".to_string(),
                 model_provider_id: "test-provider".to_string(),
                 approval_policy: AskForApproval::Never,
-                sandbox_policy: SandboxPolicy::ReadOnly,
+                sandbox_policy: SandboxPolicy::new_read_only_policy(),
                 cwd: PathBuf::from("/home/user/project"),
                 reasoning_effort: None,
                 history_log_id: 0,
@@ -3184,7 +3476,7 @@ mod tests {
                 model: "gpt-test".to_string(),
                 model_provider_id: "test-provider".to_string(),
                 approval_policy: AskForApproval::Never,
-                sandbox_policy: SandboxPolicy::ReadOnly,
+                sandbox_policy: SandboxPolicy::new_read_only_policy(),
                 cwd: PathBuf::from("/home/user/project"),
                 reasoning_effort: None,
                 history_log_id: 0,
@@ -3230,7 +3522,7 @@ mod tests {
             model: "gpt-test".to_string(),
             model_provider_id: "test-provider".to_string(),
             approval_policy: AskForApproval::Never,
-            sandbox_policy: SandboxPolicy::ReadOnly,
+            sandbox_policy: SandboxPolicy::new_read_only_policy(),
             cwd: PathBuf::from("/home/user/project"),
             reasoning_effort: None,
             history_log_id: 0,
@@ -107,6 +107,14 @@ impl App {
         tui: &mut tui::Tui,
         event: TuiEvent,
     ) -> Result<bool> {
+        // An open search prompt, or Esc with an active search, belongs to the overlay.
+        if let TuiEvent::Key(key) = &event
+            && let Some(Overlay::Transcript(t)) = &self.overlay
+            && t.wants_key(*key)
+        {
+            self.overlay_forward_event(tui, event)?;
+            return Ok(true);
+        }
         if self.backtrack.overlay_preview_active {
             match event {
                 TuiEvent::Key(KeyEvent {
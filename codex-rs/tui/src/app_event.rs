@@ -9,11 +9,15 @@
 //! quits without reaching into the app loop or coupling to shutdown/exit sequencing.
 
 use std::path::PathBuf;
+use std::sync::Arc;
 
 use codex_chatgpt::connectors::AppInfo;
 use codex_common::approval_presets::ApprovalPreset;
+use codex_core::debug_bundle::DebugBundle;
 use codex_core::protocol::Event;
+use codex_core::protocol::EventMsg;
 use codex_core::protocol::RateLimitSnapshot;
+use codex_core::usage::UsageReport;
 use codex_file_search::FileMatch;
 use codex_protocol::ThreadId;
 use codex_protocol::openai_models::ModelPreset;
@@ -21,6 +25,7 @@ use codex_protocol::openai_models::ModelPreset;
 use crate::bottom_pane::ApprovalRequest;
 use crate::bottom_pane::StatusLineItem;
 use crate::history_cell::HistoryCell;
+use crate::theme::Theme;
 
 use codex_core::features::Feature;
 use codex_core::protocol::AskForApproval;
@@ -55,6 +60,11 @@ pub(crate) enum AppEvent {
     OpenAgentPicker,
     /// Switch the active thread to the selected agent.
     SelectAgentThread(ThreadId),
+    /// An event from a sub-agent thread that changes its tile in the agent tiles view.
+    AgentThreadActivity {
+        thread_id: ThreadId,
+        msg: EventMsg,
+    },
 
     /// Start a new session.
     NewSession,
@@ -105,6 +115,9 @@ pub(crate) enum AppEvent {
     /// Result of computing a `/diff` command.
     DiffResult(String),
 
+    /// Result of loading the usage report for `/usage`.
+    UsageResult(Result<UsageReport, String>),
+
     /// Submit a prompt suggestion as a user message.
     SubmitPromptSuggestion(String),
 
@@ -326,6 +339,17 @@ pub(crate) enum AppEvent {
         category: FeedbackCategory,
     },
 
+    /// Preview a debug bundle collected for `/feedback --save` before writing it.
+    OpenDebugBundlePreview {
+        bundle: Arc<DebugBundle>,
+    },
+
+    /// Write a previewed debug bundle to `path`.
+    SaveDebugBundle {
+        bundle: Arc<DebugBundle>,
+        path: PathBuf,
+    },
+
     /// Launch the external editor after a normal draw has completed.
     LaunchExternalEditor,
 
@@ -340,6 +364,14 @@ pub(crate) enum AppEvent {
     },
     /// Dismiss the status-line setup UI without changing config.
     StatusLineSetupCancelled,
+
+    /// Temporarily activate a theme while it is highlighted in `/theme`.
+    PreviewTheme(Theme),
+
+    /// Activate the theme chosen in `/theme` and persist it as `tui.theme`.
+    PersistThemeSelection {
+        theme: Theme,
+    },
 }
 
 /// The exit strategy requested by the UI layer.
@@ -13,9 +13,12 @@ use crate::exec_command::strip_bash_lc_and_escape;
 use crate::history_cell;
 use crate::key_hint;
 use crate::key_hint::KeyBinding;
+use crate::keymap::keymap;
 use crate::render::highlight::highlight_bash_to_lines;
 use crate::render::renderable::ColumnRenderable;
 use crate::render::renderable::Renderable;
+use crate::theme;
+use codex_core::config::keybindings::KeybindingAction;
 use codex_core::features::Features;
 use codex_core::protocol::ElicitationAction;
 use codex_core::protocol::ExecPolicyAmendment;
@@ -125,7 +128,7 @@ impl ApprovalOverlay {
         };
 
         let header = Box::new(ColumnRenderable::with([
-            Line::from(title.bold()).into(),
+            Line::from(Span::styled(title, theme::current().approval_title)).into(),
             Line::from("").into(),
             header,
         ]));
@@ -446,12 +449,17 @@ impl ApprovalOption {
     }
 }
 
+/// Shortcuts for `action` from `[tui.keybindings]`.
+fn shortcuts(action: KeybindingAction) -> Vec<KeyBinding> {
+    keymap().bindings(action).to_vec()
+}
+
 fn exec_options(proposed_execpolicy_amendment: Option<ExecPolicyAmendment>) -> Vec<ApprovalOption> {
     vec![ApprovalOption {
         label: "Yes, proceed".to_string(),
         decision: ApprovalDecision::Review(ReviewDecision::Approved),
         display_shortcut: None,
-        additional_shortcuts: vec![key_hint::plain(KeyCode::Char('y'))],
+        additional_shortcuts: shortcuts(KeybindingAction::Approve),
     }]
     .into_iter()
     .chain(proposed_execpolicy_amendment.and_then(|prefix| {
@@ -468,14 +476,14 @@ fn exec_options(proposed_execpolicy_amendment: Option<ExecPolicyAmendment>) -> V
                 proposed_execpolicy_amendment: prefix,
             }),
             display_shortcut: None,
-            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('p'))],
+            additional_shortcuts: shortcuts(KeybindingAction::ApprovePrefix),
         })
     }))
     .chain([ApprovalOption {
         label: "No, and tell Codex what to do differently".to_string(),
         decision: ApprovalDecision::Review(ReviewDecision::Abort),
         display_shortcut: Some(key_hint::plain(KeyCode::Esc)),
-        additional_shortcuts: vec![key_hint::plain(KeyCode::Char('n'))],
+        additional_shortcuts: shortcuts(KeybindingAction::Deny),
     }])
     .collect()
 }
@@ -486,19 +494,19 @@ fn patch_options() -> Vec<ApprovalOption> {
             label: "Yes, proceed".to_string(),
             decision: ApprovalDecision::Review(ReviewDecision::Approved),
             display_shortcut: None,
-            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('y'))],
+            additional_shortcuts: shortcuts(KeybindingAction::Approve),
         },
         ApprovalOption {
             label: "Yes, and don't ask again for these files".to_string(),
             decision: ApprovalDecision::Review(ReviewDecision::ApprovedForSession),
             display_shortcut: None,
-            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('a'))],
+            additional_shortcuts: shortcuts(KeybindingAction::ApproveForSession),
         },
         ApprovalOption {
             label: "No, and tell Codex what to do differently".to_string(),
             decision: ApprovalDecision::Review(ReviewDecision::Abort),
             display_shortcut: Some(key_hint::plain(KeyCode::Esc)),
-            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('n'))],
+            additional_shortcuts: shortcuts(KeybindingAction::Deny),
         },
     ]
 }
@@ -509,19 +517,19 @@ fn elicitation_options() -> Vec<ApprovalOption> {
             label: "Yes, provide the requested info".to_string(),
             decision: ApprovalDecision::McpElicitation(ElicitationAction::Accept),
             display_shortcut: None,
-            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('y'))],
+            additional_shortcuts: shortcuts(KeybindingAction::Approve),
         },
         ApprovalOption {
             label: "No, but continue without it".to_string(),
             decision: ApprovalDecision::McpElicitation(ElicitationAction::Decline),
             display_shortcut: None,
-            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('n'))],
+            additional_shortcuts: shortcuts(KeybindingAction::Deny),
         },
         ApprovalOption {
             label: "Cancel this request".to_string(),
             decision: ApprovalDecision::McpElicitation(ElicitationAction::Cancel),
             display_shortcut: Some(key_hint::plain(KeyCode::Esc)),
-            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('c'))],
+            additional_shortcuts: shortcuts(KeybindingAction::Cancel),
         },
     ]
 }
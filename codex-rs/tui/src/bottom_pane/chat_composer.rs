@@ -85,6 +85,7 @@
 use crate::key_hint;
 use crate::key_hint::KeyBinding;
 use crate::key_hint::has_ctrl_or_alt;
+use crate::keymap::keymap;
 use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
 use crossterm::event::KeyEventKind;
@@ -132,13 +133,14 @@ use super::paste_burst::PasteBurst;
 use super::skill_popup::MentionItem;
 use super::skill_popup::SkillPopup;
 use super::slash_commands;
+use super::vim::Vim;
+use super::vim::VimAction;
+use super::vim::VimMode;
 use crate::bottom_pane::paste_burst::FlushResult;
 use crate::bottom_pane::prompt_args::expand_custom_prompt;
 use crate::bottom_pane::prompt_args::expand_if_numeric_with_positional_args;
 use crate::bottom_pane::prompt_args::parse_slash_name;
-use crate::bottom_pane::prompt_args::prompt_argument_names;
 use crate::bottom_pane::prompt_args::prompt_command_with_arg_placeholders;
-use crate::bottom_pane::prompt_args::prompt_has_numeric_placeholders;
 use crate::render::Insets;
 use crate::render::RectExt;
 use crate::render::renderable::Renderable;
@@ -150,6 +152,8 @@ use codex_protocol::custom_prompts::PROMPTS_CMD_PREFIX;
 use codex_protocol::models::local_image_label_text;
 use codex_protocol::user_input::ByteRange;
 use codex_protocol::user_input::TextElement;
+use codex_utils_image::is_supported_image_file;
+use codex_utils_image::split_page_suffix;
 
 use crate::app_event::AppEvent;
 use crate::app_event::ConnectorsSnapshot;
@@ -163,6 +167,9 @@ use crate::history_cell;
 use crate::ui_consts::LIVE_PREFIX_COLS;
 use codex_chatgpt::connectors;
 use codex_chatgpt::connectors::AppInfo;
+use codex_core::config::keybindings::KeybindingAction;
+use codex_core::custom_prompts::prompt_argument_names;
+use codex_core::custom_prompts::prompt_has_numeric_placeholders;
 use codex_core::skills::model::SkillMetadata;
 use codex_file_search::FileMatch;
 use std::cell::RefCell;
@@ -309,6 +316,8 @@ pub(crate) struct ChatComposer {
     connectors_enabled: bool,
     personality_command_enabled: bool,
     windows_degraded_sandbox_active: bool,
+    /// Vi-style modal editing state; `None` unless `tui.vim_mode` is enabled.
+    vim: Option<Vim>,
 }
 
 #[derive(Clone, Debug)]
@@ -405,6 +414,7 @@ impl ChatComposer {
             connectors_enabled: false,
             personality_command_enabled: false,
             windows_degraded_sandbox_active: false,
+            vim: None,
         };
         // Apply configuration via the setter to keep side-effects centralized.
         this.set_disable_paste_burst(disable_paste_burst);
@@ -468,6 +478,28 @@ impl ChatComposer {
     pub fn set_personality_command_enabled(&mut self, enabled: bool) {
         self.personality_command_enabled = enabled;
     }
+
+    /// Enables or disables vi-style modal editing. The composer starts in insert mode either way.
+    pub(crate) fn set_vim_mode_enabled(&mut self, enabled: bool) {
+        if enabled == self.vim.is_some() {
+            return;
+        }
+        self.vim = enabled.then(Vim::new);
+        self.textarea.set_highlight(None);
+    }
+
+    /// Whether `Esc` belongs to vi mode (leaving insert/visual mode or cancelling a pending
+    /// command), so callers should not treat it as interrupt or backtrack.
+    pub(crate) fn vim_captures_esc(&self) -> bool {
+        self.vim.as_ref().is_some_and(Vim::captures_esc)
+    }
+
+    /// Popups follow what is being typed, so they stay closed outside vi insert mode.
+    fn vim_command_mode(&self) -> bool {
+        self.vim
+            .as_ref()
+            .is_some_and(|vim| vim.mode() != VimMode::Insert)
+    }
     /// Centralized feature gating keeps config checks out of call sites.
     fn popups_enabled(&self) -> bool {
         self.config.popups_enabled
@@ -582,6 +614,9 @@ impl ChatComposer {
         }
         // Explicit paste events should not trigger Enter suppression.
         self.paste_burst.clear_after_explicit_paste();
+        if let Some(vim) = self.vim.as_ref() {
+            vim.sync(&mut self.textarea);
+        }
         self.sync_popups();
         true
     }
@@ -591,22 +626,21 @@ impl ChatComposer {
             return false;
         };
 
-        // normalize_pasted_path already handles Windows → WSL path conversion,
-        // so we can directly try to read the image dimensions.
-        match image::image_dimensions(&path_buf) {
-            Ok((width, height)) => {
-                tracing::info!("OK: {pasted}");
-                tracing::debug!("image dimensions={}x{}", width, height);
-                let format = pasted_image_format(&path_buf);
-                tracing::debug!("attached image format={}", format.label());
-                self.attach_image(path_buf);
-                true
-            }
-            Err(err) => {
-                tracing::trace!("ERR: {err}");
-                false
-            }
+        // normalize_pasted_path already handles Windows → WSL path conversion.
+        // A `#pages=2-3` suffix on a PDF is kept and resolved when the message is sent.
+        let (file_path, _) = split_page_suffix(&path_buf);
+        if !is_supported_image_file(&file_path) {
+            tracing::trace!("ERR: not an image, PDF or SVG: {pasted}");
+            return false;
+        }
+        tracing::info!("OK: {pasted}");
+        if let Ok((width, height)) = image::image_dimensions(&file_path) {
+            tracing::debug!("image dimensions={}x{}", width, height);
+            let format = pasted_image_format(&file_path);
+            tracing::debug!("attached image format={}", format.label());
         }
+        self.attach_image(path_buf);
+        true
     }
 
     /// Enable or disable paste-burst handling.
@@ -1017,6 +1051,11 @@ impl ChatComposer {
             return (InputResult::None, false);
         }
 
+        let Some(key_event) = self.handle_vim_key(key_event) else {
+            self.sync_popups();
+            return (InputResult::None, true);
+        };
+
         let result = match &mut self.active_popup {
             ActivePopup::Command(_) => self.handle_key_event_with_slash_popup(key_event),
             ActivePopup::File(_) => self.handle_key_event_with_file_popup(key_event),
@@ -1025,12 +1064,49 @@ impl ChatComposer {
             ActivePopup::None => self.handle_key_event_without_popup(key_event),
         };
 
+        if let Some(vim) = self.vim.as_mut() {
+            if matches!(result.0, InputResult::None) {
+                vim.sync(&mut self.textarea);
+            } else {
+                // A submitted or dispatched prompt starts the next one in insert mode.
+                vim.reset(&mut self.textarea);
+            }
+        }
+
         // Update (or hide/show) popup after processing the key.
         self.sync_popups();
 
         result
     }
 
+    /// Gives vi mode the first look at `key_event`.
+    ///
+    /// Returns the key the composer should still handle, or `None` when vi mode consumed it. In
+    /// insert mode only `Esc` is claimed, and only once no popup wants it for dismissal. Any
+    /// paste burst still being buffered is flushed into the text first, so pasted characters
+    /// are never replayed as normal-mode commands.
+    fn handle_vim_key(&mut self, key_event: KeyEvent) -> Option<KeyEvent> {
+        let Some(mode) = self.vim.as_ref().map(Vim::mode) else {
+            return Some(key_event);
+        };
+        if mode == VimMode::Insert {
+            if key_event.code != KeyCode::Esc || self.popup_active() {
+                return Some(key_event);
+            }
+            if let Some(pasted) = self.paste_burst.flush_before_modified_input() {
+                self.handle_paste(pasted);
+            }
+            self.paste_burst.clear_after_explicit_paste();
+        }
+        let Some(vim) = self.vim.as_mut() else {
+            return Some(key_event);
+        };
+        match vim.handle_key(key_event, &mut self.textarea) {
+            VimAction::Handled => None,
+            VimAction::PassThrough(key_event) => Some(key_event),
+        }
+    }
+
     /// Return true if either the slash-command popup or the file-search popup is active.
     pub(crate) fn popup_active(&self) -> bool {
         !matches!(self.active_popup, ActivePopup::None)
@@ -1396,48 +1472,44 @@ impl ChatComposer {
                 };
 
                 let sel_path = sel.to_string_lossy().to_string();
-                // If selected path looks like an image (png/jpeg), attach as image instead of inserting text.
+                // If selected path looks like an image (png/jpeg/pdf/svg), attach as image instead of inserting text.
                 let is_image = Self::is_image_path(&sel_path);
                 if is_image {
-                    // Determine dimensions; if that fails fall back to normal path insertion.
+                    // Check the contents; if they are not loadable fall back to normal path insertion.
                     let path_buf = PathBuf::from(&sel_path);
-                    match image::image_dimensions(&path_buf) {
-                        Ok((width, height)) => {
-                            tracing::debug!("selected image dimensions={}x{}", width, height);
-                            // Remove the current @token (mirror logic from insert_selected_path without inserting text)
-                            // using the flat text and byte-offset cursor API.
-                            let cursor_offset = self.textarea.cursor();
-                            let text = self.textarea.text();
-                            // Clamp to a valid char boundary to avoid panics when slicing.
-                            let safe_cursor = Self::clamp_to_char_boundary(text, cursor_offset);
-                            let before_cursor = &text[..safe_cursor];
-                            let after_cursor = &text[safe_cursor..];
-
-                            // Determine token boundaries in the full text.
-                            let start_idx = before_cursor
-                                .char_indices()
-                                .rfind(|(_, c)| c.is_whitespace())
-                                .map(|(idx, c)| idx + c.len_utf8())
-                                .unwrap_or(0);
-                            let end_rel_idx = after_cursor
-                                .char_indices()
-                                .find(|(_, c)| c.is_whitespace())
-                                .map(|(idx, _)| idx)
-                                .unwrap_or(after_cursor.len());
-                            let end_idx = safe_cursor + end_rel_idx;
-
-                            self.textarea.replace_range(start_idx..end_idx, "");
-                            self.textarea.set_cursor(start_idx);
-
-                            self.attach_image(path_buf);
-                            // Add a trailing space to keep typing fluid.
-                            self.textarea.insert_str(" ");
-                        }
-                        Err(err) => {
-                            tracing::trace!("image dimensions lookup failed: {err}");
-                            // Fallback to plain path insertion if metadata read fails.
-                            self.insert_selected_path(&sel_path);
-                        }
+                    if is_supported_image_file(&path_buf) {
+                        // Remove the current @token (mirror logic from insert_selected_path without inserting text)
+                        // using the flat text and byte-offset cursor API.
+                        let cursor_offset = self.textarea.cursor();
+                        let text = self.textarea.text();
+                        // Clamp to a valid char boundary to avoid panics when slicing.
+                        let safe_cursor = Self::clamp_to_char_boundary(text, cursor_offset);
+                        let before_cursor = &text[..safe_cursor];
+                        let after_cursor = &text[safe_cursor..];
+
+                        // Determine token boundaries in the full text.
+                        let start_idx = before_cursor
+                            .char_indices()
+                            .rfind(|(_, c)| c.is_whitespace())
+                            .map(|(idx, c)| idx + c.len_utf8())
+                            .unwrap_or(0);
+                        let end_rel_idx = after_cursor
+                            .char_indices()
+                            .find(|(_, c)| c.is_whitespace())
+                            .map(|(idx, _)| idx)
+                            .unwrap_or(after_cursor.len());
+                        let end_idx = safe_cursor + end_rel_idx;
+
+                        self.textarea.replace_range(start_idx..end_idx, "");
+                        self.textarea.set_cursor(start_idx);
+
+                        self.attach_image(path_buf);
+                        // Add a trailing space to keep typing fluid.
+                        self.textarea.insert_str(" ");
+                    } else {
+                        tracing::trace!("selected file is not a loadable image: {sel_path}");
+                        // Fallback to plain path insertion if the file cannot be loaded.
+                        self.insert_selected_path(&sel_path);
                     }
                 } else {
                     // Non-image: inserting file path.
@@ -1591,7 +1663,9 @@ impl ChatComposer {
 
     fn is_image_path(path: &str) -> bool {
         let lower = path.to_ascii_lowercase();
-        lower.ends_with(".png") || lower.ends_with(".jpg") || lower.ends_with(".jpeg")
+        [".png", ".jpg", ".jpeg", ".pdf", ".svg"]
+            .iter()
+            .any(|ext| lower.ends_with(ext))
     }
 
     fn trim_text_elements(
@@ -2448,20 +2522,18 @@ impl ChatComposer {
                 }
                 self.handle_input_basic(key_event)
             }
-            KeyEvent {
-                code: KeyCode::Tab,
-                modifiers: KeyModifiers::NONE,
-                kind: KeyEventKind::Press,
-                ..
-            } if self.is_task_running => self.handle_submission(true),
-            KeyEvent {
-                code: KeyCode::Enter,
-                modifiers: KeyModifiers::NONE,
-                ..
-            } => {
+            _ if self.is_task_running && keymap().is_press(KeybindingAction::Queue, key_event) => {
+                self.handle_submission(true)
+            }
+            _ if keymap().is_press(KeybindingAction::Submit, key_event) => {
                 let should_queue = !self.steer_enabled;
                 self.handle_submission(should_queue)
             }
+            // Route user newline chords through the textarea's own newline key so
+            // paste-burst handling sees them like Shift+Enter.
+            _ if keymap().is_press(KeybindingAction::Newline, key_event) => {
+                self.handle_input_basic(KeyEvent::new(KeyCode::Enter, KeyModifiers::SHIFT))
+            }
             input => self.handle_input_basic(input),
         }
     }
@@ -2769,7 +2841,7 @@ impl ChatComposer {
 
     fn sync_popups(&mut self) {
         self.sync_slash_command_elements();
-        if !self.popups_enabled() {
+        if !self.popups_enabled() || self.vim_command_mode() {
             self.active_popup = ActivePopup::None;
             return;
         }
@@ -3365,12 +3437,21 @@ impl ChatComposer {
                     | FooterMode::ShortcutOverlay
                     | FooterMode::EscHint => false,
                 };
-                let context_line = context_window_line(
+                let mut context_line = context_window_line(
                     footer_props.context_window_percent,
                     footer_props.context_window_used_tokens,
                     footer_props.prompt_suggestions_enabled,
                     footer_props.prompt_suggestions_autorun,
                 );
+                if let Some(vim) = self.vim.as_ref() {
+                    let mut indicator = vec![vim.mode().label().bold()];
+                    let pending = vim.pending_keys();
+                    if !pending.is_empty() {
+                        indicator.push(format!(" {pending}").dim());
+                    }
+                    indicator.push(" · ".dim());
+                    context_line.spans.splice(0..0, indicator);
+                }
                 let context_width = context_line.width() as u16;
                 let custom_height = self.custom_footer_height();
                 let footer_hint_height =
@@ -5769,6 +5850,51 @@ mod tests {
         assert_eq!(composer.local_image_paths(), vec![path]);
     }
 
+    /// Behavior: with vi mode on, Esc lands a buffered paste burst as text before leaving insert
+    /// mode, submitting returns to insert mode, and `k` on an empty prompt recalls history.
+    #[test]
+    fn vim_mode_composes_with_paste_burst_and_history() {
+        let (tx, _rx) = unbounded_channel::<AppEvent>();
+        let sender = AppEventSender::new(tx);
+        let mut composer = ChatComposer::new(
+            true,
+            sender,
+            false,
+            "Ask Codex to do anything".to_string(),
+            false,
+        );
+        composer.set_steer_enabled(true);
+        composer.set_vim_mode_enabled(true);
+        let mode = |composer: &ChatComposer| composer.vim.as_ref().map(Vim::mode);
+
+        composer
+            .paste_burst
+            .begin_with_retro_grabbed(String::new(), Instant::now());
+        for ch in ['d', 'd', 'x'] {
+            let _ = composer.handle_key_event(KeyEvent::new(KeyCode::Char(ch), KeyModifiers::NONE));
+        }
+        assert!(composer.is_in_paste_burst());
+        let _ = composer.handle_key_event(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+        assert_eq!(composer.textarea.text(), "ddx");
+        assert!(!composer.is_in_paste_burst());
+        assert_eq!(mode(&composer), Some(VimMode::Normal));
+
+        let _ = composer.handle_key_event(KeyEvent::new(KeyCode::Char('x'), KeyModifiers::NONE));
+        assert_eq!(composer.textarea.text(), "dd");
+
+        let (result, _needs_redraw) =
+            composer.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert!(matches!(result, InputResult::Submitted { .. }));
+        assert_eq!(mode(&composer), Some(VimMode::Insert));
+        assert!(composer.vim_captures_esc());
+
+        let _ = composer.handle_key_event(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+        assert!(!composer.vim_captures_esc());
+        let _ = composer.handle_key_event(KeyEvent::new(KeyCode::Char('k'), KeyModifiers::NONE));
+        assert_eq!(composer.textarea.text(), "dd");
+        assert_eq!(mode(&composer), Some(VimMode::Normal));
+    }
+
     #[test]
     fn set_text_content_reattaches_images_without_placeholder_metadata() {
         let (tx, _rx) = unbounded_channel::<AppEvent>();
@@ -6285,6 +6411,31 @@ mod tests {
         assert_eq!(imgs, vec![tmp_path]);
     }
 
+    #[test]
+    fn pasting_pdf_path_with_page_suffix_attaches_it() {
+        let tmp = tempdir().expect("create TempDir");
+        let pdf_path = tmp.path().join("design.pdf");
+        std::fs::write(&pdf_path, b"%PDF-1.7\n").expect("failed to write temp pdf");
+        let pasted = format!("{}#pages=2-3", pdf_path.display());
+
+        let (tx, _rx) = unbounded_channel::<AppEvent>();
+        let sender = AppEventSender::new(tx);
+        let mut composer = ChatComposer::new(
+            true,
+            sender,
+            false,
+            "Ask Codex to do anything".to_string(),
+            false,
+        );
+
+        assert!(composer.handle_paste(pasted.clone()));
+        assert!(composer.textarea.text().starts_with("[Image #1] "));
+        assert_eq!(
+            composer.take_recent_submission_images(),
+            vec![PathBuf::from(pasted)]
+        );
+    }
+
     #[test]
     fn selecting_custom_prompt_without_args_submits_content() {
         let prompt_text = "Hello from saved prompt";
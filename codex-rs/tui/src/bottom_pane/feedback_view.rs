@@ -1,5 +1,6 @@
 use std::cell::RefCell;
 use std::path::PathBuf;
+use std::sync::Arc;
 
 use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
@@ -19,6 +20,7 @@ use crate::app_event::FeedbackCategory;
 use crate::app_event_sender::AppEventSender;
 use crate::history_cell;
 use crate::render::renderable::Renderable;
+use codex_core::debug_bundle::DebugBundle;
 use codex_core::protocol::SessionSource;
 
 use super::CancellationEvent;
@@ -537,6 +539,56 @@ pub(crate) fn feedback_upload_consent_params(
     }
 }
 
+/// Lists what a `/feedback --save` bundle contains and what was redacted, and saves it on
+/// confirmation.
+pub(crate) fn debug_bundle_preview_params(
+    app_event_tx: AppEventSender,
+    bundle: Arc<DebugBundle>,
+    path: PathBuf,
+) -> super::SelectionViewParams {
+    let mut header_lines: Vec<Box<dyn crate::render::renderable::Renderable>> = vec![
+        Line::from("Save debug bundle?".bold()).into(),
+        Line::from(format!("Writes {}. Nothing is uploaded.", path.display()).dim()).into(),
+        Line::from("").into(),
+    ];
+    header_lines.extend(
+        bundle
+            .preview_lines()
+            .into_iter()
+            .map(|line| Box::new(Line::from(line)) as Box<dyn Renderable>),
+    );
+
+    let save_action: super::SelectionAction = Box::new(move |sender: &AppEventSender| {
+        let _ = sender;
+        app_event_tx.send(AppEvent::SaveDebugBundle {
+            bundle: bundle.clone(),
+            path: path.clone(),
+        });
+    });
+
+    super::SelectionViewParams {
+        footer_hint: Some(standard_popup_hint_line()),
+        items: vec![
+            super::SelectionItem {
+                name: "Save".to_string(),
+                description: Some("Write the bundle to share with support.".to_string()),
+                actions: vec![save_action],
+                dismiss_on_select: true,
+                ..Default::default()
+            },
+            super::SelectionItem {
+                name: "Cancel".to_string(),
+                dismiss_on_select: true,
+                ..Default::default()
+            },
+        ],
+        header: Box::new(crate::render::renderable::ColumnRenderable::with(
+            header_lines,
+        )),
+        ..Default::default()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -34,9 +34,12 @@
 //! `FooterProps` mapping.
 use crate::key_hint;
 use crate::key_hint::KeyBinding;
+use crate::keymap::keymap;
 use crate::render::line_utils::prefix_lines;
 use crate::status::format_tokens_compact;
+use crate::theme;
 use crate::ui_consts::FOOTER_INDENT_COLS;
+use codex_core::config::keybindings::KeybindingAction;
 use crossterm::event::KeyCode;
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
@@ -85,8 +88,13 @@ const FOOTER_CONTEXT_GAP_COLS: u16 = 1;
 
 impl CollaborationModeIndicator {
     fn label(self, show_cycle_hint: bool) -> String {
-        let suffix = if show_cycle_hint {
+        let keymap = keymap();
+        let suffix = if !show_cycle_hint {
+            String::new()
+        } else if keymap.is_default(KeybindingAction::CycleMode) {
             format!(" ({MODE_CYCLE_HINT})")
+        } else if let Some(key) = keymap.hint_label(KeybindingAction::CycleMode) {
+            format!(" ({key} to cycle)")
         } else {
             String::new()
         };
@@ -101,10 +109,13 @@ impl CollaborationModeIndicator {
 
     fn styled_span(self, show_cycle_hint: bool) -> Span<'static> {
         let label = self.label(show_cycle_hint);
+        let theme = theme::current();
         match self {
-            CollaborationModeIndicator::Plan => Span::from(label).magenta(),
-            CollaborationModeIndicator::PairProgramming => Span::from(label).cyan(),
-            CollaborationModeIndicator::Execute => Span::from(label).dim(),
+            CollaborationModeIndicator::Plan => Span::styled(label, theme.mode_plan),
+            CollaborationModeIndicator::PairProgramming => {
+                Span::styled(label, theme.mode_pair_programming)
+            }
+            CollaborationModeIndicator::Execute => Span::styled(label, theme.mode_execute),
         }
     }
 }
@@ -258,17 +269,21 @@ fn left_side_line(
             line.push_span(" for shortcuts".dim());
         }
         SummaryHintKind::QueueMessage => {
-            line.push_span(key_hint::plain(KeyCode::Tab));
-            line.push_span(" to queue message".dim());
+            if let Some(key) = keymap().hint(KeybindingAction::Queue) {
+                line.push_span(key);
+                line.push_span(" to queue message".dim());
+            }
         }
         SummaryHintKind::QueueShort => {
-            line.push_span(key_hint::plain(KeyCode::Tab));
-            line.push_span(" to queue".dim());
+            if let Some(key) = keymap().hint(KeybindingAction::Queue) {
+                line.push_span(key);
+                line.push_span(" to queue".dim());
+            }
         }
     };
 
     if let Some(collaboration_mode_indicator) = collaboration_mode_indicator {
-        if !matches!(state.hint, SummaryHintKind::None) {
+        if line.width() > 0 {
             line.push_span(" · ".dim());
         }
         line.push_span(collaboration_mode_indicator.styled_span(state.show_cycle_hint));
@@ -760,13 +775,14 @@ pub(crate) fn context_window_line(
         "100% context left".to_string()
     };
 
-    let mut spans = vec![Span::from(base).dim()];
+    let theme = theme::current();
+    let mut spans = vec![Span::styled(base, theme.status_line)];
     if prompt_suggestions_enabled {
-        spans.push(Span::from(" | Suggestions auto: ").dim());
+        spans.push(Span::styled(" | Suggestions auto: ", theme.status_line));
         let status = if prompt_suggestions_autorun {
-            "On".green()
+            Span::styled("On", theme.success)
         } else {
-            "Off".red()
+            Span::styled("Off", theme.error)
         };
         spans.push(status);
     }
@@ -788,6 +804,25 @@ enum ShortcutId {
     ChangeMode,
 }
 
+impl ShortcutId {
+    /// The `[tui.keybindings]` action behind this shortcut, if it is configurable.
+    fn action(self) -> Option<KeybindingAction> {
+        match self {
+            ShortcutId::InsertNewline => Some(KeybindingAction::Newline),
+            ShortcutId::QueueMessageTab => Some(KeybindingAction::Queue),
+            ShortcutId::ExternalEditor => Some(KeybindingAction::ExternalEditor),
+            ShortcutId::ShowTranscript => Some(KeybindingAction::OpenTranscript),
+            ShortcutId::ChangeMode => Some(KeybindingAction::CycleMode),
+            ShortcutId::Commands
+            | ShortcutId::ShellCommands
+            | ShortcutId::FilePaths
+            | ShortcutId::PasteImage
+            | ShortcutId::EditPrevious
+            | ShortcutId::Quit => None,
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 struct ShortcutBinding {
     key: KeyBinding,
@@ -835,7 +870,12 @@ impl ShortcutDescriptor {
 
     fn overlay_entry(&self, state: ShortcutsState) -> Option<Line<'static>> {
         let binding = self.binding_for(state)?;
-        let mut line = Line::from(vec![self.prefix.into(), binding.key.into()]);
+        // Customized bindings replace the built-in, context-dependent ones.
+        let key = match self.id.action() {
+            Some(action) if !keymap().is_default(action) => keymap().hint(action)?,
+            _ => binding.key,
+        };
+        let mut line = Line::from(vec![self.prefix.into(), key.into()]);
         match self.id {
             ShortcutId::EditPrevious => {
                 if state.esc_backtrack_hint {
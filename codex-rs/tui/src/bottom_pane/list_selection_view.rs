@@ -36,6 +36,9 @@ use unicode_width::UnicodeWidthStr;
 /// One selectable item in the generic selection list.
 pub(crate) type SelectionAction = Box<dyn Fn(&AppEventSender) + Send + Sync>;
 
+/// Called with the source index of the newly highlighted item as the user moves through the list.
+pub(crate) type SelectionChangedAction = Box<dyn Fn(usize, &AppEventSender) + Send + Sync>;
+
 /// One row in a [`ListSelectionView`] selection list.
 ///
 /// This is the source-of-truth model for row state before filtering and
@@ -78,6 +81,11 @@ pub(crate) struct SelectionViewParams {
     pub col_width_mode: ColumnWidthMode,
     pub header: Box<dyn Renderable>,
     pub initial_selected_idx: Option<usize>,
+    /// Runs whenever keyboard navigation or filtering highlights a different item, which lets
+    /// callers preview a choice before it is accepted.
+    pub on_selection_changed: Option<SelectionChangedAction>,
+    /// Runs when the view is dismissed with Esc or Ctrl+C instead of accepting an item.
+    pub on_cancel: Option<SelectionAction>,
 }
 
 impl Default for SelectionViewParams {
@@ -93,6 +101,8 @@ impl Default for SelectionViewParams {
             col_width_mode: ColumnWidthMode::AutoVisible,
             header: Box::new(()),
             initial_selected_idx: None,
+            on_selection_changed: None,
+            on_cancel: None,
         }
     }
 }
@@ -117,6 +127,9 @@ pub(crate) struct ListSelectionView {
     last_selected_actual_idx: Option<usize>,
     header: Box<dyn Renderable>,
     initial_selected_idx: Option<usize>,
+    on_selection_changed: Option<SelectionChangedAction>,
+    on_cancel: Option<SelectionAction>,
+    last_notified_idx: Option<usize>,
 }
 
 impl ListSelectionView {
@@ -157,8 +170,12 @@ impl ListSelectionView {
             last_selected_actual_idx: None,
             header,
             initial_selected_idx: params.initial_selected_idx,
+            on_selection_changed: params.on_selection_changed,
+            on_cancel: params.on_cancel,
+            last_notified_idx: None,
         };
         s.apply_filter();
+        s.last_notified_idx = s.selected_actual_idx();
         s
     }
 
@@ -274,6 +291,7 @@ impl ListSelectionView {
         let visible = Self::max_visible_rows(len);
         self.state.ensure_visible(len, visible);
         self.skip_disabled_up();
+        self.notify_selection_changed();
     }
 
     fn move_down(&mut self) {
@@ -282,6 +300,25 @@ impl ListSelectionView {
         let visible = Self::max_visible_rows(len);
         self.state.ensure_visible(len, visible);
         self.skip_disabled_down();
+        self.notify_selection_changed();
+    }
+
+    fn selected_actual_idx(&self) -> Option<usize> {
+        self.state
+            .selected_idx
+            .and_then(|idx| self.filtered_indices.get(idx))
+            .copied()
+    }
+
+    fn notify_selection_changed(&mut self) {
+        let selected = self.selected_actual_idx();
+        if selected == self.last_notified_idx {
+            return;
+        }
+        self.last_notified_idx = selected;
+        if let (Some(callback), Some(idx)) = (&self.on_selection_changed, selected) {
+            callback(idx, &self.app_event_tx);
+        }
     }
 
     fn accept(&mut self) {
@@ -408,6 +445,7 @@ impl BottomPaneView for ListSelectionView {
             } if self.is_searchable => {
                 self.search_query.pop();
                 self.apply_filter();
+                self.notify_selection_changed();
             }
             KeyEvent {
                 code: KeyCode::Esc, ..
@@ -424,6 +462,7 @@ impl BottomPaneView for ListSelectionView {
             {
                 self.search_query.push(c);
                 self.apply_filter();
+                self.notify_selection_changed();
             }
             KeyEvent {
                 code: KeyCode::Char(c),
@@ -461,6 +500,9 @@ impl BottomPaneView for ListSelectionView {
     }
 
     fn on_ctrl_c(&mut self) -> CancellationEvent {
+        if let Some(on_cancel) = &self.on_cancel {
+            on_cancel(&self.app_event_tx);
+        }
         self.complete = true;
         CancellationEvent::Handled
     }
@@ -1151,4 +1193,45 @@ mod tests {
             "fixed description column changed across scroll:\nbefore:\n{before_scroll}\nafter:\n{after_scroll}"
         );
     }
+
+    #[test]
+    fn selection_changes_and_cancel_invoke_callbacks() {
+        use std::sync::Arc;
+        use std::sync::Mutex;
+
+        let (tx_raw, _rx) = unbounded_channel::<AppEvent>();
+        let tx = AppEventSender::new(tx_raw);
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let changed = Arc::clone(&seen);
+        let cancelled = Arc::clone(&seen);
+        let mut view = ListSelectionView::new(
+            SelectionViewParams {
+                items: make_scrolling_width_items().into_iter().take(3).collect(),
+                on_selection_changed: Some(Box::new(move |idx, _| {
+                    changed.lock().unwrap().push(format!("highlight {idx}"));
+                })),
+                on_cancel: Some(Box::new(move |_| {
+                    cancelled.lock().unwrap().push("cancel".to_string());
+                })),
+                ..Default::default()
+            },
+            tx,
+        );
+
+        view.handle_key_event(KeyEvent::from(KeyCode::Down));
+        view.handle_key_event(KeyEvent::from(KeyCode::Down));
+        view.handle_key_event(KeyEvent::from(KeyCode::Up));
+        view.handle_key_event(KeyEvent::from(KeyCode::Esc));
+
+        assert!(view.is_complete());
+        assert_eq!(
+            *seen.lock().unwrap(),
+            vec![
+                "highlight 1".to_string(),
+                "highlight 2".to_string(),
+                "highlight 1".to_string(),
+                "cancel".to_string(),
+            ]
+        );
+    }
 }
@@ -21,11 +21,13 @@ use crate::bottom_pane::queued_user_messages::QueuedUserMessages;
 use crate::bottom_pane::unified_exec_footer::UnifiedExecFooter;
 use crate::key_hint;
 use crate::key_hint::KeyBinding;
+use crate::keymap::keymap;
 use crate::render::renderable::FlexRenderable;
 use crate::render::renderable::Renderable;
 use crate::render::renderable::RenderableItem;
 use crate::tui::FrameRequester;
 use bottom_pane_view::BottomPaneView;
+use codex_core::config::keybindings::KeybindingAction;
 use codex_core::features::Features;
 use codex_core::skills::model::SkillMetadata;
 use codex_file_search::FileMatch;
@@ -83,6 +85,7 @@ pub(crate) use list_selection_view::SelectionViewParams;
 pub(crate) use mcp_search_toggle_view::McpSearchToggleView;
 mod feedback_view;
 pub(crate) use feedback_view::FeedbackAudience;
+pub(crate) use feedback_view::debug_bundle_preview_params;
 pub(crate) use feedback_view::feedback_disabled_params;
 pub(crate) use feedback_view::feedback_selection_params;
 pub(crate) use feedback_view::feedback_upload_consent_params;
@@ -95,6 +98,7 @@ mod scroll_state;
 mod selection_popup_common;
 mod textarea;
 mod unified_exec_footer;
+mod vim;
 pub(crate) use background_terminals_view::BackgroundTerminalsState;
 pub(crate) use background_terminals_view::BackgroundTerminalsView;
 pub(crate) use feedback_view::FeedbackNoteView;
@@ -185,6 +189,9 @@ pub(crate) struct BottomPane {
     queued_user_messages: QueuedUserMessages,
     context_window_percent: Option<i64>,
     context_window_used_tokens: Option<i64>,
+    /// Placeholder shown while the composer is permanently read-only, e.g. when watching a
+    /// shared session. Closing a modal keeps the composer disabled while this is set.
+    read_only_placeholder: Option<String>,
 }
 
 pub(crate) struct BottomPaneParams {
@@ -235,6 +242,7 @@ impl BottomPane {
             animations_enabled,
             context_window_percent: None,
             context_window_used_tokens: None,
+            read_only_placeholder: None,
         }
     }
 
@@ -281,6 +289,11 @@ impl BottomPane {
         self.composer.set_steer_enabled(enabled);
     }
 
+    pub fn set_vim_mode_enabled(&mut self, enabled: bool) {
+        self.composer.set_vim_mode_enabled(enabled);
+        self.request_redraw();
+    }
+
     pub fn set_collaboration_modes_enabled(&mut self, enabled: bool) {
         self.composer.set_collaboration_modes_enabled(enabled);
         self.request_redraw();
@@ -377,12 +390,14 @@ impl BottomPane {
             self.request_redraw();
             InputResult::None
         } else {
-            // If a task is running and a status line is visible, allow Esc to
-            // send an interrupt even while the composer has focus.
-            // When a popup is active, prefer dismissing it over interrupting the task.
-            if key_event.code == KeyCode::Esc
+            // If a task is running and a status line is visible, allow the interrupt
+            // key (Esc by default) to interrupt even while the composer has focus.
+            // When a popup is active, prefer dismissing it over interrupting the task, and let
+            // vi mode keep the Esc it uses to leave insert/visual mode.
+            if keymap().is_press(KeybindingAction::Interrupt, key_event)
                 && self.is_task_running
                 && !self.composer.popup_active()
+                && !(key_event.code == KeyCode::Esc && self.composer.vim_captures_esc())
                 && let Some(status) = &self.status
             {
                 // Send Op::Interrupt
@@ -502,6 +517,12 @@ impl BottomPane {
         self.request_redraw();
     }
 
+    /// Disables the composer for the rest of the session.
+    pub(crate) fn set_read_only(&mut self, placeholder: String) {
+        self.read_only_placeholder = Some(placeholder.clone());
+        self.set_composer_input_enabled(false, Some(placeholder));
+    }
+
     pub(crate) fn clear_composer_for_ctrl_c(&mut self) {
         self.composer.clear_for_ctrl_c();
         self.request_redraw();
@@ -729,7 +750,10 @@ impl BottomPane {
     /// overlays or popups and not running a task. This is the safe context to
     /// use Esc-Esc for backtracking from the main view.
     pub(crate) fn is_normal_backtrack_mode(&self) -> bool {
-        !self.is_task_running && self.view_stack.is_empty() && !self.composer.popup_active()
+        !self.is_task_running
+            && self.view_stack.is_empty()
+            && !self.composer.popup_active()
+            && !self.composer.vim_captures_esc()
     }
 
     /// Return true when no popups or modal views are active, regardless of task state.
@@ -808,7 +832,10 @@ impl BottomPane {
 
     fn on_active_view_complete(&mut self) {
         self.resume_status_timer_after_modal();
-        self.set_composer_input_enabled(true, None);
+        match self.read_only_placeholder.clone() {
+            Some(placeholder) => self.set_composer_input_enabled(false, Some(placeholder)),
+            None => self.set_composer_input_enabled(true, None),
+        }
     }
 
     fn pause_status_timer_for_modal(&mut self) {
@@ -1,17 +1,14 @@
+use codex_core::custom_prompts::PositionalPlaceholder;
+use codex_core::custom_prompts::expand_named_placeholders;
+use codex_core::custom_prompts::expand_positional_placeholders;
+use codex_core::custom_prompts::prompt_argument_names;
+use codex_core::custom_prompts::prompt_has_numeric_placeholders;
 use codex_protocol::custom_prompts::CustomPrompt;
 use codex_protocol::custom_prompts::PROMPTS_CMD_PREFIX;
 use codex_protocol::user_input::ByteRange;
 use codex_protocol::user_input::TextElement;
-use lazy_static::lazy_static;
-use regex_lite::Regex;
 use shlex::Shlex;
 use std::collections::HashMap;
-use std::collections::HashSet;
-
-lazy_static! {
-    static ref PROMPT_ARG_REGEX: Regex =
-        Regex::new(r"\$[A-Z][A-Z0-9_]*").unwrap_or_else(|_| std::process::abort());
-}
 
 #[derive(Debug)]
 pub enum PromptArgsError {
@@ -113,31 +110,6 @@ pub fn parse_positional_args(rest: &str, text_elements: &[TextElement]) -> Vec<P
     parse_tokens_with_elements(rest, text_elements)
 }
 
-/// Extracts the unique placeholder variable names from a prompt template.
-///
-/// A placeholder is any token that matches the pattern `$[A-Z][A-Z0-9_]*`
-/// (for example `$USER`). The function returns the variable names without
-/// the leading `$`, de-duplicated and in the order of first appearance.
-pub fn prompt_argument_names(content: &str) -> Vec<String> {
-    let mut seen = HashSet::new();
-    let mut names = Vec::new();
-    for m in PROMPT_ARG_REGEX.find_iter(content) {
-        if m.start() > 0 && content.as_bytes()[m.start() - 1] == b'$' {
-            continue;
-        }
-        let name = &content[m.start() + 1..m.end()];
-        // Exclude special positional aggregate token from named args.
-        if name == "ARGUMENTS" {
-            continue;
-        }
-        let name = name.to_string();
-        if seen.insert(name.clone()) {
-            names.push(name);
-        }
-    }
-    names
-}
-
 /// Shift a text element's byte range left by `offset`, returning `None` if empty.
 ///
 /// `offset` is the byte length of the prefix removed from the original text.
@@ -310,25 +282,6 @@ pub fn expand_custom_prompt(
     )))
 }
 
-/// Detect whether `content` contains numeric placeholders ($1..$9) or `$ARGUMENTS`.
-pub fn prompt_has_numeric_placeholders(content: &str) -> bool {
-    if content.contains("$ARGUMENTS") {
-        return true;
-    }
-    let bytes = content.as_bytes();
-    let mut i = 0;
-    while i + 1 < bytes.len() {
-        if bytes[i] == b'$' {
-            let b1 = bytes[i + 1];
-            if (b'1'..=b'9').contains(&b1) {
-                return true;
-            }
-        }
-        i += 1;
-    }
-    false
-}
-
 /// Extract positional arguments from a composer first line like "/name a b" for a given prompt name.
 /// Returns empty when the command name does not match or when there are no args.
 pub fn extract_positional_args_for_prompt_line(
@@ -428,43 +381,17 @@ pub fn expand_numeric_placeholders(
     max_output_tokens: Option<u32>,
     history_depth: Option<u32>,
 ) -> PromptExpansion {
-    let mut out = String::with_capacity(content.len());
     let mut out_elements = Vec::new();
-    let mut i = 0;
-    while let Some(off) = content[i..].find('$') {
-        let j = i + off;
-        out.push_str(&content[i..j]);
-        let rest = &content[j..];
-        let bytes = rest.as_bytes();
-        if bytes.len() >= 2 {
-            match bytes[1] {
-                b'$' => {
-                    out.push_str("$$");
-                    i = j + 2;
-                    continue;
-                }
-                b'1'..=b'9' => {
-                    let idx = (bytes[1] - b'1') as usize;
-                    if let Some(arg) = args.get(idx) {
-                        append_arg_with_elements(&mut out, &mut out_elements, arg);
-                    }
-                    i = j + 2;
-                    continue;
-                }
-                _ => {}
+    let out = expand_positional_placeholders(content, |out, placeholder| match placeholder {
+        PositionalPlaceholder::Index(index) => {
+            if let Some(arg) = args.get(index) {
+                append_arg_with_elements(out, &mut out_elements, arg);
             }
         }
-        if rest.len() > "ARGUMENTS".len() && rest[1..].starts_with("ARGUMENTS") {
-            if !args.is_empty() {
-                append_joined_args_with_elements(&mut out, &mut out_elements, args);
-            }
-            i = j + 1 + "ARGUMENTS".len();
-            continue;
+        PositionalPlaceholder::All => {
+            append_joined_args_with_elements(out, &mut out_elements, args);
         }
-        out.push('$');
-        i = j + 1;
-    }
-    out.push_str(&content[i..]);
+    });
     PromptExpansion {
         text: out,
         text_elements: out_elements,
@@ -586,27 +513,12 @@ fn expand_named_placeholders_with_elements(
     content: &str,
     args: &HashMap<String, PromptArg>,
 ) -> (String, Vec<TextElement>) {
-    let mut out = String::with_capacity(content.len());
     let mut out_elements = Vec::new();
-    let mut cursor = 0;
-    for m in PROMPT_ARG_REGEX.find_iter(content) {
-        let start = m.start();
-        let end = m.end();
-        if start > 0 && content.as_bytes()[start - 1] == b'$' {
-            out.push_str(&content[cursor..end]);
-            cursor = end;
-            continue;
-        }
-        out.push_str(&content[cursor..start]);
-        cursor = end;
-        let key = &content[start + 1..end];
-        if let Some(arg) = args.get(key) {
-            append_arg_with_elements(&mut out, &mut out_elements, arg);
-        } else {
-            out.push_str(&content[start..end]);
-        }
-    }
-    out.push_str(&content[cursor..]);
+    let out = expand_named_placeholders(content, |out, name| {
+        args.get(name)
+            .map(|arg| append_arg_with_elements(out, &mut out_elements, arg))
+            .is_some()
+    });
     (out, out_elements)
 }
 
@@ -2,8 +2,6 @@ use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
 // Note: Table-based layout previously used Constraint; the manual renderer
 // below no longer requires it.
-use ratatui::style::Color;
-use ratatui::style::Style;
 use ratatui::style::Stylize;
 use ratatui::text::Line;
 use ratatui::text::Span;
@@ -16,6 +14,7 @@ use crate::key_hint::KeyBinding;
 use crate::render::Insets;
 use crate::render::RectExt as _;
 use crate::style::user_message_style;
+use crate::theme;
 
 use super::scroll_state::ScrollState;
 
@@ -417,10 +416,10 @@ fn render_rows_inner(
         let is_selected = Some(i) == state.selected_idx;
         let mut full_line = build_full_line(row, desc_col, is_selected);
         if is_selected && !row.is_disabled {
-            // Match previous behavior: cyan + bold for the selected row.
             // Reset the style first to avoid inheriting dim from keyboard shortcuts.
+            let selection = theme::current().selection;
             full_line.spans.iter_mut().for_each(|span| {
-                span.style = Style::default().fg(Color::Cyan).bold();
+                span.style = selection;
             });
         }
         if row.is_disabled {
@@ -592,8 +591,9 @@ pub(crate) fn render_rows_single_line(
         let is_selected = Some(i) == state.selected_idx;
         let mut full_line = build_full_line(row, desc_col, is_selected);
         if is_selected && !row.is_disabled {
+            let selection = theme::current().selection;
             full_line.spans.iter_mut().for_each(|span| {
-                span.style = Style::default().fg(Color::Cyan).bold();
+                span.style = selection;
             });
         }
         if row.is_disabled {
@@ -7,6 +7,7 @@ use crossterm::event::KeyModifiers;
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
 use ratatui::style::Color;
+use ratatui::style::Modifier;
 use ratatui::style::Style;
 use ratatui::widgets::StatefulWidgetRef;
 use ratatui::widgets::WidgetRef;
@@ -45,6 +46,8 @@ pub(crate) struct TextArea {
     elements: Vec<TextElement>,
     next_element_id: u64,
     kill_buffer: String,
+    /// Byte range drawn in reverse video, e.g. a vi visual selection.
+    highlight: Option<Range<usize>>,
 }
 
 #[derive(Debug, Clone)]
@@ -69,6 +72,7 @@ impl TextArea {
             elements: Vec::new(),
             next_element_id: 1,
             kill_buffer: String::new(),
+            highlight: None,
         }
     }
 
@@ -179,6 +183,11 @@ impl TextArea {
         self.preferred_col = None;
     }
 
+    /// Draw `range` in reverse video until cleared with `None`.
+    pub(crate) fn set_highlight(&mut self, range: Option<Range<usize>>) {
+        self.highlight = range;
+    }
+
     pub fn desired_height(&self, width: u16) -> u16 {
         self.wrapped_lines(width).len() as u16
     }
@@ -1051,7 +1060,7 @@ impl TextArea {
         self.shift_elements(start, end.saturating_sub(start), inserted_len);
     }
 
-    fn prev_atomic_boundary(&self, pos: usize) -> usize {
+    pub(crate) fn prev_atomic_boundary(&self, pos: usize) -> usize {
         if pos == 0 {
             return 0;
         }
@@ -1077,7 +1086,7 @@ impl TextArea {
         }
     }
 
-    fn next_atomic_boundary(&self, pos: usize) -> usize {
+    pub(crate) fn next_atomic_boundary(&self, pos: usize) -> usize {
         if pos >= self.text.len() {
             return self.text.len();
         }
@@ -1279,6 +1288,20 @@ impl TextArea {
                 let style = Style::default().fg(Color::Cyan);
                 buf.set_string(area.x + x_off, y, styled, style);
             }
+
+            if let Some(highlight) = &self.highlight {
+                let overlap_start = highlight.start.max(line_range.start);
+                let overlap_end = highlight.end.min(line_range.end);
+                if overlap_start < overlap_end
+                    && self.text.is_char_boundary(overlap_start)
+                    && self.text.is_char_boundary(overlap_end)
+                {
+                    let x_off = self.text[line_range.start..overlap_start].width() as u16;
+                    let width = self.text[overlap_start..overlap_end].width() as u16;
+                    let rect = Rect::new(area.x + x_off, y, width, 1).intersection(area);
+                    buf.set_style(rect, Style::default().add_modifier(Modifier::REVERSED));
+                }
+            }
         }
     }
 
@@ -1917,6 +1940,33 @@ mod tests {
         assert!(state.scroll < effective_lines);
     }
 
+    #[test]
+    fn highlight_renders_reversed_across_wrapped_lines() {
+        let mut t = ta_with("hello world here");
+        t.set_highlight(Some(3..9));
+        let area = Rect::new(0, 0, 6, 3);
+        let mut buf = Buffer::empty(area);
+        WidgetRef::render_ref(&(&t), area, &mut buf);
+
+        let reversed: Vec<(u16, u16)> = area
+            .positions()
+            .filter(|pos| buf[*pos].modifier.contains(Modifier::REVERSED))
+            .map(|pos| (pos.x, pos.y))
+            .collect();
+        assert_eq!(
+            reversed,
+            vec![(3, 0), (4, 0), (5, 0), (0, 1), (1, 1), (2, 1)]
+        );
+
+        t.set_highlight(None);
+        let mut buf = Buffer::empty(area);
+        WidgetRef::render_ref(&(&t), area, &mut buf);
+        assert!(
+            area.positions()
+                .all(|pos| !buf[pos].modifier.contains(Modifier::REVERSED))
+        );
+    }
+
     #[test]
     fn cursor_pos_with_state_basic_and_scroll_behaviors() {
         // Case 1: No wrapping needed, height fits — scroll ignored, y maps directly.
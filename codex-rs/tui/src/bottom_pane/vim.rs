@@ -0,0 +1,1769 @@
+//! Opt-in vi-style modal editing for the chat composer (`tui.vim_mode`).
+//!
+//! [`Vim`] sits in front of the composer's normal key handling. In insert mode it only watches
+//! for `Esc`; in normal and visual mode it reads counts, registers, motions, operators, and
+//! commands, and applies them to the [`TextArea`] directly.
+//!
+//! Keys it does not claim come back to the composer as [`VimAction::PassThrough`], so `Enter`,
+//! control chords, and Up/Down history recall behave exactly as they do without vi mode. A bare
+//! `k`/`j` in normal mode is forwarded as Up/Down for the same reason: on an empty or recalled
+//! prompt it walks history, otherwise it moves between (wrapped) lines.
+//!
+//! # Registers
+//!
+//! Deletes and yanks go to the unnamed register unless a `"x` prefix names another one. The
+//! unnamed register and `"+`/`"*` are backed by the system clipboard: writes are copied to it, and
+//! puts use the clipboard instead when it holds something that was copied outside the composer.
+//! `"a`–`"z` stay inside the composer (`"A`–`"Z` append) and `"_` discards.
+//!
+//! The composer has no undo history, so `u` and `.` are not supported.
+
+use std::collections::HashMap;
+use std::ops::Range;
+
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use crossterm::event::KeyModifiers;
+
+use super::textarea::TextArea;
+
+/// Counts beyond this are clamped so a stray `99999999p` cannot stall the UI.
+const MAX_COUNT: usize = 10_000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum VimMode {
+    Normal,
+    Insert,
+    Visual,
+    VisualLine,
+}
+
+impl VimMode {
+    /// Label for the footer mode indicator.
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            VimMode::Normal => "NORMAL",
+            VimMode::Insert => "INSERT",
+            VimMode::Visual => "VISUAL",
+            VimMode::VisualLine => "VISUAL LINE",
+        }
+    }
+
+    fn is_visual(self) -> bool {
+        matches!(self, VimMode::Visual | VimMode::VisualLine)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum VimAction {
+    /// The key was consumed by vi mode.
+    Handled,
+    /// The composer should handle this key as it would without vi mode.
+    PassThrough(KeyEvent),
+}
+
+pub(crate) struct Vim {
+    mode: VimMode,
+    /// Keys typed towards a command that is not complete yet, e.g. `"a2d`.
+    pending: Vec<char>,
+    /// The end of a visual selection that stays put while the cursor moves.
+    visual_anchor: usize,
+    registers: Registers,
+}
+
+impl Vim {
+    /// Starts in insert mode so the composer behaves as usual until the first `Esc`.
+    pub(crate) fn new() -> Self {
+        Self {
+            mode: VimMode::Insert,
+            pending: Vec::new(),
+            visual_anchor: 0,
+            registers: Registers::default(),
+        }
+    }
+
+    pub(crate) fn mode(&self) -> VimMode {
+        self.mode
+    }
+
+    /// Keys of a partially typed command, shown next to the mode indicator.
+    pub(crate) fn pending_keys(&self) -> String {
+        self.pending.iter().collect()
+    }
+
+    /// Whether `Esc` would change vi state (leave insert/visual mode or cancel a pending command)
+    /// rather than fall through to the composer's own `Esc` handling.
+    pub(crate) fn captures_esc(&self) -> bool {
+        self.mode != VimMode::Normal || !self.pending.is_empty()
+    }
+
+    /// Return to insert mode, e.g. after the prompt was submitted.
+    pub(crate) fn reset(&mut self, textarea: &mut TextArea) {
+        self.set_mode(VimMode::Insert);
+        textarea.set_highlight(None);
+    }
+
+    pub(crate) fn handle_key(&mut self, key: KeyEvent, textarea: &mut TextArea) -> VimAction {
+        if self.mode == VimMode::Insert {
+            if key.code == KeyCode::Esc {
+                // Like vi, leaving insert mode steps back onto the last inserted character.
+                let pos = textarea.cursor();
+                if pos > line_start(textarea.text(), pos) {
+                    textarea.set_cursor(textarea.prev_atomic_boundary(pos));
+                }
+                self.set_mode(VimMode::Normal);
+                self.sync(textarea);
+                return VimAction::Handled;
+            }
+            return VimAction::PassThrough(key);
+        }
+
+        if key
+            .modifiers
+            .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+        {
+            self.pending.clear();
+            return VimAction::PassThrough(key);
+        }
+        let action = match key.code {
+            KeyCode::Esc if !self.pending.is_empty() => {
+                self.pending.clear();
+                VimAction::Handled
+            }
+            KeyCode::Esc if self.mode.is_visual() => {
+                self.set_mode(VimMode::Normal);
+                VimAction::Handled
+            }
+            KeyCode::Char(c) => self.push_key(c, textarea),
+            KeyCode::Left | KeyCode::Backspace => self.push_key('h', textarea),
+            KeyCode::Right => self.push_key('l', textarea),
+            KeyCode::Home => self.push_key('0', textarea),
+            KeyCode::End => self.push_key('$', textarea),
+            KeyCode::Delete => self.push_key('x', textarea),
+            KeyCode::Up | KeyCode::Down if self.mode.is_visual() => {
+                let c = if key.code == KeyCode::Up { 'k' } else { 'j' };
+                self.push_key(c, textarea)
+            }
+            _ => {
+                self.pending.clear();
+                VimAction::PassThrough(key)
+            }
+        };
+        if action == VimAction::Handled {
+            self.sync(textarea);
+        }
+        action
+    }
+
+    /// Re-establish the cursor and highlight invariants after the text or cursor changed,
+    /// including through keys that were passed through to the composer.
+    ///
+    /// Outside insert mode the cursor sits on a character rather than after the last one, and
+    /// visual modes highlight the selection.
+    pub(crate) fn sync(&self, textarea: &mut TextArea) {
+        if self.mode != VimMode::Insert {
+            let pos = textarea.cursor();
+            let text = textarea.text();
+            let eol = line_end(text, pos);
+            if pos == eol && eol > line_start(text, pos) {
+                textarea.set_cursor(textarea.prev_atomic_boundary(eol));
+            }
+        }
+        let highlight = match self.mode {
+            VimMode::Visual => {
+                let (start, end) = self.selection(textarea);
+                Some(start..textarea.next_atomic_boundary(end))
+            }
+            VimMode::VisualLine => {
+                let (start, end) = self.selection(textarea);
+                let text = textarea.text();
+                Some(line_start(text, start)..line_end(text, end))
+            }
+            VimMode::Normal | VimMode::Insert => None,
+        };
+        textarea.set_highlight(highlight);
+    }
+
+    fn set_mode(&mut self, mode: VimMode) {
+        self.mode = mode;
+        self.pending.clear();
+    }
+
+    fn push_key(&mut self, c: char, textarea: &mut TextArea) -> VimAction {
+        self.pending.push(c);
+        match parse(&self.pending, self.mode.is_visual()) {
+            Parsed::Incomplete => VimAction::Handled,
+            Parsed::Invalid => {
+                self.pending.clear();
+                VimAction::Handled
+            }
+            Parsed::Done(keystroke) => {
+                self.pending.clear();
+                self.execute(keystroke, textarea)
+            }
+        }
+    }
+
+    fn execute(&mut self, keystroke: Keystroke, textarea: &mut TextArea) -> VimAction {
+        let Keystroke {
+            register,
+            count,
+            command,
+        } = keystroke;
+        let n = count.unwrap_or(1);
+        match command {
+            Command::Move(motion @ (Motion::Up | Motion::Down))
+                if count.is_none() && self.mode == VimMode::Normal =>
+            {
+                let code = if motion == Motion::Up {
+                    KeyCode::Up
+                } else {
+                    KeyCode::Down
+                };
+                return VimAction::PassThrough(KeyEvent::new(code, KeyModifiers::NONE));
+            }
+            Command::Move(Motion::Up) => (0..n).for_each(|_| textarea.move_cursor_up()),
+            Command::Move(Motion::Down) => (0..n).for_each(|_| textarea.move_cursor_down()),
+            Command::Move(motion) => {
+                if let Some(target) = motion_target(textarea, motion, count) {
+                    textarea.set_cursor(target);
+                }
+            }
+            Command::Operate(operator, target) => {
+                if let Some((range, linewise)) = self.target_range(textarea, operator, target, n) {
+                    self.operate(textarea, operator, range, linewise, register);
+                }
+            }
+            Command::Insert(at) => {
+                insert_at(textarea, at);
+                self.set_mode(VimMode::Insert);
+            }
+            Command::Put { before } => self.put(textarea, register, before, n),
+            Command::Replace(ch) => replace_chars(textarea, ch, n),
+            Command::Join if self.mode.is_visual() => {
+                // Visual `J` joins the selected lines, or the current one and the next.
+                let (start, end) = self.selection(textarea);
+                let joins = textarea.text()[start..end].matches('\n').count().max(1);
+                textarea.set_cursor(start);
+                join_lines(textarea, joins);
+                self.set_mode(VimMode::Normal);
+            }
+            Command::Join => join_lines(textarea, n.max(2) - 1),
+            Command::Visual { linewise } => {
+                let mode = if linewise {
+                    VimMode::VisualLine
+                } else {
+                    VimMode::Visual
+                };
+                if self.mode == VimMode::Normal {
+                    self.visual_anchor = textarea.cursor();
+                    self.set_mode(mode);
+                } else if self.mode == mode {
+                    self.set_mode(VimMode::Normal);
+                } else {
+                    self.set_mode(mode);
+                }
+            }
+            Command::SwapAnchor => {
+                let anchor = floor_char_boundary(textarea.text(), self.visual_anchor);
+                self.visual_anchor = textarea.cursor();
+                textarea.set_cursor(anchor);
+            }
+            Command::SelectObject { object, inner } => {
+                if let Some(range) = object_range(textarea.text(), textarea.cursor(), object, inner)
+                    && !range.is_empty()
+                {
+                    self.visual_anchor = range.start;
+                    textarea.set_cursor(textarea.prev_atomic_boundary(range.end));
+                }
+            }
+        }
+        VimAction::Handled
+    }
+
+    /// The visual selection as `(start, end)` cursor positions, both inclusive.
+    fn selection(&self, textarea: &TextArea) -> (usize, usize) {
+        let anchor = floor_char_boundary(textarea.text(), self.visual_anchor);
+        let pos = textarea.cursor();
+        (anchor.min(pos), anchor.max(pos))
+    }
+
+    /// Resolve what `operator` acts on. Linewise results cover whole lines without the final
+    /// newline; [`Vim::operate`] decides whether that newline goes too.
+    fn target_range(
+        &self,
+        textarea: &TextArea,
+        operator: Operator,
+        target: Target,
+        n: usize,
+    ) -> Option<(Range<usize>, bool)> {
+        let text = textarea.text();
+        let pos = textarea.cursor();
+        let lines = |a: usize, b: usize| Some((line_start(text, a)..line_end(text, b), true));
+        match target {
+            Target::Lines => lines(pos, lines_down(text, pos, n - 1)),
+            Target::Selection if self.mode == VimMode::VisualLine => {
+                let (start, end) = self.selection(textarea);
+                lines(start, end)
+            }
+            Target::Selection => {
+                let (start, end) = self.selection(textarea);
+                Some((start..textarea.next_atomic_boundary(end), false))
+            }
+            Target::SelectionLines => {
+                let (start, end) = self.selection(textarea);
+                lines(start, end)
+            }
+            Target::Object { object, inner } => {
+                object_range(text, pos, object, inner).map(|range| (range, false))
+            }
+            Target::Motion(Motion::Up) => lines(lines_up(text, pos, n), pos),
+            Target::Motion(Motion::Down) => lines(pos, lines_down(text, pos, n)),
+            // `cw` on a word changes to the end of that word, leaving the following space.
+            Target::Motion(Motion::WordForward { big })
+                if operator == Operator::Change
+                    && text[pos..].starts_with(|c: char| !c.is_whitespace()) =>
+            {
+                let mut end = run_end(text, pos, big);
+                for _ in 1..n {
+                    end = word_end(text, end, big);
+                }
+                Some((pos..textarea.next_atomic_boundary(end), false))
+            }
+            Target::Motion(motion) => {
+                let mut target = motion_target(textarea, motion, Some(n))?;
+                // An operator never carries `w` past the end of the line it started on.
+                if matches!(motion, Motion::WordForward { .. }) && target > pos {
+                    let target_line = line_start(text, target);
+                    if target_line > pos {
+                        target = target_line - 1;
+                    }
+                }
+                let (start, end) = (pos.min(target), pos.max(target));
+                match motion_kind(motion) {
+                    MotionKind::Exclusive => Some((start..end, false)),
+                    MotionKind::Inclusive => {
+                        Some((start..textarea.next_atomic_boundary(end), false))
+                    }
+                    MotionKind::Linewise => lines(start, end),
+                }
+            }
+        }
+    }
+
+    fn operate(
+        &mut self,
+        textarea: &mut TextArea,
+        operator: Operator,
+        range: Range<usize>,
+        linewise: bool,
+        register: Option<char>,
+    ) {
+        let text = textarea.text();
+        let mut yanked = text[range.clone()].to_string();
+        if linewise {
+            yanked.push('\n');
+        }
+        self.registers.store(
+            register,
+            Register {
+                text: yanked,
+                linewise,
+            },
+        );
+        match operator {
+            Operator::Yank => {
+                if !linewise || self.mode.is_visual() {
+                    textarea.set_cursor(range.start);
+                }
+                self.set_mode(VimMode::Normal);
+            }
+            Operator::Delete => {
+                let range = if linewise {
+                    with_line_break(text, range)
+                } else {
+                    range
+                };
+                textarea.replace_range(range.clone(), "");
+                let cursor = if linewise {
+                    first_non_blank(textarea.text(), range.start.min(textarea.text().len()))
+                } else {
+                    range.start
+                };
+                textarea.set_cursor(cursor);
+                self.set_mode(VimMode::Normal);
+            }
+            Operator::Change => {
+                textarea.replace_range(range.clone(), "");
+                textarea.set_cursor(range.start);
+                self.set_mode(VimMode::Insert);
+            }
+        }
+    }
+
+    fn put(&mut self, textarea: &mut TextArea, register: Option<char>, before: bool, n: usize) {
+        let Some(register) = self.registers.get(register) else {
+            return;
+        };
+        if register.text.is_empty() {
+            return;
+        }
+        let text = textarea.text();
+        let pos = textarea.cursor();
+        if register.linewise {
+            let body = register.text.strip_suffix('\n').unwrap_or(&register.text);
+            let body = std::iter::repeat_n(body, n).collect::<Vec<_>>().join("\n");
+            let line = if before {
+                let bol = line_start(text, pos);
+                textarea.insert_str_at(bol, &format!("{body}\n"));
+                bol
+            } else {
+                let eol = line_end(text, pos);
+                textarea.insert_str_at(eol, &format!("\n{body}"));
+                eol + 1
+            };
+            textarea.set_cursor(first_non_blank(textarea.text(), line));
+        } else {
+            let body = register.text.repeat(n);
+            let at = if before || pos >= line_end(text, pos) {
+                pos
+            } else {
+                textarea.next_atomic_boundary(pos)
+            };
+            textarea.insert_str_at(at, &body);
+            textarea.set_cursor(textarea.prev_atomic_boundary(at + body.len()));
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Register {
+    text: String,
+    linewise: bool,
+}
+
+impl Register {
+    fn from_clipboard(text: &str) -> Self {
+        let text = text.replace("\r\n", "\n");
+        let linewise = text.ends_with('\n');
+        Self { text, linewise }
+    }
+}
+
+#[derive(Default)]
+struct Registers {
+    unnamed: Register,
+    named: HashMap<char, Register>,
+    /// What the system clipboard held the last time we wrote or read it, so a put can tell
+    /// whether something else has been copied since.
+    clipboard: Register,
+}
+
+impl Registers {
+    fn store(&mut self, name: Option<char>, register: Register) {
+        match name {
+            Some('_') => {}
+            Some(c) if c.is_ascii_lowercase() => {
+                self.named.insert(c, register.clone());
+                self.unnamed = register;
+            }
+            Some(c) if c.is_ascii_uppercase() => {
+                let named = self.named.entry(c.to_ascii_lowercase()).or_default();
+                named.text.push_str(&register.text);
+                named.linewise |= register.linewise;
+                if named.linewise && !named.text.ends_with('\n') {
+                    named.text.push('\n');
+                }
+                self.unnamed = named.clone();
+            }
+            _ => {
+                if let Err(err) = copy_to_clipboard(&register.text) {
+                    tracing::debug!("vim register not copied to clipboard: {err}");
+                }
+                self.clipboard = register.clone();
+                self.unnamed = register;
+            }
+        }
+    }
+
+    fn get(&mut self, name: Option<char>) -> Option<Register> {
+        match name {
+            Some('_') => None,
+            Some(c) if c.is_ascii_alphabetic() => self.named.get(&c.to_ascii_lowercase()).cloned(),
+            Some('+' | '*') => {
+                self.sync_clipboard();
+                Some(self.clipboard.clone())
+            }
+            _ => {
+                self.sync_clipboard();
+                Some(self.unnamed.clone())
+            }
+        }
+    }
+
+    /// Adopt text copied outside the composer as the unnamed register.
+    fn sync_clipboard(&mut self) {
+        let Some(text) = read_clipboard() else {
+            return;
+        };
+        let register = Register::from_clipboard(&text);
+        if register.text != self.clipboard.text {
+            self.clipboard = register.clone();
+            self.unnamed = register;
+        }
+    }
+}
+
+#[cfg(not(test))]
+fn copy_to_clipboard(text: &str) -> Result<(), String> {
+    crate::clipboard_paste::copy_text(text)
+}
+
+#[cfg(not(test))]
+fn read_clipboard() -> Option<String> {
+    crate::clipboard_paste::paste_text().ok()
+}
+
+// Tests must not read or clobber the developer's clipboard.
+#[cfg(test)]
+fn copy_to_clipboard(_text: &str) -> Result<(), String> {
+    Ok(())
+}
+
+#[cfg(test)]
+fn read_clipboard() -> Option<String> {
+    None
+}
+
+// ===== Command grammar =====
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Motion {
+    Left,
+    Right,
+    Up,
+    Down,
+    WordForward { big: bool },
+    WordBackward { big: bool },
+    WordEnd { big: bool },
+    LineStart,
+    FirstNonBlank,
+    LineEnd,
+    FirstLine,
+    LastLine,
+    FindForward { ch: char, till: bool },
+    FindBackward { ch: char, till: bool },
+}
+
+enum MotionKind {
+    Exclusive,
+    Inclusive,
+    Linewise,
+}
+
+fn motion_kind(motion: Motion) -> MotionKind {
+    match motion {
+        Motion::Up | Motion::Down | Motion::FirstLine | Motion::LastLine => MotionKind::Linewise,
+        Motion::WordEnd { .. } | Motion::FindForward { .. } => MotionKind::Inclusive,
+        _ => MotionKind::Exclusive,
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Operator {
+    Delete,
+    Change,
+    Yank,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum TextObject {
+    Word { big: bool },
+    Quote(char),
+    Brackets(char, char),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Target {
+    Motion(Motion),
+    /// `dd`, `cc`, `yy`: the current line plus `count - 1` below it.
+    Lines,
+    Object {
+        object: TextObject,
+        inner: bool,
+    },
+    /// The visual selection.
+    Selection,
+    /// The lines touched by the visual selection (`D`, `Y`, `C` in visual mode).
+    SelectionLines,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum InsertAt {
+    Cursor,
+    After,
+    LineStart,
+    LineEnd,
+    LineBelow,
+    LineAbove,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Command {
+    Move(Motion),
+    Operate(Operator, Target),
+    Insert(InsertAt),
+    Put { before: bool },
+    Replace(char),
+    Join,
+    Visual { linewise: bool },
+    SwapAnchor,
+    SelectObject { object: TextObject, inner: bool },
+}
+
+#[derive(Debug, PartialEq)]
+struct Keystroke {
+    register: Option<char>,
+    count: Option<usize>,
+    command: Command,
+}
+
+#[derive(Debug, PartialEq)]
+enum Parsed<T> {
+    Incomplete,
+    Invalid,
+    Done(T),
+}
+
+impl<T> Parsed<T> {
+    fn map<U>(self, f: impl FnOnce(T) -> U) -> Parsed<U> {
+        match self {
+            Parsed::Incomplete => Parsed::Incomplete,
+            Parsed::Invalid => Parsed::Invalid,
+            Parsed::Done(value) => Parsed::Done(f(value)),
+        }
+    }
+}
+
+/// Parse `["x][count]command`, where an operator command takes its own optional count and a
+/// motion or text object.
+fn parse(keys: &[char], visual: bool) -> Parsed<Keystroke> {
+    let (register, keys) = match keys {
+        ['"'] => return Parsed::Incomplete,
+        ['"', c, rest @ ..] if is_register(*c) => (Some(*c), rest),
+        ['"', ..] => return Parsed::Invalid,
+        _ => (None, keys),
+    };
+    let (count, keys) = parse_count(keys);
+    let command = if visual {
+        parse_visual_command(keys)
+    } else {
+        match keys {
+            [op @ ('d' | 'c' | 'y'), rest @ ..] => {
+                let operator = match op {
+                    'd' => Operator::Delete,
+                    'c' => Operator::Change,
+                    _ => Operator::Yank,
+                };
+                let (inner_count, rest) = parse_count(rest);
+                return parse_operator_target(*op, rest).map(|target| Keystroke {
+                    register,
+                    count: multiply_counts(count, inner_count),
+                    command: Command::Operate(operator, target),
+                });
+            }
+            _ => parse_normal_command(keys),
+        }
+    };
+    command.map(|command| Keystroke {
+        register,
+        count,
+        command,
+    })
+}
+
+fn parse_normal_command(keys: &[char]) -> Parsed<Command> {
+    let command = match keys {
+        [] | ['r'] => return Parsed::Incomplete,
+        ['i'] => Command::Insert(InsertAt::Cursor),
+        ['a'] => Command::Insert(InsertAt::After),
+        ['I'] => Command::Insert(InsertAt::LineStart),
+        ['A'] => Command::Insert(InsertAt::LineEnd),
+        ['o'] => Command::Insert(InsertAt::LineBelow),
+        ['O'] => Command::Insert(InsertAt::LineAbove),
+        ['x'] => Command::Operate(Operator::Delete, Target::Motion(Motion::Right)),
+        ['X'] => Command::Operate(Operator::Delete, Target::Motion(Motion::Left)),
+        ['D'] => Command::Operate(Operator::Delete, Target::Motion(Motion::LineEnd)),
+        ['C'] => Command::Operate(Operator::Change, Target::Motion(Motion::LineEnd)),
+        ['s'] => Command::Operate(Operator::Change, Target::Motion(Motion::Right)),
+        ['S'] => Command::Operate(Operator::Change, Target::Lines),
+        ['Y'] => Command::Operate(Operator::Yank, Target::Lines),
+        ['p'] => Command::Put { before: false },
+        ['P'] => Command::Put { before: true },
+        ['r', ch] => Command::Replace(*ch),
+        ['J'] => Command::Join,
+        ['v'] => Command::Visual { linewise: false },
+        ['V'] => Command::Visual { linewise: true },
+        _ => return parse_motion(keys).map(Command::Move),
+    };
+    Parsed::Done(command)
+}
+
+fn parse_visual_command(keys: &[char]) -> Parsed<Command> {
+    let command = match keys {
+        [] | ['i' | 'a'] => return Parsed::Incomplete,
+        ['d' | 'x'] => Command::Operate(Operator::Delete, Target::Selection),
+        ['c' | 's'] => Command::Operate(Operator::Change, Target::Selection),
+        ['y'] => Command::Operate(Operator::Yank, Target::Selection),
+        ['D' | 'X'] => Command::Operate(Operator::Delete, Target::SelectionLines),
+        ['C' | 'S' | 'R'] => Command::Operate(Operator::Change, Target::SelectionLines),
+        ['Y'] => Command::Operate(Operator::Yank, Target::SelectionLines),
+        ['J'] => Command::Join,
+        ['o'] => Command::SwapAnchor,
+        ['v'] => Command::Visual { linewise: false },
+        ['V'] => Command::Visual { linewise: true },
+        [scope @ ('i' | 'a'), object] => match text_object(*object) {
+            Some(object) => Command::SelectObject {
+                object,
+                inner: *scope == 'i',
+            },
+            None => return Parsed::Invalid,
+        },
+        _ => return parse_motion(keys).map(Command::Move),
+    };
+    Parsed::Done(command)
+}
+
+fn parse_operator_target(op: char, keys: &[char]) -> Parsed<Target> {
+    match keys {
+        [] | ['i' | 'a'] => Parsed::Incomplete,
+        [c] if *c == op => Parsed::Done(Target::Lines),
+        [scope @ ('i' | 'a'), object] => match text_object(*object) {
+            Some(object) => Parsed::Done(Target::Object {
+                object,
+                inner: *scope == 'i',
+            }),
+            None => Parsed::Invalid,
+        },
+        _ => parse_motion(keys).map(Target::Motion),
+    }
+}
+
+fn parse_motion(keys: &[char]) -> Parsed<Motion> {
+    let motion = match keys {
+        [] | ['g'] | ['f' | 't' | 'F' | 'T'] => return Parsed::Incomplete,
+        ['h'] => Motion::Left,
+        ['l' | ' '] => Motion::Right,
+        ['k'] => Motion::Up,
+        ['j'] => Motion::Down,
+        ['w'] => Motion::WordForward { big: false },
+        ['W'] => Motion::WordForward { big: true },
+        ['b'] => Motion::WordBackward { big: false },
+        ['B'] => Motion::WordBackward { big: true },
+        ['e'] => Motion::WordEnd { big: false },
+        ['E'] => Motion::WordEnd { big: true },
+        ['0'] => Motion::LineStart,
+        ['^'] => Motion::FirstNonBlank,
+        ['$'] => Motion::LineEnd,
+        ['g', 'g'] => Motion::FirstLine,
+        ['G'] => Motion::LastLine,
+        ['f', ch] => Motion::FindForward {
+            ch: *ch,
+            till: false,
+        },
+        ['t', ch] => Motion::FindForward {
+            ch: *ch,
+            till: true,
+        },
+        ['F', ch] => Motion::FindBackward {
+            ch: *ch,
+            till: false,
+        },
+        ['T', ch] => Motion::FindBackward {
+            ch: *ch,
+            till: true,
+        },
+        _ => return Parsed::Invalid,
+    };
+    Parsed::Done(motion)
+}
+
+/// A leading `0` is the line-start motion, not a count.
+fn parse_count(keys: &[char]) -> (Option<usize>, &[char]) {
+    let digits = keys
+        .iter()
+        .enumerate()
+        .take_while(|(i, c)| c.is_ascii_digit() && (*i > 0 || **c != '0'))
+        .count();
+    if digits == 0 {
+        return (None, keys);
+    }
+    let count = keys[..digits].iter().fold(0usize, |count, c| {
+        count
+            .saturating_mul(10)
+            .saturating_add(c.to_digit(10).unwrap_or(0) as usize)
+    });
+    (Some(count.min(MAX_COUNT)), &keys[digits..])
+}
+
+fn multiply_counts(a: Option<usize>, b: Option<usize>) -> Option<usize> {
+    match (a, b) {
+        (None, None) => None,
+        (a, b) => Some((a.unwrap_or(1) * b.unwrap_or(1)).min(MAX_COUNT)),
+    }
+}
+
+fn is_register(c: char) -> bool {
+    c.is_ascii_alphabetic() || matches!(c, '"' | '+' | '*' | '_')
+}
+
+fn text_object(c: char) -> Option<TextObject> {
+    Some(match c {
+        'w' => TextObject::Word { big: false },
+        'W' => TextObject::Word { big: true },
+        '"' | '\'' | '`' => TextObject::Quote(c),
+        '(' | ')' | 'b' => TextObject::Brackets('(', ')'),
+        '[' | ']' => TextObject::Brackets('[', ']'),
+        '{' | '}' | 'B' => TextObject::Brackets('{', '}'),
+        '<' | '>' => TextObject::Brackets('<', '>'),
+        _ => return None,
+    })
+}
+
+// ===== Motions and text objects =====
+
+fn motion_target(textarea: &TextArea, motion: Motion, count: Option<usize>) -> Option<usize> {
+    let text = textarea.text();
+    let pos = textarea.cursor();
+    let n = count.unwrap_or(1);
+    let target = match motion {
+        Motion::Left => {
+            let bol = line_start(text, pos);
+            let mut p = pos;
+            for _ in 0..n {
+                if p <= bol {
+                    break;
+                }
+                p = textarea.prev_atomic_boundary(p);
+            }
+            p
+        }
+        Motion::Right => {
+            let eol = line_end(text, pos);
+            let mut p = pos;
+            for _ in 0..n {
+                if p >= eol {
+                    break;
+                }
+                p = textarea.next_atomic_boundary(p);
+            }
+            p
+        }
+        Motion::Up => line_start(text, lines_up(text, pos, n)),
+        Motion::Down => line_start(text, lines_down(text, pos, n)),
+        Motion::WordForward { big } => (0..n).fold(pos, |p, _| next_word_start(text, p, big)),
+        Motion::WordBackward { big } => (0..n).fold(pos, |p, _| prev_word_start(text, p, big)),
+        Motion::WordEnd { big } => (0..n).fold(pos, |p, _| word_end(text, p, big)),
+        Motion::LineStart => line_start(text, pos),
+        Motion::FirstNonBlank => first_non_blank(text, pos),
+        Motion::LineEnd => line_end(text, lines_down(text, pos, n - 1)),
+        Motion::FirstLine => first_non_blank(text, nth_line_start(text, n)),
+        Motion::LastLine => {
+            let line = match count {
+                Some(n) => nth_line_start(text, n),
+                None => line_start(text, text.len()),
+            };
+            first_non_blank(text, line)
+        }
+        Motion::FindForward { ch, till } => {
+            let eol = line_end(text, pos);
+            let mut found = pos;
+            for _ in 0..n {
+                let from = next_char_boundary(text, found);
+                found = from + text.get(from..eol)?.find(ch)?;
+            }
+            if till {
+                prev_char_boundary(text, found)
+            } else {
+                found
+            }
+        }
+        Motion::FindBackward { ch, till } => {
+            let bol = line_start(text, pos);
+            let mut found = pos;
+            for _ in 0..n {
+                found = bol + text[bol..found].rfind(ch)?;
+            }
+            if till {
+                next_char_boundary(text, found)
+            } else {
+                found
+            }
+        }
+    };
+    Some(target)
+}
+
+/// Word characters, punctuation, and whitespace form separate classes; a WORD (`big`) is any
+/// run of non-whitespace.
+fn char_class(c: char, big: bool) -> u8 {
+    if c.is_whitespace() {
+        0
+    } else if big || c.is_alphanumeric() || c == '_' {
+        2
+    } else {
+        1
+    }
+}
+
+fn next_word_start(text: &str, pos: usize, big: bool) -> usize {
+    let mut chars = text[pos..]
+        .char_indices()
+        .map(|(i, c)| (pos + i, c))
+        .peekable();
+    let Some(&(_, first)) = chars.peek() else {
+        return text.len();
+    };
+    let class = char_class(first, big);
+    if class != 0 {
+        while chars
+            .next_if(|&(_, c)| char_class(c, big) == class)
+            .is_some()
+        {}
+    }
+    let mut seen_newline = false;
+    for (i, c) in chars {
+        if !c.is_whitespace() {
+            return i;
+        }
+        // An empty line counts as a word.
+        if c == '\n' {
+            if seen_newline {
+                return i;
+            }
+            seen_newline = true;
+        }
+    }
+    text.len()
+}
+
+fn prev_word_start(text: &str, pos: usize, big: bool) -> usize {
+    let mut chars = text[..pos].char_indices().rev().peekable();
+    while chars.next_if(|&(_, c)| c.is_whitespace()).is_some() {}
+    let Some((mut start, c)) = chars.next() else {
+        return 0;
+    };
+    let class = char_class(c, big);
+    while let Some((i, _)) = chars.next_if(|&(_, c)| char_class(c, big) == class) {
+        start = i;
+    }
+    start
+}
+
+/// Position of the last character of the next word end after `pos`.
+fn word_end(text: &str, pos: usize, big: bool) -> usize {
+    let mut chars = text[pos..]
+        .char_indices()
+        .map(|(i, c)| (pos + i, c))
+        .skip(1)
+        .peekable();
+    while chars.next_if(|&(_, c)| c.is_whitespace()).is_some() {}
+    let Some((mut end, c)) = chars.next() else {
+        return pos;
+    };
+    let class = char_class(c, big);
+    while let Some((i, _)) = chars.next_if(|&(_, c)| char_class(c, big) == class) {
+        end = i;
+    }
+    end
+}
+
+/// Position of the last character in the class run containing `pos`.
+fn run_end(text: &str, pos: usize, big: bool) -> usize {
+    let Some(first) = text[pos..].chars().next() else {
+        return pos;
+    };
+    let class = char_class(first, big);
+    text[pos..]
+        .char_indices()
+        .take_while(|&(_, c)| c != '\n' && char_class(c, big) == class)
+        .last()
+        .map_or(pos, |(i, _)| pos + i)
+}
+
+/// Byte range of the run of characters matching `pred` around `pos`.
+fn run_around(text: &str, pos: usize, pred: impl Fn(char) -> bool) -> Range<usize> {
+    let start = text[..pos]
+        .char_indices()
+        .rev()
+        .take_while(|&(_, c)| pred(c))
+        .last()
+        .map_or(pos, |(i, _)| i);
+    let end = text[pos..]
+        .char_indices()
+        .find(|&(_, c)| !pred(c))
+        .map_or(text.len(), |(i, _)| pos + i);
+    start..end
+}
+
+fn object_range(text: &str, pos: usize, object: TextObject, inner: bool) -> Option<Range<usize>> {
+    match object {
+        TextObject::Word { big } => word_object(text, pos, big, inner),
+        TextObject::Quote(quote) => quote_object(text, pos, quote, inner),
+        TextObject::Brackets(open, close) => bracket_object(text, pos, open, close, inner),
+    }
+}
+
+fn word_object(text: &str, pos: usize, big: bool, inner: bool) -> Option<Range<usize>> {
+    let first = text[pos..].chars().next().filter(|c| *c != '\n')?;
+    let class = char_class(first, big);
+    let same_class = |c: char| c != '\n' && char_class(c, big) == class;
+    let word = run_around(text, pos, same_class);
+    if inner {
+        return Some(word);
+    }
+    let is_blank = |c: char| c == ' ' || c == '\t';
+    if class == 0 {
+        // `aw` on whitespace takes the whitespace and the word after it.
+        return match text[word.end..].chars().next() {
+            Some(next) if next != '\n' => {
+                let next_class = char_class(next, big);
+                let next_word = run_around(text, word.end, |c| {
+                    c != '\n' && char_class(c, big) == next_class
+                });
+                Some(word.start..next_word.end)
+            }
+            _ => Some(word),
+        };
+    }
+    // `aw` takes trailing blanks, or leading ones when the word ends the line.
+    let trailing = text[word.end..].len() - text[word.end..].trim_start_matches(is_blank).len();
+    if trailing > 0 {
+        return Some(word.start..word.end + trailing);
+    }
+    let leading = text[..word.start].len() - text[..word.start].trim_end_matches(is_blank).len();
+    Some(word.start - leading..word.end)
+}
+
+/// Quotes pair up left to right within the line; the pair around the cursor wins, otherwise
+/// the first pair after it.
+fn quote_object(text: &str, pos: usize, quote: char, inner: bool) -> Option<Range<usize>> {
+    let bol = line_start(text, pos);
+    let eol = line_end(text, pos);
+    let quotes: Vec<usize> = text[bol..eol]
+        .match_indices(quote)
+        .map(|(i, _)| bol + i)
+        .collect();
+    let (open, close) = quotes
+        .chunks_exact(2)
+        .map(|pair| (pair[0], pair[1]))
+        .find(|&(_, close)| close >= pos)?;
+    let len = quote.len_utf8();
+    Some(if inner {
+        open + len..close
+    } else {
+        open..close + len
+    })
+}
+
+fn bracket_object(
+    text: &str,
+    pos: usize,
+    open: char,
+    close: char,
+    inner: bool,
+) -> Option<Range<usize>> {
+    let search_end = if text[pos..].starts_with(open) {
+        pos + open.len_utf8()
+    } else {
+        pos
+    };
+    let mut depth = 0usize;
+    let mut start = None;
+    for (i, c) in text[..search_end].char_indices().rev() {
+        if c == close {
+            depth += 1;
+        } else if c == open {
+            if depth == 0 {
+                start = Some(i);
+                break;
+            }
+            depth -= 1;
+        }
+    }
+    let start = start?;
+    let body = start + open.len_utf8();
+    let mut depth = 0usize;
+    let mut end = None;
+    for (i, c) in text[body..].char_indices() {
+        if c == open {
+            depth += 1;
+        } else if c == close {
+            if depth == 0 {
+                end = Some(body + i);
+                break;
+            }
+            depth -= 1;
+        }
+    }
+    let end = end?;
+    Some(if inner {
+        body..end
+    } else {
+        start..end + close.len_utf8()
+    })
+}
+
+// ===== Edits =====
+
+fn insert_at(textarea: &mut TextArea, at: InsertAt) {
+    let text = textarea.text();
+    let pos = textarea.cursor();
+    match at {
+        InsertAt::Cursor => {}
+        InsertAt::After => {
+            if pos < line_end(text, pos) {
+                textarea.set_cursor(textarea.next_atomic_boundary(pos));
+            }
+        }
+        InsertAt::LineStart => textarea.set_cursor(first_non_blank(text, pos)),
+        InsertAt::LineEnd => textarea.set_cursor(line_end(text, pos)),
+        InsertAt::LineBelow => {
+            let eol = line_end(text, pos);
+            textarea.insert_str_at(eol, "\n");
+            textarea.set_cursor(eol + 1);
+        }
+        InsertAt::LineAbove => {
+            let bol = line_start(text, pos);
+            textarea.insert_str_at(bol, "\n");
+            textarea.set_cursor(bol);
+        }
+    }
+}
+
+fn replace_chars(textarea: &mut TextArea, ch: char, n: usize) {
+    let pos = textarea.cursor();
+    let eol = line_end(textarea.text(), pos);
+    let mut end = pos;
+    for _ in 0..n {
+        if end >= eol {
+            return;
+        }
+        end = textarea.next_atomic_boundary(end);
+    }
+    textarea.replace_range(pos..end, &ch.to_string().repeat(n));
+    textarea.set_cursor(pos + ch.len_utf8() * (n - 1));
+}
+
+/// `J`: join the next `joins` lines onto the current one, separated by a single space.
+fn join_lines(textarea: &mut TextArea, joins: usize) {
+    for _ in 0..joins {
+        let text = textarea.text();
+        let eol = line_end(text, textarea.cursor());
+        if eol >= text.len() {
+            break;
+        }
+        let next = &text[eol + 1..];
+        let indent = next.len() - next.trim_start_matches([' ', '\t']).len();
+        let rest = &next[indent..];
+        let separator =
+            if eol == line_start(text, eol) || rest.is_empty() || rest.starts_with(['\n', ')']) {
+                ""
+            } else {
+                " "
+            };
+        textarea.replace_range(eol..eol + 1 + indent, separator);
+        textarea.set_cursor(eol);
+    }
+}
+
+// ===== Line helpers =====
+
+fn line_start(text: &str, pos: usize) -> usize {
+    text[..pos].rfind('\n').map_or(0, |i| i + 1)
+}
+
+fn line_end(text: &str, pos: usize) -> usize {
+    text[pos..].find('\n').map_or(text.len(), |i| pos + i)
+}
+
+fn first_non_blank(text: &str, pos: usize) -> usize {
+    let start = line_start(text, pos);
+    let line = &text[start..line_end(text, pos)];
+    start
+        + line
+            .find(|c: char| !c.is_whitespace())
+            .unwrap_or(line.len())
+}
+
+/// Start of the 1-based `line`, clamped to the last line.
+fn nth_line_start(text: &str, line: usize) -> usize {
+    text.match_indices('\n')
+        .take(line.saturating_sub(1))
+        .last()
+        .map_or(0, |(i, _)| i + 1)
+}
+
+fn lines_down(text: &str, pos: usize, n: usize) -> usize {
+    let mut p = pos;
+    for _ in 0..n {
+        let eol = line_end(text, p);
+        if eol >= text.len() {
+            break;
+        }
+        p = eol + 1;
+    }
+    p
+}
+
+fn lines_up(text: &str, pos: usize, n: usize) -> usize {
+    let mut p = pos;
+    for _ in 0..n {
+        let bol = line_start(text, p);
+        if bol == 0 {
+            break;
+        }
+        p = bol - 1;
+    }
+    p
+}
+
+/// Extend a whole-line range to take one line break with it, so deleting lines leaves no
+/// empty line behind.
+fn with_line_break(text: &str, range: Range<usize>) -> Range<usize> {
+    if range.end < text.len() {
+        range.start..range.end + 1
+    } else if range.start > 0 {
+        range.start - 1..range.end
+    } else {
+        range
+    }
+}
+
+fn next_char_boundary(text: &str, pos: usize) -> usize {
+    text[pos..]
+        .chars()
+        .next()
+        .map_or(text.len(), |c| pos + c.len_utf8())
+}
+
+fn prev_char_boundary(text: &str, pos: usize) -> usize {
+    text[..pos]
+        .chars()
+        .next_back()
+        .map_or(0, |c| pos - c.len_utf8())
+}
+
+fn floor_char_boundary(text: &str, pos: usize) -> usize {
+    let mut pos = pos.min(text.len());
+    while !text.is_char_boundary(pos) {
+        pos -= 1;
+    }
+    pos
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    /// Build a textarea from `text`, where `|` marks the cursor, in normal mode.
+    fn normal(text: &str) -> (Vim, TextArea) {
+        let cursor = text.find('|').expect("cursor marker");
+        let mut textarea = TextArea::new();
+        textarea.insert_str(&text.replacen('|', "", 1));
+        textarea.set_cursor(cursor);
+        let mut vim = Vim::new();
+        vim.set_mode(VimMode::Normal);
+        vim.sync(&mut textarea);
+        (vim, textarea)
+    }
+
+    /// Feed `keys` (`\u{1b}` is Esc), moving the cursor for passed-through Up/Down the way the
+    /// composer does, and return the keys that were passed through.
+    fn keys(vim: &mut Vim, textarea: &mut TextArea, keys: &str) -> Vec<VimAction> {
+        let mut passed = Vec::new();
+        for c in keys.chars() {
+            let event = match c {
+                '\u{1b}' => KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE),
+                c => KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE),
+            };
+            if let VimAction::PassThrough(event) = vim.handle_key(event, textarea) {
+                if matches!(event.code, KeyCode::Up | KeyCode::Down) {
+                    textarea.input(event);
+                    vim.sync(textarea);
+                }
+                passed.push(VimAction::PassThrough(event));
+            }
+        }
+        passed
+    }
+
+    fn state(textarea: &TextArea) -> String {
+        let mut text = textarea.text().to_string();
+        text.insert(textarea.cursor(), '|');
+        text
+    }
+
+    #[test]
+    fn esc_leaves_insert_mode_on_last_typed_char() {
+        let mut textarea = TextArea::new();
+        let mut vim = Vim::new();
+        assert_eq!(
+            vim.handle_key(
+                KeyEvent::new(KeyCode::Char('h'), KeyModifiers::NONE),
+                &mut textarea
+            ),
+            VimAction::PassThrough(KeyEvent::new(KeyCode::Char('h'), KeyModifiers::NONE))
+        );
+        textarea.insert_str("hello");
+        keys(&mut vim, &mut textarea, "\u{1b}");
+        assert_eq!(vim.mode(), VimMode::Normal);
+        assert_eq!(state(&textarea), "hell|o");
+        assert!(!vim.captures_esc());
+    }
+
+    #[test]
+    fn word_and_line_motions() {
+        let (mut vim, mut textarea) = normal("|foo.bar baz\n  qux");
+        let steps = [
+            ("w", "foo|.bar baz\n  qux"),
+            ("w", "foo.|bar baz\n  qux"),
+            ("W", "foo.bar |baz\n  qux"),
+            ("e", "foo.bar ba|z\n  qux"),
+            ("w", "foo.bar baz\n  |qux"),
+            ("b", "foo.bar |baz\n  qux"),
+            ("B", "|foo.bar baz\n  qux"),
+            ("$", "foo.bar ba|z\n  qux"),
+            ("0", "|foo.bar baz\n  qux"),
+            ("G", "foo.bar baz\n  |qux"),
+            ("0", "foo.bar baz\n|  qux"),
+            ("^", "foo.bar baz\n  |qux"),
+            ("gg", "|foo.bar baz\n  qux"),
+            ("2w", "foo.|bar baz\n  qux"),
+            ("fz", "foo.bar ba|z\n  qux"),
+            ("Tb", "foo.bar b|az\n  qux"),
+        ];
+        for (input, expected) in steps {
+            keys(&mut vim, &mut textarea, input);
+            assert_eq!(state(&textarea), expected, "after {input:?}");
+        }
+    }
+
+    #[test]
+    fn counts_repeat_motions() {
+        let (mut vim, mut textarea) = normal("|abcdef ghi jkl\nmno\npqr");
+        let steps = [
+            ("3l", "abc|def ghi jkl\nmno\npqr"),
+            ("10l", "abcdef ghi jk|l\nmno\npqr"),
+            ("2h", "abcdef ghi |jkl\nmno\npqr"),
+            ("2b", "|abcdef ghi jkl\nmno\npqr"),
+            ("2e", "abcdef gh|i jkl\nmno\npqr"),
+            ("3w", "abcdef ghi jkl\nmno\n|pqr"),
+            ("2gg", "abcdef ghi jkl\n|mno\npqr"),
+            ("1G", "|abcdef ghi jkl\nmno\npqr"),
+            ("2$", "abcdef ghi jkl\nmn|o\npqr"),
+            ("9G", "abcdef ghi jkl\nmno\n|pqr"),
+            ("2k", "|abcdef ghi jkl\nmno\npqr"),
+        ];
+        for (input, expected) in steps {
+            keys(&mut vim, &mut textarea, input);
+            assert_eq!(state(&textarea), expected, "after {input:?}");
+        }
+    }
+
+    #[test]
+    fn find_motions_stay_on_the_line() {
+        let (mut vim, mut textarea) = normal("|a-b-c-d\ne-f");
+        let steps = [
+            ("f-", "a|-b-c-d\ne-f"),
+            ("2f-", "a-b-c|-d\ne-f"),
+            ("F-", "a-b|-c-d\ne-f"),
+            ("t-", "a-b-|c-d\ne-f"),
+            ("Ta", "a|-b-c-d\ne-f"),
+            // A character that is missing, only on another line, or found fewer times than the
+            // count does not move the cursor.
+            ("fe", "a|-b-c-d\ne-f"),
+            ("3f-", "a|-b-c-d\ne-f"),
+            ("f-", "a-b|-c-d\ne-f"),
+        ];
+        for (input, expected) in steps {
+            keys(&mut vim, &mut textarea, input);
+            assert_eq!(state(&textarea), expected, "after {input:?}");
+        }
+    }
+
+    #[test]
+    fn commands_parse_registers_and_counts() {
+        let chars = |keys: &str| keys.chars().collect::<Vec<_>>();
+        let delete_words = |register, count| {
+            Parsed::Done(Keystroke {
+                register,
+                count,
+                command: Command::Operate(
+                    Operator::Delete,
+                    Target::Motion(Motion::WordForward { big: false }),
+                ),
+            })
+        };
+        assert_eq!(parse(&chars("dw"), false), delete_words(None, None));
+        assert_eq!(parse(&chars("d3w"), false), delete_words(None, Some(3)));
+        assert_eq!(
+            parse(&chars("\"a2d3w"), false),
+            delete_words(Some('a'), Some(6))
+        );
+        assert_eq!(
+            parse(&chars("10l"), false),
+            Parsed::Done(Keystroke {
+                register: None,
+                count: Some(10),
+                command: Command::Move(Motion::Right),
+            })
+        );
+        // A leading zero is a motion, and huge counts are clamped.
+        assert_eq!(
+            parse(&chars("0"), false),
+            Parsed::Done(Keystroke {
+                register: None,
+                count: None,
+                command: Command::Move(Motion::LineStart),
+            })
+        );
+        assert_eq!(
+            parse(&chars("99999999999999999999p"), false),
+            Parsed::Done(Keystroke {
+                register: None,
+                count: Some(MAX_COUNT),
+                command: Command::Put { before: false },
+            })
+        );
+        for incomplete in ["\"", "\"a", "2", "d", "d2", "di", "g", "f", "r"] {
+            assert_eq!(
+                parse(&chars(incomplete), false),
+                Parsed::Incomplete,
+                "{incomplete:?}"
+            );
+        }
+        for invalid in ["\"!", "dx", "diq", "Q", "gx"] {
+            assert_eq!(
+                parse(&chars(invalid), false),
+                Parsed::Invalid,
+                "{invalid:?}"
+            );
+        }
+        // Visual mode reads operators as acting on the selection.
+        assert_eq!(
+            parse(&chars("d"), true),
+            Parsed::Done(Keystroke {
+                register: None,
+                count: None,
+                command: Command::Operate(Operator::Delete, Target::Selection),
+            })
+        );
+    }
+
+    #[test]
+    fn operators_with_motions_and_text_objects() {
+        let cases = [
+            ("one |two three", "dw", "one |three", VimMode::Normal),
+            ("one |two three", "d2w", "one| ", VimMode::Normal),
+            (
+                "one two |three\nfour",
+                "dw",
+                "one two| \nfour",
+                VimMode::Normal,
+            ),
+            ("one t|wo three", "cw", "one t| three", VimMode::Insert),
+            ("one t|wo three", "ciw", "one | three", VimMode::Insert),
+            ("one t|wo three", "daw", "one |three", VimMode::Normal),
+            (
+                "say \"he|llo\" now",
+                "di\"",
+                "say \"|\" now",
+                VimMode::Normal,
+            ),
+            ("say \"he|llo\" now", "da\"", "say | now", VimMode::Normal),
+            ("f(a, (b|), c)", "ci(", "f(a, (|), c)", VimMode::Insert),
+            ("f(a, (b), |c)", "dab", "|f", VimMode::Normal),
+            ("abc|def", "D", "ab|c", VimMode::Normal),
+            ("abc|def", "x", "abc|ef", VimMode::Normal),
+            ("abc|def", "2X", "a|def", VimMode::Normal),
+            ("a\n|b\nc", "dd", "a\n|c", VimMode::Normal),
+            ("a\nb\n|c", "dd", "a\n|b", VimMode::Normal),
+            ("a\n|b\nc", "dk", "|c", VimMode::Normal),
+            ("a\n  |b\nc", "cc", "a\n|\nc", VimMode::Insert),
+            ("a b|c", "rx", "a b|x", VimMode::Normal),
+            ("a\n  |b\nc", "J", "a\n  b| c", VimMode::Normal),
+        ];
+        for (before, input, after, mode) in cases {
+            let (mut vim, mut textarea) = normal(before);
+            keys(&mut vim, &mut textarea, input);
+            assert_eq!(state(&textarea), after, "{before:?} + {input:?}");
+            assert_eq!(vim.mode(), mode, "{before:?} + {input:?}");
+        }
+    }
+
+    #[test]
+    fn counted_operators() {
+        let cases = [
+            ("|a\nb\nc\nd", "2dd", "|c\nd"),
+            ("|a\nb\nc\nd", "d2j", "|d"),
+            ("|a\nb\nc\nd", "2d2j", "|"),
+            ("a\nb\nc\n|d", "2dk", "|a"),
+            ("|one two three four", "2dw", "|three four"),
+            ("|one two three four", "2d2w", "|"),
+            ("|one two three", "3x", "| two three"),
+            // Counts past the end of the line stop there.
+            ("o|ne", "5x", "|o"),
+            ("one |two three", "d2e", "one| "),
+            ("|one two three", "dfe", "| two three"),
+            ("|one two three", "dte", "|e two three"),
+            ("one two |three", "dTo", "one two|three"),
+            ("|a-b-c-d", "d2f-", "|c-d"),
+            ("|a\nb\nc", "3J", "a b| c"),
+        ];
+        for (before, input, after) in cases {
+            let (mut vim, mut textarea) = normal(before);
+            keys(&mut vim, &mut textarea, input);
+            assert_eq!(state(&textarea), after, "{before:?} + {input:?}");
+            assert_eq!(vim.mode(), VimMode::Normal, "{before:?} + {input:?}");
+        }
+    }
+
+    #[test]
+    fn change_and_insert_commands_enter_insert_mode() {
+        let cases = [
+            ("ab|cd", "i", "ab|cd"),
+            ("ab|cd", "a", "abc|d"),
+            ("ab|c\nd", "a", "abc|\nd"),
+            ("  ab|cd", "I", "  |abcd"),
+            ("ab|cd\ne", "A", "abcd|\ne"),
+            ("ab|cd\ne", "o", "abcd\n|\ne"),
+            ("a\nb|cd", "O", "a\n|\nbcd"),
+            ("ab|cd", "s", "ab|d"),
+            ("ab|cd", "2s", "ab|"),
+            ("ab|cd", "C", "ab|"),
+            ("x\n  ab|cd\ny", "S", "x\n|\ny"),
+            ("|one two three", "c2w", "| three"),
+            ("one |two three", "ct ", "one | three"),
+        ];
+        for (before, input, after) in cases {
+            let (mut vim, mut textarea) = normal(before);
+            keys(&mut vim, &mut textarea, input);
+            assert_eq!(state(&textarea), after, "{before:?} + {input:?}");
+            assert_eq!(vim.mode(), VimMode::Insert, "{before:?} + {input:?}");
+            assert!(vim.captures_esc(), "{before:?} + {input:?}");
+        }
+
+        // Typed text goes through the composer, and Esc steps back onto it.
+        let (mut vim, mut textarea) = normal("ab|c");
+        keys(&mut vim, &mut textarea, "a");
+        textarea.insert_str("XY");
+        keys(&mut vim, &mut textarea, "\u{1b}");
+        assert_eq!(state(&textarea), "abcX|Y");
+        assert_eq!(vim.mode(), VimMode::Normal);
+    }
+
+    #[test]
+    fn replace_and_join_take_counts() {
+        let cases = [
+            ("|abcd", "3rx", "xx|xd"),
+            // Replacing more characters than the line has is a no-op, like vi.
+            ("ab|cd", "3rx", "ab|cd"),
+            ("|a\n   b", "J", "a| b"),
+            ("|a\n)", "J", "a|)"),
+            ("|a\n\nb", "J", "|a\nb"),
+            ("|a\nb", "5J", "a| b"),
+        ];
+        for (before, input, after) in cases {
+            let (mut vim, mut textarea) = normal(before);
+            keys(&mut vim, &mut textarea, input);
+            assert_eq!(state(&textarea), after, "{before:?} + {input:?}");
+        }
+    }
+
+    #[test]
+    fn yank_and_put_use_registers() {
+        let (mut vim, mut textarea) = normal("|one two");
+        keys(&mut vim, &mut textarea, "yw$p");
+        assert_eq!(state(&textarea), "one twoone| ");
+
+        let (mut vim, mut textarea) = normal("|first\nsecond");
+        keys(&mut vim, &mut textarea, "\"ayyjdd\"ap");
+        assert_eq!(state(&textarea), "first\n|first");
+        keys(&mut vim, &mut textarea, "P");
+        assert_eq!(state(&textarea), "first\n|second\nfirst");
+
+        keys(&mut vim, &mut textarea, "\"Ayw\"ap");
+        assert_eq!(state(&textarea), "first\nsecond\n|first\nsecond\nfirst");
+
+        // The black-hole register leaves the unnamed register alone.
+        let (mut vim, mut textarea) = normal("|keep drop");
+        keys(&mut vim, &mut textarea, "yiww\"_dw0P");
+        assert_eq!(state(&textarea), "kee|pkeep ");
+    }
+
+    #[test]
+    fn visual_modes_select_and_operate() {
+        let (mut vim, mut textarea) = normal("one |two three");
+        keys(&mut vim, &mut textarea, "ve");
+        assert_eq!(vim.mode(), VimMode::Visual);
+        keys(&mut vim, &mut textarea, "d");
+        assert_eq!(state(&textarea), "one | three");
+        assert_eq!(vim.mode(), VimMode::Normal);
+
+        let (mut vim, mut textarea) = normal("a\n|b\nc");
+        keys(&mut vim, &mut textarea, "Vjy");
+        assert_eq!(vim.mode(), VimMode::Normal);
+        keys(&mut vim, &mut textarea, "GP");
+        assert_eq!(state(&textarea), "a\nb\n|b\nc\nc");
+
+        let (mut vim, mut textarea) = normal("x (a |b) y");
+        keys(&mut vim, &mut textarea, "vi(c");
+        assert_eq!(state(&textarea), "x (|) y");
+        assert_eq!(vim.mode(), VimMode::Insert);
+
+        let (mut vim, mut textarea) = normal("ab|c");
+        keys(&mut vim, &mut textarea, "vh");
+        assert!(vim.captures_esc());
+        keys(&mut vim, &mut textarea, "\u{1b}");
+        assert_eq!(vim.mode(), VimMode::Normal);
+    }
+
+    #[test]
+    fn yanks_and_counted_puts_place_the_cursor() {
+        let (mut vim, mut textarea) = normal("one |two");
+        keys(&mut vim, &mut textarea, "yb");
+        assert_eq!(state(&textarea), "|one two");
+        keys(&mut vim, &mut textarea, "$3p");
+        assert_eq!(state(&textarea), "one twoone one one| ");
+
+        // A linewise yank leaves the cursor where it was; puts repeat whole lines.
+        let (mut vim, mut textarea) = normal("a\nb|c\nd");
+        keys(&mut vim, &mut textarea, "yy");
+        assert_eq!(state(&textarea), "a\nb|c\nd");
+        keys(&mut vim, &mut textarea, "gg2p");
+        assert_eq!(state(&textarea), "a\n|bc\nbc\nbc\nd");
+
+        // Putting from an empty register does nothing.
+        let (mut vim, mut textarea) = normal("a|b");
+        keys(&mut vim, &mut textarea, "\"qp");
+        assert_eq!(state(&textarea), "a|b");
+    }
+
+    #[test]
+    fn visual_mode_switches_and_counts() {
+        let (mut vim, mut textarea) = normal("ab|cdef");
+        keys(&mut vim, &mut textarea, "v");
+        assert_eq!(vim.mode(), VimMode::Visual);
+        keys(&mut vim, &mut textarea, "V");
+        assert_eq!(vim.mode(), VimMode::VisualLine);
+        keys(&mut vim, &mut textarea, "v");
+        assert_eq!(vim.mode(), VimMode::Visual);
+        keys(&mut vim, &mut textarea, "v");
+        assert_eq!(vim.mode(), VimMode::Normal);
+
+        // Counts move the free end; `o` swaps ends so the selection grows the other way.
+        let (mut vim, mut textarea) = normal("ab|cdef");
+        keys(&mut vim, &mut textarea, "v2l");
+        assert_eq!(state(&textarea), "abcd|ef");
+        keys(&mut vim, &mut textarea, "o");
+        assert_eq!(state(&textarea), "ab|cdef");
+        keys(&mut vim, &mut textarea, "hd");
+        assert_eq!(state(&textarea), "a|f");
+        assert_eq!(vim.mode(), VimMode::Normal);
+
+        // Uppercase operators act on whole lines even in characterwise visual mode.
+        let (mut vim, mut textarea) = normal("a\nb|c\nd");
+        keys(&mut vim, &mut textarea, "vD");
+        assert_eq!(state(&textarea), "a\n|d");
+
+        let (mut vim, mut textarea) = normal("|a\nb\nc");
+        keys(&mut vim, &mut textarea, "VjJ");
+        assert_eq!(state(&textarea), "a| b\nc");
+        assert_eq!(vim.mode(), VimMode::Normal);
+
+        let (mut vim, mut textarea) = normal("|a\nb\nc");
+        keys(&mut vim, &mut textarea, "VjC");
+        assert_eq!(state(&textarea), "|\nc");
+        assert_eq!(vim.mode(), VimMode::Insert);
+
+        // A selected text object extends the selection to cover it.
+        let (mut vim, mut textarea) = normal("x \"a|b\" y");
+        keys(&mut vim, &mut textarea, "va\"y");
+        assert_eq!(state(&textarea), "x |\"ab\" y");
+        keys(&mut vim, &mut textarea, "$p");
+        assert_eq!(state(&textarea), "x \"ab\" y\"ab|\"");
+    }
+
+    #[test]
+    fn special_keys_map_to_vi_commands() {
+        let press = |vim: &mut Vim, textarea: &mut TextArea, code| {
+            vim.handle_key(KeyEvent::new(code, KeyModifiers::NONE), textarea)
+        };
+        let (mut vim, mut textarea) = normal("ab|cd");
+        assert_eq!(
+            press(&mut vim, &mut textarea, KeyCode::Left),
+            VimAction::Handled
+        );
+        assert_eq!(state(&textarea), "a|bcd");
+        press(&mut vim, &mut textarea, KeyCode::Right);
+        assert_eq!(state(&textarea), "ab|cd");
+        press(&mut vim, &mut textarea, KeyCode::End);
+        assert_eq!(state(&textarea), "abc|d");
+        press(&mut vim, &mut textarea, KeyCode::Home);
+        assert_eq!(state(&textarea), "|abcd");
+        press(&mut vim, &mut textarea, KeyCode::Delete);
+        assert_eq!(state(&textarea), "|bcd");
+
+        // Control chords cancel a pending command and go to the composer.
+        keys(&mut vim, &mut textarea, "2d");
+        assert_eq!(vim.pending_keys(), "2d");
+        let ctrl_c = KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        assert_eq!(
+            vim.handle_key(ctrl_c, &mut textarea),
+            VimAction::PassThrough(ctrl_c)
+        );
+        assert_eq!(vim.pending_keys(), "");
+        keys(&mut vim, &mut textarea, "w");
+        assert_eq!(state(&textarea), "bc|d");
+
+        vim.reset(&mut textarea);
+        assert_eq!(vim.mode(), VimMode::Insert);
+        assert_eq!(
+            press(&mut vim, &mut textarea, KeyCode::Left),
+            VimAction::PassThrough(KeyEvent::new(KeyCode::Left, KeyModifiers::NONE))
+        );
+    }
+
+    #[test]
+    fn unclaimed_keys_pass_through_for_history_and_submit() {
+        let (mut vim, mut textarea) = normal("|one\ntwo");
+        assert_eq!(
+            keys(&mut vim, &mut textarea, "k"),
+            vec![VimAction::PassThrough(KeyEvent::new(
+                KeyCode::Up,
+                KeyModifiers::NONE
+            ))]
+        );
+        let enter = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(
+            vim.handle_key(enter, &mut textarea),
+            VimAction::PassThrough(enter)
+        );
+        // Counted motions stay inside the buffer.
+        keys(&mut vim, &mut textarea, "1j");
+        assert_eq!(state(&textarea), "one\n|two");
+
+        // A pending operator swallows Esc instead of letting it reach the composer.
+        keys(&mut vim, &mut textarea, "d");
+        assert!(vim.captures_esc());
+        keys(&mut vim, &mut textarea, "\u{1b}");
+        assert!(!vim.captures_esc());
+        let esc = KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE);
+        assert_eq!(
+            vim.handle_key(esc, &mut textarea),
+            VimAction::PassThrough(esc)
+        );
+    }
+}
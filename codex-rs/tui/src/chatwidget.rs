@@ -33,14 +33,20 @@ use std::time::Instant;
 use crate::version::CODEX_CLI_VERSION;
 use codex_backend_client::Client as BackendClient;
 use codex_chatgpt::connectors;
+use codex_common::elapsed::format_duration;
 use codex_common::prompt_suggestions::PromptSuggestionGate;
 use codex_core::config::Config;
 use codex_core::config::ConstraintResult;
+use codex_core::config::keybindings::KeybindingAction;
 use codex_core::config::types::Notifications;
+use codex_core::config::types::SandboxProfile;
+use codex_core::debug_bundle::DebugBundle;
+use codex_core::debug_bundle::default_bundle_path;
 use codex_core::features::FEATURES;
 use codex_core::features::Feature;
 use codex_core::git_info::current_branch_name;
 use codex_core::git_info::local_git_branches;
+use codex_core::hooks::LONG_COMMAND_THRESHOLD;
 use codex_core::models_manager::manager::ModelsManager;
 use codex_core::project_doc::DEFAULT_PROJECT_DOC_FILENAME;
 use codex_core::protocol::AgentMessageDeltaEvent;
@@ -65,12 +71,14 @@ use codex_core::protocol::ExecCommandSource;
 use codex_core::protocol::ExitedReviewModeEvent;
 use codex_core::protocol::ListCustomPromptsResponseEvent;
 use codex_core::protocol::ListSkillsResponseEvent;
+use codex_core::protocol::McpListChangedKind;
 use codex_core::protocol::McpListToolsResponseEvent;
 use codex_core::protocol::McpStartupCompleteEvent;
 use codex_core::protocol::McpStartupStatus;
 use codex_core::protocol::McpStartupUpdateEvent;
 use codex_core::protocol::McpToolCallBeginEvent;
 use codex_core::protocol::McpToolCallEndEvent;
+use codex_core::protocol::McpToolsChangedEvent;
 use codex_core::protocol::Op;
 use codex_core::protocol::PatchApplyBeginEvent;
 use codex_core::protocol::PromptSuggestionContext;
@@ -96,6 +104,8 @@ use codex_core::protocol::WarningEvent;
 use codex_core::protocol::WebSearchBeginEvent;
 use codex_core::protocol::WebSearchEndEvent;
 use codex_core::skills::model::SkillMetadata;
+use codex_core::usage::UsageGroupBy;
+use codex_core::usage::load_usage_report;
 #[cfg(target_os = "windows")]
 use codex_core::windows_sandbox::WindowsSandboxLevelExt;
 use codex_otel::OtelManager;
@@ -139,6 +149,7 @@ const PLAN_IMPLEMENTATION_NO: &str = "No, stay in Plan mode";
 const PLAN_IMPLEMENTATION_CODING_MESSAGE: &str = "Implement the plan.";
 const MCP_SEARCH_TOOL_NAME_NORMALIZED: &str = "mcpsearch";
 
+use crate::agent_tiles::AgentTiles;
 use crate::app_event::AppEvent;
 use crate::app_event::ConnectorsSnapshot;
 use crate::app_event::ExitMode;
@@ -184,7 +195,9 @@ use crate::history_cell::PlainHistoryCell;
 use crate::history_cell::WebSearchCell;
 use crate::key_hint;
 use crate::key_hint::KeyBinding;
+use crate::keymap::keymap;
 use crate::markdown::append_markdown;
+use crate::notifications::DesktopNotification;
 use crate::render::Insets;
 use crate::render::renderable::ColumnRenderable;
 use crate::render::renderable::FlexRenderable;
@@ -194,6 +207,8 @@ use crate::render::renderable::RenderableItem;
 use crate::slash_command::SlashCommand;
 use crate::status::RateLimitSnapshotDisplay;
 use crate::text_formatting::truncate_text;
+use crate::theme;
+use crate::theme::Theme;
 use crate::tui::FrameRequester;
 mod interrupts;
 use self::interrupts::InterruptManager;
@@ -546,6 +561,8 @@ pub(crate) struct ChatWidget {
     thread_id: Option<ThreadId>,
     thread_name: Option<String>,
     forked_from: Option<ThreadId>,
+    // Nested AGENTS.md files loaded after session start, listed in /status.
+    loaded_project_docs: Vec<PathBuf>,
     frame_requester: FrameRequester,
     // Whether to include the initial welcome banner on session configured
     show_welcome_banner: bool,
@@ -600,6 +617,8 @@ pub(crate) struct ChatWidget {
     // Current session rollout path (if known)
     current_rollout_path: Option<PathBuf>,
     external_editor_state: ExternalEditorState,
+    // Per-agent tiles pushed by the app; empty until a sub-agent thread exists.
+    agent_tiles: AgentTiles,
 }
 
 /// Snapshot of active-cell state that affects transcript overlay rendering.
@@ -1431,6 +1450,9 @@ impl ChatWidget {
 
     fn on_error(&mut self, message: String) {
         self.finalize_turn();
+        self.notify(Notification::TurnError {
+            message: message.clone(),
+        });
         self.add_to_history(history_cell::new_error_event(message));
         self.request_redraw();
 
@@ -1511,6 +1533,20 @@ impl ChatWidget {
         self.request_redraw();
     }
 
+    fn on_mcp_tools_changed(&mut self, ev: McpToolsChangedEvent) {
+        if ev.kind != McpListChangedKind::Tools {
+            return;
+        }
+        let noun = if ev.tool_count == 1 { "tool" } else { "tools" };
+        self.add_info_message(
+            format!(
+                "MCP server `{}` updated its tools ({} {noun} available)",
+                ev.server, ev.tool_count
+            ),
+            None,
+        );
+    }
+
     /// Handle a turn aborted due to user interrupt (Esc).
     /// When there are queued user messages, restore them into the composer
     /// separated by newlines rather than auto‑submitting the next one.
@@ -2169,6 +2205,14 @@ impl ChatWidget {
         };
         let is_unified_exec_interaction =
             matches!(source, ExecCommandSource::UnifiedExecInteraction);
+        if !is_unified_exec_interaction && ev.duration >= LONG_COMMAND_THRESHOLD {
+            self.notify(Notification::CommandFinished {
+                command: shlex::try_join(command.iter().map(String::as_str))
+                    .unwrap_or_else(|_| command.join(" ")),
+                exit_code: ev.exit_code,
+                duration: ev.duration,
+            });
+        }
 
         let needs_new = self
             .active_cell
@@ -2288,6 +2332,13 @@ impl ChatWidget {
 
     pub(crate) fn handle_request_user_input_now(&mut self, ev: RequestUserInputEvent) {
         self.flush_answer_stream_with_separator();
+        self.notify(Notification::UserInputRequested {
+            question: ev
+                .questions
+                .first()
+                .map(|question| question.question.clone())
+                .unwrap_or_default(),
+        });
         self.bottom_pane.push_user_input_request(ev);
         self.request_redraw();
     }
@@ -2509,6 +2560,7 @@ impl ChatWidget {
             thread_id: None,
             thread_name: None,
             forked_from: None,
+            loaded_project_docs: Vec::new(),
             queued_user_messages: VecDeque::new(),
             show_welcome_banner: is_first_run,
             suppress_session_configured_redraw: false,
@@ -2529,12 +2581,16 @@ impl ChatWidget {
             feedback_audience,
             current_rollout_path: None,
             external_editor_state: ExternalEditorState::Closed,
+            agent_tiles: AgentTiles::default(),
         };
 
         widget.prefetch_rate_limits();
         widget
             .bottom_pane
             .set_steer_enabled(widget.config.features.enabled(Feature::Steer));
+        widget
+            .bottom_pane
+            .set_vim_mode_enabled(widget.config.tui_vim_mode);
         widget.bottom_pane.set_prompt_suggestions_status(
             widget.config.features.enabled(Feature::PromptSuggestions),
             widget
@@ -2670,6 +2726,7 @@ impl ChatWidget {
             thread_id: None,
             thread_name: None,
             forked_from: None,
+            loaded_project_docs: Vec::new(),
             saw_plan_update_this_turn: false,
             saw_plan_item_this_turn: false,
             plan_delta_buffer: String::new(),
@@ -2690,12 +2747,16 @@ impl ChatWidget {
             feedback_audience,
             current_rollout_path: None,
             external_editor_state: ExternalEditorState::Closed,
+            agent_tiles: AgentTiles::default(),
         };
 
         widget.prefetch_rate_limits();
         widget
             .bottom_pane
             .set_steer_enabled(widget.config.features.enabled(Feature::Steer));
+        widget
+            .bottom_pane
+            .set_vim_mode_enabled(widget.config.tui_vim_mode);
         widget.bottom_pane.set_prompt_suggestions_status(
             widget.config.features.enabled(Feature::PromptSuggestions),
             widget
@@ -2820,6 +2881,7 @@ impl ChatWidget {
             thread_id: None,
             thread_name: None,
             forked_from: None,
+            loaded_project_docs: Vec::new(),
             queued_user_messages: VecDeque::new(),
             show_welcome_banner: false,
             suppress_session_configured_redraw: true,
@@ -2840,12 +2902,16 @@ impl ChatWidget {
             feedback_audience,
             current_rollout_path: None,
             external_editor_state: ExternalEditorState::Closed,
+            agent_tiles: AgentTiles::default(),
         };
 
         widget.prefetch_rate_limits();
         widget
             .bottom_pane
             .set_steer_enabled(widget.config.features.enabled(Feature::Steer));
+        widget
+            .bottom_pane
+            .set_vim_mode_enabled(widget.config.tui_vim_mode);
         widget.bottom_pane.set_prompt_suggestions_status(
             widget.config.features.enabled(Feature::PromptSuggestions),
             widget
@@ -2930,11 +2996,8 @@ impl ChatWidget {
         }
 
         match key_event {
-            KeyEvent {
-                code: KeyCode::BackTab,
-                kind: KeyEventKind::Press,
-                ..
-            } if self.collaboration_modes_enabled()
+            _ if keymap().is_press(KeybindingAction::CycleMode, key_event)
+                && self.collaboration_modes_enabled()
                 && !self.bottom_pane.is_task_running()
                 && self.bottom_pane.no_modal_or_popup_active() =>
             {
@@ -3072,6 +3135,16 @@ impl ChatWidget {
         self.external_editor_state = state;
     }
 
+    /// Keeps the composer disabled, for viewers of a shared session.
+    pub(crate) fn set_read_only(&mut self, placeholder: String) {
+        self.bottom_pane.set_read_only(placeholder);
+    }
+
+    pub(crate) fn set_agent_tiles(&mut self, tiles: AgentTiles) {
+        self.agent_tiles = tiles;
+        self.request_redraw();
+    }
+
     pub(crate) fn set_footer_hint_override(&mut self, items: Option<Vec<(String, String)>>) {
         self.bottom_pane.set_footer_hint_override(items);
     }
@@ -3147,6 +3220,9 @@ impl ChatWidget {
             SlashCommand::Personality => {
                 self.open_personality_popup();
             }
+            SlashCommand::Theme => {
+                self.open_theme_popup();
+            }
             SlashCommand::SubagentModel => {
                 self.open_subagent_model_popup();
             }
@@ -3275,6 +3351,9 @@ impl ChatWidget {
             SlashCommand::Status => {
                 self.add_status_output();
             }
+            SlashCommand::Usage => {
+                self.open_usage(UsageGroupBy::Day);
+            }
             SlashCommand::Suggestions => {
                 self.prompt_suggestions_intent = true;
                 self.open_prompt_suggestions_view(self.latest_prompt_suggestion.clone());
@@ -3430,10 +3509,73 @@ impl ChatWidget {
                 });
                 self.bottom_pane.drain_pending_submission_state();
             }
+            SlashCommand::Usage if !trimmed.is_empty() => {
+                let Some((prepared_args, _prepared_elements)) =
+                    self.bottom_pane.prepare_inline_args_submission(false)
+                else {
+                    return;
+                };
+                match UsageGroupBy::parse(prepared_args.trim()) {
+                    Ok(group_by) => self.open_usage(group_by),
+                    Err(err) => self.add_error_message(err.to_string()),
+                }
+                self.bottom_pane.drain_pending_submission_state();
+            }
+            SlashCommand::Feedback if !trimmed.is_empty() => {
+                let Some((prepared_args, _prepared_elements)) =
+                    self.bottom_pane.prepare_inline_args_submission(false)
+                else {
+                    return;
+                };
+                if prepared_args.trim() == "--save" {
+                    self.collect_debug_bundle();
+                } else {
+                    self.add_error_message("Usage: /feedback [--save]".to_string());
+                }
+                self.bottom_pane.drain_pending_submission_state();
+            }
             _ => self.dispatch_command(cmd),
         }
     }
 
+    /// Collects an offline debug bundle for `/feedback --save`; the app previews it when ready.
+    fn collect_debug_bundle(&mut self) {
+        let config = self.config.clone();
+        let logs = self.feedback.snapshot(self.thread_id).as_bytes().to_vec();
+        let rollout_path = self.current_rollout_path.clone();
+        let tx = self.app_event_tx.clone();
+        self.add_info_message("Collecting debug bundle...".to_string(), None);
+        tokio::spawn(async move {
+            let bundle = DebugBundle::collect(&config, Some(logs), rollout_path.as_deref()).await;
+            tx.send(AppEvent::OpenDebugBundlePreview {
+                bundle: Arc::new(bundle),
+            });
+        });
+    }
+
+    pub(crate) fn open_debug_bundle_preview(&mut self, bundle: Arc<DebugBundle>) {
+        let params = crate::bottom_pane::debug_bundle_preview_params(
+            self.app_event_tx.clone(),
+            bundle,
+            default_bundle_path(&self.config.cwd),
+        );
+        self.bottom_pane.show_selection_view(params);
+        self.request_redraw();
+    }
+
+    /// Loads the usage recorded across sessions; the app shows it in a pager when it arrives.
+    fn open_usage(&mut self, group_by: UsageGroupBy) {
+        let config = self.config.clone();
+        let tx = self.app_event_tx.clone();
+        tokio::spawn(async move {
+            let since = crate::usage::period_start(group_by, Local::now());
+            let result = load_usage_report(&config, group_by, since, None)
+                .await
+                .map_err(|err| format!("Failed to load usage: {err}"));
+            tx.send(AppEvent::UsageResult(result));
+        });
+    }
+
     fn show_rename_prompt(&mut self) {
         let tx = self.app_event_tx.clone();
         let has_name = self
@@ -3817,6 +3959,7 @@ impl ChatWidget {
             }
             EventMsg::McpStartupUpdate(ev) => self.on_mcp_startup_update(ev),
             EventMsg::McpStartupComplete(ev) => self.on_mcp_startup_complete(ev),
+            EventMsg::McpToolsChanged(ev) => self.on_mcp_tools_changed(ev),
             EventMsg::TurnAborted(ev) => match ev.reason {
                 TurnAbortReason::Interrupted => {
                     self.on_interrupted_turn(ev.reason);
@@ -3830,11 +3973,14 @@ impl ChatWidget {
             },
             EventMsg::PlanUpdate(update) => self.on_plan_update(update),
             EventMsg::ExecApprovalRequest(ev) => {
-                // For replayed events, synthesize an empty id (these should not occur).
-                self.on_exec_approval_request(id.unwrap_or_default(), ev)
+                // Replays only carry approvals that are still pending (e.g. after switching to an
+                // agent thread); core keys those by turn id, so answer with that.
+                let id = id.unwrap_or_else(|| ev.turn_id.clone());
+                self.on_exec_approval_request(id, ev)
             }
             EventMsg::ApplyPatchApprovalRequest(ev) => {
-                self.on_apply_patch_approval_request(id.unwrap_or_default(), ev)
+                let id = id.unwrap_or_else(|| ev.turn_id.clone());
+                self.on_apply_patch_approval_request(id, ev)
             }
             EventMsg::ElicitationRequest(ev) => {
                 self.on_elicitation_request(ev);
@@ -3899,6 +4045,7 @@ impl ChatWidget {
             EventMsg::CollabCloseBegin(_) => {}
             EventMsg::CollabCloseEnd(ev) => self.on_collab_event(collab::close_end(ev)),
             EventMsg::ThreadRolledBack(_) => {}
+            EventMsg::ProjectDocLoaded(ev) => self.loaded_project_docs.push(ev.path),
             EventMsg::RawResponseItem(_)
             | EventMsg::ItemStarted(_)
             | EventMsg::AgentMessageContentDelta(_)
@@ -4017,7 +4164,7 @@ impl ChatWidget {
 
     pub(crate) fn maybe_post_pending_notification(&mut self, tui: &mut crate::tui::Tui) {
         if let Some(notif) = self.pending_notification.take() {
-            tui.notify(notif.display());
+            tui.notify(&notif.desktop_notification());
         }
     }
 
@@ -4086,6 +4233,7 @@ impl ChatWidget {
             self.model_display_name(),
             collaboration_mode,
             reasoning_effort_override,
+            &self.loaded_project_docs,
         ));
     }
 
@@ -4221,6 +4369,7 @@ impl ChatWidget {
                 cwd: None,
                 approval_policy: None,
                 sandbox_policy: None,
+                sandbox_profile: None,
                 windows_sandbox_level: None,
                 model: Some(switch_model.clone()),
                 subagent_model: None,
@@ -4353,6 +4502,7 @@ impl ChatWidget {
                     cwd: None,
                     approval_policy: None,
                     sandbox_policy: None,
+                    sandbox_profile: None,
                     windows_sandbox_level: None,
                     model: None,
                     subagent_model: Some(Some(trimmed.clone())),
@@ -4409,6 +4559,7 @@ impl ChatWidget {
                         cwd: None,
                         approval_policy: None,
                         sandbox_policy: None,
+                        sandbox_profile: None,
                         model: None,
                         subagent_model: None,
                         subagent_effort: None,
@@ -4451,6 +4602,71 @@ impl ChatWidget {
         });
     }
 
+    /// Open the `/theme` picker. Highlighting a theme previews it; Esc restores the active one.
+    pub(crate) fn open_theme_popup(&mut self) {
+        let active = theme::current();
+        let entries = theme::available_themes(&self.config.codex_home);
+        let initial_selected_idx = entries.iter().position(|entry| entry.id == active.id);
+        let previews: Vec<Option<Theme>> = entries
+            .iter()
+            .map(|entry| entry.theme.as_ref().ok().cloned())
+            .collect();
+
+        let items: Vec<SelectionItem> = entries
+            .into_iter()
+            .map(|entry| match entry.theme {
+                Ok(theme) => {
+                    let is_current = theme.id == active.id;
+                    let name = theme.name.clone();
+                    let description = (name != entry.id).then(|| entry.id.clone());
+                    let actions: Vec<SelectionAction> = vec![Box::new(move |tx| {
+                        tx.send(AppEvent::PersistThemeSelection {
+                            theme: theme.clone(),
+                        });
+                    })];
+                    SelectionItem {
+                        name,
+                        description,
+                        is_current,
+                        actions,
+                        dismiss_on_select: true,
+                        search_value: Some(entry.id),
+                        ..Default::default()
+                    }
+                }
+                Err(err) => SelectionItem {
+                    name: entry.id.clone(),
+                    search_value: Some(entry.id),
+                    disabled_reason: Some(err.to_string()),
+                    ..Default::default()
+                },
+            })
+            .collect();
+
+        let original = active.as_ref().clone();
+        let mut header = ColumnRenderable::new();
+        header.push(Line::from("Select Theme".bold()));
+        header.push(Line::from(
+            "Themes preview as you move. Add your own in $CODEX_HOME/themes/.".dim(),
+        ));
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            header: Box::new(header),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            initial_selected_idx,
+            on_selection_changed: Some(Box::new(move |idx, tx| {
+                if let Some(Some(theme)) = previews.get(idx) {
+                    tx.send(AppEvent::PreviewTheme(theme.clone()));
+                }
+            })),
+            on_cancel: Some(Box::new(move |tx| {
+                tx.send(AppEvent::PreviewTheme(original.clone()));
+            })),
+            ..Default::default()
+        });
+    }
+
     fn model_menu_header(&self, title: &str, subtitle: &str) -> Box<dyn Renderable> {
         let title = title.to_string();
         let subtitle = subtitle.to_string();
@@ -4774,6 +4990,7 @@ impl ChatWidget {
                 cwd: None,
                 approval_policy: None,
                 sandbox_policy: None,
+                sandbox_profile: None,
                 windows_sandbox_level: None,
                 model: Some(model_for_action.clone()),
                 subagent_model: None,
@@ -4810,6 +5027,7 @@ impl ChatWidget {
                 cwd: None,
                 approval_policy: None,
                 sandbox_policy: None,
+                sandbox_profile: None,
                 windows_sandbox_level: None,
                 model: None,
                 subagent_model: Some(model_for_action.clone()),
@@ -5127,6 +5345,7 @@ impl ChatWidget {
                 cwd: None,
                 approval_policy: None,
                 sandbox_policy: None,
+                sandbox_profile: None,
                 windows_sandbox_level: None,
                 model: Some(model.clone()),
                 subagent_model: None,
@@ -5166,6 +5385,7 @@ impl ChatWidget {
                 cwd: None,
                 approval_policy: None,
                 sandbox_policy: None,
+                sandbox_profile: None,
                 windows_sandbox_level: None,
                 model: None,
                 subagent_model: Some(model.clone()),
@@ -5215,12 +5435,19 @@ impl ChatWidget {
             && windows_degraded_sandbox_enabled
             && presets.iter().any(|preset| preset.id == "auto");
 
+        // A sandbox profile is more specific than the preset of the same mode.
+        let current_is_profile = self
+            .config
+            .sandbox_profiles
+            .values()
+            .any(|profile| &profile.policy == current_sandbox);
+
         for preset in presets.into_iter() {
             if !include_read_only && preset.id == "read-only" {
                 continue;
             }
-            let is_current =
-                Self::preset_matches_current(current_approval, current_sandbox, &preset);
+            let is_current = !current_is_profile
+                && Self::preset_matches_current(current_approval, current_sandbox, &preset);
             let name = if preset.id == "auto" && windows_degraded_sandbox_enabled {
                 "Default (non-elevated sandbox)".to_string()
             } else {
@@ -5304,6 +5531,21 @@ impl ChatWidget {
             });
         }
 
+        for (name, profile) in &self.config.sandbox_profiles {
+            items.push(SelectionItem {
+                name: format!("Sandbox profile: {name}"),
+                description: Some(Self::sandbox_profile_description(profile)),
+                is_current: &profile.policy == current_sandbox,
+                actions: Self::sandbox_selection_actions(
+                    current_approval,
+                    profile.policy.clone(),
+                    Some(name.clone()),
+                ),
+                dismiss_on_select: true,
+                ..Default::default()
+            });
+        }
+
         let footer_note = show_elevate_sandbox_hint.then(|| {
             vec![
                 "The non-elevated sandbox protects your files and prevents network access under most circumstances. However, it carries greater risk if prompt injected. To upgrade to the elevated sandbox, run ".dim(),
@@ -5372,6 +5614,16 @@ impl ChatWidget {
     fn approval_preset_actions(
         approval: AskForApproval,
         sandbox: SandboxPolicy,
+    ) -> Vec<SelectionAction> {
+        Self::sandbox_selection_actions(approval, sandbox, None)
+    }
+
+    /// Actions for a permissions item; `sandbox_profile` names the
+    /// `[sandbox_profiles]` entry `sandbox` comes from, if any.
+    fn sandbox_selection_actions(
+        approval: AskForApproval,
+        sandbox: SandboxPolicy,
+        sandbox_profile: Option<String>,
     ) -> Vec<SelectionAction> {
         vec![Box::new(move |tx| {
             let sandbox_clone = sandbox.clone();
@@ -5379,6 +5631,7 @@ impl ChatWidget {
                 cwd: None,
                 approval_policy: Some(approval),
                 sandbox_policy: Some(sandbox_clone.clone()),
+                sandbox_profile: sandbox_profile.clone(),
                 windows_sandbox_level: None,
                 model: None,
                 subagent_model: None,
@@ -5397,6 +5650,35 @@ impl ChatWidget {
         })]
     }
 
+    fn sandbox_profile_description(profile: &SandboxProfile) -> String {
+        let (mode, writable_roots) = match &profile.policy {
+            SandboxPolicy::ReadOnly { writable_roots, .. } => {
+                ("Read-only", writable_roots.as_slice())
+            }
+            SandboxPolicy::WorkspaceWrite { writable_roots, .. } => {
+                ("Workspace write", writable_roots.as_slice())
+            }
+            SandboxPolicy::DangerFullAccess | SandboxPolicy::ExternalSandbox { .. } => {
+                ("Full access", [].as_slice())
+            }
+        };
+        let mut parts = vec![mode.to_string()];
+        if !writable_roots.is_empty() {
+            let roots = writable_roots
+                .iter()
+                .map(|root| root.display().to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+            parts.push(format!("writable: {roots}"));
+        }
+        parts.push(match &profile.proxy_url {
+            Some(proxy_url) => format!("network via {proxy_url}"),
+            None if profile.policy.has_full_network_access() => "network".to_string(),
+            None => "no network".to_string(),
+        });
+        parts.join("; ")
+    }
+
     fn preset_matches_current(
         current_approval: AskForApproval,
         current_sandbox: &SandboxPolicy,
@@ -5407,15 +5689,16 @@ impl ChatWidget {
         }
         matches!(
             (&preset.sandbox, current_sandbox),
-            (SandboxPolicy::ReadOnly, SandboxPolicy::ReadOnly)
-                | (
-                    SandboxPolicy::DangerFullAccess,
-                    SandboxPolicy::DangerFullAccess
-                )
-                | (
-                    SandboxPolicy::WorkspaceWrite { .. },
-                    SandboxPolicy::WorkspaceWrite { .. }
-                )
+            (
+                SandboxPolicy::ReadOnly { .. },
+                SandboxPolicy::ReadOnly { .. }
+            ) | (
+                SandboxPolicy::DangerFullAccess,
+                SandboxPolicy::DangerFullAccess
+            ) | (
+                SandboxPolicy::WorkspaceWrite { .. },
+                SandboxPolicy::WorkspaceWrite { .. }
+            )
         )
     }
 
@@ -5536,7 +5819,7 @@ impl ChatWidget {
         let mut header_children: Vec<Box<dyn Renderable>> = Vec::new();
         let describe_policy = |policy: &SandboxPolicy| match policy {
             SandboxPolicy::WorkspaceWrite { .. } => "Agent mode",
-            SandboxPolicy::ReadOnly => "Read-Only mode",
+            SandboxPolicy::ReadOnly { .. } => "Read-Only mode",
             _ => "Agent mode",
         };
         let mode_label = preset
@@ -5948,7 +6231,7 @@ impl ChatWidget {
     /// Set the sandbox policy in the widget's config copy.
     pub(crate) fn set_sandbox_policy(&mut self, policy: SandboxPolicy) -> ConstraintResult<()> {
         #[cfg(target_os = "windows")]
-        let should_clear_downgrade = !matches!(&policy, SandboxPolicy::ReadOnly)
+        let should_clear_downgrade = !matches!(&policy, SandboxPolicy::ReadOnly { .. })
             || WindowsSandboxLevel::from_config(&self.config) != WindowsSandboxLevel::Disabled;
 
         self.config.sandbox_policy.set(policy)?;
@@ -7095,6 +7378,12 @@ impl ChatWidget {
         };
         let mut flex = FlexRenderable::new();
         flex.push(1, active_cell_renderable);
+        if self.agent_tiles.is_visible() {
+            flex.push(
+                0,
+                RenderableItem::Borrowed(&self.agent_tiles).inset(Insets::tlbr(1, 0, 0, 0)),
+            );
+        }
         flex.push(
             0,
             RenderableItem::Borrowed(&self.bottom_pane).inset(Insets::tlbr(1, 0, 0, 0)),
@@ -7125,10 +7414,30 @@ impl Renderable for ChatWidget {
 }
 
 enum Notification {
-    AgentTurnComplete { response: String },
-    ExecApprovalRequested { command: String },
-    EditApprovalRequested { cwd: PathBuf, changes: Vec<PathBuf> },
-    ElicitationRequested { server_name: String },
+    AgentTurnComplete {
+        response: String,
+    },
+    ExecApprovalRequested {
+        command: String,
+    },
+    EditApprovalRequested {
+        cwd: PathBuf,
+        changes: Vec<PathBuf>,
+    },
+    ElicitationRequested {
+        server_name: String,
+    },
+    UserInputRequested {
+        question: String,
+    },
+    CommandFinished {
+        command: String,
+        exit_code: i32,
+        duration: Duration,
+    },
+    TurnError {
+        message: String,
+    },
 }
 
 impl Notification {
@@ -7155,6 +7464,40 @@ impl Notification {
             Notification::ElicitationRequested { server_name } => {
                 format!("Approval requested by {server_name}")
             }
+            Notification::UserInputRequested { question } => {
+                Notification::agent_turn_preview(question)
+                    .unwrap_or_else(|| "Codex has a question".to_string())
+            }
+            Notification::CommandFinished {
+                command,
+                exit_code,
+                duration,
+            } => {
+                let outcome = if *exit_code == 0 {
+                    "finished".to_string()
+                } else {
+                    format!("failed (exit {exit_code})")
+                };
+                format!(
+                    "{} {outcome} after {}",
+                    truncate_text(command, 30),
+                    format_duration(*duration)
+                )
+            }
+            Notification::TurnError { message } => Notification::agent_turn_preview(message)
+                .unwrap_or_else(|| "Turn failed".to_string()),
+        }
+    }
+
+    fn title(&self) -> &'static str {
+        match self {
+            Notification::AgentTurnComplete { .. } => "Codex: turn complete",
+            Notification::ExecApprovalRequested { .. }
+            | Notification::EditApprovalRequested { .. }
+            | Notification::ElicitationRequested { .. } => "Codex: approval needed",
+            Notification::UserInputRequested { .. } => "Codex: question",
+            Notification::CommandFinished { .. } => "Codex: command finished",
+            Notification::TurnError { .. } => "Codex: error",
         }
     }
 
@@ -7164,16 +7507,35 @@ impl Notification {
             Notification::ExecApprovalRequested { .. }
             | Notification::EditApprovalRequested { .. }
             | Notification::ElicitationRequested { .. } => "approval-requested",
+            Notification::UserInputRequested { .. } => "user-input-requested",
+            Notification::CommandFinished { .. } => "command-finished",
+            Notification::TurnError { .. } => "turn-error",
         }
     }
 
-    fn allowed_for(&self, settings: &Notifications) -> bool {
-        match settings {
-            Notifications::Enabled(enabled) => *enabled,
-            Notifications::Custom(allowed) => allowed.iter().any(|a| a == self.type_name()),
+    /// Approvals and questions block the agent until the user answers.
+    fn is_urgent(&self) -> bool {
+        matches!(
+            self,
+            Notification::ExecApprovalRequested { .. }
+                | Notification::EditApprovalRequested { .. }
+                | Notification::ElicitationRequested { .. }
+                | Notification::UserInputRequested { .. }
+        )
+    }
+
+    fn desktop_notification(&self) -> DesktopNotification {
+        DesktopNotification {
+            title: self.title().to_string(),
+            body: self.display(),
+            urgent: self.is_urgent(),
         }
     }
 
+    fn allowed_for(&self, settings: &Notifications) -> bool {
+        settings.allows(self.type_name(), true)
+    }
+
     fn agent_turn_preview(response: &str) -> Option<String> {
         let mut normalized = String::new();
         for part in response.split_whitespace() {
@@ -89,6 +89,7 @@ use insta::assert_snapshot;
 use pretty_assertions::assert_eq;
 #[cfg(target_os = "windows")]
 use serial_test::serial;
+use std::collections::BTreeMap;
 use std::collections::HashSet;
 use std::path::PathBuf;
 use tempfile::NamedTempFile;
@@ -142,7 +143,7 @@ async fn resumed_initial_messages_render_history() {
         model: "test-model".to_string(),
         model_provider_id: "test-provider".to_string(),
         approval_policy: AskForApproval::Never,
-        sandbox_policy: SandboxPolicy::ReadOnly,
+        sandbox_policy: SandboxPolicy::new_read_only_policy(),
         cwd: PathBuf::from("/home/user/project"),
         reasoning_effort: Some(ReasoningEffortConfig::default()),
         history_log_id: 0,
@@ -209,7 +210,7 @@ async fn replayed_user_message_preserves_text_elements_and_local_images() {
         model: "test-model".to_string(),
         model_provider_id: "test-provider".to_string(),
         approval_policy: AskForApproval::Never,
-        sandbox_policy: SandboxPolicy::ReadOnly,
+        sandbox_policy: SandboxPolicy::new_read_only_policy(),
         cwd: PathBuf::from("/home/user/project"),
         reasoning_effort: Some(ReasoningEffortConfig::default()),
         history_log_id: 0,
@@ -326,7 +327,7 @@ async fn submission_preserves_text_elements_and_local_images() {
         model: "test-model".to_string(),
         model_provider_id: "test-provider".to_string(),
         approval_policy: AskForApproval::Never,
-        sandbox_policy: SandboxPolicy::ReadOnly,
+        sandbox_policy: SandboxPolicy::new_read_only_policy(),
         cwd: PathBuf::from("/home/user/project"),
         reasoning_effort: Some(ReasoningEffortConfig::default()),
         history_log_id: 0,
@@ -958,6 +959,7 @@ async fn make_chatwidget_manual(
         thread_id: None,
         thread_name: None,
         forked_from: None,
+        loaded_project_docs: Vec::new(),
         frame_requester: FrameRequester::test_dummy(),
         show_welcome_banner: true,
         queued_user_messages: VecDeque::new(),
@@ -986,6 +988,7 @@ async fn make_chatwidget_manual(
         status_line_branch_lookup_complete: false,
         external_editor_state: ExternalEditorState::Closed,
         last_completed_turn_id: None,
+        agent_tiles: AgentTiles::default(),
     };
     widget.set_model(&resolved_model);
     (widget, rx, op_rx)
@@ -2804,7 +2807,7 @@ async fn plan_slash_command_with_args_submits_prompt_in_plan_mode() {
         model: "test-model".to_string(),
         model_provider_id: "test-provider".to_string(),
         approval_policy: AskForApproval::Never,
-        sandbox_policy: SandboxPolicy::ReadOnly,
+        sandbox_policy: SandboxPolicy::new_read_only_policy(),
         cwd: PathBuf::from("/home/user/project"),
         reasoning_effort: Some(ReasoningEffortConfig::default()),
         history_log_id: 0,
@@ -3711,8 +3714,10 @@ async fn preset_matching_ignores_extra_writable_roots() {
     let current_sandbox = SandboxPolicy::WorkspaceWrite {
         writable_roots: vec![AbsolutePathBuf::try_from("C:\\extra").unwrap()],
         network_access: false,
+        allowed_loopback_ports: Vec::new(),
         exclude_tmpdir_env_var: false,
         exclude_slash_tmp: false,
+        deny_read: Vec::new(),
     };
 
     assert!(
@@ -4023,6 +4028,47 @@ async fn approvals_popup_shows_disabled_presets() {
     );
 }
 
+#[tokio::test]
+async fn permissions_popup_lists_sandbox_profiles() {
+    let (mut chat, _rx, _op_rx) = make_chatwidget_manual(None).await;
+    let test_policy = SandboxPolicy::ReadOnly {
+        writable_roots: vec![AbsolutePathBuf::from_absolute_path("/tmp").expect("absolute path")],
+        deny_read: Vec::new(),
+    };
+    chat.config.sandbox_profiles = BTreeMap::from([(
+        "test".to_string(),
+        SandboxProfile {
+            policy: test_policy.clone(),
+            proxy_url: None,
+        },
+    )]);
+    chat.config
+        .sandbox_policy
+        .set(test_policy)
+        .expect("set sandbox policy");
+    chat.open_approvals_popup();
+
+    let width = 100;
+    let height = chat.desired_height(width);
+    let mut terminal =
+        ratatui::Terminal::new(VT100Backend::new(width, height)).expect("create terminal");
+    terminal.set_viewport_area(Rect::new(0, 0, width, height));
+    terminal
+        .draw(|f| chat.render(f.area(), f.buffer_mut()))
+        .expect("render permissions popup");
+
+    let screen = terminal.backend().vt100().screen().contents();
+    let collapsed = screen.split_whitespace().collect::<Vec<_>>().join(" ");
+    assert!(
+        collapsed.contains("Sandbox profile: test (current)"),
+        "active sandbox profile should be listed as current: {collapsed}"
+    );
+    assert!(
+        collapsed.contains("Read-only; writable: /tmp; no network"),
+        "sandbox profile description should be shown: {collapsed}"
+    );
+}
+
 #[tokio::test]
 async fn approvals_popup_navigation_skips_disabled() {
     let (mut chat, mut rx, mut op_rx) = make_chatwidget_manual(None).await;
@@ -5665,3 +5711,34 @@ async fn review_queues_user_messages_snapshot() {
     .unwrap();
     assert_snapshot!(term.backend().vt100().screen().contents());
 }
+
+#[test]
+fn notification_toggles_default_to_enabled_for_unlisted_types() {
+    let settings = Notifications::Events(std::collections::BTreeMap::from([(
+        "command-finished".to_string(),
+        false,
+    )]));
+    let finished = Notification::CommandFinished {
+        command: "cargo test".to_string(),
+        exit_code: 101,
+        duration: Duration::from_secs(95),
+    };
+    let question = Notification::UserInputRequested {
+        question: "Which   database should I migrate?".to_string(),
+    };
+
+    assert!(!finished.allowed_for(&settings));
+    assert!(question.allowed_for(&settings));
+    assert_eq!(
+        question.desktop_notification(),
+        DesktopNotification {
+            title: "Codex: question".to_string(),
+            body: "Which database should I migrate?".to_string(),
+            urgent: true,
+        }
+    );
+    assert_eq!(
+        finished.display(),
+        "cargo test failed (exit 101) after 1m 35s"
+    );
+}
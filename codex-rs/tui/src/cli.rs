@@ -2,6 +2,7 @@ use clap::Parser;
 use clap::ValueHint;
 use codex_common::ApprovalModeCliArg;
 use codex_common::CliConfigOverrides;
+use std::net::SocketAddr;
 use std::path::PathBuf;
 
 #[derive(Parser, Debug)]
@@ -49,6 +50,20 @@ pub struct Cli {
     #[clap(skip)]
     pub fork_show_all: bool,
 
+    /// Internal: share the session with viewers over a websocket bound to this address. Set by
+    /// the top-level `codex share` subcommand; not exposed as a public flag.
+    #[clap(skip)]
+    pub share_listen: Option<SocketAddr>,
+
+    /// Internal: let viewers of a shared session answer approval requests.
+    #[clap(skip)]
+    pub share_allow_approvals: bool,
+
+    /// Internal: watch a session shared from another terminal instead of starting one. Set by
+    /// the top-level `codex attach <URL>` subcommand.
+    #[clap(skip)]
+    pub attach_url: Option<String>,
+
     /// Model the agent should use.
     #[arg(long, short = 'm')]
     pub model: Option<String>,
@@ -236,6 +236,54 @@ pub fn paste_image_to_temp_png() -> Result<(PathBuf, PastedImageInfo), PasteImag
     ))
 }
 
+#[cfg(not(target_os = "android"))]
+thread_local! {
+    /// On X11 and Wayland the clipboard contents are served by the process that set them, so the
+    /// handle has to outlive the copy.
+    static TEXT_CLIPBOARD: std::cell::RefCell<Option<arboard::Clipboard>> =
+        const { std::cell::RefCell::new(None) };
+}
+
+#[cfg(not(target_os = "android"))]
+fn with_text_clipboard<T>(
+    f: impl FnOnce(&mut arboard::Clipboard) -> Result<T, arboard::Error>,
+) -> Result<T, String> {
+    TEXT_CLIPBOARD.with(|slot| {
+        let mut slot = slot.borrow_mut();
+        let clipboard = match slot.as_mut() {
+            Some(clipboard) => clipboard,
+            None => slot.insert(arboard::Clipboard::new().map_err(|e| e.to_string())?),
+        };
+        f(clipboard).map_err(|e| e.to_string())
+    })
+}
+
+/// Place `text` on the system clipboard.
+#[cfg(not(target_os = "android"))]
+#[cfg_attr(test, allow(dead_code))]
+pub(crate) fn copy_text(text: &str) -> Result<(), String> {
+    with_text_clipboard(|clipboard| clipboard.set_text(text))
+}
+
+/// Read text from the system clipboard.
+#[cfg(not(target_os = "android"))]
+#[cfg_attr(test, allow(dead_code))]
+pub(crate) fn paste_text() -> Result<String, String> {
+    with_text_clipboard(arboard::Clipboard::get_text)
+}
+
+#[cfg(target_os = "android")]
+#[cfg_attr(test, allow(dead_code))]
+pub(crate) fn copy_text(_text: &str) -> Result<(), String> {
+    Err("clipboard is unsupported on Android".into())
+}
+
+#[cfg(target_os = "android")]
+#[cfg_attr(test, allow(dead_code))]
+pub(crate) fn paste_text() -> Result<String, String> {
+    Err("clipboard is unsupported on Android".into())
+}
+
 /// Normalize pasted text that may represent a filesystem path.
 ///
 /// Supports:
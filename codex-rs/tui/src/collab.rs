@@ -115,7 +115,7 @@ fn status_line(status: &AgentStatus) -> Line<'static> {
     detail_line("status", status_span(status))
 }
 
-fn status_span(status: &AgentStatus) -> Span<'static> {
+pub(crate) fn status_span(status: &AgentStatus) -> Span<'static> {
     match status {
         AgentStatus::PendingInit => Span::from("pending init").dim(),
         AgentStatus::Running => Span::from("running").cyan().bold(),
@@ -1,8 +1,6 @@
 use diffy::Hunk;
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
-use ratatui::style::Color;
-use ratatui::style::Modifier;
 use ratatui::style::Style;
 use ratatui::style::Stylize;
 use ratatui::text::Line as RtLine;
@@ -18,6 +16,7 @@ use crate::render::line_utils::prefix_lines;
 use crate::render::renderable::ColumnRenderable;
 use crate::render::renderable::InsetRenderable;
 use crate::render::renderable::Renderable;
+use crate::theme;
 use codex_core::git_info::get_git_repo_root;
 use codex_core::protocol::FileChange;
 
@@ -126,9 +125,9 @@ fn collect_rows(changes: &HashMap<PathBuf, FileChange>) -> Vec<Row> {
 fn render_line_count_summary(added: usize, removed: usize) -> Vec<RtSpan<'static>> {
     let mut spans = Vec::new();
     spans.push("(".into());
-    spans.push(format!("+{added}").green());
+    spans.push(RtSpan::styled(format!("+{added}"), style_add()));
     spans.push(" ".into());
-    spans.push(format!("-{removed}").red());
+    spans.push(RtSpan::styled(format!("-{removed}"), style_del()));
     spans.push(")".into());
     spans
 }
@@ -412,19 +411,19 @@ fn line_number_width(max_line_number: usize) -> usize {
 }
 
 fn style_gutter() -> Style {
-    Style::default().add_modifier(Modifier::DIM)
+    theme::current().diff_gutter
 }
 
 fn style_context() -> Style {
-    Style::default()
+    theme::current().diff_context
 }
 
 fn style_add() -> Style {
-    Style::default().fg(Color::Green)
+    theme::current().diff_add
 }
 
 fn style_del() -> Style {
-    Style::default().fg(Color::Red)
+    theme::current().diff_delete
 }
 
 #[cfg(test)]
@@ -9,6 +9,7 @@ use crate::render::highlight::highlight_bash_to_lines;
 use crate::render::line_utils::prefix_lines;
 use crate::render::line_utils::push_owned_lines;
 use crate::shimmer::shimmer_spans;
+use crate::theme;
 use crate::wrapping::RtOptions;
 use crate::wrapping::word_wrap_line;
 use crate::wrapping::word_wrap_lines;
@@ -236,10 +237,10 @@ impl HistoryCell for ExecCell {
                     .map(format_duration)
                     .unwrap_or_else(|| "unknown".to_string());
                 let mut result: Line = if output.exit_code == 0 {
-                    Line::from("✓".green().bold())
+                    Line::from(Span::styled("✓", theme::current().success).bold())
                 } else {
                     Line::from(vec![
-                        "✗".red().bold(),
+                        Span::styled("✗", theme::current().error).bold(),
                         format!(" ({})", output.exit_code).into(),
                     ])
                 };
@@ -362,8 +363,8 @@ impl ExecCell {
         let layout = EXEC_DISPLAY_LAYOUT;
         let success = call.output.as_ref().map(|o| o.exit_code == 0);
         let bullet = match success {
-            Some(true) => "•".green().bold(),
-            Some(false) => "•".red().bold(),
+            Some(true) => Span::styled("•", theme::current().success).bold(),
+            Some(false) => Span::styled("•", theme::current().error).bold(),
             None => spinner(call.start_time, self.animations_enabled()),
         };
         let is_interaction = call.is_unified_exec_interaction();
@@ -27,8 +27,10 @@ use crate::render::line_utils::push_owned_lines;
 use crate::render::renderable::Renderable;
 use crate::style::proposed_plan_style;
 use crate::style::user_message_style;
+use crate::terminal_graphics::InlineImage;
 use crate::text_formatting::format_and_truncate_tool_result;
 use crate::text_formatting::truncate_text;
+use crate::theme;
 use crate::tooltips;
 use crate::ui_consts::LIVE_PREFIX_COLS;
 use crate::update_action::UpdateAction;
@@ -132,6 +134,13 @@ pub(crate) trait HistoryCell: std::fmt::Debug + Send + Sync + Any {
     fn transcript_animation_tick(&self) -> Option<u64> {
         None
     }
+
+    /// Images to draw below the cell's lines when the terminal supports a graphics protocol.
+    ///
+    /// The cell's lines stay the text fallback, so they should still name the image.
+    fn inline_images(&self) -> Vec<InlineImage> {
+        Vec::new()
+    }
 }
 
 impl Renderable for Box<dyn HistoryCell> {
@@ -166,7 +175,6 @@ impl dyn HistoryCell {
 pub(crate) struct UserHistoryCell {
     pub message: String,
     pub text_elements: Vec<TextElement>,
-    #[allow(dead_code)]
     pub local_image_paths: Vec<PathBuf>,
 }
 
@@ -275,6 +283,13 @@ impl HistoryCell for UserHistoryCell {
         lines.push(Line::from("").style(style));
         lines
     }
+
+    fn inline_images(&self) -> Vec<InlineImage> {
+        self.local_image_paths
+            .iter()
+            .map(|path| InlineImage::from_path(path))
+            .collect()
+    }
 }
 
 #[derive(Debug)]
@@ -717,7 +732,7 @@ pub fn new_approval_decision_cell(
         Approved => {
             let snippet = Span::from(exec_snippet(&command)).dim();
             (
-                "✔ ".green(),
+                Span::styled("✔ ", theme::current().success),
                 vec![
                     "You ".into(),
                     "approved".bold(),
@@ -732,7 +747,7 @@ pub fn new_approval_decision_cell(
         } => {
             let snippet = Span::from(exec_snippet(&proposed_execpolicy_amendment.command)).dim();
             (
-                "✔ ".green(),
+                Span::styled("✔ ", theme::current().success),
                 vec![
                     "You ".into(),
                     "approved".bold(),
@@ -744,7 +759,7 @@ pub fn new_approval_decision_cell(
         ApprovedForSession => {
             let snippet = Span::from(exec_snippet(&command)).dim();
             (
-                "✔ ".green(),
+                Span::styled("✔ ", theme::current().success),
                 vec![
                     "You ".into(),
                     "approved".bold(),
@@ -757,7 +772,7 @@ pub fn new_approval_decision_cell(
         Denied => {
             let snippet = Span::from(exec_snippet(&command)).dim();
             (
-                "✗ ".red(),
+                Span::styled("✗ ", theme::current().error),
                 vec![
                     "You ".into(),
                     "did not approve".bold(),
@@ -769,7 +784,7 @@ pub fn new_approval_decision_cell(
         Abort => {
             let snippet = Span::from(exec_snippet(&command)).dim();
             (
-                "✗ ".red(),
+                Span::styled("✗ ", theme::current().error),
                 vec![
                     "You ".into(),
                     "canceled".bold(),
@@ -808,12 +823,16 @@ impl HistoryCell for PatchHistoryCell {
 
 #[derive(Debug)]
 struct CompletedMcpToolCallWithImageOutput {
-    _image: DynamicImage,
+    image: InlineImage,
 }
 impl HistoryCell for CompletedMcpToolCallWithImageOutput {
     fn display_lines(&self, _width: u16) -> Vec<Line<'static>> {
         vec!["tool result (image output)".into()]
     }
+
+    fn inline_images(&self) -> Vec<InlineImage> {
+        vec![self.image.clone()]
+    }
 }
 
 pub(crate) const SESSION_HEADER_MAX_INNER_WIDTH: usize = 56; // Just an eyeballed value
@@ -1285,8 +1304,8 @@ impl HistoryCell for McpToolCallCell {
         let mut lines: Vec<Line<'static>> = Vec::new();
         let status = self.success();
         let bullet = match status {
-            Some(true) => "•".green().bold(),
-            Some(false) => "•".red().bold(),
+            Some(true) => Span::styled("•", theme::current().success).bold(),
+            Some(false) => Span::styled("•", theme::current().error).bold(),
             None => spinner(Some(self.start_time), self.animations_enabled),
         };
         let header_text = if status.is_some() {
@@ -1494,7 +1513,9 @@ fn try_new_completed_mcp_tool_call_with_image_output(
         .iter()
         .find_map(decode_mcp_image)?;
 
-    Some(CompletedMcpToolCallWithImageOutput { _image: image })
+    Some(CompletedMcpToolCallWithImageOutput {
+        image: InlineImage::new(image),
+    })
 }
 
 /// Decodes an MCP `ImageContent` block into an in-memory image.
@@ -2142,7 +2163,23 @@ pub(crate) fn new_patch_apply_failure(stderr: String) -> PlainHistoryCell {
     PlainHistoryCell { lines }
 }
 
-pub(crate) fn new_view_image_tool_call(path: PathBuf, cwd: &Path) -> PlainHistoryCell {
+#[derive(Debug)]
+pub(crate) struct ViewImageHistoryCell {
+    lines: Vec<Line<'static>>,
+    image: InlineImage,
+}
+
+impl HistoryCell for ViewImageHistoryCell {
+    fn display_lines(&self, _width: u16) -> Vec<Line<'static>> {
+        self.lines.clone()
+    }
+
+    fn inline_images(&self) -> Vec<InlineImage> {
+        vec![self.image.clone()]
+    }
+}
+
+pub(crate) fn new_view_image_tool_call(path: PathBuf, cwd: &Path) -> ViewImageHistoryCell {
     let display_path = display_path_for(&path, cwd);
 
     let lines: Vec<Line<'static>> = vec![
@@ -2150,7 +2187,10 @@ pub(crate) fn new_view_image_tool_call(path: PathBuf, cwd: &Path) -> PlainHistor
         vec!["  └ ".dim(), display_path.dim()].into(),
     ];
 
-    PlainHistoryCell { lines }
+    ViewImageHistoryCell {
+        lines,
+        image: InlineImage::from_path(&path),
+    }
 }
 
 pub(crate) fn new_reasoning_summary_block(full_reasoning_buffer: String) -> Box<dyn HistoryCell> {
@@ -2590,6 +2630,8 @@ mod tests {
             enabled_tools: None,
             disabled_tools: None,
             scopes: None,
+            default_tool_approval: None,
+            tool_approvals: HashMap::new(),
         };
         let mut servers = config.mcp_servers.get().clone();
         servers.insert("docs".to_string(), stdio_config);
@@ -2613,6 +2655,8 @@ mod tests {
             enabled_tools: None,
             disabled_tools: None,
             scopes: None,
+            default_tool_approval: None,
+            tool_approvals: HashMap::new(),
         };
         servers.insert("http".to_string(), http_config);
         config
@@ -2,6 +2,9 @@ use std::fmt;
 use std::io;
 use std::io::Write;
 
+use crate::terminal_graphics::GraphicsProtocol;
+use crate::terminal_graphics::InlineImage;
+use crate::ui_consts::LIVE_PREFIX_COLS;
 use crate::wrapping::word_wrap_lines_borrowed;
 use crossterm::Command;
 use crossterm::cursor::MoveTo;
@@ -22,12 +25,70 @@ use ratatui::style::Modifier;
 use ratatui::text::Line;
 use ratatui::text::Span;
 
+/// Largest thumbnail drawn into the scrollback, in cells.
+const THUMBNAIL_MAX_COLS: u16 = 48;
+const THUMBNAIL_MAX_ROWS: u16 = 12;
+
 /// Insert `lines` above the viewport using the terminal's backend writer
 /// (avoids direct stdout references).
 pub fn insert_history_lines<B>(
     terminal: &mut crate::custom_terminal::Terminal<B>,
     lines: Vec<Line>,
 ) -> io::Result<()>
+where
+    B: Backend + Write,
+{
+    write_history_lines(terminal, lines).map(|_| ())
+}
+
+/// Insert `image` above the viewport as a thumbnail drawn with `protocol`.
+///
+/// Blank rows are inserted first to reserve space in the scrollback, plus one spacer row so
+/// protocols that move the cursor below the image do not scroll the region. Nothing is inserted
+/// when the image cannot be loaded or the screen has no room for it.
+pub fn insert_history_image<B>(
+    terminal: &mut crate::custom_terminal::Terminal<B>,
+    image: &InlineImage,
+    protocol: GraphicsProtocol,
+) -> io::Result<()>
+where
+    B: Backend + Write,
+{
+    let screen_size = terminal.backend().size().unwrap_or(Size::new(0, 0));
+    let area = terminal.viewport_area;
+    // Rows above the viewport once it has been pushed to the bottom, less the spacer row.
+    let available_rows = screen_size
+        .height
+        .saturating_sub(area.height)
+        .saturating_sub(1);
+    let max_cols = area
+        .width
+        .saturating_sub(LIVE_PREFIX_COLS + 1)
+        .min(THUMBNAIL_MAX_COLS);
+    let Some((cols, rows)) = image.fit(max_cols, available_rows.min(THUMBNAIL_MAX_ROWS)) else {
+        return Ok(());
+    };
+    let Some(sequence) = image.encode(protocol, cols, rows) else {
+        return Ok(());
+    };
+
+    let last_row = write_history_lines(terminal, vec![Line::from(""); usize::from(rows) + 1])?;
+    let last_cursor_pos = terminal.last_known_cursor_pos;
+    let writer = terminal.backend_mut();
+    queue!(
+        writer,
+        MoveTo(LIVE_PREFIX_COLS, last_row.saturating_sub(rows)),
+        Print(sequence),
+        MoveTo(last_cursor_pos.x, last_cursor_pos.y)
+    )?;
+    Ok(())
+}
+
+/// Writes `lines` above the viewport and returns the screen row of the last one.
+fn write_history_lines<B>(
+    terminal: &mut crate::custom_terminal::Terminal<B>,
+    lines: Vec<Line>,
+) -> io::Result<u16>
 where
     B: Backend + Write,
 {
@@ -131,7 +192,11 @@ where
         terminal.set_viewport_area(area);
     }
 
-    Ok(())
+    // Each line starts with a newline, so the last one lands `wrapped_lines` rows below the
+    // starting row unless the scroll region (everything above the viewport) filled up first.
+    Ok(cursor_top
+        .saturating_add(wrapped_lines)
+        .min(area.top().saturating_sub(1)))
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -0,0 +1,193 @@
+//! Configurable shortcuts from `[tui.keybindings]`.
+//!
+//! `codex-core` parses and validates the chords when the config is loaded; this module turns them
+//! into [`KeyBinding`]s once at startup so key handlers and key hints read the same source. Code
+//! that runs before [`install`] (including unit tests) sees the default bindings.
+
+use std::collections::BTreeMap;
+use std::sync::LazyLock;
+use std::sync::OnceLock;
+
+use codex_core::config::keybindings::ChordKey;
+use codex_core::config::keybindings::KeyChord;
+use codex_core::config::keybindings::KeybindingAction;
+use codex_core::config::keybindings::Keybindings;
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use crossterm::event::KeyModifiers;
+use ratatui::text::Span;
+
+use crate::key_hint::KeyBinding;
+
+static INSTALLED: OnceLock<Keymap> = OnceLock::new();
+static DEFAULT: LazyLock<Keymap> = LazyLock::new(|| Keymap::new(&Keybindings::default()));
+
+/// Installs the user's bindings. Only the first call has an effect.
+pub(crate) fn install(keybindings: &Keybindings) {
+    let _ = INSTALLED.set(Keymap::new(keybindings));
+}
+
+/// The active keymap.
+pub(crate) fn keymap() -> &'static Keymap {
+    INSTALLED.get().unwrap_or(&DEFAULT)
+}
+
+pub(crate) struct Keymap {
+    bindings: BTreeMap<KeybindingAction, Vec<KeyBinding>>,
+    defaults: Vec<KeybindingAction>,
+}
+
+impl Keymap {
+    fn new(keybindings: &Keybindings) -> Self {
+        let bindings = KeybindingAction::ALL
+            .into_iter()
+            .map(|action| {
+                let bindings = keybindings.chords(action).iter().map(key_binding).collect();
+                (action, bindings)
+            })
+            .collect();
+        let defaults = KeybindingAction::ALL
+            .into_iter()
+            .filter(|action| keybindings.is_default(*action))
+            .collect();
+        Self { bindings, defaults }
+    }
+
+    /// Whether `event` presses one of the chords bound to `action`.
+    pub(crate) fn is_press(&self, action: KeybindingAction, event: KeyEvent) -> bool {
+        let event = normalize(event);
+        self.bindings(action)
+            .iter()
+            .any(|binding| binding.is_press(event))
+    }
+
+    pub(crate) fn bindings(&self, action: KeybindingAction) -> &[KeyBinding] {
+        self.bindings
+            .get(&action)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// The binding to show in key hints, or `None` when `action` is unbound.
+    pub(crate) fn hint(&self, action: KeybindingAction) -> Option<KeyBinding> {
+        self.bindings(action).first().copied()
+    }
+
+    /// Compact hint text such as `ctrl+o`, for hints rendered as plain strings.
+    pub(crate) fn hint_label(&self, action: KeybindingAction) -> Option<String> {
+        self.hint(action)
+            .map(|binding| Span::from(binding).content.replace(" + ", "+"))
+    }
+
+    /// Whether `action` still uses its built-in chords, so callers can keep
+    /// context-specific default hints.
+    pub(crate) fn is_default(&self, action: KeybindingAction) -> bool {
+        self.defaults.contains(&action)
+    }
+}
+
+fn key_binding(chord: &KeyChord) -> KeyBinding {
+    let mut modifiers = KeyModifiers::NONE;
+    if chord.ctrl {
+        modifiers |= KeyModifiers::CONTROL;
+    }
+    if chord.alt {
+        modifiers |= KeyModifiers::ALT;
+    }
+    if chord.shift {
+        modifiers |= KeyModifiers::SHIFT;
+    }
+    let key = match chord.key {
+        // Terminals report Shift+letter as the uppercase character.
+        ChordKey::Char(c) if chord.shift => KeyCode::Char(c.to_ascii_uppercase()),
+        ChordKey::Char(c) => KeyCode::Char(c),
+        ChordKey::Enter => KeyCode::Enter,
+        ChordKey::Tab => KeyCode::Tab,
+        ChordKey::Esc => KeyCode::Esc,
+        ChordKey::Backspace => KeyCode::Backspace,
+        ChordKey::Delete => KeyCode::Delete,
+        ChordKey::Up => KeyCode::Up,
+        ChordKey::Down => KeyCode::Down,
+        ChordKey::Left => KeyCode::Left,
+        ChordKey::Right => KeyCode::Right,
+        ChordKey::Home => KeyCode::Home,
+        ChordKey::End => KeyCode::End,
+        ChordKey::PageUp => KeyCode::PageUp,
+        ChordKey::PageDown => KeyCode::PageDown,
+        ChordKey::F(n) => KeyCode::F(n),
+    };
+    KeyBinding::new(key, modifiers)
+}
+
+/// Shift+Tab arrives as `BackTab`, with or without the Shift flag depending on the terminal.
+fn normalize(mut event: KeyEvent) -> KeyEvent {
+    if event.code == KeyCode::BackTab {
+        event.code = KeyCode::Tab;
+        event.modifiers |= KeyModifiers::SHIFT;
+    }
+    event
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_core::config::keybindings::KeybindingToml;
+    use codex_core::config::keybindings::KeybindingsToml;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn default_bindings_match_built_in_keys() {
+        let keymap = Keymap::new(&Keybindings::default());
+        assert!(keymap.is_press(
+            KeybindingAction::OpenTranscript,
+            KeyEvent::new(KeyCode::Char('t'), KeyModifiers::CONTROL)
+        ));
+        assert!(keymap.is_press(
+            KeybindingAction::CycleMode,
+            KeyEvent::new(KeyCode::BackTab, KeyModifiers::NONE)
+        ));
+        assert!(keymap.is_press(
+            KeybindingAction::CycleMode,
+            KeyEvent::new(KeyCode::BackTab, KeyModifiers::SHIFT)
+        ));
+        assert!(!keymap.is_press(
+            KeybindingAction::Submit,
+            KeyEvent::new(KeyCode::Enter, KeyModifiers::SHIFT)
+        ));
+        assert_eq!(keymap.hint(KeybindingAction::CycleAgent), None);
+        assert_eq!(
+            keymap.hint_label(KeybindingAction::CycleMode),
+            Some("shift+tab".to_string())
+        );
+    }
+
+    #[test]
+    fn custom_bindings_replace_defaults() {
+        let keybindings = Keybindings::resolve(&KeybindingsToml {
+            open_transcript: Some(KeybindingToml::One("ctrl-o".to_string())),
+            approve: Some(KeybindingToml::One("shift-y".to_string())),
+            ..Default::default()
+        })
+        .expect("valid keybindings");
+        let keymap = Keymap::new(&keybindings);
+
+        assert!(!keymap.is_press(
+            KeybindingAction::OpenTranscript,
+            KeyEvent::new(KeyCode::Char('t'), KeyModifiers::CONTROL)
+        ));
+        assert!(keymap.is_press(
+            KeybindingAction::OpenTranscript,
+            KeyEvent::new(KeyCode::Char('o'), KeyModifiers::CONTROL)
+        ));
+        assert!(keymap.is_press(
+            KeybindingAction::Approve,
+            KeyEvent::new(KeyCode::Char('Y'), KeyModifiers::SHIFT)
+        ));
+        assert!(!keymap.is_default(KeybindingAction::OpenTranscript));
+        assert!(keymap.is_default(KeybindingAction::Submit));
+        assert_eq!(
+            keymap.hint_label(KeybindingAction::OpenTranscript),
+            Some("ctrl+o".to_string())
+        );
+    }
+}
@@ -53,6 +53,7 @@ use tracing_subscriber::prelude::*;
 use uuid::Uuid;
 
 mod additional_dirs;
+mod agent_tiles;
 mod app;
 mod app_backtrack;
 mod app_event;
@@ -78,6 +79,7 @@ mod get_git_diff;
 mod history_cell;
 pub mod insert_history;
 mod key_hint;
+mod keymap;
 pub mod live_wrap;
 mod markdown;
 mod markdown_render;
@@ -93,6 +95,7 @@ mod render;
 mod resume_picker;
 mod selection_list;
 mod session_log;
+mod session_share;
 mod shimmer;
 mod skills_helpers;
 mod slash_command;
@@ -100,14 +103,17 @@ mod status;
 mod status_indicator_widget;
 mod streaming;
 mod style;
+mod terminal_graphics;
 mod terminal_palette;
 mod text_formatting;
+mod theme;
 mod tooltips;
 mod tui;
 mod ui_consts;
 pub mod update_action;
 mod update_prompt;
 mod updates;
+mod usage;
 mod version;
 
 mod wrapping;
@@ -117,6 +123,7 @@ pub mod test_backend;
 
 use crate::onboarding::onboarding_screen::OnboardingScreenArgs;
 use crate::onboarding::onboarding_screen::run_onboarding_app;
+use crate::session_share::ShareSettings;
 use crate::tui::Tui;
 pub use cli::Cli;
 pub use markdown_render::render_markdown_text;
@@ -434,6 +441,10 @@ async fn run_ratatui_app(
     terminal.clear()?;
 
     let mut tui = Tui::new(terminal);
+    theme::init(
+        initial_config.tui_theme.as_deref(),
+        &initial_config.codex_home,
+    );
 
     #[cfg(not(debug_assertions))]
     {
@@ -467,8 +478,9 @@ async fn run_ratatui_app(
     );
     let login_status = get_login_status(&initial_config);
     let should_show_trust_screen_flag = should_show_trust_screen(&initial_config);
-    let should_show_onboarding =
-        should_show_onboarding(login_status, &initial_config, should_show_trust_screen_flag);
+    // Viewers of a shared session never talk to the model, so they skip login and trust.
+    let should_show_onboarding = cli.attach_url.is_none()
+        && should_show_onboarding(login_status, &initial_config, should_show_trust_screen_flag);
 
     let config = if should_show_onboarding {
         let show_login_screen = should_show_login_screen(login_status, &initial_config);
@@ -537,7 +549,9 @@ async fn run_ratatui_app(
     };
 
     let use_fork = cli.fork_picker || cli.fork_last || cli.fork_session_id.is_some();
-    let session_selection = if use_fork {
+    let session_selection = if let Some(url) = cli.attach_url.clone() {
+        resume_picker::SessionSelection::Attach(url)
+    } else if use_fork {
         if let Some(id_str) = cli.fork_session_id.as_deref() {
             let is_uuid = Uuid::parse_str(id_str).is_ok();
             let path = if is_uuid {
@@ -684,6 +698,10 @@ async fn run_ratatui_app(
     let active_profile = config.active_profile.clone();
     let should_show_trust_screen = should_show_trust_screen(&config);
 
+    let share = cli.share_listen.map(|bind_address| ShareSettings {
+        bind_address,
+        allow_approvals: cli.share_allow_approvals,
+    });
     let Cli {
         prompt,
         images,
@@ -691,6 +709,8 @@ async fn run_ratatui_app(
         ..
     } = cli;
 
+    keymap::install(&config.tui_keybindings);
+    terminal_graphics::init(config.tui_inline_images);
     let use_alt_screen = determine_alt_screen_mode(no_alt_screen, config.tui_alternate_screen);
     tui.set_alt_screen_enabled(use_alt_screen);
 
@@ -704,6 +724,7 @@ async fn run_ratatui_app(
         prompt,
         images,
         session_selection,
+        share,
         feedback,
         should_show_trust_screen, // Proxy to: is it a first run in this directory?
     )
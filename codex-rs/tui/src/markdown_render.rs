@@ -1,4 +1,6 @@
 use crate::render::line_utils::line_to_static;
+use crate::theme;
+use crate::theme::Theme;
 use crate::wrapping::RtOptions;
 use crate::wrapping::word_wrap_line;
 use pulldown_cmark::CodeBlockKind;
@@ -31,25 +33,23 @@ struct MarkdownStyles {
     blockquote: Style,
 }
 
-impl Default for MarkdownStyles {
-    fn default() -> Self {
-        use ratatui::style::Stylize;
-
+impl From<&Theme> for MarkdownStyles {
+    fn from(theme: &Theme) -> Self {
         Self {
-            h1: Style::new().bold().underlined(),
-            h2: Style::new().bold(),
-            h3: Style::new().bold().italic(),
-            h4: Style::new().italic(),
-            h5: Style::new().italic(),
-            h6: Style::new().italic(),
-            code: Style::new().cyan(),
-            emphasis: Style::new().italic(),
-            strong: Style::new().bold(),
-            strikethrough: Style::new().crossed_out(),
-            ordered_list_marker: Style::new().light_blue(),
-            unordered_list_marker: Style::new(),
-            link: Style::new().cyan().underlined(),
-            blockquote: Style::new().green(),
+            h1: theme.markdown_h1,
+            h2: theme.markdown_h2,
+            h3: theme.markdown_h3,
+            h4: theme.markdown_h4,
+            h5: theme.markdown_h5,
+            h6: theme.markdown_h6,
+            code: theme.markdown_code,
+            emphasis: theme.markdown_emphasis,
+            strong: theme.markdown_strong,
+            strikethrough: theme.markdown_strikethrough,
+            ordered_list_marker: theme.markdown_ordered_list_marker,
+            unordered_list_marker: theme.markdown_unordered_list_marker,
+            link: theme.markdown_link,
+            blockquote: theme.markdown_blockquote,
         }
     }
 }
@@ -115,7 +115,7 @@ where
         Self {
             iter,
             text: Text::default(),
-            styles: MarkdownStyles::default(),
+            styles: MarkdownStyles::from(theme::current().as_ref()),
             inline_styles: Vec::new(),
             indent_stack: Vec::new(),
             list_indices: Vec::new(),
@@ -0,0 +1,238 @@
+//! `org.freedesktop.Notifications` backend.
+//!
+//! Requests go through [`NotificationBus`] so the backend can be exercised against a mock bus;
+//! [`SessionBus`] talks to the real session bus on Linux. Calls are blocking, so they run on a
+//! worker thread that owns the bus and a slow or missing daemon never stalls the UI.
+
+use std::fmt;
+use std::io;
+use std::sync::mpsc;
+
+use super::DesktopNotification;
+
+const APP_NAME: &str = "Codex";
+
+/// Urgency levels from the Desktop Notifications spec.
+const URGENCY_NORMAL: u8 = 1;
+/// Critical notifications stay on screen until dismissed, so approvals are not missed.
+const URGENCY_CRITICAL: u8 = 2;
+
+/// Let the daemon pick how long the notification stays up.
+const EXPIRE_DEFAULT: i32 = -1;
+
+/// Arguments of an `org.freedesktop.Notifications.Notify` call.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NotifyRequest {
+    pub app_name: String,
+    /// Id of an earlier notification to replace, or 0 for a new one.
+    pub replaces_id: u32,
+    pub summary: String,
+    /// Body with markup characters escaped, since daemons may render a subset of HTML.
+    pub body: String,
+    pub urgency: u8,
+    pub expire_timeout: i32,
+}
+
+/// Something that can deliver `Notify` calls and return the notification id.
+pub trait NotificationBus: fmt::Debug + Send {
+    fn notify(&mut self, request: &NotifyRequest) -> io::Result<u32>;
+}
+
+#[derive(Debug)]
+pub struct DbusBackend {
+    /// Requests for the worker thread that owns the bus.
+    requests: mpsc::Sender<NotifyRequest>,
+}
+
+impl Default for DbusBackend {
+    fn default() -> Self {
+        Self::with_bus(Box::new(SessionBus::default()))
+    }
+}
+
+impl DbusBackend {
+    pub fn with_bus(bus: Box<dyn NotificationBus>) -> Self {
+        let (requests, receiver) = mpsc::channel();
+        std::thread::spawn(move || post_notifications(bus, receiver));
+        Self { requests }
+    }
+
+    /// Queues `notification` for the worker thread; delivery failures are logged there.
+    pub fn notify(&mut self, notification: &DesktopNotification) -> io::Result<()> {
+        let request = NotifyRequest {
+            app_name: APP_NAME.to_string(),
+            replaces_id: 0,
+            summary: notification.title.clone(),
+            body: escape_markup(&notification.body),
+            urgency: if notification.urgent {
+                URGENCY_CRITICAL
+            } else {
+                URGENCY_NORMAL
+            },
+            expire_timeout: EXPIRE_DEFAULT,
+        };
+        self.requests
+            .send(request)
+            .map_err(|_| io::Error::other("D-Bus notification thread has stopped"))
+    }
+}
+
+/// Posts requests in order, each replacing the previous notification so they do not pile up.
+fn post_notifications(mut bus: Box<dyn NotificationBus>, requests: mpsc::Receiver<NotifyRequest>) {
+    let mut last_id = 0;
+    for mut request in requests {
+        request.replaces_id = last_id;
+        match bus.notify(&request) {
+            Ok(id) => last_id = id,
+            Err(err) => tracing::warn!("failed to post D-Bus notification: {err}"),
+        }
+    }
+}
+
+fn escape_markup(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            ch => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+/// The D-Bus session bus, connected on first use.
+#[derive(Default)]
+pub struct SessionBus {
+    #[cfg(target_os = "linux")]
+    connection: Option<zbus::blocking::Connection>,
+}
+
+impl fmt::Debug for SessionBus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SessionBus").finish_non_exhaustive()
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl NotificationBus for SessionBus {
+    fn notify(&mut self, request: &NotifyRequest) -> io::Result<u32> {
+        use std::collections::HashMap;
+
+        use zbus::zvariant::Value;
+
+        let connection = match self.connection.take() {
+            Some(connection) => connection,
+            None => zbus::blocking::Connection::session().map_err(io::Error::other)?,
+        };
+        // No actions: a terminal application cannot raise its own window.
+        let actions: Vec<&str> = Vec::new();
+        let hints = HashMap::from([("urgency", Value::U8(request.urgency))]);
+        let reply = connection
+            .call_method(
+                Some("org.freedesktop.Notifications"),
+                "/org/freedesktop/Notifications",
+                Some("org.freedesktop.Notifications"),
+                "Notify",
+                &(
+                    request.app_name.as_str(),
+                    request.replaces_id,
+                    "",
+                    request.summary.as_str(),
+                    request.body.as_str(),
+                    actions,
+                    hints,
+                    request.expire_timeout,
+                ),
+            )
+            .map_err(io::Error::other);
+        self.connection = Some(connection);
+        reply?.body().deserialize::<u32>().map_err(io::Error::other)
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+impl NotificationBus for SessionBus {
+    fn notify(&mut self, _request: &NotifyRequest) -> io::Result<u32> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "D-Bus notifications are only available on Linux",
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::sync::Mutex;
+    use std::time::Duration;
+    use std::time::Instant;
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[derive(Debug, Default)]
+    struct MockBus {
+        requests: Arc<Mutex<Vec<NotifyRequest>>>,
+    }
+
+    impl NotificationBus for MockBus {
+        fn notify(&mut self, request: &NotifyRequest) -> io::Result<u32> {
+            let mut requests = self.requests.lock().expect("lock");
+            requests.push(request.clone());
+            Ok(u32::try_from(requests.len()).expect("few requests") + 40)
+        }
+    }
+
+    #[test]
+    fn sends_summary_urgency_and_replaces_the_previous_notification() {
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let mut backend = DbusBackend::with_bus(Box::new(MockBus {
+            requests: Arc::clone(&requests),
+        }));
+
+        backend
+            .notify(&DesktopNotification {
+                title: "Codex: approval needed".to_string(),
+                body: "Approval requested: cargo publish".to_string(),
+                urgent: true,
+            })
+            .expect("notify");
+        backend
+            .notify(&DesktopNotification {
+                title: "Codex: turn complete".to_string(),
+                body: "Published <0.2.0> & tagged".to_string(),
+                urgent: false,
+            })
+            .expect("notify");
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while requests.lock().expect("lock").len() < 2 && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        let requests = requests.lock().expect("lock");
+        assert_eq!(
+            *requests,
+            vec![
+                NotifyRequest {
+                    app_name: "Codex".to_string(),
+                    replaces_id: 0,
+                    summary: "Codex: approval needed".to_string(),
+                    body: "Approval requested: cargo publish".to_string(),
+                    urgency: URGENCY_CRITICAL,
+                    expire_timeout: EXPIRE_DEFAULT,
+                },
+                NotifyRequest {
+                    app_name: "Codex".to_string(),
+                    replaces_id: 41,
+                    summary: "Codex: turn complete".to_string(),
+                    body: "Published &lt;0.2.0&gt; &amp; tagged".to_string(),
+                    urgency: URGENCY_NORMAL,
+                    expire_timeout: EXPIRE_DEFAULT,
+                },
+            ]
+        );
+    }
+}
@@ -1,4 +1,6 @@
 mod bel;
+mod dbus;
+mod osc777;
 mod osc9;
 
 use std::env;
@@ -6,11 +8,25 @@ use std::io;
 
 use bel::BelBackend;
 use codex_core::config::types::NotificationMethod;
+use dbus::DbusBackend;
 use osc9::Osc9Backend;
+use osc777::Osc777Backend;
+
+/// A notification to post: `title` names the event and `body` summarizes it. Backends that only
+/// carry one line (OSC 9) show the body.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DesktopNotification {
+    pub title: String,
+    pub body: String,
+    /// Waits on the user (approvals, questions); backends that can keep it on screen do so.
+    pub urgent: bool,
+}
 
 #[derive(Debug)]
 pub enum DesktopNotificationBackend {
     Osc9(Osc9Backend),
+    Osc777(Osc777Backend),
+    Dbus(DbusBackend),
     Bel(BelBackend),
 }
 
@@ -25,6 +41,8 @@ impl DesktopNotificationBackend {
                 }
             }
             NotificationMethod::Osc9 => Self::Osc9(Osc9Backend),
+            NotificationMethod::Osc777 => Self::Osc777(Osc777Backend),
+            NotificationMethod::Dbus => Self::Dbus(DbusBackend::default()),
             NotificationMethod::Bel => Self::Bel(BelBackend),
         }
     }
@@ -32,14 +50,18 @@ impl DesktopNotificationBackend {
     pub fn method(&self) -> NotificationMethod {
         match self {
             DesktopNotificationBackend::Osc9(_) => NotificationMethod::Osc9,
+            DesktopNotificationBackend::Osc777(_) => NotificationMethod::Osc777,
+            DesktopNotificationBackend::Dbus(_) => NotificationMethod::Dbus,
             DesktopNotificationBackend::Bel(_) => NotificationMethod::Bel,
         }
     }
 
-    pub fn notify(&mut self, message: &str) -> io::Result<()> {
+    pub fn notify(&mut self, notification: &DesktopNotification) -> io::Result<()> {
         match self {
-            DesktopNotificationBackend::Osc9(backend) => backend.notify(message),
-            DesktopNotificationBackend::Bel(backend) => backend.notify(message),
+            DesktopNotificationBackend::Osc9(backend) => backend.notify(&notification.body),
+            DesktopNotificationBackend::Osc777(backend) => backend.notify(notification),
+            DesktopNotificationBackend::Dbus(backend) => backend.notify(notification),
+            DesktopNotificationBackend::Bel(backend) => backend.notify(&notification.body),
         }
     }
 }
@@ -120,6 +142,18 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn selects_osc777_and_dbus_methods() {
+        assert!(matches!(
+            detect_backend(NotificationMethod::Osc777),
+            super::DesktopNotificationBackend::Osc777(_)
+        ));
+        assert!(matches!(
+            detect_backend(NotificationMethod::Dbus),
+            super::DesktopNotificationBackend::Dbus(_)
+        ));
+    }
+
     #[test]
     fn selects_bel_method() {
         assert!(matches!(
@@ -0,0 +1,81 @@
+use std::fmt;
+use std::io;
+use std::io::stdout;
+
+use crossterm::Command;
+use ratatui::crossterm::execute;
+
+use super::DesktopNotification;
+
+#[derive(Debug, Default)]
+pub struct Osc777Backend;
+
+impl Osc777Backend {
+    pub fn notify(&mut self, notification: &DesktopNotification) -> io::Result<()> {
+        execute!(
+            stdout(),
+            PostNotification {
+                title: notification.title.clone(),
+                body: notification.body.clone(),
+            }
+        )
+    }
+}
+
+/// Command that emits an `OSC 777;notify` desktop notification with a title and a body.
+#[derive(Debug, Clone)]
+pub struct PostNotification {
+    pub title: String,
+    pub body: String,
+}
+
+impl Command for PostNotification {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        write!(
+            f,
+            "\x1b]777;notify;{};{}\x07",
+            sanitize(&self.title),
+            sanitize(&self.body)
+        )
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> io::Result<()> {
+        Err(std::io::Error::other(
+            "tried to execute PostNotification using WinAPI; use ANSI instead",
+        ))
+    }
+
+    #[cfg(windows)]
+    fn is_ansi_code_supported(&self) -> bool {
+        true
+    }
+}
+
+/// `;` separates the title from the body and control characters would end the sequence early.
+fn sanitize(text: &str) -> String {
+    text.chars()
+        .map(|c| if c == ';' || c.is_control() { ' ' } else { c })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn escapes_separators_and_control_characters() {
+        let mut out = String::new();
+        PostNotification {
+            title: "Codex: approval needed".to_string(),
+            body: "Approval requested: make; make install\n".to_string(),
+        }
+        .write_ansi(&mut out)
+        .expect("write");
+        assert_eq!(
+            out,
+            "\x1b]777;notify;Codex: approval needed;Approval requested: make  make install \x07"
+        );
+    }
+}
@@ -14,23 +14,34 @@
 //! `TranscriptOverlay::sync_live_tail` uses the key to decide when the cached tail must be
 //! recomputed. `ChatWidget` is responsible for producing a key that changes when the active cell
 //! mutates in place or when its transcript output is time-dependent.
+//!
+//! The transcript overlay can also search (`/`, then `n`/`N`), filter cells by kind (`f`), jump
+//! between turns (`[`, `]`, `:`), and copy the matching cells (`y`). Filtered-out cells render as
+//! empty placeholders so `view.renderables[i]` keeps corresponding to `cells[i]`. `i` opens a
+//! full-screen preview of the transcript's images, starting from the top of the screen.
 
 use std::io::Result;
 use std::sync::Arc;
 
 use crate::chatwidget::ActiveCellTranscriptKey;
+use crate::clipboard_paste;
 use crate::history_cell::HistoryCell;
 use crate::history_cell::UserHistoryCell;
 use crate::key_hint;
 use crate::key_hint::KeyBinding;
+use crate::keymap::keymap;
 use crate::render::Insets;
 use crate::render::renderable::InsetRenderable;
 use crate::render::renderable::Renderable;
 use crate::style::user_message_style;
+use crate::terminal_graphics;
+use crate::theme;
 use crate::tui;
 use crate::tui::TuiEvent;
+use codex_core::config::keybindings::KeybindingAction;
 use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
+use crossterm::event::KeyEventKind;
 use ratatui::buffer::Buffer;
 use ratatui::buffer::Cell;
 use ratatui::layout::Rect;
@@ -45,6 +56,12 @@ use ratatui::widgets::Widget;
 use ratatui::widgets::WidgetRef;
 use ratatui::widgets::Wrap;
 
+use self::image_preview::ImagePreview;
+use self::search::TranscriptFilter;
+
+mod image_preview;
+mod search;
+
 pub(crate) enum Overlay {
     Transcript(TranscriptOverlay),
     Static(StaticOverlay),
@@ -100,8 +117,20 @@ const KEY_CTRL_U: KeyBinding = key_hint::ctrl(KeyCode::Char('u'));
 const KEY_Q: KeyBinding = key_hint::plain(KeyCode::Char('q'));
 const KEY_ESC: KeyBinding = key_hint::plain(KeyCode::Esc);
 const KEY_ENTER: KeyBinding = key_hint::plain(KeyCode::Enter);
-const KEY_CTRL_T: KeyBinding = key_hint::ctrl(KeyCode::Char('t'));
 const KEY_CTRL_C: KeyBinding = key_hint::ctrl(KeyCode::Char('c'));
+const KEY_SLASH: KeyBinding = key_hint::plain(KeyCode::Char('/'));
+const KEY_F: KeyBinding = key_hint::plain(KeyCode::Char('f'));
+const KEY_Y: KeyBinding = key_hint::plain(KeyCode::Char('y'));
+const KEY_LEFT_BRACKET: KeyBinding = key_hint::plain(KeyCode::Char('['));
+const KEY_RIGHT_BRACKET: KeyBinding = key_hint::plain(KeyCode::Char(']'));
+const KEY_I: KeyBinding = key_hint::plain(KeyCode::Char('i'));
+
+/// Whether `event` types `c`, ignoring Shift so layouts that need it for `/`, `:` or `N` work.
+fn is_char_press(event: KeyEvent, c: char) -> bool {
+    event.code == KeyCode::Char(c)
+        && !key_hint::has_ctrl_or_alt(event.modifiers)
+        && event.kind != KeyEventKind::Release
+}
 
 // Common pager navigation hints rendered on the first line
 const PAGER_KEY_HINTS: &[(&[KeyBinding], &str)] = &[
@@ -137,9 +166,12 @@ struct PagerView {
     scroll_offset: usize,
     title: String,
     last_content_height: Option<usize>,
+    last_content_width: Option<u16>,
     last_rendered_height: Option<usize>,
     /// If set, on next render ensure this chunk is visible.
     pending_scroll_chunk: Option<usize>,
+    /// If set, on next render scroll so this chunk starts at the top of the viewport.
+    pending_top_chunk: Option<usize>,
 }
 
 impl PagerView {
@@ -149,8 +181,10 @@ impl PagerView {
             scroll_offset,
             title,
             last_content_height: None,
+            last_content_width: None,
             last_rendered_height: None,
             pending_scroll_chunk: None,
+            pending_top_chunk: None,
         }
     }
 
@@ -166,6 +200,7 @@ impl PagerView {
         self.render_header(area, buf);
         let content_area = self.content_area(area);
         self.update_last_content_height(content_area.height);
+        self.last_content_width = Some(content_area.width);
         let content_height = self.content_height(content_area.width);
         self.last_rendered_height = Some(content_height);
         // If there is a pending request to scroll a specific chunk into view,
@@ -173,6 +208,9 @@ impl PagerView {
         if let Some(idx) = self.pending_scroll_chunk.take() {
             self.ensure_chunk_visible(idx, content_area);
         }
+        if let Some(idx) = self.pending_top_chunk.take() {
+            self.scroll_offset = self.chunk_top(idx, content_area.width);
+        }
         self.scroll_offset = self
             .scroll_offset
             .min(content_height.saturating_sub(content_area.height as usize));
@@ -350,6 +388,35 @@ impl PagerView {
         self.pending_scroll_chunk = Some(chunk_index);
     }
 
+    /// Request that the given chunk start at the top of the viewport on next render.
+    fn scroll_chunk_to_top(&mut self, chunk_index: usize) {
+        self.pending_top_chunk = Some(chunk_index);
+    }
+
+    /// Row offset of the first line of chunk `idx` at the given width.
+    fn chunk_top(&self, idx: usize, width: u16) -> usize {
+        self.renderables
+            .iter()
+            .take(idx)
+            .map(|r| r.desired_height(width) as usize)
+            .sum()
+    }
+
+    /// Index of the chunk shown on the first content row, based on the last render.
+    ///
+    /// Returns `None` before the first render.
+    fn top_chunk(&self) -> Option<usize> {
+        let width = self.last_content_width?;
+        let mut bottom = 0usize;
+        for (idx, renderable) in self.renderables.iter().enumerate() {
+            bottom += renderable.desired_height(width) as usize;
+            if bottom > self.scroll_offset {
+                return Some(idx);
+            }
+        }
+        self.renderables.len().checked_sub(1)
+    }
+
     fn ensure_chunk_visible(&mut self, idx: usize, area: Rect) {
         if area.height == 0 || idx >= self.renderables.len() {
             return;
@@ -405,12 +472,17 @@ impl Renderable for CachedRenderable {
 struct CellRenderable {
     cell: Arc<dyn HistoryCell>,
     style: Style,
+    /// Active search query whose occurrences are highlighted.
+    search_query: Option<String>,
 }
 
 impl Renderable for CellRenderable {
     fn render(&self, area: Rect, buf: &mut Buffer) {
-        let p =
-            Paragraph::new(Text::from(self.cell.transcript_lines(area.width))).style(self.style);
+        let mut lines = self.cell.transcript_lines(area.width);
+        if let Some(query) = &self.search_query {
+            lines = search::highlight_matches(lines, query, theme::current().search_match);
+        }
+        let p = Paragraph::new(Text::from(lines)).style(self.style);
         p.render(area, buf);
     }
 
@@ -430,9 +502,40 @@ pub(crate) struct TranscriptOverlay {
     highlight_cell: Option<usize>,
     /// Cache key for the render-only live tail appended after committed cells.
     live_tail_key: Option<LiveTailKey>,
+    /// Which kinds of committed cells are shown.
+    filter: TranscriptFilter,
+    /// Active search query; empty when not searching.
+    query: String,
+    /// Lowercased search text per committed cell, filled in lazily on the first search.
+    search_index: Vec<String>,
+    /// Indices of committed cells that pass `filter` and contain `query`, oldest first.
+    matches: Vec<usize>,
+    /// Position in `matches` of the match last jumped to.
+    current_match: Option<usize>,
+    /// Open `/` or `:` prompt. While set, it receives every key.
+    prompt: Option<Prompt>,
+    /// Feedback from the last copy or turn jump, shown until the next one.
+    status: Option<String>,
+    /// Open image preview. While set, it replaces the transcript and receives every key.
+    image_preview: Option<ImagePreview>,
+    /// Set when drawn images must be removed and every cell repainted on the next draw.
+    graphics_stale: bool,
     is_done: bool,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum PromptKind {
+    /// `/`: incremental search.
+    Search,
+    /// `:`: jump to a turn by number.
+    Turn,
+}
+
+struct Prompt {
+    kind: PromptKind,
+    input: String,
+}
+
 /// Cache key for the active-cell "live tail" appended to the transcript overlay.
 ///
 /// Changing any field implies a different rendered tail.
@@ -456,53 +559,76 @@ impl TranscriptOverlay {
     pub(crate) fn new(transcript_cells: Vec<Arc<dyn HistoryCell>>) -> Self {
         Self {
             view: PagerView::new(
-                Self::render_cells(&transcript_cells, None),
+                Self::render_cells(&transcript_cells, None, TranscriptFilter::All, ""),
                 "T R A N S C R I P T".to_string(),
                 usize::MAX,
             ),
             cells: transcript_cells,
             highlight_cell: None,
             live_tail_key: None,
+            filter: TranscriptFilter::All,
+            query: String::new(),
+            search_index: Vec::new(),
+            matches: Vec::new(),
+            current_match: None,
+            prompt: None,
+            status: None,
+            image_preview: None,
+            graphics_stale: false,
             is_done: false,
         }
     }
 
+    /// Builds one renderable per committed cell.
+    ///
+    /// Cells hidden by `filter` become zero-height placeholders rather than being dropped, so
+    /// chunk indices in the pager view stay equal to cell indices. The highlighted cell is always
+    /// shown.
     fn render_cells(
         cells: &[Arc<dyn HistoryCell>],
         highlight_cell: Option<usize>,
+        filter: TranscriptFilter,
+        query: &str,
     ) -> Vec<Box<dyn Renderable>> {
+        let search_query = (!query.is_empty()).then(|| query.to_string());
+        let mut has_prior_visible = false;
         cells
             .iter()
             .enumerate()
-            .flat_map(|(i, c)| {
-                let mut v: Vec<Box<dyn Renderable>> = Vec::new();
-                let mut cell_renderable = if c.as_any().is::<UserHistoryCell>() {
-                    Box::new(CachedRenderable::new(CellRenderable {
-                        cell: c.clone(),
-                        style: if highlight_cell == Some(i) {
-                            user_message_style().reversed()
-                        } else {
-                            user_message_style()
-                        },
-                    })) as Box<dyn Renderable>
+            .map(|(i, c)| {
+                if highlight_cell != Some(i) && !filter.matches(c) {
+                    return Box::new(()) as Box<dyn Renderable>;
+                }
+                let style = if c.as_any().is::<UserHistoryCell>() {
+                    if highlight_cell == Some(i) {
+                        user_message_style().reversed()
+                    } else {
+                        user_message_style()
+                    }
                 } else {
-                    Box::new(CachedRenderable::new(CellRenderable {
-                        cell: c.clone(),
-                        style: Style::default(),
-                    })) as Box<dyn Renderable>
+                    Style::default()
                 };
-                if !c.is_stream_continuation() && i > 0 {
+                let mut cell_renderable = Box::new(CachedRenderable::new(CellRenderable {
+                    cell: c.clone(),
+                    style,
+                    search_query: search_query.clone(),
+                })) as Box<dyn Renderable>;
+                if !c.is_stream_continuation() && has_prior_visible {
                     cell_renderable = Box::new(InsetRenderable::new(
                         cell_renderable,
                         Insets::tlbr(1, 0, 0, 0),
                     ));
                 }
-                v.push(cell_renderable);
-                v
+                has_prior_visible = true;
+                cell_renderable
             })
             .collect()
     }
 
+    fn committed_renderables(&self) -> Vec<Box<dyn Renderable>> {
+        Self::render_cells(&self.cells, self.highlight_cell, self.filter, &self.query)
+    }
+
     /// Insert a committed history cell while keeping any cached live tail.
     ///
     /// The live tail is temporarily removed, the committed cells are rebuilt,
@@ -518,7 +644,10 @@ impl TranscriptOverlay {
         let had_prior_cells = !self.cells.is_empty();
         let tail_renderable = self.take_live_tail_renderable();
         self.cells.push(cell);
-        self.view.renderables = Self::render_cells(&self.cells, self.highlight_cell);
+        self.view.renderables = self.committed_renderables();
+        if !self.query.is_empty() {
+            self.refresh_matches();
+        }
         if let Some(tail) = tail_renderable {
             let tail = if !had_prior_cells
                 && self
@@ -605,7 +734,7 @@ impl TranscriptOverlay {
 
     fn rebuild_renderables(&mut self) {
         let tail_renderable = self.take_live_tail_renderable();
-        self.view.renderables = Self::render_cells(&self.cells, self.highlight_cell);
+        self.view.renderables = self.committed_renderables();
         if let Some(tail) = tail_renderable {
             self.view.renderables.push(tail);
         }
@@ -647,9 +776,72 @@ impl TranscriptOverlay {
             pairs.push((&[KEY_ESC], "to edit prev"));
         }
         render_key_hints(line2, buf, &pairs);
+
+        let line3 = Rect::new(area.x, area.y.saturating_add(2), area.width, 1);
+        self.render_search_line(line3, buf);
+    }
+
+    /// Renders the open prompt, the last status message, the active search and filter, or the
+    /// search key hints, in that order of preference.
+    fn render_search_line(&self, area: Rect, buf: &mut Buffer) {
+        if let Some(prompt) = &self.prompt {
+            let (prefix, hint) = match prompt.kind {
+                PromptKind::Search => ("/", "   enter to keep   esc to cancel"),
+                PromptKind::Turn => (":", "   enter to jump to turn"),
+            };
+            let line = Line::from(vec![
+                " ".into(),
+                prefix.into(),
+                prompt.input.clone().into(),
+                hint.dim(),
+            ]);
+            Paragraph::new(line).render_ref(area, buf);
+            return;
+        }
+        if let Some(status) = &self.status {
+            Paragraph::new(Line::from(format!(" {status}")).dim()).render_ref(area, buf);
+            return;
+        }
+        if !self.has_search_or_filter() {
+            render_key_hints(
+                area,
+                buf,
+                &[
+                    (&[KEY_SLASH], "to search"),
+                    (&[KEY_F], "to filter"),
+                    (&[KEY_Y], "to copy"),
+                    (&[KEY_LEFT_BRACKET, KEY_RIGHT_BRACKET], "to jump turns"),
+                    (&[KEY_I], "to view images"),
+                ],
+            );
+            return;
+        }
+
+        let mut spans: Vec<Span<'static>> = vec![" ".into()];
+        if self.filter != TranscriptFilter::All {
+            spans.push(format!("showing {}", self.filter.label()).dim());
+            spans.push("   ".into());
+        }
+        if !self.query.is_empty() {
+            spans.push(format!("/{}", self.query).into());
+            let position = match (self.current_match, self.matches.len()) {
+                (_, 0) => " (no matches)".to_string(),
+                (Some(current), total) => format!(" ({}/{total})", current + 1),
+                (None, total) => format!(" ({total} matches)"),
+            };
+            spans.push(position.dim());
+            spans.push("   n/N older/newer   ".dim());
+        }
+        spans.push(Span::from(KEY_ESC));
+        spans.push(" to clear".dim());
+        Paragraph::new(Line::from(spans)).render_ref(area, buf);
     }
 
     pub(crate) fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        if let Some(preview) = &mut self.image_preview {
+            preview.render(area, buf);
+            return;
+        }
         let top_h = area.height.saturating_sub(3);
         let top = Rect::new(area.x, area.y, area.width, top_h);
         let bottom = Rect::new(area.x, area.y + top_h, area.width, 3);
@@ -661,17 +853,60 @@ impl TranscriptOverlay {
 impl TranscriptOverlay {
     pub(crate) fn handle_event(&mut self, tui: &mut tui::Tui, event: TuiEvent) -> Result<()> {
         match event {
-            TuiEvent::Key(key_event) => match key_event {
-                e if KEY_Q.is_press(e) || KEY_CTRL_C.is_press(e) || KEY_CTRL_T.is_press(e) => {
-                    self.is_done = true;
-                    Ok(())
+            TuiEvent::Key(key_event) => {
+                if self.prompt.is_some() {
+                    self.handle_prompt_key(key_event);
+                    tui.frame_requester().schedule_frame();
+                    return Ok(());
                 }
-                other => self.view.handle_key_event(tui, other),
-            },
+                if self.image_preview.is_some() {
+                    self.handle_image_preview_key(key_event);
+                    tui.frame_requester().schedule_frame();
+                    return Ok(());
+                }
+                match key_event {
+                    e if KEY_Q.is_press(e)
+                        || KEY_CTRL_C.is_press(e)
+                        || keymap().is_press(KeybindingAction::OpenTranscript, e) =>
+                    {
+                        self.is_done = true;
+                        return Ok(());
+                    }
+                    e if is_char_press(e, '/') => self.open_prompt(PromptKind::Search),
+                    e if is_char_press(e, ':') => self.open_prompt(PromptKind::Turn),
+                    e if is_char_press(e, 'n') => self.step_match(true),
+                    e if is_char_press(e, 'N') => self.step_match(false),
+                    e if is_char_press(e, 'f') => self.set_filter(self.filter.next()),
+                    e if is_char_press(e, 'y') => self.copy_matches(),
+                    e if is_char_press(e, '[') => self.jump_turn(false),
+                    e if is_char_press(e, ']') => self.jump_turn(true),
+                    e if is_char_press(e, 'i') => self.open_image_preview(),
+                    e if KEY_ESC.is_press(e) && self.has_search_or_filter() => {
+                        self.clear_search_and_filter();
+                    }
+                    other => return self.view.handle_key_event(tui, other),
+                }
+                tui.frame_requester().schedule_frame();
+                Ok(())
+            }
             TuiEvent::Draw => {
+                if self.graphics_stale {
+                    self.graphics_stale = false;
+                    if let Some(protocol) = terminal_graphics::protocol() {
+                        tui.clear_graphics(protocol)?;
+                    }
+                    // iTerm2 and sixel images are cell contents; repaint every cell over them.
+                    tui.terminal.clear()?;
+                    if let Some(preview) = &mut self.image_preview {
+                        preview.invalidate();
+                    }
+                }
                 tui.draw(u16::MAX, |frame| {
                     self.render(frame.area(), frame.buffer);
                 })?;
+                if let Some(preview) = &mut self.image_preview {
+                    preview.draw_graphics(tui)?;
+                }
                 Ok(())
             }
             _ => Ok(()),
@@ -680,6 +915,271 @@ impl TranscriptOverlay {
     pub(crate) fn is_done(&self) -> bool {
         self.is_done
     }
+
+    /// Whether the overlay needs `key` before `App` applies its backtrack handling.
+    ///
+    /// An open prompt takes every key, and Esc clears an active search or filter before it starts
+    /// backtracking.
+    pub(crate) fn wants_key(&self, key: KeyEvent) -> bool {
+        self.prompt.is_some()
+            || self.image_preview.is_some()
+            || (KEY_ESC.is_press(key) && self.has_search_or_filter())
+    }
+
+    fn has_search_or_filter(&self) -> bool {
+        !self.query.is_empty() || self.filter != TranscriptFilter::All
+    }
+
+    fn open_prompt(&mut self, kind: PromptKind) {
+        self.status = None;
+        self.prompt = Some(Prompt {
+            kind,
+            input: String::new(),
+        });
+    }
+
+    fn handle_prompt_key(&mut self, key: KeyEvent) {
+        if key.kind == KeyEventKind::Release {
+            return;
+        }
+        let Some(prompt) = self.prompt.as_mut() else {
+            return;
+        };
+        let kind = prompt.kind;
+        match key.code {
+            KeyCode::Esc => {
+                self.prompt = None;
+                if kind == PromptKind::Search {
+                    self.set_query(String::new());
+                }
+            }
+            KeyCode::Enter => {
+                let input = std::mem::take(&mut prompt.input);
+                self.prompt = None;
+                if kind == PromptKind::Turn && !input.is_empty() {
+                    self.jump_to_turn_number(&input);
+                }
+            }
+            KeyCode::Backspace => {
+                if prompt.input.pop().is_none() {
+                    self.prompt = None;
+                } else if kind == PromptKind::Search {
+                    let query = prompt.input.clone();
+                    self.set_query(query);
+                }
+            }
+            KeyCode::Char(c) if !key_hint::has_ctrl_or_alt(key.modifiers) => {
+                if kind == PromptKind::Turn && !c.is_ascii_digit() {
+                    return;
+                }
+                prompt.input.push(c);
+                if kind == PromptKind::Search {
+                    let query = prompt.input.clone();
+                    self.set_query(query);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Replace the search query and jump to the most recent match.
+    fn set_query(&mut self, query: String) {
+        self.query = query;
+        self.current_match = None;
+        self.rebuild_renderables();
+        self.refresh_matches();
+        self.step_match(true);
+    }
+
+    fn set_filter(&mut self, filter: TranscriptFilter) {
+        self.filter = filter;
+        self.status = None;
+        self.current_match = None;
+        self.rebuild_renderables();
+        self.refresh_matches();
+        self.view.scroll_offset = usize::MAX;
+    }
+
+    /// Drop the search and filter, keeping the current match (if any) at the top of the view so
+    /// it can be read in context.
+    fn clear_search_and_filter(&mut self) {
+        let current = self
+            .current_match
+            .and_then(|idx| self.matches.get(idx))
+            .copied();
+        self.query.clear();
+        self.filter = TranscriptFilter::All;
+        self.status = None;
+        self.current_match = None;
+        self.matches.clear();
+        self.rebuild_renderables();
+        if let Some(cell) = current {
+            self.view.scroll_chunk_to_top(cell);
+        }
+    }
+
+    /// Recompute `matches`, keeping `current_match` on the same cell when it still matches.
+    fn refresh_matches(&mut self) {
+        let current = self
+            .current_match
+            .and_then(|idx| self.matches.get(idx))
+            .copied();
+        self.matches.clear();
+        self.current_match = None;
+        if self.query.is_empty() {
+            return;
+        }
+        let indexed = self.search_index.len();
+        self.search_index
+            .extend(self.cells[indexed..].iter().map(search::search_text));
+        let query = self.query.to_ascii_lowercase();
+        self.matches = self
+            .cells
+            .iter()
+            .zip(&self.search_index)
+            .enumerate()
+            .filter(|(_, (cell, text))| self.filter.matches(cell) && text.contains(&query))
+            .map(|(idx, _)| idx)
+            .collect();
+        self.current_match = current.and_then(|cell| self.matches.iter().position(|&m| m == cell));
+    }
+
+    /// Move to the next older (`n`) or newer (`N`) match, wrapping around. Without a current
+    /// match this lands on the most recent one, since the transcript opens at the bottom.
+    fn step_match(&mut self, older: bool) {
+        let total = self.matches.len();
+        if total == 0 {
+            return;
+        }
+        let next = match self.current_match {
+            None => total - 1,
+            Some(current) if older => (current + total - 1) % total,
+            Some(current) => (current + 1) % total,
+        };
+        self.current_match = Some(next);
+        self.status = None;
+        self.view.scroll_chunk_to_top(self.matches[next]);
+    }
+
+    fn turn_starts(&self) -> Vec<usize> {
+        self.cells
+            .iter()
+            .enumerate()
+            .filter(|(_, cell)| search::is_turn_start(cell))
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+
+    /// Scroll to the start of the turn after (`]`) or before (`[`) the one at the top of the view.
+    fn jump_turn(&mut self, forward: bool) {
+        let top = self.view.top_chunk().unwrap_or(0);
+        let turns = self.turn_starts();
+        let target = if forward {
+            turns.into_iter().find(|&idx| idx > top)
+        } else {
+            turns.into_iter().rev().find(|&idx| idx < top)
+        };
+        match target {
+            Some(idx) => {
+                self.status = None;
+                self.view.scroll_chunk_to_top(idx);
+            }
+            None if forward => self.status = Some("No later turn".to_string()),
+            None => self.status = Some("No earlier turn".to_string()),
+        }
+    }
+
+    /// Scroll to the start of turn `input`, counting user messages from 1.
+    fn jump_to_turn_number(&mut self, input: &str) {
+        let turns = self.turn_starts();
+        match input.parse::<usize>() {
+            Ok(turn) if (1..=turns.len()).contains(&turn) => {
+                self.status = None;
+                self.view.scroll_chunk_to_top(turns[turn - 1]);
+            }
+            _ => {
+                let count = turns.len();
+                self.status = Some(format!("No turn {input}; this transcript has {count}"));
+            }
+        }
+    }
+
+    /// Opens the image preview on the first image shown at or below the top of the screen, or the
+    /// last one above it.
+    fn open_image_preview(&mut self) {
+        self.status = None;
+        let top = self.view.top_chunk().unwrap_or(0);
+        let mut images = Vec::new();
+        let mut start = None;
+        for (idx, cell) in self.cells.iter().enumerate() {
+            if !self.filter.matches(cell) {
+                continue;
+            }
+            let cell_images = cell.inline_images();
+            if start.is_none() && idx >= top && !cell_images.is_empty() {
+                start = Some(images.len());
+            }
+            images.extend(cell_images);
+        }
+        if images.is_empty() {
+            self.status = Some("No images in this transcript".to_string());
+            return;
+        }
+        let index = start.unwrap_or(images.len() - 1);
+        self.image_preview = Some(ImagePreview::new(images, index));
+    }
+
+    fn handle_image_preview_key(&mut self, key: KeyEvent) {
+        let Some(preview) = &mut self.image_preview else {
+            return;
+        };
+        match key {
+            e if KEY_Q.is_press(e)
+                || KEY_ESC.is_press(e)
+                || KEY_CTRL_C.is_press(e)
+                || is_char_press(e, 'i') =>
+            {
+                self.image_preview = None;
+                self.graphics_stale = true;
+            }
+            e if KEY_LEFT.is_press(e) || is_char_press(e, 'h') => {
+                preview.step(false);
+                self.graphics_stale = true;
+            }
+            e if KEY_RIGHT.is_press(e) || is_char_press(e, 'l') => {
+                preview.step(true);
+                self.graphics_stale = true;
+            }
+            _ => {}
+        }
+    }
+
+    /// Copy the cells that match the search, or every cell the filter shows when there is no
+    /// search, to the clipboard.
+    fn copy_matches(&mut self) {
+        let indices: Vec<usize> = if self.query.is_empty() {
+            (0..self.cells.len())
+                .filter(|&idx| self.filter.matches(&self.cells[idx]))
+                .collect()
+        } else {
+            self.matches.clone()
+        };
+        if indices.is_empty() {
+            self.status = Some("Nothing to copy".to_string());
+            return;
+        }
+        let text = indices
+            .iter()
+            .map(|&idx| search::copy_text(&self.cells[idx]))
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        let count = indices.len();
+        let noun = if count == 1 { "cell" } else { "cells" };
+        self.status = Some(match clipboard_paste::copy_text(&text) {
+            Ok(()) => format!("Copied {count} {noun} to the clipboard"),
+            Err(err) => format!("Copy failed: {err}"),
+        });
+    }
 }
 
 pub(crate) struct StaticOverlay {
@@ -1255,4 +1755,113 @@ mod tests {
             "expected view to report at bottom after scrolling to end"
         );
     }
+
+    fn text_cell(text: &str) -> Arc<dyn HistoryCell> {
+        Arc::new(TestCell {
+            lines: vec![Line::from(text.to_string())],
+        })
+    }
+
+    fn finished_exec_cell(id: &str, command: &str, exit_code: i32) -> Arc<dyn HistoryCell> {
+        let mut cell = crate::exec_cell::new_active_exec_command(
+            id.into(),
+            vec!["bash".into(), "-lc".into(), command.into()],
+            vec![ParsedCommand::Unknown {
+                cmd: command.into(),
+            }],
+            ExecCommandSource::Agent,
+            None,
+            true,
+        );
+        cell.complete_call(
+            id,
+            CommandOutput {
+                exit_code,
+                aggregated_output: String::new(),
+                formatted_output: String::new(),
+            },
+            Duration::from_millis(10),
+        );
+        Arc::new(cell)
+    }
+
+    #[test]
+    fn transcript_search_starts_at_newest_match_and_wraps() {
+        let mut overlay = TranscriptOverlay::new(vec![
+            text_cell("alpha one"),
+            text_cell("beta"),
+            text_cell("Alpha two"),
+        ]);
+
+        overlay.set_query("ALPHA".to_string());
+        assert_eq!(overlay.matches, vec![0, 2]);
+        assert_eq!(overlay.current_match, Some(1));
+        assert_eq!(overlay.view.pending_top_chunk, Some(2));
+
+        overlay.step_match(true);
+        assert_eq!(overlay.current_match, Some(0));
+        overlay.step_match(true);
+        assert_eq!(overlay.current_match, Some(1));
+        overlay.step_match(false);
+        assert_eq!(overlay.current_match, Some(0));
+
+        overlay.insert_cell(text_cell("alpha three"));
+        assert_eq!(overlay.matches, vec![0, 2, 3]);
+        assert_eq!(overlay.current_match, Some(0), "current match is kept");
+    }
+
+    #[test]
+    fn transcript_filter_shows_only_failed_commands() {
+        let mut overlay = TranscriptOverlay::new(vec![
+            text_cell("hello"),
+            finished_exec_cell("ok", "echo passing", 0),
+            finished_exec_cell("bad", "echo failing", 1),
+        ]);
+        overlay.set_filter(TranscriptFilter::FailedCommands);
+
+        let area = Rect::new(0, 0, 60, 12);
+        let mut buf = Buffer::empty(area);
+        overlay.render(area, &mut buf);
+        let text = buffer_to_text(&buf, area);
+
+        assert!(text.contains("echo failing"), "{text}");
+        assert!(!text.contains("echo passing"), "{text}");
+        assert!(!text.contains("hello"), "{text}");
+        assert!(text.contains("showing failed commands"), "{text}");
+        assert!(overlay.wants_key(KeyEvent::from(KeyCode::Esc)));
+
+        overlay.clear_search_and_filter();
+        assert!(!overlay.wants_key(KeyEvent::from(KeyCode::Esc)));
+    }
+
+    #[test]
+    fn transcript_turn_prompt_jumps_to_user_message() {
+        let user_cell = |text: &str| -> Arc<dyn HistoryCell> {
+            Arc::new(history_cell::new_user_prompt(
+                text.to_string(),
+                Vec::new(),
+                Vec::new(),
+            ))
+        };
+        let mut overlay = TranscriptOverlay::new(vec![
+            user_cell("first"),
+            text_cell("answer one"),
+            user_cell("second"),
+            text_cell("answer two"),
+        ]);
+
+        overlay.open_prompt(PromptKind::Turn);
+        assert!(overlay.wants_key(KeyEvent::from(KeyCode::Char('q'))));
+        for key in [KeyCode::Char('x'), KeyCode::Char('2'), KeyCode::Enter] {
+            overlay.handle_prompt_key(KeyEvent::from(key));
+        }
+        assert!(overlay.prompt.is_none());
+        assert_eq!(overlay.view.pending_top_chunk, Some(2));
+
+        overlay.jump_to_turn_number("5");
+        assert_eq!(
+            overlay.status.as_deref(),
+            Some("No turn 5; this transcript has 2")
+        );
+    }
 }
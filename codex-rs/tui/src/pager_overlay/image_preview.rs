@@ -0,0 +1,148 @@
+//! Full-screen image preview for the transcript overlay (`i`).
+//!
+//! Ratatui only renders the chrome and leaves the content area blank; the image itself is written
+//! with the terminal's graphics protocol after each draw that moved it. Without a protocol the
+//! preview explains why nothing is shown.
+
+use std::io::Result;
+
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::text::Span;
+use ratatui::widgets::Clear;
+use ratatui::widgets::Paragraph;
+use ratatui::widgets::Widget;
+use ratatui::widgets::WidgetRef;
+use ratatui::widgets::Wrap;
+
+use super::KEY_ESC;
+use super::KEY_LEFT;
+use super::KEY_Q;
+use super::KEY_RIGHT;
+use super::render_key_hints;
+use crate::terminal_graphics;
+use crate::terminal_graphics::InlineImage;
+use crate::tui;
+
+pub(super) struct ImagePreview {
+    images: Vec<InlineImage>,
+    index: usize,
+    /// Content area from the last render, where the image goes.
+    content: Rect,
+    /// Where the current image was last drawn; `None` until it is drawn.
+    drawn: Option<Rect>,
+}
+
+impl ImagePreview {
+    pub(super) fn new(images: Vec<InlineImage>, index: usize) -> Self {
+        Self {
+            index: index.min(images.len().saturating_sub(1)),
+            images,
+            content: Rect::default(),
+            drawn: None,
+        }
+    }
+
+    /// Moves to the next or previous image, wrapping around. The caller must clear the graphics
+    /// already on screen.
+    pub(super) fn step(&mut self, forward: bool) {
+        let len = self.images.len();
+        if len == 0 {
+            return;
+        }
+        self.index = if forward {
+            (self.index + 1) % len
+        } else {
+            (self.index + len - 1) % len
+        };
+        self.drawn = None;
+    }
+
+    /// Forgets where the image was drawn, after the caller cleared the screen's graphics.
+    pub(super) fn invalidate(&mut self) {
+        self.drawn = None;
+    }
+
+    pub(super) fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+        let Some(image) = self.images.get(self.index) else {
+            return;
+        };
+
+        let mut title = format!("/ I M A G E   {} of {}", self.index + 1, self.images.len());
+        if let Some((width, height)) = image.dimensions() {
+            title.push_str(&format!("   {width}×{height}"));
+        }
+        Span::from("/ ".repeat(area.width as usize / 2))
+            .dim()
+            .render_ref(area, buf);
+        Span::from(title).dim().render_ref(area, buf);
+
+        self.content = Rect::new(
+            area.x,
+            area.y.saturating_add(1),
+            area.width,
+            area.height.saturating_sub(2),
+        );
+        if terminal_graphics::protocol().is_none() {
+            Paragraph::new(vec![
+                Line::from("This terminal cannot show images inline."),
+                Line::from(
+                    "Set tui.inline_images to kitty, iterm2 or sixel if it supports one of them."
+                        .dim(),
+                ),
+            ])
+            .wrap(Wrap { trim: false })
+            .render(self.content, buf);
+        }
+
+        let hints = Rect::new(
+            area.x,
+            area.bottom().saturating_sub(1),
+            area.width,
+            area.height.min(1),
+        );
+        render_key_hints(
+            hints,
+            buf,
+            &[
+                (&[KEY_LEFT, KEY_RIGHT], "to switch image"),
+                (&[KEY_Q, KEY_ESC], "to close"),
+            ],
+        );
+    }
+
+    /// Draws the current image into the content area left blank by [`Self::render`], unless it
+    /// is already there.
+    pub(super) fn draw_graphics(&mut self, tui: &mut tui::Tui) -> Result<()> {
+        let Some(protocol) = terminal_graphics::protocol() else {
+            return Ok(());
+        };
+        let Some(image) = self.images.get(self.index) else {
+            return Ok(());
+        };
+        let Some((cols, rows)) = image.fit(self.content.width, self.content.height) else {
+            return Ok(());
+        };
+        let target = Rect::new(
+            self.content.x + (self.content.width - cols) / 2,
+            self.content.y,
+            cols,
+            rows,
+        );
+        if self.drawn == Some(target) {
+            return Ok(());
+        }
+        if self.drawn.is_some() {
+            // The terminal was resized: remove the old placement before drawing the new one.
+            tui.clear_graphics(protocol)?;
+        }
+        if let Some(sequence) = image.encode(protocol, cols, rows) {
+            tui.draw_graphics(target.x, target.y, &sequence)?;
+        }
+        self.drawn = Some(target);
+        Ok(())
+    }
+}
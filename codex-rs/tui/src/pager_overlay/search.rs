@@ -0,0 +1,237 @@
+//! Search and filter helpers for the transcript overlay.
+//!
+//! Matching is ASCII case-insensitive so byte offsets found in the lowercased text stay valid in
+//! the original spans, which lets [`highlight_matches`] split spans without re-measuring them.
+
+use std::sync::Arc;
+
+use ratatui::style::Style;
+use ratatui::text::Line;
+use ratatui::text::Span;
+
+use crate::exec_cell::ExecCell;
+use crate::history_cell::AgentMessageCell;
+use crate::history_cell::HistoryCell;
+use crate::history_cell::PatchHistoryCell;
+use crate::history_cell::UserHistoryCell;
+
+/// Width used to flatten cells for searching. Wide enough that ordinary output is not wrapped,
+/// so a query rarely straddles a line break.
+const SEARCH_WRAP_WIDTH: u16 = 1000;
+
+/// Which history cells the transcript overlay shows.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(super) enum TranscriptFilter {
+    #[default]
+    All,
+    Commands,
+    FailedCommands,
+    Diffs,
+    AgentMessages,
+}
+
+impl TranscriptFilter {
+    /// The next filter in the `f` cycle.
+    pub(super) fn next(self) -> Self {
+        match self {
+            TranscriptFilter::All => TranscriptFilter::Commands,
+            TranscriptFilter::Commands => TranscriptFilter::FailedCommands,
+            TranscriptFilter::FailedCommands => TranscriptFilter::Diffs,
+            TranscriptFilter::Diffs => TranscriptFilter::AgentMessages,
+            TranscriptFilter::AgentMessages => TranscriptFilter::All,
+        }
+    }
+
+    pub(super) fn label(self) -> &'static str {
+        match self {
+            TranscriptFilter::All => "all",
+            TranscriptFilter::Commands => "commands",
+            TranscriptFilter::FailedCommands => "failed commands",
+            TranscriptFilter::Diffs => "diffs",
+            TranscriptFilter::AgentMessages => "agent messages",
+        }
+    }
+
+    pub(super) fn matches(self, cell: &Arc<dyn HistoryCell>) -> bool {
+        let any = cell.as_any();
+        match self {
+            TranscriptFilter::All => true,
+            TranscriptFilter::Commands => any.is::<ExecCell>(),
+            TranscriptFilter::FailedCommands => {
+                any.downcast_ref::<ExecCell>().is_some_and(|exec| {
+                    exec.iter_calls().any(|call| {
+                        call.output
+                            .as_ref()
+                            .is_some_and(|output| output.exit_code != 0)
+                    })
+                })
+            }
+            TranscriptFilter::Diffs => any.is::<PatchHistoryCell>(),
+            TranscriptFilter::AgentMessages => any.is::<AgentMessageCell>(),
+        }
+    }
+}
+
+/// Whether `cell` starts a turn; jump-to-turn counts these.
+pub(super) fn is_turn_start(cell: &Arc<dyn HistoryCell>) -> bool {
+    cell.as_any().is::<UserHistoryCell>()
+}
+
+/// Lowercased plain text of a cell, used as its search key.
+pub(super) fn search_text(cell: &Arc<dyn HistoryCell>) -> String {
+    plain_text(&cell.transcript_lines(SEARCH_WRAP_WIDTH)).to_ascii_lowercase()
+}
+
+/// Plain text of a cell for copying to the clipboard.
+pub(super) fn copy_text(cell: &Arc<dyn HistoryCell>) -> String {
+    plain_text(&cell.transcript_lines(SEARCH_WRAP_WIDTH))
+}
+
+fn plain_text(lines: &[Line<'_>]) -> String {
+    lines
+        .iter()
+        .map(|line| {
+            line.spans
+                .iter()
+                .map(|span| span.content.as_ref())
+                .collect::<String>()
+                .trim_end()
+                .to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Patch `style` onto every case-insensitive occurrence of `query` in `lines`.
+///
+/// Matches are found per line, so an occurrence split across a wrap boundary is not highlighted.
+pub(super) fn highlight_matches(
+    lines: Vec<Line<'static>>,
+    query: &str,
+    style: Style,
+) -> Vec<Line<'static>> {
+    if query.is_empty() {
+        return lines;
+    }
+    let query = query.to_ascii_lowercase();
+    lines
+        .into_iter()
+        .map(|line| highlight_line(line, &query, style))
+        .collect()
+}
+
+fn highlight_line(line: Line<'static>, query: &str, style: Style) -> Line<'static> {
+    let text: String = line
+        .spans
+        .iter()
+        .map(|span| span.content.as_ref())
+        .collect::<String>()
+        .to_ascii_lowercase();
+    let ranges: Vec<(usize, usize)> = text
+        .match_indices(query)
+        .map(|(start, matched)| (start, start + matched.len()))
+        .collect();
+    if ranges.is_empty() {
+        return line;
+    }
+
+    let mut spans = Vec::with_capacity(line.spans.len() + ranges.len() * 2);
+    let mut span_start = 0;
+    for span in line.spans {
+        let content = span.content.as_ref();
+        let span_end = span_start + content.len();
+        // Byte offsets inside this span where highlighting starts or stops.
+        let mut cuts = vec![0, content.len()];
+        for &(start, end) in &ranges {
+            for offset in [start, end] {
+                if offset > span_start && offset < span_end {
+                    cuts.push(offset - span_start);
+                }
+            }
+        }
+        cuts.sort_unstable();
+        cuts.dedup();
+        for pair in cuts.windows(2) {
+            let (from, to) = (pair[0], pair[1]);
+            let absolute = span_start + from;
+            let highlighted = ranges
+                .iter()
+                .any(|&(start, end)| absolute >= start && absolute < end);
+            let piece_style = if highlighted {
+                span.style.patch(style)
+            } else {
+                span.style
+            };
+            spans.push(Span::styled(content[from..to].to_string(), piece_style));
+        }
+        span_start = span_end;
+    }
+    Line {
+        spans,
+        style: line.style,
+        alignment: line.alignment,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use ratatui::style::Stylize;
+
+    #[test]
+    fn highlight_splits_spans_at_match_boundaries() {
+        let line = Line::from(vec!["cargo ".into(), "Test --all".bold()]);
+        let highlighted = highlight_matches(vec![line], "o te", Style::new().reversed());
+
+        assert_eq!(
+            highlighted,
+            vec![Line::from(vec![
+                "carg".into(),
+                "o ".reversed(),
+                "Te".bold().reversed(),
+                "st --all".bold(),
+            ])]
+        );
+    }
+
+    #[test]
+    fn highlight_marks_every_occurrence() {
+        let line = Line::from("ab AB ab");
+        let highlighted = highlight_matches(vec![line], "ab", Style::new().reversed());
+
+        assert_eq!(
+            highlighted,
+            vec![Line::from(vec![
+                "ab".reversed(),
+                " ".into(),
+                "AB".reversed(),
+                " ".into(),
+                "ab".reversed(),
+            ])]
+        );
+    }
+
+    #[test]
+    fn filters_cycle_back_to_all() {
+        let mut filter = TranscriptFilter::All;
+        let mut seen = Vec::new();
+        loop {
+            filter = filter.next();
+            seen.push(filter.label());
+            if filter == TranscriptFilter::All {
+                break;
+            }
+        }
+        assert_eq!(
+            seen,
+            vec![
+                "commands",
+                "failed commands",
+                "diffs",
+                "agent messages",
+                "all"
+            ]
+        );
+    }
+}
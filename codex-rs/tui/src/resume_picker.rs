@@ -47,6 +47,8 @@ pub enum SessionSelection {
     StartFresh,
     Resume(PathBuf),
     Fork(PathBuf),
+    /// Watch a session shared from another terminal (`codex attach <URL>`).
+    Attach(String),
     Exit,
 }
 
@@ -0,0 +1,285 @@
+//! `codex share` and `codex attach`: watching a running session from another terminal.
+//!
+//! The owner's [`crate::app::App`] publishes the primary thread's events to a
+//! [`codex_app_server::ShareServer`]. A viewer renders the same events through a regular
+//! [`crate::chatwidget::ChatWidget`] whose ops are filtered down to approval decisions, and only
+//! when the owner allowed them.
+
+use std::net::SocketAddr;
+
+use codex_app_server::ShareServer;
+use codex_app_server::ShareViewer;
+use codex_app_server::viewer_may_send;
+use codex_core::protocol::Event;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::Op;
+use codex_core::protocol::ReviewDecision;
+use tokio::sync::mpsc::UnboundedSender;
+use tokio::sync::mpsc::unbounded_channel;
+
+use crate::app_event::AppEvent;
+use crate::app_event_sender::AppEventSender;
+use crate::exec_command::strip_bash_lc_and_escape;
+use crate::history_cell;
+
+/// Where and how to share the session, from `codex share`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct ShareSettings {
+    pub(crate) bind_address: SocketAddr,
+    pub(crate) allow_approvals: bool,
+}
+
+/// This process's part in session sharing.
+pub(crate) enum ShareRole {
+    None,
+    /// Publishing the primary thread to viewers; dropping the server ends the share.
+    Owner(ShareServer),
+    /// Watching another terminal's session; there is no local thread.
+    Viewer,
+}
+
+/// The info message and hint shown to the owner once sharing starts.
+pub(crate) fn share_started_message(url: &str, allow_approvals: bool) -> (String, String) {
+    let hint = if allow_approvals {
+        "Viewers can watch and answer approval requests, but cannot type."
+    } else {
+        "Viewers can watch, but cannot type or answer approval requests."
+    };
+    (
+        format!("Sharing this session. Viewers run: codex attach '{url}'"),
+        format!("{hint} Anyone with this URL can connect."),
+    )
+}
+
+/// Composer placeholder for a viewer.
+pub(crate) fn viewer_placeholder(can_approve: bool) -> String {
+    if can_approve {
+        "Watching a shared session; you can answer approval requests".to_string()
+    } else {
+        "Watching a shared session (read-only)".to_string()
+    }
+}
+
+/// What a viewer does with one event of the shared session.
+#[derive(Debug)]
+enum ViewerEvent {
+    Render(Event),
+    /// The owner has to act on this; show a note instead of a prompt the viewer cannot answer.
+    Notice(String),
+    Skip,
+}
+
+fn viewer_event(event: Event, can_approve: bool) -> ViewerEvent {
+    match &event.msg {
+        EventMsg::ExecApprovalRequest(ev) if !can_approve => ViewerEvent::Notice(format!(
+            "Waiting for the session owner to approve `{}`",
+            strip_bash_lc_and_escape(&ev.command)
+        )),
+        EventMsg::ApplyPatchApprovalRequest(ev) if !can_approve => {
+            let files = ev.changes.len();
+            let noun = if files == 1 { "file" } else { "files" };
+            ViewerEvent::Notice(format!(
+                "Waiting for the session owner to approve edits to {files} {noun}"
+            ))
+        }
+        EventMsg::RequestUserInput(ev) => {
+            let question = ev
+                .questions
+                .first()
+                .map(|question| question.question.clone())
+                .unwrap_or_default();
+            ViewerEvent::Notice(format!("Codex asked the session owner: {question}"))
+        }
+        EventMsg::ElicitationRequest(ev) => ViewerEvent::Notice(format!(
+            "{} asked the session owner for input",
+            ev.server_name
+        )),
+        // The owner may start a new thread after this; the viewer keeps watching.
+        EventMsg::ShutdownComplete => ViewerEvent::Skip,
+        _ => ViewerEvent::Render(event),
+    }
+}
+
+/// Feeds a shared session into the app and returns the op sender for the viewer's chat widget.
+pub(crate) fn spawn_viewer(
+    viewer: ShareViewer,
+    app_event_tx: AppEventSender,
+) -> UnboundedSender<Op> {
+    let ShareViewer {
+        can_approve,
+        mut events,
+        ops,
+    } = viewer;
+
+    let (codex_op_tx, mut codex_op_rx) = unbounded_channel::<Op>();
+    let notice_tx = app_event_tx.clone();
+    tokio::spawn(async move {
+        while let Some(op) = codex_op_rx.recv().await {
+            let (op, narrowed) = one_off_decision(op);
+            if narrowed {
+                notice_tx.send(AppEvent::InsertHistoryCell(Box::new(
+                    history_cell::new_info_event(
+                        "Approved once; only the session owner can approve for longer.".to_string(),
+                        None,
+                    ),
+                )));
+            }
+            if viewer_may_send(&op, can_approve) && ops.send(op).is_err() {
+                break;
+            }
+        }
+    });
+
+    tokio::spawn(async move {
+        while let Some(event) = events.recv().await {
+            match viewer_event(event, can_approve) {
+                ViewerEvent::Render(event) => app_event_tx.send(AppEvent::CodexEvent(event)),
+                ViewerEvent::Notice(message) => app_event_tx.send(AppEvent::InsertHistoryCell(
+                    Box::new(history_cell::new_info_event(message, None)),
+                )),
+                ViewerEvent::Skip => {}
+            }
+        }
+        app_event_tx.send(AppEvent::InsertHistoryCell(Box::new(
+            history_cell::new_info_event(
+                "The shared session ended.".to_string(),
+                Some("Press Ctrl+C to quit.".to_string()),
+            ),
+        )));
+    });
+
+    codex_op_tx
+}
+
+/// Viewers cannot change the owner's approval policy, so an approval for the session or for a
+/// command prefix goes through as a one-time approval. Returns whether the decision was narrowed.
+fn one_off_decision(op: Op) -> (Op, bool) {
+    let narrow = |decision: ReviewDecision| match decision {
+        ReviewDecision::ApprovedForSession | ReviewDecision::ApprovedExecpolicyAmendment { .. } => {
+            (ReviewDecision::Approved, true)
+        }
+        decision => (decision, false),
+    };
+    match op {
+        Op::ExecApproval { id, decision } => {
+            let (decision, narrowed) = narrow(decision);
+            (Op::ExecApproval { id, decision }, narrowed)
+        }
+        Op::PatchApproval { id, decision } => {
+            let (decision, narrowed) = narrow(decision);
+            (Op::PatchApproval { id, decision }, narrowed)
+        }
+        op => (op, false),
+    }
+}
+
+/// Describes an approval decision a viewer sent to the owner's session.
+pub(crate) fn viewer_decision_message(op: &Op) -> Option<String> {
+    let decision = match op {
+        Op::ExecApproval { decision, .. } | Op::PatchApproval { decision, .. } => decision,
+        _ => return None,
+    };
+    let verb = match decision {
+        ReviewDecision::Approved | ReviewDecision::ApprovedExecpolicyAmendment { .. } => "approved",
+        ReviewDecision::ApprovedForSession => "approved for this session",
+        ReviewDecision::Denied => "denied",
+        ReviewDecision::Abort => "denied and stopped",
+    };
+    Some(format!("A viewer {verb} the pending request."))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use codex_core::protocol::AgentMessageEvent;
+    use codex_core::protocol::ExecApprovalRequestEvent;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn approval_request() -> Event {
+        Event {
+            id: "turn-1".to_string(),
+            msg: EventMsg::ExecApprovalRequest(ExecApprovalRequestEvent {
+                call_id: "call-1".to_string(),
+                turn_id: "turn-1".to_string(),
+                command: vec!["cargo".to_string(), "publish".to_string()],
+                cwd: PathBuf::from("/repo"),
+                reason: None,
+                proposed_execpolicy_amendment: None,
+                parsed_cmd: Vec::new(),
+            }),
+        }
+    }
+
+    #[test]
+    fn read_only_viewers_see_approvals_as_notices() {
+        let ViewerEvent::Notice(message) = viewer_event(approval_request(), false) else {
+            panic!("expected a notice for a read-only viewer");
+        };
+        assert_eq!(
+            message,
+            "Waiting for the session owner to approve `cargo publish`"
+        );
+
+        assert!(matches!(
+            viewer_event(approval_request(), true),
+            ViewerEvent::Render(_)
+        ));
+        assert!(matches!(
+            viewer_event(
+                Event {
+                    id: "turn-1".to_string(),
+                    msg: EventMsg::AgentMessage(AgentMessageEvent {
+                        message: "done".to_string(),
+                    }),
+                },
+                false,
+            ),
+            ViewerEvent::Render(_)
+        ));
+    }
+
+    #[test]
+    fn viewers_approve_once() {
+        assert_eq!(
+            one_off_decision(Op::ExecApproval {
+                id: "call-1".to_string(),
+                decision: ReviewDecision::ApprovedForSession,
+            }),
+            (
+                Op::ExecApproval {
+                    id: "call-1".to_string(),
+                    decision: ReviewDecision::Approved,
+                },
+                true
+            )
+        );
+        assert_eq!(
+            one_off_decision(Op::PatchApproval {
+                id: "call-1".to_string(),
+                decision: ReviewDecision::Denied,
+            }),
+            (
+                Op::PatchApproval {
+                    id: "call-1".to_string(),
+                    decision: ReviewDecision::Denied,
+                },
+                false
+            )
+        );
+    }
+
+    #[test]
+    fn describes_viewer_decisions() {
+        assert_eq!(
+            viewer_decision_message(&Op::PatchApproval {
+                id: "call-1".to_string(),
+                decision: ReviewDecision::Denied,
+            }),
+            Some("A viewer denied the pending request.".to_string())
+        );
+        assert_eq!(viewer_decision_message(&Op::Interrupt), None);
+    }
+}
@@ -36,6 +36,8 @@ pub enum SlashCommand {
     Diff,
     Mention,
     Status,
+    Usage,
+    Theme,
     Suggestions,
     Mcp,
     Tools,
@@ -67,6 +69,8 @@ impl SlashCommand {
             SlashCommand::Mention => "mention a file",
             SlashCommand::Skills => "use skills to improve how Codex performs specific tasks",
             SlashCommand::Status => "show current session configuration and token usage",
+            SlashCommand::Usage => "show token usage and estimated cost across sessions",
+            SlashCommand::Theme => "choose a color theme for the TUI",
             SlashCommand::Suggestions => "open prompt suggestions",
             SlashCommand::Ps => "view background terminals",
             SlashCommand::Model => "choose what model and reasoning effort to use",
@@ -101,7 +105,11 @@ impl SlashCommand {
     pub fn supports_inline_args(self) -> bool {
         matches!(
             self,
-            SlashCommand::Review | SlashCommand::Rename | SlashCommand::Plan
+            SlashCommand::Review
+                | SlashCommand::Rename
+                | SlashCommand::Plan
+                | SlashCommand::Usage
+                | SlashCommand::Feedback
         )
     }
 
@@ -130,6 +138,8 @@ impl SlashCommand {
             | SlashCommand::Mention
             | SlashCommand::Skills
             | SlashCommand::Status
+            | SlashCommand::Usage
+            | SlashCommand::Theme
             | SlashCommand::Suggestions
             | SlashCommand::Ps
             | SlashCommand::Mcp
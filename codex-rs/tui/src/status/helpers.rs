@@ -8,6 +8,7 @@ use codex_core::config::Config;
 use codex_core::project_doc::discover_project_doc_paths;
 use codex_protocol::account::PlanType;
 use std::path::Path;
+use std::path::PathBuf;
 use unicode_width::UnicodeWidthStr;
 
 use super::account::StatusAccountDisplay;
@@ -36,11 +37,13 @@ pub(crate) fn compose_model_display(
     (model_name.to_string(), details)
 }
 
-pub(crate) fn compose_agents_summary(config: &Config) -> String {
+/// Lists the project docs found at session start followed by
+/// `loaded_project_docs`, the nested ones loaded as the agent touched files.
+pub(crate) fn compose_agents_summary(config: &Config, loaded_project_docs: &[PathBuf]) -> String {
     match discover_project_doc_paths(config) {
         Ok(paths) => {
             let mut rels: Vec<String> = Vec::new();
-            for p in paths {
+            for p in paths.into_iter().chain(loaded_project_docs.iter().cloned()) {
                 let file_name = p
                     .file_name()
                     .map(|name| name.to_string_lossy().to_string())
@@ -101,8 +101,10 @@ async fn status_snapshot_includes_reasoning_details() {
         .set(SandboxPolicy::WorkspaceWrite {
             writable_roots: Vec::new(),
             network_access: false,
+            allowed_loopback_ports: Vec::new(),
             exclude_tmpdir_env_var: false,
             exclude_slash_tmp: false,
+            deny_read: Vec::new(),
         })
         .expect("set sandbox policy");
 
@@ -155,6 +157,7 @@ async fn status_snapshot_includes_reasoning_details() {
         &model_slug,
         None,
         reasoning_effort_override,
+        &[],
     );
     let mut rendered_lines = render_lines(&composite.display_lines(80));
     if cfg!(windows) {
@@ -209,6 +212,7 @@ async fn status_snapshot_includes_forked_from() {
         &model_slug,
         None,
         None,
+        &[],
     );
     let mut rendered_lines = render_lines(&composite.display_lines(80));
     if cfg!(windows) {
@@ -269,6 +273,7 @@ async fn status_snapshot_includes_monthly_limit() {
         &model_slug,
         None,
         None,
+        &[],
     );
     let mut rendered_lines = render_lines(&composite.display_lines(80));
     if cfg!(windows) {
@@ -317,6 +322,7 @@ async fn status_snapshot_shows_unlimited_credits() {
         &model_slug,
         None,
         None,
+        &[],
     );
     let rendered = render_lines(&composite.display_lines(120));
     assert!(
@@ -364,6 +370,7 @@ async fn status_snapshot_shows_positive_credits() {
         &model_slug,
         None,
         None,
+        &[],
     );
     let rendered = render_lines(&composite.display_lines(120));
     assert!(
@@ -411,6 +418,7 @@ async fn status_snapshot_hides_zero_credits() {
         &model_slug,
         None,
         None,
+        &[],
     );
     let rendered = render_lines(&composite.display_lines(120));
     assert!(
@@ -456,6 +464,7 @@ async fn status_snapshot_hides_when_has_no_credits_flag() {
         &model_slug,
         None,
         None,
+        &[],
     );
     let rendered = render_lines(&composite.display_lines(120));
     assert!(
@@ -501,6 +510,7 @@ async fn status_card_token_usage_excludes_cached_tokens() {
         &model_slug,
         None,
         None,
+        &[],
     );
     let rendered = render_lines(&composite.display_lines(120));
 
@@ -561,6 +571,7 @@ async fn status_snapshot_truncates_in_narrow_terminal() {
         &model_slug,
         None,
         reasoning_effort_override,
+        &[],
     );
     let mut rendered_lines = render_lines(&composite.display_lines(70));
     if cfg!(windows) {
@@ -610,6 +621,7 @@ async fn status_snapshot_shows_missing_limits_message() {
         &model_slug,
         None,
         None,
+        &[],
     );
     let mut rendered_lines = render_lines(&composite.display_lines(80));
     if cfg!(windows) {
@@ -677,6 +689,7 @@ async fn status_snapshot_includes_credits_and_limits() {
         &model_slug,
         None,
         None,
+        &[],
     );
     let mut rendered_lines = render_lines(&composite.display_lines(80));
     if cfg!(windows) {
@@ -732,6 +745,7 @@ async fn status_snapshot_shows_empty_limits_message() {
         &model_slug,
         None,
         None,
+        &[],
     );
     let mut rendered_lines = render_lines(&composite.display_lines(80));
     if cfg!(windows) {
@@ -796,6 +810,7 @@ async fn status_snapshot_shows_stale_limits_message() {
         &model_slug,
         None,
         None,
+        &[],
     );
     let mut rendered_lines = render_lines(&composite.display_lines(80));
     if cfg!(windows) {
@@ -864,6 +879,7 @@ async fn status_snapshot_cached_limits_hide_credits_without_flag() {
         &model_slug,
         None,
         None,
+        &[],
     );
     let mut rendered_lines = render_lines(&composite.display_lines(80));
     if cfg!(windows) {
@@ -922,6 +938,7 @@ async fn status_context_window_uses_last_usage() {
         &model_slug,
         None,
         None,
+        &[],
     );
     let rendered_lines = render_lines(&composite.display_lines(80));
     let context_line = rendered_lines
@@ -4,8 +4,8 @@
 use std::time::Duration;
 use std::time::Instant;
 
+use codex_core::config::keybindings::KeybindingAction;
 use codex_core::protocol::Op;
-use crossterm::event::KeyCode;
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
 use ratatui::style::Stylize;
@@ -19,10 +19,11 @@ use unicode_width::UnicodeWidthStr;
 use crate::app_event::AppEvent;
 use crate::app_event_sender::AppEventSender;
 use crate::exec_cell::spinner;
-use crate::key_hint;
+use crate::keymap::keymap;
 use crate::render::renderable::Renderable;
 use crate::shimmer::shimmer_spans;
 use crate::text_formatting::capitalize_first;
+use crate::theme;
 use crate::tui::FrameRequester;
 use crate::wrapping::RtOptions;
 use crate::wrapping::word_wrap_lines;
@@ -231,28 +232,33 @@ impl StatusIndicatorWidget {
             spans.push(self.header.clone().into());
         }
         spans.push(" ".into());
-        if self.show_interrupt_hint {
-            spans.extend(vec![
-                format!("({pretty_elapsed} • ").dim(),
-                key_hint::plain(KeyCode::Esc).into(),
-            ]);
-            if show_queue_hint {
-                spans.extend(vec![
-                    " to interrupt • ".dim(),
-                    key_hint::plain(KeyCode::Tab).into(),
-                    " to queue message)".dim(),
-                ]);
-            } else {
-                spans.push(" to interrupt)".dim());
-            }
-        } else if show_queue_hint {
-            spans.extend(vec![
-                format!("({pretty_elapsed} • ").dim(),
-                key_hint::plain(KeyCode::Tab).into(),
-                " to queue message)".dim(),
-            ]);
-        } else {
-            spans.push(format!("({pretty_elapsed})").dim());
+        let interrupt_key = self
+            .show_interrupt_hint
+            .then(|| keymap().hint(KeybindingAction::Interrupt))
+            .flatten();
+        let queue_key = show_queue_hint
+            .then(|| keymap().hint(KeybindingAction::Queue))
+            .flatten();
+        let status = theme::current().status_line;
+        match (interrupt_key, queue_key) {
+            (Some(interrupt_key), Some(queue_key)) => spans.extend(vec![
+                Span::styled(format!("({pretty_elapsed} • "), status),
+                interrupt_key.into(),
+                Span::styled(" to interrupt • ", status),
+                queue_key.into(),
+                Span::styled(" to queue message)", status),
+            ]),
+            (Some(interrupt_key), None) => spans.extend(vec![
+                Span::styled(format!("({pretty_elapsed} • "), status),
+                interrupt_key.into(),
+                Span::styled(" to interrupt)", status),
+            ]),
+            (None, Some(queue_key)) => spans.extend(vec![
+                Span::styled(format!("({pretty_elapsed} • "), status),
+                queue_key.into(),
+                Span::styled(" to queue message)", status),
+            ]),
+            (None, None) => spans.push(Span::styled(format!("({pretty_elapsed})"), status)),
         }
 
         let mut lines = Vec::new();
@@ -2,6 +2,7 @@ use crate::color::blend;
 use crate::color::is_light;
 use crate::terminal_palette::best_color;
 use crate::terminal_palette::default_bg;
+use crate::theme;
 use ratatui::style::Color;
 use ratatui::style::Style;
 
@@ -14,7 +15,13 @@ pub fn proposed_plan_style() -> Style {
 }
 
 /// Returns the style for a user-authored message using the provided terminal background.
+///
+/// A `user_message` style from the active theme takes precedence over the derived background.
 pub fn user_message_style_for(terminal_bg: Option<(u8, u8, u8)>) -> Style {
+    let themed = theme::current().user_message;
+    if themed != Style::default() {
+        return themed;
+    }
     match terminal_bg {
         Some(bg) => Style::default().bg(user_message_bg(bg)),
         None => Style::default(),
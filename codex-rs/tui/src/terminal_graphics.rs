@@ -0,0 +1,406 @@
+//! Inline images for terminals that speak a graphics protocol.
+//!
+//! Like `terminal_palette`, this module answers "what can the terminal show?" once at startup.
+//! [`init`] resolves `tui.inline_images` against the environment, and [`protocol`] returns the
+//! result; `None` means cells keep their plain-text lines as the fallback.
+//!
+//! [`InlineImage::encode`] turns an image into the escape sequence for the chosen protocol, sized
+//! in terminal cells. Kitty and iTerm2 scale the image themselves; sixel needs pixels, so it is
+//! resized to the cell size reported by the terminal.
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::OnceLock;
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
+use codex_core::config::types::InlineImages;
+use codex_utils_image::ImageSelection;
+use codex_utils_image::load_image_parts;
+use image::DynamicImage;
+use image::GenericImageView;
+use image::ImageFormat;
+use image::imageops::FilterType;
+
+/// Graphics protocols the TUI can emit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum GraphicsProtocol {
+    Kitty,
+    Iterm2,
+    Sixel,
+}
+
+static PROTOCOL: OnceLock<Option<GraphicsProtocol>> = OnceLock::new();
+
+/// Cell size in pixels assumed when the terminal does not report one.
+const FALLBACK_CELL_PIXELS: (u32, u32) = (10, 20);
+/// Kitty rejects escape payloads longer than this, so larger images are sent in chunks.
+const KITTY_CHUNK_BYTES: usize = 4096;
+
+/// Resolves the `tui.inline_images` setting. Only the first call has an effect.
+pub(crate) fn init(setting: InlineImages) {
+    let protocol = match setting {
+        InlineImages::Auto => detect(|name| std::env::var(name).ok()),
+        InlineImages::Kitty => Some(GraphicsProtocol::Kitty),
+        InlineImages::Iterm2 => Some(GraphicsProtocol::Iterm2),
+        InlineImages::Sixel => Some(GraphicsProtocol::Sixel),
+        InlineImages::Off => None,
+    };
+    let _ = PROTOCOL.set(protocol);
+}
+
+/// The protocol to draw images with, or `None` to keep the text fallback.
+pub(crate) fn protocol() -> Option<GraphicsProtocol> {
+    PROTOCOL.get().copied().flatten()
+}
+
+/// Picks a protocol from well-known terminal environment variables.
+///
+/// Multiplexers are skipped because they drop or garble graphics unless passthrough is set up.
+fn detect(env: impl Fn(&str) -> Option<String>) -> Option<GraphicsProtocol> {
+    if ["TMUX", "STY", "ZELLIJ"]
+        .iter()
+        .any(|name| env(name).is_some())
+    {
+        return None;
+    }
+    let term = env("TERM").unwrap_or_default();
+    let term_program = env("TERM_PROGRAM").unwrap_or_default();
+    if env("KITTY_WINDOW_ID").is_some() || term == "xterm-kitty" || term_program == "ghostty" {
+        return Some(GraphicsProtocol::Kitty);
+    }
+    if term_program == "iTerm.app"
+        || term_program == "WezTerm"
+        || env("LC_TERMINAL").as_deref() == Some("iTerm2")
+    {
+        return Some(GraphicsProtocol::Iterm2);
+    }
+    if term.starts_with("foot") || term.starts_with("mlterm") || term.contains("sixel") {
+        return Some(GraphicsProtocol::Sixel);
+    }
+    None
+}
+
+/// An image attached to a history cell. Images read from disk are decoded on first use and
+/// cached, so cells that are never drawn as images cost nothing.
+#[derive(Debug, Clone)]
+pub(crate) struct InlineImage {
+    source: Arc<ImageSource>,
+}
+
+#[derive(Debug)]
+enum ImageSource {
+    Decoded(DynamicImage),
+    File {
+        path: PathBuf,
+        decoded: OnceLock<Option<DynamicImage>>,
+    },
+}
+
+impl InlineImage {
+    pub(crate) fn new(image: DynamicImage) -> Self {
+        Self {
+            source: Arc::new(ImageSource::Decoded(image)),
+        }
+    }
+
+    /// An image file, PDF or SVG; PDFs show their first page.
+    pub(crate) fn from_path(path: &Path) -> Self {
+        Self {
+            source: Arc::new(ImageSource::File {
+                path: path.to_path_buf(),
+                decoded: OnceLock::new(),
+            }),
+        }
+    }
+
+    fn image(&self) -> Option<&DynamicImage> {
+        match self.source.as_ref() {
+            ImageSource::Decoded(image) => Some(image),
+            ImageSource::File { path, decoded } => decoded
+                .get_or_init(|| {
+                    let part = load_image_parts(path, &ImageSelection::default())
+                        .map_err(|err| tracing::debug!("cannot load {}: {err}", path.display()))
+                        .ok()?
+                        .into_iter()
+                        .next()?;
+                    image::load_from_memory(&part.image.bytes)
+                        .map_err(|err| tracing::debug!("cannot decode {}: {err}", path.display()))
+                        .ok()
+                })
+                .as_ref(),
+        }
+    }
+
+    /// Pixel size, or `None` when the image cannot be loaded.
+    pub(crate) fn dimensions(&self) -> Option<(u32, u32)> {
+        self.image().map(GenericImageView::dimensions)
+    }
+
+    /// Largest size in cells, at most `max_cols` by `max_rows`, that keeps the aspect ratio.
+    /// Returns `None` when the image cannot be loaded or does not fit at all.
+    pub(crate) fn fit(&self, max_cols: u16, max_rows: u16) -> Option<(u16, u16)> {
+        let (width, height) = self.dimensions()?;
+        fit_cells(width, height, cell_pixels(), max_cols, max_rows)
+    }
+
+    /// Escape sequence that draws the image over `cols` by `rows` cells from the cursor.
+    pub(crate) fn encode(
+        &self,
+        protocol: GraphicsProtocol,
+        cols: u16,
+        rows: u16,
+    ) -> Option<String> {
+        let image = self.image()?;
+        let (cell_width, cell_height) = cell_pixels();
+        let width = u32::from(cols) * cell_width;
+        let height = u32::from(rows) * cell_height;
+        match protocol {
+            GraphicsProtocol::Kitty => {
+                let png = encode_png(&image.resize(width, height, FilterType::Triangle))?;
+                Some(kitty_sequence(&png, cols, rows))
+            }
+            GraphicsProtocol::Iterm2 => {
+                let png = encode_png(&image.resize(width, height, FilterType::Triangle))?;
+                Some(iterm2_sequence(&png, cols, rows))
+            }
+            GraphicsProtocol::Sixel => Some(sixel_sequence(&image.resize_exact(
+                width,
+                height,
+                FilterType::Triangle,
+            ))),
+        }
+    }
+}
+
+/// Escape sequence that removes images drawn with `protocol`, when the protocol needs one.
+/// iTerm2 and sixel images are plain cell contents and disappear when the cells are redrawn.
+pub(crate) fn clear_sequence(protocol: GraphicsProtocol) -> Option<&'static str> {
+    match protocol {
+        GraphicsProtocol::Kitty => Some("\x1b_Ga=d,d=A,q=2\x1b\\"),
+        GraphicsProtocol::Iterm2 | GraphicsProtocol::Sixel => None,
+    }
+}
+
+fn cell_pixels() -> (u32, u32) {
+    crossterm::terminal::window_size()
+        .ok()
+        .filter(|size| size.width > 0 && size.height > 0 && size.columns > 0 && size.rows > 0)
+        .map(|size| {
+            (
+                u32::from(size.width / size.columns).max(1),
+                u32::from(size.height / size.rows).max(1),
+            )
+        })
+        .unwrap_or(FALLBACK_CELL_PIXELS)
+}
+
+fn fit_cells(
+    width: u32,
+    height: u32,
+    (cell_width, cell_height): (u32, u32),
+    max_cols: u16,
+    max_rows: u16,
+) -> Option<(u16, u16)> {
+    if width == 0 || height == 0 || max_cols == 0 || max_rows == 0 {
+        return None;
+    }
+    // Never upscale: a small icon stays small.
+    let natural_cols = width.div_ceil(cell_width).max(1);
+    let natural_rows = height.div_ceil(cell_height).max(1);
+    let scale = f64::min(
+        1.0,
+        f64::min(
+            f64::from(max_cols) / f64::from(natural_cols),
+            f64::from(max_rows) / f64::from(natural_rows),
+        ),
+    );
+    let cols = (f64::from(natural_cols) * scale).round().max(1.0) as u16;
+    let rows = (f64::from(natural_rows) * scale).round().max(1.0) as u16;
+    Some((cols.min(max_cols), rows.min(max_rows)))
+}
+
+fn encode_png(image: &DynamicImage) -> Option<Vec<u8>> {
+    let mut bytes = Vec::new();
+    image
+        .write_to(&mut std::io::Cursor::new(&mut bytes), ImageFormat::Png)
+        .map_err(|err| tracing::debug!("cannot encode inline image: {err}"))
+        .ok()?;
+    Some(bytes)
+}
+
+/// Kitty graphics protocol: transmit and display a PNG in one go, without moving the cursor
+/// (`C=1`) or asking for a reply (`q=2`).
+fn kitty_sequence(png: &[u8], cols: u16, rows: u16) -> String {
+    let encoded = BASE64_STANDARD.encode(png);
+    let chunks: Vec<&[u8]> = encoded.as_bytes().chunks(KITTY_CHUNK_BYTES).collect();
+    let mut out = String::with_capacity(encoded.len() + chunks.len() * 16 + 48);
+    for (idx, chunk) in chunks.iter().enumerate() {
+        let more = u8::from(idx + 1 < chunks.len());
+        let chunk = String::from_utf8_lossy(chunk);
+        if idx == 0 {
+            out.push_str(&format!(
+                "\x1b_Ga=T,f=100,q=2,C=1,c={cols},r={rows},m={more};{chunk}\x1b\\"
+            ));
+        } else {
+            out.push_str(&format!("\x1b_Gm={more};{chunk}\x1b\\"));
+        }
+    }
+    out
+}
+
+/// iTerm2 inline image (OSC 1337), also understood by WezTerm.
+fn iterm2_sequence(png: &[u8], cols: u16, rows: u16) -> String {
+    let encoded = BASE64_STANDARD.encode(png);
+    format!(
+        "\x1b]1337;File=inline=1;size={};width={cols};height={rows};preserveAspectRatio=1:{encoded}\x07",
+        png.len()
+    )
+}
+
+/// Sixel with a fixed 6x6x6 color cube. Pixels that are mostly transparent are left unpainted.
+fn sixel_sequence(image: &DynamicImage) -> String {
+    let rgba = image.to_rgba8();
+    let (width, height) = rgba.dimensions();
+    let level = |c: u8| (u16::from(c) * 5 + 127) / 255;
+    let indices: Vec<Option<u8>> = rgba
+        .pixels()
+        .map(|pixel| {
+            let [r, g, b, a] = pixel.0;
+            (a >= 128).then(|| (level(r) * 36 + level(g) * 6 + level(b)) as u8)
+        })
+        .collect();
+    let color_index = |x: u32, y: u32| indices[(y * width + x) as usize];
+
+    let mut out = format!("\x1bP0;1;0q\"1;1;{width};{height}");
+    for idx in 0..216u16 {
+        let percent = |level: u16| level * 100 / 5;
+        out.push_str(&format!(
+            "#{idx};2;{};{};{}",
+            percent(idx / 36),
+            percent(idx / 6 % 6),
+            percent(idx % 6)
+        ));
+    }
+    for band_top in (0..height).step_by(6) {
+        let band_rows = (height - band_top).min(6);
+        let mut used = [false; 216];
+        for y in band_top..band_top + band_rows {
+            for x in 0..width {
+                if let Some(color) = color_index(x, y) {
+                    used[usize::from(color)] = true;
+                }
+            }
+        }
+        for color in (0..216u8).filter(|color| used[usize::from(*color)]) {
+            out.push_str(&format!("#{color}"));
+            let mut run: Option<(u8, u32)> = None;
+            for x in 0..width {
+                let mut bits = 0u8;
+                for row in 0..band_rows {
+                    if color_index(x, band_top + row) == Some(color) {
+                        bits |= 1 << row;
+                    }
+                }
+                run = match run {
+                    Some((prev, count)) if prev == bits => Some((prev, count + 1)),
+                    Some((prev, count)) => {
+                        push_sixel_run(&mut out, prev, count);
+                        Some((bits, 1))
+                    }
+                    None => Some((bits, 1)),
+                };
+            }
+            if let Some((bits, count)) = run {
+                push_sixel_run(&mut out, bits, count);
+            }
+            // Graphics carriage return: the next color repaints the same band.
+            out.push('$');
+        }
+        out.push('-');
+    }
+    out.push_str("\x1b\\");
+    out
+}
+
+fn push_sixel_run(out: &mut String, bits: u8, count: u32) {
+    let sixel = char::from(63 + bits);
+    if count > 3 {
+        out.push_str(&format!("!{count}{sixel}"));
+    } else {
+        for _ in 0..count {
+            out.push(sixel);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgba;
+    use image::RgbaImage;
+    use pretty_assertions::assert_eq;
+    use std::collections::HashMap;
+
+    fn detect_with(vars: &[(&str, &str)]) -> Option<GraphicsProtocol> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect();
+        detect(|name| vars.get(name).cloned())
+    }
+
+    #[test]
+    fn detects_protocol_from_terminal_environment() {
+        assert_eq!(
+            detect_with(&[("TERM", "xterm-kitty")]),
+            Some(GraphicsProtocol::Kitty)
+        );
+        assert_eq!(
+            detect_with(&[("TERM_PROGRAM", "iTerm.app")]),
+            Some(GraphicsProtocol::Iterm2)
+        );
+        assert_eq!(
+            detect_with(&[("TERM", "foot")]),
+            Some(GraphicsProtocol::Sixel)
+        );
+        assert_eq!(detect_with(&[("TERM", "xterm-256color")]), None);
+        assert_eq!(
+            detect_with(&[
+                ("TERM", "xterm-kitty"),
+                ("TMUX", "/tmp/tmux-1000/default,1,0")
+            ]),
+            None
+        );
+    }
+
+    #[test]
+    fn fit_keeps_aspect_ratio_and_never_upscales() {
+        // 800x400 px is 80x20 cells at 10x20 px per cell; halve it to fit 40 columns.
+        assert_eq!(fit_cells(800, 400, (10, 20), 40, 12), Some((40, 10)));
+        assert_eq!(fit_cells(30, 20, (10, 20), 40, 12), Some((3, 1)));
+        assert_eq!(fit_cells(800, 400, (10, 20), 40, 0), None);
+    }
+
+    #[test]
+    fn sixel_paints_each_band_per_color() {
+        let mut image = RgbaImage::new(2, 6);
+        for y in 0..6 {
+            image.put_pixel(0, y, Rgba([255, 0, 0, 255]));
+            image.put_pixel(1, y, Rgba([0, 0, 0, 0]));
+        }
+        let sequence = sixel_sequence(&DynamicImage::ImageRgba8(image));
+
+        assert!(sequence.starts_with("\x1bP0;1;0q\"1;1;2;6"));
+        // Red is color 180; column 0 is fully painted (`~`), column 1 is transparent (`?`).
+        assert!(sequence.ends_with("#180~?$-\x1b\\"));
+    }
+
+    #[test]
+    fn kitty_payload_is_chunked() {
+        let sequence = kitty_sequence(&vec![0u8; KITTY_CHUNK_BYTES], 4, 2);
+        assert!(sequence.starts_with("\x1b_Ga=T,f=100,q=2,C=1,c=4,r=2,m=1;"));
+        assert!(sequence.contains("\x1b_Gm=0;"));
+    }
+}
@@ -0,0 +1,666 @@
+//! Color themes for the TUI.
+//!
+//! A [`Theme`] assigns a [`Style`] to every themed element: diffs, markdown, the footer status
+//! line, collaboration mode indicators, selection popups (including approvals), transcript search
+//! matches, and the user message background. Renderers read the active theme through
+//! [`current`], which keeps the many free-standing render helpers (`diff_render`,
+//! `markdown_render`, ...) free of extra parameters.
+//!
+//! Four themes are built in. Users can add more as TOML files in `$CODEX_HOME/themes/`; the file
+//! stem is the id used by `tui.theme` and `/theme`. A user theme starts from a built-in `base`
+//! and replaces the styles it lists:
+//!
+//! ```toml
+//! name = "Solarized Light"
+//! base = "light"
+//!
+//! [styles]
+//! diff_add = { fg = "#859900" }
+//! markdown_h1 = { fg = "blue", bold = true, underlined = true }
+//! ```
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::sync::OnceLock;
+use std::sync::RwLock;
+
+use ratatui::style::Color;
+use ratatui::style::Modifier;
+use ratatui::style::Style;
+use ratatui::style::Stylize;
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::color::is_light;
+use crate::terminal_palette::best_color;
+use crate::terminal_palette::default_bg;
+
+/// Ids of the built-in themes, in picker order.
+pub(crate) const BUILT_IN_THEME_IDS: [&str; 4] = ["dark", "light", "high-contrast", "colorblind"];
+
+const THEMES_DIR: &str = "themes";
+
+#[derive(Debug, Error)]
+pub(crate) enum ThemeError {
+    #[error("unknown theme `{0}`")]
+    Unknown(String),
+    #[error("failed to read {}: {source}", path.display())]
+    Read {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("invalid theme {}: {message}", path.display())]
+    Invalid { path: PathBuf, message: String },
+}
+
+/// Styles for every themed element of the TUI.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Theme {
+    /// Identifier used by `tui.theme` (the file stem for user themes).
+    pub id: String,
+    /// Human-readable name shown in `/theme`.
+    pub name: String,
+
+    /// Highlighted row in selection popups, including approval prompts.
+    pub selection: Style,
+    /// Title line of approval prompts.
+    pub approval_title: Style,
+    /// Secondary text in the footer status line and the working indicator.
+    pub status_line: Style,
+    /// Footer indicator for Plan mode.
+    pub mode_plan: Style,
+    /// Footer indicator for Pair Programming mode.
+    pub mode_pair_programming: Style,
+    /// Footer indicator for Execute mode.
+    pub mode_execute: Style,
+    /// Positive status such as an enabled toggle.
+    pub success: Style,
+    /// Negative status such as a disabled toggle.
+    pub error: Style,
+    /// Background for user messages. When unset, it is derived from the terminal background.
+    pub user_message: Style,
+    /// Search matches in the transcript pager.
+    pub search_match: Style,
+
+    pub diff_add: Style,
+    pub diff_delete: Style,
+    pub diff_context: Style,
+    /// Line numbers in diffs.
+    pub diff_gutter: Style,
+
+    pub markdown_h1: Style,
+    pub markdown_h2: Style,
+    pub markdown_h3: Style,
+    pub markdown_h4: Style,
+    pub markdown_h5: Style,
+    pub markdown_h6: Style,
+    pub markdown_code: Style,
+    pub markdown_emphasis: Style,
+    pub markdown_strong: Style,
+    pub markdown_strikethrough: Style,
+    pub markdown_ordered_list_marker: Style,
+    pub markdown_unordered_list_marker: Style,
+    pub markdown_link: Style,
+    pub markdown_blockquote: Style,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
+
+impl Theme {
+    /// The historical TUI palette: ANSI colors that follow the terminal's own color scheme.
+    pub(crate) fn dark() -> Self {
+        Self {
+            id: "dark".to_string(),
+            name: "Dark".to_string(),
+            selection: Style::new().cyan().bold(),
+            approval_title: Style::new().bold(),
+            status_line: Style::new().dim(),
+            mode_plan: Style::new().magenta(),
+            mode_pair_programming: Style::new().cyan(),
+            mode_execute: Style::new().dim(),
+            success: Style::new().green(),
+            error: Style::new().red(),
+            user_message: Style::new(),
+            search_match: Style::new().reversed(),
+            diff_add: Style::new().fg(Color::Green),
+            diff_delete: Style::new().fg(Color::Red),
+            diff_context: Style::new(),
+            diff_gutter: Style::new().add_modifier(Modifier::DIM),
+            markdown_h1: Style::new().bold().underlined(),
+            markdown_h2: Style::new().bold(),
+            markdown_h3: Style::new().bold().italic(),
+            markdown_h4: Style::new().italic(),
+            markdown_h5: Style::new().italic(),
+            markdown_h6: Style::new().italic(),
+            markdown_code: Style::new().cyan(),
+            markdown_emphasis: Style::new().italic(),
+            markdown_strong: Style::new().bold(),
+            markdown_strikethrough: Style::new().crossed_out(),
+            markdown_ordered_list_marker: Style::new().light_blue(),
+            markdown_unordered_list_marker: Style::new(),
+            markdown_link: Style::new().cyan().underlined(),
+            markdown_blockquote: Style::new().green(),
+        }
+    }
+
+    /// Magenta and bold accents, which stay legible on light backgrounds where plain cyan washes
+    /// out.
+    pub(crate) fn light() -> Self {
+        Self {
+            id: "light".to_string(),
+            name: "Light".to_string(),
+            selection: Style::new().magenta().bold(),
+            mode_pair_programming: Style::new().cyan().bold(),
+            markdown_code: Style::new().magenta(),
+            markdown_ordered_list_marker: Style::new().dim(),
+            markdown_link: Style::new().magenta().underlined(),
+            ..Self::dark()
+        }
+    }
+
+    /// Bright, bold colors and no dimmed text.
+    pub(crate) fn high_contrast() -> Self {
+        Self {
+            id: "high-contrast".to_string(),
+            name: "High contrast".to_string(),
+            selection: Style::new().reversed().bold(),
+            status_line: Style::new(),
+            mode_plan: Style::new().light_magenta().bold(),
+            mode_pair_programming: Style::new().light_cyan().bold(),
+            mode_execute: Style::new().bold(),
+            success: Style::new().light_green().bold(),
+            error: Style::new().light_red().bold(),
+            diff_add: Style::new().light_green().bold(),
+            diff_delete: Style::new().light_red().bold(),
+            diff_gutter: Style::new(),
+            markdown_code: Style::new().light_cyan().bold(),
+            markdown_ordered_list_marker: Style::new().light_cyan().bold(),
+            markdown_link: Style::new().light_cyan().bold().underlined(),
+            markdown_blockquote: Style::new().light_green().italic(),
+            ..Self::dark()
+        }
+    }
+
+    /// Blue/orange instead of green/red so additions and deletions stay distinguishable with
+    /// red-green color vision deficiencies.
+    pub(crate) fn colorblind() -> Self {
+        // Okabe-Ito sky blue and orange, falling back to ANSI when truecolor/256 colors are
+        // unavailable.
+        let blue = rgb_or((0x56, 0xb4, 0xe9), Color::LightBlue);
+        let orange = rgb_or((0xe6, 0x9f, 0x00), Color::Yellow);
+        Self {
+            id: "colorblind".to_string(),
+            name: "Colorblind-safe".to_string(),
+            success: Style::new().fg(blue),
+            error: Style::new().fg(orange),
+            diff_add: Style::new().fg(blue),
+            diff_delete: Style::new().fg(orange),
+            markdown_blockquote: Style::new().fg(blue).italic(),
+            ..Self::dark()
+        }
+    }
+
+    pub(crate) fn built_in(id: &str) -> Option<Self> {
+        match id {
+            "dark" => Some(Self::dark()),
+            "light" => Some(Self::light()),
+            "high-contrast" => Some(Self::high_contrast()),
+            "colorblind" => Some(Self::colorblind()),
+            _ => None,
+        }
+    }
+
+    /// Parse a user theme. `id` is the file stem; `path` is only used in error messages.
+    pub(crate) fn from_toml(id: &str, contents: &str, path: &Path) -> Result<Self, ThemeError> {
+        let invalid = |message: String| ThemeError::Invalid {
+            path: path.to_path_buf(),
+            message,
+        };
+        if Self::built_in(id).is_some() {
+            return Err(invalid(format!("`{id}` is the name of a built-in theme")));
+        }
+        let file: ThemeToml = toml::from_str(contents).map_err(|e| invalid(e.to_string()))?;
+        let base = file.base.as_deref().unwrap_or("dark");
+        let mut theme = Self::built_in(base).ok_or_else(|| {
+            invalid(format!(
+                "unknown base `{base}`; expected one of {}",
+                BUILT_IN_THEME_IDS.join(", ")
+            ))
+        })?;
+        theme.id = id.to_string();
+        theme.name = file.name.unwrap_or_else(|| id.to_string());
+        file.styles.apply(&mut theme).map_err(invalid)?;
+        Ok(theme)
+    }
+}
+
+fn rgb_or(target: (u8, u8, u8), fallback: Color) -> Color {
+    match best_color(target) {
+        Color::Reset => fallback,
+        color => color,
+    }
+}
+
+/// Load the theme with the given id, built-in or from `$CODEX_HOME/themes/<id>.toml`.
+pub(crate) fn resolve(id: &str, codex_home: &Path) -> Result<Theme, ThemeError> {
+    if let Some(theme) = Theme::built_in(id) {
+        return Ok(theme);
+    }
+    match load_user_theme(id, codex_home) {
+        Err(ThemeError::Read { source, .. }) if source.kind() == std::io::ErrorKind::NotFound => {
+            Err(ThemeError::Unknown(id.to_string()))
+        }
+        result => result,
+    }
+}
+
+fn load_user_theme(id: &str, codex_home: &Path) -> Result<Theme, ThemeError> {
+    let path = codex_home.join(THEMES_DIR).join(format!("{id}.toml"));
+    match std::fs::read_to_string(&path) {
+        Ok(contents) => Theme::from_toml(id, &contents, &path),
+        Err(source) => Err(ThemeError::Read { path, source }),
+    }
+}
+
+/// One row of the `/theme` picker: a theme id and either the theme or why it failed to load.
+pub(crate) struct ThemeEntry {
+    pub id: String,
+    pub theme: Result<Theme, ThemeError>,
+}
+
+/// Built-in themes followed by the user themes in `$CODEX_HOME/themes/`, sorted by id.
+///
+/// Broken user themes are returned as errors rather than skipped so the picker can explain them.
+pub(crate) fn available_themes(codex_home: &Path) -> Vec<ThemeEntry> {
+    let mut entries: Vec<ThemeEntry> = BUILT_IN_THEME_IDS
+        .iter()
+        .filter_map(|id| Theme::built_in(id))
+        .map(|theme| ThemeEntry {
+            id: theme.id.clone(),
+            theme: Ok(theme),
+        })
+        .collect();
+
+    let Ok(dir) = std::fs::read_dir(codex_home.join(THEMES_DIR)) else {
+        return entries;
+    };
+    let mut user_ids: Vec<String> = dir
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+        .filter_map(|path| path.file_stem()?.to_str().map(str::to_string))
+        .collect();
+    user_ids.sort();
+    entries.extend(user_ids.into_iter().map(|id| ThemeEntry {
+        theme: load_user_theme(&id, codex_home),
+        id,
+    }));
+    entries
+}
+
+/// Activate the theme named by `tui.theme`, or pick dark/light from the terminal background.
+///
+/// An unusable configured theme is logged and replaced by the background default so a typo in
+/// `config.toml` never prevents the TUI from starting.
+pub(crate) fn init(configured: Option<&str>, codex_home: &Path) {
+    let theme = match configured {
+        Some(id) => resolve(id, codex_home).unwrap_or_else(|err| {
+            tracing::warn!("{err}; using the default theme");
+            default_for_terminal()
+        }),
+        None => default_for_terminal(),
+    };
+    set_current(theme);
+}
+
+fn default_for_terminal() -> Theme {
+    if default_bg().is_some_and(is_light) {
+        Theme::light()
+    } else {
+        Theme::dark()
+    }
+}
+
+fn active() -> &'static RwLock<Arc<Theme>> {
+    static ACTIVE: OnceLock<RwLock<Arc<Theme>>> = OnceLock::new();
+    ACTIVE.get_or_init(|| RwLock::new(Arc::new(Theme::dark())))
+}
+
+/// The active theme.
+pub(crate) fn current() -> Arc<Theme> {
+    match active().read() {
+        Ok(theme) => Arc::clone(&theme),
+        Err(poisoned) => Arc::clone(&poisoned.into_inner()),
+    }
+}
+
+/// Replace the active theme. Content rendered from now on uses it; lines already written to the
+/// terminal scrollback keep their colors.
+pub(crate) fn set_current(theme: Theme) {
+    let theme = Arc::new(theme);
+    match active().write() {
+        Ok(mut slot) => *slot = theme,
+        Err(poisoned) => *poisoned.into_inner() = theme,
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ThemeToml {
+    name: Option<String>,
+    base: Option<String>,
+    #[serde(default)]
+    styles: ThemeStylesToml,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+struct ThemeStylesToml {
+    selection: Option<StyleToml>,
+    approval_title: Option<StyleToml>,
+    status_line: Option<StyleToml>,
+    mode_plan: Option<StyleToml>,
+    mode_pair_programming: Option<StyleToml>,
+    mode_execute: Option<StyleToml>,
+    success: Option<StyleToml>,
+    error: Option<StyleToml>,
+    user_message: Option<StyleToml>,
+    search_match: Option<StyleToml>,
+    diff_add: Option<StyleToml>,
+    diff_delete: Option<StyleToml>,
+    diff_context: Option<StyleToml>,
+    diff_gutter: Option<StyleToml>,
+    markdown_h1: Option<StyleToml>,
+    markdown_h2: Option<StyleToml>,
+    markdown_h3: Option<StyleToml>,
+    markdown_h4: Option<StyleToml>,
+    markdown_h5: Option<StyleToml>,
+    markdown_h6: Option<StyleToml>,
+    markdown_code: Option<StyleToml>,
+    markdown_emphasis: Option<StyleToml>,
+    markdown_strong: Option<StyleToml>,
+    markdown_strikethrough: Option<StyleToml>,
+    markdown_ordered_list_marker: Option<StyleToml>,
+    markdown_unordered_list_marker: Option<StyleToml>,
+    markdown_link: Option<StyleToml>,
+    markdown_blockquote: Option<StyleToml>,
+}
+
+impl ThemeStylesToml {
+    fn apply(self, theme: &mut Theme) -> Result<(), String> {
+        let Self {
+            selection,
+            approval_title,
+            status_line,
+            mode_plan,
+            mode_pair_programming,
+            mode_execute,
+            success,
+            error,
+            user_message,
+            search_match,
+            diff_add,
+            diff_delete,
+            diff_context,
+            diff_gutter,
+            markdown_h1,
+            markdown_h2,
+            markdown_h3,
+            markdown_h4,
+            markdown_h5,
+            markdown_h6,
+            markdown_code,
+            markdown_emphasis,
+            markdown_strong,
+            markdown_strikethrough,
+            markdown_ordered_list_marker,
+            markdown_unordered_list_marker,
+            markdown_link,
+            markdown_blockquote,
+        } = self;
+        let overrides = [
+            ("selection", selection, &mut theme.selection),
+            ("approval_title", approval_title, &mut theme.approval_title),
+            ("status_line", status_line, &mut theme.status_line),
+            ("mode_plan", mode_plan, &mut theme.mode_plan),
+            (
+                "mode_pair_programming",
+                mode_pair_programming,
+                &mut theme.mode_pair_programming,
+            ),
+            ("mode_execute", mode_execute, &mut theme.mode_execute),
+            ("success", success, &mut theme.success),
+            ("error", error, &mut theme.error),
+            ("user_message", user_message, &mut theme.user_message),
+            ("search_match", search_match, &mut theme.search_match),
+            ("diff_add", diff_add, &mut theme.diff_add),
+            ("diff_delete", diff_delete, &mut theme.diff_delete),
+            ("diff_context", diff_context, &mut theme.diff_context),
+            ("diff_gutter", diff_gutter, &mut theme.diff_gutter),
+            ("markdown_h1", markdown_h1, &mut theme.markdown_h1),
+            ("markdown_h2", markdown_h2, &mut theme.markdown_h2),
+            ("markdown_h3", markdown_h3, &mut theme.markdown_h3),
+            ("markdown_h4", markdown_h4, &mut theme.markdown_h4),
+            ("markdown_h5", markdown_h5, &mut theme.markdown_h5),
+            ("markdown_h6", markdown_h6, &mut theme.markdown_h6),
+            ("markdown_code", markdown_code, &mut theme.markdown_code),
+            (
+                "markdown_emphasis",
+                markdown_emphasis,
+                &mut theme.markdown_emphasis,
+            ),
+            (
+                "markdown_strong",
+                markdown_strong,
+                &mut theme.markdown_strong,
+            ),
+            (
+                "markdown_strikethrough",
+                markdown_strikethrough,
+                &mut theme.markdown_strikethrough,
+            ),
+            (
+                "markdown_ordered_list_marker",
+                markdown_ordered_list_marker,
+                &mut theme.markdown_ordered_list_marker,
+            ),
+            (
+                "markdown_unordered_list_marker",
+                markdown_unordered_list_marker,
+                &mut theme.markdown_unordered_list_marker,
+            ),
+            ("markdown_link", markdown_link, &mut theme.markdown_link),
+            (
+                "markdown_blockquote",
+                markdown_blockquote,
+                &mut theme.markdown_blockquote,
+            ),
+        ];
+        for (key, style, slot) in overrides {
+            if let Some(style) = style {
+                *slot = style
+                    .to_style()
+                    .map_err(|message| format!("styles.{key}: {message}"))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A style entry in a theme file. Each entry replaces the base theme's style for that element.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct StyleToml {
+    fg: Option<String>,
+    bg: Option<String>,
+    #[serde(default)]
+    bold: bool,
+    #[serde(default)]
+    dim: bool,
+    #[serde(default)]
+    italic: bool,
+    #[serde(default)]
+    underlined: bool,
+    #[serde(default)]
+    reversed: bool,
+    #[serde(default)]
+    crossed_out: bool,
+}
+
+impl StyleToml {
+    fn to_style(&self) -> Result<Style, String> {
+        let mut style = Style::new();
+        if let Some(fg) = &self.fg {
+            style = style.fg(parse_color(fg)?);
+        }
+        if let Some(bg) = &self.bg {
+            style = style.bg(parse_color(bg)?);
+        }
+        let modifiers = [
+            (self.bold, Modifier::BOLD),
+            (self.dim, Modifier::DIM),
+            (self.italic, Modifier::ITALIC),
+            (self.underlined, Modifier::UNDERLINED),
+            (self.reversed, Modifier::REVERSED),
+            (self.crossed_out, Modifier::CROSSED_OUT),
+        ];
+        for (enabled, modifier) in modifiers {
+            if enabled {
+                style = style.add_modifier(modifier);
+            }
+        }
+        Ok(style)
+    }
+}
+
+/// Parse an ANSI color name (`red`, `light-blue`, `reset`), a 256-color index, or `#rrggbb`.
+/// RGB colors are mapped to the nearest color the terminal supports.
+fn parse_color(value: &str) -> Result<Color, String> {
+    match Color::from_str(value) {
+        Ok(Color::Rgb(r, g, b)) => Ok(best_color((r, g, b))),
+        Ok(color) => Ok(color),
+        Err(_) => Err(format!(
+            "invalid color `{value}`; use an ANSI color name, a 256-color index, or #rrggbb"
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    #[test]
+    fn built_in_ids_resolve_to_matching_themes() {
+        for id in BUILT_IN_THEME_IDS {
+            let theme = Theme::built_in(id).expect("built-in theme");
+            assert_eq!(theme.id, id);
+        }
+        assert_eq!(Theme::built_in("solarized"), None);
+    }
+
+    #[test]
+    fn colorblind_theme_avoids_red_green_diffs() {
+        let theme = Theme::colorblind();
+        for style in [theme.diff_add, theme.diff_delete] {
+            assert!(!matches!(
+                style.fg,
+                Some(Color::Red | Color::Green | Color::LightRed | Color::LightGreen)
+            ));
+        }
+        assert_ne!(theme.diff_add.fg, theme.diff_delete.fg);
+    }
+
+    #[test]
+    fn user_theme_overrides_base_styles() {
+        let theme = Theme::from_toml(
+            "mine",
+            r##"
+name = "Mine"
+base = "light"
+
+[styles]
+diff_add = { fg = "light-blue", bold = true }
+markdown_code = { fg = "magenta", bg = "black", italic = true }
+"##,
+            Path::new("mine.toml"),
+        )
+        .expect("valid theme");
+
+        assert_eq!(
+            theme,
+            Theme {
+                id: "mine".to_string(),
+                name: "Mine".to_string(),
+                diff_add: Style::new().fg(Color::LightBlue).bold(),
+                markdown_code: Style::new().fg(Color::Magenta).bg(Color::Black).italic(),
+                ..Theme::light()
+            }
+        );
+    }
+
+    #[test]
+    fn user_theme_errors_name_the_problem() {
+        let path = Path::new("bad.toml");
+        let cases = [
+            (
+                "[styles]\ndiff_plus = { fg = \"red\" }\n",
+                "unknown field `diff_plus`",
+            ),
+            (
+                "[styles]\ndiff_add = { fg = \"redish\" }\n",
+                "styles.diff_add: invalid color `redish`",
+            ),
+            ("base = \"solarized\"\n", "unknown base `solarized`"),
+        ];
+        for (contents, expected) in cases {
+            let err = Theme::from_toml("bad", contents, path)
+                .expect_err("theme should be rejected")
+                .to_string();
+            assert!(
+                err.contains(expected),
+                "{err:?} should contain {expected:?}"
+            );
+        }
+        let err = Theme::from_toml("dark", "", path).expect_err("shadowing is rejected");
+        assert!(err.to_string().contains("built-in theme"));
+    }
+
+    #[test]
+    fn available_themes_lists_built_ins_then_user_files() {
+        let codex_home = TempDir::new().expect("tempdir");
+        let dir = codex_home.path().join(THEMES_DIR);
+        std::fs::create_dir_all(&dir).expect("themes dir");
+        std::fs::write(dir.join("zebra.toml"), "name = \"Zebra\"\n").expect("write");
+        std::fs::write(dir.join("broken.toml"), "styles = 3\n").expect("write");
+        std::fs::write(dir.join("notes.txt"), "ignored").expect("write");
+
+        let entries = available_themes(codex_home.path());
+        let summary: Vec<(String, bool)> = entries
+            .iter()
+            .map(|entry| (entry.id.clone(), entry.theme.is_ok()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("dark".to_string(), true),
+                ("light".to_string(), true),
+                ("high-contrast".to_string(), true),
+                ("colorblind".to_string(), true),
+                ("broken".to_string(), false),
+                ("zebra".to_string(), true),
+            ]
+        );
+        assert!(matches!(
+            resolve("missing", codex_home.path()),
+            Err(ThemeError::Unknown(_))
+        ));
+    }
+}
@@ -14,6 +14,9 @@ use std::time::Duration;
 
 use crossterm::Command;
 use crossterm::SynchronizedUpdate;
+use crossterm::cursor::MoveTo;
+use crossterm::cursor::RestorePosition;
+use crossterm::cursor::SavePosition;
 use crossterm::event::DisableBracketedPaste;
 use crossterm::event::DisableFocusChange;
 use crossterm::event::EnableBracketedPaste;
@@ -22,6 +25,7 @@ use crossterm::event::KeyEvent;
 use crossterm::event::KeyboardEnhancementFlags;
 use crossterm::event::PopKeyboardEnhancementFlags;
 use crossterm::event::PushKeyboardEnhancementFlags;
+use crossterm::style::Print;
 use crossterm::terminal::EnterAlternateScreen;
 use crossterm::terminal::LeaveAlternateScreen;
 use crossterm::terminal::supports_keyboard_enhancement;
@@ -39,8 +43,12 @@ use tokio_stream::Stream;
 pub use self::frame_requester::FrameRequester;
 use crate::custom_terminal;
 use crate::custom_terminal::Terminal as CustomTerminal;
+use crate::notifications::DesktopNotification;
 use crate::notifications::DesktopNotificationBackend;
 use crate::notifications::detect_backend;
+use crate::terminal_graphics;
+use crate::terminal_graphics::GraphicsProtocol;
+use crate::terminal_graphics::InlineImage;
 use crate::tui::event_stream::EventBroker;
 use crate::tui::event_stream::TuiEventStream;
 #[cfg(unix)]
@@ -244,6 +252,8 @@ pub struct Tui {
     event_broker: Arc<EventBroker>,
     pub(crate) terminal: Terminal,
     pending_history_lines: Vec<Line<'static>>,
+    /// Thumbnails to insert once this many pending history lines have been written.
+    pending_history_images: Vec<(usize, InlineImage)>,
     alt_saved_viewport: Option<ratatui::layout::Rect>,
     #[cfg(unix)]
     suspend_context: SuspendContext,
@@ -275,6 +285,7 @@ impl Tui {
             event_broker: Arc::new(EventBroker::new()),
             terminal,
             pending_history_lines: vec![],
+            pending_history_images: vec![],
             alt_saved_viewport: None,
             #[cfg(unix)]
             suspend_context: SuspendContext::new(),
@@ -359,7 +370,7 @@ impl Tui {
 
     /// Emit a desktop notification now if the terminal is unfocused.
     /// Returns true if a notification was posted.
-    pub fn notify(&mut self, message: impl AsRef<str>) -> bool {
+    pub fn notify(&mut self, notification: &DesktopNotification) -> bool {
         if self.terminal_focused.load(Ordering::Relaxed) {
             return false;
         }
@@ -368,8 +379,7 @@ impl Tui {
             return false;
         };
 
-        let message = message.as_ref().to_string();
-        match backend.notify(&message) {
+        match backend.notify(notification) {
             Ok(()) => true,
             Err(err) => {
                 let method = backend.method();
@@ -445,6 +455,34 @@ impl Tui {
         self.frame_requester().schedule_frame();
     }
 
+    /// Write a graphics-protocol `sequence` with its top-left corner at `(x, y)`. Ratatui does
+    /// not track it, so callers draw it again after repainting the cells underneath.
+    pub(crate) fn draw_graphics(&mut self, x: u16, y: u16, sequence: &str) -> Result<()> {
+        execute!(
+            self.terminal.backend_mut(),
+            SavePosition,
+            MoveTo(x, y),
+            Print(sequence),
+            RestorePosition
+        )
+    }
+
+    /// Remove images that outlive the cells they were drawn over (kitty's placements).
+    pub(crate) fn clear_graphics(&mut self, protocol: GraphicsProtocol) -> Result<()> {
+        match terminal_graphics::clear_sequence(protocol) {
+            Some(sequence) => execute!(self.terminal.backend_mut(), Print(sequence)),
+            None => Ok(()),
+        }
+    }
+
+    /// Queue a thumbnail after the history lines queued so far. Dropped at draw time when the
+    /// terminal has no graphics protocol.
+    pub(crate) fn insert_history_image(&mut self, image: InlineImage) {
+        self.pending_history_images
+            .push((self.pending_history_lines.len(), image));
+        self.frame_requester().schedule_frame();
+    }
+
     pub fn draw(
         &mut self,
         height: u16,
@@ -491,6 +529,22 @@ impl Tui {
                 terminal.set_viewport_area(area);
             }
 
+            if !self.pending_history_images.is_empty() {
+                let mut lines = std::mem::take(&mut self.pending_history_lines);
+                let mut written = 0;
+                for (at, image) in std::mem::take(&mut self.pending_history_images) {
+                    let rest = lines.split_off(at - written);
+                    written = at;
+                    if !lines.is_empty() {
+                        crate::insert_history::insert_history_lines(terminal, lines)?;
+                    }
+                    lines = rest;
+                    if let Some(protocol) = terminal_graphics::protocol() {
+                        crate::insert_history::insert_history_image(terminal, &image, protocol)?;
+                    }
+                }
+                self.pending_history_lines = lines;
+            }
             if !self.pending_history_lines.is_empty() {
                 crate::insert_history::insert_history_lines(
                     terminal,
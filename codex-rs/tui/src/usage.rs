@@ -0,0 +1,90 @@
+//! `/usage`: token usage and estimated cost recorded across sessions, shown in a pager.
+
+use chrono::DateTime;
+use chrono::Datelike;
+use chrono::Duration;
+use chrono::Local;
+use chrono::NaiveTime;
+use chrono::TimeZone;
+use chrono::Utc;
+use codex_core::usage::UsageGroupBy;
+use codex_core::usage::UsageReport;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+
+const DAYS_SHOWN: i64 = 30;
+
+/// Start of the period `/usage` reports for a grouping: the last 30 days by day, everything by
+/// month, and the current month otherwise.
+pub(crate) fn period_start(group_by: UsageGroupBy, now: DateTime<Local>) -> Option<DateTime<Utc>> {
+    match group_by {
+        UsageGroupBy::Day => Some((now - Duration::days(DAYS_SHOWN)).with_timezone(&Utc)),
+        UsageGroupBy::Month => None,
+        UsageGroupBy::Repo | UsageGroupBy::Model | UsageGroupBy::Thread => {
+            let first = now.date_naive().with_day(1)?.and_time(NaiveTime::MIN);
+            Local
+                .from_local_datetime(&first)
+                .earliest()
+                .map(|start| start.with_timezone(&Utc))
+        }
+    }
+}
+
+fn period_label(group_by: UsageGroupBy) -> String {
+    match group_by {
+        UsageGroupBy::Day => format!("last {DAYS_SHOWN} days"),
+        UsageGroupBy::Month => "all time".to_string(),
+        UsageGroupBy::Repo | UsageGroupBy::Model | UsageGroupBy::Thread => "this month".to_string(),
+    }
+}
+
+pub(crate) fn pager_lines(report: &UsageReport) -> Vec<Line<'static>> {
+    let group = report.group_by.as_str();
+    let mut lines: Vec<Line<'static>> = vec![
+        format!("Token usage by {group}, {}", period_label(report.group_by))
+            .bold()
+            .into(),
+        "".into(),
+    ];
+    if report.rows.is_empty() {
+        lines.push("No usage recorded for this period.".italic().into());
+    } else {
+        let mut table = report.table_lines().into_iter();
+        if let Some(header) = table.next() {
+            lines.push(header.dim().into());
+        }
+        lines.extend(table.map(Line::from));
+    }
+    lines.push("".into());
+    lines.push(
+        "Group with /usage day, month, repo, model or thread. Export with `codex usage --format csv` or `--format json`."
+            .dim()
+            .into(),
+    );
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn period_start_uses_the_current_month_for_breakdowns() {
+        let now = Local
+            .with_ymd_and_hms(2026, 10, 18, 15, 30, 0)
+            .single()
+            .expect("valid local time");
+        let month_start = Local
+            .with_ymd_and_hms(2026, 10, 1, 0, 0, 0)
+            .single()
+            .expect("valid local time")
+            .with_timezone(&Utc);
+        assert_eq!(period_start(UsageGroupBy::Repo, now), Some(month_start));
+        assert_eq!(period_start(UsageGroupBy::Month, now), None);
+        assert_eq!(
+            period_start(UsageGroupBy::Day, now),
+            Some((now - Duration::days(30)).with_timezone(&Utc))
+        );
+    }
+}
@@ -622,6 +622,7 @@ impl ChatWidget {
             cwd: None,
             approval_policy: None,
             sandbox_policy: None,
+            sandbox_profile: None,
             windows_sandbox_level: None,
             model: None,
             subagent_model: None,
@@ -2598,6 +2599,7 @@ impl ChatWidget {
             EventMsg::CollabCloseEnd(ev) => self.on_collab_event(collab::close_end(ev)),
             EventMsg::RawResponseItem(_)
             | EventMsg::ThreadRolledBack(_)
+            | EventMsg::ProjectDocLoaded(_)
             | EventMsg::ItemStarted(_)
             | EventMsg::ItemCompleted(_)
             | EventMsg::AgentMessageContentDelta(_)
@@ -2854,6 +2856,7 @@ impl ChatWidget {
                 cwd: None,
                 approval_policy: None,
                 sandbox_policy: None,
+                sandbox_profile: None,
                 model: Some(switch_model.clone()),
                 subagent_model: None,
                 subagent_effort: None,
@@ -3139,6 +3142,7 @@ impl ChatWidget {
                 cwd: None,
                 approval_policy: None,
                 sandbox_policy: None,
+                sandbox_profile: None,
                 model: Some(model_for_action.clone()),
                 subagent_model: None,
                 subagent_effort: None,
@@ -3318,6 +3322,7 @@ impl ChatWidget {
                 cwd: None,
                 approval_policy: None,
                 sandbox_policy: None,
+                sandbox_profile: None,
                 model: Some(model.clone()),
                 subagent_model: None,
                 subagent_effort: None,
@@ -3490,6 +3495,7 @@ impl ChatWidget {
                 cwd: None,
                 approval_policy: Some(approval),
                 sandbox_policy: Some(sandbox_clone.clone()),
+                sandbox_profile: None,
                 model: None,
                 subagent_model: None,
                 subagent_effort: None,
@@ -3517,7 +3523,7 @@ impl ChatWidget {
         }
         matches!(
             (&preset.sandbox, current_sandbox),
-            (SandboxPolicy::ReadOnly, SandboxPolicy::ReadOnly)
+            (SandboxPolicy::ReadOnly { .. }, SandboxPolicy::ReadOnly { .. })
                 | (
                     SandboxPolicy::DangerFullAccess,
                     SandboxPolicy::DangerFullAccess
@@ -3638,7 +3644,7 @@ impl ChatWidget {
         let mut header_children: Vec<Box<dyn Renderable>> = Vec::new();
         let describe_policy = |policy: &SandboxPolicy| match policy {
             SandboxPolicy::WorkspaceWrite { .. } => "Agent mode",
-            SandboxPolicy::ReadOnly => "Read-Only mode",
+            SandboxPolicy::ReadOnly { .. } => "Read-Only mode",
             _ => "Agent mode",
         };
         let mode_label = preset
@@ -4018,7 +4024,7 @@ impl ChatWidget {
     /// Set the sandbox policy in the widget's config copy.
     pub(crate) fn set_sandbox_policy(&mut self, policy: SandboxPolicy) -> ConstraintResult<()> {
         #[cfg(target_os = "windows")]
-        let should_clear_downgrade = !matches!(&policy, SandboxPolicy::ReadOnly)
+        let should_clear_downgrade = !matches!(&policy, SandboxPolicy::ReadOnly { .. })
             || codex_core::get_platform_sandbox().is_some();
 
         self.config.sandbox_policy.set(policy)?;
@@ -4055,6 +4061,7 @@ impl ChatWidget {
                     cwd: None,
                     approval_policy: None,
                     sandbox_policy: None,
+                    sandbox_profile: None,
                     windows_sandbox_level: None,
                     model: None,
                     subagent_model: None,
@@ -4260,6 +4267,7 @@ impl ChatWidget {
                 cwd: None,
                 approval_policy: None,
                 sandbox_policy: None,
+                sandbox_profile: None,
                 windows_sandbox_level: None,
                 model: None,
                 subagent_model: None,
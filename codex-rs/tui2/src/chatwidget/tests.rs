@@ -62,9 +62,9 @@ use codex_protocol::account::PlanType;
 use codex_protocol::config_types::CollaborationMode;
 use codex_protocol::config_types::ModeKind;
 use codex_protocol::config_types::Settings;
-use codex_protocol::openai_models::default_input_modalities;
 use codex_protocol::openai_models::ModelPreset;
 use codex_protocol::openai_models::ReasoningEffortPreset;
+use codex_protocol::openai_models::default_input_modalities;
 use codex_protocol::parse_command::ParsedCommand;
 use codex_protocol::plan_tool::PlanItemArg;
 use codex_protocol::plan_tool::StepStatus;
@@ -129,7 +129,7 @@ async fn resumed_initial_messages_render_history() {
         model: "test-model".to_string(),
         model_provider_id: "test-provider".to_string(),
         approval_policy: AskForApproval::Never,
-        sandbox_policy: SandboxPolicy::ReadOnly,
+        sandbox_policy: SandboxPolicy::new_read_only_policy(),
         cwd: PathBuf::from("/home/user/project"),
         reasoning_effort: Some(ReasoningEffortConfig::default()),
         history_log_id: 0,
@@ -179,7 +179,9 @@ async fn resumed_initial_messages_render_history() {
 async fn prompt_suggestion_autorun_when_enabled() {
     let (mut chat, _rx, _op_rx) = make_chatwidget_manual(None).await;
     chat.config.features.enable(Feature::PromptSuggestions);
-    chat.config.features.enable(Feature::PromptSuggestionsAutorun);
+    chat.config
+        .features
+        .enable(Feature::PromptSuggestionsAutorun);
     chat.last_completed_turn_id = Some("turn-1".to_string());
 
     let event = PromptSuggestionEvent {
@@ -204,7 +206,9 @@ async fn prompt_suggestion_autorun_when_enabled() {
 async fn prompt_suggestion_autorun_clears_intent() {
     let (mut chat, _rx, _op_rx) = make_chatwidget_manual(None).await;
     chat.config.features.enable(Feature::PromptSuggestions);
-    chat.config.features.enable(Feature::PromptSuggestionsAutorun);
+    chat.config
+        .features
+        .enable(Feature::PromptSuggestionsAutorun);
     chat.prompt_suggestions_intent = true;
     chat.last_completed_turn_id = Some("turn-1".to_string());
 
@@ -231,13 +235,12 @@ async fn prompt_suggestion_autorun_clears_intent() {
 async fn prompt_suggestion_intent_clears_on_input() {
     let (mut chat, _rx, _op_rx) = make_chatwidget_manual(None).await;
     chat.config.features.enable(Feature::PromptSuggestions);
-    chat.config.features.enable(Feature::PromptSuggestionsAutorun);
+    chat.config
+        .features
+        .enable(Feature::PromptSuggestionsAutorun);
     chat.prompt_suggestions_intent = true;
 
-    chat.handle_key_event(KeyEvent::new(
-        KeyCode::Char('a'),
-        KeyModifiers::NONE,
-    ));
+    chat.handle_key_event(KeyEvent::new(KeyCode::Char('a'), KeyModifiers::NONE));
     assert!(!chat.prompt_suggestions_intent);
 }
 
@@ -245,7 +248,9 @@ async fn prompt_suggestion_intent_clears_on_input() {
 async fn prompt_suggestion_intent_clears_on_multiline_paste() {
     let (mut chat, _rx, _op_rx) = make_chatwidget_manual(None).await;
     chat.config.features.enable(Feature::PromptSuggestions);
-    chat.config.features.enable(Feature::PromptSuggestionsAutorun);
+    chat.config
+        .features
+        .enable(Feature::PromptSuggestionsAutorun);
     chat.last_completed_turn_id = Some("turn-1".to_string());
 
     open_prompt_suggestions_via_command(&mut chat);
@@ -2009,8 +2014,10 @@ async fn preset_matching_ignores_extra_writable_roots() {
     let current_sandbox = SandboxPolicy::WorkspaceWrite {
         writable_roots: vec![AbsolutePathBuf::try_from("C:\\extra").unwrap()],
         network_access: false,
+        allowed_loopback_ports: Vec::new(),
         exclude_tmpdir_env_var: false,
         exclude_slash_tmp: false,
+        deny_read: Vec::new(),
     };
 
     assert!(
@@ -3629,8 +3636,7 @@ async fn collab_mode_cycle_auto_switch_blocks_with_composer_text() {
     chat.handle_key_event(KeyEvent::from(KeyCode::BackTab));
     assert_eq!(chat.active_collaboration_mode_kind(), ModeKind::Default);
 
-    chat.bottom_pane
-        .set_composer_text("draft".to_string());
+    chat.bottom_pane.set_composer_text("draft".to_string());
     chat.on_task_complete(Some("Done".to_string()), false);
     assert_eq!(chat.active_collaboration_mode_kind(), ModeKind::Default);
 
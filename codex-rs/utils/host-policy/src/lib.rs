@@ -0,0 +1,17 @@
+//! Host classification and domain pattern matching shared by
+//! `codex-network-proxy` and the client-side network tools in `codex-core`,
+//! so both decide the same way which hosts a policy allows and which count
+//! as local.
+
+mod host;
+mod pattern;
+
+pub use host::is_blocked_local_host;
+pub use host::is_explicit_local_allowlisted;
+pub use host::is_local_literal;
+pub use host::is_loopback_host;
+pub use host::is_non_public_ip;
+pub use host::normalize_host;
+pub use host::resolve_host;
+pub use pattern::DomainPattern;
+pub use pattern::compile_globset;
@@ -23,16 +23,42 @@ pub enum ImageProcessingError {
         #[source]
         source: image::ImageError,
     },
+    #[cfg(feature = "documents")]
+    #[error("failed to read PDF at {path}: {reason}")]
+    Pdf { path: PathBuf, reason: &'static str },
+    #[cfg(feature = "documents")]
+    #[error("failed to render SVG at {path}: {source}")]
+    Svg {
+        path: PathBuf,
+        #[source]
+        source: resvg::usvg::Error,
+    },
+    #[error("{path} has {page_count} page(s); page {page} does not exist")]
+    PageOutOfRange {
+        path: PathBuf,
+        page: u32,
+        page_count: u32,
+    },
+    #[error("invalid page range `{0}`; expected a page number like `3` or a range like `2-5`")]
+    InvalidPageRange(String),
+    #[error("region {region} lies outside the {width}x{height} image")]
+    RegionOutOfBounds {
+        region: String,
+        width: u32,
+        height: u32,
+    },
 }
 
 impl ImageProcessingError {
     pub fn is_invalid_image(&self) -> bool {
-        matches!(
-            self,
+        match self {
             ImageProcessingError::Decode {
                 source: ImageError::Decoding(_),
                 ..
-            }
-        )
+            } => true,
+            #[cfg(feature = "documents")]
+            ImageProcessingError::Pdf { .. } | ImageProcessingError::Svg { .. } => true,
+            _ => false,
+        }
     }
 }
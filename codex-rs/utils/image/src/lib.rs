@@ -1,5 +1,9 @@
+use std::fmt;
+use std::io::Cursor;
 use std::num::NonZeroUsize;
 use std::path::Path;
+use std::path::PathBuf;
+use std::str::FromStr;
 use std::sync::LazyLock;
 
 use crate::error::ImageProcessingError;
@@ -12,6 +16,7 @@ use image::DynamicImage;
 use image::GenericImageView;
 use image::ImageEncoder;
 use image::ImageFormat;
+use image::ImageReader;
 use image::codecs::jpeg::JpegEncoder;
 use image::codecs::png::PngEncoder;
 use image::imageops::FilterType;
@@ -20,7 +25,19 @@ pub const MAX_WIDTH: u32 = 2048;
 /// Maximum height used when resizing images before uploading.
 pub const MAX_HEIGHT: u32 = 768;
 
+/// Most pages attached from one PDF.
+pub const MAX_PDF_PAGES: u32 = 10;
+/// Images that would shrink by more than this factor to fit are split into
+/// tiles instead.
+const MAX_DOWNSCALE: f32 = 3.0;
+/// Most tiles produced for one image; larger images are downscaled whole.
+const MAX_TILES: u32 = 8;
+
 pub mod error;
+#[cfg(feature = "documents")]
+mod pdf;
+#[cfg(feature = "documents")]
+mod svg;
 
 #[derive(Debug, Clone)]
 pub struct EncodedImage {
@@ -40,10 +57,341 @@ impl EncodedImage {
 static IMAGE_CACHE: LazyLock<BlockingLruCache<[u8; 20], EncodedImage>> =
     LazyLock::new(|| BlockingLruCache::new(NonZeroUsize::new(32).unwrap_or(NonZeroUsize::MIN)));
 
+/// 1-based, inclusive range of PDF pages, parsed from `3` or `2-5`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PageRange {
+    pub first: u32,
+    pub last: u32,
+}
+
+impl FromStr for PageRange {
+    type Err = ImageProcessingError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || ImageProcessingError::InvalidPageRange(s.to_string());
+        let parse = |page: &str| page.trim().parse::<u32>().map_err(|_| invalid());
+        let (first, last) = match s.split_once('-') {
+            Some((first, last)) => (parse(first)?, parse(last)?),
+            None => {
+                let page = parse(s)?;
+                (page, page)
+            }
+        };
+        if first == 0 || last < first {
+            return Err(invalid());
+        }
+        Ok(Self { first, last })
+    }
+}
+
+/// Rectangle in pixels of the full image or rendered page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ImageRegion {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl ImageRegion {
+    /// Clips the region to a `width` x `height` image, or `None` when they
+    /// do not overlap.
+    fn clip_to(self, width: u32, height: u32) -> Option<Self> {
+        if self.x >= width || self.y >= height || self.width == 0 || self.height == 0 {
+            return None;
+        }
+        Some(Self {
+            x: self.x,
+            y: self.y,
+            width: self.width.min(width - self.x),
+            height: self.height.min(height - self.y),
+        })
+    }
+}
+
+impl fmt::Display for ImageRegion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "x={} y={} {}x{}",
+            self.x, self.y, self.width, self.height
+        )
+    }
+}
+
+/// Which part of a file [`load_image_parts`] returns.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ImageSelection {
+    /// PDF pages; defaults to the first [`MAX_PDF_PAGES`]. Ignored for images.
+    pub pages: Option<PageRange>,
+    /// Crop applied to the image or to each selected page.
+    pub region: Option<ImageRegion>,
+}
+
+/// One image sent to the model for a file.
+#[derive(Debug, Clone)]
+pub struct ImagePart {
+    pub image: EncodedImage,
+    /// Which page, region or tile this is, e.g. `page 2 of 5`. `None` for a
+    /// plain image file sent whole.
+    pub label: Option<String>,
+    /// Size of the full image or rendered page. Regions use these coordinates.
+    pub source_width: u32,
+    pub source_height: u32,
+    /// Text extracted from a PDF page, set on the first part of each page.
+    pub text: Option<String>,
+}
+
+/// Loads a raster image, PDF or SVG as images that fit within
+/// [`MAX_WIDTH`] x [`MAX_HEIGHT`].
+///
+/// PDFs yield one or more parts per selected page. Images that would lose too
+/// much detail when downscaled are split into up to [`MAX_TILES`] tiles, each
+/// labelled with its region so a follow-up request can zoom in.
+pub fn load_image_parts(
+    path: &Path,
+    selection: &ImageSelection,
+) -> Result<Vec<ImagePart>, ImageProcessingError> {
+    let file_bytes = read_file_bytes(path, path)?;
+
+    #[cfg(feature = "documents")]
+    if pdf::looks_like_pdf(&file_bytes) {
+        let document = pdf::PdfDocument::load(&file_bytes, path)?;
+        let page_count = document.page_count();
+        let pages = selection.pages.unwrap_or(PageRange {
+            first: 1,
+            last: MAX_PDF_PAGES,
+        });
+        if pages.first > page_count {
+            return Err(ImageProcessingError::PageOutOfRange {
+                path: path.to_path_buf(),
+                page: pages.first,
+                page_count,
+            });
+        }
+        let last = pages
+            .last
+            .min(page_count)
+            .min(pages.first + MAX_PDF_PAGES - 1);
+        let mut parts = Vec::new();
+        for number in pages.first..=last {
+            let page = document.render_page(number, path)?;
+            let label = format!("page {number} of {page_count}");
+            let mut page_parts = fit_image(
+                DynamicImage::ImageRgba8(page.image),
+                selection.region,
+                ImageFormat::Png,
+                Some(label),
+            )?;
+            if let Some(first) = page_parts.first_mut() {
+                first.text = page.text;
+            }
+            parts.extend(page_parts);
+        }
+        return Ok(parts);
+    }
+
+    #[cfg(feature = "documents")]
+    if svg::looks_like_svg(path, &file_bytes) {
+        let rendered = svg::render_svg(&file_bytes, path)?;
+        return fit_image(
+            DynamicImage::ImageRgba8(rendered),
+            selection.region,
+            ImageFormat::Png,
+            None,
+        );
+    }
+
+    if selection.region.is_none()
+        && let Some((width, height)) = ImageReader::new(Cursor::new(&file_bytes))
+            .with_guessed_format()
+            .ok()
+            .and_then(|reader| reader.into_dimensions().ok())
+        && downscale_factor(width, height) <= MAX_DOWNSCALE
+    {
+        let image = encode_file_bytes(path, file_bytes)?;
+        return Ok(vec![ImagePart {
+            image,
+            label: None,
+            source_width: width,
+            source_height: height,
+            text: None,
+        }]);
+    }
+
+    let format = match image::guess_format(&file_bytes) {
+        Ok(ImageFormat::Jpeg) => ImageFormat::Jpeg,
+        _ => ImageFormat::Png,
+    };
+    let dynamic =
+        image::load_from_memory(&file_bytes).map_err(|source| ImageProcessingError::Decode {
+            path: path.to_path_buf(),
+            source,
+        })?;
+    fit_image(dynamic, selection.region, format, None)
+}
+
+/// Whether [`load_image_parts`] can load `path`: a raster image, a PDF or an
+/// SVG. Only the start of the file is read.
+pub fn is_supported_image_file(path: &Path) -> bool {
+    if image::image_dimensions(path).is_ok() {
+        return true;
+    }
+    is_supported_document(path)
+}
+
+#[cfg(feature = "documents")]
+fn is_supported_document(path: &Path) -> bool {
+    use std::io::Read;
+
+    let Ok(file) = std::fs::File::open(path) else {
+        return false;
+    };
+    let mut head = Vec::with_capacity(1024);
+    if file.take(1024).read_to_end(&mut head).is_err() {
+        return false;
+    }
+    pdf::looks_like_pdf(&head) || svg::looks_like_svg(path, &head)
+}
+
+#[cfg(not(feature = "documents"))]
+fn is_supported_document(_path: &Path) -> bool {
+    false
+}
+
+/// Splits a `#page=3` or `#pages=2-5` suffix off a path that does not exist
+/// as given, so `design.pdf#pages=2-3` attaches two pages of `design.pdf`.
+/// A file literally named `design.pdf#pages=2-3`, or a suffix on a path that
+/// still does not exist without it, is left alone.
+pub fn split_page_suffix(path: &Path) -> (PathBuf, Option<PageRange>) {
+    if path.exists() {
+        return (path.to_path_buf(), None);
+    }
+    let Some(raw) = path.to_str() else {
+        return (path.to_path_buf(), None);
+    };
+    if let Some((file, suffix)) = raw.rsplit_once('#')
+        && let Some(pages) = suffix
+            .strip_prefix("pages=")
+            .or_else(|| suffix.strip_prefix("page="))
+        && let Ok(pages) = pages.parse::<PageRange>()
+        && Path::new(file).exists()
+    {
+        return (PathBuf::from(file), Some(pages));
+    }
+    (path.to_path_buf(), None)
+}
+
+/// Crops, downscales and, when needed, tiles a decoded image.
+fn fit_image(
+    image: DynamicImage,
+    region: Option<ImageRegion>,
+    format: ImageFormat,
+    label: Option<String>,
+) -> Result<Vec<ImagePart>, ImageProcessingError> {
+    let (source_width, source_height) = image.dimensions();
+    let mut context: Vec<String> = label.into_iter().collect();
+    let (image, origin) = match region {
+        Some(region) => {
+            let Some(clipped) = region.clip_to(source_width, source_height) else {
+                return Err(ImageProcessingError::RegionOutOfBounds {
+                    region: region.to_string(),
+                    width: source_width,
+                    height: source_height,
+                });
+            };
+            context.push(format!("region {clipped}"));
+            let cropped = image.crop_imm(clipped.x, clipped.y, clipped.width, clipped.height);
+            (cropped, (clipped.x, clipped.y))
+        }
+        None => (image, (0, 0)),
+    };
+
+    let (width, height) = image.dimensions();
+    let (columns, rows) = tile_grid(width, height);
+    let make_part = |tile: &DynamicImage, label: Vec<String>| {
+        let resized = if tile.width() > MAX_WIDTH || tile.height() > MAX_HEIGHT {
+            tile.resize(MAX_WIDTH, MAX_HEIGHT, FilterType::Triangle)
+        } else {
+            tile.clone()
+        };
+        let (bytes, output_format) = encode_image(&resized, format)?;
+        Ok(ImagePart {
+            image: EncodedImage {
+                bytes,
+                mime: format_to_mime(output_format),
+                width: resized.width(),
+                height: resized.height(),
+            },
+            label: (!label.is_empty()).then(|| label.join(", ")),
+            source_width,
+            source_height,
+            text: None,
+        })
+    };
+
+    if columns * rows == 1 {
+        if downscale_factor(width, height) > MAX_DOWNSCALE {
+            context.push(format!("downscaled from {width}x{height}"));
+        }
+        return Ok(vec![make_part(&image, context)?]);
+    }
+
+    let tile_width = width.div_ceil(columns);
+    let tile_height = height.div_ceil(rows);
+    let count = columns * rows;
+    let mut parts = Vec::with_capacity(count as usize);
+    for row in 0..rows {
+        for column in 0..columns {
+            let x = column * tile_width;
+            let y = row * tile_height;
+            let tile = ImageRegion {
+                x: origin.0 + x,
+                y: origin.1 + y,
+                width: tile_width.min(width - x),
+                height: tile_height.min(height - y),
+            };
+            let mut label = context.clone();
+            label.push(format!(
+                "tile {} of {count} at {tile}",
+                row * columns + column + 1
+            ));
+            let cropped = image.crop_imm(x, y, tile.width, tile.height);
+            parts.push(make_part(&cropped, label)?);
+        }
+    }
+    Ok(parts)
+}
+
+fn downscale_factor(width: u32, height: u32) -> f32 {
+    (width as f32 / MAX_WIDTH as f32).max(height as f32 / MAX_HEIGHT as f32)
+}
+
+/// Columns and rows to split an image into. Each tile is at most twice the
+/// model limit in each direction; `(1, 1)` means the image is sent whole.
+fn tile_grid(width: u32, height: u32) -> (u32, u32) {
+    if downscale_factor(width, height) <= MAX_DOWNSCALE {
+        return (1, 1);
+    }
+    let columns = width.div_ceil(MAX_WIDTH * 2);
+    let rows = height.div_ceil(MAX_HEIGHT * 2);
+    if columns * rows > MAX_TILES {
+        (1, 1)
+    } else {
+        (columns, rows)
+    }
+}
+
 pub fn load_and_resize_to_fit(path: &Path) -> Result<EncodedImage, ImageProcessingError> {
-    let path_buf = path.to_path_buf();
+    let file_bytes = read_file_bytes(path, path)?;
+    encode_file_bytes(path, file_bytes)
+}
 
-    let file_bytes = read_file_bytes(path, &path_buf)?;
+fn encode_file_bytes(
+    path: &Path,
+    file_bytes: Vec<u8>,
+) -> Result<EncodedImage, ImageProcessingError> {
+    let path_buf = path.to_path_buf();
 
     let key = sha1_digest(&file_bytes);
 
@@ -249,4 +597,328 @@ mod tests {
         assert_eq!(second.height, 48);
         assert_ne!(second.bytes, first.bytes);
     }
+
+    #[cfg(feature = "documents")]
+    fn write_test_pdf(path: &Path) {
+        use lopdf::Document;
+        use lopdf::Object;
+        use lopdf::Stream;
+        use lopdf::content::Content;
+        use lopdf::content::Operation;
+        use lopdf::dictionary;
+
+        let mut doc = Document::with_version("1.5");
+        let pages_id = doc.new_object_id();
+        let font_id = doc.add_object(dictionary! {
+            "Type" => "Font",
+            "Subtype" => "Type1",
+            "BaseFont" => "Courier",
+        });
+        // Two pixels, green then blue.
+        let image_id = doc.add_object(Stream::new(
+            dictionary! {
+                "Type" => "XObject",
+                "Subtype" => "Image",
+                "Width" => 2,
+                "Height" => 1,
+                "ColorSpace" => "DeviceRGB",
+                "BitsPerComponent" => 8,
+            },
+            vec![0, 255, 0, 0, 0, 255],
+        ));
+        let resources_id = doc.add_object(dictionary! {
+            "Font" => dictionary! { "F1" => font_id },
+            "XObject" => dictionary! { "Im1" => image_id },
+        });
+        let text = |body: &str| {
+            vec![
+                Operation::new("BT", vec![]),
+                Operation::new("Tf", vec!["F1".into(), 24.into()]),
+                Operation::new("Td", vec![20.into(), 20.into()]),
+                Operation::new("Tj", vec![Object::string_literal(body)]),
+                Operation::new("ET", vec![]),
+            ]
+        };
+        let first = [
+            vec![
+                Operation::new("rg", vec![1.into(), 0.into(), 0.into()]),
+                Operation::new("re", vec![100.into(), 100.into(), 200.into(), 200.into()]),
+                Operation::new("f", vec![]),
+            ],
+            text("Hello page one"),
+        ]
+        .concat();
+        let second = [
+            vec![
+                Operation::new("q", vec![]),
+                Operation::new(
+                    "cm",
+                    vec![
+                        400.into(),
+                        0.into(),
+                        0.into(),
+                        400.into(),
+                        0.into(),
+                        0.into(),
+                    ],
+                ),
+                Operation::new("Do", vec!["Im1".into()]),
+                Operation::new("Q", vec![]),
+            ],
+            text("Second"),
+        ]
+        .concat();
+        let mut kids = Vec::new();
+        for operations in [first, second] {
+            let content = Content { operations }.encode().expect("encode content");
+            let content_id = doc.add_object(Stream::new(dictionary! {}, content));
+            let page_id = doc.add_object(dictionary! {
+                "Type" => "Page",
+                "Parent" => pages_id,
+                "Contents" => content_id,
+            });
+            kids.push(page_id.into());
+        }
+        doc.objects.insert(
+            pages_id,
+            Object::Dictionary(dictionary! {
+                "Type" => "Pages",
+                "Kids" => kids,
+                "Count" => 2,
+                "Resources" => resources_id,
+                "MediaBox" => vec![0.into(), 0.into(), 400.into(), 400.into()],
+            }),
+        );
+        let catalog_id = doc.add_object(dictionary! {
+            "Type" => "Catalog",
+            "Pages" => pages_id,
+        });
+        doc.trailer.set("Root", catalog_id);
+        doc.compress();
+        doc.save(path).expect("write pdf");
+    }
+
+    #[cfg(feature = "documents")]
+    fn decode_part(part: &ImagePart) -> image::RgbaImage {
+        image::load_from_memory(&part.image.bytes)
+            .expect("decode part")
+            .to_rgba8()
+    }
+
+    #[test]
+    fn parses_page_ranges() {
+        assert_eq!(
+            "3".parse::<PageRange>().ok(),
+            Some(PageRange { first: 3, last: 3 })
+        );
+        assert_eq!(
+            " 2 - 5 ".parse::<PageRange>().ok(),
+            Some(PageRange { first: 2, last: 5 })
+        );
+        for invalid in ["0", "5-2", "two", "1-"] {
+            assert!(
+                matches!(
+                    invalid.parse::<PageRange>(),
+                    Err(ImageProcessingError::InvalidPageRange(_))
+                ),
+                "{invalid}"
+            );
+        }
+    }
+
+    #[test]
+    fn splits_page_suffix_only_for_missing_paths() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let pdf = dir.path().join("design.pdf");
+        std::fs::write(&pdf, b"%PDF-1.5").expect("write file");
+
+        assert_eq!(split_page_suffix(&pdf), (pdf.clone(), None));
+        let with_suffix = dir.path().join("design.pdf#pages=2-3");
+        assert_eq!(
+            split_page_suffix(&with_suffix),
+            (pdf, Some(PageRange { first: 2, last: 3 }))
+        );
+        let unrelated = dir.path().join("notes#1.png");
+        assert_eq!(split_page_suffix(&unrelated), (unrelated.clone(), None));
+
+        let literal = dir.path().join("scan.pdf#page=1");
+        std::fs::write(&literal, b"%PDF-1.5").expect("write file");
+        assert_eq!(split_page_suffix(&literal), (literal.clone(), None));
+        let missing = dir.path().join("missing.pdf#page=1");
+        assert_eq!(split_page_suffix(&missing), (missing.clone(), None));
+    }
+
+    #[cfg(feature = "documents")]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn renders_pdf_pages_with_text() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let path = dir.path().join("doc.pdf");
+        write_test_pdf(&path);
+        assert!(is_supported_image_file(&path));
+
+        let parts = load_image_parts(&path, &ImageSelection::default()).expect("render pdf");
+        let labels: Vec<Option<String>> = parts.iter().map(|part| part.label.clone()).collect();
+        assert_eq!(
+            labels,
+            vec![
+                Some("page 1 of 2".to_string()),
+                Some("page 2 of 2".to_string())
+            ]
+        );
+        assert_eq!((parts[0].source_width, parts[0].source_height), (800, 800));
+        assert!(parts[0].image.height <= MAX_HEIGHT);
+        assert!(
+            parts[0]
+                .text
+                .as_deref()
+                .is_some_and(|text| text.contains("Hello page one"))
+        );
+
+        let first = decode_part(&parts[0]);
+        let (width, height) = first.dimensions();
+        assert_eq!(first.get_pixel(width / 2, height / 2).0, [255, 0, 0, 255]);
+        assert_eq!(first.get_pixel(4, 4).0, [255, 255, 255, 255]);
+
+        let second = decode_part(&parts[1]);
+        let (width, height) = second.dimensions();
+        assert_eq!(second.get_pixel(width / 8, height / 2).0, [0, 255, 0, 255]);
+        assert_eq!(
+            second.get_pixel(width * 7 / 8, height / 2).0,
+            [0, 0, 255, 255]
+        );
+    }
+
+    #[cfg(feature = "documents")]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn draws_pdf_text() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let path = dir.path().join("doc.pdf");
+        write_test_pdf(&path);
+
+        let parts = load_image_parts(&path, &ImageSelection::default()).expect("render pdf");
+        let first = decode_part(&parts[0]);
+        let (width, height) = first.dimensions();
+        // "Hello page one" sits at the bottom left, below the red square.
+        let is_inked = |x: u32, y: u32| first.get_pixel(x, y).0[..3].iter().any(|c| *c < 128);
+        let inked_in = |xs: std::ops::Range<u32>, ys: std::ops::Range<u32>| {
+            ys.flat_map(|y| xs.clone().map(move |x| (x, y)))
+                .filter(|(x, y)| is_inked(*x, *y))
+                .count()
+        };
+        assert!(inked_in(0..width / 2, height * 7 / 8..height) > 100);
+        assert_eq!(inked_in(0..width, 0..height / 8), 0);
+    }
+
+    #[cfg(feature = "documents")]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn selects_pdf_pages_and_regions() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let path = dir.path().join("doc.pdf");
+        write_test_pdf(&path);
+
+        let selection = ImageSelection {
+            pages: Some(PageRange { first: 2, last: 9 }),
+            region: Some(ImageRegion {
+                x: 0,
+                y: 0,
+                width: 100,
+                height: 50,
+            }),
+        };
+        let parts = load_image_parts(&path, &selection).expect("render page 2");
+        assert_eq!(parts.len(), 1);
+        assert_eq!(
+            parts[0].label.as_deref(),
+            Some("page 2 of 2, region x=0 y=0 100x50")
+        );
+        assert_eq!((parts[0].image.width, parts[0].image.height), (100, 50));
+        assert_eq!(decode_part(&parts[0]).get_pixel(50, 25).0, [0, 255, 0, 255]);
+
+        let out_of_range = ImageSelection {
+            pages: Some(PageRange { first: 3, last: 3 }),
+            region: None,
+        };
+        assert!(matches!(
+            load_image_parts(&path, &out_of_range),
+            Err(ImageProcessingError::PageOutOfRange {
+                page: 3,
+                page_count: 2,
+                ..
+            })
+        ));
+    }
+
+    #[cfg(feature = "documents")]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn renders_svg() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let path = dir.path().join("logo.svg");
+        std::fs::write(
+            &path,
+            r##"<svg xmlns="http://www.w3.org/2000/svg" width="200" height="100"><rect width="200" height="100" fill="#0000ff"/></svg>"##,
+        )
+        .expect("write svg");
+
+        let parts = load_image_parts(&path, &ImageSelection::default()).expect("render svg");
+        assert_eq!(parts.len(), 1);
+        assert_eq!((parts[0].source_width, parts[0].source_height), (1024, 512));
+        assert_eq!(parts[0].image.mime, "image/png");
+        assert_eq!(decode_part(&parts[0]).get_pixel(10, 10).0, [0, 0, 255, 255]);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn tiles_very_tall_images() {
+        let temp_file = NamedTempFile::new().expect("temp file");
+        let image = ImageBuffer::from_pixel(1000, 5000, Rgba([10u8, 200, 10, 255]));
+        image
+            .save_with_format(temp_file.path(), ImageFormat::Png)
+            .expect("write png to temp file");
+
+        let parts =
+            load_image_parts(temp_file.path(), &ImageSelection::default()).expect("tile image");
+        let labels: Vec<String> = parts.iter().filter_map(|part| part.label.clone()).collect();
+        assert_eq!(
+            labels,
+            vec![
+                "tile 1 of 4 at x=0 y=0 1000x1250".to_string(),
+                "tile 2 of 4 at x=0 y=1250 1000x1250".to_string(),
+                "tile 3 of 4 at x=0 y=2500 1000x1250".to_string(),
+                "tile 4 of 4 at x=0 y=3750 1000x1250".to_string(),
+            ]
+        );
+        for part in &parts {
+            assert!(part.image.width <= MAX_WIDTH && part.image.height <= MAX_HEIGHT);
+        }
+
+        let region = ImageSelection {
+            pages: None,
+            region: Some(ImageRegion {
+                x: 900,
+                y: 4900,
+                width: 500,
+                height: 500,
+            }),
+        };
+        let parts = load_image_parts(temp_file.path(), &region).expect("crop image");
+        assert_eq!(parts.len(), 1);
+        assert_eq!(
+            parts[0].label.as_deref(),
+            Some("region x=900 y=4900 100x100")
+        );
+        assert_eq!((parts[0].image.width, parts[0].image.height), (100, 100));
+
+        let outside = ImageSelection {
+            pages: None,
+            region: Some(ImageRegion {
+                x: 1000,
+                y: 0,
+                width: 10,
+                height: 10,
+            }),
+        };
+        assert!(matches!(
+            load_image_parts(temp_file.path(), &outside),
+            Err(ImageProcessingError::RegionOutOfBounds { .. })
+        ));
+    }
 }
@@ -0,0 +1,116 @@
+//! Rasterizes PDF pages with hayro.
+//!
+//! The page text is also extracted and returned next to the rendered page,
+//! since the model reads it more reliably than small rendered glyphs.
+
+use std::path::Path;
+
+use hayro::PixmapSettings;
+use hayro::RenderCache;
+use hayro::RenderSettings;
+use hayro::hayro_interpret::InterpreterSettings;
+use hayro::hayro_syntax::LoadPdfError;
+use hayro::hayro_syntax::Pdf;
+use hayro::vello_cpu::color::palette::css::WHITE;
+use image::RgbaImage;
+
+use crate::error::ImageProcessingError;
+
+/// PDF user space has 72 units per inch; pages render at 144 dpi.
+const RENDER_SCALE: f32 = 2.0;
+/// Longest side, in pixels, of a rendered page.
+const MAX_RENDER_SIDE: f32 = 4096.0;
+
+pub(crate) fn looks_like_pdf(bytes: &[u8]) -> bool {
+    let head = &bytes[..bytes.len().min(1024)];
+    head.windows(5).any(|window| window == b"%PDF-")
+}
+
+pub(crate) struct RenderedPage {
+    pub image: RgbaImage,
+    pub text: Option<String>,
+}
+
+pub(crate) struct PdfDocument {
+    pdf: Pdf,
+    /// Only used for text extraction; `None` when lopdf cannot parse a file
+    /// that hayro renders.
+    text: Option<lopdf::Document>,
+}
+
+impl PdfDocument {
+    pub(crate) fn load(bytes: &[u8], path: &Path) -> Result<Self, ImageProcessingError> {
+        let pdf = Pdf::new(bytes.to_vec()).map_err(|err| ImageProcessingError::Pdf {
+            path: path.to_path_buf(),
+            reason: match err {
+                LoadPdfError::Decryption(_) => "the file is encrypted",
+                LoadPdfError::Invalid => "the file is not a valid PDF",
+            },
+        })?;
+        let text = lopdf::Document::load_mem(bytes).ok();
+        Ok(Self { pdf, text })
+    }
+
+    pub(crate) fn page_count(&self) -> u32 {
+        self.pdf.pages().len() as u32
+    }
+
+    /// Renders the 1-based page `number`.
+    pub(crate) fn render_page(
+        &self,
+        number: u32,
+        path: &Path,
+    ) -> Result<RenderedPage, ImageProcessingError> {
+        let Some(page) = number
+            .checked_sub(1)
+            .and_then(|index| self.pdf.pages().get(index as usize))
+        else {
+            return Err(ImageProcessingError::PageOutOfRange {
+                path: path.to_path_buf(),
+                page: number,
+                page_count: self.page_count(),
+            });
+        };
+
+        let (page_width, page_height) = page.render_dimensions();
+        let scale = RENDER_SCALE.min(MAX_RENDER_SIDE / page_width.max(page_height));
+        if page_width * scale < 1.0 || page_height * scale < 1.0 {
+            return Err(ImageProcessingError::Pdf {
+                path: path.to_path_buf(),
+                reason: "the page is too narrow to render",
+            });
+        }
+        let pixmap = hayro::render(
+            page,
+            &RenderCache::new(),
+            &InterpreterSettings::default(),
+            &RenderSettings::default(),
+            &PixmapSettings {
+                x_scale: scale,
+                y_scale: scale,
+                bg_color: WHITE,
+            },
+        );
+        let (width, height) = (u32::from(pixmap.width()), u32::from(pixmap.height()));
+        // Pages are drawn over an opaque background, so premultiplied and
+        // straight alpha are the same.
+        let image = RgbaImage::from_raw(width, height, pixmap.data_as_u8_slice().to_vec())
+            .unwrap_or_else(|| RgbaImage::new(width, height));
+
+        let text: String = self
+            .text
+            .as_ref()
+            .map(|doc| {
+                doc.extract_text_chunks(&[number])
+                    .into_iter()
+                    .filter_map(Result::ok)
+                    .collect()
+            })
+            .unwrap_or_default();
+        let text = text.trim();
+        Ok(RenderedPage {
+            image,
+            text: (!text.is_empty()).then(|| text.to_string()),
+        })
+    }
+}
@@ -0,0 +1,91 @@
+//! Renders SVG files with resvg.
+
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::LazyLock;
+
+use image::RgbaImage;
+use resvg::tiny_skia::Pixmap;
+use resvg::tiny_skia::Transform;
+use resvg::usvg;
+use resvg::usvg::fontdb;
+
+use crate::error::ImageProcessingError;
+
+/// Longest side, in pixels, that small drawings are scaled up to.
+const MIN_RENDER_SIDE: f32 = 1024.0;
+/// Longest side, in pixels, that large drawings are scaled down to.
+const MAX_RENDER_SIDE: f32 = 4096.0;
+
+/// System fonts are loaded once; scanning them takes longer than rendering.
+static FONT_DB: LazyLock<Arc<fontdb::Database>> = LazyLock::new(|| {
+    let mut db = fontdb::Database::new();
+    db.load_system_fonts();
+    Arc::new(db)
+});
+
+pub(crate) fn looks_like_svg(path: &Path, bytes: &[u8]) -> bool {
+    let by_extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("svg") || ext.eq_ignore_ascii_case("svgz"));
+    if by_extension {
+        return true;
+    }
+    let head = &bytes[..bytes.len().min(512)];
+    let head = String::from_utf8_lossy(head);
+    let head = head.trim_start_matches('\u{feff}').trim_start();
+    head.starts_with("<svg") || (head.starts_with("<?xml") && head.contains("<svg"))
+}
+
+pub(crate) fn render_svg(bytes: &[u8], path: &Path) -> Result<RgbaImage, ImageProcessingError> {
+    let options = usvg::Options {
+        resources_dir: path.parent().map(Path::to_path_buf),
+        fontdb: FONT_DB.clone(),
+        ..usvg::Options::default()
+    };
+    let tree =
+        usvg::Tree::from_data(bytes, &options).map_err(|source| ImageProcessingError::Svg {
+            path: path.to_path_buf(),
+            source,
+        })?;
+
+    let size = tree.size();
+    let longest = size.width().max(size.height());
+    let scale = if longest < MIN_RENDER_SIDE {
+        MIN_RENDER_SIDE / longest
+    } else {
+        (MAX_RENDER_SIDE / longest).min(1.0)
+    };
+    let width = (size.width() * scale).ceil().max(1.0) as u32;
+    let height = (size.height() * scale).ceil().max(1.0) as u32;
+    let Some(mut pixmap) = Pixmap::new(width, height) else {
+        return Err(ImageProcessingError::Svg {
+            path: path.to_path_buf(),
+            source: usvg::Error::InvalidSize,
+        });
+    };
+    resvg::render(
+        &tree,
+        Transform::from_scale(scale, scale),
+        &mut pixmap.as_mut(),
+    );
+    Ok(pixmap_to_rgba(pixmap))
+}
+
+/// Converts a premultiplied tiny-skia pixmap into a straight-alpha image.
+fn pixmap_to_rgba(pixmap: Pixmap) -> RgbaImage {
+    let width = pixmap.width();
+    let height = pixmap.height();
+    let mut data = pixmap.take();
+    for pixel in data.chunks_exact_mut(4) {
+        let alpha = pixel[3];
+        if alpha != 0 && alpha != 255 {
+            for channel in &mut pixel[..3] {
+                *channel = ((u16::from(*channel) * 255 + u16::from(alpha) / 2) / u16::from(alpha))
+                    .min(255) as u8;
+            }
+        }
+    }
+    RgbaImage::from_raw(width, height, data).unwrap_or_else(|| RgbaImage::new(width, height))
+}
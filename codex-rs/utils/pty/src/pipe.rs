@@ -19,6 +19,7 @@ use tokio::sync::oneshot;
 use tokio::task::JoinHandle;
 
 use crate::process::ChildTerminator;
+use crate::process::PreExec;
 use crate::process::ProcessHandle;
 use crate::process::SpawnedProcess;
 
@@ -103,6 +104,7 @@ async fn spawn_process_with_stdin_mode(
     env: &HashMap<String, String>,
     arg0: &Option<String>,
     stdin_mode: PipeStdinMode,
+    pre_exec: Option<PreExec>,
 ) -> Result<SpawnedProcess> {
     if program.is_empty() {
         anyhow::bail!("missing program for pipe spawn");
@@ -121,11 +123,14 @@ async fn spawn_process_with_stdin_mode(
             crate::process_group::detach_from_tty()?;
             #[cfg(target_os = "linux")]
             crate::process_group::set_parent_death_signal(parent_pid)?;
+            if let Some(pre_exec) = &pre_exec {
+                pre_exec()?;
+            }
             Ok(())
         });
     }
     #[cfg(not(unix))]
-    let _ = arg0;
+    let _ = (arg0, pre_exec);
     command.current_dir(cwd);
     command.env_clear();
     for (key, value) in env {
@@ -253,7 +258,7 @@ pub async fn spawn_process(
     env: &HashMap<String, String>,
     arg0: &Option<String>,
 ) -> Result<SpawnedProcess> {
-    spawn_process_with_stdin_mode(program, args, cwd, env, arg0, PipeStdinMode::Piped).await
+    spawn_process_with_stdin_mode(program, args, cwd, env, arg0, PipeStdinMode::Piped, None).await
 }
 
 /// Spawn a process using regular pipes, but close stdin immediately.
@@ -264,5 +269,19 @@ pub async fn spawn_process_no_stdin(
     env: &HashMap<String, String>,
     arg0: &Option<String>,
 ) -> Result<SpawnedProcess> {
-    spawn_process_with_stdin_mode(program, args, cwd, env, arg0, PipeStdinMode::Null).await
+    spawn_process_with_stdin_mode(program, args, cwd, env, arg0, PipeStdinMode::Null, None).await
+}
+
+/// Like [`spawn_process_no_stdin`], running `pre_exec` (when set) in the
+/// child right before it execs.
+pub async fn spawn_process_no_stdin_with_pre_exec(
+    program: &str,
+    args: &[String],
+    cwd: &Path,
+    env: &HashMap<String, String>,
+    arg0: &Option<String>,
+    pre_exec: Option<PreExec>,
+) -> Result<SpawnedProcess> {
+    spawn_process_with_stdin_mode(program, args, cwd, env, arg0, PipeStdinMode::Null, pre_exec)
+        .await
 }
@@ -14,6 +14,10 @@ use tokio::sync::oneshot;
 use tokio::task::AbortHandle;
 use tokio::task::JoinHandle;
 
+/// Hook run in the child after `fork` and before `exec` (Unix only), e.g. to
+/// set resource limits that must not apply to the parent.
+pub type PreExec = Arc<dyn Fn() -> io::Result<()> + Send + Sync>;
+
 pub(crate) trait ChildTerminator: Send + Sync {
     fn kill(&mut self) -> io::Result<()>;
 }
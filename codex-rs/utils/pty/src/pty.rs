@@ -18,6 +18,7 @@ use tokio::sync::oneshot;
 use tokio::task::JoinHandle;
 
 use crate::process::ChildTerminator;
+use crate::process::PreExec;
 use crate::process::ProcessHandle;
 use crate::process::PtyHandles;
 use crate::process::SpawnedProcess;
@@ -44,6 +45,23 @@ impl ChildTerminator for PtyChildTerminator {
     }
 }
 
+/// Kills a PTY child spawned by [`spawn_child_with_pre_exec`], which leads
+/// its own session and therefore its own process group.
+#[cfg(unix)]
+struct ProcessGroupTerminator {
+    process_group_id: u32,
+}
+
+#[cfg(unix)]
+impl ChildTerminator for ProcessGroupTerminator {
+    fn kill(&mut self) -> std::io::Result<()> {
+        crate::process_group::kill_process_group(self.process_group_id)
+    }
+}
+
+/// Blocks until the child exits and returns its exit code.
+type WaitForExit = Box<dyn FnOnce() -> i32 + Send>;
+
 fn platform_native_pty_system() -> Box<dyn portable_pty::PtySystem + Send> {
     #[cfg(windows)]
     {
@@ -202,6 +220,20 @@ pub async fn spawn_process(
     env: &HashMap<String, String>,
     arg0: &Option<String>,
     size: Option<(u16, u16)>,
+) -> Result<SpawnedProcess> {
+    spawn_process_with_pre_exec(program, args, cwd, env, arg0, size, None).await
+}
+
+/// Like [`spawn_process`], running `pre_exec` (when set) in the child right
+/// before it execs. `pre_exec` is ignored on Windows.
+pub async fn spawn_process_with_pre_exec(
+    program: &str,
+    args: &[String],
+    cwd: &Path,
+    env: &HashMap<String, String>,
+    arg0: &Option<String>,
+    size: Option<(u16, u16)>,
+    pre_exec: Option<PreExec>,
 ) -> Result<SpawnedProcess> {
     if program.is_empty() {
         anyhow::bail!("missing program for PTY spawn");
@@ -224,18 +256,48 @@ pub async fn spawn_process(
         pixel_height: size.pixel_height,
     })?;
 
-    let mut command_builder = CommandBuilder::new(arg0.as_ref().unwrap_or(&program.to_string()));
-    command_builder.cwd(cwd);
-    command_builder.env_clear();
-    for arg in args {
-        command_builder.arg(arg);
-    }
-    for (key, value) in env {
-        command_builder.env(key, value);
-    }
+    let (terminator, wait_for_exit): (Box<dyn ChildTerminator>, WaitForExit) = match pre_exec {
+        #[cfg(unix)]
+        Some(pre_exec) => {
+            let mut child = spawn_child_with_pre_exec(
+                pair.master.as_ref(),
+                program,
+                args,
+                cwd,
+                env,
+                arg0,
+                pre_exec,
+            )?;
+            let terminator = ProcessGroupTerminator {
+                process_group_id: child.id(),
+            };
+            let wait_for_exit = Box::new(move || match child.wait() {
+                Ok(status) => status.code().unwrap_or(-1),
+                Err(_) => -1,
+            });
+            (Box::new(terminator), wait_for_exit)
+        }
+        _ => {
+            let mut command_builder =
+                CommandBuilder::new(arg0.as_ref().unwrap_or(&program.to_string()));
+            command_builder.cwd(cwd);
+            command_builder.env_clear();
+            for arg in args {
+                command_builder.arg(arg);
+            }
+            for (key, value) in env {
+                command_builder.env(key, value);
+            }
 
-    let mut child = pair.slave.spawn_command(command_builder)?;
-    let killer = child.clone_killer();
+            let mut child = pair.slave.spawn_command(command_builder)?;
+            let killer = child.clone_killer();
+            let wait_for_exit = Box::new(move || match child.wait() {
+                Ok(status) => status.exit_code() as i32,
+                Err(_) => -1,
+            });
+            (Box::new(PtyChildTerminator { killer }), wait_for_exit)
+        }
+    };
 
     let (writer_tx, mut writer_rx) = mpsc::channel::<Vec<u8>>(128);
     let (output_tx, _) = broadcast::channel::<Vec<u8>>(256);
@@ -281,10 +343,7 @@ pub async fn spawn_process(
     let exit_code = Arc::new(StdMutex::new(None));
     let wait_exit_code = Arc::clone(&exit_code);
     let wait_handle: JoinHandle<()> = tokio::task::spawn_blocking(move || {
-        let code = match child.wait() {
-            Ok(status) => status.exit_code() as i32,
-            Err(_) => -1,
-        };
+        let code = wait_for_exit();
         wait_exit_status.store(true, std::sync::atomic::Ordering::SeqCst);
         if let Ok(mut guard) = wait_exit_code.lock() {
             *guard = Some(code);
@@ -305,7 +364,7 @@ pub async fn spawn_process(
         writer_tx,
         output_tx,
         initial_output_rx,
-        Box::new(PtyChildTerminator { killer }),
+        terminator,
         reader_handle,
         Vec::new(),
         writer_handle,
@@ -321,3 +380,54 @@ pub async fn spawn_process(
         exit_rx,
     })
 }
+
+/// Spawns `program` on the slave side of `master` with `std::process`, since
+/// `portable_pty::CommandBuilder` offers no `pre_exec` hook. Mirrors what
+/// `portable_pty` does for the child: a new session with the PTY as its
+/// controlling terminal and stdio.
+#[cfg(unix)]
+fn spawn_child_with_pre_exec(
+    master: &(dyn portable_pty::MasterPty + Send),
+    program: &str,
+    args: &[String],
+    cwd: &Path,
+    env: &HashMap<String, String>,
+    arg0: &Option<String>,
+    pre_exec: PreExec,
+) -> Result<std::process::Child> {
+    use std::os::unix::fs::OpenOptionsExt;
+    use std::os::unix::process::CommandExt;
+
+    let tty_name = master
+        .tty_name()
+        .ok_or_else(|| anyhow::anyhow!("missing PTY slave path"))?;
+    let tty = std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .custom_flags(libc::O_NOCTTY)
+        .open(tty_name)?;
+
+    let mut command = std::process::Command::new(program);
+    if let Some(arg0) = arg0 {
+        command.arg0(arg0);
+    }
+    command.args(args);
+    command.current_dir(cwd);
+    command.env_clear();
+    command.envs(env);
+    command.stdin(tty.try_clone()?);
+    command.stdout(tty.try_clone()?);
+    command.stderr(tty);
+    unsafe {
+        command.pre_exec(move || {
+            if libc::setsid() == -1 {
+                return Err(std::io::Error::last_os_error());
+            }
+            if libc::ioctl(libc::STDIN_FILENO, libc::TIOCSCTTY as _, 0) == -1 {
+                return Err(std::io::Error::last_os_error());
+            }
+            pre_exec()
+        });
+    }
+    Ok(command.spawn()?)
+}
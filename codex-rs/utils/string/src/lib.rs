@@ -62,11 +62,27 @@ pub fn sanitize_metric_tag_value(value: &str) -> String {
     }
 }
 
+/// Compares secrets such as bearer tokens without stopping at the first
+/// differing byte, so the time taken does not reveal how much of a guess
+/// matched.
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
 #[cfg(test)]
 mod tests {
+    use super::constant_time_eq;
     use super::sanitize_metric_tag_value;
     use pretty_assertions::assert_eq;
 
+    #[test]
+    fn constant_time_eq_compares_whole_values() {
+        assert!(constant_time_eq(b"secret", b"secret"));
+        assert!(!constant_time_eq(b"secret", b"secreT"));
+        assert!(!constant_time_eq(b"secret", b"secret-and-more"));
+        assert!(!constant_time_eq(b"", b"secret"));
+    }
+
     #[test]
     fn sanitize_metric_tag_value_trims_and_fills_unspecified() {
         let msg = "///";
@@ -1,4 +1,5 @@
 use crate::policy::SandboxPolicy;
+use codex_utils_absolute_path::AbsolutePathBuf;
 use dunce::canonicalize;
 use std::collections::HashMap;
 use std::collections::HashSet;
@@ -38,43 +39,45 @@ pub fn compute_allow_paths(
         }
     );
 
-    if matches!(policy, SandboxPolicy::WorkspaceWrite { .. }) {
-        let add_writable_root =
-            |root: PathBuf,
-             policy_cwd: &Path,
-             add_allow: &mut dyn FnMut(PathBuf),
-             add_deny: &mut dyn FnMut(PathBuf)| {
-                let candidate = if root.is_absolute() {
-                    root
-                } else {
-                    policy_cwd.join(root)
-                };
-                let canonical = canonicalize(&candidate).unwrap_or(candidate);
-                add_allow(canonical.clone());
-
-                let git_entry = canonical.join(".git");
-                if git_entry.exists() {
-                    add_deny(git_entry);
-                }
-            };
+    // Read-only policies may write only to their explicit roots, never the cwd.
+    let (include_command_cwd, writable_roots): (bool, &[AbsolutePathBuf]) = match policy {
+        SandboxPolicy::WorkspaceWrite { writable_roots, .. } => (true, writable_roots.as_slice()),
+        SandboxPolicy::ReadOnly { writable_roots, .. } => (false, writable_roots.as_slice()),
+        SandboxPolicy::DangerFullAccess | SandboxPolicy::ExternalSandbox { .. } => (false, &[]),
+    };
+    let add_writable_root = |root: PathBuf,
+                             policy_cwd: &Path,
+                             add_allow: &mut dyn FnMut(PathBuf),
+                             add_deny: &mut dyn FnMut(PathBuf)| {
+        let candidate = if root.is_absolute() {
+            root
+        } else {
+            policy_cwd.join(root)
+        };
+        let canonical = canonicalize(&candidate).unwrap_or(candidate);
+        add_allow(canonical.clone());
+
+        let git_entry = canonical.join(".git");
+        if git_entry.exists() {
+            add_deny(git_entry);
+        }
+    };
 
+    if include_command_cwd {
         add_writable_root(
             command_cwd.to_path_buf(),
             policy_cwd,
             &mut add_allow_path,
             &mut add_deny_path,
         );
-
-        if let SandboxPolicy::WorkspaceWrite { writable_roots, .. } = policy {
-            for root in writable_roots {
-                add_writable_root(
-                    root.clone().into(),
-                    policy_cwd,
-                    &mut add_allow_path,
-                    &mut add_deny_path,
-                );
-            }
-        }
+    }
+    for root in writable_roots {
+        add_writable_root(
+            root.clone().into(),
+            policy_cwd,
+            &mut add_allow_path,
+            &mut add_deny_path,
+        );
     }
     if include_tmp_env_vars {
         for key in ["TEMP", "TMP"] {
@@ -109,8 +112,10 @@ mod tests {
         let policy = SandboxPolicy::WorkspaceWrite {
             writable_roots: vec![AbsolutePathBuf::try_from(extra_root.as_path()).unwrap()],
             network_access: false,
+            allowed_loopback_ports: Vec::new(),
             exclude_tmpdir_env_var: false,
             exclude_slash_tmp: false,
+            deny_read: Vec::new(),
         };
 
         let paths = compute_allow_paths(&policy, &command_cwd, &command_cwd, &HashMap::new());
@@ -124,6 +129,34 @@ mod tests {
         assert!(paths.deny.is_empty(), "no deny paths expected");
     }
 
+    #[test]
+    fn read_only_includes_only_its_writable_roots() {
+        let tmp = TempDir::new().expect("tempdir");
+        let command_cwd = tmp.path().join("workspace");
+        let extra_root = tmp.path().join("extra");
+        let _ = fs::create_dir_all(&command_cwd);
+        let _ = fs::create_dir_all(extra_root.join(".git"));
+
+        let policy = SandboxPolicy::ReadOnly {
+            writable_roots: vec![AbsolutePathBuf::try_from(extra_root.as_path()).unwrap()],
+            deny_read: Vec::new(),
+        };
+        let mut env_map = HashMap::new();
+        env_map.insert("TEMP".into(), command_cwd.to_string_lossy().to_string());
+
+        let paths = compute_allow_paths(&policy, &command_cwd, &command_cwd, &env_map);
+        let expected_allow: HashSet<PathBuf> = [dunce::canonicalize(&extra_root).unwrap()]
+            .into_iter()
+            .collect();
+        let expected_deny: HashSet<PathBuf> =
+            [dunce::canonicalize(extra_root.join(".git")).unwrap()]
+                .into_iter()
+                .collect();
+
+        assert_eq!(expected_allow, paths.allow);
+        assert_eq!(expected_deny, paths.deny);
+    }
+
     #[test]
     fn excludes_tmp_env_vars_when_requested() {
         let tmp = TempDir::new().expect("tempdir");
@@ -135,8 +168,10 @@ mod tests {
         let policy = SandboxPolicy::WorkspaceWrite {
             writable_roots: vec![],
             network_access: false,
+            allowed_loopback_ports: Vec::new(),
             exclude_tmpdir_env_var: true,
             exclude_slash_tmp: false,
+            deny_read: Vec::new(),
         };
         let mut env_map = HashMap::new();
         env_map.insert("TEMP".into(), temp_dir.to_string_lossy().to_string());
@@ -162,8 +197,10 @@ mod tests {
         let policy = SandboxPolicy::WorkspaceWrite {
             writable_roots: vec![],
             network_access: false,
+            allowed_loopback_ports: Vec::new(),
             exclude_tmpdir_env_var: true,
             exclude_slash_tmp: false,
+            deny_read: Vec::new(),
         };
 
         let paths = compute_allow_paths(&policy, &command_cwd, &command_cwd, &HashMap::new());
@@ -189,8 +226,10 @@ mod tests {
         let policy = SandboxPolicy::WorkspaceWrite {
             writable_roots: vec![],
             network_access: false,
+            allowed_loopback_ports: Vec::new(),
             exclude_tmpdir_env_var: true,
             exclude_slash_tmp: false,
+            deny_read: Vec::new(),
         };
 
         let paths = compute_allow_paths(&policy, &command_cwd, &command_cwd, &HashMap::new());
@@ -214,8 +253,10 @@ mod tests {
         let policy = SandboxPolicy::WorkspaceWrite {
             writable_roots: vec![],
             network_access: false,
+            allowed_loopback_ports: Vec::new(),
             exclude_tmpdir_env_var: true,
             exclude_slash_tmp: false,
+            deny_read: Vec::new(),
         };
 
         let paths = compute_allow_paths(&policy, &command_cwd, &command_cwd, &HashMap::new());
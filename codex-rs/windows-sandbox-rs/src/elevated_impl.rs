@@ -218,6 +218,15 @@ mod windows_impl {
         timeout_ms: Option<u64>,
     ) -> Result<CaptureResult> {
         let policy = parse_policy(policy_json_or_preset)?;
+        // Setup only grants persistent write access to the workspace capability, so a read-only
+        // token could never write to these roots; refuse instead of silently ignoring them.
+        if let SandboxPolicy::ReadOnly { writable_roots, .. } = &policy {
+            if !writable_roots.is_empty() {
+                anyhow::bail!(
+                    "read-only writable_roots are not supported by the elevated Windows sandbox"
+                )
+            }
+        }
         normalize_null_device_env(&mut env_map);
         ensure_non_interactive_pager(&mut env_map);
         inherit_path_env(&mut env_map);
@@ -240,7 +249,7 @@ mod windows_impl {
         }
         let caps = load_or_create_cap_sids(codex_home)?;
         let (psid_to_use, cap_sids) = match &policy {
-            SandboxPolicy::ReadOnly => (
+            SandboxPolicy::ReadOnly { .. } => (
                 unsafe { convert_string_sid_to_sid(&caps.readonly).unwrap() },
                 vec![caps.readonly.clone()],
             ),
@@ -470,8 +479,10 @@ mod windows_impl {
             SandboxPolicy::WorkspaceWrite {
                 writable_roots: Vec::new(),
                 network_access,
+                allowed_loopback_ports: Vec::new(),
                 exclude_tmpdir_env_var: false,
                 exclude_slash_tmp: false,
+                deny_read: Vec::new(),
             }
         }
 
@@ -487,7 +498,7 @@ mod windows_impl {
 
         #[test]
         fn applies_network_block_for_read_only() {
-            assert!(!SandboxPolicy::ReadOnly.has_full_network_access());
+            assert!(!SandboxPolicy::new_read_only_policy().has_full_network_access());
         }
     }
 }
@@ -237,11 +237,13 @@ pub(crate) fn gather_write_roots(
     command_cwd: &Path,
     env_map: &HashMap<String, String>,
 ) -> Vec<PathBuf> {
-    let mut roots: Vec<PathBuf> = Vec::new();
-    // Always include the command CWD for workspace-write.
-    if matches!(policy, SandboxPolicy::WorkspaceWrite { .. }) {
-        roots.push(command_cwd.to_path_buf());
+    // Setup grants persist and go to the workspace capability SID, so read-only roots (which the
+    // elevated runner rejects) must never end up here.
+    if !matches!(policy, SandboxPolicy::WorkspaceWrite { .. }) {
+        return Vec::new();
     }
+    // Always include the command CWD for workspace-write.
+    let mut roots: Vec<PathBuf> = vec![command_cwd.to_path_buf()];
     let AllowDenyPaths { allow, .. } =
         compute_allow_paths(policy, policy_cwd, command_cwd, env_map);
     roots.extend(allow);